@@ -28,6 +28,7 @@ use mz_audit_log::{
 use mz_build_info::DUMMY_BUILD_INFO;
 use mz_compute_client::controller::ComputeReplicaConfig;
 use mz_compute_client::logging::LogVariant;
+use mz_compute_client::types::sinks::QuarantineMode;
 use mz_controller::clusters::{
     ClusterEvent, ClusterId, ClusterRole, ClusterStatus, ManagedReplicaLocation, ProcessId,
     ReplicaAllocation, ReplicaConfig, ReplicaId, ReplicaLocation, ReplicaLogging,
@@ -672,7 +673,7 @@ impl CatalogState {
             .unwrap_or_else(|| panic!("unknown cluster {cluster_id}"))
     }
 
-    fn try_get_cluster(&self, cluster_id: ClusterId) -> Option<&Cluster> {
+    pub(crate) fn try_get_cluster(&self, cluster_id: ClusterId) -> Option<&Cluster> {
         self.clusters_by_id.get(&cluster_id)
     }
 
@@ -2003,6 +2004,8 @@ pub struct Table {
     /// Whether the table's logical compaction window is controlled by
     /// METRICS_RETENTION
     pub is_retained_metrics_object: bool,
+    /// Whether the table only admits insertions, and is therefore monotonic.
+    pub append_only: bool,
 }
 
 impl Table {
@@ -2318,6 +2321,9 @@ pub struct MaterializedView {
     pub desc: RelationDesc,
     pub resolved_ids: ResolvedIds,
     pub cluster_id: ClusterId,
+    /// How the materialized view handles rows whose evaluation errors, if it does not
+    /// report these errors.
+    pub on_error: Option<QuarantineMode>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -3466,6 +3472,7 @@ impl Catalog {
                                     .is_retained_metrics_object
                                     .then(|| catalog.state.system_config().metrics_retention()),
                                 is_retained_metrics_object: table.is_retained_metrics_object,
+                                append_only: false,
                             }),
                             MZ_SYSTEM_ROLE_ID,
                             PrivilegeMap::from_mz_acl_items(vec![
@@ -7401,6 +7408,7 @@ impl Catalog {
                 resolved_ids,
                 custom_logical_compaction_window,
                 is_retained_metrics_object,
+                append_only: table.append_only,
             }),
             Plan::CreateSource(CreateSourcePlan {
                 source,
@@ -7460,13 +7468,18 @@ impl Catalog {
                 let optimizer =
                     Optimizer::logical_optimizer(&mz_transform::typecheck::empty_context());
                 let optimized_expr = optimizer.optimize(materialized_view.expr)?;
-                let desc = RelationDesc::new(optimized_expr.typ(), materialized_view.column_names);
+                let mut typ = optimized_expr.typ();
+                if let Some(on_error) = &materialized_view.on_error {
+                    on_error.adjust_type(&mut typ);
+                }
+                let desc = RelationDesc::new(typ, materialized_view.column_names);
                 CatalogItem::MaterializedView(MaterializedView {
                     create_sql: materialized_view.create_sql,
                     optimized_expr,
                     desc,
                     resolved_ids,
                     cluster_id: materialized_view.cluster_id,
+                    on_error: materialized_view.on_error,
                 })
             }
             Plan::CreateIndex(CreateIndexPlan { index, .. }) => CatalogItem::Index(Index {
@@ -9157,6 +9170,7 @@ mod tests {
                         resolved_ids: ResolvedIds(BTreeSet::new()),
                         custom_logical_compaction_window: None,
                         is_retained_metrics_object: false,
+                        append_only: false,
                     }),
                     SimplifiedItem::MaterializedView { referenced_names } => {
                         let table_list = referenced_names.iter().join(",");
@@ -9177,6 +9191,7 @@ mod tests {
                                 .with_key(vec![0]),
                             resolved_ids: ResolvedIds(BTreeSet::from_iter(resolved_ids)),
                             cluster_id: ClusterId::User(1),
+                            on_error: None,
                         })
                     }
                     SimplifiedItem::Index { on } => {
@@ -38,7 +38,10 @@ use mz_sql::catalog::{
 };
 use mz_sql::session::user::{INTROSPECTION_USER, SYSTEM_USER};
 use mz_storage_client::controller::IntrospectionType;
-use mz_storage_client::healthcheck::{MZ_SINK_STATUS_HISTORY_DESC, MZ_SOURCE_STATUS_HISTORY_DESC};
+use mz_storage_client::healthcheck::{
+    MZ_MATERIALIZED_VIEW_QUARANTINED_ERROR_HISTORY_DESC, MZ_SINK_STATUS_HISTORY_DESC,
+    MZ_SOURCE_STATUS_HISTORY_DESC,
+};
 use once_cell::sync::Lazy;
 use serde::Serialize;
 
@@ -1329,6 +1332,18 @@ pub const MZ_COMPUTE_DELAYS_HISTOGRAM_RAW: BuiltinLog = BuiltinLog {
     variant: LogVariant::Compute(ComputeLog::FrontierDelay),
 };
 
+pub const MZ_COMPUTE_RECURSION_ITERATIONS_PER_WORKER: BuiltinLog = BuiltinLog {
+    name: "mz_compute_recursion_iterations_per_worker",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Compute(ComputeLog::LetRecIterations),
+};
+
+pub const MZ_COMPUTE_RECURSION_DELTAS_PER_WORKER: BuiltinLog = BuiltinLog {
+    name: "mz_compute_recursion_deltas_per_worker",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Compute(ComputeLog::LetRecDeltas),
+};
+
 pub const MZ_ACTIVE_PEEKS_PER_WORKER: BuiltinLog = BuiltinLog {
     name: "mz_active_peeks_per_worker",
     schema: MZ_INTERNAL_SCHEMA,
@@ -1819,6 +1834,44 @@ pub static MZ_CLUSTER_REPLICA_HEARTBEATS: Lazy<BuiltinTable> = Lazy::new(|| Buil
     is_retained_metrics_object: false,
 });
 
+pub static MZ_COMPUTE_HYDRATION_STATUSES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_compute_hydration_statuses",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("replica_id", ScalarType::String.nullable(false))
+        .with_column("hydrated", ScalarType::Bool.nullable(false))
+        .with_column("records_processed", ScalarType::UInt64.nullable(false))
+        .with_column("snapshot_records", ScalarType::UInt64.nullable(true))
+        .with_column("arrangement_records", ScalarType::UInt64.nullable(true))
+        .with_column("frontier_lag", ScalarType::Interval.nullable(true)),
+    is_retained_metrics_object: false,
+});
+
+pub static MZ_RECOMMENDED_INDEXES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_recommended_indexes",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("key", ScalarType::String.nullable(false))
+        .with_column("reason", ScalarType::String.nullable(false))
+        .with_column("query_count", ScalarType::UInt64.nullable(false))
+        .with_column("last_seen_at", ScalarType::TimestampTz.nullable(false)),
+    is_retained_metrics_object: false,
+});
+
+pub static MZ_SHARED_SUBPLANS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_shared_subplans",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("subplan_id", ScalarType::UInt64.nullable(false))
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("plan", ScalarType::String.nullable(false)),
+    is_retained_metrics_object: false,
+});
+
 pub static MZ_AUDIT_EVENTS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_audit_events",
     schema: MZ_CATALOG_SCHEMA,
@@ -1896,6 +1949,31 @@ WHERE
     mz_sinks.id NOT LIKE 's%'",
 };
 
+pub static MZ_MATERIALIZED_VIEW_QUARANTINED_ERROR_HISTORY: Lazy<BuiltinSource> =
+    Lazy::new(|| BuiltinSource {
+        name: "mz_materialized_view_quarantined_error_history",
+        schema: MZ_INTERNAL_SCHEMA,
+        data_source: Some(IntrospectionType::MaterializedViewQuarantinedErrorHistory),
+        desc: MZ_MATERIALIZED_VIEW_QUARANTINED_ERROR_HISTORY_DESC.clone(),
+        is_retained_metrics_object: false,
+    });
+
+pub const MZ_MATERIALIZED_VIEW_QUARANTINED_ERRORS: BuiltinView = BuiltinView {
+    name: "mz_materialized_view_quarantined_errors",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_internal.mz_materialized_view_quarantined_errors AS
+SELECT
+    materialized_view_id,
+    error,
+    input_key,
+    sum(count)::int8 AS count,
+    max(occurred_at) AS last_occurred_at
+FROM mz_internal.mz_materialized_view_quarantined_error_history
+JOIN mz_materialized_views ON mz_materialized_views.id = materialized_view_id
+GROUP BY materialized_view_id, error, input_key
+HAVING sum(count) <> 0",
+};
+
 pub static MZ_STORAGE_USAGE_BY_SHARD: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_storage_usage_by_shard",
     schema: MZ_INTERNAL_SCHEMA,
@@ -1910,6 +1988,62 @@ pub static MZ_STORAGE_USAGE_BY_SHARD: Lazy<BuiltinTable> = Lazy::new(|| BuiltinT
     is_retained_metrics_object: false,
 });
 
+pub static MZ_STORAGE_USAGE_HISTORY: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_storage_usage_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(true))
+        .with_column("shard_id", ScalarType::String.nullable(false))
+        .with_column("live_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("rollup_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("not_leaked_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("leaked_bytes", ScalarType::UInt64.nullable(false))
+        .with_column(
+            "collection_timestamp",
+            ScalarType::TimestampTz.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+});
+
+pub static MZ_COLUMN_STATISTICS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_column_statistics",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("position", ScalarType::UInt64.nullable(false))
+        .with_column("row_count", ScalarType::UInt64.nullable(false))
+        .with_column("null_fraction", ScalarType::Float64.nullable(false))
+        .with_column("distinct_count", ScalarType::UInt64.nullable(false))
+        .with_column(
+            "most_common_values",
+            ScalarType::Array(Box::new(ScalarType::String)).nullable(false),
+        )
+        .with_column(
+            "most_common_frequencies",
+            ScalarType::Array(Box::new(ScalarType::Float64)).nullable(false),
+        )
+        .with_column(
+            "histogram_bounds",
+            ScalarType::Array(Box::new(ScalarType::String)).nullable(false),
+        )
+        .with_column("analyzed_at", ScalarType::TimestampTz.nullable(false)),
+    is_retained_metrics_object: false,
+});
+
+pub static MZ_PERSIST_COMPACTION_QUEUE: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_persist_compaction_queue",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("replica_id", ScalarType::String.nullable(true))
+        .with_column("shard_id", ScalarType::String.nullable(false))
+        .with_column("state", ScalarType::String.nullable(false))
+        .with_column("runs", ScalarType::UInt64.nullable(false))
+        .with_column("bytes_awaiting_merge", ScalarType::UInt64.nullable(false))
+        .with_column("memory_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("enqueued_at", ScalarType::TimestampTz.nullable(false)),
+    is_retained_metrics_object: false,
+});
+
 pub static MZ_EGRESS_IPS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_egress_ips",
     schema: MZ_CATALOG_SCHEMA,
@@ -2003,6 +2137,21 @@ pub static MZ_SUBSCRIPTIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     is_retained_metrics_object: false,
 });
 
+pub static MZ_RETAINED_SUBSCRIPTIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_retained_subscriptions",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("authenticated_user", ScalarType::String.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("definition", ScalarType::String.nullable(false))
+        .with_column("resume_as_of", ScalarType::MzTimestamp.nullable(false))
+        .with_column("with_snapshot", ScalarType::Bool.nullable(false))
+        .with_column("retention", ScalarType::Interval.nullable(false))
+        .with_column("expires_at", ScalarType::TimestampTz.nullable(true)),
+    is_retained_metrics_object: false,
+});
+
 pub static MZ_SESSIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_sessions",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2342,6 +2491,24 @@ FROM mz_internal.mz_compute_import_frontiers_per_worker
 GROUP BY export_id, import_id",
 };
 
+pub const MZ_COMPUTE_RECURSION_ITERATIONS: BuiltinView = BuiltinView {
+    name: "mz_compute_recursion_iterations",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_internal.mz_compute_recursion_iterations AS SELECT
+    export_id, binding_id, pg_catalog.max(iterations) AS iterations
+FROM mz_internal.mz_compute_recursion_iterations_per_worker
+GROUP BY export_id, binding_id",
+};
+
+pub const MZ_COMPUTE_RECURSION_DELTAS: BuiltinView = BuiltinView {
+    name: "mz_compute_recursion_deltas",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_internal.mz_compute_recursion_deltas AS SELECT
+    export_id, binding_id, iteration, pg_catalog.sum(records) AS records
+FROM mz_internal.mz_compute_recursion_deltas_per_worker
+GROUP BY export_id, binding_id, iteration",
+};
+
 pub const MZ_RECORDS_PER_DATAFLOW_OPERATOR_PER_WORKER: BuiltinView = BuiltinView {
     name: "mz_records_per_dataflow_operator_per_worker",
     schema: MZ_INTERNAL_SCHEMA,
@@ -3219,6 +3386,74 @@ pub const MZ_DATAFLOW_ARRANGEMENT_SIZES: BuiltinView = BuiltinView {
         GROUP BY mo.name, mdod.dataflow_id",
 };
 
+// The estimated size of an index is its estimated number of records times the estimated size of
+// a record: 16 bytes for its time and diff, and for each column a tag byte plus the size of a
+// value of the column's type. Values of variable-length types are assumed to take 16 bytes.
+pub const MZ_RECOMMENDED_INDEX_COSTS: BuiltinView = BuiltinView {
+    name: "mz_recommended_index_costs",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW
+    mz_internal.mz_recommended_index_costs
+    AS
+        SELECT
+            mri.cluster_id,
+            mri.object_id,
+            mri.key,
+            mri.reason,
+            COALESCE(
+                arrangements.records::numeric,
+                statistics.row_count::numeric
+            ) AS estimated_records,
+            COALESCE(
+                arrangements.records::numeric,
+                statistics.row_count::numeric
+            ) * widths.record_width AS estimated_size_bytes
+        FROM
+            mz_internal.mz_recommended_indexes AS mri
+                LEFT JOIN (
+                    SELECT mi.on_id, mi.cluster_id, max(mdas.records) AS records
+                    FROM
+                        mz_indexes AS mi
+                            JOIN mz_internal.mz_compute_exports AS mce ON mce.export_id = mi.id
+                            JOIN mz_internal.mz_dataflow_arrangement_sizes AS mdas ON mdas.id = mce.dataflow_id
+                    GROUP BY mi.on_id, mi.cluster_id
+                ) AS arrangements ON arrangements.on_id = mri.object_id AND arrangements.cluster_id = mri.cluster_id
+                LEFT JOIN (
+                    SELECT object_id, max(row_count) AS row_count
+                    FROM mz_internal.mz_column_statistics
+                    GROUP BY object_id
+                ) AS statistics ON statistics.object_id = mri.object_id
+                LEFT JOIN (
+                    SELECT
+                        id,
+                        16 + sum(
+                            1 + CASE type
+                                WHEN 'boolean' THEN 0
+                                WHEN 'smallint' THEN 2
+                                WHEN 'uint2' THEN 2
+                                WHEN 'integer' THEN 4
+                                WHEN 'uint4' THEN 4
+                                WHEN 'oid' THEN 4
+                                WHEN 'real' THEN 4
+                                WHEN 'date' THEN 4
+                                WHEN 'bigint' THEN 8
+                                WHEN 'uint8' THEN 8
+                                WHEN 'double precision' THEN 8
+                                WHEN 'time' THEN 8
+                                WHEN 'timestamp' THEN 8
+                                WHEN 'timestamp with time zone' THEN 8
+                                WHEN 'mz_timestamp' THEN 8
+                                WHEN 'interval' THEN 16
+                                WHEN 'uuid' THEN 16
+                                WHEN 'numeric' THEN 40
+                                ELSE 16
+                            END
+                        )::numeric AS record_width
+                    FROM mz_columns
+                    GROUP BY id
+                ) AS widths ON widths.id = mri.object_id",
+};
+
 // NOTE: If you add real data to this implementation, then please update
 // the related `pg_` function implementations (like `pg_get_constraintdef`)
 pub const PG_CONSTRAINT: BuiltinView = BuiltinView {
@@ -4549,6 +4784,8 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Log(&MZ_COMPUTE_FRONTIERS_PER_WORKER),
         Builtin::Log(&MZ_COMPUTE_IMPORT_FRONTIERS_PER_WORKER),
         Builtin::Log(&MZ_COMPUTE_DELAYS_HISTOGRAM_RAW),
+        Builtin::Log(&MZ_COMPUTE_RECURSION_ITERATIONS_PER_WORKER),
+        Builtin::Log(&MZ_COMPUTE_RECURSION_DELTAS_PER_WORKER),
         Builtin::Table(&MZ_VIEW_KEYS),
         Builtin::Table(&MZ_VIEW_FOREIGN_KEYS),
         Builtin::Table(&MZ_KAFKA_SINKS),
@@ -4587,11 +4824,18 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_CLUSTER_REPLICA_SIZES),
         Builtin::Table(&MZ_CLUSTER_REPLICA_STATUSES),
         Builtin::Table(&MZ_CLUSTER_REPLICA_HEARTBEATS),
+        Builtin::Table(&MZ_COMPUTE_HYDRATION_STATUSES),
+        Builtin::Table(&MZ_RECOMMENDED_INDEXES),
+        Builtin::Table(&MZ_SHARED_SUBPLANS),
         Builtin::Table(&MZ_AUDIT_EVENTS),
         Builtin::Table(&MZ_STORAGE_USAGE_BY_SHARD),
+        Builtin::Table(&MZ_STORAGE_USAGE_HISTORY),
+        Builtin::Table(&MZ_COLUMN_STATISTICS),
+        Builtin::Table(&MZ_PERSIST_COMPACTION_QUEUE),
         Builtin::Table(&MZ_EGRESS_IPS),
         Builtin::Table(&MZ_AWS_PRIVATELINK_CONNECTIONS),
         Builtin::Table(&MZ_SUBSCRIPTIONS),
+        Builtin::Table(&MZ_RETAINED_SUBSCRIPTIONS),
         Builtin::Table(&MZ_SESSIONS),
         Builtin::Table(&MZ_SESSION_HISTORY),
         Builtin::Table(&MZ_DEFAULT_PRIVILEGES),
@@ -4620,10 +4864,13 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_COMPUTE_EXPORTS),
         Builtin::View(&MZ_COMPUTE_DEPENDENCIES),
         Builtin::View(&MZ_DATAFLOW_ARRANGEMENT_SIZES),
+        Builtin::View(&MZ_RECOMMENDED_INDEX_COSTS),
         Builtin::View(&MZ_COMPUTE_FRONTIERS),
         Builtin::View(&MZ_DATAFLOW_CHANNEL_OPERATORS_PER_WORKER),
         Builtin::View(&MZ_DATAFLOW_CHANNEL_OPERATORS),
         Builtin::View(&MZ_COMPUTE_IMPORT_FRONTIERS),
+        Builtin::View(&MZ_COMPUTE_RECURSION_ITERATIONS),
+        Builtin::View(&MZ_COMPUTE_RECURSION_DELTAS),
         Builtin::View(&MZ_MESSAGE_COUNTS_PER_WORKER),
         Builtin::View(&MZ_MESSAGE_COUNTS),
         Builtin::View(&MZ_ACTIVE_PEEKS),
@@ -4708,6 +4955,8 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_SHOW_MY_DEFAULT_PRIVILEGES),
         Builtin::Source(&MZ_SINK_STATUS_HISTORY),
         Builtin::View(&MZ_SINK_STATUSES),
+        Builtin::Source(&MZ_MATERIALIZED_VIEW_QUARANTINED_ERROR_HISTORY),
+        Builtin::View(&MZ_MATERIALIZED_VIEW_QUARANTINED_ERRORS),
         Builtin::Source(&MZ_SOURCE_STATUS_HISTORY),
         Builtin::View(&MZ_SOURCE_STATUSES),
         Builtin::Source(&MZ_STORAGE_SHARDS),
@@ -9,10 +9,12 @@
 
 use std::net::Ipv4Addr;
 
+use bytes::BytesMut;
 use bytesize::ByteSize;
 use chrono::{DateTime, Utc};
 use mz_audit_log::{EventDetails, EventType, ObjectType, VersionedEvent, VersionedStorageUsage};
 use mz_compute_client::controller::NewReplicaId;
+use mz_compute_client::protocol::response::HydrationStatus;
 use mz_controller::clusters::{
     ClusterId, ClusterStatus, ManagedReplicaLocation, ProcessId, ReplicaAllocation, ReplicaId,
     ReplicaLocation,
@@ -21,8 +23,12 @@ use mz_expr::MirScalarExpr;
 use mz_orchestrator::{CpuLimit, DiskLimit, MemoryLimit, NotReadyReason, ServiceProcessMetrics};
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
-use mz_ore::now::to_datetime;
+use mz_ore::now::{to_datetime, EpochMillis};
+use mz_persist_client::compaction::CompactionQueueEntry;
+use mz_persist_client::usage::ShardUsageAudit;
+use mz_persist_client::ShardId;
 use mz_repr::adt::array::ArrayDimension;
+use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem, PrivilegeMap};
 use mz_repr::role_id::RoleId;
@@ -30,7 +36,7 @@ use mz_repr::statement_logging::{
     SessionHistoryEvent, StatementBeganExecutionRecord, StatementEndedExecutionReason,
     StatementEndedExecutionRecord, StatementPreparedRecord,
 };
-use mz_repr::{Datum, Diff, GlobalId, Row, RowPacker};
+use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, RowArena, RowPacker};
 use mz_sql::ast::{CreateIndexStatement, Statement};
 use mz_sql::catalog::{CatalogCluster, CatalogDatabase, CatalogSchema, CatalogType, TypeCategory};
 use mz_sql::func::FuncImplCatalogDetails;
@@ -41,6 +47,7 @@ use mz_storage_client::types::sinks::{KafkaSinkConnection, StorageSinkConnection
 use mz_storage_client::types::sources::{
     GenericSourceConnection, KafkaSourceConnection, PostgresSourceConnection,
 };
+use mz_transform::statistics::{CollectionStatistics, ColumnStatistics};
 
 use crate::catalog::builtin::{
     MZ_AGGREGATES, MZ_ARRAY_TYPES, MZ_AUDIT_EVENTS, MZ_AWS_PRIVATELINK_CONNECTIONS, MZ_BASE_TYPES,
@@ -54,15 +61,21 @@ use crate::catalog::builtin::{
     MZ_SYSTEM_PRIVILEGES, MZ_TABLES, MZ_TYPES, MZ_VIEWS,
 };
 use crate::catalog::builtin::{
-    MZ_PREPARED_STATEMENT_HISTORY, MZ_SESSION_HISTORY, MZ_STATEMENT_EXECUTION_HISTORY,
+    MZ_COLUMN_STATISTICS, MZ_COMPUTE_HYDRATION_STATUSES, MZ_PERSIST_COMPACTION_QUEUE,
+    MZ_PREPARED_STATEMENT_HISTORY, MZ_RECOMMENDED_INDEXES, MZ_RETAINED_SUBSCRIPTIONS,
+    MZ_SESSION_HISTORY, MZ_SHARED_SUBPLANS, MZ_STATEMENT_EXECUTION_HISTORY,
+    MZ_STORAGE_USAGE_HISTORY,
 };
 use crate::catalog::{
     AwsPrincipalContext, CatalogItem, CatalogState, ClusterVariant, Connection, DataSourceDesc,
     Database, DefaultPrivilegeObject, Error, ErrorKind, Func, Index, MaterializedView, Sink,
     StorageSinkConnectionState, Type, View, SYSTEM_CONN_ID,
 };
+use crate::coord::index_advice::IndexAdviceEntry;
+use crate::coord::read_policy::ReadHolds;
+use crate::coord::shared_subplans::SharedSubplanEntry;
 use crate::session::Session;
-use crate::subscribe::ActiveSubscribe;
+use crate::subscribe::{ActiveSubscribe, RetainedSubscribe};
 
 /// An update to a built-in table.
 #[derive(Debug)]
@@ -1126,6 +1139,35 @@ impl CatalogState {
         }
     }
 
+    pub fn pack_hydration_status_update(
+        &self,
+        object_id: GlobalId,
+        replica_id: ReplicaId,
+        status: &HydrationStatus,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        // TODO(#18377): Make replica IDs `NewReplicaId`s throughout the code.
+        let replica_id = NewReplicaId::User(replica_id);
+        let frontier_lag = status.frontier_lag_ms.map(|ms| {
+            let micros = i64::try_from(ms.saturating_mul(1000)).unwrap_or(i64::MAX);
+            Interval::new(0, 0, micros)
+        });
+
+        BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_COMPUTE_HYDRATION_STATUSES),
+            row: Row::pack_slice(&[
+                Datum::String(&object_id.to_string()),
+                Datum::String(&replica_id.to_string()),
+                Datum::from(status.hydrated),
+                Datum::UInt64(status.records_processed),
+                Datum::from(status.snapshot_records),
+                Datum::from(status.arrangement_records),
+                Datum::from(frontier_lag),
+            ]),
+            diff,
+        }
+    }
+
     pub fn pack_storage_usage_update(
         &self,
         VersionedStorageUsage::V1(event): &VersionedStorageUsage,
@@ -1185,6 +1227,245 @@ impl CatalogState {
         updates
     }
 
+    /// Packs a row of `mz_storage_usage_history` for the audited usage of a
+    /// shard. Shards that don't belong to any live catalog object have a null
+    /// `object_id`.
+    pub fn pack_storage_usage_history_row(
+        object_id: Option<GlobalId>,
+        shard_id: &ShardId,
+        usage: &ShardUsageAudit,
+        collection_timestamp: EpochMillis,
+    ) -> Row {
+        let object_id = object_id.map(|id| id.to_string());
+        Row::pack_slice(&[
+            Datum::from(object_id.as_deref()),
+            Datum::String(&shard_id.to_string()),
+            Datum::UInt64(usage.current_state_batches_bytes),
+            Datum::UInt64(usage.current_state_rollups_bytes),
+            Datum::UInt64(usage.not_leaked_bytes()),
+            Datum::UInt64(usage.leaked_bytes),
+            Datum::TimestampTz(
+                mz_ore::now::to_datetime(collection_timestamp)
+                    .try_into()
+                    .expect("must fit"),
+            ),
+        ])
+    }
+
+    pub fn pack_storage_usage_history_updates(
+        &self,
+        rows: impl IntoIterator<Item = Row>,
+        diff: Diff,
+    ) -> Vec<BuiltinTableUpdate> {
+        let id = self.resolve_builtin_table(&MZ_STORAGE_USAGE_HISTORY);
+        rows.into_iter()
+            .map(|row| BuiltinTableUpdate { id, row, diff })
+            .collect()
+    }
+
+    /// Packs a row of `mz_column_statistics` for each column of the collection
+    /// `id`, described by `desc`, as analyzed at `analyzed_at`.
+    pub fn pack_column_statistics_rows(
+        id: GlobalId,
+        desc: &RelationDesc,
+        statistics: &CollectionStatistics,
+        analyzed_at: EpochMillis,
+    ) -> Vec<Row> {
+        let analyzed_at = mz_ore::now::to_datetime(analyzed_at)
+            .try_into()
+            .expect("must fit");
+        let id = id.to_string();
+        let mut rows = Vec::with_capacity(statistics.columns.len());
+        for (position, (column, typ)) in
+            statistics.columns.iter().zip(desc.iter_types()).enumerate()
+        {
+            let to_text = |row: &Row| {
+                let mut buf = BytesMut::new();
+                if let Some(value) =
+                    mz_pgrepr::Value::from_datum(row.unpack_first(), &typ.scalar_type)
+                {
+                    value.encode_text(&mut buf);
+                }
+                String::from_utf8_lossy(&buf).into_owned()
+            };
+            let most_common_values: Vec<_> = column
+                .most_common_values
+                .iter()
+                .map(|(value, _)| to_text(value))
+                .collect();
+            let histogram_bounds: Vec<_> = column.histogram_bounds.iter().map(to_text).collect();
+
+            let mut row = Row::default();
+            let mut packer = row.packer();
+            packer.push(Datum::String(&id));
+            packer.push(Datum::UInt64(u64::cast_from(position + 1)));
+            packer.push(Datum::UInt64(statistics.row_count));
+            packer.push(Datum::Float64(column.null_fraction.into()));
+            packer.push(Datum::UInt64(column.distinct_count));
+            packer
+                .push_array(
+                    &[ArrayDimension {
+                        lower_bound: 1,
+                        length: most_common_values.len(),
+                    }],
+                    most_common_values.iter().map(|value| Datum::String(value)),
+                )
+                .expect("most_common_values is 1 dimensional");
+            packer
+                .push_array(
+                    &[ArrayDimension {
+                        lower_bound: 1,
+                        length: column.most_common_values.len(),
+                    }],
+                    column
+                        .most_common_values
+                        .iter()
+                        .map(|(_, frequency)| Datum::Float64((*frequency).into())),
+                )
+                .expect("most_common_frequencies is 1 dimensional");
+            packer
+                .push_array(
+                    &[ArrayDimension {
+                        lower_bound: 1,
+                        length: histogram_bounds.len(),
+                    }],
+                    histogram_bounds.iter().map(|bound| Datum::String(bound)),
+                )
+                .expect("histogram_bounds is 1 dimensional");
+            packer.push(Datum::TimestampTz(analyzed_at));
+            rows.push(row);
+        }
+        rows
+    }
+
+    /// Restores the statistics of the collection described by `desc` from the
+    /// rows that [`CatalogState::pack_column_statistics_rows`] packed for it.
+    ///
+    /// Returns `None` if the rows don't describe each of the collection's
+    /// columns, or if their values cannot be decoded.
+    pub fn unpack_column_statistics_rows(
+        desc: &RelationDesc,
+        rows: &[Row],
+    ) -> Option<CollectionStatistics> {
+        let mut row_count = 0;
+        let mut columns = vec![None; desc.arity()];
+        for row in rows {
+            let datums = row.unpack();
+            let position = usize::cast_from(datums[1].unwrap_uint64()).checked_sub(1)?;
+            let typ = mz_pgrepr::Type::from(&desc.typ().column_types.get(position)?.scalar_type);
+            let arena = RowArena::new();
+            let from_text = |datum: Datum| {
+                let value = mz_pgrepr::Value::decode_text(&typ, datum.unwrap_str().as_bytes());
+                Some(Row::pack_slice(&[value.ok()?.into_datum(&arena, &typ)]))
+            };
+            let most_common_values = datums[5]
+                .unwrap_array()
+                .elements()
+                .iter()
+                .zip(datums[6].unwrap_array().elements().iter())
+                .map(|(value, frequency)| Some((from_text(value)?, frequency.unwrap_float64())))
+                .collect::<Option<_>>()?;
+            let histogram_bounds = datums[7]
+                .unwrap_array()
+                .elements()
+                .iter()
+                .map(from_text)
+                .collect::<Option<_>>()?;
+
+            row_count = datums[2].unwrap_uint64();
+            *columns.get_mut(position)? = Some(ColumnStatistics {
+                null_fraction: datums[3].unwrap_float64(),
+                distinct_count: datums[4].unwrap_uint64(),
+                most_common_values,
+                histogram_bounds,
+            });
+        }
+        let columns = columns.into_iter().collect::<Option<_>>()?;
+        Some(CollectionStatistics { row_count, columns })
+    }
+
+    pub fn pack_column_statistics_updates(
+        &self,
+        rows: impl IntoIterator<Item = Row>,
+        diff: Diff,
+    ) -> Vec<BuiltinTableUpdate> {
+        let id = self.resolve_builtin_table(&MZ_COLUMN_STATISTICS);
+        rows.into_iter()
+            .map(|row| BuiltinTableUpdate { id, row, diff })
+            .collect()
+    }
+
+    /// Packs the compaction queue of the given replica, or of `environmentd` if `replica_id` is
+    /// `None`.
+    pub fn pack_compaction_queue_updates(
+        &self,
+        replica_id: Option<ReplicaId>,
+        entries: &[CompactionQueueEntry],
+        diff: Diff,
+    ) -> Vec<BuiltinTableUpdate> {
+        let id = self.resolve_builtin_table(&MZ_PERSIST_COMPACTION_QUEUE);
+        // TODO(#18377): Make replica IDs `NewReplicaId`s throughout the code.
+        let replica_id = replica_id.map(|id| NewReplicaId::User(id).to_string());
+        entries
+            .iter()
+            .map(|entry| {
+                let enqueued_at = to_datetime(entry.enqueued_at).try_into().expect("must fit");
+                let row = Row::pack_slice(&[
+                    Datum::from(replica_id.as_deref()),
+                    Datum::String(&entry.shard_id.to_string()),
+                    Datum::String(entry.state.as_str()),
+                    u64::cast_from(entry.runs).into(),
+                    u64::cast_from(entry.bytes_awaiting_merge).into(),
+                    u64::cast_from(entry.memory_bytes).into(),
+                    Datum::TimestampTz(enqueued_at),
+                ]);
+                BuiltinTableUpdate { id, row, diff }
+            })
+            .collect()
+    }
+
+    pub fn pack_index_advice_updates(
+        &self,
+        entries: &[IndexAdviceEntry],
+        diff: Diff,
+    ) -> Vec<BuiltinTableUpdate> {
+        let id = self.resolve_builtin_table(&MZ_RECOMMENDED_INDEXES);
+        entries
+            .iter()
+            .map(|entry| {
+                let row = Row::pack_slice(&[
+                    Datum::String(&entry.cluster_id.to_string()),
+                    Datum::String(&entry.advice.on_id.to_string()),
+                    Datum::String(&entry.key_text),
+                    Datum::String(entry.advice.reason.as_str()),
+                    Datum::UInt64(entry.query_count),
+                    Datum::TimestampTz(to_datetime(entry.last_seen).try_into().expect("must fit")),
+                ]);
+                BuiltinTableUpdate { id, row, diff }
+            })
+            .collect()
+    }
+
+    pub fn pack_shared_subplan_updates(
+        &self,
+        entries: &[SharedSubplanEntry],
+        diff: Diff,
+    ) -> Vec<BuiltinTableUpdate> {
+        let id = self.resolve_builtin_table(&MZ_SHARED_SUBPLANS);
+        entries
+            .iter()
+            .map(|entry| {
+                let row = Row::pack_slice(&[
+                    Datum::String(&entry.cluster_id.to_string()),
+                    Datum::UInt64(entry.subplan_id),
+                    Datum::String(&entry.object_id.to_string()),
+                    Datum::String(&entry.plan),
+                ]);
+                BuiltinTableUpdate { id, row, diff }
+            })
+            .collect()
+    }
+
     pub fn pack_all_replica_size_updates(&self) -> Vec<BuiltinTableUpdate> {
         let id = self.resolve_builtin_table(&MZ_CLUSTER_REPLICA_SIZES);
         let updates = self
@@ -1256,6 +1537,61 @@ impl CatalogState {
         }
     }
 
+    pub fn pack_retained_subscribe_update(
+        &self,
+        name: &str,
+        retained: &RetainedSubscribe,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let definition = serde_json::to_string(&retained.from).expect("valid json");
+        let retention = i64::try_from(retained.retention.as_micros()).unwrap_or(i64::MAX);
+        let expires_at = retained
+            .expires_at
+            .map(|expires_at| to_datetime(expires_at).try_into().expect("must fit"));
+        BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_RETAINED_SUBSCRIPTIONS),
+            row: Row::pack_slice(&[
+                Datum::String(name),
+                Datum::String(&retained.user),
+                Datum::String(&retained.cluster_id.to_string()),
+                Datum::String(&definition),
+                Datum::MzTimestamp(retained.resume_as_of),
+                Datum::from(retained.with_snapshot),
+                Datum::Interval(Interval::new(0, 0, retention)),
+                Datum::from(expires_at),
+            ]),
+            diff,
+        }
+    }
+
+    /// Restores a named subscribe from a row that
+    /// [`CatalogState::pack_retained_subscribe_update`] packed for it.
+    ///
+    /// The restored subscribe is not running and holds no read holds.
+    /// Returns `None` if the row cannot be decoded.
+    pub(crate) fn unpack_retained_subscribe_row(row: &Row) -> Option<(String, RetainedSubscribe)> {
+        let datums = row.unpack();
+        let name = datums[0].unwrap_str().to_string();
+        let retained = RetainedSubscribe {
+            user: datums[1].unwrap_str().to_string(),
+            cluster_id: datums[2].unwrap_str().parse().ok()?,
+            from: serde_json::from_str(datums[3].unwrap_str()).ok()?,
+            read_holds: ReadHolds::new(),
+            resume_as_of: datums[4].unwrap_mz_timestamp(),
+            with_snapshot: datums[5].unwrap_bool(),
+            retention: datums[6].unwrap_interval().duration().ok()?,
+            sink: None,
+            acknowledgements: None,
+            expires_at: match datums[7] {
+                Datum::Null => None,
+                expires_at => {
+                    Some(u64::try_from(expires_at.unwrap_timestamptz().timestamp_millis()).ok()?)
+                }
+            },
+        };
+        Some((name, retained))
+    }
+
     pub fn pack_session_update(&self, session: &Session, diff: Diff) -> BuiltinTableUpdate {
         let connect_dt = mz_ore::now::to_datetime(session.connect_time());
         BuiltinTableUpdate {
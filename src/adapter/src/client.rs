@@ -309,7 +309,8 @@ impl SessionClient {
         let Some(statement_kind) = parse_error.statement else {
             return;
         };
-        let Some((action, object_type)) = telemetry::analyze_audited_statement(statement_kind) else {
+        let Some((action, object_type)) = telemetry::analyze_audited_statement(statement_kind)
+        else {
             return;
         };
         let event_type = StatementFailureType::ParseFailure;
@@ -760,7 +761,7 @@ impl Timeout {
     }
 }
 
-/// A wrapper around an UnboundedReceiver of PeekResponseUnary that records when it sees the
+/// A wrapper around a Receiver of PeekResponseUnary that records when it sees the
 /// first row data in the given histogram
 #[derive(Derivative)]
 #[derivative(Debug)]
@@ -804,7 +805,7 @@ impl RecordFirstRowStream {
     }
 
     /// If you want to match [`RecordFirstRowStream`]'s logic but don't need
-    /// a UnboundedReceiver, you can tell it when to record an observation.
+    /// a Receiver, you can tell it when to record an observation.
     pub fn record(execute_started: Instant, client: &SessionClient) {
         Self::histogram(client).observe(execute_started.elapsed().as_secs_f64());
     }
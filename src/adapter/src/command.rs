@@ -41,6 +41,7 @@ use crate::coord::peek::PeekResponseUnary;
 use crate::coord::ExecuteContextExtra;
 use crate::error::AdapterError;
 use crate::session::{EndTransactionAction, RowBatchStream, Session};
+use crate::subscribe::SubscribeAcknowledger;
 use crate::util::Transmittable;
 
 #[derive(Debug)]
@@ -498,6 +499,8 @@ pub enum ExecuteResponse {
     AlteredRole,
     /// The system configuration was altered.
     AlteredSystemConfiguration,
+    /// Statistics about the requested relation were collected.
+    Analyzed,
     /// The query was canceled.
     Canceled,
     /// The requested cursor was closed.
@@ -602,7 +605,12 @@ pub enum ExecuteResponse {
     StartedTransaction,
     /// Updates to the requested source or view will be streamed to the
     /// contained receiver.
-    Subscribing { rx: RowBatchStream },
+    Subscribing {
+        rx: RowBatchStream,
+        /// Acknowledges the responses the client has consumed, if the
+        /// subscribe is named.
+        acknowledger: Option<SubscribeAcknowledger>,
+    },
     /// The active transaction committed.
     TransactionCommitted {
         /// Session parameters that changed because the transaction ended.
@@ -677,6 +685,7 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::AlteredSystemConfiguration => {
                 Ok(ExecuteResponse::AlteredSystemConfiguration)
             }
+            ExecuteResponseKind::Analyzed => Ok(ExecuteResponse::Analyzed),
             ExecuteResponseKind::Canceled => Ok(ExecuteResponse::Canceled),
             ExecuteResponseKind::ClosedCursor => Ok(ExecuteResponse::ClosedCursor),
             ExecuteResponseKind::CopyTo => Err(()),
@@ -739,6 +748,7 @@ impl ExecuteResponse {
             AlteredIndexLogicalCompaction => Some("ALTER INDEX".into()),
             AlteredRole => Some("ALTER ROLE".into()),
             AlteredSystemConfiguration => Some("ALTER SYSTEM".into()),
+            Analyzed => Some("ANALYZE".into()),
             Canceled => None,
             ClosedCursor => Some("CLOSE CURSOR".into()),
             CopyTo { .. } => None,
@@ -820,6 +830,7 @@ impl ExecuteResponse {
                 vec![AlteredObject]
             }
             AlterDefaultPrivileges => vec![AlteredDefaultPrivileges],
+            Analyze => vec![Analyzed],
             AlterSetCluster => vec![AlteredObject],
             AlterIndexSetOptions | AlterIndexResetOptions => {
                 vec![AlteredObject, AlteredIndexLogicalCompaction]
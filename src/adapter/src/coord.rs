@@ -82,6 +82,7 @@ use itertools::Itertools;
 use mz_build_info::BuildInfo;
 use mz_cloud_resources::{CloudResourceController, VpcEndpointConfig};
 use mz_compute_client::controller::ComputeInstanceId;
+use mz_compute_client::protocol::response::HydrationStatus;
 use mz_compute_client::types::dataflows::DataflowDescription;
 use mz_controller::clusters::{
     ClusterConfig, ClusterEvent, ClusterId, CreateReplicaConfig, ReplicaId,
@@ -90,16 +91,19 @@ use mz_expr::{MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, RowSetFi
 use mz_orchestrator::ServiceProcessMetrics;
 use mz_ore::cast::CastFrom;
 use mz_ore::metrics::MetricsRegistry;
-use mz_ore::now::NowFn;
+use mz_ore::now::{EpochMillis, NowFn};
 use mz_ore::retry::Retry;
 use mz_ore::task::spawn;
 use mz_ore::thread::JoinHandleExt;
 use mz_ore::tracing::{OpenTelemetryContext, TracingHandle};
 use mz_ore::{stack, task};
-use mz_persist_client::usage::{ShardsUsageReferenced, StorageUsageClient};
+use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::compaction::CompactionQueueEntry;
+use mz_persist_client::usage::{ShardsUsageAudit, ShardsUsageReferenced, StorageUsageClient};
+use mz_persist_client::ShardId;
 use mz_repr::explain::ExplainFormat;
 use mz_repr::role_id::RoleId;
-use mz_repr::{Datum, GlobalId, RelationType, Row, Timestamp};
+use mz_repr::{Datum, Diff, GlobalId, RelationType, Row, Timestamp};
 use mz_secrets::cache::CachingSecretsReader;
 use mz_secrets::SecretsController;
 use mz_sql::ast::{CreateSubsourceStatement, Raw, Statement};
@@ -113,6 +117,7 @@ use mz_storage_client::controller::{
 use mz_storage_client::types::connections::ConnectionContext;
 use mz_storage_client::types::sinks::StorageSinkConnection;
 use mz_storage_client::types::sources::Timeline;
+use mz_transform::statistics::CollectionStatistics;
 use mz_transform::Optimizer;
 use timely::progress::Antichain;
 use tokio::runtime::Handle as TokioHandle;
@@ -121,10 +126,14 @@ use tokio::sync::{mpsc, oneshot, watch, OwnedMutexGuard};
 use tracing::{info, info_span, span, warn, Instrument, Level, Span};
 use uuid::Uuid;
 
-use crate::catalog::builtin::{BUILTINS, MZ_VIEW_FOREIGN_KEYS, MZ_VIEW_KEYS};
+use crate::catalog::builtin::{
+    BUILTINS, MZ_COLUMN_STATISTICS, MZ_RETAINED_SUBSCRIPTIONS, MZ_STORAGE_USAGE_HISTORY,
+    MZ_VIEW_FOREIGN_KEYS, MZ_VIEW_KEYS,
+};
 use crate::catalog::{
     self, storage, AwsPrincipalContext, BuiltinMigrationMetadata, BuiltinTableUpdate, Catalog,
-    CatalogItem, ClusterReplicaSizeMap, DataSourceDesc, Source, StorageSinkConnectionState,
+    CatalogItem, CatalogState, ClusterReplicaSizeMap, DataSourceDesc, Source,
+    StorageSinkConnectionState,
 };
 use crate::client::{Client, ConnectionId, Handle};
 use crate::command::{Canceled, Command, ExecuteResponse};
@@ -132,20 +141,27 @@ use crate::config::SystemParameterSyncConfig;
 use crate::coord::appends::{Deferred, PendingWriteTxn};
 use crate::coord::dataflows::dataflow_import_id_bundle;
 use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::index_advice::IndexAdvisor;
 use crate::coord::peek::PendingPeek;
+use crate::coord::published_snapshot::PublishedSnapshot;
 use crate::coord::read_policy::ReadCapability;
+use crate::coord::shared_subplans::SharedSubplanTracker;
 use crate::coord::timeline::{TimelineContext, TimelineState, WriteTimestamp};
 use crate::coord::timestamp_selection::TimestampContext;
 use crate::error::AdapterError;
 use crate::metrics::Metrics;
 use crate::session::{EndTransactionAction, Session};
-use crate::subscribe::ActiveSubscribe;
+use crate::subscribe::{ActiveSubscribe, RetainedSubscribe};
 use crate::util::{ClientTransmitter, CompletedClientTransmitter, ComputeSinkId, ResultExt};
 use crate::{flags, AdapterNotice, TimestampProvider};
 
 pub(crate) mod dataflows;
 pub(crate) mod id_bundle;
+pub(crate) mod index_advice;
 pub(crate) mod peek;
+pub(crate) mod published_snapshot;
+pub(crate) mod read_policy;
+pub(crate) mod shared_subplans;
 pub(crate) mod statement_logging;
 pub(crate) mod timeline;
 pub(crate) mod timestamp_selection;
@@ -156,7 +172,6 @@ mod ddl;
 mod indexes;
 mod introspection;
 mod message_handler;
-mod read_policy;
 mod sequencer;
 mod sql;
 
@@ -190,6 +205,7 @@ pub enum Message<T = mz_repr::Timestamp> {
     ControllerReady,
     PurifiedStatementReady(PurifiedStatementReady),
     CreateConnectionValidationReady(CreateConnectionValidationReady),
+    AnalyzeReady(AnalyzeReady),
     SinkConnectionReady(SinkConnectionReady),
     WriteLockGrant(tokio::sync::OwnedMutexGuard<()>),
     /// Initiates a group commit.
@@ -211,6 +227,14 @@ pub enum Message<T = mz_repr::Timestamp> {
     LinearizeReads(Vec<PendingReadTxn>),
     StorageUsageFetch,
     StorageUsageUpdate(ShardsUsageReferenced),
+    StorageUsageAuditUpdate {
+        shard_objects: BTreeMap<ShardId, GlobalId>,
+        audit: ShardsUsageAudit,
+        collection_timestamp: EpochMillis,
+    },
+    CompactionQueueFetch,
+    IndexAdvicePublish,
+    SharedSubplansPublish,
     RealTimeRecencyTimestamp {
         conn_id: ConnectionId,
         real_time_recency_ts: Timestamp,
@@ -236,6 +260,24 @@ pub enum Message<T = mz_repr::Timestamp> {
         ctx: ExecuteContext,
         stage: PeekStage,
     },
+    /// Checks whether an `ALTER CLUSTER` that waits for replicas to hydrate can
+    /// be applied.
+    ClusterReconfigurationCheck {
+        ctx: ExecuteContext,
+        reconfiguration: PendingClusterReconfiguration,
+    },
+    /// Reports how many responses of a flow-controlled subscribe its client
+    /// has received.
+    SubscribeResponsesDelivered {
+        sink_id: GlobalId,
+        delivered: u64,
+    },
+    /// Reports how many responses of a named subscribe its client has
+    /// acknowledged.
+    SubscribeResponsesAcknowledged {
+        sink_id: GlobalId,
+        acknowledged: u64,
+    },
 }
 
 #[derive(Derivative)]
@@ -266,6 +308,19 @@ pub struct CreateConnectionValidationReady {
     pub otel_ctx: OpenTelemetryContext,
 }
 
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct AnalyzeReady {
+    #[derivative(Debug = "ignore")]
+    pub ctx: ExecuteContext,
+    pub id: GlobalId,
+    pub result: Result<CollectionStatistics, AdapterError>,
+    /// The read hold that kept the analyzed snapshot readable, to be released.
+    pub read_holds: crate::coord::read_policy::ReadHolds<mz_repr::Timestamp>,
+    pub plan_validity: PlanValidity,
+    pub otel_ctx: OpenTelemetryContext,
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct SinkConnectionReady {
@@ -468,6 +523,7 @@ pub struct Config {
     pub connection_context: ConnectionContext,
     pub storage_usage_client: StorageUsageClient,
     pub storage_usage_collection_interval: Duration,
+    pub persist_clients: Arc<PersistClientCache>,
     pub storage_usage_retention_period: Option<Duration>,
     pub segment_client: Option<mz_segment::Client>,
     pub egress_ips: Vec<Ipv4Addr>,
@@ -562,6 +618,22 @@ impl From<PendingTxnResponse> for ExecuteResponse {
     }
 }
 
+/// A reconfiguration of a managed cluster that is applied once the replicas it keeps have
+/// hydrated all indexes and materialized views on the cluster.
+#[derive(Debug)]
+pub struct PendingClusterReconfiguration {
+    cluster_id: ClusterId,
+    /// The replicas that must hydrate before the reconfiguration is applied.
+    replicas: Vec<ReplicaId>,
+    /// The catalog operations that apply the reconfiguration, e.g. dropping the replaced
+    /// replicas.
+    ops: Vec<catalog::Op>,
+    /// The replicas created for the reconfiguration, which are dropped if it is abandoned.
+    created_replicas: Vec<ReplicaId>,
+    /// When to abandon the reconfiguration.
+    deadline: Instant,
+}
+
 #[derive(Debug)]
 /// A pending read transaction waiting to be linearized along with metadata about it's state
 pub struct PendingReadTxn {
@@ -818,6 +890,11 @@ pub struct Coordinator {
 
     /// A map from active subscribes to the subscribe description.
     active_subscribes: BTreeMap<GlobalId, ActiveSubscribe>,
+    /// A map from the names of resumable subscribes to their state.
+    ///
+    /// This state is not persisted, so named subscribes cannot be resumed
+    /// after environmentd restarts.
+    retained_subscribes: BTreeMap<String, RetainedSubscribe>,
 
     /// Serializes accesses to write critical sections.
     write_lock: Arc<tokio::sync::Mutex<()>>,
@@ -860,6 +937,29 @@ pub struct Coordinator {
     storage_usage_client: StorageUsageClient,
     /// The interval at which to collect storage usage information.
     storage_usage_collection_interval: Duration,
+    /// How long to retain storage usage information, if not forever.
+    storage_usage_retention_period: Option<Duration>,
+    /// The statistics last collected by `ANALYZE` for each collection, along
+    /// with the rows written to `mz_column_statistics` for them, so they can be
+    /// retracted when the collection is analyzed again or dropped. The rows are
+    /// the durable record of the statistics, from which they are restored when
+    /// `environmentd` restarts.
+    collection_statistics: BTreeMap<GlobalId, (Arc<CollectionStatistics>, Vec<Row>)>,
+
+    /// The persist clients used by this process, for introspection into e.g.
+    /// compaction.
+    persist_clients: Arc<PersistClientCache>,
+    /// The compaction queues last written to `mz_persist_compaction_queue`, by
+    /// replica, or `None` for this process, so that they can be retracted when
+    /// they change.
+    compaction_queues: BTreeMap<Option<ReplicaId>, PublishedSnapshot<CompactionQueueEntry>>,
+    /// The hydration statuses last written to `mz_compute_hydration_statuses`, by collection
+    /// and replica, so that they can be retracted when they change.
+    hydration_statuses: BTreeMap<(GlobalId, ReplicaId), HydrationStatus>,
+    /// Index recommendations derived from the plans of recent peeks.
+    index_advisor: IndexAdvisor,
+    /// Subplans that are shared across the dataflows installed on each cluster.
+    shared_subplans: SharedSubplanTracker,
 
     /// Segment analytics client.
     #[derivative(Debug = "ignore")]
@@ -1137,6 +1237,11 @@ impl Coordinator {
             .await
             .unwrap_or_terminate("cannot fail to create collections");
 
+        // Named subscribes must be restored before installing indexes, which they might need to
+        // read from at their resume points.
+        info!("coordinator init: restoring retained subscribes");
+        self.load_retained_subscribes().await;
+
         info!("coordinator init: installing existing objects in catalog");
         let mut privatelink_connections = BTreeMap::new();
         for entry in &entries {
@@ -1336,6 +1441,10 @@ impl Coordinator {
             }
         }
 
+        // Named subscribes must hold back compaction of their inputs before read policies are
+        // relaxed.
+        self.bootstrap_retained_subscribes();
+
         // Having installed all entries, creating all constraints, we can now relax read policies.
         //
         // TODO -- Improve `initialize_read_policies` API so we can avoid calling this in a loop.
@@ -1431,6 +1540,13 @@ impl Coordinator {
         // Add builtin table updates the clear the contents of all system tables
         info!("coordinator init: resetting system tables");
         let read_ts = self.get_local_read_ts();
+        let column_statistics_id = self.catalog().resolve_builtin_table(&MZ_COLUMN_STATISTICS);
+        let retained_subscriptions_id = self
+            .catalog()
+            .resolve_builtin_table(&MZ_RETAINED_SUBSCRIPTIONS);
+        let storage_usage_history_id = self
+            .catalog()
+            .resolve_builtin_table(&MZ_STORAGE_USAGE_HISTORY);
         for system_table in entries
             .iter()
             .filter(|entry| entry.is_table() && entry.id().is_system())
@@ -1447,6 +1563,16 @@ impl Coordinator {
                 .await
                 .unwrap_or_terminate("cannot fail to fetch snapshot");
             info!("coordinator init: table size {}", current_contents.len());
+            // Statistics collected by `ANALYZE` are restored rather than reset.
+            if system_table.id() == column_statistics_id {
+                builtin_table_updates.extend(self.restore_column_statistics(current_contents));
+                continue;
+            }
+            // Storage usage history is retained, up to the retention period.
+            if system_table.id() == storage_usage_history_id {
+                builtin_table_updates.extend(self.prune_storage_usage_history(current_contents));
+                continue;
+            }
             let retractions = current_contents
                 .into_iter()
                 .map(|(row, diff)| BuiltinTableUpdate {
@@ -1455,6 +1581,13 @@ impl Coordinator {
                     diff: diff.neg(),
                 });
             builtin_table_updates.extend(retractions);
+            // Named subscribes were restored above, possibly with a new expiration time.
+            if system_table.id() == retained_subscriptions_id {
+                let state = self.catalog().state();
+                builtin_table_updates.extend(self.retained_subscribes.iter().map(
+                    |(name, retained)| state.pack_retained_subscribe_update(name, retained, 1),
+                ));
+            }
         }
 
         info!("coordinator init: sending builtin table updates");
@@ -1493,6 +1626,154 @@ impl Coordinator {
         Ok(())
     }
 
+    /// Restores the statistics collected by `ANALYZE` from the contents of
+    /// `mz_column_statistics`.
+    ///
+    /// Returns the retractions of the rows that describe collections that no
+    /// longer exist or whose statistics cannot be restored.
+    fn restore_column_statistics(&mut self, contents: Vec<(Row, Diff)>) -> Vec<BuiltinTableUpdate> {
+        let mut rows_by_id: BTreeMap<String, Vec<Row>> = BTreeMap::new();
+        for (row, diff) in contents {
+            let object_id = row.unpack_first().unwrap_str().to_string();
+            let rows = rows_by_id.entry(object_id).or_default();
+            for _ in 0..diff {
+                rows.push(row.clone());
+            }
+        }
+
+        let mut retractions = Vec::new();
+        for (object_id, rows) in rows_by_id {
+            let statistics = object_id.parse::<GlobalId>().ok().and_then(|id| {
+                let collection = self.controller.storage.collection(id).ok()?;
+                let desc = &collection.collection_metadata.relation_desc;
+                let statistics = CatalogState::unpack_column_statistics_rows(desc, &rows)?;
+                Some((id, statistics))
+            });
+            match statistics {
+                Some((id, statistics)) => {
+                    self.collection_statistics
+                        .insert(id, (Arc::new(statistics), rows));
+                }
+                None => retractions.extend(
+                    self.catalog()
+                        .state()
+                        .pack_column_statistics_updates(rows, -1),
+                ),
+            }
+        }
+        retractions
+    }
+
+    /// Restores the named subscribes recorded in `mz_retained_subscriptions`.
+    ///
+    /// The restored subscribes don't hold back compaction of their inputs until
+    /// [`Coordinator::bootstrap_retained_subscribes`] is called. A subscribe that was running
+    /// when the coordinator went away starts its retention period now.
+    async fn load_retained_subscribes(&mut self) {
+        let id = self
+            .catalog()
+            .resolve_builtin_table(&MZ_RETAINED_SUBSCRIPTIONS);
+        let write_frontier = self.storage_write_frontier(id);
+        let Some(as_of) = write_frontier
+            .as_option()
+            .and_then(|upper| upper.step_back())
+        else {
+            return;
+        };
+        let contents = self
+            .controller
+            .storage
+            .snapshot(id, as_of)
+            .await
+            .unwrap_or_terminate("cannot fail to fetch snapshot");
+        let now = self.now();
+        for (row, _diff) in contents {
+            let Some((name, mut retained)) = CatalogState::unpack_retained_subscribe_row(&row)
+            else {
+                warn!("cannot restore retained subscribe from {row:?}");
+                continue;
+            };
+            if retained.expires_at.is_none() {
+                let retention_ms =
+                    u64::try_from(retained.retention.as_millis()).unwrap_or(u64::MAX);
+                retained.expires_at = Some(now.saturating_add(retention_ms));
+            }
+            self.retained_subscribes.insert(name, retained);
+        }
+    }
+
+    /// Acquires read holds on the inputs of the named subscribes restored by
+    /// [`Coordinator::load_retained_subscribes`] at their resume points.
+    ///
+    /// Drops the subscribes that can no longer be resumed, because their cluster or inputs no
+    /// longer exist, or because their inputs are not readable at their resume points anymore.
+    fn bootstrap_retained_subscribes(&mut self) {
+        let names: Vec<_> = self.retained_subscribes.keys().cloned().collect();
+        for name in names {
+            let retained = &self.retained_subscribes[&name];
+            let cluster_id = retained.cluster_id;
+            let resume_as_of = retained.resume_as_of;
+            let depends_on = retained.from.depends_on();
+            let exists = self.catalog().try_get_cluster(cluster_id).is_some()
+                && depends_on
+                    .iter()
+                    .all(|id| self.catalog().try_get_entry(id).is_some());
+            if !exists {
+                info!("coordinator init: dropping retained subscribe {name}");
+                self.retained_subscribes.remove(&name);
+                continue;
+            }
+            let id_bundle = self
+                .index_oracle(cluster_id)
+                .sufficient_collections(&depends_on);
+            let read_holds = self.acquire_bootstrap_read_holds(resume_as_of, &id_bundle);
+            let readable = read_holds
+                .times()
+                .all(|time| time.less_equal(&resume_as_of));
+            if readable {
+                let retained = self
+                    .retained_subscribes
+                    .get_mut(&name)
+                    .expect("retained subscribe exists");
+                retained.read_holds = read_holds;
+            } else {
+                info!("coordinator init: dropping retained subscribe {name}");
+                self.retained_subscribes.remove(&name);
+                self.release_read_hold(&read_holds);
+            }
+        }
+    }
+
+    /// Returns the retractions of the rows of `mz_storage_usage_history`, whose
+    /// contents are `contents`, that are older than the storage usage retention
+    /// period.
+    fn prune_storage_usage_history(&self, contents: Vec<(Row, Diff)>) -> Vec<BuiltinTableUpdate> {
+        let Some(retention_period) = self.storage_usage_retention_period else {
+            return Vec::new();
+        };
+        let retention_period = u64::try_from(retention_period.as_millis()).unwrap_or(u64::MAX);
+        let cutoff = mz_ore::now::to_datetime(self.now().saturating_sub(retention_period));
+        let id = self
+            .catalog()
+            .resolve_builtin_table(&MZ_STORAGE_USAGE_HISTORY);
+        contents
+            .into_iter()
+            .filter(|(row, _diff)| {
+                let collection_timestamp = row
+                    .iter()
+                    .last()
+                    .expect("collection_timestamp is the last column")
+                    .unwrap_timestamptz();
+                *collection_timestamp < cutoff
+            })
+            .map(|(row, diff)| BuiltinTableUpdate {
+                id,
+                row,
+                diff: diff.neg(),
+            })
+            .collect()
+    }
+
     /// Returns an `as_of` suitable for bootstrapping the given index dataflow.
     fn bootstrap_index_as_of(
         &self,
@@ -1511,7 +1792,19 @@ impl Coordinator {
         // meet of the `upper`s of all dependencies, as we know that no replica can have produced
         // output for that time, so we can assume that no replica `as_of` has been adanced beyond
         // this time either.
-        let write_frontier = self.least_valid_write(&id_bundle);
+        let mut write_frontier = self.least_valid_write(&id_bundle);
+        // Named subscribes must be able to resume at their resume points after a restart, so we
+        // don't advance the `as_of` beyond the resume points of the subscribes that might read
+        // from the index. This gives up on reusing existing dataflows in that case.
+        let resume_as_of = self
+            .retained_subscribes
+            .values()
+            .filter(|retained| retained.cluster_id == cluster_id)
+            .map(|retained| retained.resume_as_of)
+            .min();
+        if let Some(resume_as_of) = resume_as_of {
+            write_frontier.meet_assign(&Antichain::from_elem(resume_as_of));
+        }
         // Things go wrong if we try to create a dataflow with `as_of = []`, so avoid that.
         if !write_frontier.is_empty() {
             as_of.join_assign(&write_frontier);
@@ -1578,6 +1871,9 @@ impl Coordinator {
         });
 
         self.schedule_storage_usage_collection();
+        self.spawn_compaction_queue_collection();
+        self.spawn_index_advice_publishing();
+        self.spawn_shared_subplans_publishing();
         flags::tracing_config(self.catalog.system_config()).apply(&self.tracing_handle);
 
         loop {
@@ -1712,6 +2008,7 @@ pub async fn serve(
         connection_context,
         storage_usage_client,
         storage_usage_collection_interval,
+        persist_clients,
         storage_usage_retention_period,
         segment_client,
         egress_ips,
@@ -1834,6 +2131,7 @@ pub async fn serve(
                 client_pending_peeks: BTreeMap::new(),
                 pending_real_time_recency_timestamp: BTreeMap::new(),
                 active_subscribes: BTreeMap::new(),
+                retained_subscribes: BTreeMap::new(),
                 write_lock: Arc::new(tokio::sync::Mutex::new(())),
                 write_lock_wait_group: VecDeque::new(),
                 pending_writes: Vec::new(),
@@ -1845,6 +2143,13 @@ pub async fn serve(
                 transient_replica_metadata: BTreeMap::new(),
                 storage_usage_client,
                 storage_usage_collection_interval,
+                storage_usage_retention_period,
+                collection_statistics: BTreeMap::new(),
+                persist_clients,
+                compaction_queues: BTreeMap::new(),
+                hydration_statuses: BTreeMap::new(),
+                index_advisor: IndexAdvisor::default(),
+                shared_subplans: SharedSubplanTracker::default(),
                 segment_client,
                 metrics,
                 tracing_handle,
@@ -1854,6 +2159,7 @@ pub async fn serve(
                     .bootstrap(builtin_migration_metadata, builtin_table_updates)
                     .instrument(span)
                     .await?;
+                coord.drop_pending_cluster_replicas().await?;
                 coord
                     .controller
                     .remove_orphaned_replicas(coord.catalog().get_next_replica_id().await?)
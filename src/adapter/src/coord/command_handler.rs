@@ -553,6 +553,7 @@ impl Coordinator {
                     | Statement::AlterSystemReset(_)
                     | Statement::AlterSystemResetAll(_)
                     | Statement::AlterSystemSet(_)
+                    | Statement::Analyze(_)
                     | Statement::CreateCluster(_)
                     | Statement::CreateClusterReplica(_)
                     | Statement::CreateConnection(_)
@@ -24,7 +24,7 @@ use mz_compute_client::types::dataflows::{
     BuildDesc, DataflowDesc, DataflowDescription, IndexDesc,
 };
 use mz_compute_client::types::sinks::{
-    ComputeSinkConnection, ComputeSinkDesc, PersistSinkConnection,
+    ComputeSinkConnection, ComputeSinkDesc, PersistSinkConnection, QuarantineMode, QuarantinePlan,
 };
 use mz_controller::Controller;
 use mz_expr::visit::Visit;
@@ -36,8 +36,10 @@ use mz_ore::cast::ReinterpretCast;
 use mz_ore::stack::{maybe_grow, CheckedRecursion, RecursionGuard, RecursionLimitError};
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::role_id::RoleId;
-use mz_repr::{Datum, GlobalId, Row, Timestamp};
+use mz_repr::{ColumnName, Datum, GlobalId, RelationDesc, Row, Timestamp};
 use mz_sql::catalog::{CatalogRole, SessionCatalog};
+use mz_sql::names::ResolvedIds;
+use mz_transform::dataflow::{SharedSubplan, SharedSubplanImport};
 use timely::progress::Antichain;
 use timely::PartialOrder;
 use tracing::warn;
@@ -191,6 +193,7 @@ impl Coordinator {
         let mut dataflow_plans = Vec::with_capacity(dataflows.len());
         for mut dataflow in dataflows.into_iter() {
             output_ids.extend(dataflow.export_ids());
+            self.observe_shared_subplans(instance, &dataflow);
             // If the only outputs of the dataflow are sinks, we might
             // be able to turn off the computation early, if they all
             // have non-trivial `up_to`s.
@@ -303,6 +306,13 @@ impl Coordinator {
             self.catalog()
                 .system_config()
                 .enable_monotonic_oneshot_selects(),
+            self.catalog()
+                .system_config()
+                .enable_incremental_window_functions(),
+            self.catalog()
+                .system_config()
+                .enable_ordered_top_k()
+                .then(|| self.catalog().system_config().ordered_top_k_threshold()),
         )
         .map_err(AdapterError::Internal)
     }
@@ -387,7 +397,7 @@ impl<'a> DataflowBuilder<'a> {
                 let entry = self.catalog.get_entry(id);
                 match entry.item() {
                     CatalogItem::Table(table) => {
-                        dataflow.import_source(*id, table.desc.typ().clone(), false);
+                        dataflow.import_source(*id, table.desc.typ().clone(), table.append_only);
                     }
                     CatalogItem::Source(source) => {
                         dataflow.import_source(
@@ -470,6 +480,8 @@ impl<'a> DataflowBuilder<'a> {
         }
         dataflow.export_index(id, index_description, on_type);
 
+        self.share_subplans(&mut dataflow, &index.resolved_ids)?;
+
         // Optimize the dataflow across views, and any other ways that appeal.
         mz_transform::optimize_dataflow(&mut dataflow, self, &mz_transform::EmptyStatisticsOracle)?;
 
@@ -541,6 +553,7 @@ impl<'a> DataflowBuilder<'a> {
         for BuildDesc { plan, .. } in &mut dataflow.objects_to_build {
             prep_relation_expr(self.catalog, plan, ExprPrepStyle::Index)?;
         }
+        self.share_subplans(&mut dataflow, &mview.resolved_ids)?;
 
         let sink_description = ComputeSinkDesc {
             from: internal_view_id,
@@ -548,24 +561,228 @@ impl<'a> DataflowBuilder<'a> {
             connection: ComputeSinkConnection::Persist(PersistSinkConnection {
                 value_desc: mview.desc.clone(),
                 storage_metadata: (),
+                quarantine: None,
             }),
             with_snapshot: true,
             up_to: Antichain::default(),
         };
+        let on_error = mview.on_error;
         self.build_sink_dataflow_into(&mut dataflow, id, sink_description)?;
 
+        if let Some(mode) = on_error {
+            Self::quarantine_sink_errors(&mut dataflow, id, internal_view_id, mode)?;
+        }
+
         Ok(dataflow)
     }
 
+    /// Instructs the persist sink `sink_id` to quarantine the errors of the view it sinks, rather
+    /// than write them to its shard.
+    ///
+    /// The final map, filter, and project of the view `view_id` are moved into the sink, so that
+    /// the sink can associate the errors they produce with the input rows that caused them. The
+    /// input rows are identified by a unique key of the input, if one is known, or by all of
+    /// their columns otherwise. Errors produced by the rest of the view, or imported from its
+    /// inputs, are not quarantined.
+    fn quarantine_sink_errors(
+        dataflow: &mut DataflowDesc,
+        sink_id: GlobalId,
+        view_id: GlobalId,
+        mode: QuarantineMode,
+    ) -> Result<(), AdapterError> {
+        let build = dataflow
+            .objects_to_build
+            .iter_mut()
+            .find(|build| build.id == view_id)
+            .ok_or_else(|| AdapterError::Internal(format!("view {view_id} is not built")))?;
+
+        let (mfp, _) = MapFilterProject::extract_non_errors_from_expr(&build.plan);
+        let mfp = match mfp.into_plan().map(|plan| plan.into_nontemporal()) {
+            Ok(Ok(mfp)) => {
+                MapFilterProject::extract_non_errors_from_expr_mut(&mut build.plan.0);
+                mfp
+            }
+            // Temporal filters need to be applied by the view, as the sink cannot delay updates.
+            _ => MapFilterProject::new(build.plan.arity())
+                .into_plan()
+                .expect("identity is a valid plan")
+                .into_nontemporal()
+                .expect("identity is not temporal"),
+        };
+
+        let typ = build.plan.typ();
+        let input_key = match typ.keys.iter().min_by_key(|key| key.len()) {
+            Some(key) => key.clone(),
+            None => (0..typ.arity()).collect(),
+        };
+        let names = (0..typ.arity()).map(|i| ColumnName::from(format!("column{}", i + 1)));
+        let from_desc = RelationDesc::new(typ, names);
+
+        let sink = dataflow
+            .sink_exports
+            .get_mut(&sink_id)
+            .ok_or_else(|| AdapterError::Internal(format!("sink {sink_id} is not exported")))?;
+        sink.from_desc = from_desc;
+        if let ComputeSinkConnection::Persist(connection) = &mut sink.connection {
+            connection.quarantine = Some(QuarantinePlan {
+                mode,
+                mfp,
+                input_key,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Replaces subplans of `dataflow` that are structurally identical to the definition of a
+    /// shared subplan by reads of its contents, if subplan sharing is enabled.
+    ///
+    /// The contents of indexed views are read from any index on this instance, like the indexes
+    /// that the dataflow builder selects for the views that a dataflow imports. Materialized
+    /// views are only read if they are among the `dependencies` of the object that the dataflow
+    /// maintains, as their storage collections stop advancing once they are dropped.
+    fn share_subplans(
+        &self,
+        dataflow: &mut DataflowDesc,
+        dependencies: &ResolvedIds,
+    ) -> Result<(), AdapterError> {
+        if !self.catalog.system_config().enable_subplan_sharing() {
+            return Ok(());
+        }
+        let subplans = self.shared_subplans(&dependencies.0);
+        mz_transform::dataflow::share_subplans(dataflow, &subplans)?;
+        Ok(())
+    }
+
+    /// Returns the IDs of the indexes and materialized views whose contents the dataflow of the
+    /// view `view_id` defined by `expr` would read in place of recomputing shared subplans, given
+    /// the `dependencies` of the object that the dataflow maintains.
+    ///
+    /// These are not dependencies of the object, but its as-of must be readable from them.
+    pub fn view_shared_subplan_imports(
+        &mut self,
+        view_id: GlobalId,
+        expr: &OptimizedMirRelationExpr,
+        dependencies: &ResolvedIds,
+    ) -> Result<BTreeSet<GlobalId>, AdapterError> {
+        if !self.catalog.system_config().enable_subplan_sharing() {
+            return Ok(BTreeSet::new());
+        }
+        let mut dataflow = DataflowDesc::new(view_id.to_string());
+        self.import_view_into_dataflow(&view_id, expr, &mut dataflow)?;
+        for BuildDesc { plan, .. } in &mut dataflow.objects_to_build {
+            prep_relation_expr(self.catalog, plan, ExprPrepStyle::Index)?;
+        }
+        let subplans = self.shared_subplans(&dependencies.0);
+        Ok(mz_transform::dataflow::share_subplans(
+            &mut dataflow,
+            &subplans,
+        )?)
+    }
+
+    /// Returns the subplans of the dataflow of the index or materialized view `id` that are worth
+    /// sharing with other dataflows.
+    ///
+    /// The subplans are taken from the dataflow before it is optimized, as that is the form in
+    /// which they are matched against the definitions of shared subplans.
+    pub fn shareable_subplans(
+        &mut self,
+        id: GlobalId,
+    ) -> Result<BTreeSet<MirRelationExpr>, AdapterError> {
+        let mut dataflow = DataflowDesc::new(id.to_string());
+        let Some(entry) = self.catalog.try_get_entry(&id) else {
+            return Ok(BTreeSet::new());
+        };
+        match entry.item() {
+            CatalogItem::Index(index) => self.import_into_dataflow(&index.on, &mut dataflow)?,
+            CatalogItem::MaterializedView(mview) => {
+                self.import_view_into_dataflow(&id, &mview.optimized_expr, &mut dataflow)?
+            }
+            _ => return Ok(BTreeSet::new()),
+        }
+        for BuildDesc { plan, .. } in &mut dataflow.objects_to_build {
+            prep_relation_expr(self.catalog, plan, ExprPrepStyle::Index)?;
+        }
+        self.share_subplans(&mut dataflow, entry.uses())?;
+        Ok(mz_transform::dataflow::shareable_subplans(&dataflow))
+    }
+
+    /// Returns the views and materialized views whose contents can be read by dataflows on this
+    /// instance in place of recomputing their definitions.
+    ///
+    /// These are the views and materialized views that are indexed on this instance, and the
+    /// materialized views of any cluster, which are read from their storage collections.
+    /// Materialized views that quarantine errors are excluded, as their contents differ from
+    /// their definitions. Only the materialized views among `dependencies` are returned.
+    fn shared_subplans(&self, dependencies: &BTreeSet<GlobalId>) -> Vec<SharedSubplan> {
+        let mut subplans = Vec::new();
+        if let Some(cluster) = self.catalog.try_get_cluster(self.compute.instance_id()) {
+            for index_id in &cluster.bound_objects {
+                if !self.compute.contains_collection(index_id) {
+                    continue;
+                }
+                let CatalogItem::Index(index) = self.catalog.get_entry(index_id).item() else {
+                    continue;
+                };
+                let Some(plan) = self.shared_subplan_plan(index.on) else {
+                    continue;
+                };
+                subplans.push(SharedSubplan {
+                    id: index.on,
+                    plan,
+                    import: SharedSubplanImport::Index {
+                        id: *index_id,
+                        key: index.keys.clone(),
+                    },
+                    monotonic: self.monotonic_view(index.on),
+                });
+            }
+        }
+        for entry in dependencies
+            .iter()
+            .filter_map(|id| self.catalog.try_get_entry(id))
+        {
+            if let CatalogItem::MaterializedView(_) = entry.item() {
+                let Some(plan) = self.shared_subplan_plan(entry.id()) else {
+                    continue;
+                };
+                subplans.push(SharedSubplan {
+                    id: entry.id(),
+                    plan,
+                    import: SharedSubplanImport::Persist,
+                    monotonic: self.monotonic_view(entry.id()),
+                });
+            }
+        }
+        subplans
+    }
+
+    /// Returns the definition of the view or materialized view `id`, prepared in the same way as
+    /// the objects built by dataflows on this instance.
+    fn shared_subplan_plan(&self, id: GlobalId) -> Option<MirRelationExpr> {
+        let mut plan = match self.catalog.get_entry(&id).item() {
+            CatalogItem::View(View { optimized_expr, .. })
+            | CatalogItem::MaterializedView(MaterializedView {
+                optimized_expr,
+                on_error: None,
+                ..
+            }) => optimized_expr.clone(),
+            _ => return None,
+        };
+        prep_relation_expr(self.catalog, &mut plan, ExprPrepStyle::Index).ok()?;
+        Some(plan.into_inner())
+    }
+
     /// Determine the given source's monotonicity.
     fn monotonic_source(&self, source: &Source) -> bool {
         // TODO(petrosagg): store an inverse mapping of subsource -> source in the catalog so that
         // we can retrieve monotonicity information from the parent source.
         match &source.data_source {
             DataSourceDesc::Ingestion(ingestion) => ingestion.desc.monotonic(),
+            // Webhook sources are only ever appended to.
+            DataSourceDesc::Webhook { .. } => true,
             DataSourceDesc::Introspection(_)
             | DataSourceDesc::Progress
-            | DataSourceDesc::Webhook { .. }
             | DataSourceDesc::Source => false,
         }
     }
@@ -591,6 +808,7 @@ impl<'a> DataflowBuilder<'a> {
         self.checked_recur(|_| {
             match self.catalog.get_entry(&id).item() {
                 CatalogItem::Source(source) => Ok(self.monotonic_source(source)),
+                CatalogItem::Table(table) => Ok(table.append_only),
                 CatalogItem::View(View { optimized_expr, .. })
                 | CatalogItem::MaterializedView(MaterializedView { optimized_expr, .. }) => {
                     let mut view_expr = optimized_expr.clone().into_inner();
@@ -640,7 +858,6 @@ impl<'a> DataflowBuilder<'a> {
                 CatalogItem::Secret(_)
                 | CatalogItem::Type(_)
                 | CatalogItem::Connection(_)
-                | CatalogItem::Table(_)
                 | CatalogItem::Log(_)
                 | CatalogItem::Index(_)
                 | CatalogItem::Sink(_)
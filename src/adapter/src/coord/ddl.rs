@@ -217,6 +217,34 @@ impl Coordinator {
             .chain(materialized_views_to_drop.iter().map(|(_, id)| id))
             .collect();
 
+        // Forget any statistics collected by `ANALYZE` about dropped relations.
+        let statistics_to_drop: Vec<_> = relations_to_drop
+            .iter()
+            .filter(|id| self.collection_statistics.contains_key(id))
+            .map(|id| **id)
+            .collect();
+
+        // Forget the hydration statuses of dropped compute collections and replicas.
+        let replicas_to_drop: BTreeSet<_> = cluster_replicas_to_drop
+            .iter()
+            .map(|(_, replica_id)| *replica_id)
+            .chain(clusters_to_drop.iter().flat_map(|cluster_id| {
+                self.catalog()
+                    .get_cluster(*cluster_id)
+                    .replicas_by_id
+                    .keys()
+                    .copied()
+            }))
+            .collect();
+        let hydration_statuses_to_drop: Vec<_> = self
+            .hydration_statuses
+            .keys()
+            .filter(|(id, replica_id)| {
+                relations_to_drop.contains(&id) || replicas_to_drop.contains(replica_id)
+            })
+            .copied()
+            .collect();
+
         // Clean up any active subscribes that rely on dropped relations.
         let subscribe_sinks_to_drop: Vec<_> = self
             .active_subscribes
@@ -245,6 +273,22 @@ impl Coordinator {
             })
             .collect();
 
+        // Forget any named subscribes that rely on dropped relations, as they
+        // can no longer be resumed.
+        let retained_subscribes_to_drop: Vec<_> = self
+            .retained_subscribes
+            .iter()
+            .filter(|(_name, retained)| {
+                retained
+                    .from
+                    .depends_on()
+                    .into_iter()
+                    .chain(retained.read_holds.id_bundle().iter())
+                    .any(|id| relations_to_drop.contains(&id))
+            })
+            .map(|(name, _retained)| name.clone())
+            .collect();
+
         // Clean up any pending peeks that rely on dropped relations.
         for (uuid, pending_peek) in &self.pending_peeks {
             if let Some(id) = pending_peek
@@ -334,7 +378,7 @@ impl Coordinator {
 
         let (catalog, controller) = self.catalog_and_controller_mut();
         let TransactionResult {
-            builtin_table_updates,
+            mut builtin_table_updates,
             audit_events,
             result,
         } = catalog
@@ -349,6 +393,24 @@ impl Coordinator {
         // No error returns are allowed after this point. Enforce this at compile time
         // by using this odd structure so we don't accidentally add a stray `?`.
         let _: () = async {
+            for id in statistics_to_drop {
+                if let Some((_, rows)) = self.collection_statistics.remove(&id) {
+                    builtin_table_updates.extend(
+                        self.catalog()
+                            .state()
+                            .pack_column_statistics_updates(rows, -1),
+                    );
+                }
+            }
+            for (id, replica_id) in hydration_statuses_to_drop {
+                if let Some(status) = self.hydration_statuses.remove(&(id, replica_id)) {
+                    builtin_table_updates.push(
+                        self.catalog()
+                            .state()
+                            .pack_hydration_status_update(id, replica_id, &status, -1),
+                    );
+                }
+            }
             self.send_builtin_table_updates(builtin_table_updates).await;
 
             if !timeline_associations.is_empty() {
@@ -367,6 +429,9 @@ impl Coordinator {
             if !storage_sinks_to_drop.is_empty() {
                 self.drop_storage_sinks(storage_sinks_to_drop);
             }
+            for name in retained_subscribes_to_drop {
+                self.drop_retained_subscribe(&name);
+            }
             if !subscribe_sinks_to_drop.is_empty() {
                 let (dropped_metadata, subscribe_sinks_to_drop): (Vec<_>, BTreeSet<_>) =
                     subscribe_sinks_to_drop.into_iter().unzip();
@@ -0,0 +1,340 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Recommendation of indexes based on the plans of recent peeks.
+//!
+//! The plans of the peeks that the coordinator sequences are inspected for indexes that they would
+//! have used if they existed (see [`mz_transform::index_advice`]). To keep this work off the peek
+//! path, peeks only retain their plans, and the plans are inspected when the recommendations are
+//! next published. At most [`MAX_PENDING_INDEX_ADVICE`] plans are retained per publication, as a
+//! uniform sample of the peeks since the last one. Each sampled peek stands in for an equal share
+//! of those peeks, so `query_count` estimates the number of all peeks that would have used an
+//! index, and is exact while there are no more peeks per publication than retained plans. The
+//! recommendations are aggregated per cluster and periodically published to
+//! `mz_internal.mz_recommended_indexes`.
+//! Recommendations are retracted once a matching index exists, or once the indexed object or
+//! the cluster is dropped.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use itertools::Itertools;
+use mz_compute_client::types::dataflows::DataflowDesc;
+use mz_controller::clusters::ClusterId;
+use mz_expr::MirScalarExpr;
+use mz_ore::cast::CastFrom;
+use mz_ore::now::EpochMillis;
+use mz_transform::index_advice::{dataflow_index_advice, IndexAdvice};
+use rand::Rng;
+
+use crate::catalog::CatalogState;
+use crate::coord::published_snapshot::PublishedSnapshot;
+use crate::coord::{Coordinator, Message};
+
+/// The maximum number of recommendations retained across all clusters.
+///
+/// When exceeded, the least recently observed recommendations are forgotten.
+const MAX_INDEX_ADVICE: usize = 1000;
+
+/// The interval at which recommendations are published.
+const INDEX_ADVICE_PUBLISH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The maximum number of peek plans retained for inspection between publications.
+///
+/// Further peeks replace retained plans at random, which bounds the work that index
+/// recommendations add to the coordinator.
+const MAX_PENDING_INDEX_ADVICE: usize = 100;
+
+/// An index recommended for a cluster, along with how often it was recommended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexAdviceEntry {
+    /// The cluster on which the index would be created.
+    pub cluster_id: ClusterId,
+    /// The recommended index.
+    pub advice: IndexAdvice,
+    /// The key of the recommended index, rendered in terms of the column names of the indexed
+    /// object.
+    pub key_text: String,
+    /// The estimated number of peeks whose plans would have used the index.
+    pub query_count: u64,
+    /// The time at which the index was last recommended.
+    pub last_seen: EpochMillis,
+}
+
+/// Aggregates index recommendations across peeks.
+#[derive(Debug, Default)]
+pub struct IndexAdvisor {
+    /// A uniform sample of the plans of peeks that have yet to be inspected, with the clusters
+    /// they ran on.
+    pending: Vec<(ClusterId, DataflowDesc)>,
+    /// The number of peeks since the sample was last inspected, including those not retained.
+    peeks: usize,
+    /// The current recommendations.
+    entries: BTreeMap<(ClusterId, IndexAdvice), IndexAdviceEntry>,
+    /// The recommendations last written to `mz_recommended_indexes`.
+    published: PublishedSnapshot<IndexAdviceEntry>,
+}
+
+impl IndexAdvisor {
+    /// Offers the plan of a peek on `cluster_id` to the sample of plans retained for inspection.
+    ///
+    /// Once [`MAX_PENDING_INDEX_ADVICE`] plans are retained, the `n`th peek replaces a random
+    /// retained plan with probability `MAX_PENDING_INDEX_ADVICE / n`, so that every peek is
+    /// equally likely to be inspected.
+    fn sample(&mut self, cluster_id: ClusterId, dataflow: &DataflowDesc) {
+        self.peeks += 1;
+        if self.pending.len() < MAX_PENDING_INDEX_ADVICE {
+            self.pending.push((cluster_id, dataflow.clone()));
+        } else {
+            let index = rand::thread_rng().gen_range(0..self.peeks);
+            if index < MAX_PENDING_INDEX_ADVICE {
+                self.pending[index] = (cluster_id, dataflow.clone());
+            }
+        }
+    }
+
+    /// Takes the sampled plans, each with the number of peeks it stands in for.
+    ///
+    /// The peeks since the last call are divided among the sampled plans as evenly as possible.
+    fn take_pending(&mut self) -> Vec<(ClusterId, DataflowDesc, u64)> {
+        let pending = std::mem::take(&mut self.pending);
+        let peeks = u64::cast_from(std::mem::take(&mut self.peeks));
+        let samples = u64::cast_from(pending.len());
+        pending
+            .into_iter()
+            .zip(0..)
+            .map(|((cluster_id, dataflow), i)| {
+                let weight = peeks * (i + 1) / samples - peeks * i / samples;
+                (cluster_id, dataflow, weight)
+            })
+            .collect()
+    }
+
+    /// Records that `peeks` peeks on `cluster_id` would have used the indexes in `advice` at
+    /// `now`.
+    ///
+    /// Each recommended index is accompanied by its rendered key.
+    pub fn observe(
+        &mut self,
+        cluster_id: ClusterId,
+        advice: impl IntoIterator<Item = (IndexAdvice, String)>,
+        peeks: u64,
+        now: EpochMillis,
+    ) {
+        for (advice, key_text) in advice {
+            let entry = self
+                .entries
+                .entry((cluster_id, advice.clone()))
+                .or_insert_with(|| IndexAdviceEntry {
+                    cluster_id,
+                    advice,
+                    key_text,
+                    query_count: 0,
+                    last_seen: now,
+                });
+            entry.query_count += peeks;
+            entry.last_seen = now;
+        }
+
+        if self.entries.len() > MAX_INDEX_ADVICE {
+            let mut last_seen: Vec<_> = self.entries.values().map(|e| e.last_seen).collect();
+            last_seen.sort_unstable();
+            let cutoff = last_seen[self.entries.len() - MAX_INDEX_ADVICE];
+            self.entries.retain(|_, e| e.last_seen >= cutoff);
+        }
+    }
+
+    /// Forgets all recommendations for which `f` returns `false`.
+    pub fn retain(&mut self, mut f: impl FnMut(ClusterId, &IndexAdvice) -> bool) {
+        self.entries
+            .retain(|(cluster_id, advice), _| f(*cluster_id, advice));
+    }
+
+    /// Returns the current recommendations, and replaces the published recommendations with
+    /// them.
+    ///
+    /// See [`PublishedSnapshot::replace`].
+    fn publish(&mut self) -> Option<(Vec<IndexAdviceEntry>, Vec<IndexAdviceEntry>)> {
+        let entries = self.entries.values().cloned().collect();
+        self.published.replace(entries)
+    }
+}
+
+impl Coordinator {
+    /// Retains the optimized peek `dataflow` on `cluster_id`, so that the indexes it would have
+    /// used if they existed are recorded when recommendations are next published.
+    pub(crate) fn observe_index_advice(&mut self, cluster_id: ClusterId, dataflow: &DataflowDesc) {
+        self.index_advisor.sample(cluster_id, dataflow);
+    }
+
+    /// Records the indexes that the retained peek plans would have used if they existed.
+    fn inspect_pending_index_advice(&mut self) {
+        let pending = self.index_advisor.take_pending();
+        let now = self.now();
+        for (cluster_id, dataflow, peeks) in pending {
+            if self.catalog().try_get_cluster(cluster_id).is_none() {
+                continue;
+            }
+            let advice = dataflow_index_advice(&dataflow, &self.index_oracle(cluster_id))
+                .into_iter()
+                // Only user objects can be indexed by users.
+                .filter(|a| a.on_id.is_user())
+                .filter_map(|a| {
+                    let key_text = self.index_advice_key_text(&a)?;
+                    Some((a, key_text))
+                })
+                .collect::<Vec<_>>();
+            self.index_advisor.observe(cluster_id, advice, peeks, now);
+        }
+    }
+
+    /// Renders the key of the recommended index `advice` in terms of the column names of the
+    /// indexed object.
+    ///
+    /// Returns `None` if the indexed object no longer exists.
+    pub(crate) fn index_advice_key_text(&self, advice: &IndexAdvice) -> Option<String> {
+        let catalog = self.catalog();
+        let entry = catalog.try_get_entry(&advice.on_id)?;
+        let name = catalog.resolve_full_name(entry.name(), entry.conn_id());
+        let desc = entry.desc(&name).ok()?;
+        let key_text = advice
+            .key
+            .iter()
+            .map(|expr| match expr {
+                MirScalarExpr::Column(c) => desc
+                    .get_unambiguous_name(*c)
+                    .map_or_else(|| expr.to_string(), |name| name.to_string()),
+                _ => expr.to_string(),
+            })
+            .join(", ");
+        Some(key_text)
+    }
+
+    /// Publishes the current index recommendations to `mz_internal.mz_recommended_indexes`.
+    ///
+    /// The retained peek plans are inspected first. Recommendations for dropped clusters or
+    /// objects, and recommendations that are now served by an existing index, are then forgotten.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn index_advice_update(&mut self) {
+        self.inspect_pending_index_advice();
+
+        let mut advisor = std::mem::take(&mut self.index_advisor);
+        advisor.retain(|cluster_id, advice| {
+            let catalog = self.catalog();
+            catalog.try_get_cluster(cluster_id).is_some()
+                && catalog.try_get_entry(&advice.on_id).is_some()
+                && !advice.is_served_by(&self.index_oracle(cluster_id))
+        });
+        let published = advisor.publish();
+        self.index_advisor = advisor;
+
+        if let Some(published) = published {
+            self.buffer_snapshot_updates(published, CatalogState::pack_index_advice_updates);
+        }
+    }
+
+    pub fn spawn_index_advice_publishing(&self) {
+        self.spawn_periodic_publishing(
+            "index_advice_publishing",
+            INDEX_ADVICE_PUBLISH_INTERVAL,
+            || Message::IndexAdvicePublish,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::GlobalId;
+    use mz_transform::index_advice::IndexAdviceReason;
+
+    use super::*;
+
+    fn advice(id: u64) -> (IndexAdvice, String) {
+        let advice = IndexAdvice {
+            on_id: GlobalId::User(id),
+            key: vec![MirScalarExpr::column(0)],
+            reason: IndexAdviceReason::Filter,
+        };
+        (advice, "a".into())
+    }
+
+    #[mz_ore::test]
+    fn test_index_advisor() {
+        let cluster_id = ClusterId::User(1);
+        let mut advisor = IndexAdvisor::default();
+        advisor.observe(cluster_id, [advice(1)], 1, 10);
+        advisor.observe(cluster_id, [advice(1), advice(2)], 1, 20);
+
+        let (old, new) = advisor.publish().expect("changed");
+        assert!(old.is_empty());
+        assert_eq!(
+            new.iter()
+                .map(|e| (e.advice.on_id, e.query_count, e.last_seen))
+                .collect::<Vec<_>>(),
+            vec![(GlobalId::User(1), 2, 20), (GlobalId::User(2), 1, 20)],
+        );
+        assert_eq!(advisor.publish(), None);
+
+        advisor.retain(|_, a| a.on_id != GlobalId::User(1));
+        // Only the forgotten recommendation is retracted.
+        let (old, new) = advisor.publish().expect("changed");
+        assert_eq!(
+            old.iter().map(|e| e.advice.on_id).collect::<Vec<_>>(),
+            vec![GlobalId::User(1)],
+        );
+        assert!(new.is_empty());
+    }
+
+    #[mz_ore::test]
+    fn test_index_advisor_bounded() {
+        let cluster_id = ClusterId::User(1);
+        let mut advisor = IndexAdvisor::default();
+        for id in 0..u64::cast_from(MAX_INDEX_ADVICE) + 10 {
+            advisor.observe(cluster_id, [advice(id)], 1, id);
+        }
+        assert_eq!(advisor.entries.len(), MAX_INDEX_ADVICE);
+        assert!(advisor
+            .entries
+            .keys()
+            .all(|(_, a)| a.on_id >= GlobalId::User(10)));
+    }
+
+    #[mz_ore::test]
+    fn test_index_advisor_sample() {
+        let mut advisor = IndexAdvisor::default();
+        let dataflow = DataflowDesc::new("peek".into());
+        for id in 1..=3 {
+            advisor.sample(ClusterId::User(id), &dataflow);
+        }
+        // While all peeks are retained, each stands in for itself.
+        let pending = advisor.take_pending();
+        assert_eq!(
+            pending.iter().map(|(c, _, w)| (*c, *w)).collect::<Vec<_>>(),
+            vec![
+                (ClusterId::User(1), 1),
+                (ClusterId::User(2), 1),
+                (ClusterId::User(3), 1)
+            ],
+        );
+
+        let peeks = 10 * MAX_PENDING_INDEX_ADVICE + 7;
+        for _ in 0..peeks {
+            advisor.sample(ClusterId::User(1), &dataflow);
+        }
+        let pending = advisor.take_pending();
+        assert_eq!(pending.len(), MAX_PENDING_INDEX_ADVICE);
+        // The retained peeks stand in for all peeks, as evenly as possible.
+        assert_eq!(
+            pending.iter().map(|(_, _, w)| *w).sum::<u64>(),
+            u64::cast_from(peeks)
+        );
+        assert!(pending.iter().all(|(_, _, w)| *w == 10 || *w == 11));
+        assert!(advisor.take_pending().is_empty());
+    }
+}
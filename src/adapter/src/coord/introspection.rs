@@ -127,6 +127,7 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::AlterDefaultPrivileges(_)
         | Plan::ReassignOwned(_)
         | Plan::ValidateConnection(_)
+        | Plan::Analyze(_)
         | Plan::SideEffectingFunc(_) => return TargetCluster::Active,
     };
 
@@ -323,7 +324,8 @@ pub fn user_privilege_hack(
         | Plan::RevokePrivileges(_)
         | Plan::AlterDefaultPrivileges(_)
         | Plan::CopyRows(_)
-        | Plan::ReassignOwned(_) => {
+        | Plan::ReassignOwned(_)
+        | Plan::Analyze(_) => {
             return Err(AdapterError::Unauthorized(
                 rbac::UnauthorizedError::MzIntrospection {
                     action: plan.name().to_string(),
@@ -11,14 +11,20 @@
 //! messages from various sources (ex: controller, clients, background tasks, etc).
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::pin;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use chrono::DurationRound;
-use mz_controller::clusters::ClusterEvent;
+use futures::StreamExt;
+use mz_controller::clusters::{ClusterEvent, ReplicaId};
 use mz_controller::ControllerResponse;
 use mz_ore::now::EpochMillis;
 use mz_ore::task;
-use mz_persist_client::usage::ShardsUsageReferenced;
+use mz_persist_client::compaction::CompactionQueueEntry;
+use mz_persist_client::usage::{ShardsUsageAudit, ShardsUsageReferenced};
+use mz_persist_client::ShardId;
+use mz_repr::GlobalId;
 use mz_sql::ast::Statement;
 use mz_sql::names::ResolvedIds;
 use mz_sql::plan::{CreateSourcePlans, Plan};
@@ -26,17 +32,22 @@ use mz_storage_client::controller::CollectionMetadata;
 use rand::{rngs, Rng, SeedableRng};
 use tracing::{event, warn, Instrument, Level};
 
+use crate::catalog::CatalogState;
 use crate::client::ConnectionId;
 use crate::command::{Command, ExecuteResponse};
 use crate::coord::appends::Deferred;
 use crate::coord::{
-    Coordinator, CreateConnectionValidationReady, Message, PeekStage, PeekStageFinish,
-    PendingReadTxn, PlanValidity, PurifiedStatementReady, RealTimeRecencyContext,
+    AnalyzeReady, Coordinator, CreateConnectionValidationReady, Message, PeekStage,
+    PeekStageFinish, PendingReadTxn, PlanValidity, PurifiedStatementReady, RealTimeRecencyContext,
     SinkConnectionReady,
 };
 use crate::util::ResultExt;
 use crate::{catalog, AdapterNotice, TimestampContext};
 
+/// The number of shards audited, and recorded in `mz_storage_usage_history`,
+/// at a time.
+const AUDIT_BATCH_SIZE: usize = 1000;
+
 impl Coordinator {
     pub(crate) async fn handle_message(&mut self, msg: Message) {
         match msg {
@@ -57,6 +68,7 @@ impl Coordinator {
             Message::CreateConnectionValidationReady(ready) => {
                 self.message_create_connection_validation_ready(ready).await
             }
+            Message::AnalyzeReady(ready) => self.message_analyze_ready(ready).await,
             Message::SinkConnectionReady(ready) => self.message_sink_connection_ready(ready).await,
             Message::Execute {
                 portal_name,
@@ -76,6 +88,7 @@ impl Coordinator {
             }
             Message::AdvanceTimelines => {
                 self.advance_timelines().await;
+                self.expire_retained_subscribes();
             }
             Message::ClusterEvent(event) => self.message_cluster_event(event).await,
             // Processing this message DOES NOT send a response to the client;
@@ -93,6 +106,22 @@ impl Coordinator {
             Message::StorageUsageUpdate(sizes) => {
                 self.storage_usage_update(sizes).await;
             }
+            Message::StorageUsageAuditUpdate {
+                shard_objects,
+                audit,
+                collection_timestamp,
+            } => {
+                self.storage_usage_audit_update(shard_objects, audit, collection_timestamp);
+            }
+            Message::CompactionQueueFetch => {
+                self.compaction_queue_update();
+            }
+            Message::IndexAdvicePublish => {
+                self.index_advice_update();
+            }
+            Message::SharedSubplansPublish => {
+                self.shared_subplans_update();
+            }
             Message::RealTimeRecencyTimestamp {
                 conn_id,
                 real_time_recency_ts,
@@ -108,9 +137,31 @@ impl Coordinator {
                 self.sequence_execute_single_statement_transaction(ctx, stmt, params)
                     .await;
             }
+            Message::ClusterReconfigurationCheck {
+                ctx,
+                reconfiguration,
+            } => {
+                self.check_cluster_reconfiguration(ctx, reconfiguration)
+                    .await;
+            }
             Message::PeekStageReady { ctx, stage } => {
                 self.sequence_peek_stage(ctx, stage).await;
             }
+            Message::SubscribeResponsesDelivered { sink_id, delivered } => {
+                if let Some(active_subscribe) = self.active_subscribes.get_mut(&sink_id) {
+                    if let Some(deliveries) = &mut active_subscribe.deliveries {
+                        deliveries.deliver(delivered);
+                    }
+                    self.acknowledge_subscribe_deliveries(sink_id);
+                }
+            }
+            Message::SubscribeResponsesAcknowledged {
+                sink_id,
+                acknowledged,
+            } => {
+                self.acknowledge_retained_subscribe(sink_id, acknowledged)
+                    .await;
+            }
         }
     }
 
@@ -118,16 +169,18 @@ impl Coordinator {
     pub async fn storage_usage_fetch(&mut self) {
         let internal_cmd_tx = self.internal_cmd_tx.clone();
         let client = self.storage_usage_client.clone();
+        let now = self.catalog().config().now.clone();
 
-        // Record the currently live shards.
-        let live_shards: BTreeSet<_> = self
+        // Record the currently live shards, and the catalog object each
+        // belongs to.
+        let shard_objects: BTreeMap<_, _> = self
             .controller
             .storage
             .collections()
             // A collection is dropped if its read capability has been advanced
             // to the empty antichain.
             .filter(|(_id, collection)| !collection.read_capabilities.is_empty())
-            .flat_map(|(_id, collection)| {
+            .flat_map(|(id, collection)| {
                 let CollectionMetadata {
                     data_shard,
                     remap_shard,
@@ -142,10 +195,13 @@ impl Coordinator {
                     persist_location: _,
                     relation_desc: _,
                 } = &collection.collection_metadata;
-                [*remap_shard, *status_shard, Some(*data_shard)].into_iter()
+                [*remap_shard, *status_shard, Some(*data_shard)]
+                    .into_iter()
+                    .map(move |shard| (shard, *id))
             })
-            .filter_map(|shard| shard)
+            .filter_map(|(shard, id)| shard.map(|shard| (shard, id)))
             .collect();
+        let live_shards: BTreeSet<_> = shard_objects.keys().copied().collect();
 
         let collection_metric = self
             .metrics
@@ -163,6 +219,30 @@ impl Coordinator {
             // `internal_cmd_rx` is dropped.
             if let Err(e) = internal_cmd_tx.send(Message::StorageUsageUpdate(shard_sizes)) {
                 warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                return;
+            }
+
+            // The audit additionally lists every blob in the environment, so
+            // it's considerably slower. Do it after the billing numbers are
+            // sent so it can't delay them. Shards are audited, and their usage
+            // recorded, a batch at a time to bound the memory it takes.
+            let collection_timestamp = now();
+            let mut batches = pin::pin!(client.shards_usage_audit_batched(AUDIT_BATCH_SIZE));
+            while let Some(audit) = batches.next().await {
+                let shard_objects = audit
+                    .by_shard
+                    .keys()
+                    .filter_map(|shard_id| Some((*shard_id, *shard_objects.get(shard_id)?)))
+                    .collect();
+                let msg = Message::StorageUsageAuditUpdate {
+                    shard_objects,
+                    audit,
+                    collection_timestamp,
+                };
+                if let Err(e) = internal_cmd_tx.send(msg) {
+                    warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                    return;
+                }
             }
         });
     }
@@ -190,6 +270,33 @@ impl Coordinator {
         self.schedule_storage_usage_collection();
     }
 
+    /// Appends the audited usage of a batch of shards to
+    /// `mz_storage_usage_history`.
+    ///
+    /// Collections that are older than the retention period are pruned from
+    /// the table when the coordinator bootstraps.
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn storage_usage_audit_update(
+        &mut self,
+        shard_objects: BTreeMap<ShardId, GlobalId>,
+        audit: ShardsUsageAudit,
+        collection_timestamp: EpochMillis,
+    ) {
+        let rows = audit.by_shard.iter().map(|(shard_id, usage)| {
+            CatalogState::pack_storage_usage_history_row(
+                shard_objects.get(shard_id).copied(),
+                shard_id,
+                usage,
+                collection_timestamp,
+            )
+        });
+        let updates = self
+            .catalog()
+            .state()
+            .pack_storage_usage_history_updates(rows, 1);
+        self.buffer_builtin_table_updates(updates);
+    }
+
     pub fn schedule_storage_usage_collection(&self) {
         // Instead of using an `tokio::timer::Interval`, we calculate the time until the next
         // usage collection and wait for that amount of time. This is so we can keep the intervals
@@ -240,6 +347,47 @@ impl Coordinator {
         });
     }
 
+    /// Replaces the compaction requests of this process in
+    /// `mz_internal.mz_persist_compaction_queue` with those currently queued or
+    /// running.
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn compaction_queue_update(&mut self) {
+        let queue = self.persist_clients.compaction_queue();
+        self.publish_compaction_queue(None, queue);
+    }
+
+    /// Replaces the compaction requests of the given replica, or of this
+    /// process if `replica_id` is `None`, in
+    /// `mz_internal.mz_persist_compaction_queue` with `queue`.
+    fn publish_compaction_queue(
+        &mut self,
+        replica_id: Option<ReplicaId>,
+        queue: Vec<CompactionQueueEntry>,
+    ) {
+        let is_empty = queue.is_empty();
+        let published = self.compaction_queues.entry(replica_id).or_default();
+        let snapshot = published.replace(queue);
+        if is_empty {
+            self.compaction_queues.remove(&replica_id);
+        }
+        let Some(snapshot) = snapshot else {
+            return;
+        };
+        self.buffer_snapshot_updates(snapshot, |state, entries, diff| {
+            state.pack_compaction_queue_updates(replica_id, entries, diff)
+        });
+    }
+
+    pub fn spawn_compaction_queue_collection(&self) {
+        const COMPACTION_QUEUE_COLLECTION_INTERVAL: Duration = Duration::from_secs(30);
+
+        self.spawn_periodic_publishing(
+            "compaction_queue_collection",
+            COMPACTION_QUEUE_COLLECTION_INTERVAL,
+            || Message::CompactionQueueFetch,
+        );
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     async fn message_command(&mut self, cmd: Command) {
         event!(Level::TRACE, cmd = format!("{:?}", cmd));
@@ -259,6 +407,7 @@ impl Coordinator {
                 // a `Dropped` response.
                 if let Some(active_subscribe) = self.active_subscribes.get_mut(&sink_id) {
                     let remove = active_subscribe.process_response(response);
+                    self.acknowledge_subscribe_deliveries(sink_id);
                     if remove {
                         self.remove_active_subscribe(sink_id).await;
                     }
@@ -298,6 +447,29 @@ impl Coordinator {
                     self.buffer_builtin_table_updates(updates);
                 }
             }
+            ControllerResponse::ComputeHydrationStatus(id, replica_id, new) => {
+                let key = (id, replica_id);
+                let old = match &new {
+                    Some(status) => self.hydration_statuses.insert(key, status.clone()),
+                    None => self.hydration_statuses.remove(&key),
+                };
+                if old == new {
+                    return;
+                }
+
+                let state = self.catalog().state();
+                let mut updates = Vec::new();
+                if let Some(old) = old {
+                    updates.push(state.pack_hydration_status_update(id, replica_id, &old, -1));
+                }
+                if let Some(new) = new {
+                    updates.push(state.pack_hydration_status_update(id, replica_id, &new, 1));
+                }
+                self.buffer_builtin_table_updates(updates);
+            }
+            ControllerResponse::ComputeCompactionQueue(replica_id, queue) => {
+                self.publish_compaction_queue(Some(replica_id), queue);
+            }
             ControllerResponse::ComputeReplicaMetrics(replica_id, new) => {
                 let m = match self
                     .transient_replica_metadata
@@ -493,6 +665,54 @@ impl Coordinator {
         ctx.retire(result);
     }
 
+    #[tracing::instrument(level = "debug", skip(self, ctx))]
+    async fn message_analyze_ready(
+        &mut self,
+        AnalyzeReady {
+            ctx,
+            id,
+            result,
+            read_holds,
+            mut plan_validity,
+            otel_ctx,
+        }: AnalyzeReady,
+    ) {
+        otel_ctx.attach_as_parent();
+        self.release_read_hold(&read_holds);
+
+        // Ensure that the collection wasn't dropped while we were analyzing it.
+        if let Err(e) = plan_validity.check(self.catalog()) {
+            return ctx.retire(Err(e));
+        }
+        let statistics = match result {
+            Ok(statistics) => statistics,
+            Err(e) => return ctx.retire(Err(e)),
+        };
+        let desc = match self.controller.storage.collection(id) {
+            Ok(collection) => collection.collection_metadata.relation_desc.clone(),
+            Err(e) => return ctx.retire(Err(e.into())),
+        };
+
+        let rows = CatalogState::pack_column_statistics_rows(id, &desc, &statistics, self.now());
+        let mut updates = self
+            .catalog()
+            .state()
+            .pack_column_statistics_updates(rows.iter().cloned(), 1);
+        if let Some((_, old_rows)) = self
+            .collection_statistics
+            .insert(id, (Arc::new(statistics), rows))
+        {
+            updates.extend(
+                self.catalog()
+                    .state()
+                    .pack_column_statistics_updates(old_rows, -1),
+            );
+        }
+        self.send_builtin_table_updates(updates).await;
+
+        ctx.retire(Ok(ExecuteResponse::Analyzed));
+    }
+
     #[tracing::instrument(level = "debug", skip(self, ctx))]
     async fn message_sink_connection_ready(
         &mut self,
@@ -0,0 +1,117 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Periodic publication of coordinator state to builtin tables.
+//!
+//! Some introspection, like index recommendations, is aggregated in memory by the coordinator and
+//! changes too frequently to be written to its builtin table on every change. Instead, the
+//! coordinator periodically replaces the contents of the table with a snapshot of its state. A
+//! [`PublishedSnapshot`] remembers the entries last written, so that only the entries that changed
+//! are retracted and inserted.
+
+use std::time::Duration;
+
+use mz_ore::task;
+use mz_repr::Diff;
+
+use crate::catalog::{BuiltinTableUpdate, CatalogState};
+use crate::coord::{Coordinator, Message};
+
+/// The entries last written to a builtin table.
+#[derive(Debug)]
+pub struct PublishedSnapshot<T> {
+    published: Vec<T>,
+}
+
+impl<T> Default for PublishedSnapshot<T> {
+    fn default() -> Self {
+        Self {
+            published: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> PublishedSnapshot<T> {
+    /// Replaces the published entries with `new`.
+    ///
+    /// Returns `None` if the entries did not change since they were last published. Otherwise
+    /// returns the previously published entries that are not among the new ones, along with the
+    /// new entries that were not published before. Entries that did not change are in neither.
+    pub fn replace(&mut self, new: Vec<T>) -> Option<(Vec<T>, Vec<T>)> {
+        if new == self.published {
+            return None;
+        }
+        let mut retracted = std::mem::replace(&mut self.published, new.clone());
+        let mut inserted = Vec::new();
+        for entry in new {
+            match retracted.iter().position(|old| *old == entry) {
+                Some(index) => {
+                    retracted.swap_remove(index);
+                }
+                None => inserted.push(entry),
+            }
+        }
+        Some((retracted, inserted))
+    }
+}
+
+impl Coordinator {
+    /// Buffers the builtin table updates that retract the `retracted` entries of a published
+    /// snapshot and insert the `inserted` ones, as packed by `pack`.
+    ///
+    /// See [`PublishedSnapshot::replace`].
+    pub(crate) fn buffer_snapshot_updates<T>(
+        &mut self,
+        (retracted, inserted): (Vec<T>, Vec<T>),
+        pack: impl Fn(&CatalogState, &[T], Diff) -> Vec<BuiltinTableUpdate>,
+    ) {
+        let state = self.catalog().state();
+        let mut updates = pack(state, &retracted, -1);
+        updates.extend(pack(state, &inserted, 1));
+        self.buffer_builtin_table_updates(updates);
+    }
+
+    /// Spawns a task named `name` that sends `message` to the coordinator every `interval`, until
+    /// the coordinator shuts down.
+    pub(crate) fn spawn_periodic_publishing(
+        &self,
+        name: &'static str,
+        interval: Duration,
+        message: fn() -> Message,
+    ) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| name, async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                if internal_cmd_tx.send(message()).is_err() {
+                    // If sending fails, the main thread has shutdown.
+                    break;
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_published_snapshot() {
+        let mut snapshot = PublishedSnapshot::default();
+        assert_eq!(snapshot.replace(vec![]), None);
+        assert_eq!(snapshot.replace(vec![1, 2]), Some((vec![], vec![1, 2])));
+        assert_eq!(snapshot.replace(vec![1, 2]), None);
+        // Only changed entries are retracted and inserted.
+        assert_eq!(snapshot.replace(vec![2]), Some((vec![1], vec![])));
+        assert_eq!(snapshot.replace(vec![2, 3]), Some((vec![], vec![3])));
+        assert_eq!(snapshot.replace(vec![2, 2]), Some((vec![3], vec![2])));
+    }
+}
@@ -228,6 +228,9 @@ impl crate::coord::Coordinator {
     /// This should be called only after a collection is created, and
     /// ideally very soon afterwards. The collection is otherwise initialized
     /// with a read policy that allows no compaction.
+    ///
+    /// Read holds acquired by [`Coordinator::acquire_bootstrap_read_holds`]
+    /// are kept.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) async fn initialize_read_policies(
         &mut self,
@@ -266,6 +269,9 @@ impl crate::coord::Coordinator {
             for (compute_instance, compute_ids) in id_bundle.compute_ids {
                 for id in compute_ids {
                     let mut read_capability = Self::default_read_capability(compaction_window_ms);
+                    if let Some(existing) = self.compute_read_capabilities.remove(&id) {
+                        read_capability.holds = existing.holds;
+                    }
                     if let Some(time) = &time {
                         read_capability
                             .holds
@@ -281,6 +287,9 @@ impl crate::coord::Coordinator {
 
             for id in id_bundle.storage_ids {
                 let mut read_capability = Self::default_read_capability(compaction_window_ms);
+                if let Some(existing) = self.storage_read_capabilities.remove(&id) {
+                    read_capability.holds = existing.holds;
+                }
                 if let Some(time) = &time {
                     read_capability
                         .holds
@@ -429,6 +438,30 @@ impl crate::coord::Coordinator {
         read_holds
     }
 
+    /// Attempt to acquire read holds on the indicated collections at the indicated `time`, before
+    /// their read policies have been initialized during bootstrap.
+    ///
+    /// See [`Coordinator::acquire_read_holds`].
+    pub(crate) fn acquire_bootstrap_read_holds(
+        &mut self,
+        time: mz_repr::Timestamp,
+        id_bundle: &CollectionIdBundle,
+    ) -> ReadHolds<mz_repr::Timestamp> {
+        for id in &id_bundle.storage_ids {
+            self.storage_read_capabilities
+                .entry(*id)
+                .or_insert_with(|| Self::default_read_capability(None));
+        }
+        for compute_ids in id_bundle.compute_ids.values() {
+            for id in compute_ids {
+                self.compute_read_capabilities
+                    .entry(*id)
+                    .or_insert_with(|| Self::default_read_capability(None));
+            }
+        }
+        self.acquire_read_holds(time, id_bundle)
+    }
+
     /// Attempt to acquire read holds on the indicated collections at the indicated `time`.
     ///
     /// If we are unable to acquire a read hold at the provided `time` for a specific id, then we
@@ -18,7 +18,7 @@ use mz_expr::OptimizedMirRelationExpr;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_repr::explain::ExplainFormat;
 use mz_repr::{GlobalId, Timestamp};
-use mz_sql::catalog::CatalogCluster;
+use mz_sql::catalog::{CatalogCluster, ObjectType};
 use mz_sql::names::ResolvedIds;
 use mz_sql::plan::{
     AbortTransactionPlan, CommitTransactionPlan, CopyRowsPlan, CreateRolePlan, CreateSourcePlans,
@@ -309,8 +309,16 @@ impl Coordinator {
                 ctx.retire(Ok(ExecuteResponse::AlteredObject(plan.object_type)));
             }
             Plan::AlterCluster(plan) => {
-                let result = self.sequence_alter_cluster(ctx.session(), plan).await;
-                ctx.retire(result);
+                match self.sequence_alter_cluster(ctx.session(), plan).await {
+                    Ok(Some(reconfiguration)) => {
+                        self.check_cluster_reconfiguration(ctx, reconfiguration)
+                            .await;
+                    }
+                    Ok(None) => {
+                        ctx.retire(Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster)));
+                    }
+                    Err(e) => ctx.retire(Err(e)),
+                }
             }
             Plan::AlterClusterRename(plan) => {
                 let result = self
@@ -514,6 +522,9 @@ impl Coordinator {
                 let result = self.sequence_reassign_owned(ctx.session_mut(), plan).await;
                 ctx.retire(result);
             }
+            Plan::Analyze(plan) => {
+                self.sequence_analyze(ctx, plan);
+            }
             Plan::ValidateConnection(plan) => {
                 let connection_context = self.connection_context.clone();
                 mz_ore::task::spawn(|| "coord::validate_connection", async move {
@@ -10,15 +10,19 @@
 //! Coordinator functionality to sequence cluster-related plans
 
 use std::collections::{BTreeMap, BTreeSet};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use anyhow::anyhow;
 use mz_compute_client::controller::ComputeReplicaConfig;
 use mz_controller::clusters::{
     ClusterId, CreateReplicaConfig, ReplicaConfig, ReplicaId, ReplicaLocation, ReplicaLogging,
     DEFAULT_REPLICA_LOGGING_INTERVAL_MICROS,
 };
 use mz_ore::cast::CastFrom;
+use mz_ore::str::StrExt;
+use mz_ore::task;
 use mz_repr::role_id::RoleId;
+use mz_repr::GlobalId;
 use mz_sql::catalog::{CatalogCluster, CatalogItem, CatalogItemType, ObjectType};
 use mz_sql::names::ObjectId;
 use mz_sql::plan::{
@@ -27,15 +31,24 @@ use mz_sql::plan::{
     CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant, PlanClusterOption,
 };
 use mz_sql::session::vars::{SystemVars, Var, MAX_REPLICAS_PER_CLUSTER};
+use mz_sql_parser::ast::{Ident, QualifiedReplica};
 use rand::seq::SliceRandom;
+use tracing::warn;
 
 use crate::catalog::{
     ClusterConfig, ClusterVariant, ClusterVariantManaged, Op, SerializedReplicaLocation,
 };
-use crate::coord::{Coordinator, DEFAULT_LOGICAL_COMPACTION_WINDOW_TS};
+use crate::coord::{
+    Coordinator, ExecuteContext, Message, PendingClusterReconfiguration,
+    DEFAULT_LOGICAL_COMPACTION_WINDOW_TS,
+};
 use crate::session::Session;
 use crate::{catalog, AdapterError, ExecuteResponse};
 
+/// How often to check whether the replicas a [`PendingClusterReconfiguration`] waits on have
+/// hydrated.
+const CLUSTER_RECONFIGURATION_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Helper to select availability zones based on the least-populated zone.
 pub struct AzHelper {
     n_replicas_per_az: BTreeMap<String, usize>,
@@ -570,7 +583,7 @@ impl Coordinator {
             name: _,
             options,
         }: AlterClusterPlan,
-    ) -> Result<ExecuteResponse, AdapterError> {
+    ) -> Result<Option<PendingClusterReconfiguration>, AdapterError> {
         use catalog::ClusterVariant::*;
 
         let config = self.catalog.get_cluster(cluster_id).config.clone();
@@ -691,15 +704,16 @@ impl Coordinator {
         }
 
         if new_config == config {
-            return Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster));
+            return Ok(None);
         }
 
         match (&config.variant, new_config.variant) {
             (Managed(config), Managed(new_config)) => {
-                self.sequence_alter_cluster_managed_to_managed(
-                    session, cluster_id, config, new_config,
-                )
-                .await?;
+                return self
+                    .sequence_alter_cluster_managed_to_managed(
+                        session, cluster_id, config, new_config,
+                    )
+                    .await;
             }
             (Unmanaged, Managed(new_config)) => {
                 self.sequence_alter_cluster_unmanaged_to_managed(
@@ -720,16 +734,21 @@ impl Coordinator {
             }
         }
 
-        Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster))
+        Ok(None)
     }
 
+    /// Alters the configuration of a managed cluster.
+    ///
+    /// If `enable_hydration_aware_cluster_alter` is set, replicas are not dropped right away.
+    /// Instead, this returns a reconfiguration that drops them once the replicas that replace
+    /// them have hydrated, see [`Coordinator::check_cluster_reconfiguration`].
     async fn sequence_alter_cluster_managed_to_managed(
         &mut self,
         session: &Session,
         cluster_id: ClusterId,
         config: &ClusterVariantManaged,
         new_config: ClusterVariantManaged,
-    ) -> Result<(), AdapterError> {
+    ) -> Result<Option<PendingClusterReconfiguration>, AdapterError> {
         let cluster = self.catalog.get_cluster(cluster_id);
         let name = cluster.name().to_string();
         let owner_id = cluster.owner_id();
@@ -793,28 +812,58 @@ impl Coordinator {
             )?;
         }
 
+        let hydration_aware = self
+            .catalog()
+            .system_config()
+            .enable_hydration_aware_cluster_alter();
+        // Operations to apply once the replicas in `await_replicas` have hydrated.
+        let mut deferred_ops = vec![];
+        let mut await_replicas = vec![];
+
         if new_size != size
             || new_availability_zones != availability_zones
             || new_idle_arrangement_merge_effort != idle_arrangement_merge_effort
             || new_logging != logging
             || new_disk != disk
         {
-            // tear down all replicas, create new ones
+            // Tear down all replicas, create new ones. If hydration-aware, the new replicas run
+            // alongside the old ones under temporary names until they have hydrated.
+            let drop_ops = if hydration_aware {
+                &mut deferred_ops
+            } else {
+                &mut ops
+            };
             for name in (0..*replication_factor).map(managed_cluster_replica_name) {
                 let replica = cluster.replica_id_by_name.get(&name);
                 if let Some(replica) = replica {
-                    ops.push(catalog::Op::DropObject(ObjectId::ClusterReplica((
+                    drop_ops.push(catalog::Op::DropObject(ObjectId::ClusterReplica((
                         cluster.id(),
                         *replica,
                     ))))
                 }
             }
-            for name in (0..*new_replication_factor).map(managed_cluster_replica_name) {
+            for index in 0..*new_replication_factor {
                 let id = self.catalog_mut().allocate_replica_id().await?;
+                let replica_name = if hydration_aware {
+                    let pending_name = pending_managed_cluster_replica_name(index);
+                    deferred_ops.push(catalog::Op::RenameClusterReplica {
+                        cluster_id,
+                        replica_id: id,
+                        name: QualifiedReplica {
+                            cluster: Ident::new(name.clone()),
+                            replica: Ident::new(pending_name.clone()),
+                        },
+                        to_name: managed_cluster_replica_name(index),
+                    });
+                    await_replicas.push(id);
+                    pending_name
+                } else {
+                    managed_cluster_replica_name(index)
+                };
                 self.create_managed_cluster_replica_op(
                     cluster_id,
                     id,
-                    name,
+                    replica_name,
                     az_user_specified,
                     &compute,
                     new_size,
@@ -826,13 +875,23 @@ impl Coordinator {
                 create_cluster_replicas.push((cluster_id, id))
             }
         } else if new_replication_factor < replication_factor {
-            // Adjust size down
+            // Adjust size down. If hydration-aware, only once the remaining replicas have
+            // hydrated.
+            let drop_ops = if hydration_aware {
+                await_replicas = (0..*new_replication_factor)
+                    .map(managed_cluster_replica_name)
+                    .filter_map(|name| cluster.replica_id_by_name.get(&name).copied())
+                    .collect();
+                &mut deferred_ops
+            } else {
+                &mut ops
+            };
             for name in
                 (*new_replication_factor..*replication_factor).map(managed_cluster_replica_name)
             {
                 let replica = cluster.replica_id_by_name.get(&name);
                 if let Some(replica) = replica {
-                    ops.push(catalog::Op::DropObject(ObjectId::ClusterReplica((
+                    drop_ops.push(catalog::Op::DropObject(ObjectId::ClusterReplica((
                         cluster.id(),
                         *replica,
                     ))))
@@ -870,15 +929,182 @@ impl Coordinator {
         }
 
         let variant = ClusterVariant::Managed(new_config);
-        ops.push(catalog::Op::UpdateClusterConfig {
+        let update_config = catalog::Op::UpdateClusterConfig {
             id: cluster_id,
             name,
             config: ClusterConfig { variant },
+        };
+
+        if !hydration_aware {
+            ops.push(update_config);
+            self.catalog_transact(Some(session), ops).await?;
+            self.create_cluster_replicas(&create_cluster_replicas).await;
+            return Ok(None);
+        }
+
+        deferred_ops.push(update_config);
+        if !ops.is_empty() {
+            self.catalog_transact(Some(session), ops).await?;
+            self.create_cluster_replicas(&create_cluster_replicas).await;
+        }
+        let timeout = self
+            .catalog()
+            .system_config()
+            .cluster_alter_hydration_timeout();
+        Ok(Some(PendingClusterReconfiguration {
+            cluster_id,
+            replicas: await_replicas,
+            ops: deferred_ops,
+            created_replicas: create_cluster_replicas
+                .into_iter()
+                .map(|(_, id)| id)
+                .collect(),
+            deadline: Instant::now() + timeout,
+        }))
+    }
+
+    /// Applies `reconfiguration` if its replicas have hydrated, abandons it if its deadline
+    /// has passed, and otherwise checks again later.
+    ///
+    /// The client is responded to once the reconfiguration is applied or abandoned.
+    pub(crate) async fn check_cluster_reconfiguration(
+        &mut self,
+        ctx: ExecuteContext,
+        reconfiguration: PendingClusterReconfiguration,
+    ) {
+        let cluster_id = reconfiguration.cluster_id;
+        let Some(cluster) = self.catalog().try_get_cluster(cluster_id) else {
+            ctx.retire(Err(AdapterError::Unstructured(anyhow!(
+                "cluster was dropped while it was being altered"
+            ))));
+            return;
+        };
+        if let Some(replica_id) = reconfiguration
+            .replicas
+            .iter()
+            .find(|id| !cluster.replicas_by_id.contains_key(id))
+        {
+            let cluster_name = cluster.name().to_string();
+            let drop_ops = self.abandon_cluster_reconfiguration_ops(&reconfiguration);
+            let result = self.catalog_transact(Some(ctx.session()), drop_ops).await;
+            ctx.retire(result.and(Err(AdapterError::Unstructured(anyhow!(
+                "replica {replica_id} of cluster {} was dropped while the cluster was being altered",
+                cluster_name.quoted(),
+            )))));
+            return;
+        }
+
+        let Some(unhydrated) = self.unhydrated_object(cluster_id, &reconfiguration.replicas) else {
+            let result = self
+                .catalog_transact(Some(ctx.session()), reconfiguration.ops)
+                .await
+                .map(|()| ExecuteResponse::AlteredObject(ObjectType::Cluster));
+            ctx.retire(result);
+            return;
+        };
+
+        if Instant::now() >= reconfiguration.deadline {
+            let cluster_name = cluster.name().to_string();
+            let object_name = self
+                .catalog()
+                .resolve_full_name(
+                    self.catalog().get_entry(&unhydrated).name(),
+                    Some(ctx.session().conn_id()),
+                )
+                .to_string();
+            let drop_ops = self.abandon_cluster_reconfiguration_ops(&reconfiguration);
+            let result = self.catalog_transact(Some(ctx.session()), drop_ops).await;
+            ctx.retire(result.and(Err(AdapterError::ClusterReplicasNotHydrated {
+                cluster_name,
+                object_name,
+            })));
+            return;
+        }
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "cluster_reconfiguration_check", async move {
+            tokio::time::sleep(CLUSTER_RECONFIGURATION_CHECK_INTERVAL).await;
+            let result = internal_cmd_tx.send(Message::ClusterReconfigurationCheck {
+                ctx,
+                reconfiguration,
+            });
+            if let Err(e) = result {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
         });
+    }
 
-        self.catalog_transact(Some(session), ops).await?;
-        self.create_cluster_replicas(&create_cluster_replicas).await;
-        Ok(())
+    /// Abandons the reconfigurations of managed clusters that were still waiting for their
+    /// replicas to hydrate when the coordinator last shut down.
+    ///
+    /// A pending reconfiguration only lives in the coordinator's memory, while the replicas it
+    /// created are durable. Nothing of the new configuration has been applied yet, so the
+    /// clusters are left in their old configuration by dropping the pending replicas.
+    pub(crate) async fn drop_pending_cluster_replicas(&mut self) -> Result<(), AdapterError> {
+        let ops: Vec<_> = self
+            .catalog()
+            .clusters()
+            .filter(|cluster| cluster.is_managed())
+            .flat_map(|cluster| {
+                cluster
+                    .replicas_by_id
+                    .values()
+                    .filter(|replica| is_pending_managed_cluster_replica_name(&replica.name))
+                    .map(|replica| {
+                        Op::DropObject(ObjectId::ClusterReplica((cluster.id(), replica.replica_id)))
+                    })
+            })
+            .collect();
+        if ops.is_empty() {
+            return Ok(());
+        }
+        warn!(
+            "abandoning interrupted cluster reconfigurations, dropping {} pending replicas",
+            ops.len()
+        );
+        self.catalog_transact(None, ops).await
+    }
+
+    /// Returns the operations that drop the replicas `reconfiguration` created and that
+    /// still exist.
+    fn abandon_cluster_reconfiguration_ops(
+        &self,
+        reconfiguration: &PendingClusterReconfiguration,
+    ) -> Vec<Op> {
+        let cluster_id = reconfiguration.cluster_id;
+        let Some(cluster) = self.catalog().try_get_cluster(cluster_id) else {
+            return vec![];
+        };
+        reconfiguration
+            .created_replicas
+            .iter()
+            .filter(|id| cluster.replicas_by_id.contains_key(id))
+            .map(|id| Op::DropObject(ObjectId::ClusterReplica((cluster_id, *id))))
+            .collect()
+    }
+
+    /// Returns an index or materialized view maintained by the cluster that is not hydrated on
+    /// any of the given `replicas`, if one exists.
+    ///
+    /// An empty set of `replicas` is always considered hydrated, as the objects cannot be served
+    /// by any replica then.
+    fn unhydrated_object(&self, cluster_id: ClusterId, replicas: &[ReplicaId]) -> Option<GlobalId> {
+        if replicas.is_empty() {
+            return None;
+        }
+
+        let cluster = self.catalog().get_cluster(cluster_id);
+        cluster.bound_objects().iter().copied().find(|id| {
+            let entry = self.catalog().get_entry(id);
+            if !entry.is_index() && !entry.is_materialized_view() {
+                return false;
+            }
+            !replicas.iter().any(|replica_id| {
+                self.hydration_statuses
+                    .get(&(*id, *replica_id))
+                    .map_or(false, |status| status.hydrated)
+            })
+        })
     }
 
     async fn sequence_alter_cluster_unmanaged_to_managed(
@@ -1086,3 +1312,17 @@ impl Coordinator {
 fn managed_cluster_replica_name(index: u32) -> String {
     format!("r{}", index + 1)
 }
+
+/// The name of a managed cluster replica that is hydrating before it replaces the replica
+/// named [`managed_cluster_replica_name`].
+fn pending_managed_cluster_replica_name(index: u32) -> String {
+    format!("r{}-pending", index + 1)
+}
+
+/// Whether `name` is the name of a managed cluster replica that is hydrating, see
+/// [`pending_managed_cluster_replica_name`].
+fn is_pending_managed_cluster_replica_name(name: &str) -> bool {
+    name.strip_prefix('r')
+        .and_then(|name| name.strip_suffix("-pending"))
+        .map_or(false, |index| index.parse::<u32>().is_ok())
+}
@@ -17,6 +17,7 @@ use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
 use futures::future::BoxFuture;
+use futures::StreamExt;
 use itertools::Itertools;
 use maplit::btreeset;
 use mz_cloud_resources::VpcEndpointConfig;
@@ -26,9 +27,10 @@ use mz_compute_client::types::sinks::{
 };
 use mz_controller::clusters::{ClusterId, ReplicaId};
 use mz_expr::{
-    permutation_for_arrangement, CollectionPlan, MirRelationExpr, MirScalarExpr,
+    permutation_for_arrangement, CollectionPlan, MapFilterProject, MirRelationExpr, MirScalarExpr,
     OptimizedMirRelationExpr, RowSetFinishing,
 };
+use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_ore::result::ResultExt as OreResultExt;
 use mz_ore::task;
@@ -36,9 +38,11 @@ use mz_ore::tracing::OpenTelemetryContext;
 use mz_ore::vec::VecExt;
 use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::adt::mz_acl_item::{MzAclItem, PrivilegeMap};
-use mz_repr::explain::{ExplainFormat, Explainee};
+use mz_repr::explain::{ExplainFormat, Explainee, RecommendedIndex};
 use mz_repr::role_id::RoleId;
-use mz_repr::{Datum, Diff, GlobalId, RelationDesc, RelationType, Row, RowArena, Timestamp};
+use mz_repr::{
+    Datum, Diff, GlobalId, RelationDesc, RelationType, Row, RowArena, ScalarType, Timestamp,
+};
 use mz_sql::ast::{ExplainStage, IndexOptionName};
 use mz_sql::catalog::{
     CatalogCluster, CatalogClusterReplica, CatalogDatabase, CatalogError,
@@ -68,6 +72,7 @@ use mz_sql_parser::ast::{
 use mz_ssh_util::keys::SshKeyPairSet;
 use mz_storage_client::controller::{CollectionDescription, DataSource, ReadPolicy, StorageError};
 use mz_storage_client::types::sinks::StorageSinkConnectionBuilder;
+use mz_transform::statistics::{CollectionStatistics, StatisticsBuilder};
 use mz_transform::{EmptyStatisticsOracle, Optimizer, StatisticsOracle};
 use timely::progress::{Antichain, Timestamp as TimelyTimestamp};
 use tokio::sync::{mpsc, oneshot, OwnedMutexGuard};
@@ -86,23 +91,27 @@ use crate::coord::dataflows::{
 };
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::peek::{FastPathPlan, PlannedPeek};
-use crate::coord::read_policy::SINCE_GRANULARITY;
+use crate::coord::read_policy::{ReadHolds, SINCE_GRANULARITY};
 use crate::coord::timeline::TimelineContext;
 use crate::coord::timestamp_selection::{
     TimestampContext, TimestampDetermination, TimestampProvider, TimestampSource,
 };
 use crate::coord::{
-    peek, Coordinator, CreateConnectionValidationReady, ExecuteContext, Message, PeekStage,
-    PeekStageFinish, PeekStageOptimize, PeekStageTimestamp, PeekStageValidate, PendingRead,
-    PendingReadTxn, PendingTxn, PendingTxnResponse, PlanValidity, RealTimeRecencyContext,
-    SinkConnectionReady, TargetCluster, DEFAULT_LOGICAL_COMPACTION_WINDOW_TS,
+    peek, AnalyzeReady, Coordinator, CreateConnectionValidationReady, ExecuteContext, Message,
+    PeekStage, PeekStageFinish, PeekStageOptimize, PeekStageTimestamp, PeekStageValidate,
+    PendingRead, PendingReadTxn, PendingTxn, PendingTxnResponse, PlanValidity,
+    RealTimeRecencyContext, SinkConnectionReady, TargetCluster,
+    DEFAULT_LOGICAL_COMPACTION_WINDOW_TS,
 };
 use crate::error::AdapterError;
 use crate::explain::optimizer_trace::OptimizerTrace;
 use crate::notice::AdapterNotice;
 use crate::rbac::{self, is_rbac_enabled_for_session};
 use crate::session::{EndTransactionAction, Session, TransactionOps, TransactionStatus, WriteOp};
-use crate::subscribe::ActiveSubscribe;
+use crate::subscribe::{
+    spawn_subscribe_forwarder, ActiveSubscribe, RetainedSubscribe, SubscribeAcknowledger,
+    SubscribeDeliveries, DEFAULT_RETAINED_SUBSCRIBE_RETENTION,
+};
 use crate::util::{
     send_immediate_rows, viewable_variables, ClientTransmitter, ComputeSinkId, ResultExt,
 };
@@ -533,6 +542,7 @@ impl Coordinator {
             resolved_ids,
             custom_logical_compaction_window: None,
             is_retained_metrics_object: false,
+            append_only: table.append_only,
         };
         let table_oid = self.catalog_mut().allocate_oid()?;
         let ops = vec![catalog::Op::CreateItem {
@@ -917,6 +927,7 @@ impl Coordinator {
                     expr: view_expr,
                     column_names,
                     cluster_id,
+                    on_error,
                 },
             replace: _,
             drop_ids,
@@ -965,14 +976,47 @@ impl Coordinator {
         let internal_view_id = self.allocate_transient_id()?;
 
         let optimized_expr = self.view_optimizer.optimize(view_expr)?;
-        let desc = RelationDesc::new(optimized_expr.typ(), column_names);
+        let mut typ = optimized_expr.typ();
+        if let Some(on_error) = &on_error {
+            // The errors of the view's final map, filter, and project are quarantined by its
+            // persist sink, which cannot apply temporal filters.
+            let (mfp, _) = MapFilterProject::extract_non_errors_from_expr(&optimized_expr);
+            if !matches!(
+                mfp.into_plan().map(|plan| plan.into_nontemporal()),
+                Ok(Ok(_))
+            ) {
+                return Err(AdapterError::Unsupported(
+                    "ON ERROR for materialized views with temporal filters",
+                ));
+            }
+            on_error.adjust_type(&mut typ);
+        }
+        let desc = RelationDesc::new(typ, column_names);
+
+        // The dataflow may read indexes and materialized views that hold the contents of its
+        // shared subplans. Objects that are replaced by the view cannot be read.
+        let mut shared_subplan_ids = self
+            .dataflow_builder(cluster_id)
+            .view_shared_subplan_imports(internal_view_id, &optimized_expr, &resolved_ids)?;
+        shared_subplan_ids.retain(|id| !drop_ids.contains(id));
 
         // Pick the least valid read timestamp as the as-of for the view
         // dataflow. This makes the materialized view include the maximum possible
         // amount of historical detail.
-        let id_bundle = self
+        let mut id_bundle = self
             .index_oracle(cluster_id)
             .sufficient_collections(&expr_depends_on);
+        for id in shared_subplan_ids {
+            if self.catalog().get_entry(&id).is_index() {
+                id_bundle
+                    .compute_ids
+                    .entry(cluster_id)
+                    .or_default()
+                    .insert(id);
+            } else {
+                id_bundle.storage_ids.insert(id);
+            }
+        }
         let as_of = self.least_valid_read(&id_bundle);
 
         let mut ops = Vec::new();
@@ -991,6 +1035,7 @@ impl Coordinator {
                 desc: desc.clone(),
                 resolved_ids,
                 cluster_id,
+                on_error,
             }),
             owner_id: *session.current_role_id(),
         });
@@ -1850,11 +1895,23 @@ impl Coordinator {
         if let EndTransactionAction::Commit = action {
             if let (Some(mut ops), write_lock_guard) = txn.into_ops_and_lock_guard() {
                 if let TransactionOps::Writes(writes) = &mut ops {
-                    for WriteOp { id, .. } in &mut writes.iter() {
+                    for WriteOp { id, rows } in &mut writes.iter() {
                         // Re-verify this id exists.
-                        let _ = self.catalog().try_get_entry(id).ok_or_else(|| {
+                        let entry = self.catalog().try_get_entry(id).ok_or_else(|| {
                             AdapterError::SqlCatalog(CatalogError::UnknownItem(id.to_string()))
                         })?;
+
+                        // Append-only tables admit only insertions.
+                        if let CatalogItem::Table(table) = entry.item() {
+                            if table.append_only && rows.iter().any(|(_, diff)| *diff < 0) {
+                                let name = self
+                                    .catalog()
+                                    .resolve_full_name(entry.name(), Some(session.conn_id()));
+                                return Err(AdapterError::AppendOnlyTableRetraction(
+                                    name.to_string(),
+                                ));
+                            }
+                        }
                     }
 
                     // `rows` can be empty if, say, a DELETE's WHERE clause had 0 results.
@@ -2195,6 +2252,8 @@ impl Coordinator {
             typ,
         }: PeekStageTimestamp,
     ) -> Option<(ExecuteContext, PeekStageFinish)> {
+        self.observe_index_advice(cluster_id, &dataflow);
+
         match self.recent_timestamp(ctx.session(), source_ids.iter().cloned()) {
             Some(fut) => {
                 let internal_cmd_tx = self.internal_cmd_tx.clone();
@@ -2538,8 +2597,60 @@ impl Coordinator {
             emit_progress,
             up_to,
             output,
+            resumable,
+            batch_size,
+            emit_interval,
+            max_pending_rows,
         } = plan;
 
+        // A resumed subscribe continues at the point up to which the client
+        // has received the original subscribe's updates, or at the point the
+        // client chose.
+        let (when, with_snapshot) = match &resumable {
+            Some(resumable) if resumable.resume => {
+                let retained = self
+                    .retained_subscribes
+                    .get(&resumable.name)
+                    .filter(|retained| retained.user == session.user().name)
+                    .ok_or_else(|| AdapterError::UnknownSubscribe(resumable.name.clone()))?;
+                let active = retained
+                    .sink
+                    .and_then(|sink_id| self.active_subscribes.get(&sink_id));
+                if active.map_or(false, |active| !active.dropping) {
+                    return Err(AdapterError::SubscribeInUse(resumable.name.clone()));
+                }
+                if retained.from != from {
+                    return Err(AdapterError::SubscribeResumeMismatch(
+                        resumable.name.clone(),
+                    ));
+                }
+                let (resume_as_of, with_snapshot) = match resumable.resume_at.clone() {
+                    Some(resume_at) => {
+                        let resume_at =
+                            Coordinator::evaluate_when(self.catalog().state(), resume_at, session)?;
+                        retained.resume_at(resume_at).ok_or_else(|| {
+                            AdapterError::SubscribeResumeUnavailable {
+                                name: resumable.name.clone(),
+                                at: resume_at,
+                                earliest: retained.resume_as_of.step_forward(),
+                            }
+                        })?
+                    }
+                    None => (retained.resume_as_of, retained.with_snapshot),
+                };
+                let when = QueryWhen::AtTimestamp(MirScalarExpr::literal_ok(
+                    Datum::MzTimestamp(resume_as_of),
+                    ScalarType::MzTimestamp,
+                ));
+                (when, with_snapshot)
+            }
+            Some(resumable) if self.retained_subscribes.contains_key(&resumable.name) => {
+                return Err(AdapterError::DuplicateSubscribe(resumable.name.clone()));
+            }
+            _ => (when, with_snapshot),
+        };
+        let retained_from = resumable.as_ref().map(|_| from.clone());
+
         let cluster = self
             .catalog()
             .resolve_target_cluster(target_cluster, session)?;
@@ -2593,7 +2704,11 @@ impl Coordinator {
             Ok::<_, AdapterError>(ComputeSinkDesc {
                 from,
                 from_desc,
-                connection: ComputeSinkConnection::Subscribe(SubscribeSinkConnection::default()),
+                connection: ComputeSinkConnection::Subscribe(SubscribeSinkConnection {
+                    emit_interval: emit_interval
+                        .map(|interval| u64::try_from(interval.as_millis()).unwrap_or(u64::MAX)),
+                    max_pending_rows,
+                }),
                 with_snapshot,
                 up_to,
             })
@@ -2636,8 +2751,23 @@ impl Coordinator {
             .iter()
             .next()
             .expect("subscribes have a single sink export");
+        // Responses are forwarded to the client as it asks for them, so
+        // that flow-controlled subscribes learn which responses the client
+        // has received. Named subscribes learn which responses the client
+        // has acknowledged.
         let (tx, rx) = mpsc::unbounded_channel();
-        let active_subscribe = ActiveSubscribe {
+        let (client_tx, client_rx) = mpsc::channel(1);
+        let flow_control = max_pending_rows.is_some();
+        spawn_subscribe_forwarder(
+            sink_id,
+            rx,
+            client_tx,
+            flow_control.then(|| self.internal_cmd_tx.clone()),
+        );
+        let acknowledger = resumable
+            .as_ref()
+            .map(|_| SubscribeAcknowledger::new(sink_id, self.internal_cmd_tx.clone()));
+        let mut active_subscribe = ActiveSubscribe {
             user: session.user().clone(),
             conn_id: session.conn_id().clone(),
             channel: tx,
@@ -2649,6 +2779,11 @@ impl Coordinator {
             start_time: self.now(),
             dropping: false,
             output,
+            name: resumable.as_ref().map(|resumable| resumable.name.clone()),
+            batch_size: batch_size.map(usize::cast_from),
+            sent: 0,
+            deliveries: flow_control.then(SubscribeDeliveries::default),
+            acknowledgements: resumable.as_ref().map(|_| SubscribeDeliveries::default()),
         };
         active_subscribe.initialize();
         self.add_active_subscribe(sink_id, active_subscribe).await;
@@ -2660,6 +2795,63 @@ impl Coordinator {
                 return Err(e);
             }
         };
+        if flow_control {
+            // The sink's read frontier acknowledges the batches the client
+            // has received, so it only advances as the client keeps up.
+            self.update_compute_base_read_policy(
+                cluster_id,
+                sink_id,
+                ReadPolicy::ValidFrom(Antichain::from_elem(as_of)),
+            );
+        }
+        if let (Some(resumable), Some(from)) = (resumable, retained_from) {
+            let read_holds = self.acquire_read_holds(as_of, &id_bundle);
+            let name = resumable.name;
+            let retention = resumable.retention;
+            let mut updates = Vec::new();
+            if let Some(retained) = self.retained_subscribes.get(&name) {
+                updates.push(
+                    self.catalog()
+                        .state()
+                        .pack_retained_subscribe_update(&name, retained, -1),
+                );
+            }
+            let retained = self
+                .retained_subscribes
+                .entry(name.clone())
+                .or_insert_with(|| RetainedSubscribe {
+                    user: session.user().name.clone(),
+                    cluster_id,
+                    from,
+                    read_holds: ReadHolds::new(),
+                    resume_as_of: as_of,
+                    with_snapshot,
+                    retention: retention.unwrap_or(DEFAULT_RETAINED_SUBSCRIBE_RETENTION),
+                    sink: None,
+                    acknowledgements: None,
+                    expires_at: None,
+                });
+            retained.cluster_id = cluster_id;
+            retained.sink = Some(sink_id);
+            retained.acknowledgements = None;
+            retained.expires_at = None;
+            retained.resume_as_of = as_of;
+            retained.with_snapshot = with_snapshot;
+            if let Some(retention) = retention {
+                retained.retention = retention;
+            }
+            // Holds of a resumed subscribe are replaced by the ones for its
+            // new inputs only after those have been acquired, and the new
+            // resume point has been recorded.
+            let prev_read_holds = std::mem::replace(&mut retained.read_holds, read_holds);
+            updates.push(self.catalog().state().pack_retained_subscribe_update(
+                &name,
+                &self.retained_subscribes[&name],
+                1,
+            ));
+            self.send_builtin_table_updates(updates).await;
+            self.release_read_hold(&prev_read_holds);
+        }
         if let Some(target) = target_replica {
             self.controller
                 .compute
@@ -2676,7 +2868,10 @@ impl Coordinator {
                 global_id: sink_id,
             });
 
-        let resp = ExecuteResponse::Subscribing { rx };
+        let resp = ExecuteResponse::Subscribing {
+            rx: client_rx,
+            acknowledger,
+        };
         match copy_to {
             None => Ok(resp),
             Some(format) => Ok(ExecuteResponse::CopyTo {
@@ -2743,28 +2938,39 @@ impl Coordinator {
                 .await
         };
 
-        let (used_indexes, fast_path_plan) = match pipeline_result {
-            Ok((used_indexes, fast_path_plan)) => (used_indexes, fast_path_plan),
+        let (used_indexes, recommended_indexes, fast_path_plan) = match pipeline_result {
+            Ok((used_indexes, recommended_indexes, fast_path_plan)) => {
+                (used_indexes, recommended_indexes, fast_path_plan)
+            }
             Err(err) => {
                 if no_errors {
                     tracing::error!("error while handling EXPLAIN statement: {}", err);
 
                     let used_indexes: Vec<GlobalId> = vec![];
+                    let recommended_indexes: Vec<RecommendedIndex> = vec![];
                     let fast_path_plan: Option<FastPathPlan> = None;
 
-                    (used_indexes, fast_path_plan)
+                    (used_indexes, recommended_indexes, fast_path_plan)
                 } else {
                     return Err(err);
                 }
             }
         };
 
+        // Recommended indexes are only shown on request.
+        let recommended_indexes = if config.recommended_indexes {
+            recommended_indexes
+        } else {
+            vec![]
+        };
+
         let trace = optimizer_trace.drain_all(
             format,
             config,
             self.catalog().for_session(session),
             row_set_finishing,
             used_indexes,
+            recommended_indexes,
             fast_path_plan,
         )?;
 
@@ -2815,7 +3021,7 @@ impl Coordinator {
         no_errors: bool,
         cluster_id: mz_storage_client::types::instances::StorageInstanceId,
         session: &mut Session,
-    ) -> Result<(Vec<GlobalId>, Option<FastPathPlan>), AdapterError> {
+    ) -> Result<(Vec<GlobalId>, Vec<RecommendedIndex>, Option<FastPathPlan>), AdapterError> {
         use mz_repr::explain::trace_plan;
 
         /// Like [`mz_ore::panic::catch_unwind`], with an extra guard that must be true
@@ -2854,7 +3060,7 @@ impl Coordinator {
 
         // Execute the `optimize/hir_to_mir` stage.
         let decorrelated_plan = catch_unwind(no_errors, "hir_to_mir", || {
-            raw_plan.optimize_and_lower(&OptimizerConfig {})
+            raw_plan.optimize_and_lower(&OptimizerConfig::from(self.catalog().system_config()))
         })?;
 
         let mut timeline_context =
@@ -2936,6 +3142,22 @@ impl Coordinator {
             .cloned()
             .collect::<Vec<GlobalId>>();
 
+        // Calculate indexes the dataflow would use if they existed
+        let recommended_indexes = mz_transform::index_advice::dataflow_index_advice(
+            &dataflow,
+            &self.index_oracle(cluster_id),
+        )
+        .into_iter()
+        .filter_map(|advice| {
+            let key = self.index_advice_key_text(&advice)?;
+            Some(RecommendedIndex {
+                on_id: advice.on_id,
+                key,
+                reason: advice.reason.to_string(),
+            })
+        })
+        .collect::<Vec<_>>();
+
         // Determine if fast path plan will be used for this explainee
         let fast_path_plan = match explainee {
             Explainee::Query => {
@@ -2976,7 +3198,7 @@ impl Coordinator {
 
         // Return objects that need to be passed to the `ExplainContext`
         // when rendering explanations for the various trace entries.
-        Ok((used_indexes, fast_path_plan))
+        Ok((used_indexes, recommended_indexes, fast_path_plan))
     }
 
     fn sequence_explain_timestamp_begin(
@@ -3053,7 +3275,8 @@ impl Coordinator {
             raw_plan, format, ..
         } = plan;
 
-        let decorrelated_plan = raw_plan.optimize_and_lower(&OptimizerConfig {})?;
+        let decorrelated_plan =
+            raw_plan.optimize_and_lower(&OptimizerConfig::from(self.catalog().system_config()))?;
         let optimized_plan = self.view_optimizer.optimize(decorrelated_plan)?;
         let source_ids = optimized_plan.depends_on();
         let cluster = self.catalog().active_cluster(session)?;
@@ -4881,11 +5104,85 @@ impl Coordinator {
             .await
             .map(|_| ExecuteResponse::ReassignOwned)
     }
+
+    /// Collects statistics about the contents of a collection in the
+    /// background, from a snapshot of the collection at its latest readable
+    /// time.
+    ///
+    /// The snapshot is streamed from persist into a [`StatisticsBuilder`], so
+    /// that it is never held in memory, while a read hold keeps it readable.
+    #[tracing::instrument(level = "debug", skip(self, ctx))]
+    pub(super) fn sequence_analyze(&mut self, ctx: ExecuteContext, plan: plan::AnalyzePlan) {
+        let id = plan.id;
+        let collection = return_if_err!(self.controller.storage.collection(id), ctx);
+        let upper = Coordinator::largest_not_in_advance_of_upper(&collection.write_frontier);
+        let as_of = match collection.implied_capability.as_option() {
+            Some(since) => std::cmp::max(upper, *since),
+            None => upper,
+        };
+        let arity = collection.collection_metadata.relation_desc.arity();
+        // Size the sample for the collection as it was last analyzed.
+        let expected_rows = self
+            .collection_statistics
+            .get(&id)
+            .map_or(0, |(statistics, _)| statistics.row_count);
+
+        let id_bundle = CollectionIdBundle {
+            storage_ids: btreeset![id],
+            compute_ids: BTreeMap::new(),
+        };
+        let read_holds = self.acquire_read_holds(as_of, &id_bundle);
+        let mut snapshot = match self.controller.storage.snapshot_stream(id, as_of) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                self.release_read_hold(&read_holds);
+                return ctx.retire(Err(e.into()));
+            }
+        };
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let transient_revision = self.catalog().transient_revision();
+        let otel_ctx = OpenTelemetryContext::obtain();
+        task::spawn(|| format!("analyze:{id}"), async move {
+            let mut builder = StatisticsBuilder::new(arity, expected_rows);
+            let mut result = Ok(());
+            while let Some(update) = snapshot.next().await {
+                match update {
+                    Ok((row, diff)) => builder.push(&row, diff),
+                    Err(err) => {
+                        result = Err(err.into());
+                        break;
+                    }
+                }
+            }
+            let result = result.map(|()| builder.finish());
+
+            // It is not an error for the analysis to complete after `internal_cmd_rx` is dropped.
+            let result = internal_cmd_tx.send(Message::AnalyzeReady(AnalyzeReady {
+                ctx,
+                id,
+                result,
+                read_holds,
+                plan_validity: PlanValidity {
+                    transient_revision,
+                    dependency_ids: btreeset![id],
+                    cluster_id: None,
+                    replica_id: None,
+                },
+                otel_ctx,
+            }));
+            if let Err(e) = result {
+                tracing::warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
 }
 
 #[derive(Debug)]
 struct CachedStatisticsOracle {
     cache: BTreeMap<GlobalId, usize>,
+    /// The statistics last collected by `ANALYZE`, for the collections that have been analyzed.
+    statistics: BTreeMap<GlobalId, Arc<CollectionStatistics>>,
 }
 
 const OPTIMIZER_MAX_STATS_WAIT: Duration = Duration::from_millis(250);
@@ -4896,6 +5193,7 @@ impl CachedStatisticsOracle {
         ids: &BTreeSet<GlobalId>,
         as_of: &Antichain<T>,
         storage: &dyn mz_storage_client::controller::StorageController<Timestamp = T>,
+        statistics: BTreeMap<GlobalId, Arc<CollectionStatistics>>,
     ) -> Result<Self, StorageError> {
         let mut cache = BTreeMap::new();
 
@@ -4920,7 +5218,7 @@ impl CachedStatisticsOracle {
             }
         }
 
-        Ok(Self { cache })
+        Ok(Self { cache, statistics })
     }
 }
 
@@ -4928,6 +5226,10 @@ impl mz_transform::StatisticsOracle for CachedStatisticsOracle {
     fn cardinality_estimate(&self, id: GlobalId) -> Option<usize> {
         self.cache.get(&id).map(|estimate| *estimate)
     }
+
+    fn collection_statistics(&self, id: GlobalId) -> Option<Arc<CollectionStatistics>> {
+        self.statistics.get(&id).cloned()
+    }
 }
 
 impl Coordinator {
@@ -4949,16 +5251,35 @@ impl Coordinator {
             OPTIMIZER_MAX_STATS_WAIT
         };
 
+        let statistics: BTreeMap<_, _> = source_ids
+            .iter()
+            .filter_map(|id| {
+                self.collection_statistics
+                    .get(id)
+                    .map(|(statistics, _)| (*id, Arc::clone(statistics)))
+            })
+            .collect();
+
         let cached_stats = mz_ore::future::timeout(
             timeout,
-            CachedStatisticsOracle::new(source_ids, &query_as_of, self.controller.storage.as_ref()),
+            CachedStatisticsOracle::new(
+                source_ids,
+                &query_as_of,
+                self.controller.storage.as_ref(),
+                statistics.clone(),
+            ),
         )
         .await;
 
         match cached_stats {
             Ok(stats) => Ok(Box::new(stats)),
             Err(mz_ore::future::TimeoutError::DeadlineElapsed) => {
-                Ok(Box::new(EmptyStatisticsOracle))
+                // Statistics collected by `ANALYZE` are available without
+                // waiting on storage, so don't discard them.
+                Ok(Box::new(CachedStatisticsOracle {
+                    cache: BTreeMap::new(),
+                    statistics,
+                }))
             }
             Err(mz_ore::future::TimeoutError::Inner(e)) => Err(AdapterError::Storage(e)),
         }
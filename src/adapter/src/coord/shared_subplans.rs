@@ -0,0 +1,283 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Detection of subplans that are shared across the dataflows of a cluster.
+//!
+//! Every dataflow that the coordinator installs for an index or materialized view is inspected
+//! for subplans that are worth maintaining once (see
+//! [`mz_transform::dataflow::shareable_subplans`]). Subplans that are computed by the dataflows
+//! of at least two objects on the same cluster are periodically published to
+//! `mz_internal.mz_shared_subplans`, so that they can be extracted into an indexed or
+//! materialized view. When `enable_subplan_sharing` is on, indexes and materialized views created
+//! afterwards read such views instead of recomputing their definitions, and depend on them.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use mz_compute_client::types::dataflows::DataflowDesc;
+use mz_controller::clusters::ClusterId;
+use mz_expr::MirRelationExpr;
+use mz_repr::GlobalId;
+use tracing::warn;
+
+use crate::catalog::CatalogState;
+use crate::coord::published_snapshot::PublishedSnapshot;
+use crate::coord::{Coordinator, Message};
+
+/// The interval at which shared subplans are published.
+const SHARED_SUBPLANS_PUBLISH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A subplan that is computed by the dataflow of more than one object on a cluster.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SharedSubplanEntry {
+    /// The cluster on which the subplan is computed.
+    pub cluster_id: ClusterId,
+    /// An identifier of the subplan that is unique within the cluster.
+    pub subplan_id: u64,
+    /// An object whose dataflow computes the subplan.
+    pub object_id: GlobalId,
+    /// The subplan, rendered as text.
+    pub plan: String,
+}
+
+/// Tracks the shareable subplans of the dataflows installed on each cluster.
+#[derive(Debug, Default)]
+pub struct SharedSubplanTracker {
+    /// The shareable subplans of each installed dataflow, keyed by cluster and by the objects
+    /// that the dataflow exports.
+    dataflows: BTreeMap<(ClusterId, GlobalId), BTreeSet<MirRelationExpr>>,
+    /// The entries last written to `mz_shared_subplans`.
+    published: PublishedSnapshot<SharedSubplanEntry>,
+}
+
+impl SharedSubplanTracker {
+    /// Records that the dataflow exporting `object_id` on `cluster_id` computes `subplans`.
+    pub fn observe(
+        &mut self,
+        cluster_id: ClusterId,
+        object_id: GlobalId,
+        subplans: BTreeSet<MirRelationExpr>,
+    ) {
+        if subplans.is_empty() {
+            self.dataflows.remove(&(cluster_id, object_id));
+        } else {
+            self.dataflows.insert((cluster_id, object_id), subplans);
+        }
+    }
+
+    /// Forgets all dataflows for which `f` returns `false`.
+    pub fn retain(&mut self, mut f: impl FnMut(ClusterId, GlobalId) -> bool) {
+        self.dataflows
+            .retain(|(cluster_id, object_id), _| f(*cluster_id, *object_id));
+    }
+
+    /// Returns the subplans that are computed by the dataflows of at least two objects on the
+    /// same cluster.
+    ///
+    /// A shared subplan is omitted if it is contained in a larger subplan that is shared by the
+    /// same objects.
+    fn entries(&self) -> Vec<SharedSubplanEntry> {
+        let mut by_cluster: BTreeMap<ClusterId, BTreeMap<&MirRelationExpr, BTreeSet<GlobalId>>> =
+            BTreeMap::new();
+        for ((cluster_id, object_id), subplans) in &self.dataflows {
+            let cluster = by_cluster.entry(*cluster_id).or_default();
+            for subplan in subplans {
+                cluster.entry(subplan).or_default().insert(*object_id);
+            }
+        }
+
+        let mut entries = Vec::new();
+        for (cluster_id, subplans) in by_cluster {
+            let mut by_objects: BTreeMap<BTreeSet<GlobalId>, BTreeMap<&MirRelationExpr, bool>> =
+                BTreeMap::new();
+            for (subplan, objects) in subplans {
+                if objects.len() > 1 {
+                    by_objects
+                        .entry(objects)
+                        .or_default()
+                        .insert(subplan, false);
+                }
+            }
+            for (objects, mut shared) in by_objects {
+                // Mark the subplans that occur within another subplan shared by the same objects.
+                // Each subplan is visited once, rather than compared against every other one.
+                let subplans: Vec<_> = shared.keys().copied().collect();
+                for subplan in subplans {
+                    for child in subplan.children() {
+                        child.visit_pre(|e| {
+                            if let Some(subsumed) = shared.get_mut(e) {
+                                *subsumed = true;
+                            }
+                        });
+                    }
+                }
+                for (subplan, subsumed) in shared {
+                    if subsumed {
+                        continue;
+                    }
+                    let subplan_id = mz_ore::hash::hash(subplan);
+                    let plan = subplan.pretty();
+                    entries.extend(objects.iter().map(|object_id| SharedSubplanEntry {
+                        cluster_id,
+                        subplan_id,
+                        object_id: *object_id,
+                        plan: plan.clone(),
+                    }));
+                }
+            }
+        }
+        entries
+    }
+
+    /// Returns the current shared subplans, and replaces the published entries with them.
+    ///
+    /// See [`PublishedSnapshot::replace`].
+    fn publish(&mut self) -> Option<(Vec<SharedSubplanEntry>, Vec<SharedSubplanEntry>)> {
+        let entries = self.entries();
+        self.published.replace(entries)
+    }
+}
+
+impl Coordinator {
+    /// Records the shareable subplans of the `dataflow` installed on `cluster_id`.
+    ///
+    /// Only dataflows of indexes and materialized views are considered, as other dataflows are
+    /// short-lived or cannot share subplans. The subplans are taken from the dataflow before it is
+    /// optimized, see [`crate::coord::dataflows::DataflowBuilder::shareable_subplans`].
+    pub(crate) fn observe_shared_subplans(
+        &mut self,
+        cluster_id: ClusterId,
+        dataflow: &DataflowDesc,
+    ) {
+        let Some(object_id) = dataflow.export_ids().find(|id| !id.is_transient()) else {
+            return;
+        };
+        let subplans = match self
+            .dataflow_builder(cluster_id)
+            .shareable_subplans(object_id)
+        {
+            Ok(subplans) => subplans,
+            Err(e) => {
+                warn!("Error inspecting dataflow of {object_id} for shareable subplans: {e}");
+                return;
+            }
+        };
+        self.shared_subplans
+            .observe(cluster_id, object_id, subplans);
+    }
+
+    /// Publishes the current shared subplans to `mz_internal.mz_shared_subplans`.
+    ///
+    /// Dataflows of dropped clusters or objects are forgotten first.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn shared_subplans_update(&mut self) {
+        let mut tracker = std::mem::take(&mut self.shared_subplans);
+        tracker.retain(|cluster_id, object_id| {
+            let catalog = self.catalog();
+            catalog.try_get_cluster(cluster_id).is_some()
+                && catalog.try_get_entry(&object_id).is_some()
+        });
+        let published = tracker.publish();
+        self.shared_subplans = tracker;
+
+        if let Some(published) = published {
+            self.buffer_snapshot_updates(published, CatalogState::pack_shared_subplan_updates);
+        }
+    }
+
+    pub fn spawn_shared_subplans_publishing(&self) {
+        self.spawn_periodic_publishing(
+            "shared_subplans_publishing",
+            SHARED_SUBPLANS_PUBLISH_INTERVAL,
+            || Message::SharedSubplansPublish,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_expr::{Id, JoinImplementation, JoinKind};
+    use mz_repr::{RelationType, ScalarType};
+
+    use super::*;
+
+    fn get(id: u64) -> MirRelationExpr {
+        MirRelationExpr::Get {
+            id: Id::Global(GlobalId::User(id)),
+            typ: RelationType::new(vec![ScalarType::Int64.nullable(true)]),
+        }
+    }
+
+    fn join(inputs: Vec<MirRelationExpr>) -> MirRelationExpr {
+        MirRelationExpr::Join {
+            inputs,
+            equivalences: vec![],
+            implementation: JoinImplementation::Unimplemented,
+            kind: JoinKind::Inner,
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_shared_subplan_tracker() {
+        let cluster_id = ClusterId::User(1);
+        let inner = join(vec![get(1), get(2)]);
+        let outer = join(vec![inner.clone(), get(3)]);
+
+        let mut tracker = SharedSubplanTracker::default();
+        tracker.observe(cluster_id, GlobalId::User(10), [inner.clone()].into());
+        assert_eq!(tracker.publish(), None);
+
+        // Both the inner and the outer join are shared by the same objects, so only the outer
+        // one is reported.
+        tracker.observe(
+            cluster_id,
+            GlobalId::User(10),
+            [inner.clone(), outer.clone()].into(),
+        );
+        tracker.observe(
+            cluster_id,
+            GlobalId::User(11),
+            [inner.clone(), outer.clone()].into(),
+        );
+        tracker.observe(cluster_id, GlobalId::User(12), [inner.clone()].into());
+        let (old, new) = tracker.publish().expect("changed");
+        assert!(old.is_empty());
+        let mut reported: Vec<_> = new.iter().map(|e| (e.subplan_id, e.object_id)).collect();
+        reported.sort();
+        let mut expected = vec![
+            (mz_ore::hash::hash(&inner), GlobalId::User(10)),
+            (mz_ore::hash::hash(&inner), GlobalId::User(11)),
+            (mz_ore::hash::hash(&inner), GlobalId::User(12)),
+            (mz_ore::hash::hash(&outer), GlobalId::User(10)),
+            (mz_ore::hash::hash(&outer), GlobalId::User(11)),
+        ];
+        expected.sort();
+        assert_eq!(reported, expected);
+        assert_eq!(tracker.publish(), None);
+
+        tracker.retain(|_, id| id != GlobalId::User(12));
+        tracker.retain(|_, id| id != GlobalId::User(11));
+        let (old, new) = tracker.publish().expect("changed");
+        assert_eq!(old.len(), 5);
+        assert!(new.is_empty());
+    }
+
+    #[mz_ore::test]
+    fn test_shared_subplans_per_cluster() {
+        let inner = join(vec![get(1), get(2)]);
+        let mut tracker = SharedSubplanTracker::default();
+        tracker.observe(
+            ClusterId::User(1),
+            GlobalId::User(10),
+            [inner.clone()].into(),
+        );
+        tracker.observe(ClusterId::User(2), GlobalId::User(11), [inner].into());
+        assert_eq!(tracker.publish(), None);
+    }
+}
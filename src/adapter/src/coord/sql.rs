@@ -14,11 +14,13 @@ use mz_repr::{GlobalId, ScalarType};
 use mz_sql::names::{Aug, ResolvedIds};
 use mz_sql::plan::StatementDesc;
 use mz_sql_parser::ast::{Raw, Statement};
+use mz_storage_client::controller::ReadPolicy;
 
 use crate::catalog::Catalog;
+use crate::coord::read_policy::ReadHolds;
 use crate::coord::Coordinator;
 use crate::session::{Session, TransactionStatus};
-use crate::subscribe::ActiveSubscribe;
+use crate::subscribe::{ActiveSubscribe, SubscribeDeliveries};
 use crate::util::describe;
 use crate::{metrics, AdapterError, ExecuteContext, ExecuteResponse};
 
@@ -228,7 +230,171 @@ impl Coordinator {
                 .active_subscribes
                 .with_label_values(&[session_type])
                 .dec();
+
+            if let Some(name) = &active_subscribe.name {
+                self.detach_retained_subscribe(name, id, active_subscribe.acknowledgements)
+                    .await;
+            }
         }
         // Note: Drop sinks are removed at commit time.
     }
+
+    /// Acknowledges the batches of the flow-controlled subscribe run by the
+    /// sink `id` that its client has received, which allows its dataflow to
+    /// send more.
+    pub(crate) fn acknowledge_subscribe_deliveries(&mut self, id: GlobalId) {
+        let Some(active_subscribe) = self.active_subscribes.get_mut(&id) else {
+            return;
+        };
+        if active_subscribe.dropping {
+            return;
+        }
+        let cluster_id = active_subscribe.cluster_id;
+        let Some(upper) = active_subscribe
+            .deliveries
+            .as_mut()
+            .and_then(|deliveries| deliveries.received_upper())
+        else {
+            return;
+        };
+        // A complete subscribe has nothing left to acknowledge, and its read
+        // policy is dropped along with it.
+        if !upper.is_empty() {
+            self.update_compute_base_read_policy(cluster_id, id, ReadPolicy::ValidFrom(upper));
+        }
+    }
+
+    /// Records that the client of the named subscribe run by the sink `id` has
+    /// acknowledged its first `acknowledged` responses, and advances the
+    /// resume point of the subscribe past the batches they complete.
+    ///
+    /// The new resume point is recorded durably before the read holds of the
+    /// subscribe are advanced to it.
+    pub(crate) async fn acknowledge_retained_subscribe(&mut self, id: GlobalId, acknowledged: u64) {
+        // The client can acknowledge responses of a subscribe that completed,
+        // and has thus been removed, until it goes away.
+        let acknowledgements = match self.active_subscribes.get_mut(&id) {
+            Some(active_subscribe) => active_subscribe
+                .name
+                .clone()
+                .zip(active_subscribe.acknowledgements.as_mut()),
+            None => self
+                .retained_subscribes
+                .iter_mut()
+                .find(|(_, retained)| retained.sink == Some(id))
+                .and_then(|(name, retained)| {
+                    Some((name.clone(), retained.acknowledgements.as_mut()?))
+                }),
+        };
+        let Some((name, acknowledgements)) = acknowledgements else {
+            return;
+        };
+        acknowledgements.deliver(acknowledged);
+        let Some(upper) = acknowledgements.received_upper() else {
+            return;
+        };
+        let Some(retained) = self.retained_subscribes.get(&name) else {
+            return;
+        };
+        if retained.sink != Some(id) {
+            return;
+        }
+        let Some(upper) = upper.into_option() else {
+            // The subscribe is complete, so there is nothing left to resume.
+            self.drop_retained_subscribe(&name);
+            return;
+        };
+        let retraction = self
+            .catalog()
+            .state()
+            .pack_retained_subscribe_update(&name, retained, -1);
+        let retained = self
+            .retained_subscribes
+            .get_mut(&name)
+            .expect("retained subscribe exists");
+        let Some(resume_as_of) = retained.acknowledge(upper) else {
+            return;
+        };
+        let insertion = self.catalog().state().pack_retained_subscribe_update(
+            &name,
+            &self.retained_subscribes[&name],
+            1,
+        );
+        self.send_builtin_table_updates(vec![retraction, insertion])
+            .await;
+
+        if let Some(retained) = self.retained_subscribes.get_mut(&name) {
+            let read_holds = std::mem::replace(&mut retained.read_holds, ReadHolds::new());
+            let read_holds = self.update_read_hold(read_holds, resume_as_of);
+            if let Some(retained) = self.retained_subscribes.get_mut(&name) {
+                retained.read_holds = read_holds;
+            }
+        }
+    }
+
+    /// Starts the retention period of the named subscribe `name`, if it was
+    /// run by the sink `id`.
+    ///
+    /// `acknowledgements` tracks the responses of the sink that its client
+    /// has not acknowledged yet.
+    async fn detach_retained_subscribe(
+        &mut self,
+        name: &str,
+        id: GlobalId,
+        acknowledgements: Option<SubscribeDeliveries>,
+    ) {
+        let now = self.now();
+        let Some(retained) = self.retained_subscribes.get(name) else {
+            return;
+        };
+        if retained.sink != Some(id) || retained.expires_at.is_some() {
+            return;
+        }
+        let retraction = self
+            .catalog()
+            .state()
+            .pack_retained_subscribe_update(name, retained, -1);
+        let retained = self
+            .retained_subscribes
+            .get_mut(name)
+            .expect("retained subscribe exists");
+        let retention_ms = u64::try_from(retained.retention.as_millis()).unwrap_or(u64::MAX);
+        retained.expires_at = Some(now.saturating_add(retention_ms));
+        retained.acknowledgements = acknowledgements;
+        let insertion = self.catalog().state().pack_retained_subscribe_update(
+            name,
+            &self.retained_subscribes[name],
+            1,
+        );
+        self.send_builtin_table_updates(vec![retraction, insertion])
+            .await;
+    }
+
+    /// Drops all named subscribes whose retention period has passed.
+    pub(crate) fn expire_retained_subscribes(&mut self) {
+        let now = self.now();
+        let expired: Vec<_> = self
+            .retained_subscribes
+            .iter()
+            .filter(
+                |(_, retained)| matches!(retained.expires_at, Some(expires_at) if expires_at <= now),
+            )
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in expired {
+            self.drop_retained_subscribe(&name);
+        }
+    }
+
+    /// Drops the named subscribe `name`, releasing its read holds.
+    pub(crate) fn drop_retained_subscribe(&mut self, name: &str) {
+        if let Some(retained) = self.retained_subscribes.remove(name) {
+            let update = self
+                .catalog()
+                .state()
+                .pack_retained_subscribe_update(name, &retained, -1);
+            self.buffer_builtin_table_updates(vec![update]);
+            self.release_read_hold(&retained.read_holds);
+        }
+    }
 }
@@ -49,8 +49,16 @@ pub enum AdapterError {
     Catalog(catalog::Error),
     /// The cached plan or descriptor changed.
     ChangedPlan,
+    /// Timed out waiting for an object on a cluster to hydrate on the replicas that replace
+    /// those an `ALTER CLUSTER` drops.
+    ClusterReplicasNotHydrated {
+        cluster_name: String,
+        object_name: String,
+    },
     /// The cursor already exists.
     DuplicateCursor(String),
+    /// A named subscribe with this name already exists.
+    DuplicateSubscribe(String),
     /// An error while evaluating an expression.
     Eval(EvalError),
     /// An error occurred while planning the statement.
@@ -92,6 +100,8 @@ pub enum AdapterError {
     },
     /// The selection value for a table mutation operation refers to an invalid object.
     InvalidTableMutationSelection,
+    /// A table mutation attempted to retract rows from an append-only table.
+    AppendOnlyTableRetraction(String),
     /// An operation attempted to create an illegal item in a
     /// storage-only cluster
     BadItemInStorageCluster {
@@ -147,8 +157,18 @@ pub enum AdapterError {
     IdleInTransactionSessionTimeout,
     /// An error occurred in a SQL catalog operation.
     SqlCatalog(mz_sql::catalog::CatalogError),
+    /// The named subscribe is still running on another connection.
+    SubscribeInUse(String),
     /// The transaction is in single-subscribe mode.
     SubscribeOnlyTransaction,
+    /// A named subscribe was resumed with a different relation than it was started with.
+    SubscribeResumeMismatch(String),
+    /// A named subscribe was resumed at a point whose updates are no longer retained.
+    SubscribeResumeUnavailable {
+        name: String,
+        at: mz_repr::Timestamp,
+        earliest: mz_repr::Timestamp,
+    },
     /// An error occurred in the MIR stage of the optimizer.
     Transform(TransformError),
     /// A query depends on items which are not allowed to be referenced from the current cluster.
@@ -168,6 +188,8 @@ pub enum AdapterError {
     /// The named role does not exist.
     UnknownLoginRole(String),
     UnknownPreparedStatement(String),
+    /// The named subscribe does not exist or can no longer be resumed.
+    UnknownSubscribe(String),
     /// The named cluster replica does not exist.
     UnknownClusterReplica {
         cluster_name: String,
@@ -326,6 +348,11 @@ impl AdapterError {
             AdapterError::NoClusterReplicasAvailable(_) => {
                 Some("You can create cluster replicas using CREATE CLUSTER REPLICA".into())
             }
+            AdapterError::ClusterReplicasNotHydrated { .. } => Some(
+                "The replicas created for the alteration were dropped again. Hydration progress \
+                is reported in mz_internal.mz_compute_hydration_statuses."
+                    .into(),
+            ),
             AdapterError::UnmaterializableFunction(UnmaterializableFunc::CurrentTimestamp) => {
                 Some("Try using `mz_now()` here instead.".into())
             }
@@ -368,7 +395,11 @@ impl AdapterError {
             AdapterError::BadItemInStorageCluster { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::Catalog(_) => SqlState::INTERNAL_ERROR,
             AdapterError::ChangedPlan => SqlState::FEATURE_NOT_SUPPORTED,
+            AdapterError::ClusterReplicasNotHydrated { .. } => {
+                SqlState::OBJECT_NOT_IN_PREREQUISITE_STATE
+            }
             AdapterError::DuplicateCursor(_) => SqlState::DUPLICATE_CURSOR,
+            AdapterError::DuplicateSubscribe(_) => SqlState::DUPLICATE_OBJECT,
             AdapterError::Eval(EvalError::CharacterNotValidForEncoding(_)) => {
                 SqlState::PROGRAM_LIMIT_EXCEEDED
             }
@@ -391,6 +422,9 @@ impl AdapterError {
             AdapterError::InvalidStorageClusterSize { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::SourceOrSinkSizeRequired { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::InvalidTableMutationSelection => SqlState::INVALID_TRANSACTION_STATE,
+            AdapterError::AppendOnlyTableRetraction(_) => {
+                SqlState::OBJECT_NOT_IN_PREREQUISITE_STATE
+            }
             AdapterError::ConstraintViolation(NotNullViolation(_)) => SqlState::NOT_NULL_VIOLATION,
             AdapterError::NoClusterReplicasAvailable(_) => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::OperationProhibitsTransaction(_) => SqlState::ACTIVE_SQL_TRANSACTION,
@@ -413,7 +447,10 @@ impl AdapterError {
             AdapterError::ResultSize(_) => SqlState::OUT_OF_MEMORY,
             AdapterError::SafeModeViolation(_) => SqlState::INTERNAL_ERROR,
             AdapterError::SqlCatalog(_) => SqlState::INTERNAL_ERROR,
+            AdapterError::SubscribeInUse(_) => SqlState::OBJECT_IN_USE,
             AdapterError::SubscribeOnlyTransaction => SqlState::INVALID_TRANSACTION_STATE,
+            AdapterError::SubscribeResumeMismatch(_) => SqlState::INVALID_PARAMETER_VALUE,
+            AdapterError::SubscribeResumeUnavailable { .. } => SqlState::INVALID_PARAMETER_VALUE,
             AdapterError::Transform(_) => SqlState::INTERNAL_ERROR,
             AdapterError::UnallowedOnCluster { .. } => {
                 SqlState::S_R_E_PROHIBITED_SQL_STATEMENT_ATTEMPTED
@@ -422,6 +459,7 @@ impl AdapterError {
             AdapterError::UncallableFunction { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::UnknownCursor(_) => SqlState::INVALID_CURSOR_NAME,
             AdapterError::UnknownPreparedStatement(_) => SqlState::UNDEFINED_PSTATEMENT,
+            AdapterError::UnknownSubscribe(_) => SqlState::UNDEFINED_OBJECT,
             AdapterError::UnknownLoginRole(_) => SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
             AdapterError::UnknownClusterReplica { .. } => SqlState::UNDEFINED_OBJECT,
             AdapterError::UnknownWebhookSource { .. } => SqlState::UNDEFINED_OBJECT,
@@ -479,10 +517,22 @@ impl fmt::Display for AdapterError {
                 )
             }
             AdapterError::ChangedPlan => f.write_str("cached plan must not change result type"),
+            AdapterError::ClusterReplicasNotHydrated {
+                cluster_name,
+                object_name,
+            } => write!(
+                f,
+                "timed out altering cluster {} while waiting for {} to hydrate",
+                cluster_name.quoted(),
+                object_name.quoted(),
+            ),
             AdapterError::Catalog(e) => e.fmt(f),
             AdapterError::DuplicateCursor(name) => {
                 write!(f, "cursor {} already exists", name.quoted())
             }
+            AdapterError::DuplicateSubscribe(name) => {
+                write!(f, "subscribe {} already exists", name.quoted())
+            }
             AdapterError::Eval(e) => e.fmt(f),
             AdapterError::Explain(e) => e.fmt(f),
             AdapterError::IdExhaustionError => f.write_str("ID allocator exhausted all valid IDs"),
@@ -516,6 +566,13 @@ impl fmt::Display for AdapterError {
             AdapterError::InvalidTableMutationSelection => {
                 f.write_str("invalid selection: operation may only refer to user-defined tables")
             }
+            AdapterError::AppendOnlyTableRetraction(name) => {
+                write!(
+                    f,
+                    "cannot delete or update rows in append-only table {}",
+                    name.quoted()
+                )
+            }
             AdapterError::ConstraintViolation(not_null_violation) => {
                 write!(f, "{}", not_null_violation)
             }
@@ -582,9 +639,22 @@ impl fmt::Display for AdapterError {
                 write!(f, "cannot create {} in safe mode", feature)
             }
             AdapterError::SqlCatalog(e) => e.fmt(f),
+            AdapterError::SubscribeInUse(name) => {
+                write!(f, "subscribe {} is already running", name.quoted())
+            }
             AdapterError::SubscribeOnlyTransaction => {
                 f.write_str("SUBSCRIBE in transactions must be the only read statement")
             }
+            AdapterError::SubscribeResumeMismatch(name) => write!(
+                f,
+                "cannot resume subscribe {} with a different relation",
+                name.quoted()
+            ),
+            AdapterError::SubscribeResumeUnavailable { name, at, earliest } => write!(
+                f,
+                "cannot resume subscribe {} at {at}: it can only be resumed at {earliest} or later",
+                name.quoted()
+            ),
             AdapterError::Transform(e) => e.fmt(f),
             AdapterError::UncallableFunction { func, context } => {
                 write!(f, "cannot call {} in {}", func, context)
@@ -618,6 +688,9 @@ impl fmt::Display for AdapterError {
             AdapterError::UnknownPreparedStatement(name) => {
                 write!(f, "prepared statement {} does not exist", name.quoted())
             }
+            AdapterError::UnknownSubscribe(name) => {
+                write!(f, "subscribe {} does not exist", name.quoted())
+            }
             AdapterError::UnknownClusterReplica {
                 cluster_name,
                 replica_name,
@@ -17,7 +17,10 @@ use mz_compute_client::types::dataflows::DataflowDescription;
 use mz_expr::explain::ExplainContext;
 use mz_expr::{MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, RowSetFinishing};
 use mz_repr::explain::tracing::{PlanTrace, TraceEntry};
-use mz_repr::explain::{Explain, ExplainConfig, ExplainError, ExplainFormat, UsedIndexes};
+use mz_repr::explain::{
+    Explain, ExplainConfig, ExplainError, ExplainFormat, RecommendedIndex, RecommendedIndexes,
+    UsedIndexes,
+};
 use mz_sql::plan::{HirRelationExpr, HirScalarExpr};
 use mz_sql_parser::ast::ExplainStage;
 use tracing::dispatcher::{self};
@@ -86,6 +89,7 @@ impl OptimizerTrace {
         catalog: ConnCatalog,
         row_set_finishing: Option<RowSetFinishing>,
         used_indexes: Vec<mz_repr::GlobalId>,
+        recommended_indexes: Vec<RecommendedIndex>,
         fast_path_plan: Option<FastPathPlan>,
     ) -> Result<Vec<TraceEntry<String>>, ExplainError> {
         let mut results = vec![];
@@ -94,6 +98,7 @@ impl OptimizerTrace {
             config: &config,
             humanizer: &catalog,
             used_indexes: UsedIndexes::new(vec![]),
+            recommended_indexes: RecommendedIndexes::default(),
             finishing: row_set_finishing.clone(),
             duration: Duration::default(),
         };
@@ -109,6 +114,7 @@ impl OptimizerTrace {
             config: &config,
             humanizer: &catalog,
             used_indexes: UsedIndexes::new(used_indexes),
+            recommended_indexes: RecommendedIndexes::new(recommended_indexes),
             finishing: row_set_finishing,
             duration: Duration::default(),
         };
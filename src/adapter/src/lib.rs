@@ -136,3 +136,4 @@ pub use crate::coord::ExecuteContextExtra;
 pub use crate::coord::{serve, Config, DUMMY_AVAILABILITY_ZONE};
 pub use crate::error::AdapterError;
 pub use crate::notice::AdapterNotice;
+pub use crate::subscribe::SubscribeAcknowledger;
@@ -366,7 +366,8 @@ pub fn generate_required_role_membership(
         | Plan::RevokeRole(_)
         | Plan::GrantPrivileges(_)
         | Plan::RevokePrivileges(_)
-        | Plan::ValidateConnection(_) => Vec::new(),
+        | Plan::ValidateConnection(_)
+        | Plan::Analyze(_) => Vec::new(),
     }
 }
 
@@ -425,6 +426,7 @@ fn generate_required_ownership(plan: &Plan) -> Vec<ObjectId> {
         | Plan::SideEffectingFunc(_) => Vec::new(),
         Plan::CreateClusterReplica(plan) => vec![ObjectId::Cluster(plan.cluster_id)],
         Plan::CreateIndex(plan) => vec![ObjectId::Item(plan.index.on)],
+        Plan::Analyze(plan::AnalyzePlan { id }) => vec![ObjectId::Item(*id)],
         Plan::CreateView(plan::CreateViewPlan { replace, .. })
         | Plan::CreateMaterializedView(plan::CreateMaterializedViewPlan { replace, .. }) => replace
             .map(|id| vec![ObjectId::Item(id)])
@@ -817,6 +819,10 @@ fn generate_required_privileges(
             copy_to: _,
             emit_progress: _,
             output: _,
+            resumable: _,
+            batch_size: _,
+            emit_interval: _,
+            max_pending_rows: _,
         }) => {
             let mut privileges =
                 generate_read_privileges(catalog, resolved_ids.0.iter().cloned(), role_id);
@@ -1136,6 +1142,7 @@ fn generate_required_privileges(
             secret_as: _,
         })
         | Plan::RotateKeys(plan::RotateKeysPlan { id: _ })
+        | Plan::Analyze(plan::AnalyzePlan { id: _ })
         | Plan::AlterSystemSet(plan::AlterSystemSetPlan { name: _, value: _ })
         | Plan::AlterSystemReset(plan::AlterSystemResetPlan { name: _ })
         | Plan::AlterSystemResetAll(plan::AlterSystemResetAllPlan {})
@@ -35,7 +35,7 @@ use mz_sql_parser::ast::TransactionIsolationLevel;
 use mz_storage_client::types::sources::Timeline;
 use qcell::{QCell, QCellOwner};
 use rand::Rng;
-use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::mpsc::{self, UnboundedSender};
 use tokio::sync::OwnedMutexGuard;
 use uuid::Uuid;
 
@@ -44,6 +44,7 @@ use crate::coord::peek::PeekResponseUnary;
 use crate::coord::statement_logging::PreparedStatementLoggingInfo;
 use crate::coord::timestamp_selection::{TimestampContext, TimestampDetermination};
 use crate::error::AdapterError;
+use crate::subscribe::SubscribeAcknowledger;
 use crate::AdapterNotice;
 
 const DUMMY_CONNECTION_ID: ConnectionId = ConnectionId::Static(0);
@@ -818,6 +819,11 @@ pub struct InProgressRows {
     pub current: Option<Vec<Row>>,
     /// A stream from which to fetch more row batches.
     pub remaining: RecordFirstRowStream,
+    /// The number of row batches fetched from `remaining`.
+    received: u64,
+    /// Acknowledges the row batches the client has consumed, if the rows are
+    /// those of a named subscribe.
+    acknowledger: Option<SubscribeAcknowledger>,
 }
 
 impl InProgressRows {
@@ -826,12 +832,43 @@ impl InProgressRows {
         Self {
             current: None,
             remaining,
+            received: 0,
+            acknowledger: None,
+        }
+    }
+
+    /// Creates a new InProgressRows from the batch stream of a subscribe.
+    pub fn subscribe(
+        remaining: RecordFirstRowStream,
+        acknowledger: Option<SubscribeAcknowledger>,
+    ) -> Self {
+        Self {
+            acknowledger,
+            ..Self::new(remaining)
+        }
+    }
+
+    /// Fetches the next row batch from `remaining`.
+    pub async fn recv(&mut self) -> Option<PeekResponseUnary> {
+        let batch = self.remaining.recv().await;
+        if batch.is_some() {
+            self.received += 1;
+        }
+        batch
+    }
+
+    /// Acknowledges the row batches the client has consumed, which are those
+    /// fetched so far, except for the partially sent `current` batch.
+    pub fn acknowledge(&self) {
+        if let Some(acknowledger) = &self.acknowledger {
+            let partial = u64::from(self.current.is_some());
+            acknowledger.acknowledge(self.received - partial);
         }
     }
 }
 
 /// A channel of batched rows.
-pub type RowBatchStream = UnboundedReceiver<PeekResponseUnary>;
+pub type RowBatchStream = mpsc::Receiver<PeekResponseUnary>;
 
 /// The transaction status of a session.
 ///
@@ -10,23 +10,31 @@
 //! Implementations around supporting the SUBSCRIBE protocol with the dataflow layer
 
 use std::cmp::Ordering;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, VecDeque};
 use std::iter;
+use std::time::Duration;
 
 use itertools::Itertools;
 use mz_compute_client::protocol::response::{SubscribeBatch, SubscribeResponse};
 use mz_controller::clusters::ClusterId;
 use mz_expr::compare_columns;
 use mz_ore::now::EpochMillis;
+use mz_ore::task;
 use mz_repr::adt::numeric;
 use mz_repr::{Datum, GlobalId, Row, Timestamp};
-use mz_sql::plan::SubscribeOutput;
+use mz_sql::plan::{SubscribeFrom, SubscribeOutput};
 use mz_sql::session::user::User;
 use timely::progress::Antichain;
 use tokio::sync::mpsc;
 
 use crate::client::ConnectionId;
 use crate::coord::peek::PeekResponseUnary;
+use crate::coord::read_policy::ReadHolds;
+use crate::coord::Message;
+
+/// How long a named subscribe is retained after its connection goes away, if
+/// no `RETENTION` is specified.
+pub const DEFAULT_RETAINED_SUBSCRIBE_RETENTION: Duration = Duration::from_secs(10 * 60);
 
 /// A description of an active subscribe from coord's perspective
 #[derive(Debug)]
@@ -55,15 +63,34 @@ pub struct ActiveSubscribe {
     pub dropping: bool,
     /// How to modify output
     pub output: SubscribeOutput,
+    /// The name under which the subscribe can be resumed, if any.
+    pub name: Option<String>,
+    /// The maximum number of rows to send to the client in a single response.
+    pub batch_size: Option<usize>,
+    /// The number of responses sent towards the client.
+    pub sent: u64,
+    /// Tracks the responses received by the client, if the subscribe is flow-controlled.
+    pub deliveries: Option<SubscribeDeliveries>,
+    /// Tracks the responses acknowledged by the client, if the subscribe is named.
+    pub acknowledgements: Option<SubscribeDeliveries>,
 }
 
 impl ActiveSubscribe {
-    pub(crate) fn initialize(&self) {
+    pub(crate) fn initialize(&mut self) {
         // Always emit progress message indicating snapshot timestamp.
         self.send_progress_message(&Antichain::from_elem(self.as_of));
     }
 
-    fn send_progress_message(&self, upper: &Antichain<Timestamp>) {
+    fn send(&mut self, response: PeekResponseUnary) {
+        let result = self.channel.send(response);
+        if result.is_err() {
+            // TODO(benesch): we should actually drop the sink if the
+            // receiver has gone away. E.g. form a DROP SINK command?
+        }
+        self.sent += 1;
+    }
+
+    fn send_progress_message(&mut self, upper: &Antichain<Timestamp>) {
         if !self.emit_progress {
             return;
         }
@@ -87,11 +114,7 @@ impl ActiveSubscribe {
                 }
             }
 
-            let result = self.channel.send(PeekResponseUnary::Rows(vec![row_buf]));
-            if result.is_err() {
-                // TODO(benesch): we should actually drop the sink if the
-                // receiver has gone away. E.g. form a DROP SINK command?
-            }
+            self.send(PeekResponseUnary::Rows(vec![row_buf]));
         }
     }
 
@@ -263,7 +286,7 @@ impl ActiveSubscribe {
                             SubscribeOutput::Diffs => rows.sort_by_key(|(time, _, _)| *time),
                         }
 
-                        let rows = rows
+                        let mut rows: Vec<_> = rows
                             .into_iter()
                             .map(|(time, row, diff)| {
                                 assert!(self.as_of <= time);
@@ -295,18 +318,17 @@ impl ActiveSubscribe {
                             .collect();
                         // TODO(benesch): the lack of backpressure here can result in
                         // unbounded memory usage.
-                        let result = self.channel.send(PeekResponseUnary::Rows(rows));
-                        if result.is_err() {
-                            // TODO(benesch): we should actually drop the sink if the
-                            // receiver has gone away. E.g. form a DROP SINK command?
+                        if let Some(batch_size) = self.batch_size {
+                            while rows.len() > batch_size {
+                                let rest = rows.split_off(batch_size);
+                                self.send(PeekResponseUnary::Rows(rows));
+                                rows = rest;
+                            }
                         }
+                        self.send(PeekResponseUnary::Rows(rows));
                     }
                     Err(text) => {
-                        let result = self.channel.send(PeekResponseUnary::Error(text));
-                        if result.is_err() {
-                            // TODO(benesch): we should actually drop the sink if the
-                            // receiver has gone away. E.g. form a DROP SINK command?
-                        }
+                        self.send(PeekResponseUnary::Error(text));
                     }
                 }
                 // Emit progress message if requested. Don't emit progress for the first batch if the upper
@@ -315,7 +337,13 @@ impl ActiveSubscribe {
                 if !upper.less_equal(&self.as_of) {
                     self.send_progress_message(&upper);
                 }
-                upper.is_empty()
+                let done = upper.is_empty();
+                for deliveries in [&mut self.deliveries, &mut self.acknowledgements] {
+                    if let Some(deliveries) = deliveries {
+                        deliveries.pending.push_back((self.sent, upper.clone()));
+                    }
+                }
+                done
             }
             SubscribeResponse::DroppedAt(_frontier) => {
                 // TODO: Could perhaps do this earlier, in response to DROP SINK.
@@ -324,3 +352,212 @@ impl ActiveSubscribe {
         }
     }
 }
+
+/// Tracks the responses of a subscribe that its client has received, or
+/// acknowledged.
+///
+/// The dataflow of a flow-controlled subscribe stops sending batches once too
+/// many of its rows have not been received. Batches are acknowledged to the
+/// dataflow by advancing the read policy of the subscribe's sink to the upper
+/// of the latest batch the client has received completely.
+///
+/// A named subscribe instead advances its resume point to the upper of the
+/// latest batch the client has acknowledged completely, as receiving a
+/// response only means that it has been written to the client's connection.
+#[derive(Debug, Default)]
+pub struct SubscribeDeliveries {
+    /// The number of responses the client has received.
+    delivered: u64,
+    /// The uppers of batches the client has not received completely, along
+    /// with the number of responses sent up to and including each batch.
+    pending: VecDeque<(u64, Antichain<Timestamp>)>,
+}
+
+impl SubscribeDeliveries {
+    /// Records that the client has received `delivered` responses.
+    pub(crate) fn deliver(&mut self, delivered: u64) {
+        self.delivered = std::cmp::max(self.delivered, delivered);
+    }
+
+    /// Returns the upper of the latest batch the client has received
+    /// completely since the last call, if any.
+    pub(crate) fn received_upper(&mut self) -> Option<Antichain<Timestamp>> {
+        let mut received = None;
+        while let Some((sent, _)) = self.pending.front() {
+            if *sent > self.delivered {
+                break;
+            }
+            received = self.pending.pop_front().map(|(_, upper)| upper);
+        }
+        received
+    }
+}
+
+/// Forwards the responses of the subscribe `sink_id` to its client.
+///
+/// Responses are handed to the client one at a time, as it asks for them,
+/// which keeps a slow client from accumulating responses in `client_tx`. If
+/// `internal_cmd_tx` is present, the coordinator is informed about every
+/// response the client has received.
+///
+/// A response counts as received once the client has taken it from
+/// `client_tx` and asks for the next one, i.e. once it has written the
+/// response to its connection. Responses that are still buffered by the
+/// connection's socket when it goes away are lost nonetheless, which is why
+/// a named subscribe only advances its resume point once its client
+/// acknowledges responses through a [`SubscribeAcknowledger`].
+///
+/// `rx` is unbounded. For a flow-controlled subscribe, it only holds the
+/// responses of batches the client has not received yet: the dataflow stops
+/// sending batches once `MAX PENDING ROWS` of their rows are unacknowledged,
+/// so `rx` holds at most that many rows plus those of the batch that exceeded
+/// the limit, along with their progress responses. A single batch can be as
+/// large as all updates at one timestamp. Without flow control, `rx` is not
+/// bounded, and responses a slow client does not take accumulate there.
+pub(crate) fn spawn_subscribe_forwarder(
+    sink_id: GlobalId,
+    mut rx: mpsc::UnboundedReceiver<PeekResponseUnary>,
+    client_tx: mpsc::Sender<PeekResponseUnary>,
+    internal_cmd_tx: Option<mpsc::UnboundedSender<Message>>,
+) {
+    task::spawn(|| format!("subscribe_forwarder:{sink_id}"), async move {
+        let mut sent = 0;
+        let mut delivered = 0;
+        loop {
+            // `client_tx` holds a single response, so there is room for the
+            // next one only once the client has taken the previous one.
+            let Ok(permit) = client_tx.reserve().await else {
+                break;
+            };
+            if delivered < sent {
+                delivered = sent;
+                if let Some(internal_cmd_tx) = &internal_cmd_tx {
+                    // It is not an error for the coordinator to have shut down.
+                    let _ = internal_cmd_tx
+                        .send(Message::SubscribeResponsesDelivered { sink_id, delivered });
+                }
+            }
+            let response = tokio::select! {
+                response = rx.recv() => match response {
+                    Some(response) => response,
+                    None => break,
+                },
+                // Stop receiving responses as soon as the client goes away.
+                () = client_tx.closed() => break,
+            };
+            permit.send(response);
+            sent += 1;
+        }
+    });
+}
+
+/// Acknowledges to the coordinator the responses of a named subscribe that its
+/// client has consumed.
+///
+/// A client consumes the responses of a subscribe it reads through a cursor by
+/// asking for more of them, i.e. by issuing the next `FETCH` on the cursor.
+#[derive(Debug)]
+pub struct SubscribeAcknowledger {
+    sink_id: GlobalId,
+    internal_cmd_tx: mpsc::UnboundedSender<Message>,
+}
+
+impl SubscribeAcknowledger {
+    pub(crate) fn new(sink_id: GlobalId, internal_cmd_tx: mpsc::UnboundedSender<Message>) -> Self {
+        SubscribeAcknowledger {
+            sink_id,
+            internal_cmd_tx,
+        }
+    }
+
+    /// Acknowledges that the client has consumed the first `acknowledged`
+    /// responses of the subscribe.
+    pub fn acknowledge(&self, acknowledged: u64) {
+        // It is not an error for the coordinator to have shut down.
+        let _ = self
+            .internal_cmd_tx
+            .send(Message::SubscribeResponsesAcknowledged {
+                sink_id: self.sink_id,
+                acknowledged,
+            });
+    }
+}
+
+/// A named subscribe that can be resumed after the connection running it goes
+/// away.
+///
+/// The subscribe holds back compaction of its inputs at the point up to which
+/// its updates have been acknowledged by the client. Resuming it produces
+/// only the updates at or after that point.
+///
+/// Retained subscribes are recorded in `mz_internal.mz_retained_subscriptions`,
+/// and are restored from it when the coordinator restarts. The point up to
+/// which updates have been acknowledged is recorded there before the read
+/// holds of the subscribe are advanced past it.
+#[derive(Debug)]
+pub(crate) struct RetainedSubscribe {
+    /// The name of the user that started the subscribe.
+    pub(crate) user: String,
+    /// The cluster the subscribe runs on.
+    pub(crate) cluster_id: ClusterId,
+    /// The relation the subscribe reads from.
+    pub(crate) from: SubscribeFrom,
+    /// Read holds that keep the inputs readable at `resume_as_of`.
+    pub(crate) read_holds: ReadHolds<Timestamp>,
+    /// The as of at which a resumed subscribe starts.
+    pub(crate) resume_as_of: Timestamp,
+    /// Whether a resumed subscribe must emit a snapshot, which is the case
+    /// until the snapshot of the original subscribe has been acknowledged.
+    pub(crate) with_snapshot: bool,
+    /// How long to retain the subscribe after its connection goes away.
+    pub(crate) retention: Duration,
+    /// The sink that most recently ran the subscribe, if any.
+    pub(crate) sink: Option<GlobalId>,
+    /// The responses of `sink` its client has not acknowledged yet, if `sink`
+    /// is no longer running. The client can acknowledge them until it goes
+    /// away.
+    pub(crate) acknowledgements: Option<SubscribeDeliveries>,
+    /// The time at which the subscribe is dropped, if it is not running.
+    pub(crate) expires_at: Option<EpochMillis>,
+}
+
+impl RetainedSubscribe {
+    /// Records that all updates before `upper` have been acknowledged.
+    ///
+    /// Returns the new resume as of, if it changed.
+    pub(crate) fn acknowledge(&mut self, upper: Timestamp) -> Option<Timestamp> {
+        if upper <= self.resume_as_of {
+            return None;
+        }
+        // Without a snapshot, a subscribe only emits updates at times greater
+        // than its as of, so resuming at `upper - 1` produces exactly the
+        // updates at or after `upper`.
+        let resume_as_of = upper.step_back().expect("greater than resume_as_of");
+        if resume_as_of == self.resume_as_of && !self.with_snapshot {
+            return None;
+        }
+        self.resume_as_of = resume_as_of;
+        self.with_snapshot = false;
+        Some(resume_as_of)
+    }
+
+    /// Returns the as of, and whether to emit a snapshot, at which to resume
+    /// the subscribe for a client that has received all updates before
+    /// `upper`.
+    ///
+    /// Returns `None` if the updates at `upper` are no longer retained.
+    pub(crate) fn resume_at(&self, upper: Timestamp) -> Option<(Timestamp, bool)> {
+        if upper > self.resume_as_of {
+            // See `acknowledge` for why this produces exactly the updates at
+            // or after `upper`.
+            let resume_as_of = upper.step_back().expect("greater than resume_as_of");
+            Some((resume_as_of, false))
+        } else if self.with_snapshot {
+            // The client cannot have received the snapshot, which is at
+            // `resume_as_of`.
+            Some((self.resume_as_of, true))
+        } else {
+            None
+        }
+    }
+}
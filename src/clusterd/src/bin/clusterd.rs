@@ -330,7 +330,7 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
             secrets_reader,
             None,
         ),
-        StorageInstanceContext::new(args.scratch_directory, args.announce_memory_limit)?,
+        StorageInstanceContext::new(args.scratch_directory.clone(), args.announce_memory_limit)?,
     )?;
     info!(
         "listening for storage controller connections on {}",
@@ -347,12 +347,14 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
     );
 
     // Start compute server.
-    let (_compute_server, compute_client) =
-        mz_compute::server::serve(mz_cluster::server::ClusterConfig {
+    let (_compute_server, compute_client) = mz_compute::server::serve(
+        mz_cluster::server::ClusterConfig {
             metrics_registry,
             persist_clients,
             tracing_handle,
-        })?;
+        },
+        args.scratch_directory,
+    )?;
     info!(
         "listening for compute controller connections on {}",
         args.compute_controller_listen_addr
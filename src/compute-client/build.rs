@@ -117,6 +117,7 @@ fn main() {
                 "compute-client/src/plan/reduce.proto",
                 "compute-client/src/plan/threshold.proto",
                 "compute-client/src/plan/top_k.proto",
+                "compute-client/src/plan/window.proto",
                 "compute-client/src/protocol/command.proto",
                 "compute-client/src/protocol/response.proto",
                 "compute-client/src/service.proto",
@@ -41,7 +41,8 @@ use mz_cluster_client::client::ClusterReplicaLocation;
 use mz_expr::RowSetFinishing;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::tracing::OpenTelemetryContext;
-use mz_repr::{GlobalId, Row};
+use mz_persist_client::compaction::CompactionQueueEntry;
+use mz_repr::{Diff, GlobalId, Row};
 use mz_storage_client::controller::{ReadPolicy, StorageController};
 use mz_storage_client::types::instances::StorageInstanceId;
 use serde::{Deserialize, Serialize};
@@ -60,7 +61,9 @@ use crate::controller::replica::ReplicaConfig;
 use crate::logging::{LogVariant, LoggingConfig};
 use crate::metrics::ComputeControllerMetrics;
 use crate::protocol::command::ComputeParameters;
-use crate::protocol::response::{ComputeResponse, PeekResponse, SubscribeResponse};
+use crate::protocol::response::{
+    ComputeResponse, HydrationStatus, PeekResponse, SubscribeResponse,
+};
 use crate::service::{ComputeClient, ComputeGrpcClient};
 use crate::types::dataflows::DataflowDescription;
 
@@ -89,6 +92,15 @@ pub enum ComputeControllerResponse<T> {
     /// A notification that we heard a response from the given replica at the
     /// given time.
     ReplicaHeartbeat(ReplicaId, DateTime<Utc>),
+    /// A notification that the hydration status of a collection on the given replica changed.
+    ///
+    /// A `None` status indicates that the status is no longer tracked, because the collection or
+    /// the replica was dropped.
+    HydrationStatus(GlobalId, ReplicaId, Option<HydrationStatus>),
+    /// A notification that the persist compaction queue of the given replica changed.
+    ///
+    /// An empty queue is reported when the replica is dropped.
+    CompactionQueue(ReplicaId, Vec<CompactionQueueEntry>),
 }
 
 /// Replica configuration
@@ -222,6 +234,15 @@ where
         }
         result
     }
+
+    /// Takes the errors quarantined by persist sinks that have not been recorded yet, as tuples
+    /// of sink ID, time, error, input key, and diff.
+    pub fn take_quarantined_errors(&mut self) -> Vec<(GlobalId, T, String, Row, Diff)> {
+        self.instances
+            .values_mut()
+            .flat_map(|i| std::mem::take(&mut i.quarantined_errors))
+            .collect()
+    }
 }
 
 impl<T> ComputeController<T>
@@ -623,6 +644,8 @@ pub struct CollectionState<T> {
     write_frontier: Antichain<T>,
     /// The write frontiers reported by individual replicas.
     replica_write_frontiers: BTreeMap<ReplicaId, Antichain<T>>,
+    /// The hydration statuses reported by individual replicas.
+    replica_hydration_statuses: BTreeMap<ReplicaId, HydrationStatus>,
 }
 
 impl<T: Timestamp> CollectionState<T> {
@@ -644,6 +667,7 @@ impl<T: Timestamp> CollectionState<T> {
             compute_dependencies,
             write_frontier: Antichain::from_elem(Timestamp::minimum()),
             replica_write_frontiers: BTreeMap::new(),
+            replica_hydration_statuses: BTreeMap::new(),
         }
     }
 
@@ -668,4 +692,10 @@ impl<T: Timestamp> CollectionState<T> {
     pub fn write_frontier(&self) -> AntichainRef<T> {
         self.write_frontier.borrow()
     }
+
+    /// Reports the latest hydration status of the collection on the given replica, if the replica
+    /// has reported one.
+    pub fn hydration_status(&self, replica_id: ReplicaId) -> Option<&HydrationStatus> {
+        self.replica_hydration_statuses.get(&replica_id)
+    }
 }
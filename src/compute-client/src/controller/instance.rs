@@ -21,7 +21,7 @@ use mz_cluster_client::client::ClusterStartupEpoch;
 use mz_expr::RowSetFinishing;
 use mz_ore::cast::CastFrom;
 use mz_ore::tracing::OpenTelemetryContext;
-use mz_repr::{GlobalId, Row};
+use mz_repr::{Diff, GlobalId, Row};
 use mz_storage_client::controller::{ReadPolicy, StorageController};
 use thiserror::Error;
 use timely::progress::{Antichain, ChangeBatch, Timestamp};
@@ -36,7 +36,10 @@ use crate::metrics::InstanceMetrics;
 use crate::metrics::UIntGauge;
 use crate::protocol::command::{ComputeCommand, ComputeParameters, Peek};
 use crate::protocol::history::ComputeCommandHistory;
-use crate::protocol::response::{ComputeResponse, PeekResponse, SubscribeBatch, SubscribeResponse};
+use crate::protocol::response::{
+    ComputeResponse, HydrationStatus, PeekResponse, QuarantinedErrorBatch, SubscribeBatch,
+    SubscribeResponse,
+};
 use crate::service::{ComputeClient, ComputeGrpcClient};
 use crate::types::dataflows::DataflowDescription;
 use crate::types::sinks::{ComputeSinkConnection, ComputeSinkDesc, PersistSinkConnection};
@@ -141,6 +144,21 @@ pub(super) struct Instance<T> {
     /// on the subscribe's input. `subscribes` is only used to track which updates have been
     /// emitted, to decide if new ones should be emitted or suppressed.
     subscribes: BTreeMap<GlobalId, ActiveSubscribe<T>>,
+    /// The frontiers up to which the errors quarantined by persist sinks have been recorded.
+    ///
+    /// New entries are added for all persist sinks with a quarantine plan exported from dataflows
+    /// created through [`ActiveInstance::create_dataflows`], starting at the upper of the
+    /// quarantined error history, as errors before that frontier have been recorded already.
+    /// Starting at the write frontier of the sink's collection instead would drop the errors the
+    /// sink reported before a restart but that were not recorded yet. Entries are removed together
+    /// with the sink's collection state.
+    ///
+    /// Like subscribe batches, quarantined errors are only recorded from the first replica to
+    /// report them, which ensures that every error is recorded once.
+    quarantines: BTreeMap<GlobalId, Antichain<T>>,
+    /// Quarantined errors that are yet to be recorded, as tuples of sink ID, time, error, input
+    /// key, and diff.
+    pub quarantined_errors: Vec<(GlobalId, T, String, Row, Diff)>,
     /// The command history, used when introducing new replicas or restarting existing replicas.
     history: ComputeCommandHistory<UIntGauge, T>,
     /// IDs of replicas that have failed and require rehydration.
@@ -262,6 +280,8 @@ where
             log_sources: arranged_logs,
             peeks: Default::default(),
             subscribes: Default::default(),
+            quarantines: Default::default(),
+            quarantined_errors: Default::default(),
             history,
             failed_replicas: Default::default(),
             ready_responses: Default::default(),
@@ -471,6 +491,24 @@ where
         // Remove frontier tracking for this replica.
         self.remove_write_frontiers(id);
 
+        // Remove hydration tracking for this replica.
+        for (collection_id, collection) in self.compute.collections.iter_mut() {
+            if collection.replica_hydration_statuses.remove(&id).is_some() {
+                self.compute
+                    .ready_responses
+                    .push_back(ComputeControllerResponse::HydrationStatus(
+                        *collection_id,
+                        id,
+                        None,
+                    ));
+            }
+        }
+
+        // Retract the compaction queue reported by this replica.
+        self.compute
+            .ready_responses
+            .push_back(ComputeControllerResponse::CompactionQueue(id, Vec::new()));
+
         // Removing a replica might implicitly finish peeks.
         let mut peeks_to_remove = BTreeSet::new();
         for (uuid, peek) in &mut self.compute.peeks {
@@ -634,6 +672,21 @@ where
                     .subscribes
                     .insert(subscribe_id, ActiveSubscribe::new());
             }
+
+            // Initialize tracking of quarantined errors.
+            for (sink_id, sink) in &dataflow.sink_exports {
+                if let ComputeSinkConnection::Persist(PersistSinkConnection {
+                    quarantine: Some(_),
+                    ..
+                }) = &sink.connection
+                {
+                    let recorded_frontier = self
+                        .storage_controller
+                        .quarantined_error_history_upper()
+                        .clone();
+                    self.compute.quarantines.insert(*sink_id, recorded_frontier);
+                }
+            }
         }
 
         // Here we augment all imported sources and all exported sinks with with the appropriate
@@ -667,6 +720,7 @@ where
                         let conn = PersistSinkConnection {
                             value_desc: conn.value_desc,
                             storage_metadata: metadata,
+                            quarantine: conn.quarantine,
                         };
                         ComputeSinkConnection::Persist(conn)
                     }
@@ -1084,6 +1138,17 @@ where
             ComputeResponse::SubscribeResponse(id, response) => {
                 self.handle_subscribe_response(id, response, replica_id)
             }
+            ComputeResponse::HydrationStatuses(list) => {
+                self.handle_hydration_statuses(list, replica_id);
+                None
+            }
+            ComputeResponse::QuarantinedErrors(id, batch) => {
+                self.handle_quarantined_errors(id, batch, replica_id);
+                None
+            }
+            ComputeResponse::CompactionQueue(queue) => Some(
+                ComputeControllerResponse::CompactionQueue(replica_id, queue),
+            ),
         }
     }
 
@@ -1100,7 +1165,13 @@ where
                         .values()
                         .all(|frontier| frontier.is_empty())
                 {
-                    self.compute.collections.remove(&id);
+                    let collection = self.compute.collections.remove(&id).expect("must exist");
+                    self.compute.quarantines.remove(&id);
+                    for replica_id in collection.replica_hydration_statuses.into_keys() {
+                        self.compute.ready_responses.push_back(
+                            ComputeControllerResponse::HydrationStatus(id, replica_id, None),
+                        );
+                    }
                 }
             }
         }
@@ -1145,6 +1216,59 @@ where
         self.update_write_frontiers(replica_id, &updates);
     }
 
+    fn handle_hydration_statuses(
+        &mut self,
+        list: Vec<(GlobalId, HydrationStatus)>,
+        replica_id: ReplicaId,
+    ) {
+        for (id, status) in list {
+            let Ok(coll) = self.compute.collection_mut(id) else {
+                // The collection might have been dropped concurrently.
+                tracing::debug!(?replica_id, "Hydration status for unknown collection {id}");
+                continue;
+            };
+
+            let previous = coll
+                .replica_hydration_statuses
+                .insert(replica_id, status.clone());
+            if previous.as_ref() != Some(&status) {
+                self.compute
+                    .ready_responses
+                    .push_back(ComputeControllerResponse::HydrationStatus(
+                        id,
+                        replica_id,
+                        Some(status),
+                    ));
+            }
+        }
+    }
+
+    fn handle_quarantined_errors(
+        &mut self,
+        sink_id: GlobalId,
+        batch: QuarantinedErrorBatch<T>,
+        replica_id: ReplicaId,
+    ) {
+        let Some(frontier) = self.compute.quarantines.get_mut(&sink_id) else {
+            // The sink might have been dropped concurrently.
+            tracing::debug!(?replica_id, "Quarantined errors for unknown sink {sink_id}");
+            return;
+        };
+
+        // Record only the errors at times no replica has reported yet, to avoid recording
+        // duplicate errors.
+        if !PartialOrder::less_than(frontier, &batch.upper) {
+            return;
+        }
+        let lower = std::mem::replace(frontier, batch.upper);
+        let errors = batch
+            .errors
+            .into_iter()
+            .filter(|(time, _error, _input_key, _diff)| lower.less_equal(time))
+            .map(|(time, error, input_key, diff)| (sink_id, time, error, input_key, diff));
+        self.compute.quarantined_errors.extend(errors);
+    }
+
     fn handle_peek_response(
         &mut self,
         uuid: Uuid,
@@ -132,6 +132,7 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for Plan {
                 ids,
                 values,
                 limits,
+                semi_naive: _,
                 body,
             } => {
                 let bindings = izip!(ids.iter(), values, limits).collect_vec();
@@ -308,10 +309,67 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for Plan {
                             write!(f, " offset={}", plan.offset)?;
                         }
                     }
+                    TopKPlan::Ordered(plan) => {
+                        write!(f, "{}TopK::Ordered", ctx.indent)?;
+                        if plan.group_key.len() > 0 {
+                            let group_by = Indices(&plan.group_key);
+                            write!(f, " group_by=[{}]", group_by)?;
+                        }
+                        if plan.order_key.len() > 0 {
+                            let order_by = separated(", ", &plan.order_key);
+                            write!(f, " order_by=[{}]", order_by)?;
+                        }
+                        if let Some(limit) = &plan.limit {
+                            write!(f, " limit={}", limit)?;
+                        }
+                        if &plan.offset > &0 {
+                            write!(f, " offset={}", plan.offset)?;
+                        }
+                    }
                 }
                 writeln!(f)?;
                 ctx.indented(|ctx| input.fmt_text(f, ctx))?;
             }
+            Window {
+                input,
+                key_val_plan,
+                window_plan,
+                input_key,
+            } => {
+                use crate::plan::window::WindowFunc;
+                match &window_plan.func {
+                    WindowFunc::RowNumber => write!(f, "{}Window::RowNumber", ctx.indent)?,
+                    WindowFunc::Rank => write!(f, "{}Window::Rank", ctx.indent)?,
+                    WindowFunc::DenseRank => write!(f, "{}Window::DenseRank", ctx.indent)?,
+                    WindowFunc::LagLead { lag_lead, offset } => {
+                        write!(f, "{}Window::{:?} offset={}", ctx.indent, lag_lead, offset)?
+                    }
+                }
+                if window_plan.order_by.len() > 0 {
+                    let order_by = separated(", ", &window_plan.order_by);
+                    write!(f, " order_by=[{}]", order_by)?;
+                }
+                writeln!(f)?;
+                ctx.indented(|ctx| {
+                    if key_val_plan.val_plan.deref().is_identity() {
+                        writeln!(f, "{}val_plan=id", ctx.indent)?;
+                    } else {
+                        writeln!(f, "{}val_plan", ctx.indent)?;
+                        ctx.indented(|ctx| key_val_plan.val_plan.deref().fmt_text(f, ctx))?;
+                    }
+                    if key_val_plan.key_plan.deref().is_identity() {
+                        writeln!(f, "{}key_plan=id", ctx.indent)?;
+                    } else {
+                        writeln!(f, "{}key_plan", ctx.indent)?;
+                        ctx.indented(|ctx| key_val_plan.key_plan.deref().fmt_text(f, ctx))?;
+                    }
+                    if let Some(key) = input_key {
+                        let key = CompactScalarSeq(key);
+                        writeln!(f, "{}input_key={}", ctx.indent, key)?;
+                    }
+                    input.fmt_text(f, ctx)
+                })?;
+            }
             Negate { input } => {
                 writeln!(f, "{}Negate", ctx.indent)?;
                 ctx.indented(|ctx| input.fmt_text(f, ctx))?;
@@ -445,6 +503,17 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for LinearStagePlan {
             writeln!(f, "{}closure", ctx.indent)?;
             ctx.indented(|ctx| plan.closure.fmt_text(f, ctx))?;
         }
+        if let Some(padding) = plan.padding.as_ref() {
+            let lookup_key = CompactScalarSeq(&plan.lookup_key);
+            writeln!(
+                f,
+                "{}padding={{ arity={}, present_keys=[{}] }}",
+                ctx.indent, padding.lookup_arity, lookup_key
+            )?;
+            if !padding.closure.is_identity() {
+                ctx.indented(|ctx| padding.closure.fmt_text(f, ctx))?;
+            }
+        }
         {
             let lookup_relation = &plan.lookup_relation;
             let lookup_key = CompactScalarSeq(&plan.lookup_key);
@@ -502,6 +571,14 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for DeltaPathPlan {
             writeln!(f, "{}initial_closure", ctx.indent)?;
             ctx.indented(|ctx| plan.initial_closure.fmt_text(f, ctx))?;
         }
+        if let Some(arity) = plan.padded_source_arity {
+            let source_key = CompactScalarSeq(&plan.source_key);
+            writeln!(
+                f,
+                "{}padded_source={{ arity={}, present_keys=[{}] }}",
+                ctx.indent, arity, source_key
+            )?;
+        }
         {
             let source_relation = &plan.source_relation;
             let source_key = CompactScalarSeq(&plan.source_key);
@@ -526,6 +603,17 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for DeltaStagePlan {
             writeln!(f, "{}closure", ctx.indent)?;
             ctx.indented(|ctx| plan.closure.fmt_text(f, ctx))?;
         }
+        if let Some(padding) = plan.padding.as_ref() {
+            let lookup_key = CompactScalarSeq(&plan.lookup_key);
+            writeln!(
+                f,
+                "{}padding={{ arity={}, present_keys=[{}] }}",
+                ctx.indent, padding.lookup_arity, lookup_key
+            )?;
+            if !padding.closure.is_identity() {
+                ctx.indented(|ctx| padding.closure.fmt_text(f, ctx))?;
+            }
+        }
         {
             let lookup_relation = &plan.lookup_relation;
             let lookup_key = CompactScalarSeq(&plan.lookup_key);
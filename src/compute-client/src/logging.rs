@@ -220,6 +220,8 @@ pub enum ComputeLog {
     PeekDuration,
     FrontierDelay,
     ImportFrontierCurrent,
+    LetRecIterations,
+    LetRecDeltas,
 }
 
 impl RustType<ProtoComputeLog> for ComputeLog {
@@ -234,6 +236,8 @@ impl RustType<ProtoComputeLog> for ComputeLog {
                 ComputeLog::PeekDuration => PeekDuration(()),
                 ComputeLog::FrontierDelay => FrontierDelay(()),
                 ComputeLog::ImportFrontierCurrent => ImportFrontierCurrent(()),
+                ComputeLog::LetRecIterations => LetRecIterations(()),
+                ComputeLog::LetRecDeltas => LetRecDeltas(()),
             }),
         }
     }
@@ -248,6 +252,8 @@ impl RustType<ProtoComputeLog> for ComputeLog {
             Some(PeekDuration(())) => Ok(ComputeLog::PeekDuration),
             Some(FrontierDelay(())) => Ok(ComputeLog::FrontierDelay),
             Some(ImportFrontierCurrent(())) => Ok(ComputeLog::ImportFrontierCurrent),
+            Some(LetRecIterations(())) => Ok(ComputeLog::LetRecIterations),
+            Some(LetRecDeltas(())) => Ok(ComputeLog::LetRecDeltas),
             None => Err(TryFromProtoError::missing_field("ProtoComputeLog::kind")),
         }
     }
@@ -274,6 +280,8 @@ pub static DEFAULT_LOG_VARIANTS: Lazy<Vec<LogVariant>> = Lazy::new(|| {
         LogVariant::Compute(ComputeLog::FrontierDelay),
         LogVariant::Compute(ComputeLog::PeekCurrent),
         LogVariant::Compute(ComputeLog::PeekDuration),
+        LogVariant::Compute(ComputeLog::LetRecIterations),
+        LogVariant::Compute(ComputeLog::LetRecDeltas),
     ];
 
     default_logs
@@ -407,6 +415,21 @@ impl LogVariant {
             LogVariant::Compute(ComputeLog::PeekDuration) => RelationDesc::empty()
                 .with_column("worker_id", ScalarType::UInt64.nullable(false))
                 .with_column("duration_ns", ScalarType::UInt64.nullable(false)),
+
+            LogVariant::Compute(ComputeLog::LetRecIterations) => RelationDesc::empty()
+                .with_column("export_id", ScalarType::String.nullable(false))
+                .with_column("binding_id", ScalarType::String.nullable(false))
+                .with_column("worker_id", ScalarType::UInt64.nullable(false))
+                .with_column("iterations", ScalarType::UInt64.nullable(false))
+                .with_key(vec![0, 1, 2]),
+
+            LogVariant::Compute(ComputeLog::LetRecDeltas) => RelationDesc::empty()
+                .with_column("export_id", ScalarType::String.nullable(false))
+                .with_column("binding_id", ScalarType::String.nullable(false))
+                .with_column("worker_id", ScalarType::UInt64.nullable(false))
+                .with_column("iteration", ScalarType::UInt64.nullable(false))
+                .with_column("records", ScalarType::UInt64.nullable(false))
+                .with_key(vec![0, 1, 2, 3]),
         }
     }
 
@@ -456,6 +479,8 @@ impl LogVariant {
             LogVariant::Compute(ComputeLog::FrontierDelay) => vec![],
             LogVariant::Compute(ComputeLog::PeekCurrent) => vec![],
             LogVariant::Compute(ComputeLog::PeekDuration) => vec![],
+            LogVariant::Compute(ComputeLog::LetRecIterations) => vec![],
+            LogVariant::Compute(ComputeLog::LetRecDeltas) => vec![],
         }
     }
 }
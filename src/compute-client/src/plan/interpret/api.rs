@@ -31,6 +31,7 @@ use crate::plan::join::JoinPlan;
 use crate::plan::reduce::{KeyValPlan, ReducePlan};
 use crate::plan::threshold::ThresholdPlan;
 use crate::plan::top_k::TopKPlan;
+use crate::plan::window::WindowPlan;
 use crate::plan::{AvailableCollections, GetPlan, Plan};
 
 /// An [abstract interpreter] for [Plan] expressions.
@@ -103,6 +104,15 @@ pub trait Interpreter<T = mz_repr::Timestamp> {
         top_k_plan: &TopKPlan,
     ) -> Self::Domain;
 
+    fn window(
+        &self,
+        ctx: &Context<Self::Domain>,
+        input: Self::Domain,
+        key_val_plan: &KeyValPlan,
+        window_plan: &WindowPlan,
+        input_key: &Option<Vec<MirScalarExpr>>,
+    ) -> Self::Domain;
+
     fn negate(&self, ctx: &Context<Self::Domain>, input: Self::Domain) -> Self::Domain;
 
     fn threshold(
@@ -262,6 +272,7 @@ where
                     ids,
                     values,
                     limits,
+                    semi_naive: _,
                     body,
                 } => {
                     // Make context recursive and extend it with `bottom` for each recursive
@@ -388,6 +399,23 @@ where
                     // Interpret the current node.
                     Ok(self.interpret.top_k(&self.ctx, input, top_k_plan))
                 }
+                Window {
+                    input,
+                    key_val_plan,
+                    window_plan,
+                    input_key,
+                } => {
+                    // Descend recursively into all children.
+                    let input = self.apply_rec(input, rg)?;
+                    // Interpret the current node.
+                    Ok(self.interpret.window(
+                        &self.ctx,
+                        input,
+                        key_val_plan,
+                        window_plan,
+                        input_key,
+                    ))
+                }
                 Negate { input } => {
                     // Descend recursively into all children.
                     let input = self.apply_rec(input, rg)?;
@@ -518,6 +546,7 @@ where
                     ids,
                     values,
                     limits,
+                    semi_naive: _,
                     body,
                 } => {
                     // Make context recursive and extend it with `bottom` for each recursive
@@ -675,6 +704,27 @@ where
                     // Pass the interpretation result up.
                     Ok(result)
                 }
+                Window {
+                    input,
+                    key_val_plan,
+                    window_plan,
+                    input_key,
+                } => {
+                    // Descend recursively into all children.
+                    let input = self.apply_rec(input, rg)?;
+                    // Interpret the current node.
+                    let result = self.interpret.window(
+                        &self.ctx,
+                        input.clone(),
+                        key_val_plan,
+                        window_plan,
+                        input_key,
+                    );
+                    // Mutate the current node using the given `action`.
+                    (self.action)(expr, &result, &[input]);
+                    // Pass the interpretation result up.
+                    Ok(result)
+                }
                 Negate { input } => {
                     // Descend recursively into all children.
                     let input = self.apply_rec(input, rg)?;
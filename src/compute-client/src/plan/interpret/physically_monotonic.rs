@@ -24,6 +24,7 @@ use crate::plan::join::JoinPlan;
 use crate::plan::reduce::{KeyValPlan, ReducePlan};
 use crate::plan::threshold::ThresholdPlan;
 use crate::plan::top_k::TopKPlan;
+use crate::plan::window::WindowPlan;
 use crate::plan::{AvailableCollections, GetPlan};
 
 /// Represents a boolean physical monotonicity property, where the bottom value
@@ -197,6 +198,20 @@ impl<T> Interpreter<T> for SingleTimeMonotonic<'_, T> {
         PhysicallyMonotonic(!ctx.is_rec)
     }
 
+    fn window(
+        &self,
+        ctx: &Context<Self::Domain>,
+        _input: Self::Domain,
+        _key_val_plan: &KeyValPlan,
+        _window_plan: &WindowPlan,
+        _input_key: &Option<Vec<MirScalarExpr>>,
+    ) -> Self::Domain {
+        // The window operator consolidates its output at each time, and so produces
+        // physically monotonic output when exposed to a single time. We never plan
+        // it in recursive contexts.
+        PhysicallyMonotonic(!ctx.is_rec)
+    }
+
     fn negate(&self, _ctx: &Context<Self::Domain>, _input: Self::Domain) -> Self::Domain {
         // Negation produces retractions, so it breaks physical monotonicity.
         PhysicallyMonotonic(false)
@@ -17,16 +17,19 @@
 //! This implementation strategy allows us to re-use existing arrangements, and
 //! not create any new stateful operators.
 
+use std::collections::BTreeMap;
+
 use mz_expr::{
     join_permutations, permutation_for_arrangement, JoinInputCharacteristics, JoinInputMapper,
-    MapFilterProject, MirScalarExpr,
+    JoinKind, MapFilterProject, MirScalarExpr,
 };
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use proptest::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::plan::join::{
-    JoinBuildState, JoinClosure, ProtoDeltaJoinPlan, ProtoDeltaPathPlan, ProtoDeltaStagePlan,
+    JoinBuildState, JoinClosure, PaddingPlan, ProtoDeltaJoinPlan, ProtoDeltaPathPlan,
+    ProtoDeltaStagePlan,
 };
 use crate::plan::AvailableCollections;
 
@@ -85,6 +88,17 @@ pub struct DeltaPathPlan {
     ///
     /// Values of `None` indicate the identity closure.
     pub final_closure: Option<JoinClosure>,
+    /// For the paths of outer joins that correct the padding of other paths, the arity of the
+    /// source relation.
+    ///
+    /// These paths are seeded by the negated changes to the set of keys present in the source
+    /// arrangement, as records that consist of the key followed by a null for each column of
+    /// the source relation.
+    ///
+    /// Rendering such a path maintains an arrangement of the distinct keys present in the source
+    /// arrangement, which is shared with the stages of other paths that pad records looked up in
+    /// the same arrangement.
+    pub padded_source_arity: Option<usize>,
 }
 
 impl Arbitrary for DeltaPathPlan {
@@ -98,15 +112,24 @@ impl Arbitrary for DeltaPathPlan {
             any::<JoinClosure>(),
             prop::collection::vec(any::<DeltaStagePlan>(), 0..1),
             any::<Option<JoinClosure>>(),
+            any::<Option<usize>>(),
         )
             .prop_map(
-                |(source_relation, source_key, initial_closure, stage_plans, final_closure)| {
+                |(
+                    source_relation,
+                    source_key,
+                    initial_closure,
+                    stage_plans,
+                    final_closure,
+                    padded_source_arity,
+                )| {
                     DeltaPathPlan {
                         source_relation,
                         source_key,
                         initial_closure,
                         stage_plans,
                         final_closure,
+                        padded_source_arity,
                     }
                 },
             )
@@ -122,6 +145,7 @@ impl RustType<ProtoDeltaPathPlan> for DeltaPathPlan {
             initial_closure: Some(self.initial_closure.into_proto()),
             stage_plans: self.stage_plans.into_proto(),
             final_closure: self.final_closure.into_proto(),
+            padded_source_arity: self.padded_source_arity.into_proto(),
         }
     }
     fn from_proto(proto: ProtoDeltaPathPlan) -> Result<Self, TryFromProtoError> {
@@ -133,6 +157,7 @@ impl RustType<ProtoDeltaPathPlan> for DeltaPathPlan {
                 .into_rust_if_some("ProtoDeltaPathPlan::initial_closure")?,
             stage_plans: proto.stage_plans.into_rust()?,
             final_closure: proto.final_closure.into_rust()?,
+            padded_source_arity: proto.padded_source_arity.into_rust()?,
         })
     }
 }
@@ -155,6 +180,9 @@ pub struct DeltaStagePlan {
     /// The closure to apply to the concatenation of columns
     /// of the stream and lookup relations.
     pub closure: JoinClosure,
+    /// For the stages of outer joins, the plan for producing the stream
+    /// records that have no match in the lookup relation.
+    pub padding: Option<PaddingPlan>,
 }
 
 impl Arbitrary for DeltaStagePlan {
@@ -168,15 +196,17 @@ impl Arbitrary for DeltaStagePlan {
             prop::collection::vec(any::<usize>(), 0..3),
             prop::collection::vec(any::<MirScalarExpr>(), 0..3),
             any::<JoinClosure>(),
+            any::<Option<PaddingPlan>>(),
         )
             .prop_map(
-                |(lookup_relation, stream_key, stream_thinning, lookup_key, closure)| {
+                |(lookup_relation, stream_key, stream_thinning, lookup_key, closure, padding)| {
                     DeltaStagePlan {
                         lookup_relation,
                         stream_key,
                         stream_thinning,
                         lookup_key,
                         closure,
+                        padding,
                     }
                 },
             )
@@ -192,6 +222,7 @@ impl RustType<ProtoDeltaStagePlan> for DeltaStagePlan {
             stream_thinning: self.stream_thinning.into_proto(),
             lookup_key: self.lookup_key.into_proto(),
             closure: Some(self.closure.into_proto()),
+            padding: self.padding.into_proto(),
         }
     }
 
@@ -204,6 +235,7 @@ impl RustType<ProtoDeltaStagePlan> for DeltaStagePlan {
             closure: proto
                 .closure
                 .into_rust_if_some("ProtoDeltaStagePlan::closure")?,
+            padding: proto.padding.into_rust()?,
         })
     }
 }
@@ -216,27 +248,24 @@ impl DeltaJoinPlan {
         input_mapper: JoinInputMapper,
         map_filter_project: &mut MapFilterProject,
         available: &[AvailableCollections],
+        kind: JoinKind,
     ) -> (Self, Vec<AvailableCollections>) {
+        if kind == JoinKind::LeftOuter {
+            return Self::create_outer_from(
+                equivalences,
+                join_orders,
+                input_mapper,
+                map_filter_project,
+                available,
+            );
+        }
+
         let mut requested: Vec<AvailableCollections> =
             vec![Default::default(); input_mapper.total_inputs()];
         let number_of_inputs = input_mapper.total_inputs();
         assert_eq!(number_of_inputs, join_orders.len());
 
-        // Pick the "first" (by `Ord`) key for the source relation of each path.
-        // (This matches the probably arbitrary historical practice from `mod render`.)
-        let mut source_keys = vec![None; number_of_inputs];
-        for source_relation in 0..number_of_inputs {
-            for (lookup_relation, lookup_key, _characteristics) in &join_orders[source_relation] {
-                let key = &mut source_keys[*lookup_relation];
-                if key.is_none() || key.as_ref().unwrap() > lookup_key {
-                    *key = Some(lookup_key.clone())
-                }
-            }
-        }
-        let source_keys: Vec<_> = source_keys
-            .into_iter()
-            .map(|k| k.expect("There should be at least one arrangement for each relation!"))
-            .collect();
+        let source_keys = source_keys(join_orders);
 
         // Create an empty plan, with capacity for the intended number of path plans.
         let mut join_plan = DeltaJoinPlan {
@@ -349,6 +378,7 @@ impl DeltaJoinPlan {
                     lookup_key: lookup_key.clone(),
                     stream_thinning,
                     closure,
+                    padding: None,
                 });
             }
             // determine a final closure, and complete the path plan.
@@ -366,6 +396,180 @@ impl DeltaJoinPlan {
                 stage_plans,
                 final_closure,
                 source_key: source_key.to_vec(),
+                padded_source_arity: None,
+            });
+        }
+
+        // Now that `map_filter_project` has been captured in the state builder,
+        // assign the remaining temporal predicates to it, for the caller's use.
+        *map_filter_project = temporal_mfp;
+
+        (join_plan, requested)
+    }
+
+    /// Create a new plan for a left outer join from the required arguments.
+    ///
+    /// The path of the first input looks up each other input, padding the records without a
+    /// match with nulls. The path of each other input looks up the first input, and then the
+    /// remaining other inputs in the same manner. As records of the first input are padded only
+    /// while the other input has no matching records, each other input contributes an additional
+    /// path that retracts (or restores) the padded records when the input gains its first
+    /// (or loses its last) record with a key.
+    fn create_outer_from(
+        equivalences: &[Vec<MirScalarExpr>],
+        join_orders: &[Vec<(usize, Vec<MirScalarExpr>, Option<JoinInputCharacteristics>)>],
+        input_mapper: JoinInputMapper,
+        map_filter_project: &mut MapFilterProject,
+        available: &[AvailableCollections],
+    ) -> (Self, Vec<AvailableCollections>) {
+        let mut requested: Vec<AvailableCollections> =
+            vec![Default::default(); input_mapper.total_inputs()];
+        let number_of_inputs = input_mapper.total_inputs();
+        assert_eq!(number_of_inputs, join_orders.len());
+
+        let source_keys = source_keys(join_orders);
+
+        // The pairs of expressions on which each input is matched against the first input,
+        // in the global context.
+        let outer_keys = (0..number_of_inputs)
+            .map(|index| {
+                if index == 0 {
+                    return Vec::new();
+                }
+                input_mapper
+                    .outer_join_keys(equivalences, index)
+                    .into_iter()
+                    .map(|(p, o)| (p, input_mapper.map_expr_to_global(o, index)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut join_plan = DeltaJoinPlan {
+            path_plans: Vec::with_capacity(2 * number_of_inputs - 1),
+        };
+
+        let temporal_mfp = map_filter_project.extract_temporal();
+
+        for source_relation in 0..number_of_inputs {
+            // The keys of the other inputs, which are looked up along the path.
+            let lookup_keys = (1..number_of_inputs)
+                .filter(|index| *index != source_relation)
+                .flat_map(|index| outer_keys[index].iter().cloned());
+            // The keys of the source relation, which are used to look up the first input.
+            let source_outer_keys = outer_keys[source_relation]
+                .iter()
+                .map(|(p, o)| (o.clone(), p.clone()))
+                .collect::<Vec<_>>();
+            let order = &join_orders[source_relation];
+            let source_key = &source_keys[source_relation];
+
+            let mut join_build_state = JoinBuildState::new_outer(
+                input_mapper.global_columns(source_relation),
+                source_outer_keys.iter().cloned().chain(lookup_keys.clone()),
+                map_filter_project,
+            );
+            let (initial_permutation, initial_thinning) =
+                permutation_for_arrangement(source_key, input_mapper.input_arity(source_relation));
+            let initial_closure = join_build_state.extract_closure(
+                initial_permutation,
+                source_key.len() + initial_thinning.len(),
+            );
+            let unthinned_stream_arity = initial_closure.before.projection.len();
+            let stage_plans = plan_outer_stages(
+                &mut join_build_state,
+                order,
+                unthinned_stream_arity,
+                &source_outer_keys,
+                &outer_keys,
+                &input_mapper,
+                available,
+                &mut requested,
+            );
+            let final_closure = join_build_state.complete();
+            let final_closure = if final_closure.is_identity() {
+                None
+            } else {
+                Some(final_closure)
+            };
+            join_plan.path_plans.push(DeltaPathPlan {
+                source_relation,
+                source_key: source_key.clone(),
+                initial_closure,
+                stage_plans,
+                final_closure,
+                padded_source_arity: None,
+            });
+
+            if source_relation == 0 {
+                continue;
+            }
+
+            // The path that corrects the padding of the source relation. Its records are the
+            // present keys of the source relation followed by nulls for its columns, which we
+            // present as the (null) columns of the source relation. The first stage looks up
+            // the first input by the key columns.
+            let source_arity = input_mapper.input_arity(source_relation);
+            let key_arity = source_key.len();
+            let mut join_build_state = JoinBuildState::new_outer(
+                input_mapper.global_columns(source_relation),
+                lookup_keys,
+                map_filter_project,
+            );
+            let initial_closure = JoinClosure::identity(key_arity + source_arity);
+            let mut stage_plans = Vec::with_capacity(number_of_inputs);
+            let (first_relation, first_key, _characteristics) = &order[0];
+            assert_eq!(
+                *first_relation, 0,
+                "outer join paths first look up the first input"
+            );
+            let (lookup_permutation, lookup_thinning) =
+                lookup_arrangement(0, first_key, &input_mapper, available, &mut requested);
+            let stream_thinning = (key_arity..key_arity + source_arity).collect::<Vec<_>>();
+            let permutation = join_permutations(
+                key_arity,
+                (0..source_arity).map(|c| (c, key_arity + c)).collect(),
+                source_arity,
+                lookup_permutation,
+            );
+            // The keys of the source relation are not bound expressions, as its columns are null.
+            let closure = join_build_state.add_columns(
+                input_mapper.global_columns(0),
+                &[],
+                key_arity + source_arity + lookup_thinning.len(),
+                permutation,
+            );
+            let unthinned_stream_arity = closure.before.projection.len();
+            stage_plans.push(DeltaStagePlan {
+                lookup_relation: 0,
+                stream_key: (0..key_arity).map(MirScalarExpr::Column).collect(),
+                stream_thinning,
+                lookup_key: first_key.clone(),
+                closure,
+                padding: None,
+            });
+            stage_plans.extend(plan_outer_stages(
+                &mut join_build_state,
+                &order[1..],
+                unthinned_stream_arity,
+                &[],
+                &outer_keys,
+                &input_mapper,
+                available,
+                &mut requested,
+            ));
+            let final_closure = join_build_state.complete();
+            let final_closure = if final_closure.is_identity() {
+                None
+            } else {
+                Some(final_closure)
+            };
+            join_plan.path_plans.push(DeltaPathPlan {
+                source_relation,
+                source_key: source_key.clone(),
+                initial_closure,
+                stage_plans,
+                final_closure,
+                padded_source_arity: Some(source_arity),
             });
         }
 
@@ -376,3 +580,136 @@ impl DeltaJoinPlan {
         (join_plan, requested)
     }
 }
+
+/// Picks the "first" (by `Ord`) key for the source relation of each path.
+/// (This matches the probably arbitrary historical practice from `mod render`.)
+fn source_keys(
+    join_orders: &[Vec<(usize, Vec<MirScalarExpr>, Option<JoinInputCharacteristics>)>],
+) -> Vec<Vec<MirScalarExpr>> {
+    let mut source_keys = vec![None; join_orders.len()];
+    for order in join_orders {
+        for (lookup_relation, lookup_key, _characteristics) in order {
+            let key = &mut source_keys[*lookup_relation];
+            if key.is_none() || key.as_ref().unwrap() > lookup_key {
+                *key = Some(lookup_key.clone())
+            }
+        }
+    }
+    source_keys
+        .into_iter()
+        .map(|k| k.expect("There should be at least one arrangement for each relation!"))
+        .collect()
+}
+
+/// Returns the permutation and thinning of the arrangement of `lookup_relation` by `lookup_key`,
+/// and requests the arrangement if it is not available.
+fn lookup_arrangement(
+    lookup_relation: usize,
+    lookup_key: &[MirScalarExpr],
+    input_mapper: &JoinInputMapper,
+    available: &[AvailableCollections],
+    requested: &mut [AvailableCollections],
+) -> (BTreeMap<usize, usize>, Vec<usize>) {
+    available[lookup_relation]
+        .arranged
+        .iter()
+        .find_map(|(key, permutation, thinning)| {
+            if key == lookup_key {
+                Some((permutation.clone(), thinning.clone()))
+            } else {
+                None
+            }
+        })
+        .unwrap_or_else(|| {
+            let (permutation, thinning) =
+                permutation_for_arrangement(lookup_key, input_mapper.input_arity(lookup_relation));
+            requested[lookup_relation].arranged.push((
+                lookup_key.to_vec(),
+                permutation.clone(),
+                thinning.clone(),
+            ));
+            (permutation, thinning)
+        })
+}
+
+/// Plans the stages of a path of a left outer join.
+///
+/// Lookups into the first input match the records of the stream on `source_outer_keys`, the
+/// pairs of expressions of the source relation and of the first input. Lookups into other inputs
+/// match on `outer_keys`, and pad the records without a match.
+#[allow(clippy::too_many_arguments)]
+fn plan_outer_stages(
+    join_build_state: &mut JoinBuildState,
+    order: &[(usize, Vec<MirScalarExpr>, Option<JoinInputCharacteristics>)],
+    mut unthinned_stream_arity: usize,
+    source_outer_keys: &[(MirScalarExpr, MirScalarExpr)],
+    outer_keys: &[Vec<(MirScalarExpr, MirScalarExpr)>],
+    input_mapper: &JoinInputMapper,
+    available: &[AvailableCollections],
+    requested: &mut [AvailableCollections],
+) -> Vec<DeltaStagePlan> {
+    let mut stage_plans = Vec::with_capacity(order.len());
+    for (lookup_relation, lookup_key, _characteristics) in order {
+        let (lookup_permutation, lookup_thinning) = lookup_arrangement(
+            *lookup_relation,
+            lookup_key,
+            input_mapper,
+            available,
+            requested,
+        );
+        let lookup_key_rebased = lookup_key
+            .iter()
+            .map(|k| input_mapper.map_expr_to_global(k.clone(), *lookup_relation))
+            .collect::<Vec<_>>();
+        let keys = if *lookup_relation == 0 {
+            source_outer_keys
+        } else {
+            &outer_keys[*lookup_relation]
+        };
+        let stream_key = join_build_state.outer_stream_key(keys, &lookup_key_rebased);
+        let (stream_permutation, stream_thinning) =
+            permutation_for_arrangement(&stream_key, unthinned_stream_arity);
+        let key_arity = stream_key.len();
+
+        let (closure, padding) = if *lookup_relation == 0 {
+            // The first input is looked up like in an inner join, and the keys of the source
+            // relation are bound by the lookup.
+            let bound_expressions = keys.iter().map(|(o, _)| o.clone()).collect::<Vec<_>>();
+            let permutation = join_permutations(
+                key_arity,
+                stream_permutation,
+                stream_thinning.len(),
+                lookup_permutation,
+            );
+            let closure = join_build_state.add_columns(
+                input_mapper.global_columns(0),
+                &bound_expressions,
+                key_arity + stream_thinning.len() + lookup_thinning.len(),
+                permutation,
+            );
+            (closure, None)
+        } else {
+            let (closure, padding) = join_build_state.add_outer_columns(
+                input_mapper.global_columns(*lookup_relation),
+                &lookup_key_rebased,
+                key_arity,
+                stream_permutation,
+                stream_thinning.len(),
+                lookup_permutation,
+                lookup_thinning.len(),
+            );
+            (closure, Some(padding))
+        };
+        unthinned_stream_arity = closure.before.projection.len();
+
+        stage_plans.push(DeltaStagePlan {
+            lookup_relation: *lookup_relation,
+            stream_key,
+            stream_thinning,
+            lookup_key: lookup_key.clone(),
+            closure,
+            padding,
+        });
+    }
+    stage_plans
+}
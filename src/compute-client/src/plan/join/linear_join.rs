@@ -12,8 +12,8 @@
 use std::collections::BTreeMap;
 
 use mz_expr::{
-    join_permutations, permutation_for_arrangement, JoinInputCharacteristics, MapFilterProject,
-    MirScalarExpr,
+    join_permutations, permutation_for_arrangement, JoinInputCharacteristics, JoinKind,
+    MapFilterProject, MirScalarExpr,
 };
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use proptest::prelude::*;
@@ -22,7 +22,8 @@ use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
 use crate::plan::join::{
-    JoinBuildState, JoinClosure, ProtoLinearJoinPlan, ProtoLinearStagePlan, ProtoMirScalarVec,
+    JoinBuildState, JoinClosure, PaddingPlan, ProtoLinearJoinPlan, ProtoLinearStagePlan,
+    ProtoMirScalarVec,
 };
 use crate::plan::AvailableCollections;
 
@@ -129,6 +130,9 @@ pub struct LinearStagePlan {
     /// The closure to apply to the concatenation of the key columns,
     /// the stream value columns, and the lookup value colunms.
     pub closure: JoinClosure,
+    /// For the stages of outer joins, the plan for producing the stream
+    /// records that have no match in the lookup relation.
+    pub padding: Option<PaddingPlan>,
 }
 
 impl RustType<ProtoLinearStagePlan> for LinearStagePlan {
@@ -139,6 +143,7 @@ impl RustType<ProtoLinearStagePlan> for LinearStagePlan {
             stream_thinning: self.stream_thinning.into_proto(),
             lookup_key: self.lookup_key.into_proto(),
             closure: Some(self.closure.into_proto()),
+            padding: self.padding.into_proto(),
         }
     }
 
@@ -151,12 +156,17 @@ impl RustType<ProtoLinearStagePlan> for LinearStagePlan {
             closure: proto
                 .closure
                 .into_rust_if_some("ProtoLinearStagePlan::closure")?,
+            padding: proto.padding.into_rust()?,
         })
     }
 }
 
 impl LinearJoinPlan {
     /// Create a new join plan from the required arguments.
+    ///
+    /// Left outer joins must start from their first input, and look up each other input by the
+    /// keys on which it is matched against the first input.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_from(
         source_relation: usize,
         // When specified, a key and its corresponding permutation and thinning.
@@ -167,19 +177,45 @@ impl LinearJoinPlan {
         // An MFP to apply to the result of the join.
         mfp_above: &mut MapFilterProject,
         available: &[AvailableCollections],
+        kind: JoinKind,
     ) -> (Self, Vec<AvailableCollections>) {
         let mut requested: Vec<AvailableCollections> =
             vec![Default::default(); input_mapper.total_inputs()];
         // Temporal predicates cannot currently be pushed down, and so we extract them and
         // set `mfp` to be the temporal predicates at the end of the method.
         let temporal_mfp = mfp_above.extract_temporal();
+        // For outer joins, the pairs of expressions on which each input is matched against
+        // the first input, with the expressions of the input in the global context.
+        let outer_keys = (0..input_mapper.total_inputs())
+            .map(|index| match kind {
+                JoinKind::LeftOuter if index > 0 => input_mapper
+                    .outer_join_keys(equivalences, index)
+                    .into_iter()
+                    .map(|(p, o)| (p, input_mapper.map_expr_to_global(o, index)))
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .collect::<Vec<Vec<_>>>();
         // Construct initial join build state.
         // This state evolves as we build the join dataflow.
-        let mut join_build_state = JoinBuildState::new(
-            input_mapper.global_columns(source_relation),
-            equivalences,
-            mfp_above,
-        );
+        let mut join_build_state = match kind {
+            JoinKind::Inner => JoinBuildState::new(
+                input_mapper.global_columns(source_relation),
+                equivalences,
+                mfp_above,
+            ),
+            JoinKind::LeftOuter => {
+                assert_eq!(
+                    source_relation, 0,
+                    "outer joins start from their first input"
+                );
+                JoinBuildState::new_outer(
+                    input_mapper.global_columns(source_relation),
+                    outer_keys.iter().flatten().cloned(),
+                    mfp_above,
+                )
+            }
+        };
 
         // Determine if `available` contains the indicated source arrangement, and if not request it.
         if let Some(kpt) = source_arrangement {
@@ -242,21 +278,26 @@ impl LinearJoinPlan {
                     // the existing bound `columns`. If that cannot be done, the plan
                     // is irrecoverably defective and we panic.
                     // TODO: explicitly validate this before rendering.
-                    let stream_key = lookup_key_rebased
-                        .iter()
-                        .map(|expr| {
-                            let mut bound_expr = input_mapper
-                                .find_bound_expr(
-                                    expr,
-                                    &bound_inputs,
-                                    &join_build_state.equivalences,
-                                )
-                                .expect("Expression in join plan is not bound at time of use");
-                            // Rewrite column references to physical locations.
-                            bound_expr.permute_map(&join_build_state.column_map);
-                            bound_expr
-                        })
-                        .collect::<Vec<_>>();
+                    let stream_key = if kind == JoinKind::LeftOuter {
+                        join_build_state
+                            .outer_stream_key(&outer_keys[*lookup_relation], &lookup_key_rebased)
+                    } else {
+                        lookup_key_rebased
+                            .iter()
+                            .map(|expr| {
+                                let mut bound_expr = input_mapper
+                                    .find_bound_expr(
+                                        expr,
+                                        &bound_inputs,
+                                        &join_build_state.equivalences,
+                                    )
+                                    .expect("Expression in join plan is not bound at time of use");
+                                // Rewrite column references to physical locations.
+                                bound_expr.permute_map(&join_build_state.column_map);
+                                bound_expr
+                            })
+                            .collect::<Vec<_>>()
+                    };
                     let (stream_permutation, stream_thinning) =
                         permutation_for_arrangement(&stream_key, unthinned_stream_arity);
 
@@ -264,19 +305,36 @@ impl LinearJoinPlan {
                 });
 
             let key_arity = stream_key.len();
-            let permutation = join_permutations(
-                key_arity,
-                stream_permutation,
-                stream_thinning.len(),
-                lookup_permutation,
-            );
             // Introduce new columns and expressions they enable. Form a new closure.
-            let closure = join_build_state.add_columns(
-                input_mapper.global_columns(*lookup_relation),
-                &lookup_key_rebased,
-                key_arity + stream_thinning.len() + lookup_thinning.len(),
-                permutation,
-            );
+            let (closure, padding) = match kind {
+                JoinKind::Inner => {
+                    let permutation = join_permutations(
+                        key_arity,
+                        stream_permutation,
+                        stream_thinning.len(),
+                        lookup_permutation,
+                    );
+                    let closure = join_build_state.add_columns(
+                        input_mapper.global_columns(*lookup_relation),
+                        &lookup_key_rebased,
+                        key_arity + stream_thinning.len() + lookup_thinning.len(),
+                        permutation,
+                    );
+                    (closure, None)
+                }
+                JoinKind::LeftOuter => {
+                    let (closure, padding) = join_build_state.add_outer_columns(
+                        input_mapper.global_columns(*lookup_relation),
+                        &lookup_key_rebased,
+                        key_arity,
+                        stream_permutation,
+                        stream_thinning.len(),
+                        lookup_permutation,
+                        lookup_thinning.len(),
+                    );
+                    (closure, Some(padding))
+                }
+            };
             let new_unthinned_stream_arity = closure.before.projection.len();
 
             bound_inputs.push(*lookup_relation);
@@ -288,6 +346,7 @@ impl LinearJoinPlan {
                 stream_thinning,
                 lookup_key: lookup_key.to_vec(),
                 closure,
+                padding,
             });
             unthinned_stream_arity = new_unthinned_stream_arity;
         }
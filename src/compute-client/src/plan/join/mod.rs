@@ -29,7 +29,7 @@
 
 use std::collections::BTreeMap;
 
-use mz_expr::{MapFilterProject, MirScalarExpr};
+use mz_expr::{join_permutations, MapFilterProject, MirScalarExpr};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{Datum, Row, RowArena};
 use proptest::prelude::*;
@@ -122,6 +122,18 @@ impl RustType<ProtoJoinClosure> for JoinClosure {
 }
 
 impl JoinClosure {
+    /// A closure that neither filters nor transforms records of the given arity.
+    pub fn identity(arity: usize) -> Self {
+        Self {
+            ready_equivalences: Vec::new(),
+            before: MapFilterProject::new(arity)
+                .into_plan()
+                .unwrap()
+                .into_nontemporal()
+                .unwrap(),
+        }
+    }
+
     /// Applies per-row filtering and logic.
     #[inline(always)]
     pub fn apply<'a>(
@@ -285,6 +297,42 @@ impl JoinClosure {
     }
 }
 
+/// Instructions for producing the records of the stream of an outer join stage that have no
+/// matching records in the lookup relation.
+///
+/// These records are padded with a null for each column of the lookup relation.
+///
+/// Rendering a padding plan maintains an additional arrangement of the distinct keys present in
+/// the lookup relation, whose matches retract the padded records. Its size is proportional to the
+/// number of distinct keys rather than to the size of the lookup relation, and EXPLAIN reports it
+/// as the `present_keys` of the stage.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct PaddingPlan {
+    /// The arity of the lookup relation.
+    pub lookup_arity: usize,
+    /// The closure to apply to the concatenation of the key columns,
+    /// the stream value columns, and the nulls of the lookup relation.
+    pub closure: JoinClosure,
+}
+
+impl RustType<ProtoPaddingPlan> for PaddingPlan {
+    fn into_proto(&self) -> ProtoPaddingPlan {
+        ProtoPaddingPlan {
+            lookup_arity: self.lookup_arity.into_proto(),
+            closure: Some(self.closure.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoPaddingPlan) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            lookup_arity: proto.lookup_arity.into_rust()?,
+            closure: proto
+                .closure
+                .into_rust_if_some("ProtoPaddingPlan::closure")?,
+        })
+    }
+}
+
 /// Maintained state as we construct join dataflows.
 ///
 /// This state primarily tracks the *remaining* work that has not yet been applied to a
@@ -294,7 +342,7 @@ impl JoinClosure {
 /// filtering, expressions, projection) and the physical organization of the current stream
 /// of data, which columns may be partially assembled in non-standard locations and which
 /// may already have been partially subjected to logic we need to apply.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct JoinBuildState {
     /// Map from expected locations in extended output column reckoning to physical locations.
     column_map: BTreeMap<usize, usize>,
@@ -335,6 +383,100 @@ impl JoinBuildState {
         }
     }
 
+    /// Create a new join state for a left outer join.
+    ///
+    /// Instead of equivalences, the state tracks the pairs of expressions on which the optional
+    /// inputs that are yet to be looked up are matched against the first input. This keeps the
+    /// columns of the first input available until they are used as keys. The pairs are never
+    /// applied as predicates, as each pair is removed when its optional input is looked up.
+    fn new_outer(
+        columns: impl IntoIterator<Item = usize>,
+        keys: impl IntoIterator<Item = (MirScalarExpr, MirScalarExpr)>,
+        mfp: &MapFilterProject,
+    ) -> Self {
+        let mut column_map = BTreeMap::new();
+        for column in columns {
+            column_map.insert(column, column_map.len());
+        }
+        Self {
+            column_map,
+            equivalences: keys.into_iter().map(|(p, o)| vec![p, o]).collect(),
+            mfp: mfp.clone(),
+        }
+    }
+
+    /// Determines the stream key with which to look up an optional input of a left outer join.
+    ///
+    /// The `keys` are the pairs of expressions on which the input is matched against the first
+    /// input, and must correspond to `lookup_key`. The expressions of the first input must be
+    /// bound, and are rewritten to reference physical locations.
+    fn outer_stream_key(
+        &self,
+        keys: &[(MirScalarExpr, MirScalarExpr)],
+        lookup_key: &[MirScalarExpr],
+    ) -> Vec<MirScalarExpr> {
+        assert!(
+            keys.iter().map(|(_, o)| o).eq(lookup_key.iter()),
+            "Outer join lookup key does not match the join equivalences"
+        );
+        keys.iter()
+            .map(|(p, _)| {
+                let mut bound_expr = p.clone();
+                bound_expr.permute_map(&self.column_map);
+                bound_expr
+            })
+            .collect()
+    }
+
+    /// Present the columns of an optional input of a left outer join, and extract the closures
+    /// to apply to the matched and to the padded records of the stream.
+    ///
+    /// Padded records are the concatenation of the key columns, the stream value columns, and
+    /// a null for each column of the lookup relation.
+    #[allow(clippy::too_many_arguments)]
+    fn add_outer_columns(
+        &mut self,
+        new_columns: std::ops::Range<usize>,
+        bound_expressions: &[MirScalarExpr],
+        key_arity: usize,
+        stream_permutation: BTreeMap<usize, usize>,
+        thinned_stream_arity: usize,
+        lookup_permutation: BTreeMap<usize, usize>,
+        thinned_lookup_arity: usize,
+    ) -> (JoinClosure, PaddingPlan) {
+        let lookup_arity = new_columns.len();
+        let padding_permutation = join_permutations(
+            key_arity,
+            stream_permutation.clone(),
+            thinned_stream_arity,
+            (0..lookup_arity).map(|c| (c, key_arity + c)).collect(),
+        );
+        // Both closures see the same columns, and so leave the state in the same shape.
+        let padding_closure = self.clone().add_columns(
+            new_columns.clone(),
+            bound_expressions,
+            key_arity + thinned_stream_arity + lookup_arity,
+            padding_permutation,
+        );
+        let permutation = join_permutations(
+            key_arity,
+            stream_permutation,
+            thinned_stream_arity,
+            lookup_permutation,
+        );
+        let closure = self.add_columns(
+            new_columns,
+            bound_expressions,
+            key_arity + thinned_stream_arity + thinned_lookup_arity,
+            permutation,
+        );
+        let padding = PaddingPlan {
+            lookup_arity,
+            closure: padding_closure,
+        };
+        (closure, padding)
+    }
+
     /// Present new columns and extract any newly available closure.
     fn add_columns(
         &mut self,
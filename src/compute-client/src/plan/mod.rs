@@ -17,8 +17,9 @@ use std::num::NonZeroU64;
 use itertools::Itertools;
 use mz_expr::JoinImplementation::{DeltaQuery, Differential, IndexedFilter, Unimplemented};
 use mz_expr::{
-    permutation_for_arrangement, CollectionPlan, EvalError, Id, JoinInputMapper, LetRecLimit,
-    LocalId, MapFilterProject, MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, TableFunc,
+    permutation_for_arrangement, CollectionPlan, EvalError, Id, JoinInputMapper, JoinKind,
+    LetRecLimit, LocalId, MapFilterProject, MirRelationExpr, MirScalarExpr,
+    OptimizedMirRelationExpr, TableFunc,
 };
 use mz_ore::soft_panic_or_log;
 use mz_ore::str::Indent;
@@ -33,18 +34,21 @@ use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
 use crate::plan::join::{DeltaJoinPlan, JoinPlan, LinearJoinPlan};
-use crate::plan::reduce::{KeyValPlan, ReducePlan};
+use crate::plan::reduce::{BasicPlan, KeyValPlan, ReducePlan};
 use crate::plan::threshold::ThresholdPlan;
 use crate::plan::top_k::TopKPlan;
 use crate::plan::transform::{Transform, TransformConfig};
+use crate::plan::window::WindowPlan;
 use crate::types::dataflows::{BuildDesc, DataflowDescription};
 
 pub mod interpret;
 pub mod join;
 pub mod reduce;
+pub mod semi_naive;
 pub mod threshold;
 pub mod top_k;
 pub mod transform;
+pub mod window;
 
 include!(concat!(env!("OUT_DIR"), "/mz_compute_client.plan.rs"));
 
@@ -217,6 +221,10 @@ pub enum Plan<T = mz_repr::Timestamp> {
         values: Vec<Plan<T>>,
         /// Maximum number of iterations. See further info on the MIR `LetRec`.
         limits: Vec<Option<LetRecLimit>>,
+        /// Whether the binding to `id` holds the records newly derived in each iteration by a
+        /// semi-naively evaluated binding, which is bound to the next `id`. See
+        /// [`semi_naive::plan_semi_naive`].
+        semi_naive: Vec<bool>,
         /// The collection that results, which is allowed to contain `Get` stages
         /// that reference `Id::Local(id)`.
         body: Box<Plan<T>>,
@@ -302,6 +310,25 @@ pub enum Plan<T = mz_repr::Timestamp> {
         /// out the documentation for this type for more detail.
         top_k_plan: TopKPlan,
     },
+    /// Incrementally maintained window function.
+    ///
+    /// This stage replaces a `Reduce` that evaluates a window function over each partition,
+    /// followed by a `FlatMap` that unnests the per-partition results. Each output record
+    /// contains the partition key followed by the window function's result for one input
+    /// record, i.e., it corresponds to the output of the `FlatMap` without the list column.
+    Window {
+        /// The input collection.
+        input: Box<Plan<T>>,
+        /// A plan for changing input records into key, value pairs.
+        ///
+        /// The keys identify partitions, and the values are the inputs to the window function.
+        key_val_plan: KeyValPlan,
+        /// A plan for maintaining the window function.
+        window_plan: WindowPlan,
+        /// The particular arrangement of the input we expect to use,
+        /// if any
+        input_key: Option<Vec<MirScalarExpr>>,
+    },
     /// Inverts the sign of each update.
     Negate {
         /// The input collection.
@@ -377,6 +404,7 @@ impl<T> Plan<T> {
             | FlatMap { input, .. }
             | Reduce { input, .. }
             | TopK { input, .. }
+            | Window { input, .. }
             | Negate { input }
             | Threshold { input, .. }
             | ArrangeBy { input, .. } => {
@@ -501,6 +529,22 @@ impl Arbitrary for Plan {
                         top_k_plan,
                     })
                     .boxed(),
+                //Plan::Window
+                (
+                    inner.clone(),
+                    any::<KeyValPlan>(),
+                    any::<WindowPlan>(),
+                    any::<Option<Vec<MirScalarExpr>>>(),
+                )
+                    .prop_map(
+                        |(input, key_val_plan, window_plan, input_key)| Plan::Window {
+                            input: input.into(),
+                            key_val_plan,
+                            window_plan,
+                            input_key,
+                        },
+                    )
+                    .boxed(),
                 //Plan::Negate
                 inner
                     .clone()
@@ -605,10 +649,12 @@ impl RustType<ProtoPlan> for Plan {
                     ids,
                     values,
                     limits,
+                    semi_naive,
                     body,
                 } => LetRec(
                     ProtoPlanLetRec {
                         ids: ids.into_proto(),
+                        semi_naive: semi_naive.clone(),
                         limits: limits
                             .clone()
                             .into_iter()
@@ -686,6 +732,20 @@ impl RustType<ProtoPlan> for Plan {
                     }
                     .into(),
                 ),
+                Plan::Window {
+                    input,
+                    key_val_plan,
+                    window_plan,
+                    input_key,
+                } => Window(
+                    ProtoPlanWindow {
+                        input: Some(input.into_proto()),
+                        key_val_plan: Some(key_val_plan.into_proto()),
+                        window_plan: Some(window_plan.into_proto()),
+                        input_key: input_k_into(input_key.as_ref()),
+                    }
+                    .into(),
+                ),
                 Plan::Negate { input } => Negate(input.into_proto()),
                 Plan::Threshold {
                     input,
@@ -775,6 +835,7 @@ impl RustType<ProtoPlan> for Plan {
                 let values: Vec<Plan> = proto.values.into_rust()?;
                 let limits_raw: Vec<LetRecLimit> = proto.limits.into_rust()?;
                 let limit_is_some: Vec<bool> = proto.limit_is_some.into_rust()?;
+                let semi_naive = proto.semi_naive;
                 assert_eq!(ids.len(), values.len());
                 assert_eq!(ids.len(), limits_raw.len());
                 assert_eq!(ids.len(), limit_is_some.len());
@@ -792,10 +853,12 @@ impl RustType<ProtoPlan> for Plan {
                     )
                     .collect_vec();
                 assert_eq!(ids.len(), limits.len());
+                assert_eq!(ids.len(), semi_naive.len());
                 Plan::LetRec {
                     ids,
                     values,
                     limits,
+                    semi_naive,
                     body: proto.body.into_rust_if_some("ProtoPlanLetRec::body")?,
                 }
             }
@@ -829,6 +892,16 @@ impl RustType<ProtoPlan> for Plan {
                     .top_k_plan
                     .into_rust_if_some("ProtoPlanTopK::top_k_plan")?,
             },
+            Window(proto) => Plan::Window {
+                input: proto.input.into_rust_if_some("ProtoPlanWindow::input")?,
+                key_val_plan: proto
+                    .key_val_plan
+                    .into_rust_if_some("ProtoPlanWindow::key_val_plan")?,
+                window_plan: proto
+                    .window_plan
+                    .into_rust_if_some("ProtoPlanWindow::window_plan")?,
+                input_key: input_k_try_into(proto.input_key)?,
+            },
             Negate(proto) => Plan::Negate {
                 input: proto.into_rust()?,
             },
@@ -1218,11 +1291,13 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                                 ids,
                                 values,
                                 limits,
+                                semi_naive,
                                 body,
                             } => Plan::LetRec {
                                 ids,
                                 values,
                                 limits,
+                                semi_naive,
                                 body: Box::new(Plan::ArrangeBy {
                                     input: body,
                                     forms,
@@ -1254,12 +1329,16 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                 for id in ids.iter() {
                     arrangements.remove(&Id::Local(*id));
                 }
+                // Bindings that hold the records newly derived by semi-naively evaluated
+                // bindings are marked once the entire dataflow object has been lowered.
+                let semi_naive = vec![false; ids.len()];
                 // Return the plan, and any `body` arrangements.
                 (
                     Plan::LetRec {
                         ids: ids.clone(),
                         values: lir_values,
                         limits: limits.clone(),
+                        semi_naive,
                         body: Box::new(body),
                     },
                     b_keys,
@@ -1300,6 +1379,7 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                 inputs,
                 equivalences,
                 implementation,
+                kind,
             } => {
                 let input_mapper = JoinInputMapper::new(inputs);
 
@@ -1340,6 +1420,7 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                             input_mapper,
                             &mut mfp,
                             &input_keys,
+                            JoinKind::Inner,
                         );
                         (JoinPlan::Linear(ljp), missing)
                     }
@@ -1359,6 +1440,7 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                             input_mapper,
                             &mut mfp,
                             &input_keys,
+                            *kind,
                         );
                         (JoinPlan::Linear(ljp), missing)
                     }
@@ -1369,6 +1451,7 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                             input_mapper,
                             &mut mfp,
                             &input_keys,
+                            *kind,
                         );
                         (JoinPlan::Delta(djp), missing)
                     }
@@ -1729,6 +1812,8 @@ This is not expected to cause incorrect results, but could indicate a performanc
         desc: DataflowDescription<OptimizedMirRelationExpr>,
         enable_consolidate_after_union_negate: bool,
         enable_monotonic_oneshot_selects: bool,
+        enable_incremental_window_functions: bool,
+        ordered_top_k_threshold: Option<usize>,
     ) -> Result<DataflowDescription<Self>, String> {
         // First, we lower the dataflow description from MIR to LIR.
         let mut dataflow = Self::lower_dataflow(desc)?;
@@ -1736,6 +1821,10 @@ This is not expected to cause incorrect results, but could indicate a performanc
         // Subsequently, we perform plan refinements for the dataflow.
         Self::refine_source_mfps(&mut dataflow);
 
+        if enable_incremental_window_functions {
+            Self::refine_window_functions(&mut dataflow);
+        }
+
         if enable_consolidate_after_union_negate {
             Self::refine_union_negate_consolidation(&mut dataflow);
         }
@@ -1768,6 +1857,12 @@ This is not expected to cause incorrect results, but could indicate a performanc
             Self::refine_single_time_consolidation(&mut dataflow, &config)?;
         }
 
+        // Ordered TopK plans are only considered for the basic plans that were not upgraded to
+        // monotonic plans above.
+        if let Some(threshold) = ordered_top_k_threshold {
+            Self::refine_ordered_top_k(&mut dataflow, threshold);
+        }
+
         mz_repr::explain::trace_plan(&dataflow);
 
         Ok(dataflow)
@@ -1814,14 +1909,18 @@ This is not expected to cause incorrect results, but could indicate a performanc
         // Build each object in order, registering the arrangements it forms.
         let mut objects_to_build = Vec::with_capacity(desc.objects_to_build.len());
         for build in desc.objects_to_build.into_iter() {
-            let (plan, keys) = Self::from_mir(
-                &build.plan,
+            // Monotone recursive bindings are evaluated on the records they newly derive.
+            let mut expr = build.plan.into_inner();
+            let deltas = semi_naive::plan_semi_naive(&mut expr);
+            let (mut plan, keys) = Self::from_mir(
+                &expr,
                 &mut arrangements,
                 LirDebugInfo {
                     debug_name: &desc.debug_name,
                     id: build.id,
                 },
             )?;
+            semi_naive::mark_semi_naive(&mut plan, &deltas);
             arrangements.insert(Id::Global(build.id), keys);
             objects_to_build.push(BuildDesc { id: build.id, plan });
         }
@@ -1931,6 +2030,164 @@ This is not expected to cause incorrect results, but could indicate a performanc
         mz_repr::explain::trace_plan(dataflow);
     }
 
+    /// Replaces the evaluation of window functions over entire partitions by `Window` stages
+    /// that maintain the partitions incrementally, for those window functions that support it.
+    ///
+    /// The `Window` stage processes updates in time order, which requires totally ordered
+    /// times. This is not the case within the values of `LetRec` stages, which we leave alone.
+    #[tracing::instrument(
+        target = "optimizer",
+        level = "debug",
+        skip_all,
+        fields(path.segment = "refine_window_functions")
+    )]
+    fn refine_window_functions(dataflow: &mut DataflowDescription<Self>) {
+        for build_desc in dataflow.objects_to_build.iter_mut() {
+            let mut todo = vec![&mut build_desc.plan];
+            while let Some(expression) = todo.pop() {
+                match expression {
+                    Plan::LetRec { body, .. } => {
+                        // Only the non-recursive `body` is evaluated at totally ordered times.
+                        todo.push(body);
+                    }
+                    _ => {
+                        if let Some(window) = Self::window_from_flat_map(expression) {
+                            *expression = window;
+                        }
+                        todo.extend(expression.children_mut());
+                    }
+                }
+            }
+        }
+        mz_repr::explain::trace_plan(dataflow);
+    }
+
+    /// Replaces basic TopK plans that skip or produce more than `threshold` rows per group by
+    /// ordered plans, which maintain each group in order rather than re-sorting it at every
+    /// stage.
+    ///
+    /// Ordered plans process updates in time order, which requires totally ordered times.
+    /// This is not the case within the values of `LetRec` stages, which we leave alone.
+    #[tracing::instrument(
+        target = "optimizer",
+        level = "debug",
+        skip_all,
+        fields(path.segment = "refine_ordered_top_k")
+    )]
+    fn refine_ordered_top_k(dataflow: &mut DataflowDescription<Self>, threshold: usize) {
+        for build_desc in dataflow.objects_to_build.iter_mut() {
+            let mut todo = vec![&mut build_desc.plan];
+            while let Some(expression) = todo.pop() {
+                match expression {
+                    Plan::LetRec { body, .. } => {
+                        // Only the non-recursive `body` is evaluated at totally ordered times.
+                        todo.push(body);
+                    }
+                    Plan::TopK { top_k_plan, .. } => {
+                        top_k_plan.as_ordered(threshold);
+                        todo.extend(expression.children_mut());
+                    }
+                    _ => {
+                        todo.extend(expression.children_mut());
+                    }
+                }
+            }
+        }
+        mz_repr::explain::trace_plan(dataflow);
+    }
+
+    /// Returns a `Window` stage equivalent to `plan`, if `plan` is a `FlatMap` unnesting the
+    /// results of a `Reduce` that evaluates a window function with an incremental
+    /// implementation.
+    ///
+    /// The `FlatMap` may also read the `Reduce` through an `Mfp` that only projects its output,
+    /// which is how the partition keys are removed when the query does not refer to them.
+    ///
+    /// The `Reduce` input is moved into the returned plan, leaving an empty constant in its
+    /// place, so `plan` should be replaced by the result.
+    fn window_from_flat_map(plan: &mut Self) -> Option<Self> {
+        let Plan::FlatMap {
+            input,
+            func: TableFunc::UnnestList { .. },
+            exprs,
+            mfp,
+            input_key: _,
+        } = plan
+        else {
+            return None;
+        };
+        let (reduce, projection) = match &mut **input {
+            Plan::Mfp {
+                input,
+                mfp,
+                input_key_val: _,
+            } => {
+                let (map, filter, projection) = mfp.as_map_filter_project();
+                if !map.is_empty() || !filter.is_empty() {
+                    return None;
+                }
+                (&mut **input, Some(projection))
+            }
+            reduce => (reduce, None),
+        };
+        let Plan::Reduce {
+            input: reduce_input,
+            key_val_plan,
+            plan: ReducePlan::Basic(BasicPlan::Single(_, aggr)),
+            input_key,
+        } = reduce
+        else {
+            return None;
+        };
+
+        // The list produced by the reduction must only be used as the input to the `FlatMap`.
+        let key_arity = key_val_plan.key_plan.projection.len();
+        let projection = projection.unwrap_or_else(|| (0..=key_arity).collect());
+        let [MirScalarExpr::Column(list)] = exprs[..] else {
+            return None;
+        };
+        if projection.get(list) != Some(&key_arity) {
+            return None;
+        }
+        let demand = mfp.demand();
+        if demand
+            .iter()
+            .any(|column| projection.get(*column) == Some(&key_arity))
+        {
+            return None;
+        }
+        let window_plan = WindowPlan::create_from(aggr)?;
+
+        // The `Window` stage omits the list column, so we shift the unnested column into its place
+        // and resolve the remaining columns through the projection of the reduction's output.
+        let mut mfp = mfp.clone();
+        let shuffle = demand
+            .into_iter()
+            .map(|column| match projection.get(column) {
+                Some(key) => (column, *key),
+                None => (column, key_arity),
+            })
+            .collect();
+        mfp.permute(shuffle, key_arity + 1);
+
+        let reduce_input = std::mem::replace(
+            &mut **reduce_input,
+            Plan::Constant {
+                rows: Ok(Vec::new()),
+            },
+        );
+        Some(Plan::Mfp {
+            input: Box::new(Plan::Window {
+                input: Box::new(reduce_input),
+                key_val_plan: key_val_plan.clone(),
+                window_plan,
+                input_key: input_key.clone(),
+            }),
+            mfp,
+            input_key_val: None,
+        })
+    }
+
     /// Refines the plans of objects to be built as part of `dataflow` to take advantage
     /// of monotonic operators if the dataflow refers to a single-time, i.e., is for a
     /// one-shot SELECT query.
@@ -2067,6 +2324,7 @@ This is not expected to cause incorrect results, but could indicate a performanc
                     ids,
                     values,
                     limits,
+                    semi_naive,
                     body,
                 } => {
                     let mut values_parts: Vec<Vec<Self>> = vec![Vec::new(); parts];
@@ -2083,6 +2341,7 @@ This is not expected to cause incorrect results, but could indicate a performanc
                             values,
                             body: Box::new(body),
                             limits: limits.clone(),
+                            semi_naive: semi_naive.clone(),
                             ids: ids.clone(),
                         })
                         .collect()
@@ -2157,6 +2416,21 @@ This is not expected to cause incorrect results, but could indicate a performanc
                         top_k_plan: top_k_plan.clone(),
                     })
                     .collect(),
+                Plan::Window {
+                    input,
+                    key_val_plan,
+                    window_plan,
+                    input_key,
+                } => input
+                    .partition_among(parts)
+                    .into_iter()
+                    .map(|input| Plan::Window {
+                        input: Box::new(input),
+                        key_val_plan: key_val_plan.clone(),
+                        window_plan: window_plan.clone(),
+                        input_key: input_key.clone(),
+                    })
+                    .collect(),
                 Plan::Negate { input } => input
                     .partition_among(parts)
                     .into_iter()
@@ -2237,6 +2511,7 @@ impl<T> CollectionPlan for Plan<T> {
                 ids: _,
                 values,
                 limits: _,
+                semi_naive: _,
                 body,
             } => {
                 for value in values.iter() {
@@ -2281,6 +2556,12 @@ impl<T> CollectionPlan for Plan<T> {
                 input,
                 top_k_plan: _,
             }
+            | Plan::Window {
+                input,
+                key_val_plan: _,
+                window_plan: _,
+                input_key: _,
+            }
             | Plan::Negate { input }
             | Plan::Threshold {
                 input,
@@ -33,6 +33,8 @@
 //!    example of an accumulable reduction, and when some element `x` is removed from the set
 //!    of elements being summed, we can introduce `-x` to incrementally maintain the sum. More
 //!    formally, accumulable reductions correspond to instances of commutative Abelian groups.
+//!    `bit_and()`, `bit_or()` and `bit_xor()` are accumulable as well, as they can be recovered
+//!    from the number of values that have each bit set.
 //! 2. Hierarchical:
 //!    Hierarchical reductions don't have a meaningful negation like accumulable reductions do, but
 //!    they are still commutative and associative, which lets us compute the reduction over subsets
@@ -43,11 +45,25 @@
 //!    hierarchical reductions correspond to instances of semigroups, in that they are associative,
 //!    but in order to benefit from being computed hierarchically, they need to have some reduction
 //!    in data size as well. A function like "concat-everything-to-a-string" wouldn't benefit from
-//!    hierarchical evaluation.
+//!    hierarchical evaluation. `approx_count_distinct` is also hierarchical, but the results
+//!    it carries between stages are HyperLogLog sketches of subsets of the input, which are
+//!    only turned into an estimate by the last stage. A value cannot be removed from a sketch,
+//!    but each stage recomputes the sketches of the buckets an update falls in from their
+//!    current inputs, so retractions are handled like any other update.
+//!
+//!    The order statistics `percentile_cont`, `percentile_disc`, and `mode` are planned as
+//!    hierarchical reductions as well, but each is rendered as a tree of buckets of its values,
+//!    whose counts are kept in arrangements. For percentiles, the buckets partition the values
+//!    in order, and a percentile is located by descending from the root, at each level
+//!    following the child whose counts cover its position. For `mode`, every bucket keeps the
+//!    most frequent value of its children. An update to the input thus only revisits one bucket
+//!    per level, rather than the whole group.
 //!
 //!    When the input is append-only, or monotonic, reductions that would otherwise have to be computed
 //!    hierarchically can instead be computed in-place, because we only need to keep the value that's
-//!    better than the "best" (minimal or maximal for min and max) seen so far.
+//!    better than the "best" (minimal or maximal for min and max) seen so far. For
+//!    `approx_count_distinct`, this merges each new value into the sketch of its group, which
+//!    needs no retractions.
 //! 3. Basic:
 //!    Basic reductions are a bit like the Hufflepuffs of this trifecta. They are neither accumulable nor
 //!    hierarchical (most likely they are associative but don't involve any data reduction) and so for these
@@ -600,7 +616,7 @@ impl ReducePlan {
         // We need to make sure that each list of aggregates by type forms
         // a subsequence of the overall sequence of aggregates.
         for index in 0..aggregates.len() {
-            let typ = reduction_type(&aggregates[index].func);
+            let typ = reduction_type(&aggregates[index]);
             let aggregates_list = reduction_types.entry(typ).or_insert_with(Vec::new);
             aggregates_list.push((index, aggregates[index].clone()));
         }
@@ -629,10 +645,7 @@ impl ReducePlan {
 
         // Construct a mapping from output_position -> reduction that we can
         // use to reconstruct the output in the correct order.
-        let aggregate_types = aggregates
-            .iter()
-            .map(|a| reduction_type(&a.func))
-            .collect::<Vec<_>>();
+        let aggregate_types = aggregates.iter().map(reduction_type).collect::<Vec<_>>();
 
         collation.aggregate_types = aggregate_types;
 
@@ -873,8 +886,8 @@ pub fn convert_indexes_to_skips(mut indexes: Vec<usize>) -> Vec<usize> {
 /// significant input data). Hierarchical aggregates can be rendered more efficiently if the
 /// input stream is append-only as then we only need to retain the "currently winning" value.
 /// Every hierarchical aggregate needs to supply a corresponding ReductionMonoid implementation.
-fn reduction_type(func: &AggregateFunc) -> ReductionType {
-    match func {
+fn reduction_type(aggr: &AggregateExpr) -> ReductionType {
+    match &aggr.func {
         AggregateFunc::SumInt16
         | AggregateFunc::SumInt32
         | AggregateFunc::SumInt64
@@ -887,6 +900,16 @@ fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::Count
         | AggregateFunc::Any
         | AggregateFunc::All
+        | AggregateFunc::Xor
+        | AggregateFunc::BitAndInt16
+        | AggregateFunc::BitAndInt32
+        | AggregateFunc::BitAndInt64
+        | AggregateFunc::BitOrInt16
+        | AggregateFunc::BitOrInt32
+        | AggregateFunc::BitOrInt64
+        | AggregateFunc::BitXorInt16
+        | AggregateFunc::BitXorInt32
+        | AggregateFunc::BitXorInt64
         | AggregateFunc::Dummy => ReductionType::Accumulable,
         AggregateFunc::MaxNumeric
         | AggregateFunc::MaxInt16
@@ -917,7 +940,19 @@ fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::MinString
         | AggregateFunc::MinDate
         | AggregateFunc::MinTimestamp
-        | AggregateFunc::MinTimestampTz => ReductionType::Hierarchical,
+        | AggregateFunc::MinTimestampTz
+        | AggregateFunc::ApproxCountDistinct => ReductionType::Hierarchical,
+        // Order statistics depend on the multiplicities of their inputs, which hierarchical
+        // reductions do not discard for distinct aggregations.
+        AggregateFunc::PercentileCont { .. }
+        | AggregateFunc::PercentileDisc { .. }
+        | AggregateFunc::Mode { .. } => {
+            if aggr.distinct {
+                ReductionType::Basic
+            } else {
+                ReductionType::Hierarchical
+            }
+        }
         AggregateFunc::JsonbAgg { .. }
         | AggregateFunc::JsonbObjectAgg { .. }
         | AggregateFunc::ArrayConcat { .. }
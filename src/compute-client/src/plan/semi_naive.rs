@@ -0,0 +1,364 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Semi-naive evaluation of recursive bindings.
+//!
+//! A `WITH MUTUALLY RECURSIVE` binding is evaluated by repeatedly applying its definition to the
+//! contents the binding had in the previous iteration. For a binding `r = distinct(f(r))` where
+//! `f` is a union of joins that only refers to `r` positively, each iteration derives all records
+//! of the previous iteration anew, and the work of an iteration is proportional to the entire
+//! contents of `r` rather than to the records that changed.
+//!
+//! We rewrite such bindings to carry the records newly derived in each iteration in an additional
+//! binding `d`, bound right before `r`:
+//!
+//! ```text
+//! d = threshold(distinct(f'(r, d)) - r)
+//! r = r + d
+//! ```
+//!
+//! where `f'` replaces each join that refers to `r` by a union of copies of the join, each of
+//! which reads `d` in place of one of the inputs that refer to `r`. Each iteration thus joins only
+//! the records derived in the previous iteration with the accumulated contents of `r`. As `f` is
+//! monotone, the contents of `r` are the same as those of the original binding in each iteration,
+//! which means that the recursion limits of the original binding continue to apply to `r`.
+
+use std::collections::BTreeSet;
+
+use mz_expr::{Id, LetRecLimit, LocalId, MirRelationExpr};
+
+use crate::plan::Plan;
+
+/// Rewrites the monotone recursive bindings in `expr` to be evaluated semi-naively.
+///
+/// Returns the identifiers of the bindings that hold the records newly derived in each iteration
+/// by a semi-naively evaluated binding, which is always bound right after them.
+pub fn plan_semi_naive(expr: &mut MirRelationExpr) -> BTreeSet<LocalId> {
+    let mut next_id = next_local_id(expr);
+    let mut deltas = BTreeSet::new();
+    expr.visit_pre_mut(|expr| {
+        if let MirRelationExpr::LetRec {
+            ids,
+            values,
+            limits,
+            ..
+        } = expr
+        {
+            let mut index = 0;
+            while index < ids.len() {
+                let id = ids[index];
+                let delta = LocalId::new(next_id);
+                if let Some(delta_value) = plan_delta(ids, values, index, &delta) {
+                    next_id += 1;
+
+                    let typ = values[index].typ();
+                    let get_id = MirRelationExpr::Get {
+                        id: Id::Local(id),
+                        typ: typ.clone(),
+                    };
+                    let get_delta = MirRelationExpr::Get {
+                        id: Id::Local(delta),
+                        typ,
+                    };
+
+                    let delta_value = delta_value.union(get_id.clone().negate()).threshold();
+                    values[index] = get_id.union(get_delta);
+
+                    // The delta can only stop changing once the binding has, so we never let it
+                    // report that the limit has been exceeded.
+                    let delta_limit = limits[index].clone().map(|limit| LetRecLimit {
+                        return_at_limit: true,
+                        ..limit
+                    });
+
+                    ids.insert(index, delta);
+                    values.insert(index, delta_value);
+                    limits.insert(index, delta_limit);
+                    deltas.insert(delta);
+                    index += 1;
+                }
+                index += 1;
+            }
+        }
+    });
+    deltas
+}
+
+/// Marks the bindings of the `LetRec`s in `plan` that hold the records newly derived by a
+/// semi-naively evaluated binding.
+pub fn mark_semi_naive<T>(plan: &mut Plan<T>, deltas: &BTreeSet<LocalId>) {
+    let mut todo = vec![plan];
+    while let Some(plan) = todo.pop() {
+        if let Plan::LetRec {
+            ids, semi_naive, ..
+        } = plan
+        {
+            for (id, semi_naive) in ids.iter().zip(semi_naive.iter_mut()) {
+                *semi_naive = deltas.contains(id);
+            }
+        }
+        todo.extend(plan.children_mut());
+    }
+}
+
+/// Returns the value of the binding `delta` holding the records newly derived in each iteration
+/// by the binding at `index`, if that binding can be evaluated semi-naively.
+fn plan_delta(
+    ids: &[LocalId],
+    values: &[MirRelationExpr],
+    index: usize,
+    delta: &LocalId,
+) -> Option<MirRelationExpr> {
+    let id = ids[index];
+    let MirRelationExpr::Reduce {
+        input,
+        group_key,
+        aggregates,
+        monotonic: _,
+        expected_group_size,
+    } = &values[index]
+    else {
+        return None;
+    };
+    if !aggregates.is_empty() || !refers_to(input, &id) {
+        return None;
+    }
+
+    // The binding may refer to itself and to bindings that precede it and do not change across
+    // iterations, but not to bindings that change alongside it.
+    let mut valid = true;
+    input.visit_pre(|expr| match expr {
+        MirRelationExpr::Let { .. } | MirRelationExpr::LetRec { .. } => valid = false,
+        MirRelationExpr::Get {
+            id: Id::Local(other),
+            ..
+        } if *other != id => {
+            if let Some(position) = ids.iter().position(|i| i == other) {
+                let invariant = !ids.iter().any(|i| refers_to(&values[position], i));
+                valid &= position < index && invariant;
+            }
+        }
+        _ => (),
+    });
+    if !valid {
+        return None;
+    }
+
+    let derived = derive_delta(input, &id, delta, false)?;
+    Some(MirRelationExpr::Reduce {
+        input: Box::new(derived),
+        group_key: group_key.clone(),
+        aggregates: vec![],
+        monotonic: false,
+        expected_group_size: *expected_group_size,
+    })
+}
+
+/// Derives from `expr` an expression that produces the records of `expr` that involve the
+/// records newly derived for `id`, which are read from `delta`.
+///
+/// Returns `None` if `expr` does not refer to `id` only through unions of inner joins.
+fn derive_delta(
+    expr: &MirRelationExpr,
+    id: &LocalId,
+    delta: &LocalId,
+    in_join: bool,
+) -> Option<MirRelationExpr> {
+    if !refers_to(expr, id) {
+        return Some(expr.clone());
+    }
+    match expr {
+        MirRelationExpr::Get {
+            id: Id::Local(_),
+            typ,
+        } if in_join => Some(MirRelationExpr::Get {
+            id: Id::Local(*delta),
+            typ: typ.clone(),
+        }),
+        MirRelationExpr::Union { base, inputs } => Some(MirRelationExpr::Union {
+            base: Box::new(derive_delta(base, id, delta, in_join)?),
+            inputs: inputs
+                .iter()
+                .map(|input| derive_delta(input, id, delta, in_join))
+                .collect::<Option<_>>()?,
+        }),
+        MirRelationExpr::Map { .. }
+        | MirRelationExpr::Filter { .. }
+        | MirRelationExpr::Project { .. }
+        | MirRelationExpr::FlatMap { .. }
+        | MirRelationExpr::ArrangeBy { .. } => {
+            let mut derived = expr.clone();
+            let input = derived
+                .children_mut()
+                .next()
+                .expect("operator has an input");
+            *input = derive_delta(input, id, delta, in_join)?;
+            Some(derived)
+        }
+        MirRelationExpr::Join { inputs, kind, .. } if kind.is_inner() => {
+            // One copy of the join per input that refers to `id`, reading the delta in its place.
+            let mut copies = Vec::new();
+            for (position, input) in inputs.iter().enumerate() {
+                if refers_to(input, id) {
+                    let mut copy = expr.clone();
+                    if let MirRelationExpr::Join { inputs, .. } = &mut copy {
+                        inputs[position] = derive_delta(input, id, delta, true)?;
+                    }
+                    copies.push(copy);
+                }
+            }
+            Some(MirRelationExpr::union_many(copies, expr.typ()))
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether `expr` contains a `Get` of `id`.
+fn refers_to(expr: &MirRelationExpr, id: &LocalId) -> bool {
+    let mut found = false;
+    expr.visit_pre(|expr| {
+        if let MirRelationExpr::Get {
+            id: Id::Local(other),
+            ..
+        } = expr
+        {
+            found |= other == id;
+        }
+    });
+    found
+}
+
+/// Returns an identifier greater than that of any local binding in `expr`.
+fn next_local_id(expr: &MirRelationExpr) -> u64 {
+    let mut max = 0;
+    expr.visit_pre(|expr| match expr {
+        MirRelationExpr::Get {
+            id: Id::Local(id), ..
+        }
+        | MirRelationExpr::Let { id, .. } => max = std::cmp::max(max, u64::from(id)),
+        MirRelationExpr::LetRec { ids, .. } => {
+            for id in ids {
+                max = std::cmp::max(max, u64::from(id));
+            }
+        }
+        _ => (),
+    });
+    max + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{GlobalId, RelationType, ScalarType};
+
+    use super::*;
+
+    /// Binds `reach` to `value` in a `LetRec` and plans it semi-naively.
+    fn plan(value: MirRelationExpr, reach: LocalId) -> (MirRelationExpr, BTreeSet<LocalId>) {
+        let typ = value.typ();
+        let mut expr = MirRelationExpr::LetRec {
+            ids: vec![reach],
+            values: vec![value],
+            limits: vec![None],
+            body: Box::new(MirRelationExpr::Get {
+                id: Id::Local(reach),
+                typ,
+            }),
+        };
+        let deltas = plan_semi_naive(&mut expr);
+        (expr, deltas)
+    }
+
+    #[mz_ore::test]
+    fn test_plan_semi_naive() {
+        let typ = RelationType::new(vec![
+            ScalarType::Int64.nullable(false),
+            ScalarType::Int64.nullable(false),
+        ]);
+        let id = LocalId::new(0);
+        let edges = MirRelationExpr::global_get(GlobalId::User(1), typ.clone());
+        let reach = MirRelationExpr::Get {
+            id: Id::Local(id),
+            typ: typ.clone(),
+        };
+
+        // Transitive closure: `reach = distinct(edges ∪ π(reach ⋈ edges))`.
+        let step = MirRelationExpr::join(
+            vec![reach.clone(), edges.clone()],
+            vec![vec![(0, 1), (1, 0)]],
+        )
+        .project(vec![0, 3]);
+        let closure = edges.clone().union(step.clone()).distinct();
+        let (expr, deltas) = plan(closure, id);
+
+        let delta = LocalId::new(1);
+        assert_eq!(deltas, BTreeSet::from([delta]));
+        let MirRelationExpr::LetRec { ids, values, .. } = expr else {
+            panic!("expected a LetRec");
+        };
+        assert_eq!(ids, vec![delta, id]);
+        let get_delta = MirRelationExpr::Get {
+            id: Id::Local(delta),
+            typ: typ.clone(),
+        };
+        // Each iteration joins only the records derived in the previous one.
+        let delta_step = MirRelationExpr::join(
+            vec![get_delta.clone(), edges.clone()],
+            vec![vec![(0, 1), (1, 0)]],
+        )
+        .project(vec![0, 3]);
+        let expected = edges
+            .clone()
+            .union(delta_step)
+            .distinct()
+            .union(reach.clone().negate())
+            .threshold();
+        assert_eq!(values[0], expected);
+        assert_eq!(values[1], reach.clone().union(get_delta));
+
+        // Without the `Distinct`, records are derived over and over again.
+        let (_, deltas) = plan(edges.clone().union(step.clone()), id);
+        assert!(deltas.is_empty());
+
+        // The binding must not occur negatively.
+        let (_, deltas) = plan(edges.clone().union(step.clone().negate()).distinct(), id);
+        assert!(deltas.is_empty());
+
+        // Bindings that do not join themselves gain nothing from semi-naive evaluation.
+        let (_, deltas) = plan(edges.clone().union(reach.clone()).distinct(), id);
+        assert!(deltas.is_empty());
+
+        // The binding must not refer to other bindings that change across iterations.
+        let other = LocalId::new(2);
+        let mut expr = MirRelationExpr::LetRec {
+            ids: vec![other, id],
+            values: vec![
+                reach.clone(),
+                edges
+                    .clone()
+                    .union(
+                        MirRelationExpr::join(
+                            vec![
+                                MirRelationExpr::Get {
+                                    id: Id::Local(other),
+                                    typ: typ.clone(),
+                                },
+                                reach.clone(),
+                            ],
+                            vec![vec![(0, 1), (1, 0)]],
+                        )
+                        .project(vec![0, 3]),
+                    )
+                    .distinct(),
+            ],
+            limits: vec![None, None],
+            body: Box::new(reach),
+        };
+        assert!(plan_semi_naive(&mut expr).is_empty());
+    }
+}
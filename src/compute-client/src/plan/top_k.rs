@@ -16,6 +16,8 @@
 //! * A [MonotonicTop1Plan] maintains a single row per key and is suitable for monotonic inputs.
 //! * A [MonotonicTopKPlan] maintains up to K rows per key and is suitable for monotonic inputs.
 //! * A [BasicTopKPlan] maintains up to K rows per key and can handle retractions.
+//! * An [OrderedTopKPlan] maintains each group in order and can handle retractions, which is
+//!   suitable for large offsets and limits.
 
 use mz_expr::ColumnOrder;
 use mz_proto::{ProtoType, RustType, TryFromProtoError};
@@ -35,6 +37,8 @@ pub enum TopKPlan {
     MonotonicTopK(MonotonicTopKPlan),
     /// A plan for generic TopK operations.
     Basic(BasicTopKPlan),
+    /// A plan for generic TopK operations that maintains each group in order.
+    Ordered(OrderedTopKPlan),
 }
 
 impl TopKPlan {
@@ -121,6 +125,26 @@ impl TopKPlan {
             TopKPlan::MonotonicTopK(plan) => {
                 plan.must_consolidate = must_consolidate;
             }
+            TopKPlan::Ordered(_) => {}
+        }
+    }
+
+    /// Changes a basic topk plan to an ordered plan, if it skips or produces more than
+    /// `threshold` rows per group.
+    ///
+    /// The stages of a basic plan sort up to `offset + limit` rows per input bucket on each
+    /// update, which is cheap for small numbers but not for large ones.
+    pub fn as_ordered(&mut self, threshold: usize) {
+        if let TopKPlan::Basic(plan) = self {
+            let bound = plan.limit.and_then(|limit| plan.offset.checked_add(limit));
+            if bound.map_or(true, |bound| bound > threshold) {
+                *self = TopKPlan::Ordered(OrderedTopKPlan {
+                    group_key: plan.group_key.clone(),
+                    order_key: plan.order_key.clone(),
+                    limit: plan.limit,
+                    offset: plan.offset,
+                });
+            }
         }
     }
 }
@@ -134,6 +158,7 @@ impl RustType<ProtoTopKPlan> for TopKPlan {
                 TopKPlan::Basic(plan) => Some(Basic(plan.into_proto())),
                 TopKPlan::MonotonicTop1(plan) => Some(MonotonicTop1(plan.into_proto())),
                 TopKPlan::MonotonicTopK(plan) => Some(MonotonicTopK(plan.into_proto())),
+                TopKPlan::Ordered(plan) => Some(Ordered(plan.into_proto())),
             },
         }
     }
@@ -145,6 +170,7 @@ impl RustType<ProtoTopKPlan> for TopKPlan {
             Some(Basic(plan)) => Ok(TopKPlan::Basic(plan.into_rust()?)),
             Some(MonotonicTop1(plan)) => Ok(TopKPlan::MonotonicTop1(plan.into_rust()?)),
             Some(MonotonicTopK(plan)) => Ok(TopKPlan::MonotonicTopK(plan.into_rust()?)),
+            Some(Ordered(plan)) => Ok(TopKPlan::Ordered(plan.into_rust()?)),
             None => Err(TryFromProtoError::missing_field("ProtoTopKPlan::kind")),
         }
     }
@@ -288,6 +314,49 @@ impl RustType<ProtoBasicTopKPlan> for BasicTopKPlan {
     }
 }
 
+/// A plan for generic TopKs that maintains the rows of each group in order.
+///
+/// Unlike a [BasicTopKPlan], which re-sorts the rows of a group whenever it changes and keeps
+/// `offset + limit` rows at each of its stages, this plan keeps the rows of each group in an
+/// ordered structure and only reports the changes to the rows that enter or leave the range
+/// between `offset` and `offset + limit`. It processes updates in time order, and so requires
+/// totally ordered times.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct OrderedTopKPlan {
+    /// The columns that form the key for each group.
+    pub group_key: Vec<usize>,
+    /// Ordering that is used within each group.
+    pub order_key: Vec<mz_expr::ColumnOrder>,
+    /// Optionally, an upper bound on the per-group ordinal position of the
+    /// records to produce from each group.
+    pub limit: Option<usize>,
+    /// A lower bound on the per-group ordinal position of the records to
+    /// produce from each group.
+    ///
+    /// This can be set to zero to have no effect.
+    pub offset: usize,
+}
+
+impl RustType<ProtoOrderedTopKPlan> for OrderedTopKPlan {
+    fn into_proto(&self) -> ProtoOrderedTopKPlan {
+        ProtoOrderedTopKPlan {
+            group_key: self.group_key.into_proto(),
+            order_key: self.order_key.into_proto(),
+            limit: self.limit.into_proto(),
+            offset: self.offset.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoOrderedTopKPlan) -> Result<Self, TryFromProtoError> {
+        Ok(OrderedTopKPlan {
+            group_key: proto.group_key.into_rust()?,
+            order_key: proto.order_key.into_rust()?,
+            limit: proto.limit.into_rust()?,
+            offset: proto.offset.into_rust()?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mz_proto::protobuf_roundtrip;
@@ -295,6 +364,40 @@ mod tests {
 
     use super::*;
 
+    #[mz_ore::test]
+    fn test_as_ordered() {
+        let basic = |offset, limit| {
+            TopKPlan::Basic(BasicTopKPlan {
+                group_key: vec![0],
+                order_key: vec![],
+                limit,
+                offset,
+                arity: 2,
+                buckets: vec![],
+            })
+        };
+        for (offset, limit, ordered) in [
+            (0, Some(1), false),
+            (0, Some(16), false),
+            (8, Some(8), false),
+            (0, Some(17), true),
+            (16, Some(1), true),
+            (0, None, true),
+            (1, None, true),
+            (usize::MAX, Some(1), true),
+        ] {
+            let mut plan = basic(offset, limit);
+            plan.as_ordered(16);
+            match plan {
+                TopKPlan::Ordered(plan) => {
+                    assert!(ordered, "offset={offset}, limit={limit:?}");
+                    assert_eq!((plan.offset, plan.limit), (offset, limit));
+                }
+                _ => assert!(!ordered, "offset={offset}, limit={limit:?}"),
+            }
+        }
+    }
+
     proptest! {
         #[mz_ore::test]
         fn top_k_plan_protobuf_roundtrip(expect in any::<TopKPlan>()) {
@@ -0,0 +1,226 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Window function planning logic.
+//!
+//! Window functions reach us as a `Reduce` that collects each partition into a list and
+//! evaluates the function over the whole list, followed by a `FlatMap` that unnests the
+//! resulting list. A change to a single row then costs work proportional to the size of its
+//! partition.
+//!
+//! A [WindowPlan] describes an alternative implementation that maintains each partition in
+//! the order of the window function's `ORDER BY` clause, and only revisits the rows whose
+//! outputs may have changed:
+//! * For [WindowFunc::RowNumber], [WindowFunc::Rank], and [WindowFunc::DenseRank], these
+//!   are the rows from the peer group of the first changed row up to the point past the last
+//!   changed row where the changes no longer move the rows. The number of rows and peer groups
+//!   before the first changed row are counted in time logarithmic in the size of the partition.
+//! * For [WindowFunc::LagLead], these are the changed rows and the rows at most `offset`
+//!   positions away from them that refer to them.
+//!
+//! All other window functions keep recomputing the whole partition. Notably, this includes
+//! `first_value` and `last_value`, whose window frames can make the output of a row depend on
+//! rows arbitrarily far away from it, and `lag` and `lead` calls that ignore nulls or whose
+//! offsets are not literals. Aggregations over windows, such as running sums, are not
+//! supported by the SQL layer yet. `EXPLAIN PHYSICAL PLAN` shows which implementation a
+//! query uses: a `Window` stage, or a `Reduce` followed by a `FlatMap`.
+
+use mz_expr::{
+    AggregateExpr, AggregateFunc, ColumnOrder, LagLeadType, MirScalarExpr, VariadicFunc,
+};
+use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_compute_client.plan.window.rs"
+));
+
+/// A plan for maintaining a window function incrementally.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct WindowPlan {
+    /// The window function to maintain.
+    pub func: WindowFunc,
+    /// The ordering of the `ORDER BY` columns in the input to the window function.
+    pub order_by: Vec<ColumnOrder>,
+}
+
+/// The window functions that can be maintained incrementally.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum WindowFunc {
+    /// `row_number()`
+    RowNumber,
+    /// `rank()`
+    Rank,
+    /// `dense_rank()`
+    DenseRank,
+    /// `lag(..)` and `lead(..)` with a constant offset, respecting nulls.
+    LagLead {
+        /// Whether to look backward or forward.
+        lag_lead: LagLeadType,
+        /// The number of rows to look backward or forward.
+        offset: i32,
+    },
+}
+
+impl WindowFunc {
+    /// The position of the row a [WindowFunc::LagLead] refers to, relative to the current row.
+    ///
+    /// Returns zero for the other functions.
+    pub fn distance(&self) -> i64 {
+        match self {
+            WindowFunc::LagLead {
+                lag_lead: LagLeadType::Lag,
+                offset,
+            } => -i64::from(*offset),
+            WindowFunc::LagLead {
+                lag_lead: LagLeadType::Lead,
+                offset,
+            } => i64::from(*offset),
+            WindowFunc::RowNumber | WindowFunc::Rank | WindowFunc::DenseRank => 0,
+        }
+    }
+}
+
+impl WindowPlan {
+    /// Creates a plan for the window function `aggr`, if it can be maintained incrementally.
+    ///
+    /// Returns `None` for window functions without an incremental implementation, and for
+    /// `lag` and `lead` calls whose offset is not a literal or that ignore nulls.
+    pub fn create_from(aggr: &AggregateExpr) -> Option<Self> {
+        if aggr.distinct {
+            return None;
+        }
+        let (func, order_by) = match &aggr.func {
+            AggregateFunc::RowNumber { order_by } => (WindowFunc::RowNumber, order_by),
+            AggregateFunc::Rank { order_by } => (WindowFunc::Rank, order_by),
+            AggregateFunc::DenseRank { order_by } => (WindowFunc::DenseRank, order_by),
+            AggregateFunc::LagLead {
+                order_by,
+                lag_lead,
+                ignore_nulls: false,
+            } => {
+                let offset = lag_lead_offset(&aggr.expr)?;
+                let func = WindowFunc::LagLead {
+                    lag_lead: lag_lead.clone(),
+                    offset,
+                };
+                (func, order_by)
+            }
+            _ => return None,
+        };
+        Some(WindowPlan {
+            func,
+            order_by: order_by.clone(),
+        })
+    }
+}
+
+/// Extracts the offset of a `lag` or `lead` call from the input to its aggregation.
+///
+/// The input has the form `((original_row, (value, offset, default)), order_by..)`, and we
+/// only support offsets that are non-null literals.
+fn lag_lead_offset(expr: &MirScalarExpr) -> Option<i32> {
+    fn record_fields(expr: &MirScalarExpr) -> Option<&[MirScalarExpr]> {
+        match expr {
+            MirScalarExpr::CallVariadic {
+                func: VariadicFunc::RecordCreate { .. },
+                exprs,
+            } => Some(exprs),
+            _ => None,
+        }
+    }
+
+    let fn_input = record_fields(expr)?.first()?;
+    let encoded_args = record_fields(fn_input)?.get(1)?;
+    let offset = record_fields(encoded_args)?.get(1)?;
+    match offset.as_literal()? {
+        Ok(datum) if !datum.is_null() => Some(datum.unwrap_int32()),
+        _ => None,
+    }
+}
+
+impl RustType<ProtoWindowPlan> for WindowPlan {
+    fn into_proto(&self) -> ProtoWindowPlan {
+        ProtoWindowPlan {
+            func: Some(self.func.into_proto()),
+            order_by: self.order_by.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoWindowPlan) -> Result<Self, TryFromProtoError> {
+        Ok(WindowPlan {
+            func: proto.func.into_rust_if_some("ProtoWindowPlan::func")?,
+            order_by: proto.order_by.into_rust()?,
+        })
+    }
+}
+
+impl RustType<ProtoWindowFunc> for WindowFunc {
+    fn into_proto(&self) -> ProtoWindowFunc {
+        use proto_window_func::proto_lag_lead::LagLead;
+        use proto_window_func::{Kind, ProtoLagLead};
+        ProtoWindowFunc {
+            kind: Some(match self {
+                WindowFunc::RowNumber => Kind::RowNumber(()),
+                WindowFunc::Rank => Kind::Rank(()),
+                WindowFunc::DenseRank => Kind::DenseRank(()),
+                WindowFunc::LagLead { lag_lead, offset } => Kind::LagLead(ProtoLagLead {
+                    lag_lead: Some(match lag_lead {
+                        LagLeadType::Lag => LagLead::Lag(()),
+                        LagLeadType::Lead => LagLead::Lead(()),
+                    }),
+                    offset: *offset,
+                }),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoWindowFunc) -> Result<Self, TryFromProtoError> {
+        use proto_window_func::proto_lag_lead::LagLead;
+        use proto_window_func::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoWindowFunc::kind"))?;
+        Ok(match kind {
+            Kind::RowNumber(()) => WindowFunc::RowNumber,
+            Kind::Rank(()) => WindowFunc::Rank,
+            Kind::DenseRank(()) => WindowFunc::DenseRank,
+            Kind::LagLead(proto) => {
+                let lag_lead = match proto.lag_lead {
+                    Some(LagLead::Lag(())) => LagLeadType::Lag,
+                    Some(LagLead::Lead(())) => LagLeadType::Lead,
+                    None => return Err(TryFromProtoError::missing_field("ProtoLagLead::lag_lead")),
+                };
+                WindowFunc::LagLead {
+                    lag_lead,
+                    offset: proto.offset,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_proto::protobuf_roundtrip;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[mz_ore::test]
+        fn window_plan_protobuf_roundtrip(expect in any::<WindowPlan>()) {
+            let actual = protobuf_roundtrip::<_, ProtoWindowPlan>(&expect);
+            assert!(actual.is_ok());
+            assert_eq!(actual.unwrap(), expect);
+        }
+    }
+}
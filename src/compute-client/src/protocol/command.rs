@@ -363,6 +363,8 @@ pub struct ComputeParameters {
     pub dataflow_max_inflight_bytes: Option<usize>,
     /// Whether rendering should use `mz_join_core` rather than DD's `JoinCore::join_core`.
     pub enable_mz_join_core: Option<bool>,
+    /// Whether arrangements may spill to the replica's scratch directory.
+    pub enable_arrangement_spilling: Option<bool>,
     /// Persist client configuration.
     pub persist: PersistParameters,
     /// Tracing configuration.
@@ -378,6 +380,7 @@ impl ComputeParameters {
             max_result_size,
             dataflow_max_inflight_bytes,
             enable_mz_join_core,
+            enable_arrangement_spilling,
             persist,
             tracing,
             grpc_client,
@@ -392,6 +395,9 @@ impl ComputeParameters {
         if enable_mz_join_core.is_some() {
             self.enable_mz_join_core = enable_mz_join_core;
         }
+        if enable_arrangement_spilling.is_some() {
+            self.enable_arrangement_spilling = enable_arrangement_spilling;
+        }
 
         self.persist.update(persist);
         self.tracing.update(tracing);
@@ -410,6 +416,7 @@ impl RustType<ProtoComputeParameters> for ComputeParameters {
             max_result_size: self.max_result_size.into_proto(),
             dataflow_max_inflight_bytes: self.dataflow_max_inflight_bytes.into_proto(),
             enable_mz_join_core: self.enable_mz_join_core.into_proto(),
+            enable_arrangement_spilling: self.enable_arrangement_spilling.into_proto(),
             persist: Some(self.persist.into_proto()),
             tracing: Some(self.tracing.into_proto()),
             grpc_client: Some(self.grpc_client.into_proto()),
@@ -421,6 +428,7 @@ impl RustType<ProtoComputeParameters> for ComputeParameters {
             max_result_size: proto.max_result_size.into_rust()?,
             dataflow_max_inflight_bytes: proto.dataflow_max_inflight_bytes.into_rust()?,
             enable_mz_join_core: proto.enable_mz_join_core.into_rust()?,
+            enable_arrangement_spilling: proto.enable_arrangement_spilling.into_rust()?,
             persist: proto
                 .persist
                 .into_rust_if_some("ProtoComputeParameters::persist")?,
@@ -12,11 +12,13 @@
 use std::num::NonZeroUsize;
 
 use mz_ore::tracing::OpenTelemetryContext;
+use mz_persist_client::compaction::{CompactionQueueEntry, CompactionTaskState};
 use mz_proto::{any_uuid, IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{Diff, GlobalId, Row};
 use mz_timely_util::progress::any_antichain;
 use proptest::prelude::{any, Arbitrary, Just};
 use proptest::strategy::{BoxedStrategy, Strategy, Union};
+use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 use timely::progress::frontier::Antichain;
 use uuid::Uuid;
@@ -127,6 +129,40 @@ pub enum ComputeResponse<T = mz_repr::Timestamp> {
     /// [`CreateDataflows` command]: super::command::ComputeCommand::CreateDataflows
     /// [`AllowCompaction` command]: super::command::ComputeCommand::AllowCompaction
     SubscribeResponse(GlobalId, SubscribeResponse<T>),
+
+    /// `HydrationStatuses` reports the hydration progress of the specified compute collections.
+    ///
+    /// Replicas should send a `HydrationStatuses` response for each index and storage sink when
+    /// it is created, periodically while it processes its input snapshots, and once when it has
+    /// become hydrated. A collection is hydrated once its output frontier has advanced beyond its
+    /// `as_of`. Replicas should not send `HydrationStatuses` responses for subscribes.
+    ///
+    /// Replicas must not report a collection as hydrated and subsequently report it as not
+    /// hydrated, unless the collection was re-created by a [`CreateDataflows` command] during
+    /// reconciliation.
+    ///
+    /// [`CreateDataflows` command]: super::command::ComputeCommand::CreateDataflows
+    HydrationStatuses(Vec<(GlobalId, HydrationStatus)>),
+
+    /// `QuarantinedErrors` reports the evaluation errors a persist sink quarantined instead of
+    /// writing them to its output, up to some frontier.
+    ///
+    /// Replicas may send `QuarantinedErrors` responses for persist sinks that were installed with
+    /// a [`QuarantinePlan`]. All errors transmitted in a response must be consolidated and have
+    /// times before the response's `upper`. A response must contain all
+    /// errors the sink quarantined at times before its `upper`, except for those already
+    /// reported by previous responses. The `upper`s of consecutive responses for the same sink
+    /// must be increasing. Replicas need not send responses that contain no errors.
+    ///
+    /// [`QuarantinePlan`]: crate::types::sinks::QuarantinePlan
+    QuarantinedErrors(GlobalId, QuarantinedErrorBatch<T>),
+
+    /// `CompactionQueue` reports the persist compactions that are queued or running in the
+    /// replica's processes.
+    ///
+    /// Replicas should send a `CompactionQueue` response periodically while the set of
+    /// compactions changes. Each response replaces the queue reported by the previous one.
+    CompactionQueue(Vec<CompactionQueueEntry>),
 }
 
 impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
@@ -149,6 +185,28 @@ impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
                         resp: Some(resp.into_proto()),
                     })
                 }
+                ComputeResponse::HydrationStatuses(statuses) => {
+                    HydrationStatuses(ProtoHydrationStatusesKind {
+                        entries: statuses
+                            .iter()
+                            .map(|(id, status)| proto_hydration_statuses_kind::ProtoEntry {
+                                id: Some(id.into_proto()),
+                                status: Some(status.into_proto()),
+                            })
+                            .collect(),
+                    })
+                }
+                ComputeResponse::QuarantinedErrors(id, batch) => {
+                    QuarantinedErrors(ProtoQuarantinedErrorsKind {
+                        id: Some(id.into_proto()),
+                        batch: Some(batch.into_proto()),
+                    })
+                }
+                ComputeResponse::CompactionQueue(entries) => {
+                    CompactionQueue(ProtoCompactionQueueKind {
+                        entries: entries.into_proto(),
+                    })
+                }
             }),
         }
     }
@@ -170,6 +228,29 @@ impl RustType<ProtoComputeResponse> for ComputeResponse<mz_repr::Timestamp> {
                 resp.resp
                     .into_rust_if_some("ProtoSubscribeResponseKind::resp")?,
             )),
+            Some(HydrationStatuses(statuses)) => Ok(ComputeResponse::HydrationStatuses(
+                statuses
+                    .entries
+                    .into_iter()
+                    .map(|entry| {
+                        Ok((
+                            entry.id.into_rust_if_some("ProtoEntry::id")?,
+                            entry.status.into_rust_if_some("ProtoEntry::status")?,
+                        ))
+                    })
+                    .collect::<Result<_, TryFromProtoError>>()?,
+            )),
+            Some(QuarantinedErrors(errors)) => Ok(ComputeResponse::QuarantinedErrors(
+                errors
+                    .id
+                    .into_rust_if_some("ProtoQuarantinedErrorsKind::id")?,
+                errors
+                    .batch
+                    .into_rust_if_some("ProtoQuarantinedErrorsKind::batch")?,
+            )),
+            Some(CompactionQueue(queue)) => {
+                Ok(ComputeResponse::CompactionQueue(queue.entries.into_rust()?))
+            }
             None => Err(TryFromProtoError::missing_field(
                 "ProtoComputeResponse::kind",
             )),
@@ -194,6 +275,15 @@ impl Arbitrary for ComputeResponse<mz_repr::Timestamp> {
             (any::<GlobalId>(), any::<SubscribeResponse>())
                 .prop_map(|(id, resp)| ComputeResponse::SubscribeResponse(id, resp))
                 .boxed(),
+            proptest::collection::vec((any::<GlobalId>(), any::<HydrationStatus>()), 1..4)
+                .prop_map(ComputeResponse::HydrationStatuses)
+                .boxed(),
+            (any::<GlobalId>(), any::<QuarantinedErrorBatch>())
+                .prop_map(|(id, batch)| ComputeResponse::QuarantinedErrors(id, batch))
+                .boxed(),
+            proptest::collection::vec(any::<CompactionQueueEntry>(), 0..4)
+                .prop_map(ComputeResponse::CompactionQueue)
+                .boxed(),
         ])
     }
 }
@@ -461,6 +551,172 @@ impl Arbitrary for SubscribeBatch<mz_repr::Timestamp> {
     }
 }
 
+/// The errors a persist sink quarantined at times before `upper`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QuarantinedErrorBatch<T = mz_repr::Timestamp> {
+    /// The upper frontier of the quarantined errors.
+    pub upper: Antichain<T>,
+    /// The quarantined errors, along with the key of the input row that caused each.
+    pub errors: Vec<(T, String, Row, Diff)>,
+}
+
+impl RustType<ProtoQuarantinedErrorBatch> for QuarantinedErrorBatch<mz_repr::Timestamp> {
+    fn into_proto(&self) -> ProtoQuarantinedErrorBatch {
+        use proto_quarantined_error_batch::ProtoQuarantinedError;
+        ProtoQuarantinedErrorBatch {
+            upper: Some(self.upper.into_proto()),
+            errors: self
+                .errors
+                .iter()
+                .map(|(t, error, input_key, d)| ProtoQuarantinedError {
+                    timestamp: t.into(),
+                    error: error.clone(),
+                    input_key: Some(input_key.into_proto()),
+                    diff: *d,
+                })
+                .collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoQuarantinedErrorBatch) -> Result<Self, TryFromProtoError> {
+        Ok(QuarantinedErrorBatch {
+            upper: proto
+                .upper
+                .into_rust_if_some("ProtoQuarantinedErrorBatch::upper")?,
+            errors: proto
+                .errors
+                .into_iter()
+                .map(|error| {
+                    Ok((
+                        error.timestamp.into(),
+                        error.error,
+                        error
+                            .input_key
+                            .into_rust_if_some("ProtoQuarantinedError::input_key")?,
+                        error.diff,
+                    ))
+                })
+                .collect::<Result<Vec<_>, TryFromProtoError>>()?,
+        })
+    }
+}
+
+impl Arbitrary for QuarantinedErrorBatch<mz_repr::Timestamp> {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            proptest::collection::vec(any::<mz_repr::Timestamp>(), 1..4),
+            proptest::collection::vec(
+                (
+                    any::<mz_repr::Timestamp>(),
+                    ".*",
+                    any::<Row>(),
+                    any::<Diff>(),
+                ),
+                1..4,
+            ),
+        )
+            .prop_map(|(upper, errors)| QuarantinedErrorBatch {
+                upper: Antichain::from(upper),
+                errors,
+            })
+            .boxed()
+    }
+}
+
+/// The hydration progress of a compute collection on a replica.
+#[derive(Arbitrary, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HydrationStatus {
+    /// Whether the collection's output frontier has advanced beyond its `as_of`, i.e., whether
+    /// the collection has finished processing the snapshots of its inputs.
+    pub hydrated: bool,
+    /// The number of input snapshot records processed so far.
+    pub records_processed: u64,
+    /// The number of records in the input snapshots, if known.
+    ///
+    /// This is an upper bound, as persist does not consolidate snapshots before counting their
+    /// records.
+    pub snapshot_records: Option<u64>,
+    /// The number of records in the collection's arrangement, if the collection is an index.
+    pub arrangement_records: Option<u64>,
+    /// How far, in milliseconds, the collection's output frontier lags behind the frontier of
+    /// its inputs, if known.
+    pub frontier_lag_ms: Option<u64>,
+}
+
+impl HydrationStatus {
+    /// Incorporates the status reported by another part of the same replica.
+    ///
+    /// The collection is hydrated once all parts have hydrated it, while its record counts are
+    /// spread across the parts.
+    pub fn absorb(&mut self, other: &HydrationStatus) {
+        fn add(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            }
+        }
+
+        self.hydrated &= other.hydrated;
+        self.records_processed += other.records_processed;
+        self.snapshot_records = add(self.snapshot_records, other.snapshot_records);
+        self.arrangement_records = add(self.arrangement_records, other.arrangement_records);
+        self.frontier_lag_ms = self.frontier_lag_ms.max(other.frontier_lag_ms);
+    }
+}
+
+impl RustType<ProtoHydrationStatus> for HydrationStatus {
+    fn into_proto(&self) -> ProtoHydrationStatus {
+        ProtoHydrationStatus {
+            hydrated: self.hydrated,
+            records_processed: self.records_processed,
+            snapshot_records: self.snapshot_records,
+            arrangement_records: self.arrangement_records,
+            frontier_lag_ms: self.frontier_lag_ms,
+        }
+    }
+
+    fn from_proto(proto: ProtoHydrationStatus) -> Result<Self, TryFromProtoError> {
+        Ok(HydrationStatus {
+            hydrated: proto.hydrated,
+            records_processed: proto.records_processed,
+            snapshot_records: proto.snapshot_records,
+            arrangement_records: proto.arrangement_records,
+            frontier_lag_ms: proto.frontier_lag_ms,
+        })
+    }
+}
+
+impl RustType<ProtoCompactionQueueEntry> for CompactionQueueEntry {
+    fn into_proto(&self) -> ProtoCompactionQueueEntry {
+        ProtoCompactionQueueEntry {
+            shard_id: self.shard_id.into_proto(),
+            running: self.state == CompactionTaskState::Running,
+            runs: self.runs.into_proto(),
+            bytes_awaiting_merge: self.bytes_awaiting_merge.into_proto(),
+            memory_bytes: self.memory_bytes.into_proto(),
+            enqueued_at: self.enqueued_at,
+        }
+    }
+
+    fn from_proto(proto: ProtoCompactionQueueEntry) -> Result<Self, TryFromProtoError> {
+        Ok(CompactionQueueEntry {
+            shard_id: proto.shard_id.into_rust()?,
+            state: if proto.running {
+                CompactionTaskState::Running
+            } else {
+                CompactionTaskState::Queued
+            },
+            runs: proto.runs.into_rust()?,
+            bytes_awaiting_merge: proto.bytes_awaiting_merge.into_rust()?,
+            memory_bytes: proto.memory_bytes.into_rust()?,
+            enqueued_at: proto.enqueued_at,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mz_proto::protobuf_roundtrip;
@@ -479,4 +735,33 @@ mod tests {
             assert_eq!(actual.unwrap(), expect);
         }
     }
+
+    #[mz_ore::test]
+    fn hydration_status_absorb() {
+        let mut status = HydrationStatus {
+            hydrated: true,
+            records_processed: 10,
+            snapshot_records: None,
+            arrangement_records: Some(4),
+            frontier_lag_ms: Some(100),
+        };
+        status.absorb(&HydrationStatus {
+            hydrated: false,
+            records_processed: 5,
+            snapshot_records: Some(20),
+            arrangement_records: Some(3),
+            frontier_lag_ms: None,
+        });
+
+        assert_eq!(
+            status,
+            HydrationStatus {
+                hydrated: false,
+                records_processed: 15,
+                snapshot_records: Some(20),
+                arrangement_records: Some(7),
+                frontier_lag_ms: Some(100),
+            }
+        );
+    }
 }
@@ -19,6 +19,7 @@ use std::iter;
 use async_trait::async_trait;
 use differential_dataflow::consolidation::consolidate_updates;
 use differential_dataflow::lattice::Lattice;
+use mz_persist_client::compaction::CompactionQueueEntry;
 use mz_repr::{Diff, GlobalId, Row};
 use mz_service::client::{GenericClient, Partitionable, PartitionedState};
 use mz_service::grpc::{GrpcClient, GrpcServer, ProtoServiceTypes, ResponseStream};
@@ -30,7 +31,8 @@ use uuid::Uuid;
 use crate::metrics::ReplicaMetrics;
 use crate::protocol::command::{ComputeCommand, ProtoComputeCommand};
 use crate::protocol::response::{
-    ComputeResponse, PeekResponse, ProtoComputeResponse, SubscribeBatch, SubscribeResponse,
+    ComputeResponse, HydrationStatus, PeekResponse, ProtoComputeResponse, SubscribeBatch,
+    SubscribeResponse,
 };
 use crate::service::proto_compute_server::ProtoCompute;
 
@@ -156,6 +158,20 @@ pub struct PartitionedComputeState<T> {
     /// the tracking state maintained for it and b) we won't re-initialize tracking for a subscribe
     /// we have already dropped.
     pending_subscribes: BTreeMap<GlobalId, PendingSubscribe<T>>,
+    /// The latest hydration status reported by each shard, for collections that are not yet
+    /// hydrated on all shards.
+    ///
+    /// Tracking of a collection is initialized when the first `HydrationStatuses` response for
+    /// that collection is received. A unified status is emitted whenever a shard reports a new
+    /// status, once all shards have reported at least once. When the unified status reports the
+    /// collection as hydrated, the tracking state is dropped again.
+    hydration_statuses: BTreeMap<GlobalId, Vec<Option<HydrationStatus>>>,
+    /// The compaction queue last reported by each shard.
+    ///
+    /// Shards that share a process also share a compaction queue, which only one of them reports.
+    /// The unified queue is the concatenation of the queues of all shards, and is emitted whenever
+    /// a shard reports a new queue.
+    compaction_queues: Vec<Vec<CompactionQueueEntry>>,
 }
 
 impl<T> Partitionable<ComputeCommand<T>, ComputeResponse<T>>
@@ -171,6 +187,8 @@ where
             uppers: BTreeMap::new(),
             peek_responses: BTreeMap::new(),
             pending_subscribes: BTreeMap::new(),
+            hydration_statuses: BTreeMap::new(),
+            compaction_queues: vec![Vec::new(); parts],
         }
     }
 }
@@ -185,10 +203,14 @@ where
             uppers,
             peek_responses,
             pending_subscribes,
+            hydration_statuses,
+            compaction_queues,
         } = self;
         uppers.clear();
         peek_responses.clear();
         pending_subscribes.clear();
+        hydration_statuses.clear();
+        compaction_queues.iter_mut().for_each(Vec::clear);
     }
 
     /// Observes commands that move past, and prepares state for responses.
@@ -410,6 +432,49 @@ where
 
                 emit_response
             }
+            ComputeResponse::HydrationStatuses(list) => {
+                let mut new_statuses = Vec::new();
+
+                for (id, shard_status) in list {
+                    let parts = self.parts;
+                    let entry = self
+                        .hydration_statuses
+                        .entry(id)
+                        .or_insert_with(|| vec![None; parts]);
+                    entry[shard_id] = Some(shard_status);
+
+                    // Only announce a status once all shards have weighed in.
+                    if entry.iter().any(|s| s.is_none()) {
+                        continue;
+                    }
+                    let mut statuses = entry.iter().flatten();
+                    let mut status = statuses.next().expect("parts > 0").clone();
+                    for other in statuses {
+                        status.absorb(other);
+                    }
+
+                    if status.hydrated {
+                        self.hydration_statuses.remove(&id);
+                    }
+                    new_statuses.push((id, status));
+                }
+
+                if new_statuses.is_empty() {
+                    None
+                } else {
+                    Some(Ok(ComputeResponse::HydrationStatuses(new_statuses)))
+                }
+            }
+            ComputeResponse::QuarantinedErrors(id, batch) => {
+                // The errors of a sink are all reported by the same worker, so there is nothing
+                // to merge.
+                Some(Ok(ComputeResponse::QuarantinedErrors(id, batch)))
+            }
+            ComputeResponse::CompactionQueue(queue) => {
+                self.compaction_queues[shard_id] = queue;
+                let queue = self.compaction_queues.iter().flatten().cloned().collect();
+                Some(Ok(ComputeResponse::CompactionQueue(queue)))
+            }
         }
     }
 }
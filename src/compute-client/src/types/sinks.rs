@@ -9,8 +9,9 @@
 
 //! Types for describing dataflow sinks.
 
+use mz_expr::SafeMfpPlan;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
-use mz_repr::{GlobalId, RelationDesc};
+use mz_repr::{GlobalId, RelationDesc, RelationType};
 use mz_storage_client::controller::CollectionMetadata;
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
 use proptest_derive::Arbitrary;
@@ -115,7 +116,9 @@ impl RustType<ProtoComputeSinkConnection> for ComputeSinkConnection<CollectionMe
         use proto_compute_sink_connection::Kind;
         ProtoComputeSinkConnection {
             kind: Some(match self {
-                ComputeSinkConnection::Subscribe(_) => Kind::Subscribe(()),
+                ComputeSinkConnection::Subscribe(subscribe) => {
+                    Kind::Subscribe(subscribe.into_proto())
+                }
                 ComputeSinkConnection::Persist(persist) => Kind::Persist(persist.into_proto()),
             }),
         }
@@ -127,19 +130,47 @@ impl RustType<ProtoComputeSinkConnection> for ComputeSinkConnection<CollectionMe
             .kind
             .ok_or_else(|| TryFromProtoError::missing_field("ProtoComputeSinkConnection::kind"))?;
         Ok(match kind {
-            Kind::Subscribe(_) => ComputeSinkConnection::Subscribe(SubscribeSinkConnection {}),
+            Kind::Subscribe(subscribe) => ComputeSinkConnection::Subscribe(subscribe.into_rust()?),
             Kind::Persist(persist) => ComputeSinkConnection::Persist(persist.into_rust()?),
         })
     }
 }
 
 #[derive(Arbitrary, Default, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
-pub struct SubscribeSinkConnection {}
+pub struct SubscribeSinkConnection {
+    /// If set, update times are rounded up to multiples of this many milliseconds, so that each
+    /// batch only contains the net change of the intervals it completes.
+    pub emit_interval: Option<u64>,
+    /// If set, the maximum number of rows each worker sends before it waits for the controller
+    /// to acknowledge their receipt.
+    ///
+    /// Acknowledgements are communicated through `AllowCompaction` commands for the sink: a
+    /// read frontier of `f` acknowledges all batches with an upper less than or equal to `f`.
+    pub max_pending_rows: Option<u64>,
+}
+
+impl RustType<ProtoSubscribeSinkConnection> for SubscribeSinkConnection {
+    fn into_proto(&self) -> ProtoSubscribeSinkConnection {
+        ProtoSubscribeSinkConnection {
+            emit_interval: self.emit_interval,
+            max_pending_rows: self.max_pending_rows,
+        }
+    }
+
+    fn from_proto(proto: ProtoSubscribeSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(SubscribeSinkConnection {
+            emit_interval: proto.emit_interval,
+            max_pending_rows: proto.max_pending_rows,
+        })
+    }
+}
 
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PersistSinkConnection<S> {
     pub value_desc: RelationDesc,
     pub storage_metadata: S,
+    /// If set, row-level evaluation errors are quarantined instead of written.
+    pub quarantine: Option<QuarantinePlan>,
 }
 
 impl RustType<ProtoPersistSinkConnection> for PersistSinkConnection<CollectionMetadata> {
@@ -147,6 +178,7 @@ impl RustType<ProtoPersistSinkConnection> for PersistSinkConnection<CollectionMe
         ProtoPersistSinkConnection {
             value_desc: Some(self.value_desc.into_proto()),
             storage_metadata: Some(self.storage_metadata.into_proto()),
+            quarantine: self.quarantine.into_proto(),
         }
     }
 
@@ -158,6 +190,77 @@ impl RustType<ProtoPersistSinkConnection> for PersistSinkConnection<CollectionMe
             storage_metadata: proto
                 .storage_metadata
                 .into_rust_if_some("ProtoPersistSinkConnection::storage_metadata")?,
+            quarantine: proto.quarantine.into_rust()?,
+        })
+    }
+}
+
+/// Instructions for a persist sink to quarantine row-level evaluation errors.
+///
+/// The sink applies `mfp` to the rows of the sinked collection itself, so that it can associate
+/// evaluation errors with the rows that caused them. Only errors raised by `mfp` are quarantined;
+/// errors in the sinked collection are written to the sink's shard as usual. Quarantined errors
+/// are reported to the controller, together with the key of the row that caused them.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QuarantinePlan {
+    /// How to handle rows whose evaluation errors.
+    pub mode: QuarantineMode,
+    /// The final map, filter, and project of the sinked collection.
+    pub mfp: SafeMfpPlan,
+    /// The columns of the sinked collection's rows that identify them in reported errors.
+    pub input_key: Vec<usize>,
+}
+
+/// How a persist sink handles rows whose evaluation errors.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum QuarantineMode {
+    /// Drop the row.
+    SkipRow,
+    /// Replace the values that failed to evaluate with `NULL`.
+    Null,
+}
+
+impl QuarantineMode {
+    /// Adjusts the type of a sinked collection to the values the sink may write in this mode.
+    pub fn adjust_type(&self, typ: &mut RelationType) {
+        if let QuarantineMode::Null = self {
+            // Any value may be replaced by `NULL`, which may also make rows agree on their keys.
+            for column_type in typ.column_types.iter_mut() {
+                column_type.nullable = true;
+            }
+            typ.keys.clear();
+        }
+    }
+}
+
+impl RustType<ProtoQuarantinePlan> for QuarantinePlan {
+    fn into_proto(&self) -> ProtoQuarantinePlan {
+        use proto_quarantine_plan::Mode;
+        ProtoQuarantinePlan {
+            mode: Some(match self.mode {
+                QuarantineMode::SkipRow => Mode::SkipRow(()),
+                QuarantineMode::Null => Mode::SetNull(()),
+            }),
+            mfp: Some(self.mfp.into_proto()),
+            input_key: self.input_key.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoQuarantinePlan) -> Result<Self, TryFromProtoError> {
+        use proto_quarantine_plan::Mode;
+        let mode = match proto.mode {
+            Some(Mode::SkipRow(())) => QuarantineMode::SkipRow,
+            Some(Mode::SetNull(())) => QuarantineMode::Null,
+            None => {
+                return Err(TryFromProtoError::missing_field(
+                    "ProtoQuarantinePlan::mode",
+                ))
+            }
+        };
+        Ok(QuarantinePlan {
+            mode,
+            mfp: proto.mfp.into_rust_if_some("ProtoQuarantinePlan::mfp")?,
+            input_key: proto.input_key.into_rust()?,
         })
     }
 }
@@ -0,0 +1,68 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Spilling of arrangement contents to disk.
+//!
+//! Arrangement batches store their rows in columnar regions (see
+//! [`mz_repr::Row`]'s `Columnation` implementation). When spilling is enabled,
+//! large region chunks are allocated in memory-mapped files in the replica's
+//! scratch directory instead of on the heap. The kernel then pages cold
+//! batches out to local disk under memory pressure, which lets arrangements
+//! exceed the memory of the replica at the cost of slower access to the
+//! spilled data.
+//!
+//! Spilling requires a scratch directory, which is only present for replicas
+//! of clusters with `DISK` enabled. It is controlled by the
+//! `enable_compute_arrangement_spilling` configuration parameter and only
+//! applies to compute workers: the storage workers sharing the process keep
+//! their regions on the heap. The `mz_arrangement_spilled_bytes` metric
+//! reports the amount of spilled data.
+
+use std::path::Path;
+
+use mz_ore::region::{set_spill_config, SpillConfig};
+use tracing::{info, warn};
+
+/// The name of the subdirectory of the scratch directory that holds spilled
+/// arrangement chunks.
+const SPILL_SUBDIRECTORY: &str = "arrangements";
+
+/// The minimum size of region chunks that are spilled to disk.
+///
+/// Smaller chunks belong to small or recently created batches, which are
+/// likely to be accessed or merged soon.
+const MIN_SPILL_CHUNK_BYTES: usize = 2 << 20;
+
+/// Enables or disables spilling of arrangements to `scratch_directory`.
+///
+/// Spilling is a setting of the calling worker thread that applies to batches
+/// formed after the call, so each compute worker must apply it. If no scratch
+/// directory is available, spilling stays disabled.
+pub fn configure(scratch_directory: Option<&Path>, enabled: bool) {
+    let config = match (scratch_directory, enabled) {
+        (Some(scratch_directory), true) => {
+            let path = scratch_directory.join(SPILL_SUBDIRECTORY);
+            if let Err(err) = std::fs::create_dir_all(&path) {
+                warn!("failed to create arrangement spill directory {path:?}: {err}");
+                None
+            } else {
+                Some(SpillConfig {
+                    path,
+                    min_chunk_bytes: MIN_SPILL_CHUNK_BYTES,
+                })
+            }
+        }
+        (None, true) => {
+            info!("arrangement spilling requested, but replica has no scratch directory");
+            None
+        }
+        (_, false) => None,
+    };
+    set_spill_config(config);
+}
@@ -6,13 +6,14 @@
 //! Worker-local state for compute timely instances.
 
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::{BTreeMap, BTreeSet};
 use std::num::NonZeroUsize;
 use std::ops::DerefMut;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use bytesize::ByteSize;
 use differential_dataflow::trace::TraceReader;
@@ -20,12 +21,15 @@ use mz_compute_client::logging::LoggingConfig;
 use mz_compute_client::plan::Plan;
 use mz_compute_client::protocol::command::{ComputeCommand, ComputeParameters, Peek};
 use mz_compute_client::protocol::history::ComputeCommandHistory;
-use mz_compute_client::protocol::response::{ComputeResponse, PeekResponse, SubscribeResponse};
+use mz_compute_client::protocol::response::{
+    ComputeResponse, HydrationStatus, PeekResponse, QuarantinedErrorBatch, SubscribeResponse,
+};
 use mz_compute_client::types::dataflows::DataflowDescription;
 use mz_ore::cast::CastFrom;
 use mz_ore::metrics::UIntGauge;
 use mz_ore::tracing::{OpenTelemetryContext, TracingHandle};
 use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::compaction::CompactionQueueEntry;
 use mz_repr::{GlobalId, Row, Timestamp};
 use mz_storage_client::controller::CollectionMetadata;
 use mz_timely_util::probe;
@@ -38,10 +42,12 @@ use tracing::{error, info, span, Level};
 use uuid::Uuid;
 
 use crate::arrangement::manager::{TraceBundle, TraceManager};
+use crate::arrangement::spill;
 use crate::logging;
 use crate::logging::compute::ComputeEvent;
 use crate::metrics::{CollectionMetrics, ComputeMetrics};
 use crate::render::LinearJoinImpl;
+use crate::sink::subscribe::SubscribeAcknowledgements;
 
 /// Worker-local state that is maintained across dataflows.
 ///
@@ -66,6 +72,8 @@ pub struct ComputeState {
     /// The entries are pairs of sink identifier (to identify the subscribe instance)
     /// and the response itself.
     pub subscribe_response_buffer: Rc<RefCell<Vec<(GlobalId, SubscribeResponse)>>>,
+    /// Shared buffer with persist sinks by which they report the errors they quarantined.
+    pub quarantined_error_buffer: Rc<RefCell<Vec<(GlobalId, QuarantinedErrorBatch)>>>,
     /// Peek commands that are awaiting fulfillment.
     pub pending_peeks: BTreeMap<Uuid, PendingPeek>,
     /// The logger, from Timely's logging framework, if logs are enabled.
@@ -85,6 +93,12 @@ pub struct ComputeState {
     pub metrics: ComputeMetrics,
     /// A process-global handle to tracing configuration.
     tracing_handle: Arc<TracingHandle>,
+    /// The replica's scratch directory, if disk is enabled.
+    scratch_directory: Option<PathBuf>,
+    /// The persist compaction queue that has last been reported to the controller.
+    reported_compaction_queue: Vec<CompactionQueueEntry>,
+    /// Time at which the persist compaction queue was last reported.
+    last_compaction_queue_report: Option<Instant>,
 }
 
 impl ComputeState {
@@ -94,6 +108,7 @@ impl ComputeState {
         persist_clients: Arc<PersistClientCache>,
         metrics: ComputeMetrics,
         tracing_handle: Arc<TracingHandle>,
+        scratch_directory: Option<PathBuf>,
     ) -> Self {
         let traces = TraceManager::new(metrics.for_traces(worker_id));
         let command_history = ComputeCommandHistory::new(metrics.for_history());
@@ -103,6 +118,7 @@ impl ComputeState {
             dropped_collections: Default::default(),
             traces,
             subscribe_response_buffer: Default::default(),
+            quarantined_error_buffer: Default::default(),
             pending_peeks: Default::default(),
             compute_logger: None,
             persist_clients,
@@ -112,9 +128,19 @@ impl ComputeState {
             linear_join_impl: Default::default(),
             metrics,
             tracing_handle,
+            scratch_directory,
+            reported_compaction_queue: Vec::new(),
+            last_compaction_queue_report: None,
         }
     }
 
+    /// Forget the compaction queue reported to the controller, so that the next report is sent
+    /// regardless of previous reports.
+    pub fn reset_reported_compaction_queue(&mut self) {
+        self.reported_compaction_queue.clear();
+        self.last_compaction_queue_report = None;
+    }
+
     /// Return whether a collection with the given ID exists.
     pub fn collection_exists(&self, id: GlobalId) -> bool {
         self.collections.contains_key(&id)
@@ -193,6 +219,7 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
             max_result_size,
             dataflow_max_inflight_bytes,
             enable_mz_join_core,
+            enable_arrangement_spilling,
             persist,
             tracing,
             grpc_client: _grpc_client,
@@ -210,6 +237,9 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
                 true => LinearJoinImpl::Materialize,
             };
         }
+        if let Some(v) = enable_arrangement_spilling {
+            spill::configure(self.compute_state.scratch_directory.as_deref(), v);
+        }
 
         persist.apply(self.compute_state.persist_clients.cfg());
         tracing.apply(self.compute_state.tracing_handle.as_ref());
@@ -285,6 +315,16 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
                 // Indicates that we may drop `id`, as there are no more valid times to read.
                 self.drop_collection(id);
             } else {
+                // Flow-controlled subscribes learn about the receipt of their batches through
+                // compaction of their read frontier.
+                if let Some(acknowledgements) = self
+                    .compute_state
+                    .collections
+                    .get(&id)
+                    .and_then(|collection| collection.subscribe_acknowledgements.as_ref())
+                {
+                    acknowledgements.borrow_mut().acknowledge(&frontier);
+                }
                 self.compute_state
                     .traces
                     .allow_compaction(id, frontier.borrow());
@@ -430,6 +470,113 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
             .remove("materialize/compute");
     }
 
+    /// Report the hydration progress of collections to the controller.
+    ///
+    /// Progress is reported at most once per [`HYDRATION_REPORT_INTERVAL`] per collection, except
+    /// for the first report and the report that announces the collection as hydrated. Once a
+    /// collection has been reported as hydrated, no further reports are sent for it.
+    pub fn report_hydration_statuses(&mut self) {
+        let mut statuses = Vec::new();
+
+        for (&id, collection) in self.compute_state.collections.iter_mut() {
+            // Subscribes are transient and are not tracked.
+            if collection.is_subscribe() {
+                continue;
+            }
+            let Some(progress) = &collection.hydration else {
+                continue;
+            };
+            if collection
+                .reported_hydration
+                .as_ref()
+                .map_or(false, |s| s.hydrated)
+            {
+                continue;
+            }
+
+            let hydrated = match collection.reported_frontier() {
+                ReportedFrontier::Reported(frontier) => {
+                    PartialOrder::less_than(&collection.as_of, frontier)
+                }
+                ReportedFrontier::NotReported { .. } => false,
+            };
+
+            let mut output_frontier = Antichain::new();
+            let arrangement_records = match self.compute_state.traces.get_mut(&id) {
+                Some(traces) => {
+                    traces.oks_mut().read_upper(&mut output_frontier);
+                    let mut records = 0;
+                    traces.oks_mut().map_batches(|batch| {
+                        use differential_dataflow::trace::BatchReader;
+                        records += u64::cast_from(batch.len());
+                    });
+                    Some(records)
+                }
+                None => {
+                    if let Some(frontier) = &collection.sink_write_frontier {
+                        output_frontier.clone_from(&frontier.borrow());
+                    }
+                    None
+                }
+            };
+
+            // The lag of the output frontier behind the frontier of the dataflow's inputs.
+            let frontier_lag_ms = progress.source_probe.with_frontier(|source| {
+                let source = source.get(0)?;
+                let output = output_frontier.get(0)?;
+                Some(u64::from(source.saturating_sub(*output)))
+            });
+
+            let status = HydrationStatus {
+                hydrated,
+                records_processed: progress.records_processed.get(),
+                snapshot_records: progress.snapshot_records.get(),
+                arrangement_records,
+                frontier_lag_ms,
+            };
+
+            let due = collection
+                .last_hydration_report
+                .map_or(true, |t| t.elapsed() >= HYDRATION_REPORT_INTERVAL);
+            let send = match &collection.reported_hydration {
+                None => true,
+                Some(_) if hydrated => true,
+                Some(reported) => due && reported != &status,
+            };
+            if send {
+                collection.reported_hydration = Some(status.clone());
+                collection.last_hydration_report = Some(Instant::now());
+                statuses.push((id, status));
+            }
+        }
+
+        if !statuses.is_empty() {
+            self.send_compute_response(ComputeResponse::HydrationStatuses(statuses));
+        }
+    }
+
+    /// Report the persist compaction queue of this process to the controller.
+    ///
+    /// The queue is shared by all workers in the process, so only one of them should report it.
+    /// It is reported at most once per [`COMPACTION_QUEUE_REPORT_INTERVAL`], and only if it has
+    /// changed since the last report.
+    pub fn report_compaction_queue(&mut self) {
+        let due = self
+            .compute_state
+            .last_compaction_queue_report
+            .map_or(true, |t| t.elapsed() >= COMPACTION_QUEUE_REPORT_INTERVAL);
+        if !due {
+            return;
+        }
+        self.compute_state.last_compaction_queue_report = Some(Instant::now());
+
+        let queue = self.compute_state.persist_clients.compaction_queue();
+        if queue != self.compute_state.reported_compaction_queue {
+            self.compute_state.reported_compaction_queue = queue.clone();
+            self.send_compute_response(ComputeResponse::CompactionQueue(queue));
+        }
+    }
+
     /// Send progress information to the coordinator.
     pub fn report_compute_frontiers(&mut self) {
         let mut new_uppers = Vec::new();
@@ -609,6 +756,14 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
         }
     }
 
+    /// Scan the shared quarantined error buffer, and forward the errors along.
+    pub fn process_quarantined_errors(&mut self) {
+        let mut batches = self.compute_state.quarantined_error_buffer.borrow_mut();
+        for (sink_id, batch) in batches.drain(..) {
+            self.send_compute_response(ComputeResponse::QuarantinedErrors(sink_id, batch));
+        }
+    }
+
     /// Send a response to the coordinator.
     fn send_compute_response(&self, response: ComputeResponse) {
         // Ignore send errors because the coordinator is free to ignore our
@@ -917,6 +1072,28 @@ impl ReportedFrontier {
     }
 }
 
+/// The minimum interval between hydration progress reports for a collection.
+const HYDRATION_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The minimum interval between reports of the persist compaction queue.
+const COMPACTION_QUEUE_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Hydration progress of a dataflow, shared by the collections it exports.
+///
+/// The counters are updated by operators in the dataflow and read when reporting hydration
+/// statuses to the controller.
+#[derive(Clone, Default)]
+pub struct HydrationProgress {
+    /// The number of snapshot records this worker has read from the dataflow's sources.
+    pub records_processed: Rc<Cell<u64>>,
+    /// An estimate of the total number of snapshot records in the dataflow's sources.
+    ///
+    /// Only determined by a single worker, and `None` until known.
+    pub snapshot_records: Rc<Cell<Option<u64>>>,
+    /// A probe tracking the frontier of the dataflow's sources.
+    pub source_probe: probe::Handle<Timestamp>,
+}
+
 /// State maintained for a compute collection.
 pub struct CollectionState {
     /// Tracks the frontier that has been reported to the controller.
@@ -933,6 +1110,10 @@ pub struct CollectionState {
     ///
     /// Only `Some` if the collection is a sink and *not* a subscribe.
     pub sink_write_frontier: Option<Rc<RefCell<Antichain<Timestamp>>>>,
+    /// Acknowledgements of the batches sent by a subscribe.
+    ///
+    /// Only `Some` if the collection is a subscribe with flow control.
+    pub subscribe_acknowledgements: Option<Rc<RefCell<SubscribeAcknowledgements>>>,
     /// Probe handles for regulating the output of dataflow sources that (transitively) feed this
     /// collection.
     ///
@@ -949,6 +1130,14 @@ pub struct CollectionState {
     created_at: Instant,
     /// Original as_of of this collection.
     as_of: Antichain<Timestamp>,
+    /// Hydration progress of the dataflow exporting this collection.
+    ///
+    /// Only `None` until the collection's dataflow has been rendered.
+    pub hydration: Option<HydrationProgress>,
+    /// The hydration status that has last been reported to the controller.
+    reported_hydration: Option<HydrationStatus>,
+    /// Time at which the hydration status was last reported.
+    last_hydration_report: Option<Instant>,
 }
 
 impl CollectionState {
@@ -957,10 +1146,14 @@ impl CollectionState {
             reported_frontier: ReportedFrontier::new(),
             sink_token: None,
             sink_write_frontier: None,
+            subscribe_acknowledgements: None,
             index_flow_control_probes: Default::default(),
             metrics,
             created_at: Instant::now(),
             as_of: Antichain::from_elem(Timestamp::MIN),
+            hydration: None,
+            reported_hydration: None,
+            last_hydration_report: None,
         }
     }
 
@@ -975,6 +1168,13 @@ impl CollectionState {
         self.observe_snapshot_produced();
     }
 
+    /// Forget the hydration status reported to the controller, so that the next report is sent
+    /// regardless of previous reports.
+    pub fn reset_reported_hydration(&mut self) {
+        self.reported_hydration = None;
+        self.last_hydration_report = None;
+    }
+
     fn is_subscribe(&self) -> bool {
         self.sink_token.is_some() && self.sink_write_frontier.is_none()
     }
@@ -15,10 +15,12 @@ use std::rc::Rc;
 use std::time::Duration;
 
 use differential_dataflow::collection::AsCollection;
+use differential_dataflow::dynamic::pointstamp::PointStamp;
 use differential_dataflow::operators::arrange::arrangement::Arrange;
 use differential_dataflow::operators::arrange::Arranged;
 use differential_dataflow::trace::TraceReader;
-use mz_expr::{permutation_for_arrangement, MirScalarExpr};
+use differential_dataflow::{Collection, Data};
+use mz_expr::{permutation_for_arrangement, LocalId, MirScalarExpr};
 use mz_ore::cast::CastFrom;
 use mz_repr::{Datum, DatumVec, Diff, GlobalId, Row, Timestamp};
 use mz_timely_util::replay::MzReplay;
@@ -27,9 +29,10 @@ use timely::dataflow::channels::pact::Pipeline;
 use timely::dataflow::channels::pushers::buffer::Session;
 use timely::dataflow::channels::pushers::{Counter, Tee};
 use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
-use timely::dataflow::operators::{Filter, InspectCore};
+use timely::dataflow::operators::{Filter, Inspect, InspectCore};
 use timely::dataflow::{Scope, StreamCore};
 use timely::logging::WorkerIdentifier;
+use timely::order::Product;
 use timely::Container;
 use tracing::error;
 use uuid::Uuid;
@@ -77,6 +80,27 @@ pub enum ComputeEvent {
         time: Timestamp,
         diff: i8,
     },
+    /// The largest number of iterations a recursive binding of a dataflow export required.
+    LetRecIterations {
+        /// Identifier of the export.
+        export_id: GlobalId,
+        /// Identifier of the recursive binding.
+        binding: LocalId,
+        /// The number of iterations.
+        iterations: u64,
+    },
+    /// Records newly derived in an iteration by a semi-naively evaluated recursive binding of a
+    /// dataflow export.
+    LetRecDelta {
+        /// Identifier of the export.
+        export_id: GlobalId,
+        /// Identifier of the recursive binding.
+        binding: LocalId,
+        /// The iteration, counting from 1.
+        iteration: u64,
+        /// The number of records newly derived.
+        records: u64,
+    },
 }
 
 /// A logged peek event.
@@ -138,6 +162,8 @@ pub(super) fn construct<A: Allocate>(
         let (mut frontier_delay_out, frontier_delay) = demux.new_output();
         let (mut peek_out, peek) = demux.new_output();
         let (mut peek_duration_out, peek_duration) = demux.new_output();
+        let (mut letrec_iterations_out, letrec_iterations) = demux.new_output();
+        let (mut letrec_deltas_out, letrec_deltas) = demux.new_output();
 
         let mut demux_state = DemuxState::default();
         let mut demux_buffer = Vec::new();
@@ -150,6 +176,8 @@ pub(super) fn construct<A: Allocate>(
                 let mut frontier_delay = frontier_delay_out.activate();
                 let mut peek = peek_out.activate();
                 let mut peek_duration = peek_duration_out.activate();
+                let mut letrec_iterations = letrec_iterations_out.activate();
+                let mut letrec_deltas = letrec_deltas_out.activate();
 
                 input.for_each(|cap, data| {
                     data.swap(&mut demux_buffer);
@@ -162,6 +190,8 @@ pub(super) fn construct<A: Allocate>(
                         frontier_delay: frontier_delay.session(&cap),
                         peek: peek.session(&cap),
                         peek_duration: peek_duration.session(&cap),
+                        letrec_iterations: letrec_iterations.session(&cap),
+                        letrec_deltas: letrec_deltas.session(&cap),
                     };
 
                     for (time, logger_id, event) in demux_buffer.drain(..) {
@@ -235,6 +265,25 @@ pub(super) fn construct<A: Allocate>(
             ])
         });
 
+        let letrec_iterations = letrec_iterations.as_collection().map(move |datum| {
+            Row::pack_slice(&[
+                Datum::String(&datum.export_id.to_string()),
+                Datum::String(&datum.binding.to_string()),
+                Datum::UInt64(u64::cast_from(worker_id)),
+                Datum::UInt64(datum.iterations),
+            ])
+        });
+
+        let letrec_deltas = letrec_deltas.as_collection().map(move |datum| {
+            Row::pack_slice(&[
+                Datum::String(&datum.export_id.to_string()),
+                Datum::String(&datum.binding.to_string()),
+                Datum::UInt64(u64::cast_from(worker_id)),
+                Datum::UInt64(datum.iteration),
+                Datum::UInt64(datum.records),
+            ])
+        });
+
         let logs = [
             (
                 LogVariant::Compute(ComputeLog::DataflowCurrent),
@@ -258,6 +307,11 @@ pub(super) fn construct<A: Allocate>(
             ),
             (LogVariant::Compute(ComputeLog::PeekCurrent), peek_current),
             (LogVariant::Compute(ComputeLog::PeekDuration), peek_duration),
+            (
+                LogVariant::Compute(ComputeLog::LetRecIterations),
+                letrec_iterations,
+            ),
+            (LogVariant::Compute(ComputeLog::LetRecDeltas), letrec_deltas),
         ];
 
         // Build the output arrangements.
@@ -304,6 +358,11 @@ struct DemuxState {
     export_imports: BTreeMap<GlobalId, BTreeMap<GlobalId, FrontierDelayState>>,
     /// Maps pending peeks to their installation time (in ns).
     peek_stash: BTreeMap<Uuid, Duration>,
+    /// Maps dataflow exports to the iteration counts of their recursive bindings.
+    export_letrec_iterations: BTreeMap<GlobalId, BTreeMap<LocalId, u64>>,
+    /// Maps dataflow exports to the records newly derived in each iteration by their
+    /// semi-naively evaluated recursive bindings.
+    export_letrec_deltas: BTreeMap<GlobalId, BTreeMap<(LocalId, u64), u64>>,
 }
 
 /// State for tracking import-export frontier lag.
@@ -330,6 +389,8 @@ struct DemuxOutput<'a> {
     frontier_delay: OutputSession<'a, FrontierDelayDatum>,
     peek: OutputSession<'a, Peek>,
     peek_duration: OutputSession<'a, u128>,
+    letrec_iterations: OutputSession<'a, LetRecIterationsDatum>,
+    letrec_deltas: OutputSession<'a, LetRecDeltasDatum>,
 }
 
 #[derive(Clone)]
@@ -357,6 +418,21 @@ struct ImportFrontierDatum {
     frontier: Timestamp,
 }
 
+#[derive(Clone)]
+struct LetRecDeltasDatum {
+    export_id: GlobalId,
+    binding: LocalId,
+    iteration: u64,
+    records: u64,
+}
+
+#[derive(Clone)]
+struct LetRecIterationsDatum {
+    export_id: GlobalId,
+    binding: LocalId,
+    iterations: u64,
+}
+
 #[derive(Clone)]
 struct FrontierDelayDatum {
     export_id: GlobalId,
@@ -406,6 +482,17 @@ impl DemuxHandler<'_, '_> {
                 time,
                 diff,
             } => self.handle_import_frontier(import_id, export_id, time, diff),
+            LetRecIterations {
+                export_id,
+                binding,
+                iterations,
+            } => self.handle_letrec_iterations(export_id, binding, iterations),
+            LetRecDelta {
+                export_id,
+                binding,
+                iteration,
+                records,
+            } => self.handle_letrec_delta(export_id, binding, iteration, records),
         }
     }
 
@@ -454,6 +541,31 @@ impl DemuxHandler<'_, '_> {
                 "missing export_imports entry at time of export drop"
             );
         }
+
+        // Remove iteration count logging for this export.
+        if let Some(bindings) = self.state.export_letrec_iterations.remove(&id) {
+            for (binding, iterations) in bindings {
+                let datum = LetRecIterationsDatum {
+                    export_id: id,
+                    binding,
+                    iterations,
+                };
+                self.output.letrec_iterations.give((datum, ts, -1));
+            }
+        }
+
+        // Remove the logging of newly derived records for this export.
+        if let Some(deltas) = self.state.export_letrec_deltas.remove(&id) {
+            for ((binding, iteration), records) in deltas {
+                let datum = LetRecDeltasDatum {
+                    export_id: id,
+                    binding,
+                    iteration,
+                    records,
+                };
+                self.output.letrec_deltas.give((datum, ts, -1));
+            }
+        }
     }
 
     fn handle_export_dependency(&mut self, export_id: GlobalId, import_id: GlobalId) {
@@ -549,6 +661,74 @@ impl DemuxHandler<'_, '_> {
         }
     }
 
+    fn handle_letrec_iterations(&mut self, export_id: GlobalId, binding: LocalId, iterations: u64) {
+        // Iterations may still be reported by a dataflow that is shutting down after its exports
+        // have been dropped.
+        if !self.state.export_dataflows.contains_key(&export_id) {
+            return;
+        }
+
+        let ts = self.ts();
+        let bindings = self
+            .state
+            .export_letrec_iterations
+            .entry(export_id)
+            .or_default();
+        if let Some(old) = bindings.insert(binding, iterations) {
+            let datum = LetRecIterationsDatum {
+                export_id,
+                binding,
+                iterations: old,
+            };
+            self.output.letrec_iterations.give((datum, ts, -1));
+        }
+        let datum = LetRecIterationsDatum {
+            export_id,
+            binding,
+            iterations,
+        };
+        self.output.letrec_iterations.give((datum, ts, 1));
+    }
+
+    fn handle_letrec_delta(
+        &mut self,
+        export_id: GlobalId,
+        binding: LocalId,
+        iteration: u64,
+        records: u64,
+    ) {
+        // Records may still be reported by a dataflow that is shutting down after its exports
+        // have been dropped.
+        if !self.state.export_dataflows.contains_key(&export_id) {
+            return;
+        }
+
+        let ts = self.ts();
+        let deltas = self
+            .state
+            .export_letrec_deltas
+            .entry(export_id)
+            .or_default();
+        let count = deltas.entry((binding, iteration)).or_default();
+        if *count > 0 {
+            let datum = LetRecDeltasDatum {
+                export_id,
+                binding,
+                iteration,
+                records: *count,
+            };
+            self.output.letrec_deltas.give((datum, ts, -1));
+        }
+        *count += records;
+        let datum = LetRecDeltasDatum {
+            export_id,
+            binding,
+            iteration,
+            records: *count,
+        };
+        self.output.letrec_deltas.give((datum, ts, 1));
+    }
+
     fn handle_import_frontier(
         &mut self,
         import_id: GlobalId,
@@ -679,3 +859,91 @@ impl Drop for RetractImportFrontiers {
         self.log();
     }
 }
+
+pub(crate) trait LogLetRecIterations {
+    /// Logs the largest number of iterations that the recursive `binding` required, based on the
+    /// `level`th iteration coordinate of its updates.
+    fn log_letrec_iterations(
+        self,
+        logger: Logger,
+        export_ids: Vec<GlobalId>,
+        binding: LocalId,
+        level: usize,
+    ) -> Self;
+
+    /// Logs the number of records newly derived in each iteration by the semi-naively evaluated
+    /// recursive `binding`, which are the records that these updates insert, based on the
+    /// `level`th iteration coordinate of the updates.
+    fn log_letrec_deltas(
+        self,
+        logger: Logger,
+        export_ids: Vec<GlobalId>,
+        binding: LocalId,
+        level: usize,
+    ) -> Self;
+}
+
+impl<G, D> LogLetRecIterations for Collection<G, D, Diff>
+where
+    G: Scope<Timestamp = Product<Timestamp, PointStamp<u64>>>,
+    D: Data,
+{
+    fn log_letrec_iterations(
+        self,
+        logger: Logger,
+        export_ids: Vec<GlobalId>,
+        binding: LocalId,
+        level: usize,
+    ) -> Self {
+        let mut iterations = 0;
+        self.inner
+            .inspect_batch(move |_time, data| {
+                // The iteration coordinate starts counting from 0, and is absent in the first
+                // iteration.
+                let max_index = data
+                    .iter()
+                    .map(|(_, time, _)| time.inner.vector.get(level).copied().unwrap_or(0))
+                    .max();
+                let Some(max_index) = max_index else { return };
+                if max_index + 1 > iterations {
+                    iterations = max_index + 1;
+                    for &export_id in export_ids.iter() {
+                        logger.log(ComputeEvent::LetRecIterations {
+                            export_id,
+                            binding,
+                            iterations,
+                        });
+                    }
+                }
+            })
+            .as_collection()
+    }
+
+    fn log_letrec_deltas(
+        self,
+        logger: Logger,
+        export_ids: Vec<GlobalId>,
+        binding: LocalId,
+        level: usize,
+    ) -> Self {
+        self.inner
+            .inspect_batch(move |_time, data| {
+                let mut deltas = BTreeMap::new();
+                for (_, time, diff) in data.iter().filter(|(_, _, diff)| *diff > 0) {
+                    let index = time.inner.vector.get(level).copied().unwrap_or(0);
+                    *deltas.entry(index + 1).or_insert(0) += diff.unsigned_abs();
+                }
+                for (iteration, records) in deltas {
+                    for &export_id in export_ids.iter() {
+                        logger.log(ComputeEvent::LetRecDelta {
+                            export_id,
+                            binding,
+                            iteration,
+                            records,
+                        });
+                    }
+                }
+            })
+            .as_collection()
+    }
+}
@@ -8,8 +8,11 @@
 // by the Apache License, Version 2.0.
 
 use mz_compute_client::metrics::{CommandMetrics, HistoryMetrics};
+use mz_ore::cast::CastFrom;
 use mz_ore::metric;
-use mz_ore::metrics::{raw, DeleteOnDropGauge, GaugeVec, GaugeVecExt, MetricsRegistry, UIntGauge};
+use mz_ore::metrics::{
+    raw, ComputedUIntGauge, DeleteOnDropGauge, GaugeVec, GaugeVecExt, MetricsRegistry, UIntGauge,
+};
 use mz_repr::GlobalId;
 use prometheus::core::{AtomicF64, GenericCounter};
 
@@ -30,6 +33,7 @@ pub struct ComputeMetrics {
     // arrangements
     arrangement_maintenance_seconds_total: raw::CounterVec,
     arrangement_maintenance_active_info: raw::UIntGaugeVec,
+    _arrangement_spilled_bytes: ComputedUIntGauge,
 }
 
 impl ComputeMetrics {
@@ -69,6 +73,13 @@ impl ComputeMetrics {
                 help: "Whether maintenance is currently occuring.",
                 var_labels: ["worker_id"],
             )),
+            _arrangement_spilled_bytes: registry.register_computed_gauge(
+                metric!(
+                    name: "mz_arrangement_spilled_bytes",
+                    help: "The number of bytes of arrangement batches backed by files in the scratch directory.",
+                ),
+                || u64::cast_from(mz_ore::region::spilled_bytes()),
+            ),
         }
     }
 
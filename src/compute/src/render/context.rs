@@ -95,6 +95,10 @@ where
     pub(super) shutdown_token: ShutdownToken,
     /// The implementation to use for rendering linear joins.
     pub(super) linear_join_impl: LinearJoinImpl,
+    /// The IDs of the exports of the dataflow associated with this context.
+    pub(super) export_ids: Vec<GlobalId>,
+    /// A logger for compute events, used to report the iterations of recursive bindings.
+    pub(super) compute_logger: Option<crate::logging::compute::Logger>,
 }
 
 impl<S: Scope, V: Data + columnation::Columnation> Context<S, V>
@@ -122,6 +126,13 @@ where
             bindings: BTreeMap::new(),
             shutdown_token: Default::default(),
             linear_join_impl: Default::default(),
+            export_ids: dataflow
+                .index_exports
+                .keys()
+                .chain(dataflow.sink_exports.keys())
+                .copied()
+                .collect(),
+            compute_logger: None,
         }
     }
 }
@@ -0,0 +1,123 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Tracking of dataflow hydration progress.
+//!
+//! A dataflow is hydrated once it has processed the snapshot of its inputs at its `as_of`. To
+//! report progress towards that point, we count the snapshot records that the dataflow's
+//! `persist_source`s emit and compare them against an estimate of the total snapshot size,
+//! obtained from persist's statistics.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use differential_dataflow::Hashable;
+use mz_ore::cast::CastFrom;
+use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::Diagnostics;
+use mz_persist_types::codec_impls::UnitSchema;
+use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_storage_client::controller::CollectionMetadata;
+use mz_storage_client::types::sources::SourceData;
+use mz_timely_util::builder_async::OperatorBuilder as AsyncOperatorBuilder;
+use mz_timely_util::probe::ProbeNotify;
+use timely::dataflow::operators::Inspect;
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+use tracing::warn;
+
+use crate::compute_state::HydrationProgress;
+
+/// Counts the snapshot records in `stream` towards the hydration progress of its dataflow, and
+/// reports the stream's frontier to the progress' source probe.
+pub(crate) fn track_snapshot_records<G>(
+    stream: &Stream<G, (Row, Timestamp, Diff)>,
+    as_of: Antichain<Timestamp>,
+    progress: &HydrationProgress,
+) -> Stream<G, (Row, Timestamp, Diff)>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let records_processed = Rc::clone(&progress.records_processed);
+    stream
+        .inspect_batch(move |time, data| {
+            // `persist_source` advances snapshot updates to the `as_of`.
+            if !as_of.less_than(time) {
+                let records = u64::cast_from(data.len());
+                records_processed.set(records_processed.get() + records);
+            }
+        })
+        .probe_notify_with(vec![progress.source_probe.clone()])
+}
+
+/// Renders an operator that estimates the number of snapshot records in the persisted `inputs`
+/// of dataflow `id` at `as_of` and records the estimate in `progress`.
+///
+/// Only a single worker determines the estimate. The operator produces no output and shuts down
+/// once the estimate is known.
+pub(crate) fn render_snapshot_estimate<G>(
+    scope: &G,
+    id: GlobalId,
+    persist_clients: Arc<PersistClientCache>,
+    inputs: Vec<(GlobalId, CollectionMetadata)>,
+    as_of: Antichain<Timestamp>,
+    progress: &HydrationProgress,
+) where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let active_worker = usize::cast_from(id.hashed()) % scope.peers() == scope.index();
+    let snapshot_records = Rc::clone(&progress.snapshot_records);
+
+    let op = AsyncOperatorBuilder::new(format!("SnapshotEstimate({id})"), scope.clone());
+    // The operator completes by itself, so there is no need to hold on to its shutdown button.
+    let _ = op.build(move |_capabilities| async move {
+        if !active_worker {
+            return;
+        }
+
+        let mut total = 0;
+        for (input_id, metadata) in inputs {
+            let client = match persist_clients.open(metadata.persist_location).await {
+                Ok(client) => client,
+                Err(err) => {
+                    warn!("dataflow {id}: failed to open persist client for {input_id}: {err}");
+                    return;
+                }
+            };
+            let read = client
+                .open_leased_reader::<SourceData, (), Timestamp, Diff>(
+                    metadata.data_shard,
+                    Arc::new(metadata.relation_desc),
+                    Arc::new(UnitSchema),
+                    Diagnostics {
+                        shard_name: input_id.to_string(),
+                        handle_purpose: format!("compute::hydration::snapshot_estimate {id}"),
+                    },
+                )
+                .await
+                .expect("could not open persist shard");
+
+            let stats = read.snapshot_stats(as_of.clone()).await;
+            read.expire().await;
+            match stats {
+                Ok(stats) => total += u64::cast_from(stats.num_updates),
+                Err(since) => {
+                    warn!(
+                        "dataflow {id}: cannot estimate snapshot of {input_id} at {:?}, \
+                         since is {:?}",
+                        as_of, since.0
+                    );
+                    return;
+                }
+            }
+        }
+
+        snapshot_records.set(Some(total));
+    });
+}
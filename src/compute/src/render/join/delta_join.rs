@@ -15,13 +15,15 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 
-use differential_dataflow::operators::arrange::Arranged;
+use differential_dataflow::lattice::Lattice;
+use differential_dataflow::operators::arrange::{Arranged, TraceAgent};
+use differential_dataflow::operators::reduce::ReduceCore;
 use differential_dataflow::trace::{BatchReader, Cursor, TraceReader};
 use differential_dataflow::{AsCollection, Collection};
 use mz_compute_client::plan::join::delta_join::{DeltaJoinPlan, DeltaPathPlan, DeltaStagePlan};
-use mz_compute_client::plan::join::JoinClosure;
+use mz_compute_client::plan::join::{JoinClosure, PaddingPlan};
 use mz_expr::MirScalarExpr;
-use mz_repr::{DatumVec, Diff, Row, RowArena};
+use mz_repr::{Datum, DatumVec, Diff, Row, RowArena};
 use mz_storage_client::types::errors::DataflowError;
 use mz_timely_util::operator::{CollectionExt, StreamExt};
 use timely::dataflow::channels::pact::Pipeline;
@@ -32,6 +34,7 @@ use timely::progress::Antichain;
 
 use crate::render::context::{ArrangementFlavor, CollectionBundle, Context, ShutdownToken};
 use crate::render::RenderTimestamp;
+use crate::typedefs::RowSpine;
 
 impl<G> Context<G, Row>
 where
@@ -103,6 +106,39 @@ where
                 }
             }
 
+            // Left outer joins additionally need the distinct keys of the arrangements of the
+            // optional inputs, to retract the padding of records that do have matches.
+            let mut present_keys = BTreeMap::new();
+            for path_plan in join_plan.path_plans.iter() {
+                let padded_source = path_plan.padded_source_arity.map(|arity| {
+                    (
+                        path_plan.source_relation,
+                        path_plan.source_key.clone(),
+                        arity,
+                    )
+                });
+                let padded_lookups = path_plan.stage_plans.iter().filter_map(|stage_plan| {
+                    stage_plan.padding.as_ref().map(|padding| {
+                        (
+                            stage_plan.lookup_relation,
+                            stage_plan.lookup_key.clone(),
+                            padding.lookup_arity,
+                        )
+                    })
+                });
+                for (relation, key, arity) in padded_source.into_iter().chain(padded_lookups) {
+                    if present_keys.contains_key(&(relation, key.clone())) {
+                        continue;
+                    }
+                    let present = match arrangements.get(&(relation, key.clone())) {
+                        Some(Ok(local)) => build_present_keys(local, arity),
+                        Some(Err(trace)) => build_present_keys(trace, arity),
+                        None => panic!("Arrangement alarmingly absent!: {}, {:?}", relation, key),
+                    };
+                    present_keys.insert((relation, key), present);
+                }
+            }
+
             for path_plan in join_plan.path_plans {
                 // Deconstruct the stages of the path plan.
                 let DeltaPathPlan {
@@ -111,6 +147,7 @@ where
                     stage_plans,
                     final_closure,
                     source_key,
+                    padded_source_arity,
                 } = path_plan;
 
                 // This collection determines changes that result from updates inbound
@@ -134,33 +171,50 @@ where
                     // Collects error streams for the region scope. Concats before leaving.
                     let mut region_errs = Vec::with_capacity(inputs.len());
 
-                    // Ensure this input is rendered, and extract its update stream.
-                    let val = arrangements
-                        .get(&(source_relation, source_key))
-                        .expect("Arrangement promised by the planner is absent!");
                     let as_of = self.as_of_frontier.clone();
-                    let update_stream = match val {
-                        Ok(local) => {
-                            let arranged = local.enter_region(region);
-                            let (update_stream, err_stream) = build_update_stream(
-                                arranged,
-                                as_of,
-                                source_relation,
-                                initial_closure,
-                            );
-                            region_errs.push(err_stream);
-                            update_stream
-                        }
-                        Err(trace) => {
-                            let arranged = trace.enter_region(region);
-                            let (update_stream, err_stream) = build_update_stream(
-                                arranged,
-                                as_of,
-                                source_relation,
-                                initial_closure,
-                            );
-                            region_errs.push(err_stream);
-                            update_stream
+                    let update_stream = if padded_source_arity.is_some() {
+                        // The source relation pads the records of the first input without
+                        // matches, so keys that become present retract padded records and keys
+                        // that become absent restore them.
+                        let present = present_keys
+                            .get(&(source_relation, source_key))
+                            .expect("Present keys promised by the planner are absent!");
+                        let (update_stream, err_stream) = build_update_stream(
+                            present.enter_region(region),
+                            as_of,
+                            source_relation,
+                            initial_closure,
+                        );
+                        region_errs.push(err_stream);
+                        update_stream.negate()
+                    } else {
+                        // Ensure this input is rendered, and extract its update stream.
+                        let val = arrangements
+                            .get(&(source_relation, source_key))
+                            .expect("Arrangement promised by the planner is absent!");
+                        match val {
+                            Ok(local) => {
+                                let arranged = local.enter_region(region);
+                                let (update_stream, err_stream) = build_update_stream(
+                                    arranged,
+                                    as_of,
+                                    source_relation,
+                                    initial_closure,
+                                );
+                                region_errs.push(err_stream);
+                                update_stream
+                            }
+                            Err(trace) => {
+                                let arranged = trace.enter_region(region);
+                                let (update_stream, err_stream) = build_update_stream(
+                                    arranged,
+                                    as_of,
+                                    source_relation,
+                                    initial_closure,
+                                );
+                                region_errs.push(err_stream);
+                                update_stream
+                            }
                         }
                     };
                     // Promote `time` to a datum element.
@@ -182,8 +236,39 @@ where
                             stream_thinning,
                             lookup_key,
                             closure,
+                            padding,
                         } = stage_plan;
 
+                        // Records of the stream without matching records in the lookup relation
+                        // are padded with nulls, with the same relative order of the inputs.
+                        let padded = padding.map(|padding| {
+                            let present = present_keys
+                                .get(&(lookup_relation, lookup_key.clone()))
+                                .expect("Present keys promised by the planner are absent!")
+                                .enter_region(region);
+                            if source_relation < lookup_relation {
+                                build_padding(
+                                    update_stream.clone(),
+                                    present,
+                                    stream_key.clone(),
+                                    stream_thinning.clone(),
+                                    |t1, t2| t1.le(t2),
+                                    padding,
+                                    self.shutdown_token.clone(),
+                                )
+                            } else {
+                                build_padding(
+                                    update_stream.clone(),
+                                    present,
+                                    stream_key.clone(),
+                                    stream_thinning.clone(),
+                                    |t1, t2| t1.lt(t2),
+                                    padding,
+                                    self.shutdown_token.clone(),
+                                )
+                            }
+                        });
+
                         // We require different logic based on the relative order of the two inputs.
                         // If the `source` relation precedes the `lookup` relation, we present all
                         // updates with less or equal `time`, and otherwise we present only updates
@@ -242,6 +327,10 @@ where
                             };
                         update_stream = oks;
                         region_errs.push(errs);
+                        if let Some((padded, errs)) = padded {
+                            update_stream = update_stream.concat(&padded);
+                            region_errs.push(errs);
+                        }
                     }
 
                     // Delay updates as appropriate.
@@ -417,6 +506,94 @@ where
     }
 }
 
+/// Constructs the padded records of a left outer join stage from supplied arguments.
+///
+/// Each record of `updates` is padded with nulls, and the padded records whose keys are present
+/// in the lookup relation are retracted. The `present` arrangement contains the distinct keys of
+/// the lookup relation, each with a null value for each of its columns, so that a `half_join`
+/// against it produces exactly the padded records to retract.
+fn build_padding<G, Tr, CF>(
+    updates: Collection<G, (Row, G::Timestamp), Diff>,
+    present: Arranged<G, Tr>,
+    prev_key: Vec<MirScalarExpr>,
+    prev_thinning: Vec<usize>,
+    comparison: CF,
+    PaddingPlan {
+        lookup_arity,
+        closure,
+    }: PaddingPlan,
+    shutdown_token: ShutdownToken,
+) -> (
+    Collection<G, (Row, G::Timestamp), Diff>,
+    Collection<G, DataflowError, Diff>,
+)
+where
+    G: Scope,
+    G::Timestamp: crate::render::RenderTimestamp,
+    Tr: TraceReader<Time = G::Timestamp, Key = Row, Val = Row, R = Diff> + Clone + 'static,
+    CF: Fn(&G::Timestamp, &G::Timestamp) -> bool + 'static,
+{
+    let (matched, matched_errs) = build_halfjoin(
+        updates.clone(),
+        present,
+        prev_key.clone(),
+        prev_thinning.clone(),
+        comparison,
+        closure.clone(),
+        shutdown_token,
+    );
+
+    let (padded, padded_errs) = updates.flat_map_fallible("DeltaJoinPadding", {
+        // Reuseable allocation for unpacking.
+        let mut datums = DatumVec::new();
+        let mut row_builder = Row::default();
+        move |(row, time)| {
+            let temp_storage = RowArena::new();
+            let datums_local = datums.borrow_with(&row);
+            let padded_datums = prev_key
+                .iter()
+                .map(|e| e.eval(&datums_local, &temp_storage))
+                .collect::<Result<Vec<_>, _>>();
+            let result = padded_datums.and_then(|mut padded_datums| {
+                padded_datums.extend(prev_thinning.iter().map(|&c| datums_local[c]));
+                padded_datums.extend(std::iter::repeat(Datum::Null).take(lookup_arity));
+                closure.apply(&mut padded_datums, &temp_storage, &mut row_builder)
+            });
+            result
+                .map(|row| row.map(|row| (row, time)))
+                .map_err(DataflowError::from)
+                .transpose()
+        }
+    });
+
+    (
+        padded.concat(&matched.negate()),
+        padded_errs.concat(&matched_errs.negate()),
+    )
+}
+
+/// Arranges the distinct keys of `arrangement`, each with a null value for each of `arity` columns.
+///
+/// The resulting arrangement is maintained in addition to `arrangement`, and is shared by all
+/// paths that pad records looked up in it.
+fn build_present_keys<G, R>(
+    arrangement: &R,
+    arity: usize,
+) -> Arranged<G, TraceAgent<RowSpine<Row, Row, G::Timestamp, Diff>>>
+where
+    G: Scope,
+    G::Timestamp: Lattice,
+    R: ReduceCore<G, Row, Row, Diff>,
+{
+    let mut nulls = Row::default();
+    nulls
+        .packer()
+        .extend(std::iter::repeat(Datum::Null).take(arity));
+    arrangement.reduce_abelian("DeltaJoinPresentKeys", move |_key, _s, t| {
+        t.push((nulls.clone(), 1))
+    })
+}
+
 /// Builds the beginning of the update stream of a delta path.
 ///
 /// At start-up time only the delta path for the first relation sees updates, since any updates fed to the
@@ -13,11 +13,13 @@
 
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::operators::arrange::arrangement::{Arrange, Arranged};
+use differential_dataflow::operators::arrange::TraceAgent;
+use differential_dataflow::operators::reduce::ReduceCore;
 use differential_dataflow::trace::TraceReader;
 use differential_dataflow::{AsCollection, Collection, Data};
 use mz_compute_client::plan::join::linear_join::{LinearJoinPlan, LinearStagePlan};
-use mz_compute_client::plan::join::JoinClosure;
-use mz_repr::{DatumVec, Diff, Row, RowArena};
+use mz_compute_client::plan::join::{JoinClosure, PaddingPlan};
+use mz_repr::{Datum, DatumVec, Diff, Row, RowArena};
 use mz_storage_client::types::errors::DataflowError;
 use mz_timely_util::operator::CollectionExt;
 use timely::dataflow::operators::OkErr;
@@ -214,6 +216,7 @@ fn differential_join<G, T>(
         lookup_key,
         closure,
         lookup_relation: _,
+        padding,
     }: LinearStagePlan,
     errors: &mut Vec<Collection<G, DataflowError, Diff>>,
 ) -> Collection<G, Row, Diff>
@@ -260,30 +263,42 @@ where
         }
         JoinedFlavor::Local(local) => match arrangement {
             ArrangementFlavor::Local(oks, errs1) => {
+                let padded = padding.map(|padding| {
+                    differential_join_padding(join_impl, &local, &oks, padding, errors)
+                });
                 let (oks, errs2) = differential_join_inner(join_impl, local, oks, closure);
                 errors.push(errs1.as_collection(|k, _v| k.clone()));
                 errors.extend(errs2);
-                oks
+                oks.concat_many(padded)
             }
             ArrangementFlavor::Trace(_gid, oks, errs1) => {
+                let padded = padding.map(|padding| {
+                    differential_join_padding(join_impl, &local, &oks, padding, errors)
+                });
                 let (oks, errs2) = differential_join_inner(join_impl, local, oks, closure);
                 errors.push(errs1.as_collection(|k, _v| k.clone()));
                 errors.extend(errs2);
-                oks
+                oks.concat_many(padded)
             }
         },
         JoinedFlavor::Trace(trace) => match arrangement {
             ArrangementFlavor::Local(oks, errs1) => {
+                let padded = padding.map(|padding| {
+                    differential_join_padding(join_impl, &trace, &oks, padding, errors)
+                });
                 let (oks, errs2) = differential_join_inner(join_impl, trace, oks, closure);
                 errors.push(errs1.as_collection(|k, _v| k.clone()));
                 errors.extend(errs2);
-                oks
+                oks.concat_many(padded)
             }
             ArrangementFlavor::Trace(_gid, oks, errs1) => {
+                let padded = padding.map(|padding| {
+                    differential_join_padding(join_impl, &trace, &oks, padding, errors)
+                });
                 let (oks, errs2) = differential_join_inner(join_impl, trace, oks, closure);
                 errors.push(errs1.as_collection(|k, _v| k.clone()));
                 errors.extend(errs2);
-                oks
+                oks.concat_many(padded)
             }
         },
     }
@@ -347,3 +362,61 @@ where
         (oks, None)
     }
 }
+
+/// Produces the records of the arranged version of the join of previous inputs that have no
+/// matching records in `next_input`, padded as instructed by `padding`.
+///
+/// All records are padded, and the padded records with matching keys are retracted, which only
+/// requires the distinct keys of `next_input` rather than a separate negative join. The distinct
+/// keys are maintained in an additional arrangement, which EXPLAIN reports as `present_keys`.
+fn differential_join_padding<G, T, Tr1, R>(
+    join_impl: LinearJoinImpl,
+    prev_keyed: &Arranged<G, Tr1>,
+    next_input: &R,
+    PaddingPlan {
+        lookup_arity,
+        closure,
+    }: PaddingPlan,
+    errors: &mut Vec<Collection<G, DataflowError, Diff>>,
+) -> Collection<G, Row, Diff>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Refines<T>,
+    T: Timestamp + Lattice,
+    Tr1: TraceReader<Key = Row, Val = Row, Time = G::Timestamp, R = Diff> + Clone + 'static,
+    R: ReduceCore<G, Row, Row, Diff>,
+{
+    let mut nulls = Row::default();
+    nulls
+        .packer()
+        .extend(std::iter::repeat(Datum::Null).take(lookup_arity));
+
+    // The keys present in `next_input`, each with a null for each column of the lookup relation.
+    let present: Arranged<G, TraceAgent<RowSpine<Row, Row, G::Timestamp, Diff>>> = next_input
+        .reduce_abelian("OuterJoinPresentKeys", {
+            let nulls = nulls.clone();
+            move |_key, _s, t| t.push((nulls.clone(), 1))
+        });
+    let (matched, matched_errs) =
+        differential_join_inner(join_impl, prev_keyed.clone(), present, closure.clone());
+
+    let (padded, padded_errs) = prev_keyed
+        .as_collection(|key, val| (key.clone(), val.clone()))
+        .flat_map_fallible("OuterJoinPadding", {
+            // Reuseable allocation for unpacking.
+            let mut datums = DatumVec::new();
+            let mut row_builder = Row::default();
+            move |(key, val)| {
+                let temp_storage = RowArena::new();
+                let mut datums_local = datums.borrow_with_many(&[&key, &val, &nulls]);
+                closure
+                    .apply(&mut datums_local, &temp_storage, &mut row_builder)
+                    .map_err(DataflowError::from)
+                    .transpose()
+            }
+        });
+    errors.push(padded_errs);
+    errors.extend(matched_errs.map(|errs| errs.negate()));
+
+    padded.concat(&matched.negate())
+}
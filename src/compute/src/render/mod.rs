@@ -112,6 +112,7 @@ use differential_dataflow::{AsCollection, Collection};
 use itertools::izip;
 use mz_compute_client::plan::Plan;
 use mz_compute_client::types::dataflows::{BuildDesc, DataflowDescription, IndexDesc};
+use mz_compute_client::types::sinks::ComputeSinkConnection;
 use mz_expr::{EvalError, Id};
 use mz_repr::{GlobalId, Row};
 use mz_storage_client::controller::CollectionMetadata;
@@ -132,19 +133,22 @@ use timely::worker::Worker as TimelyWorker;
 use timely::PartialOrder;
 
 use crate::arrangement::manager::TraceBundle;
-use crate::compute_state::ComputeState;
-use crate::logging::compute::LogImportFrontiers;
+use crate::compute_state::{ComputeState, HydrationProgress};
+use crate::logging::compute::{LogImportFrontiers, LogLetRecIterations};
 use crate::render::context::{ArrangementFlavor, Context, ShutdownToken};
 use crate::typedefs::{ErrSpine, RowKeySpine};
 
 pub mod context;
 mod errors;
 mod flat_map;
+mod hydration;
 mod join;
+mod order_statistic;
 mod reduce;
 pub mod sinks;
 mod threshold;
 mod top_k;
+mod window;
 
 pub use context::CollectionBundle;
 pub use join::LinearJoinImpl;
@@ -188,6 +192,26 @@ pub fn build_compute_dataflow<A: Allocate>(
     // Only set if the dataflow instantiates any `persist_source`s.
     let mut flow_control_probe: Option<probe::Handle<_>> = None;
 
+    // Flow-controlled subscribes rely on `persist_source` flow control to pause the dataflow
+    // while they wait for acknowledgements, which requires a bound on the in-flight bytes.
+    let flow_controlled_subscribe = dataflow.sink_exports.values().any(|sink| {
+        matches!(
+            &sink.connection,
+            ComputeSinkConnection::Subscribe(subscribe) if subscribe.max_pending_rows.is_some()
+        )
+    });
+    let max_inflight_bytes = if flow_controlled_subscribe {
+        std::cmp::min(
+            compute_state.dataflow_max_inflight_bytes,
+            crate::sink::subscribe::FLOW_CONTROL_MAX_INFLIGHT_BYTES,
+        )
+    } else {
+        compute_state.dataflow_max_inflight_bytes
+    };
+
+    // Hydration progress, shared by all collections exported by this dataflow.
+    let hydration_progress = HydrationProgress::default();
+
     let name = format!("Dataflow: {}", &dataflow.debug_name);
     let input_name = format!("InputRegion: {}", &dataflow.debug_name);
     let build_name = format!("BuildRegion: {}", &dataflow.debug_name);
@@ -216,7 +240,7 @@ pub fn build_compute_dataflow<A: Allocate>(
                     );
                     let flow_control = FlowControl {
                         progress_stream: flow_control_input,
-                        max_inflight_bytes: compute_state.dataflow_max_inflight_bytes,
+                        max_inflight_bytes,
                         summary: mz_repr::Timestamp::minimum().step_forward(),
                         // TODO(guswynn): add metrics for compute flow control
                         metrics: None,
@@ -250,6 +274,10 @@ pub fn build_compute_dataflow<A: Allocate>(
                         ok_stream = ok_stream.log_import_frontiers(logger, *source_id, export_ids);
                     }
 
+                    let as_of = dataflow.as_of.clone().unwrap();
+                    ok_stream =
+                        hydration::track_snapshot_records(&ok_stream, as_of, &hydration_progress);
+
                     let (oks, errs) = (
                         ok_stream.as_collection().leave_region().leave_region(),
                         err_stream.as_collection().leave_region().leave_region(),
@@ -263,6 +291,23 @@ pub fn build_compute_dataflow<A: Allocate>(
             }
         });
 
+        // Estimate the snapshot size of the persisted inputs, to report hydration progress.
+        let persisted_inputs = dataflow
+            .source_imports
+            .iter()
+            .map(|(id, (source, _))| (*id, source.storage_metadata.clone()))
+            .collect::<Vec<_>>();
+        if let Some(id) = dataflow.export_ids().next() {
+            hydration::render_snapshot_estimate(
+                &*scope,
+                id,
+                Arc::clone(&compute_state.persist_clients),
+                persisted_inputs,
+                dataflow.as_of.clone().unwrap(),
+                &hydration_progress,
+            );
+        }
+
         // Collect flow control probes for this dataflow.
         let index_ids = dataflow.index_imports.keys();
         let output_probes: Vec<_> = index_ids
@@ -280,6 +325,7 @@ pub fn build_compute_dataflow<A: Allocate>(
             scope.clone().iterative::<PointStamp<u64>, _, _>(|region| {
                 let mut context = Context::for_dataflow_in(&dataflow, region.clone());
                 context.linear_join_impl = compute_state.linear_join_impl;
+                context.compute_logger = compute_state.compute_logger.clone();
 
                 for (id, (oks, errs)) in imported_sources.into_iter() {
                     let bundle = crate::render::CollectionBundle::from_collections(
@@ -384,7 +430,13 @@ pub fn build_compute_dataflow<A: Allocate>(
                 }
             });
         }
-    })
+    });
+
+    for id in dataflow.export_ids() {
+        if let Some(collection) = compute_state.collections.get_mut(&id) {
+            collection.hydration = Some(hydration_progress.clone());
+        }
+    }
 }
 
 // This implementation block allows child timestamps to vary from parent timestamps,
@@ -621,11 +673,13 @@ where
             ids,
             values,
             limits,
+            semi_naive,
             body,
         } = plan
         {
             assert_eq!(ids.len(), values.len());
             assert_eq!(ids.len(), limits.len());
+            assert_eq!(ids.len(), semi_naive.len());
             // It is important that we only use the `Variable` until the object is bound.
             // At that point, all subsequent uses should have access to the object itself.
             let mut variables = BTreeMap::new();
@@ -646,7 +700,14 @@ where
                 variables.insert(Id::Local(*id), (oks_v, err_v));
             }
             // Now render each of the bindings.
-            for (id, value, limit) in izip!(ids.iter(), values.into_iter(), limits.into_iter()) {
+            for (index, (id, value, limit, semi_naive)) in izip!(
+                ids.iter(),
+                values.into_iter(),
+                limits.into_iter(),
+                semi_naive.into_iter()
+            )
+            .enumerate()
+            {
                 let bundle = self.render_recursive_plan(level + 1, value);
                 // We need to ensure that the raw collection exists, but do not have enough information
                 // here to cause that to happen.
@@ -656,6 +717,25 @@ where
 
                 // Set oks variable to `oks` but consolidated to ensure iteration ceases at fixed point.
                 let mut oks = oks.consolidate_named::<RowKeySpine<_, _, _>>("LetRecConsolidation");
+                if let Some(logger) = &self.compute_logger {
+                    // The records newly derived by a semi-naively evaluated binding are reported
+                    // for that binding, which follows them.
+                    oks = if semi_naive {
+                        oks.log_letrec_deltas(
+                            logger.clone(),
+                            self.export_ids.clone(),
+                            ids[index + 1],
+                            level,
+                        )
+                    } else {
+                        oks.log_letrec_iterations(
+                            logger.clone(),
+                            self.export_ids.clone(),
+                            *id,
+                            level,
+                        )
+                    };
+                }
                 if let Some(token) = &self.shutdown_token.get_inner() {
                     oks = oks.with_token(Weak::clone(token));
                 }
@@ -875,6 +955,15 @@ where
                 let input = self.render_plan(*input);
                 self.render_topk(input, top_k_plan)
             }
+            Plan::Window {
+                input,
+                key_val_plan,
+                window_plan,
+                input_key,
+            } => {
+                let input = self.render_plan(*input);
+                self.render_window(input, key_val_plan, window_plan, input_key)
+            }
             Plan::Negate { input } => {
                 let input = self.render_plan(*input);
                 let (oks, errs) = input.as_specific_collection(None);
@@ -0,0 +1,442 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Incremental maintenance of order statistics.
+//!
+//! The order statistics `percentile_cont`, `percentile_disc`, and `mode` are planned as
+//! hierarchical reductions, but each of them is rendered as a tree of buckets whose counts
+//! are kept in arrangements. Every non-null value is mapped to a 64-bit prefix, and the
+//! buckets at depth `d` of the tree of a group are the distinct leading `d` bytes of the
+//! prefixes of its values. The leaves of the tree are the full prefixes, which hold the
+//! distinct values with that prefix and their counts.
+//!
+//! For percentiles, the prefix preserves the order of the values, and every bucket keeps the
+//! counts of its children. A percentile is located by descending from the root, at each
+//! level following the child whose counts cover the position of the percentile, and picking
+//! the value at the remaining position from the leaf. An update to the input only changes
+//! one count per level, and locating a percentile only visits one bucket per level.
+//!
+//! For `mode`, the prefix is a hash of the value, and every bucket keeps the most frequent
+//! value among its children, with its count.
+
+use differential_dataflow::hashable::Hashable;
+use differential_dataflow::lattice::Lattice;
+use differential_dataflow::operators::arrange::arrangement::Arrange;
+use differential_dataflow::operators::reduce::ReduceCore;
+use differential_dataflow::operators::JoinCore;
+use differential_dataflow::Collection;
+use mz_expr::{AggregateFunc, EvalError};
+use mz_ore::cast::{CastFrom, ReinterpretCast};
+use mz_repr::{Datum, Diff, Row};
+use mz_storage_client::types::errors::DataflowError;
+use timely::dataflow::Scope;
+use timely::progress::timestamp::Refines;
+use timely::progress::Timestamp;
+
+use crate::render::context::{Arrangement, Context};
+use crate::typedefs::{ErrValSpine, RowSpine};
+
+/// The number of bits of a prefix that select a child of a bucket.
+const DIGIT_BITS: u32 = 8;
+/// The number of levels of buckets above the leaves, which together consume all bits of a
+/// prefix.
+const DEPTH: u32 = 64 / DIGIT_BITS;
+
+impl<G, T> Context<G, Row, T>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Refines<T>,
+    T: Timestamp + Lattice,
+{
+    /// Build the dataflow to maintain the order statistic `func` over the values of `input`,
+    /// which are single-datum rows.
+    ///
+    /// The output holds the value of the order statistic for every group with non-null
+    /// values, as a single-datum row.
+    pub(super) fn build_order_statistic<S>(
+        &self,
+        input: &Collection<S, (Row, Row), Diff>,
+        func: AggregateFunc,
+    ) -> (
+        Collection<S, (Row, Row), Diff>,
+        Collection<S, DataflowError, Diff>,
+    )
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        let is_mode = matches!(func, AggregateFunc::Mode { .. });
+        let prefixed = input.flat_map(move |(key, value)| {
+            let datum = value.unpack_first();
+            if datum.is_null() {
+                None
+            } else if is_mode {
+                let prefix = value.hashed();
+                Some((key, prefix, value))
+            } else {
+                Some((key, order_prefix(datum), value))
+            }
+        });
+
+        // The leaves of the tree hold the distinct values of each prefix.
+        let leaves = prefixed
+            .map(|(key, prefix, value)| (bucket_key(&key, prefix), value))
+            .arrange_named::<RowSpine<_, _, _, _>>("Arrange OrderStatistic leaves");
+        let error_logger = self.error_logger();
+        let errs = leaves
+            .reduce_abelian::<_, ErrValSpine<_, _, _>>(
+                "OrderStatistic Error Check",
+                move |_key, source, target| {
+                    for (value, count) in source.iter() {
+                        if count.is_positive() {
+                            continue;
+                        }
+                        let message = "Non-positive accumulation in OrderStatistic";
+                        error_logger.log(message, &format!("value={value:?}, count={count}"));
+                        target.push((EvalError::Internal(message.to_string()).into(), 1));
+                        return;
+                    }
+                },
+            )
+            .as_collection(|_, v| v.clone());
+
+        let oks = if is_mode {
+            self.build_mode(leaves, func)
+        } else {
+            self.build_percentile(&prefixed, leaves, func)
+        };
+        (oks, errs)
+    }
+
+    /// Build the dataflow to maintain the percentile `func`, by descending the counts of the
+    /// buckets of a tree to the leaf that holds the value at each relevant position.
+    fn build_percentile<S>(
+        &self,
+        prefixed: &Collection<S, (Row, u64, Row), Diff>,
+        leaves: Arrangement<S, Row>,
+        func: AggregateFunc,
+    ) -> Collection<S, (Row, Row), Diff>
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        // Each leaf is summarized as its values in ascending order, alternating with their
+        // counts.
+        let leaves = leaves.reduce_abelian::<_, RowSpine<_, _, _, _>>(
+            "Reduce OrderStatistic leaves",
+            move |_key, source, target| {
+                let mut histogram: Vec<_> = source
+                    .iter()
+                    .filter(|(_value, count)| count.is_positive())
+                    .map(|(value, count)| (value.unpack_first(), *count))
+                    .collect();
+                if histogram.is_empty() {
+                    return;
+                }
+                histogram.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+                target.push((
+                    Row::pack(
+                        histogram
+                            .into_iter()
+                            .flat_map(|(value, count)| [value, Datum::Int64(count)]),
+                    ),
+                    1,
+                ));
+            },
+        );
+
+        // The buckets at each depth keep the counts of their children, in ascending order,
+        // alternating with the digits that select them.
+        let levels: Vec<_> = (0..DEPTH)
+            .map(|depth| {
+                prefixed
+                    .map(move |(key, prefix, _value)| {
+                        let child = bucket(prefix, depth + 1) & ((1 << DIGIT_BITS) - 1);
+                        (
+                            bucket_key(&key, bucket(prefix, depth)),
+                            Row::pack_slice(&[Datum::UInt64(child)]),
+                        )
+                    })
+                    .arrange_named::<RowSpine<_, _, _, _>>("Arrange OrderStatistic counts")
+                    .reduce_abelian::<_, RowSpine<_, _, _, _>>(
+                        "Reduce OrderStatistic counts",
+                        move |_key, source, target| {
+                            let mut counts: Vec<_> = source
+                                .iter()
+                                .filter(|(_digit, count)| count.is_positive())
+                                .map(|(digit, count)| {
+                                    (digit.unpack_first().unwrap_uint64(), *count)
+                                })
+                                .collect();
+                            if counts.is_empty() {
+                                return;
+                            }
+                            counts.sort();
+                            target.push((
+                                Row::pack(counts.into_iter().flat_map(|(digit, count)| {
+                                    [Datum::UInt64(digit), Datum::Int64(count)]
+                                })),
+                                1,
+                            ));
+                        },
+                    )
+            })
+            .collect();
+
+        // The positions of the values that determine the percentile follow from the number of
+        // values in the root bucket. A query holds its index, its position within the bucket
+        // it is in, and the weight of the upper position.
+        let query_func = func.clone();
+        let mut queries = levels[0]
+            .as_collection(|root, counts| (root.clone(), counts.clone()))
+            .flat_map(move |(root, counts)| {
+                let total: Diff = counts
+                    .iter()
+                    .skip(1)
+                    .step_by(2)
+                    .map(|c| c.unwrap_int64())
+                    .sum();
+                let total = u64::try_from(total).expect("counts are positive");
+                let (lower, upper, weight) = query_func.percentile_positions(total);
+                let positions = if lower == upper {
+                    vec![lower]
+                } else {
+                    vec![lower, upper]
+                };
+                positions
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, position)| {
+                        let query = Row::pack_slice(&[
+                            Datum::UInt64(u64::cast_from(index)),
+                            Datum::UInt64(position),
+                            Datum::from(weight),
+                        ]);
+                        (root.clone(), query)
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+        // Descend one level of the tree at a time, into the child that covers the position.
+        for level in levels.iter() {
+            queries = queries
+                .arrange_named::<RowSpine<_, _, _, _>>("Arrange OrderStatistic queries")
+                .join_core(level, |node, query, counts| {
+                    let (index, position, weight) = unpack_query(query);
+                    let (key, bucket) = split_bucket_key(node);
+                    let mut counts = counts.iter();
+                    let mut position = position;
+                    while let (Some(digit), Some(count)) = (counts.next(), counts.next()) {
+                        let count =
+                            u64::try_from(count.unwrap_int64()).expect("counts are positive");
+                        if position < count {
+                            let child = (bucket << DIGIT_BITS) | digit.unwrap_uint64();
+                            let query = Row::pack_slice(&[
+                                Datum::UInt64(index),
+                                Datum::UInt64(position),
+                                Datum::from(weight),
+                            ]);
+                            return Some((bucket_key(&key, child), query));
+                        }
+                        position -= count;
+                    }
+                    None
+                });
+        }
+
+        // Pick the value at the remaining position of each leaf, and evaluate the percentile
+        // from the values of the queries of each group.
+        queries
+            .arrange_named::<RowSpine<_, _, _, _>>("Arrange OrderStatistic queries")
+            .join_core(&leaves, |node, query, histogram| {
+                let (index, position, weight) = unpack_query(query);
+                let (key, _prefix) = split_bucket_key(node);
+                let mut histogram = histogram.iter();
+                let mut position = position;
+                while let (Some(value), Some(count)) = (histogram.next(), histogram.next()) {
+                    let count = u64::try_from(count.unwrap_int64()).expect("counts are positive");
+                    if position < count {
+                        let value =
+                            Row::pack_slice(&[Datum::UInt64(index), value, Datum::from(weight)]);
+                        return Some((key, value));
+                    }
+                    position -= count;
+                }
+                None
+            })
+            .arrange_named::<RowSpine<_, _, _, _>>("Arrange OrderStatistic values")
+            .reduce_abelian::<_, RowSpine<_, _, _, _>>("Reduce OrderStatistic", {
+                move |_key, source, target| {
+                    // The upper position is missing if it is the lower one.
+                    let mut values = [None, None];
+                    for (value, _count) in source.iter() {
+                        let mut datums = value.iter();
+                        let index = usize::cast_from(datums.next().unwrap().unwrap_uint64());
+                        let value = datums.next().unwrap();
+                        let weight = datums.next().unwrap().unwrap_float64();
+                        values[index] = Some((value, weight));
+                    }
+                    let Some((lower, weight)) = values[0] else {
+                        return;
+                    };
+                    let upper = values[1].map_or(lower, |(upper, _weight)| upper);
+                    target.push((
+                        Row::pack_slice(&[func.eval_percentile(lower, upper, weight)]),
+                        1,
+                    ));
+                }
+            })
+            .as_collection(|key, value| (key.clone(), value.clone()))
+    }
+
+    /// Build the dataflow to maintain `mode`, by keeping the most frequent value of each
+    /// bucket of a tree, from the most frequent values of its children.
+    fn build_mode<S>(
+        &self,
+        leaves: Arrangement<S, Row>,
+        func: AggregateFunc,
+    ) -> Collection<S, (Row, Row), Diff>
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        let leaf_func = func.clone();
+        let mut modes = leaves
+            .reduce_abelian::<_, RowSpine<_, _, _, _>>("Reduce OrderStatistic leaves", {
+                move |_key, source, target| {
+                    let mode = source
+                        .iter()
+                        .filter(|(_value, count)| count.is_positive())
+                        .map(|(value, count)| (value.unpack_first(), *count))
+                        .max_by(|lhs, rhs| leaf_func.mode_cmp(*lhs, *rhs));
+                    if let Some((value, count)) = mode {
+                        target.push((Row::pack_slice(&[value, Datum::Int64(count)]), 1));
+                    }
+                }
+            })
+            .as_collection(|node, mode| (node.clone(), mode.clone()));
+
+        for _ in 0..DEPTH {
+            let func = func.clone();
+            modes = modes
+                .map(|(node, mode)| {
+                    let (key, bucket) = split_bucket_key(&node);
+                    (bucket_key(&key, bucket >> DIGIT_BITS), mode)
+                })
+                .arrange_named::<RowSpine<_, _, _, _>>("Arrange OrderStatistic modes")
+                .reduce_abelian::<_, RowSpine<_, _, _, _>>(
+                    "Reduce OrderStatistic modes",
+                    move |_key, source, target| {
+                        let mode = source
+                            .iter()
+                            .map(|(mode, _count)| *mode)
+                            .max_by(|lhs, rhs| func.mode_cmp(unpack_mode(lhs), unpack_mode(rhs)));
+                        if let Some(mode) = mode {
+                            target.push((mode.clone(), 1));
+                        }
+                    },
+                )
+                .as_collection(|node, mode| (node.clone(), mode.clone()));
+        }
+
+        modes.map(|(root, mode)| {
+            let (key, _root) = split_bucket_key(&root);
+            (key, Row::pack_slice(&[mode.unpack_first()]))
+        })
+    }
+}
+
+/// Returns the bucket at `depth` that contains `prefix`, i.e., its leading `depth` digits.
+fn bucket(prefix: u64, depth: u32) -> u64 {
+    prefix.checked_shr(64 - DIGIT_BITS * depth).unwrap_or(0)
+}
+
+/// Appends `bucket` to the datums of `key`.
+fn bucket_key(key: &Row, bucket: u64) -> Row {
+    Row::pack(key.iter().chain([Datum::UInt64(bucket)]))
+}
+
+/// Splits a row produced by [`bucket_key`] into the key and the bucket.
+fn split_bucket_key(bucket_key: &Row) -> (Row, u64) {
+    let mut datums: Vec<_> = bucket_key.iter().collect();
+    let bucket = datums.pop().unwrap().unwrap_uint64();
+    (Row::pack_slice(&datums), bucket)
+}
+
+/// Unpacks the index, position, and weight of a query for a percentile.
+fn unpack_query(query: &Row) -> (u64, u64, f64) {
+    let mut datums = query.iter();
+    let index = datums.next().unwrap().unwrap_uint64();
+    let position = datums.next().unwrap().unwrap_uint64();
+    let weight = datums.next().unwrap().unwrap_float64();
+    (index, position, weight)
+}
+
+/// Unpacks the value and count of the most frequent value of a bucket.
+fn unpack_mode(mode: &Row) -> (Datum, Diff) {
+    let mut datums = mode.iter();
+    let value = datums.next().unwrap();
+    let count = datums.next().unwrap().unwrap_int64();
+    (value, count)
+}
+
+/// Maps a non-null value to a prefix that does not decrease as values of its type increase,
+/// such that the buckets of the prefixes of a tree are ordered like the values they hold.
+///
+/// Types without such a prefix map all of their values to the same prefix, which puts all
+/// values of a group into a single leaf.
+fn order_prefix(datum: Datum) -> u64 {
+    /// Flips the sign bit of a signed integer, which orders it like an unsigned one.
+    fn signed(i: i64) -> u64 {
+        u64::reinterpret_cast(i) ^ (1 << 63)
+    }
+
+    /// Orders the bits of a float like the float, with negative and positive zero equal and
+    /// NaN greatest.
+    fn float(f: f64) -> u64 {
+        if f.is_nan() {
+            return u64::MAX;
+        }
+        // Adding zero turns negative zero into positive zero.
+        let bits = (f + 0.0).to_bits();
+        if bits & (1 << 63) != 0 {
+            !bits
+        } else {
+            bits | (1 << 63)
+        }
+    }
+
+    /// Takes the leading eight bytes, which orders byte strings like lexicographic order.
+    fn bytes(b: &[u8]) -> u64 {
+        let mut prefix = [0; 8];
+        let len = b.len().min(8);
+        prefix[..len].copy_from_slice(&b[..len]);
+        u64::from_be_bytes(prefix)
+    }
+
+    match datum {
+        Datum::False => 0,
+        Datum::True => 1,
+        Datum::Int16(i) => signed(i64::from(i)),
+        Datum::Int32(i) => signed(i64::from(i)),
+        Datum::Int64(i) => signed(i),
+        Datum::UInt8(u) => u64::from(u),
+        Datum::UInt16(u) => u64::from(u),
+        Datum::UInt32(u) => u64::from(u),
+        Datum::UInt64(u) => u,
+        Datum::Float32(f) => float(f64::from(f.into_inner())),
+        Datum::Float64(f) => float(f.into_inner()),
+        Datum::Numeric(n) => float(n.0.to_string().parse().unwrap_or(f64::NAN)),
+        Datum::Date(d) => signed(i64::from(d.pg_epoch_days())),
+        Datum::Timestamp(ts) => signed(ts.timestamp_micros()),
+        Datum::TimestampTz(ts) => signed(ts.timestamp_micros()),
+        Datum::MzTimestamp(ts) => u64::from(ts),
+        Datum::Bytes(b) => bytes(b),
+        Datum::String(s) => bytes(s.as_bytes()),
+        Datum::Uuid(u) => bytes(u.as_bytes()),
+        _ => 0,
+    }
+}
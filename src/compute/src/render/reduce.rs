@@ -26,6 +26,9 @@ use mz_compute_client::plan::reduce::{
     ReducePlan, ReductionType,
 };
 use mz_expr::{AggregateExpr, AggregateFunc, EvalError, MirScalarExpr};
+use mz_ore::cast::CastFrom;
+use mz_ore::soft_panic_or_log;
+use mz_repr::adt::hll::HyperLogLog;
 use mz_repr::adt::numeric::{self, Numeric, NumericAgg};
 use mz_repr::{Datum, DatumList, DatumVec, Diff, Row, RowArena};
 use mz_storage_client::types::errors::DataflowError;
@@ -200,16 +203,9 @@ where
                 errors.push(errs);
                 arranged_output
             }
-            ReducePlan::Hierarchical(HierarchicalPlan::Monotonic(expr)) => {
-                let (output, errs) = self.build_monotonic(collection, expr);
-                errors.push(errs);
-                output
-            }
-            ReducePlan::Hierarchical(HierarchicalPlan::Bucketed(expr)) => {
-                let (output, errs) = self.build_bucketed(collection, expr);
-                if let Some(e) = errs {
-                    errors.push(e);
-                }
+            ReducePlan::Hierarchical(expr) => {
+                let (output, errs) = self.build_hierarchical(collection, expr);
+                errors.extend(errs);
                 output
             }
             ReducePlan::Basic(BasicPlan::Single(index, aggr)) => {
@@ -642,6 +638,129 @@ where
             )
     }
 
+    /// Build the dataflow to compute and arrange multiple hierarchical aggregations.
+    ///
+    /// The order statistics among the aggregations are not maintained by a reduction tree,
+    /// but each by a tree of counts of its own (see the `order_statistic` module), whose
+    /// results are then assembled with those of the other aggregations in the requested order.
+    fn build_hierarchical<S>(
+        &self,
+        collection: Collection<S, (Row, Row), Diff>,
+        mut plan: HierarchicalPlan,
+    ) -> (Arrangement<S, Row>, Vec<Collection<S, DataflowError, Diff>>)
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        let (aggr_funcs, skips) = match &mut plan {
+            HierarchicalPlan::Monotonic(MonotonicPlan {
+                aggr_funcs, skips, ..
+            })
+            | HierarchicalPlan::Bucketed(BucketedPlan {
+                aggr_funcs, skips, ..
+            }) => (aggr_funcs, skips),
+        };
+        if !aggr_funcs.iter().any(is_order_statistic) {
+            return match plan {
+                HierarchicalPlan::Monotonic(expr) => {
+                    let (output, errs) = self.build_monotonic(collection, expr);
+                    (output, vec![errs])
+                }
+                HierarchicalPlan::Bucketed(expr) => {
+                    let (output, errs) = self.build_bucketed(collection, expr);
+                    (output, errs.into_iter().collect())
+                }
+            };
+        }
+
+        // Recover the columns of the aggregations from the skips between them, and only leave
+        // the other aggregations in the plan.
+        let all_funcs = aggr_funcs.clone();
+        let columns: Vec<usize> = skips
+            .iter()
+            .scan(None, |column: &mut Option<usize>, skip| {
+                *column = Some(column.map_or(*skip, |column| column + 1 + skip));
+                *column
+            })
+            .collect();
+        let mut other_columns = Vec::new();
+        aggr_funcs.retain(|func| !is_order_statistic(func));
+        skips.clear();
+        for (func, column) in all_funcs.iter().zip(columns.iter()) {
+            if !is_order_statistic(func) {
+                skips.push(
+                    other_columns
+                        .last()
+                        .map_or(*column, |last| column - last - 1),
+                );
+                other_columns.push(*column);
+            }
+        }
+
+        // Tag the results of each order statistic with its index, and the results of the
+        // other aggregations with the number of aggregations.
+        let others = all_funcs.len();
+        let tagged = |tag: usize, row: &Row| {
+            Row::pack(std::iter::once(Datum::UInt64(u64::cast_from(tag))).chain(row.iter()))
+        };
+        let mut errors = Vec::new();
+        let mut to_concat = Vec::new();
+        if other_columns.is_empty() {
+            // Groups without non-null values have no order statistics, but still an output.
+            to_concat
+                .push(collection.map(move |(key, _row)| (key, tagged(others, &Row::default()))));
+        } else {
+            let (output, errs) = self.build_hierarchical(collection.clone(), plan);
+            errors.extend(errs);
+            to_concat
+                .push(output.as_collection(move |key, row| (key.clone(), tagged(others, row))));
+        }
+        for (index, (func, column)) in all_funcs.iter().zip(columns).enumerate() {
+            if !is_order_statistic(func) {
+                continue;
+            }
+            let values = collection.map(move |(key, row)| {
+                let datum = row.iter().nth(column).unwrap();
+                (key, Row::pack_slice(&[datum]))
+            });
+            let (output, errs) = self.build_order_statistic(&values, func.clone());
+            errors.push(errs);
+            to_concat.push(output.map(move |(key, row)| (key, tagged(index, &row))));
+        }
+
+        use differential_dataflow::collection::concatenate;
+        let output = concatenate(&mut collection.scope(), to_concat)
+            .arrange_named::<RowSpine<_, _, _, _>>("Arrange ReduceHierarchical")
+            .reduce_abelian::<_, RowSpine<_, _, _, _>>("ReduceHierarchical", {
+                let mut row_buf = Row::default();
+                move |_key, source, target| {
+                    let mut results = vec![None; all_funcs.len()];
+                    let mut other_results = None;
+                    for (row, _count) in source.iter() {
+                        let mut datums = row.iter();
+                        let tag = usize::cast_from(datums.next().unwrap().unwrap_uint64());
+                        if tag == others {
+                            other_results = Some(datums);
+                        } else {
+                            results[tag] = datums.next();
+                        }
+                    }
+                    let Some(mut other_results) = other_results else {
+                        return;
+                    };
+                    let mut row_packer = row_buf.packer();
+                    for (func, result) in all_funcs.iter().zip(results) {
+                        if is_order_statistic(func) {
+                            row_packer.push(result.unwrap_or(Datum::Null));
+                        } else {
+                            row_packer.push(other_results.next().unwrap());
+                        }
+                    }
+                    target.push((row_buf.clone(), 1));
+                }
+            });
+        (output, errors)
+    }
+
     /// Build the dataflow to compute and arrange multiple hierarchical aggregations
     /// on non-monotonic inputs.
     ///
@@ -651,8 +770,9 @@ where
     /// and feed the results up to larger buckets.
     ///
     /// Note that this implementation currently ignores the distinct bit because we
-    /// currently only perform min / max hierarchically and the reduction tree
-    /// efficiently suppresses non-distinct updates.
+    /// currently only perform min / max / approx_count_distinct with reduction trees,
+    /// which are insensitive to duplicates, and the reduction tree efficiently suppresses
+    /// non-distinct updates.
     fn build_bucketed<S>(
         &self,
         input: Collection<S, (Row, Row), Diff>,
@@ -674,11 +794,15 @@ where
 
             // Gather the relevant values into a vec of rows ordered by aggregation_index
             let mut row_buf = Row::default();
+            let funcs = aggr_funcs.clone();
             let input = input.map(move |(key, row)| {
                 let mut values = Vec::with_capacity(skips.len());
                 let mut row_iter = row.iter();
-                for skip in skips.iter() {
-                    row_buf.packer().push(row_iter.nth(*skip).unwrap());
+                for (skip, func) in skips.iter().zip(funcs.iter()) {
+                    let datum = row_iter.nth(*skip).unwrap();
+                    row_buf
+                        .packer()
+                        .push(hierarchical_input(func, datum, &RowArena::new()));
                     values.push(row_buf.clone());
                 }
 
@@ -771,7 +895,7 @@ where
                             let iter = source
                                 .iter()
                                 .map(|(values, _cnt)| values[aggr_index].iter().next().unwrap());
-                            row_packer.push(func.eval(iter, &RowArena::new()));
+                            row_packer.push(finalize_hierarchical(func, iter, &RowArena::new()));
                         }
                         target.push((row_buf.clone(), 1));
                     }
@@ -830,7 +954,12 @@ where
                         let iter = source
                             .iter()
                             .map(|(values, _cnt)| values[aggr_index].iter().next().unwrap());
-                        output.push(Row::pack_slice(&[func.eval(iter, &RowArena::new())]));
+                        let temp_storage = RowArena::new();
+                        output.push(Row::pack_slice(&[eval_hierarchical(
+                            func,
+                            iter,
+                            &temp_storage,
+                        )]));
                     }
                     // We only want to arrange the parts of the input that are not part of the output.
                     // More specifically, we want to arrange it so that `input.concat(&output.negate())`
@@ -913,6 +1042,11 @@ where
                         use ReductionMonoid::*;
                         match monoid {
                             Min(row) | Max(row) => row_packer.extend(row.iter()),
+                            ApproxCountDistinct(row) => row_packer.push(finalize_hierarchical(
+                                &AggregateFunc::ApproxCountDistinct,
+                                row.iter(),
+                                &RowArena::new(),
+                            )),
                         }
                     }
                     output.push((row_buf.clone(), 1));
@@ -969,10 +1103,19 @@ where
             full_aggrs
                 .iter()
                 .map(|f| match f.func {
-                    AggregateFunc::Any | AggregateFunc::All => Accum::Bool {
+                    AggregateFunc::Any | AggregateFunc::All | AggregateFunc::Xor => Accum::Bool {
                         trues: 0,
                         falses: 0,
                     },
+                    AggregateFunc::BitAndInt16
+                    | AggregateFunc::BitAndInt32
+                    | AggregateFunc::BitAndInt64
+                    | AggregateFunc::BitOrInt16
+                    | AggregateFunc::BitOrInt32
+                    | AggregateFunc::BitOrInt64
+                    | AggregateFunc::BitXorInt16
+                    | AggregateFunc::BitXorInt32
+                    | AggregateFunc::BitXorInt64 => bits_accumulator(Datum::Null),
                     AggregateFunc::SumFloat32 | AggregateFunc::SumFloat64 => Accum::Float {
                         accum: 0,
                         pos_infs: 0,
@@ -1003,7 +1146,7 @@ where
                     accum: 0, // unused for AggregateFunc::Count
                     non_nulls: if datum.is_null() { 0 } else { 1 },
                 },
-                AggregateFunc::Any | AggregateFunc::All => match datum {
+                AggregateFunc::Any | AggregateFunc::All | AggregateFunc::Xor => match datum {
                     Datum::True => Accum::Bool {
                         trues: 1,
                         falses: 0,
@@ -1018,6 +1161,15 @@ where
                     },
                     x => panic!("Invalid argument to AggregateFunc::Any: {x:?}"),
                 },
+                AggregateFunc::BitAndInt16
+                | AggregateFunc::BitAndInt32
+                | AggregateFunc::BitAndInt64
+                | AggregateFunc::BitOrInt16
+                | AggregateFunc::BitOrInt32
+                | AggregateFunc::BitOrInt64
+                | AggregateFunc::BitXorInt16
+                | AggregateFunc::BitXorInt32
+                | AggregateFunc::BitXorInt64 => bits_accumulator(datum),
                 AggregateFunc::Dummy => match datum {
                     Datum::Dummy => Accum::SimpleNumber {
                         accum: 0,
@@ -1248,6 +1400,34 @@ where
                                             Datum::Null
                                         }
                                     }
+                                    (AggregateFunc::Xor, Accum::Bool { trues, falses }) => {
+                                        if *trues + *falses == 0 {
+                                            // All values were null.
+                                            Datum::Null
+                                        } else {
+                                            // The exclusive or is true if there are an odd number of trues.
+                                            Datum::from(trues % 2 != 0)
+                                        }
+                                    }
+                                    (
+                                        AggregateFunc::BitAndInt16
+                                        | AggregateFunc::BitAndInt32
+                                        | AggregateFunc::BitAndInt64
+                                        | AggregateFunc::BitOrInt16
+                                        | AggregateFunc::BitOrInt32
+                                        | AggregateFunc::BitOrInt64
+                                        | AggregateFunc::BitXorInt16
+                                        | AggregateFunc::BitXorInt32
+                                        | AggregateFunc::BitXorInt64,
+                                        Accum::Bits { counts, non_nulls },
+                                    ) => {
+                                        if *non_nulls == 0 {
+                                            // All values were null.
+                                            Datum::Null
+                                        } else {
+                                            bits_datum(&aggr.func, counts, *non_nulls)
+                                        }
+                                    }
                                     (AggregateFunc::Dummy, _) => Datum::Dummy,
                                     // If any non-nulls, just report the aggregate.
                                     (
@@ -1448,6 +1628,172 @@ where
     }
 }
 
+/// Returns whether `func` is an order statistic, which is planned as a hierarchical
+/// aggregation, but is not maintained by a reduction tree.
+fn is_order_statistic(func: &AggregateFunc) -> bool {
+    matches!(
+        func,
+        AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+    )
+}
+
+/// Prepares an input value of a hierarchical aggregation for the first stage of a reduction
+/// tree.
+///
+/// The stages of the reduction tree carry partial results from which the stages above them
+/// can compute their own. For most hierarchical aggregations these are aggregate values, but
+/// `approx_count_distinct` carries HyperLogLog sketches, which this creates from input values.
+fn hierarchical_input<'a>(
+    func: &AggregateFunc,
+    datum: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Datum<'a> {
+    match func {
+        AggregateFunc::ApproxCountDistinct if !datum.is_null() => {
+            Datum::Bytes(temp_storage.push_bytes(HyperLogLog::from_datum(datum).to_bytes()))
+        }
+        _ => datum,
+    }
+}
+
+/// Evaluates a hierarchical aggregation over the partial results of a stage of a reduction
+/// tree, producing the partial result of the next stage.
+fn eval_hierarchical<'a, I>(
+    func: &AggregateFunc,
+    datums: I,
+    temp_storage: &'a RowArena,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    match func {
+        AggregateFunc::ApproxCountDistinct => {
+            Datum::Bytes(temp_storage.push_bytes(merge_sketches(datums).to_bytes()))
+        }
+        _ => func.eval(datums, temp_storage),
+    }
+}
+
+/// Evaluates a hierarchical aggregation over the partial results of the last stage of a
+/// reduction tree, producing the value of the aggregation.
+fn finalize_hierarchical<'a, I>(
+    func: &AggregateFunc,
+    datums: I,
+    temp_storage: &'a RowArena,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    match func {
+        AggregateFunc::ApproxCountDistinct => {
+            let estimate = merge_sketches(datums).estimate();
+            Datum::Int64(i64::try_from(estimate).unwrap_or(i64::MAX))
+        }
+        _ => func.eval(datums, temp_storage),
+    }
+}
+
+/// Merges the HyperLogLog sketches in `datums`, ignoring nulls.
+fn merge_sketches<'a, I>(datums: I) -> HyperLogLog
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut sketch = HyperLogLog::new();
+    for datum in datums {
+        if datum.is_null() {
+            continue;
+        }
+        match HyperLogLog::from_bytes(datum.unwrap_bytes()) {
+            Ok(other) => sketch.merge(&other),
+            Err(e) => soft_panic_or_log!("{e} in approx_count_distinct: {datum:?}"),
+        }
+    }
+    sketch
+}
+
+/// Returns the accumulator of a bitwise aggregation for an integer `datum`.
+fn bits_accumulator(datum: Datum) -> Accum {
+    let value = match datum {
+        Datum::Int16(i) => Some(i64::from(i)),
+        Datum::Int32(i) => Some(i64::from(i)),
+        Datum::Int64(i) => Some(i),
+        Datum::Null => None,
+        x => panic!("Invalid argument to bitwise aggregation: {x:?}"),
+    };
+    let mut counts = Box::new([0; 64]);
+    if let Some(value) = value {
+        for (bit, count) in counts.iter_mut().enumerate() {
+            *count = (value >> bit) & 1;
+        }
+    }
+    Accum::Bits {
+        counts,
+        non_nulls: Diff::from(value.is_some()),
+    }
+}
+
+/// Packs the result of the bitwise aggregation `func` over `non_nulls` values, with `counts`
+/// values having each bit set, into a datum of its type.
+fn bits_datum(func: &AggregateFunc, counts: &[Diff; 64], non_nulls: Diff) -> Datum<'static> {
+    let is_set = |count: Diff| match func {
+        // A bit is set if it is set in all values.
+        AggregateFunc::BitAndInt16 | AggregateFunc::BitAndInt32 | AggregateFunc::BitAndInt64 => {
+            count == non_nulls
+        }
+        // A bit is set if it is set in any value.
+        AggregateFunc::BitOrInt16 | AggregateFunc::BitOrInt32 | AggregateFunc::BitOrInt64 => {
+            count > 0
+        }
+        // A bit is set if it is set in an odd number of values.
+        AggregateFunc::BitXorInt16 | AggregateFunc::BitXorInt32 | AggregateFunc::BitXorInt64 => {
+            count % 2 != 0
+        }
+        _ => panic!("Unexpected bitwise aggregation: {func:?}"),
+    };
+    // The inputs were sign extended, so the bits beyond the width of the type agree with its most
+    // significant bit, and the result fits into the type.
+    let mut value = 0i64;
+    for (bit, count) in counts.iter().enumerate() {
+        if is_set(*count) {
+            value |= 1 << bit;
+        }
+    }
+    match func {
+        AggregateFunc::BitAndInt16 | AggregateFunc::BitOrInt16 | AggregateFunc::BitXorInt16 => {
+            Datum::Int16(i16::try_from(value).expect("value has 16 bits"))
+        }
+        AggregateFunc::BitAndInt32 | AggregateFunc::BitOrInt32 | AggregateFunc::BitXorInt32 => {
+            Datum::Int32(i32::try_from(value).expect("value has 32 bits"))
+        }
+        _ => Datum::Int64(value),
+    }
+}
+
+/// Serializes the bit counts of [`Accum::Bits`], as serde only implements its traits for arrays
+/// of up to 32 elements.
+mod bit_counts {
+    use mz_repr::Diff;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(counts: &[Diff; 64], serializer: S) -> Result<S::Ok, S::Error> {
+        counts.as_slice().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Box<[Diff; 64]>, D::Error> {
+        let counts = Vec::<Diff>::deserialize(deserializer)?;
+        let len = counts.len();
+        counts
+            .into_boxed_slice()
+            .try_into()
+            .map_err(|_| D::Error::invalid_length(len, &"64 bit counts"))
+    }
+}
+
 /// Accumulates values for the various types of accumulable aggregations.
 ///
 /// We assume that there are not more than 2^32 elements for the aggregation.
@@ -1467,6 +1813,17 @@ enum Accum {
         /// The number of `false` values observed.
         falses: Diff,
     },
+    /// Accumulates the bits of integer values.
+    Bits {
+        /// The number of values observed with each bit set, from the least significant bit.
+        ///
+        /// Values are sign extended to 64 bits. The counts are boxed to not grow the other
+        /// accumulators.
+        #[serde(with = "bit_counts")]
+        counts: Box<[Diff; 64]>,
+        /// The number of non-NULL values observed.
+        non_nulls: Diff,
+    },
     /// Accumulates simple numeric values.
     SimpleNumber {
         /// The accumulation of all non-NULL values observed.
@@ -1507,6 +1864,9 @@ impl Semigroup for Accum {
     fn is_zero(&self) -> bool {
         match self {
             Accum::Bool { trues, falses } => trues.is_zero() && falses.is_zero(),
+            Accum::Bits { counts, non_nulls } => {
+                counts.iter().all(|count| count.is_zero()) && non_nulls.is_zero()
+            }
             Accum::SimpleNumber { accum, non_nulls } => accum.is_zero() && non_nulls.is_zero(),
             Accum::Float {
                 accum,
@@ -1549,6 +1909,18 @@ impl Semigroup for Accum {
                 *trues += other_trues;
                 *falses += other_falses;
             }
+            (
+                Accum::Bits { counts, non_nulls },
+                Accum::Bits {
+                    counts: other_counts,
+                    non_nulls: other_non_nulls,
+                },
+            ) => {
+                for (count, other_count) in counts.iter_mut().zip(other_counts.iter()) {
+                    *count += other_count;
+                }
+                *non_nulls += other_non_nulls;
+            }
             (
                 Accum::SimpleNumber { accum, non_nulls },
                 Accum::SimpleNumber {
@@ -1649,6 +2021,18 @@ impl Multiply<Diff> for Accum {
                 trues: trues * factor,
                 falses: falses * factor,
             },
+            Accum::Bits {
+                mut counts,
+                non_nulls,
+            } => {
+                for count in counts.iter_mut() {
+                    *count *= factor;
+                }
+                Accum::Bits {
+                    counts,
+                    non_nulls: non_nulls * factor,
+                }
+            }
             Accum::SimpleNumber { accum, non_nulls } => Accum::SimpleNumber {
                 accum: accum * i128::from(factor),
                 non_nulls: non_nulls * factor,
@@ -1716,6 +2100,7 @@ pub mod monoids {
     use differential_dataflow::difference::{Multiply, Semigroup};
     use mz_expr::AggregateFunc;
     use mz_ore::soft_panic_or_log;
+    use mz_repr::adt::hll::HyperLogLog;
     use mz_repr::{Datum, Diff, Row};
     use serde::{Deserialize, Serialize};
 
@@ -1724,6 +2109,8 @@ pub mod monoids {
     pub enum ReductionMonoid {
         Min(Row),
         Max(Row),
+        /// A single-datum row with an encoded HyperLogLog sketch.
+        ApproxCountDistinct(Row),
     }
 
     impl Multiply<Diff> for ReductionMonoid {
@@ -1772,6 +2159,13 @@ pub mod monoids {
                         lhs.clone_from(rhs);
                     }
                 }
+                (
+                    ReductionMonoid::ApproxCountDistinct(lhs),
+                    ReductionMonoid::ApproxCountDistinct(rhs),
+                ) => {
+                    let merged = super::merge_sketches([lhs.unpack_first(), rhs.unpack_first()]);
+                    lhs.packer().push(Datum::Bytes(&merged.to_bytes()));
+                }
                 (lhs, rhs) => {
                     soft_panic_or_log!(
                         "Mismatched monoid variants in reduction! lhs: {lhs:?} rhs: {rhs:?}"
@@ -1819,6 +2213,12 @@ pub mod monoids {
             | AggregateFunc::MinDate
             | AggregateFunc::MinTimestamp
             | AggregateFunc::MinTimestampTz => Some(ReductionMonoid::Min(row)),
+            AggregateFunc::ApproxCountDistinct => {
+                let sketch = HyperLogLog::from_datum(row.unpack_first());
+                Some(ReductionMonoid::ApproxCountDistinct(Row::pack_slice(&[
+                    Datum::Bytes(&sketch.to_bytes()),
+                ])))
+            }
             AggregateFunc::SumInt16
             | AggregateFunc::SumInt32
             | AggregateFunc::SumInt64
@@ -1831,6 +2231,19 @@ pub mod monoids {
             | AggregateFunc::Count
             | AggregateFunc::Any
             | AggregateFunc::All
+            | AggregateFunc::Xor
+            | AggregateFunc::BitAndInt16
+            | AggregateFunc::BitAndInt32
+            | AggregateFunc::BitAndInt64
+            | AggregateFunc::BitOrInt16
+            | AggregateFunc::BitOrInt32
+            | AggregateFunc::BitOrInt64
+            | AggregateFunc::BitXorInt16
+            | AggregateFunc::BitXorInt32
+            | AggregateFunc::BitXorInt64
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
             | AggregateFunc::Dummy
             | AggregateFunc::JsonbAgg { .. }
             | AggregateFunc::JsonbObjectAgg { .. }
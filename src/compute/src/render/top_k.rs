@@ -12,23 +12,25 @@
 //! Consult [TopKPlan] documentation for details.
 
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::rc::Rc;
 
+use differential_dataflow::consolidation::consolidate;
 use differential_dataflow::hashable::Hashable;
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::operators::arrange::Arrange;
 use differential_dataflow::operators::reduce::ReduceCore;
 use differential_dataflow::{AsCollection, Collection};
 use mz_compute_client::plan::top_k::{
-    BasicTopKPlan, MonotonicTop1Plan, MonotonicTopKPlan, TopKPlan,
+    BasicTopKPlan, MonotonicTop1Plan, MonotonicTopKPlan, OrderedTopKPlan, TopKPlan,
 };
-use mz_expr::EvalError;
+use mz_expr::{ColumnOrder, EvalError};
 use mz_ore::soft_assert_or_log;
-use mz_repr::{DatumVec, Diff, Row};
+use mz_repr::{Datum, DatumVec, Diff, Row};
 use mz_storage_client::types::errors::DataflowError;
 use mz_timely_util::operator::CollectionExt;
-use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::channels::pact::{Exchange, Pipeline};
 use timely::dataflow::operators::Operator;
 use timely::dataflow::Scope;
 
@@ -168,6 +170,29 @@ where
                     err_collection = err_collection.concat(&errs);
                     oks
                 }
+                TopKPlan::Ordered(OrderedTopKPlan {
+                    group_key,
+                    order_key,
+                    limit,
+                    offset,
+                }) => {
+                    let mut datum_vec = mz_repr::DatumVec::new();
+                    let collection = ok_input.map(move |row| {
+                        let group_row = {
+                            let datums = datum_vec.borrow_with(&row);
+                            let iterator = group_key.iter().map(|i| datums[*i]);
+                            let total_size = mz_repr::datums_size(iterator.clone());
+                            let mut group_row = Row::with_capacity(total_size);
+                            group_row.packer().extend(iterator);
+                            group_row
+                        };
+                        (group_row, row)
+                    });
+                    let (oks, errs) = render_ordered_topk(collection, order_key, offset, limit)
+                        .flat_map_fallible("OrderedTopKDemux", Some);
+                    err_collection = err_collection.concat(&errs);
+                    oks
+                }
             };
             // Extract the results from the region.
             (ok_result.leave_region(), err_collection.leave_region())
@@ -534,6 +559,382 @@ where
         .as_collection()
 }
 
+/// Maintains the `(group_key, row)` pairs in `collection` ordered by `order_key` within each
+/// group, and produces the rows at the positions from `offset` up to `offset + limit` of each
+/// group.
+///
+/// The updates of each time are applied in time order, which requires the timestamps to be
+/// totally ordered. Each group is kept in an [OrderedGroup], which finds the rows at given
+/// positions in logarithmic time, so that applying an update only costs time in the number of
+/// rows that enter or leave the produced positions, and not in `offset` or `limit`.
+///
+/// The groups are operator state rather than an arrangement, and so are not shared with other
+/// operators and are rebuilt from the input whenever the dataflow is rendered.
+fn render_ordered_topk<G>(
+    collection: Collection<G, (Row, Row), Diff>,
+    order_key: Vec<ColumnOrder>,
+    offset: usize,
+    limit: Option<usize>,
+) -> Collection<G, Result<Row, DataflowError>, Diff>
+where
+    G: Scope,
+{
+    // Entries carry the values of the order key in a separate row, which the order key then
+    // refers to by their positions in that row.
+    let key_columns: Vec<usize> = order_key.iter().map(|order| order.column).collect();
+    let key_order: Rc<[ColumnOrder]> = order_key
+        .into_iter()
+        .enumerate()
+        .map(|(column, order)| ColumnOrder { column, ..order })
+        .collect();
+    // The output of a group consists of its rows at the positions from `offset` up to `end`.
+    let end = limit.map_or(usize::MAX, |limit| offset.saturating_add(limit));
+    let mut groups: BTreeMap<Row, OrderedGroup> = BTreeMap::new();
+    let mut pending: BTreeMap<G::Timestamp, Vec<((Row, Row), Diff)>> = BTreeMap::new();
+    let mut buffer = Vec::new();
+    let mut datum_vec = DatumVec::new();
+
+    collection
+        .inner
+        .unary_notify(
+            Exchange::new(
+                |((key, _row), _time, _diff): &((Row, Row), G::Timestamp, Diff)| key.hashed(),
+            ),
+            "OrderedTopK",
+            [],
+            move |input, output, notificator| {
+                while let Some((time, data)) = input.next() {
+                    data.swap(&mut buffer);
+                    for (key_row, record_time, diff) in buffer.drain(..) {
+                        pending
+                            .entry(record_time.clone())
+                            .or_insert_with(|| {
+                                notificator.notify_at(time.delayed(&record_time));
+                                Vec::new()
+                            })
+                            .push((key_row, diff));
+                    }
+                }
+
+                notificator.for_each(|time, _, _| {
+                    let Some(mut updates) = pending.remove(time.time()) else {
+                        return;
+                    };
+                    consolidate(&mut updates);
+
+                    // Updates are sorted by key, and so those of each group are contiguous.
+                    let mut results = Vec::new();
+                    let mut updates = updates.into_iter().peekable();
+                    while let Some(((key, row), diff)) = updates.next() {
+                        let group = groups.entry(key.clone()).or_default();
+                        let had_negative = group.negative > 0;
+
+                        let mut first = Some((row, diff));
+                        while let Some((row, diff)) = first.take().or_else(|| {
+                            updates
+                                .next_if(|((next_key, _), _)| next_key == &key)
+                                .map(|((_, row), diff)| (row, diff))
+                        }) {
+                            let entry =
+                                OrderedEntry::new(row, &key_columns, &key_order, &mut datum_vec);
+                            group.update(entry, diff, offset, end, &mut results);
+                        }
+
+                        let has_negative = group.negative > 0;
+                        if had_negative != has_negative {
+                            let message = "Negative multiplicities in TopK";
+                            let err = EvalError::Internal(message.to_string()).into();
+                            results.push((Err(err), if has_negative { 1 } else { -1 }));
+                        }
+                        if group.root.is_none() {
+                            groups.remove(&key);
+                        }
+                    }
+
+                    consolidate(&mut results);
+                    let mut session = output.session(&time);
+                    session.give_iterator(
+                        results
+                            .into_iter()
+                            .map(|(result, diff)| (result, time.time().clone(), diff)),
+                    );
+                });
+            },
+        )
+        .as_collection()
+}
+
+/// A row of a TopK group, ordered as the order key prescribes.
+///
+/// The values of the order key are packed into `key` when the entry is created, so that
+/// comparisons only decode the order key columns, and only up to the first one that differs.
+/// Rows with equal order key values are further ordered by their encoding.
+#[derive(Clone, Debug)]
+struct OrderedEntry {
+    /// The values of the order key columns of `row`.
+    key: Row,
+    row: Row,
+    /// The order key, referring to the columns of `key`.
+    order: Rc<[ColumnOrder]>,
+}
+
+impl OrderedEntry {
+    fn new(
+        row: Row,
+        key_columns: &[usize],
+        order: &Rc<[ColumnOrder]>,
+        datum_vec: &mut DatumVec,
+    ) -> Self {
+        let key = {
+            let datums = datum_vec.borrow_with(&row);
+            Row::pack(key_columns.iter().map(|column| datums[*column]))
+        };
+        OrderedEntry {
+            key,
+            row,
+            order: Rc::clone(order),
+        }
+    }
+}
+
+impl Ord for OrderedEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let columns = self.order.iter().zip(self.key.iter().zip(other.key.iter()));
+        for (order, (left, right)) in columns {
+            let cmp = match (left, right) {
+                (Datum::Null, Datum::Null) => Ordering::Equal,
+                (Datum::Null, _) if order.nulls_last => Ordering::Greater,
+                (Datum::Null, _) => Ordering::Less,
+                (_, Datum::Null) if order.nulls_last => Ordering::Less,
+                (_, Datum::Null) => Ordering::Greater,
+                (left, right) if order.desc => right.cmp(&left),
+                (left, right) => left.cmp(&right),
+            };
+            if cmp != Ordering::Equal {
+                return cmp;
+            }
+        }
+        self.row.cmp(&other.row)
+    }
+}
+
+impl PartialOrd for OrderedEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for OrderedEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedEntry {}
+
+/// The ordered rows of a single TopK group.
+///
+/// The distinct rows are kept in a treap, a binary search tree that is balanced in expectation
+/// by heap-ordering its nodes by pseudo-random priorities. Each node records the number of rows
+/// in its subtree, which allows finding the rows at given positions in logarithmic time.
+#[derive(Debug, Default)]
+struct OrderedGroup {
+    root: OrderedTree,
+    /// The number of distinct rows with negative multiplicities.
+    negative: usize,
+}
+
+/// A possibly empty treap of [OrderedNode]s.
+type OrderedTree = Option<Box<OrderedNode>>;
+
+/// A distinct row of an [OrderedGroup].
+#[derive(Debug)]
+struct OrderedNode {
+    entry: OrderedEntry,
+    /// The multiplicity of the row, which is never zero.
+    count: Diff,
+    /// The priority of the node, which is not less than those of its children.
+    priority: u64,
+    /// The number of rows in the subtree rooted at this node, not counting rows with negative
+    /// multiplicities.
+    weight: usize,
+    left: OrderedTree,
+    right: OrderedTree,
+}
+
+impl OrderedNode {
+    fn new(entry: OrderedEntry, count: Diff) -> Box<Self> {
+        // The hash of the row is as good as a random priority, and needs no further state.
+        let priority = entry.row.hashed();
+        let mut node = Box::new(OrderedNode {
+            entry,
+            count,
+            priority,
+            weight: 0,
+            left: None,
+            right: None,
+        });
+        node.update_weight();
+        node
+    }
+
+    /// The number of rows this node contributes to the positions of its group.
+    fn own_weight(&self) -> usize {
+        usize::try_from(self.count).unwrap_or(0)
+    }
+
+    fn update_weight(&mut self) {
+        self.weight = weight(&self.left) + self.own_weight() + weight(&self.right);
+    }
+}
+
+/// The number of rows in `tree`.
+fn weight(tree: &OrderedTree) -> usize {
+    tree.as_ref().map_or(0, |node| node.weight)
+}
+
+/// Splits `tree` into the nodes ordered before `entry` and the remaining nodes, or, if
+/// `inclusive`, into the nodes not ordered after `entry` and the remaining nodes.
+fn split(tree: OrderedTree, entry: &OrderedEntry, inclusive: bool) -> (OrderedTree, OrderedTree) {
+    let Some(mut node) = tree else {
+        return (None, None);
+    };
+    let goes_left = match node.entry.cmp(entry) {
+        Ordering::Less => true,
+        Ordering::Equal => inclusive,
+        Ordering::Greater => false,
+    };
+    if goes_left {
+        let (left, right) = split(node.right.take(), entry, inclusive);
+        node.right = left;
+        node.update_weight();
+        (Some(node), right)
+    } else {
+        let (left, right) = split(node.left.take(), entry, inclusive);
+        node.left = right;
+        node.update_weight();
+        (left, Some(node))
+    }
+}
+
+/// Merges `left` and `right`, all of whose nodes are ordered after those of `left`.
+fn merge(left: OrderedTree, right: OrderedTree) -> OrderedTree {
+    match (left, right) {
+        (None, tree) | (tree, None) => tree,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority >= right.priority {
+                left.right = merge(left.right.take(), Some(right));
+                left.update_weight();
+                Some(left)
+            } else {
+                right.left = merge(Some(left), right.left.take());
+                right.update_weight();
+                Some(right)
+            }
+        }
+    }
+}
+
+/// Reports, with multiplicity `diff`, the rows of `tree` at the positions from `lo` up to
+/// `hi`, where the first row of `tree` is at position `base`.
+fn range_outputs(
+    tree: &OrderedTree,
+    base: usize,
+    lo: usize,
+    hi: usize,
+    diff: Diff,
+    results: &mut Vec<(Result<Row, DataflowError>, Diff)>,
+) {
+    let Some(node) = tree else {
+        return;
+    };
+    if lo >= hi || hi <= base || base + node.weight <= lo {
+        return;
+    }
+    range_outputs(&node.left, base, lo, hi, diff, results);
+    let start = base + weight(&node.left);
+    let end = start + node.own_weight();
+    let overlap = std::cmp::min(end, hi).saturating_sub(std::cmp::max(start, lo));
+    if overlap > 0 {
+        let count = Diff::try_from(overlap).expect("must fit");
+        results.push((Ok(node.entry.row.clone()), diff * count));
+    }
+    range_outputs(&node.right, end, lo, hi, diff, results);
+}
+
+impl OrderedGroup {
+    /// Adds `diff` to the multiplicity of `entry`, and reports the resulting changes to the
+    /// rows at the positions from `offset` up to `end`.
+    ///
+    /// The rows ordered before `entry` keep their positions, and those ordered after it shift
+    /// by the change in the number of copies of `entry`. Only the rows that shift into or out
+    /// of the reported positions are visited.
+    fn update(
+        &mut self,
+        entry: OrderedEntry,
+        diff: Diff,
+        offset: usize,
+        end: usize,
+        results: &mut Vec<(Result<Row, DataflowError>, Diff)>,
+    ) {
+        let (before, rest) = split(self.root.take(), &entry, false);
+        let (node, after) = split(rest, &entry, true);
+        let position = weight(&before);
+
+        let old_count = node.as_ref().map_or(0, |node| node.count);
+        let new_count = old_count + diff;
+        let old_weight = usize::try_from(old_count).unwrap_or(0);
+        let new_weight = usize::try_from(new_count).unwrap_or(0);
+
+        // Report the change to the copies of `entry` among the reported positions.
+        let overlap = |weight: usize| {
+            let overlap = std::cmp::min(position.saturating_add(weight), end)
+                .saturating_sub(std::cmp::max(position, offset));
+            Diff::try_from(overlap).expect("must fit")
+        };
+        let change = overlap(new_weight) - overlap(old_weight);
+        if change != 0 {
+            results.push((Ok(entry.row.clone()), change));
+        }
+
+        // Report the rows after `entry` that shift into or out of the reported positions, as
+        // the differences between the reported positions relative to the first row after
+        // `entry`, before and after the change.
+        let relative = |start: usize| (offset.saturating_sub(start), end.saturating_sub(start));
+        let (old_lo, old_hi) = relative(position + old_weight);
+        let (new_lo, new_hi) = relative(position + new_weight);
+        for (lo, hi) in [
+            (new_lo, std::cmp::min(new_hi, old_lo)),
+            (std::cmp::max(new_lo, old_hi), new_hi),
+        ] {
+            range_outputs(&after, 0, lo, hi, 1, results);
+        }
+        for (lo, hi) in [
+            (old_lo, std::cmp::min(old_hi, new_lo)),
+            (std::cmp::max(old_lo, new_hi), old_hi),
+        ] {
+            range_outputs(&after, 0, lo, hi, -1, results);
+        }
+
+        if old_count < 0 {
+            self.negative -= 1;
+        }
+        if new_count < 0 {
+            self.negative += 1;
+        }
+        let node = match (node, new_count) {
+            (_, 0) => None,
+            (Some(mut node), _) => {
+                node.count = new_count;
+                node.update_weight();
+                Some(node)
+            }
+            (None, _) => Some(OrderedNode::new(entry, new_count)),
+        };
+        self.root = merge(merge(before, node), after);
+    }
+}
+
 /// Types for in-place intra-ts aggregation of monotonic streams.
 pub mod topk_agg {
     use differential_dataflow::consolidation;
@@ -0,0 +1,875 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Incremental window function execution logic.
+//!
+//! Consult [WindowPlan] documentation for details.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound;
+use std::rc::Rc;
+
+use differential_dataflow::consolidation::consolidate;
+use differential_dataflow::hashable::Hashable;
+use differential_dataflow::logging::{BatchEvent, DifferentialEvent, DropEvent, Logger};
+use differential_dataflow::{AsCollection, Collection};
+use itertools::Either;
+use mz_compute_client::plan::reduce::KeyValPlan;
+use mz_compute_client::plan::window::{WindowFunc, WindowPlan};
+use mz_expr::{compare_columns, ColumnOrder, EvalError, MirScalarExpr};
+use mz_repr::{Datum, DatumVec, Diff, Row, RowArena};
+use mz_storage_client::types::errors::DataflowError;
+use mz_timely_util::operator::CollectionExt;
+use smallvec::SmallVec;
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::FrontierNotificator;
+use timely::dataflow::operators::Operator;
+use timely::dataflow::Scope;
+use timely::worker::AsWorker;
+
+use crate::render::context::{CollectionBundle, Context};
+
+// The implementation applies the updates of each time in time order, which requires the
+// timestamps to be totally ordered.
+impl<G> Context<G, Row>
+where
+    G: Scope,
+    G::Timestamp: crate::render::RenderTimestamp,
+{
+    /// Renders a `Plan::Window`, which maintains each window partition in order and only
+    /// produces updates for the rows whose window function results change.
+    pub(crate) fn render_window(
+        &mut self,
+        input: CollectionBundle<G, Row>,
+        key_val_plan: KeyValPlan,
+        window_plan: WindowPlan,
+        input_key: Option<Vec<MirScalarExpr>>,
+    ) -> CollectionBundle<G, Row> {
+        input.scope().region_named("Window", |inner| {
+            let KeyValPlan { key_plan, val_plan } = key_val_plan;
+            let mut row_buf = Row::default();
+            let mut row_mfp = Row::default();
+            let mut datums = DatumVec::new();
+            let (key_val_input, err_input): (
+                timely::dataflow::Stream<_, (Result<(Row, Row), DataflowError>, _, _)>,
+                _,
+            ) = input
+                .enter_region(inner)
+                .flat_map(input_key.map(|k| (k, None)), || {
+                    move |row_parts, time, diff| {
+                        let temp_storage = RowArena::new();
+                        let mut datums_local = datums.borrow_with_many(row_parts);
+                        let arity = datums_local.len();
+
+                        // Evaluate the key expressions.
+                        let key = match key_plan.evaluate_into(
+                            &mut datums_local,
+                            &temp_storage,
+                            &mut row_mfp,
+                        ) {
+                            Err(e) => {
+                                return Some((
+                                    Err(DataflowError::from(e)),
+                                    time.clone(),
+                                    diff.clone(),
+                                ))
+                            }
+                            Ok(key) => key.expect("Row expected as no predicate was used"),
+                        };
+                        // Evaluate the value expressions.
+                        // The prior evaluation may have left additional columns we should delete.
+                        datums_local.truncate(arity);
+                        let val = match val_plan.evaluate_iter(&mut datums_local, &temp_storage) {
+                            Err(e) => {
+                                return Some((
+                                    Err(DataflowError::from(e)),
+                                    time.clone(),
+                                    diff.clone(),
+                                ))
+                            }
+                            Ok(val) => val.expect("Row expected as no predicate was used"),
+                        };
+                        row_buf.packer().extend(val);
+                        let row = row_buf.clone();
+                        Some((Ok((key, row)), time.clone(), diff.clone()))
+                    }
+                });
+
+            // Demux out the potential errors from key and value selector evaluation.
+            let (ok, err) = key_val_input
+                .as_collection()
+                .consolidate_stream()
+                .flat_map_fallible("OkErrDemux", Some);
+
+            let (ok, window_err) =
+                render_window_operator(ok, window_plan).flat_map_fallible("WindowDemux", Some);
+
+            CollectionBundle::from_collections(ok, err.concat(&err_input).concat(&window_err))
+                .leave_region()
+        })
+    }
+}
+
+/// Maintains the window function described by `window_plan` over the `(key, val)` pairs in
+/// `collection`, where `key` identifies the window partition and `val` is the input to the
+/// window function.
+///
+/// Each output row consists of the key columns followed by a single column with an element
+/// of the list the window function would produce for the partition.
+fn render_window_operator<G>(
+    collection: Collection<G, (Row, Row), Diff>,
+    window_plan: WindowPlan,
+) -> Collection<G, Result<Row, DataflowError>, Diff>
+where
+    G: Scope,
+{
+    let WindowPlan { func, order_by } = window_plan;
+    let order_by: Rc<[ColumnOrder]> = order_by.into();
+    let logger = collection
+        .scope()
+        .log_register()
+        .get::<DifferentialEvent>("differential/arrange");
+    let mut partitions: BTreeMap<Row, WindowPartition> = BTreeMap::new();
+    let mut pending: BTreeMap<G::Timestamp, Vec<((Row, Row), Diff)>> = BTreeMap::new();
+    let mut buffer = Vec::new();
+    let mut row_buf = Row::default();
+
+    collection
+        .inner
+        .unary_frontier(
+            Exchange::new(
+                |((key, _val), _time, _diff): &((Row, Row), G::Timestamp, Diff)| key.hashed(),
+            ),
+            "Window",
+            move |_capability, info| {
+                let mut state_size = WindowStateSize {
+                    logger,
+                    operator: info.global_id,
+                    records: 0,
+                };
+                let mut notificator = FrontierNotificator::new();
+
+                move |input, output| {
+                    while let Some((time, data)) = input.next() {
+                        data.swap(&mut buffer);
+                        for (key_val, record_time, diff) in buffer.drain(..) {
+                            pending
+                                .entry(record_time.clone())
+                                .or_insert_with(|| {
+                                    notificator.notify_at(time.delayed(&record_time));
+                                    Vec::new()
+                                })
+                                .push((key_val, diff));
+                        }
+                    }
+
+                    let mut records = state_size.records;
+                    notificator.for_each(&[input.frontier()], |time, _| {
+                        let Some(mut updates) = pending.remove(time.time()) else {
+                            return;
+                        };
+                        consolidate(&mut updates);
+
+                        // Updates are sorted by key, and so those of each partition are
+                        // contiguous.
+                        let mut results = Vec::new();
+                        let mut updates = updates.into_iter().peekable();
+                        while let Some(((key, val), diff)) = updates.next() {
+                            let mut changes = vec![(WindowEntry::new(val, &func, &order_by), diff)];
+                            while let Some(((_, val), diff)) =
+                                updates.next_if(|((next_key, _), _)| next_key == &key)
+                            {
+                                changes.push((WindowEntry::new(val, &func, &order_by), diff));
+                            }
+
+                            let partition = partitions.entry(key.clone()).or_default();
+                            records -= partition.entries.len();
+                            let mut window_output = WindowOutput {
+                                key: &key,
+                                row_buf: &mut row_buf,
+                                results: &mut results,
+                            };
+                            partition.update(&func, changes, &mut window_output);
+                            records += partition.entries.len();
+                            if partition.entries.is_empty() {
+                                partitions.remove(&key);
+                            }
+                        }
+
+                        consolidate(&mut results);
+                        let mut session = output.session(&time);
+                        session.give_iterator(
+                            results
+                                .into_iter()
+                                .map(|(result, diff)| (result, time.time().clone(), diff)),
+                        );
+                    });
+                    state_size.update(records);
+                }
+            },
+        )
+        .as_collection()
+}
+
+/// Reports the number of distinct inputs a window operator maintains as the records of an
+/// arrangement, so that the state of the operator shows up in `mz_arrangement_sizes`.
+///
+/// The state is reported as a single batch, which is replaced whenever its size changes.
+struct WindowStateSize {
+    logger: Option<Logger>,
+    /// The global identifier of the window operator.
+    operator: usize,
+    /// The number of records last reported.
+    records: usize,
+}
+
+impl WindowStateSize {
+    /// Reports that the operator now maintains `records` distinct inputs.
+    fn update(&mut self, records: usize) {
+        if records == self.records {
+            return;
+        }
+        if let Some(logger) = &self.logger {
+            if self.records > 0 {
+                logger.log(DropEvent {
+                    operator: self.operator,
+                    length: self.records,
+                });
+            }
+            if records > 0 {
+                logger.log(BatchEvent {
+                    operator: self.operator,
+                    length: records,
+                });
+            }
+        }
+        self.records = records;
+    }
+}
+
+impl Drop for WindowStateSize {
+    fn drop(&mut self) {
+        self.update(0);
+    }
+}
+
+/// Compares two sets of `ORDER BY` values, falling back to comparing the rows themselves so
+/// that only identical values are peers.
+fn compare_orders(order_by: &[ColumnOrder], left: &Row, right: &Row) -> Ordering {
+    let left_datums: SmallVec<[Datum; 8]> = left.iter().collect();
+    let right_datums: SmallVec<[Datum; 8]> = right.iter().collect();
+    compare_columns(order_by, &left_datums, &right_datums, || left.cmp(right))
+}
+
+/// A distinct input to a window function, ordered as the function's `ORDER BY` clause
+/// prescribes.
+///
+/// Inputs with equal `ORDER BY` values are further ordered by their values, which is the order
+/// in which the non-incremental implementation visits them.
+#[derive(Clone, Debug)]
+struct WindowEntry {
+    /// The values of the `ORDER BY` expressions.
+    order: Row,
+    /// The input to the window function, a record whose first field describes the output rows
+    /// and whose remaining fields are the values of the `ORDER BY` expressions.
+    val: Row,
+    /// The number of output rows for each copy of this input.
+    len: i64,
+    /// The ordering of the `ORDER BY` values.
+    order_by: Rc<[ColumnOrder]>,
+}
+
+impl WindowEntry {
+    fn new(val: Row, func: &WindowFunc, order_by: &Rc<[ColumnOrder]>) -> Self {
+        let mut fields = val.unpack_first().unwrap_list().iter();
+        let first = fields.next().unwrap();
+        let len = match func {
+            WindowFunc::RowNumber | WindowFunc::Rank | WindowFunc::DenseRank => {
+                i64::try_from(first.unwrap_list().iter().count()).expect("must fit")
+            }
+            WindowFunc::LagLead { .. } => 1,
+        };
+        let order = Row::pack(fields);
+        WindowEntry {
+            order,
+            val,
+            len,
+            order_by: Rc::clone(order_by),
+        }
+    }
+
+    /// The first field of the input, which describes the output rows.
+    fn input(&self) -> Datum {
+        self.val.unpack_first().unwrap_list().iter().next().unwrap()
+    }
+
+    /// Decodes the input to `lag` or `lead` into the original row, the value the function
+    /// reports to rows referring to this one, and the default value of this row.
+    fn lag_lead_args(&self) -> (Datum, Datum, Datum) {
+        let mut fields = self.input().unwrap_list().iter();
+        let original_row = fields.next().unwrap();
+        let mut encoded_args = fields.next().unwrap().unwrap_list().iter();
+        let (value, _offset, default_value) = (
+            encoded_args.next().unwrap(),
+            encoded_args.next().unwrap(),
+            encoded_args.next().unwrap(),
+        );
+        (original_row, value, default_value)
+    }
+
+    /// Compares the `ORDER BY` values of this input with `order`.
+    fn cmp_order(&self, order: &Row) -> Ordering {
+        compare_orders(&self.order_by, &self.order, order)
+    }
+}
+
+impl Ord for WindowEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_order(&other.order)
+            .then_with(|| self.val.cmp(&other.val))
+    }
+}
+
+impl PartialOrd for WindowEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for WindowEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for WindowEntry {}
+
+/// The ordered contents of a single window partition.
+#[derive(Debug, Default)]
+struct WindowPartition {
+    /// The distinct inputs and their multiplicities, which are never zero between updates.
+    entries: BTreeMap<WindowEntry, Diff>,
+    /// The peer groups of the inputs with positive multiplicities.
+    groups: PeerGroups,
+    /// The number of inputs with negative multiplicities.
+    negative: usize,
+}
+
+/// The peer groups of a window partition that contain inputs with positive multiplicities,
+/// in `ORDER BY` order.
+///
+/// The groups form a treap, whose priorities are hashes of the `ORDER BY` values, and each
+/// node records the number of rows and groups in its subtree. This lets ranking functions
+/// count the rows and peer groups before any input in time logarithmic in the number of
+/// groups.
+#[derive(Debug, Default)]
+struct PeerGroups {
+    /// The nodes of the treap, some of which may be unused.
+    nodes: Vec<PeerGroup>,
+    /// The indexes of the unused nodes.
+    free: Vec<usize>,
+    /// The index of the root node, if any.
+    root: Option<usize>,
+}
+
+/// A node of [`PeerGroups`].
+#[derive(Debug)]
+struct PeerGroup {
+    /// The `ORDER BY` values of the group.
+    order: Row,
+    /// The treap priority of the group.
+    priority: u64,
+    /// The number of output rows of the inputs in the group with positive multiplicities.
+    rows: i64,
+    /// The number of inputs in the group with positive multiplicities, which is never zero.
+    present: usize,
+    /// The number of output rows of the groups in the subtree rooted at this node.
+    subtree_rows: i64,
+    /// The number of groups in the subtree rooted at this node.
+    subtree_groups: i64,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl PeerGroups {
+    /// Returns the number of output rows and peer groups before the peer group of `entry`.
+    fn before(&self, entry: &WindowEntry) -> (i64, i64) {
+        let (mut rows, mut groups) = (0, 0);
+        let mut node = self.root;
+        while let Some(index) = node {
+            let group = &self.nodes[index];
+            if entry.cmp_order(&group.order) == Ordering::Greater {
+                let (left_rows, left_groups) = self.subtree(group.left);
+                rows += left_rows + group.rows;
+                groups += left_groups + 1;
+                node = group.right;
+            } else {
+                node = group.left;
+            }
+        }
+        (rows, groups)
+    }
+
+    /// Adds `rows` output rows and `present` inputs with positive multiplicities to the peer
+    /// group of `entry`, adding or removing the group as necessary.
+    fn update(&mut self, entry: &WindowEntry, rows: i64, present: isize) {
+        let priority = entry.order.hashed();
+        let root = self.root;
+        self.root = self.update_subtree(root, entry, priority, rows, present);
+    }
+
+    /// Applies [`PeerGroups::update`] to the subtree rooted at `node`, and returns the new
+    /// root of the subtree.
+    fn update_subtree(
+        &mut self,
+        node: Option<usize>,
+        entry: &WindowEntry,
+        priority: u64,
+        rows: i64,
+        present: isize,
+    ) -> Option<usize> {
+        let Some(index) = node else {
+            return Some(self.insert(entry, priority, rows, present));
+        };
+        let ordering = entry.cmp_order(&self.nodes[index].order);
+        if ordering != Ordering::Equal && priority > self.nodes[index].priority {
+            // The group would be an ancestor of this node, and so is not in the subtree.
+            let (left, right) = self.split(Some(index), entry);
+            let new = self.insert(entry, priority, rows, present);
+            self.nodes[new].left = left;
+            self.nodes[new].right = right;
+            self.recount(new);
+            return Some(new);
+        }
+        match ordering {
+            Ordering::Less => {
+                let left = self.nodes[index].left;
+                self.nodes[index].left = self.update_subtree(left, entry, priority, rows, present);
+            }
+            Ordering::Greater => {
+                let right = self.nodes[index].right;
+                self.nodes[index].right =
+                    self.update_subtree(right, entry, priority, rows, present);
+            }
+            Ordering::Equal => {
+                let group = &mut self.nodes[index];
+                group.rows += rows;
+                group.present = group
+                    .present
+                    .checked_add_signed(present)
+                    .expect("peer group must not have negative inputs");
+                if group.present == 0 {
+                    let (left, right) = (group.left, group.right);
+                    self.free.push(index);
+                    return self.merge(left, right);
+                }
+            }
+        }
+        self.recount(index);
+        Some(index)
+    }
+
+    /// Adds a node without children for the peer group of `entry`, and returns its index.
+    fn insert(&mut self, entry: &WindowEntry, priority: u64, rows: i64, present: isize) -> usize {
+        let present = usize::try_from(present).expect("new peer group must have inputs");
+        assert!(present > 0, "new peer group must have inputs");
+        let group = PeerGroup {
+            order: entry.order.clone(),
+            priority,
+            rows,
+            present,
+            subtree_rows: rows,
+            subtree_groups: 1,
+            left: None,
+            right: None,
+        };
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = group;
+                index
+            }
+            None => {
+                self.nodes.push(group);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Splits the subtree rooted at `node` into the groups before and after the peer group of
+    /// `entry`, which must not be in the subtree.
+    fn split(
+        &mut self,
+        node: Option<usize>,
+        entry: &WindowEntry,
+    ) -> (Option<usize>, Option<usize>) {
+        let Some(index) = node else {
+            return (None, None);
+        };
+        if entry.cmp_order(&self.nodes[index].order) == Ordering::Greater {
+            let right = self.nodes[index].right;
+            let (middle, right) = self.split(right, entry);
+            self.nodes[index].right = middle;
+            self.recount(index);
+            (Some(index), right)
+        } else {
+            let left = self.nodes[index].left;
+            let (left, middle) = self.split(left, entry);
+            self.nodes[index].left = middle;
+            self.recount(index);
+            (left, Some(index))
+        }
+    }
+
+    /// Merges the subtrees rooted at `left` and `right`, whose groups must all be before those
+    /// of `right`, and returns the root of the result.
+    fn merge(&mut self, left: Option<usize>, right: Option<usize>) -> Option<usize> {
+        match (left, right) {
+            (None, node) | (node, None) => node,
+            (Some(left), Some(right)) => {
+                if self.nodes[left].priority >= self.nodes[right].priority {
+                    let middle = self.nodes[left].right;
+                    self.nodes[left].right = self.merge(middle, Some(right));
+                    self.recount(left);
+                    Some(left)
+                } else {
+                    let middle = self.nodes[right].left;
+                    self.nodes[right].left = self.merge(Some(left), middle);
+                    self.recount(right);
+                    Some(right)
+                }
+            }
+        }
+    }
+
+    /// Returns the number of output rows and groups in the subtree rooted at `node`.
+    fn subtree(&self, node: Option<usize>) -> (i64, i64) {
+        node.map_or((0, 0), |index| {
+            let group = &self.nodes[index];
+            (group.subtree_rows, group.subtree_groups)
+        })
+    }
+
+    /// Recomputes the subtree counts of the node at `index` from those of its children.
+    fn recount(&mut self, index: usize) {
+        let (left_rows, left_groups) = self.subtree(self.nodes[index].left);
+        let (right_rows, right_groups) = self.subtree(self.nodes[index].right);
+        let group = &mut self.nodes[index];
+        group.subtree_rows = left_rows + group.rows + right_rows;
+        group.subtree_groups = left_groups + 1 + right_groups;
+    }
+}
+
+/// The position of a peer group in a window partition, as a ranking function sees it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct RankPosition {
+    /// The number of output rows before the peer group.
+    rows: i64,
+    /// The number of peer groups before the peer group.
+    groups: i64,
+}
+
+impl RankPosition {
+    /// Moves past the peer group with the given inputs and multiplicities, reporting the
+    /// results of its rows with multiplicity `diff` if one is given.
+    fn advance(
+        &mut self,
+        func: &WindowFunc,
+        group: &[(&WindowEntry, Diff)],
+        diff: Option<Diff>,
+        output: &mut WindowOutput,
+    ) {
+        let rank = self.rows + 1;
+        let dense_rank = self.groups + 1;
+        let mut present = false;
+        for (entry, count) in group.iter().filter(|(_, count)| *count > 0) {
+            present = true;
+            let Some(diff) = diff else {
+                self.rows += entry.len * count;
+                continue;
+            };
+            for _ in 0..*count {
+                for elem in entry.input().unwrap_list().iter() {
+                    self.rows += 1;
+                    let result = match func {
+                        WindowFunc::RowNumber => self.rows,
+                        WindowFunc::Rank => rank,
+                        WindowFunc::DenseRank => dense_rank,
+                        WindowFunc::LagLead { .. } => unreachable!(),
+                    };
+                    output.push(Datum::Int64(result), elem, diff);
+                }
+            }
+        }
+        if present {
+            self.groups += 1;
+        }
+    }
+}
+
+/// Collects the output updates of window partitions.
+struct WindowOutput<'a> {
+    key: &'a Row,
+    row_buf: &'a mut Row,
+    results: &'a mut Vec<(Result<Row, DataflowError>, Diff)>,
+}
+
+impl WindowOutput<'_> {
+    /// Reports an output row with window function result `result` for `elem`.
+    fn push(&mut self, result: Datum, elem: Datum, diff: Diff) {
+        let mut packer = self.row_buf.packer();
+        packer.extend(self.key.iter());
+        packer.push_list([result, elem]);
+        self.results.push((Ok(self.row_buf.clone()), diff));
+    }
+}
+
+impl WindowPartition {
+    /// Applies `changes` to the partition, and reports the resulting changes to the outputs.
+    fn update(
+        &mut self,
+        func: &WindowFunc,
+        changes: Vec<(WindowEntry, Diff)>,
+        output: &mut WindowOutput,
+    ) {
+        let had_negative = self.negative > 0;
+
+        match func {
+            WindowFunc::RowNumber | WindowFunc::Rank | WindowFunc::DenseRank => {
+                self.rank_update(func, changes.iter().cloned().collect(), output);
+            }
+            WindowFunc::LagLead { .. } => {
+                // The results that can change are those of the rows referring to a changed
+                // input, either before or after the changes.
+                let distance = func.distance();
+                let mut affected = BTreeSet::new();
+                for (entry, _) in changes.iter() {
+                    self.lag_lead_affected(entry, distance, &mut affected);
+                }
+                for (entry, diff) in changes.iter() {
+                    self.apply(entry, *diff);
+                }
+                for (entry, _) in changes.iter() {
+                    self.lag_lead_affected(entry, distance, &mut affected);
+                }
+                for (entry, diff) in changes.iter() {
+                    self.apply(entry, -*diff);
+                }
+                self.lag_lead_outputs(&affected, distance, -1, output);
+                for (entry, diff) in changes.iter() {
+                    self.apply(entry, *diff);
+                }
+                self.lag_lead_outputs(&affected, distance, 1, output);
+            }
+        }
+
+        for (entry, _) in changes.iter() {
+            if self.entries.get(entry) == Some(&0) {
+                self.entries.remove(entry);
+            }
+        }
+
+        let has_negative = self.negative > 0;
+        if had_negative != has_negative {
+            let message = "Negative multiplicities in window function";
+            let err = EvalError::Internal(message.to_string()).into();
+            output
+                .results
+                .push((Err(err), if has_negative { 1 } else { -1 }));
+        }
+    }
+
+    /// Adds `diff` to the multiplicity of `entry`, leaving it in place if the multiplicity
+    /// becomes zero.
+    fn apply(&mut self, entry: &WindowEntry, diff: Diff) {
+        let count = self.entries.entry(entry.clone()).or_insert(0);
+        let old = *count;
+        let new = old + diff;
+        *count = new;
+
+        if old < 0 {
+            self.negative -= 1;
+        }
+        if new < 0 {
+            self.negative += 1;
+        }
+        let present = isize::from(new > 0) - isize::from(old > 0);
+        let rows = entry.len * (new.max(0) - old.max(0));
+        if present != 0 || rows != 0 {
+            self.groups.update(entry, rows, present);
+        }
+    }
+
+    /// Applies `changes` to the partition, and reports the resulting changes to the results of
+    /// a ranking function.
+    ///
+    /// Only the results from the peer group of the first changed input onwards can change. Past
+    /// the last changed input, the results of a peer group change only if the changes moved it,
+    /// which is when they changed the number of rows (for `row_number` and `rank`) or peer
+    /// groups (for `dense_rank`) before it. Once that is no longer the case, none of the
+    /// results that follow change either, and so they are not visited.
+    fn rank_update(
+        &mut self,
+        func: &WindowFunc,
+        changes: BTreeMap<WindowEntry, Diff>,
+        output: &mut WindowOutput,
+    ) {
+        let (Some((first, _)), Some((last, _))) =
+            (changes.first_key_value(), changes.last_key_value())
+        else {
+            return;
+        };
+
+        // The groups before that of `first` are the same before and after the changes.
+        let (rows, groups) = self.groups.before(first);
+        let mut old = RankPosition { rows, groups };
+        let mut new = old;
+        for (entry, diff) in changes.iter() {
+            self.apply(entry, *diff);
+        }
+
+        // Start at the first input of the peer group of `first`, regardless of its
+        // multiplicity.
+        let start = self
+            .entries
+            .range(..first)
+            .rev()
+            .take_while(|(entry, _)| entry.order == first.order)
+            .last()
+            .map_or(first, |(entry, _)| entry);
+        let mut suffix = self
+            .entries
+            .range((Bound::Included(start), Bound::Unbounded))
+            .peekable();
+        let mut old_group = Vec::new();
+        let mut new_group = Vec::new();
+        while let Some(&(head, _)) = suffix.peek() {
+            let order = &head.order;
+            let past_changes = head > last;
+            old_group.clear();
+            new_group.clear();
+            let mut changed = false;
+            while let Some((entry, count)) = suffix.next_if(|(entry, _)| &entry.order == order) {
+                let diff = changes.get(entry).copied().unwrap_or(0);
+                changed |= diff != 0;
+                old_group.push((entry, *count - diff));
+                new_group.push((entry, *count));
+            }
+
+            let moved = match func {
+                WindowFunc::DenseRank => old.groups != new.groups,
+                _ => old.rows != new.rows,
+            };
+            if changed || moved {
+                old.advance(func, &old_group, Some(-1), output);
+                new.advance(func, &new_group, Some(1), output);
+            } else if past_changes {
+                break;
+            } else {
+                old.advance(func, &old_group, None, output);
+                new.advance(func, &new_group, None, output);
+            }
+        }
+    }
+
+    /// Iterates over the inputs with positive multiplicities that are adjacent to `entry` in
+    /// the given direction, excluding `entry` itself.
+    fn neighbors<'a>(
+        &'a self,
+        entry: &'a WindowEntry,
+        forward: bool,
+    ) -> impl Iterator<Item = (&'a WindowEntry, Diff)> + 'a {
+        let neighbors = if forward {
+            Either::Left(
+                self.entries
+                    .range((Bound::Excluded(entry), Bound::Unbounded)),
+            )
+        } else {
+            Either::Right(self.entries.range(..entry).rev())
+        };
+        neighbors
+            .filter(|(_, count)| **count > 0)
+            .map(|(entry, count)| (entry, *count))
+    }
+
+    /// Adds to `affected` the inputs with positive multiplicities whose rows refer to
+    /// `changed`, or to a row on the other side of `changed`, for a `lag` or `lead` looking
+    /// `distance` rows away.
+    fn lag_lead_affected(
+        &self,
+        changed: &WindowEntry,
+        distance: i64,
+        affected: &mut BTreeSet<WindowEntry>,
+    ) {
+        if self.entries.get(changed).map_or(false, |count| *count > 0) {
+            affected.insert(changed.clone());
+        }
+        // The rows referring to `changed` are on the side opposite to the one they look at.
+        let mut remaining = distance.abs();
+        for (entry, count) in self.neighbors(changed, distance < 0) {
+            if remaining <= 0 {
+                break;
+            }
+            affected.insert(entry.clone());
+            remaining -= count;
+        }
+    }
+
+    /// Reports the results of a `lag` or `lead` looking `distance` rows away, with
+    /// multiplicity `diff`, for the rows of the inputs in `entries`.
+    fn lag_lead_outputs(
+        &self,
+        entries: &BTreeSet<WindowEntry>,
+        distance: i64,
+        diff: Diff,
+        output: &mut WindowOutput,
+    ) {
+        for entry in entries {
+            let count = match self.entries.get(entry) {
+                Some(count) if *count > 0 => *count,
+                _ => continue,
+            };
+            let (original_row, _, default_value) = entry.lag_lead_args();
+            for copy in 0..count {
+                let result = match self.lag_lead_target(entry, count, copy, distance) {
+                    Some(target) => target.lag_lead_args().1,
+                    None => default_value,
+                };
+                output.push(result, original_row, diff);
+            }
+        }
+    }
+
+    /// Returns the input of the row `distance` rows away from the row of the `copy`-th copy of
+    /// `entry`, which has multiplicity `count`, or `None` if that row does not exist.
+    fn lag_lead_target<'a>(
+        &'a self,
+        entry: &'a WindowEntry,
+        count: Diff,
+        copy: Diff,
+        distance: i64,
+    ) -> Option<&'a WindowEntry> {
+        // The number of rows to step past the copies of `entry`.
+        let mut remaining = if distance >= 0 {
+            distance - (count - 1 - copy)
+        } else {
+            -distance - copy
+        };
+        if remaining <= 0 {
+            return Some(entry);
+        }
+        for (neighbor, count) in self.neighbors(entry, distance >= 0) {
+            if remaining <= count {
+                return Some(neighbor);
+            }
+            remaining -= count;
+        }
+        None
+    }
+}
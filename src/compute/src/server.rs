@@ -12,8 +12,9 @@
 use std::cell::RefCell;
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fmt::Debug;
+use std::path::PathBuf;
 use std::rc::Rc;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use anyhow::Error;
 use crossbeam_channel::{RecvError, TryRecvError};
@@ -47,11 +48,17 @@ pub struct Config {
     // TODO(guswynn): cluster-unification: ensure these stats
     // also work for storage when merging.
     pub metrics: ComputeMetrics,
+    /// The directory in which arrangements may spill to disk, if any.
+    pub scratch_directory: Option<PathBuf>,
+    /// The index of the worker that reports the persist compaction queue of this process, once
+    /// one has been chosen.
+    pub compaction_queue_reporter: Arc<Mutex<Option<usize>>>,
 }
 
 /// Initiates a timely dataflow computation, processing compute commands.
 pub fn serve(
     config: mz_cluster::server::ClusterConfig,
+    scratch_directory: Option<PathBuf>,
 ) -> Result<
     (
         TimelyContainerRef<ComputeCommand, ComputeResponse, SyncActivator>,
@@ -60,7 +67,11 @@ pub fn serve(
     Error,
 > {
     let metrics = ComputeMetrics::register_with(&config.metrics_registry);
-    let compute_config = Config { metrics };
+    let compute_config = Config {
+        metrics,
+        scratch_directory,
+        compaction_queue_reporter: Default::default(),
+    };
 
     let (timely_container, client_builder) = mz_cluster::server::serve::<
         Config,
@@ -136,6 +147,10 @@ struct Worker<'w, A: Allocate> {
     persist_clients: Arc<PersistClientCache>,
     /// A process-global handle to tracing configuration.
     tracing_handle: Arc<TracingHandle>,
+    /// The replica's scratch directory, if disk is enabled.
+    scratch_directory: Option<PathBuf>,
+    /// Whether this worker reports the persist compaction queue of its process.
+    reports_compaction_queue: bool,
 }
 
 impl mz_cluster::types::AsRunnableWorker<ComputeCommand, ComputeResponse> for Config {
@@ -151,6 +166,15 @@ impl mz_cluster::types::AsRunnableWorker<ComputeCommand, ComputeResponse> for Co
         persist_clients: Arc<PersistClientCache>,
         tracing_handle: Arc<TracingHandle>,
     ) {
+        // All workers of a process share a compaction queue. The first worker to get here
+        // reports it.
+        let reports_compaction_queue = {
+            let mut reporter = config
+                .compaction_queue_reporter
+                .lock()
+                .expect("lock poisoned");
+            *reporter.get_or_insert(timely_worker.index()) == timely_worker.index()
+        };
         Worker {
             timely_worker,
             client_rx,
@@ -158,6 +182,8 @@ impl mz_cluster::types::AsRunnableWorker<ComputeCommand, ComputeResponse> for Co
             persist_clients,
             compute_state: None,
             tracing_handle,
+            scratch_directory: config.scratch_directory,
+            reports_compaction_queue,
         }
         .run()
     }
@@ -329,6 +355,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
         }
 
         // Commence normal operation.
+        let reports_compaction_queue = self.reports_compaction_queue;
         let mut shutdown = false;
         while !shutdown {
             // Enable trace compaction.
@@ -341,7 +368,11 @@ impl<'w, A: Allocate> Worker<'w, A> {
             // Report frontier information back the coordinator.
             if let Some(mut compute_state) = self.activate_compute(&mut response_tx) {
                 compute_state.report_compute_frontiers();
+                compute_state.report_hydration_statuses();
                 compute_state.report_dropped_collections();
+                if reports_compaction_queue {
+                    compute_state.report_compaction_queue();
+                }
             }
 
             // Handle any received commands.
@@ -364,6 +395,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
             if let Some(mut compute_state) = self.activate_compute(&mut response_tx) {
                 compute_state.process_peeks();
                 compute_state.process_subscribes();
+                compute_state.process_quarantined_errors();
             }
         }
     }
@@ -384,6 +416,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
                     Arc::clone(&self.persist_clients),
                     self.metrics.clone(),
                     Arc::clone(&self.tracing_handle),
+                    self.scratch_directory.clone(),
                 ));
             }
             _ => (),
@@ -651,6 +684,12 @@ impl<'w, A: Allocate> Worker<'w, A> {
 
                 collection.set_reported_frontier(new_reported_frontier);
 
+                // Report the hydration status of retained dataflows anew, since the controller
+                // may have forgotten what we reported before.
+                if retained {
+                    collection.reset_reported_hydration();
+                }
+
                 // Sink tokens should be retained for retained dataflows, and dropped for dropped
                 // dataflows.
                 //
@@ -666,6 +705,10 @@ impl<'w, A: Allocate> Worker<'w, A> {
             // We must drop the subscribe response buffer as it is global across all subscribes.
             // If it were broken out by `GlobalId` then we could drop only those of dataflows we drop.
             compute_state.subscribe_response_buffer = Rc::new(RefCell::new(Vec::new()));
+
+            // Report the compaction queue anew, since the controller may have forgotten what we
+            // reported before.
+            compute_state.reset_reported_compaction_queue();
         } else {
             todo_commands = new_commands.clone();
         }
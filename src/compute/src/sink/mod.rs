@@ -8,4 +8,4 @@
 // by the Apache License, Version 2.0.
 
 mod persist_sink;
-mod subscribe;
+pub(crate) mod subscribe;
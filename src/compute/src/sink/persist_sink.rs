@@ -16,7 +16,10 @@ use std::sync::Arc;
 use differential_dataflow::consolidation::consolidate_updates;
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::{Collection, Hashable};
-use mz_compute_client::types::sinks::{ComputeSinkDesc, PersistSinkConnection};
+use mz_compute_client::protocol::response::QuarantinedErrorBatch;
+use mz_compute_client::types::sinks::{
+    ComputeSinkDesc, PersistSinkConnection, QuarantineMode, QuarantinePlan,
+};
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::HashMap;
 use mz_persist_client::batch::{Batch, BatchBuilder};
@@ -24,14 +27,16 @@ use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::write::WriterEnrichedHollowBatch;
 use mz_persist_client::Diagnostics;
 use mz_persist_types::codec_impls::UnitSchema;
-use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_repr::{DatumVec, Diff, GlobalId, Row, RowArena, Timestamp};
 use mz_storage_client::controller::CollectionMetadata;
 use mz_storage_client::types::errors::DataflowError;
 use mz_storage_client::types::sources::SourceData;
 use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
+use mz_timely_util::operator::CollectionExt;
 use mz_timely_util::probe::{self, ProbeNotify};
 use serde::{Deserialize, Serialize};
 use timely::dataflow::channels::pact::{Exchange, Pipeline};
+use timely::dataflow::operators::generic::Operator;
 use timely::dataflow::operators::{
     Broadcast, Capability, CapabilitySet, ConnectLoop, Feedback, Inspect,
 };
@@ -60,6 +65,13 @@ where
     where
         G: Scope<Timestamp = Timestamp>,
     {
+        let sinked_collection = match &self.quarantine {
+            Some(quarantine) => {
+                quarantine_errors(compute_state, sink_id, quarantine, sinked_collection)
+            }
+            None => sinked_collection,
+        };
+
         let desired_collection = sinked_collection.map(Ok).concat(&err_collection.map(Err));
         if sink.up_to != Antichain::default() {
             unimplemented!(
@@ -78,6 +90,118 @@ where
     }
 }
 
+/// Applies the map, filter, and project of `quarantine` to `sinked_collection`, and removes the
+/// evaluation errors it produces.
+///
+/// Only errors raised by the map, filter, and project itself are quarantined. Errors that are
+/// already part of the sink's error collection, for example because they were imported from an
+/// input, still make the sinked collection unreadable. Removed errors are reported to the
+/// controller, together with the key of the input row that caused them.
+fn quarantine_errors<G>(
+    compute_state: &ComputeState,
+    sink_id: GlobalId,
+    quarantine: &QuarantinePlan,
+    sinked_collection: Collection<G, Row, Diff>,
+) -> Collection<G, Row, Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let QuarantinePlan {
+        mode,
+        mfp,
+        input_key,
+    } = quarantine.clone();
+    let (ok_collection, row_errors) = sinked_collection.flat_map_fallible("QuarantineRowErrors", {
+        let mut datum_vec = DatumVec::new();
+        let mut row_buf = Row::default();
+        move |row| {
+            let arena = RowArena::new();
+            let mut datums = datum_vec.borrow_with(&row);
+            let mut errors = Vec::new();
+            let result = match mode {
+                QuarantineMode::SkipRow => mfp.evaluate_inner(&mut datums, &arena),
+                QuarantineMode::Null => {
+                    mfp.evaluate_inner_nulling_errors(&mut datums, &arena, &mut errors)
+                }
+            };
+            let mut output = Vec::new();
+            match result {
+                Ok(true) => {
+                    row_buf
+                        .packer()
+                        .extend(mfp.projection.iter().map(|c| datums[*c]));
+                    output.push(Ok(row_buf.clone()));
+                }
+                Ok(false) => (),
+                Err(error) => errors.push(error),
+            }
+            if !errors.is_empty() {
+                let datums = row.unpack();
+                row_buf
+                    .packer()
+                    .extend(input_key.iter().map(|c| datums[*c]));
+                output.extend(
+                    errors
+                        .into_iter()
+                        .map(|error| Err((error.to_string(), row_buf.clone()))),
+                );
+            }
+            output
+        }
+    });
+
+    report_quarantined_errors(
+        sink_id,
+        row_errors,
+        Rc::clone(&compute_state.quarantined_error_buffer),
+    );
+
+    ok_collection
+}
+
+/// Reports the quarantined `errors` of the sink `sink_id` through `buffer`.
+///
+/// All errors are sent to a single worker, which reports them once their times are complete, so
+/// that the controller can tell which errors a replica has reported.
+fn report_quarantined_errors<G>(
+    sink_id: GlobalId,
+    errors: Collection<G, (String, Row), Diff>,
+    buffer: Rc<RefCell<Vec<(GlobalId, QuarantinedErrorBatch)>>>,
+) where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let hashed_id = sink_id.hashed();
+    let mut pending = Vec::new();
+    let mut errors_buf = Default::default();
+    errors.inner.sink(
+        Exchange::new(move |_| hashed_id),
+        &format!("QuarantinedErrors({sink_id})"),
+        move |input| {
+            input.for_each(|_, data| {
+                data.swap(&mut errors_buf);
+                pending.append(&mut errors_buf);
+            });
+
+            let upper = input.frontier().frontier().to_owned();
+            consolidate_updates(&mut pending);
+            let (keep, ship): (Vec<_>, Vec<_>) = pending
+                .drain(..)
+                .partition(|(_, time, _)| upper.less_equal(time));
+            pending = keep;
+
+            if !ship.is_empty() {
+                let errors = ship
+                    .into_iter()
+                    .map(|((error, input_key), time, diff)| (time, error, input_key, diff))
+                    .collect();
+                buffer
+                    .borrow_mut()
+                    .push((sink_id, QuarantinedErrorBatch { upper, errors }));
+            }
+        },
+    );
+}
+
 pub(crate) fn persist_sink<G>(
     sink_id: GlobalId,
     target: &CollectionMetadata,
@@ -9,6 +9,7 @@
 
 use std::any::Any;
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::convert::Infallible;
 use std::ops::DerefMut;
 use std::rc::Rc;
@@ -17,6 +18,7 @@ use differential_dataflow::consolidation::consolidate_updates;
 use differential_dataflow::Collection;
 use mz_compute_client::protocol::response::{SubscribeBatch, SubscribeResponse};
 use mz_compute_client::types::sinks::{ComputeSinkDesc, SubscribeSinkConnection};
+use mz_ore::cast::CastFrom;
 use mz_repr::{Diff, GlobalId, Row, Timestamp};
 use mz_storage_client::controller::CollectionMetadata;
 use mz_storage_client::types::errors::DataflowError;
@@ -26,6 +28,7 @@ use timely::dataflow::operators::Operator;
 use timely::dataflow::{Scope, Stream};
 use timely::progress::timestamp::Timestamp as TimelyTimestamp;
 use timely::progress::Antichain;
+use timely::scheduling::{Activator, Scheduler};
 use timely::PartialOrder;
 
 use crate::render::sinks::SinkRender;
@@ -59,13 +62,31 @@ where
         })));
         let subscribe_protocol_weak = Rc::downgrade(&subscribe_protocol_handle);
 
+        // A flow-controlled subscribe learns about the receipt of its batches through the
+        // `AllowCompaction` commands the worker receives for it.
+        let flow_control = self.max_pending_rows.map(|max_pending_rows| {
+            let acknowledgements = Rc::new(RefCell::new(SubscribeAcknowledgements::new()));
+            let collection = compute_state.expect_collection_mut(sink_id);
+            collection.subscribe_acknowledgements = Some(Rc::clone(&acknowledgements));
+            FlowControl {
+                max_pending_rows: usize::cast_from(max_pending_rows),
+                acknowledgements,
+                pending_batches: VecDeque::new(),
+            }
+        });
+
+        let options = SubscribeOptions {
+            with_snapshot: sink.with_snapshot,
+            as_of,
+            up_to: sink.up_to.clone(),
+            emit_interval: self.emit_interval.map(Timestamp::new),
+            flow_control,
+        };
         subscribe(
             sinked_collection,
             err_collection,
             sink_id,
-            sink.with_snapshot,
-            as_of,
-            sink.up_to.clone(),
+            options,
             subscribe_protocol_handle,
             probes,
         );
@@ -81,13 +102,95 @@ where
     }
 }
 
+/// The in-flight bytes the sources of a dataflow with a flow-controlled subscribe may emit
+/// before pausing, if `dataflow_max_inflight_bytes` does not impose a tighter bound.
+///
+/// Without a bound, the flow control probes never engage and a blocked subscribe would leave an
+/// unbounded amount of updates in its inputs.
+pub(crate) const FLOW_CONTROL_MAX_INFLIGHT_BYTES: usize = 64 << 20;
+
+/// Options of a subscribe operator.
+struct SubscribeOptions {
+    /// Whether to emit the updates at the `as_of`.
+    with_snapshot: bool,
+    as_of: Antichain<Timestamp>,
+    up_to: Antichain<Timestamp>,
+    /// The interval to coalesce updates into, if any.
+    emit_interval: Option<Timestamp>,
+    /// Flow control state, if the subscribe waits for its batches to be acknowledged.
+    flow_control: Option<FlowControl>,
+}
+
+/// Flow control state of a subscribe operator.
+struct FlowControl {
+    /// The maximum number of rows sent but not yet acknowledged, before the operator stops
+    /// sending batches.
+    max_pending_rows: usize,
+    /// Acknowledgements received for the subscribe.
+    acknowledgements: Rc<RefCell<SubscribeAcknowledgements>>,
+    /// The uppers and row counts of batches that have been sent but not yet acknowledged.
+    pending_batches: VecDeque<(Antichain<Timestamp>, usize)>,
+}
+
+impl FlowControl {
+    /// Forgets acknowledged batches and reports whether more rows may be sent.
+    fn may_send(&mut self) -> bool {
+        let acknowledgements = self.acknowledgements.borrow();
+        while let Some((upper, _)) = self.pending_batches.front() {
+            if PartialOrder::less_equal(upper, &acknowledgements.frontier) {
+                self.pending_batches.pop_front();
+            } else {
+                break;
+            }
+        }
+        let pending_rows: usize = self.pending_batches.iter().map(|(_, rows)| rows).sum();
+        pending_rows < self.max_pending_rows
+    }
+}
+
+/// Acknowledgements of the batches sent by a flow-controlled subscribe, shared between the
+/// subscribe operator and the `ComputeState`.
+pub struct SubscribeAcknowledgements {
+    /// The frontier up to which the controller has acknowledged the receipt of batches.
+    frontier: Antichain<Timestamp>,
+    /// Activates the subscribe operator, which might be waiting for acknowledgements.
+    activator: Option<Activator>,
+}
+
+impl SubscribeAcknowledgements {
+    fn new() -> Self {
+        Self {
+            frontier: Antichain::from_elem(Timestamp::minimum()),
+            activator: None,
+        }
+    }
+
+    /// Records that all batches with an upper less than or equal to `frontier` were received.
+    pub fn acknowledge(&mut self, frontier: &Antichain<Timestamp>) {
+        if PartialOrder::less_than(&self.frontier, frontier) {
+            self.frontier = frontier.clone();
+            if let Some(activator) = &self.activator {
+                activator.activate();
+            }
+        }
+    }
+}
+
+/// Rounds `time` up to the next multiple of `interval`.
+fn round_up(time: Timestamp, interval: Timestamp) -> Timestamp {
+    let remainder = u64::from(time) % u64::from(interval);
+    if remainder == 0 {
+        time
+    } else {
+        time.saturating_add(u64::from(interval) - remainder)
+    }
+}
+
 fn subscribe<G>(
     sinked_collection: Collection<G, Row, Diff>,
     err_collection: Collection<G, DataflowError, Diff>,
     sink_id: GlobalId,
-    with_snapshot: bool,
-    as_of: Antichain<G::Timestamp>,
-    up_to: Antichain<G::Timestamp>,
+    options: SubscribeOptions,
     subscribe_protocol_handle: Rc<RefCell<Option<SubscribeProtocol>>>,
     probes: Vec<probe::Handle<Timestamp>>,
 ) where
@@ -98,17 +201,37 @@ fn subscribe<G>(
     // TODO: Replace `Infallible` with `!` once the latter is stabilized.
     let progress_stream: Stream<G, Infallible>;
 
+    let SubscribeOptions {
+        with_snapshot,
+        as_of,
+        up_to,
+        emit_interval,
+        mut flow_control,
+    } = options;
     let mut rows_to_emit = Vec::new();
     let mut errors_to_emit = Vec::new();
     let mut finished = false;
     let mut ok_buf = Default::default();
     let mut err_buf = Default::default();
+    let scope = sinked_collection.scope();
     progress_stream = sinked_collection.inner.binary_frontier(
         &err_collection.inner,
         Pipeline,
         Pipeline,
         &format!("subscribe-{}", sink_id),
-        move |_cap, _info| {
+        move |cap, info| {
+            // A flow-controlled subscribe holds back its output frontier at the upper of the
+            // last batch it sent, so the flow control probes pause the dataflow's sources while
+            // the subscribe waits for acknowledgements.
+            let mut capability = match &flow_control {
+                Some(flow_control) => {
+                    let activator = scope.activator_for(&info.address[..]);
+                    flow_control.acknowledgements.borrow_mut().activator = Some(activator);
+                    Some(cap)
+                }
+                None => None,
+            };
+
             move |ok_input, err_input, _output| {
                 if finished {
                     // Drain the inputs, to avoid the operator being constantly rescheduled
@@ -117,6 +240,15 @@ fn subscribe<G>(
                     return;
                 }
 
+                // Without room for more rows, leave further updates in the inputs until earlier
+                // batches have been acknowledged, rather than buffering them here. Meanwhile, the
+                // held capability lets the flow control probes pause the dataflow's sources.
+                if let Some(flow_control) = &mut flow_control {
+                    if !flow_control.may_send() {
+                        return;
+                    }
+                }
+
                 let mut frontier = ok_input.frontier().frontier().to_owned();
                 frontier.extend(err_input.frontier().frontier().iter().copied());
 
@@ -130,11 +262,18 @@ fn subscribe<G>(
                     beyond_as_of && before_up_to
                 };
 
+                // Coalesced updates are reported at the end of their interval, where they
+                // consolidate with all other updates of that interval.
+                let emit_time = |time: Timestamp| match emit_interval {
+                    Some(interval) => round_up(time, interval),
+                    None => time,
+                };
+
                 ok_input.for_each(|_, data| {
                     data.swap(&mut ok_buf);
                     for (row, time, diff) in ok_buf.drain(..) {
                         if should_emit(&time) {
-                            rows_to_emit.push((time, row, diff));
+                            rows_to_emit.push((emit_time(time), row, diff));
                         }
                     }
                 });
@@ -142,22 +281,33 @@ fn subscribe<G>(
                     data.swap(&mut err_buf);
                     for (error, time, diff) in err_buf.drain(..) {
                         if should_emit(&time) {
-                            errors_to_emit.push((time, error, diff));
+                            errors_to_emit.push((emit_time(time), error, diff));
                         }
                     }
                 });
 
                 if let Some(subscribe_protocol) = subscribe_protocol_handle.borrow_mut().deref_mut()
                 {
-                    subscribe_protocol.send_batch(
+                    let sent_rows = subscribe_protocol.send_batch(
                         frontier.clone(),
                         &mut rows_to_emit,
                         &mut errors_to_emit,
                     );
+                    if let (Some(flow_control), Some(sent_rows)) = (&mut flow_control, sent_rows) {
+                        if sent_rows > 0 {
+                            flow_control
+                                .pending_batches
+                                .push_back((frontier.clone(), sent_rows));
+                        }
+                        if let (Some(cap), Some(time)) = (&mut capability, frontier.as_option()) {
+                            cap.downgrade(time);
+                        }
+                    }
                 }
 
                 if PartialOrder::less_equal(&up_to, &frontier) {
                     finished = true;
+                    capability.take();
                     // We are done; indicate this by sending a batch at the
                     // empty frontier.
                     if let Some(subscribe_protocol) =
@@ -209,28 +359,32 @@ impl SubscribeProtocol {
     /// only actually send the first error received in a `SubscribeResponse`. Subsequent errors are
     /// dropped. To simplify life for the caller, this method still maintains the illusion that
     /// `errors` are handled the same way as `rows`.
+    ///
+    /// Returns the number of rows sent, if a batch was sent.
     fn send_batch(
         &mut self,
         upper: Antichain<Timestamp>,
         rows: &mut Vec<(Timestamp, Row, Diff)>,
         errors: &mut Vec<(Timestamp, DataflowError, Diff)>,
-    ) {
+    ) -> Option<usize> {
         // Only send a batch if both conditions hold:
         //  a) `upper` has reached or passed the sink's `as_of` frontier.
         //  b) `upper` is different from when we last sent a batch.
         if !PartialOrder::less_equal(&self.sink_as_of, &upper) || upper == self.prev_upper {
-            return;
+            return None;
         }
 
         // The compute protocol requires us to only send out consolidated batches.
         consolidate_updates(rows);
         consolidate_updates(errors);
 
-        let (keep_rows, ship_rows) = rows.drain(..).partition(|u| upper.less_equal(&u.0));
+        let (keep_rows, ship_rows): (Vec<_>, Vec<_>) =
+            rows.drain(..).partition(|u| upper.less_equal(&u.0));
         let (keep_errors, ship_errors) = errors.drain(..).partition(|u| upper.less_equal(&u.0));
         *rows = keep_rows;
         *errors = keep_errors;
 
+        let sent_rows = ship_rows.len();
         let updates = match (&self.poison, ship_errors.first()) {
             (Some(error), _) => {
                 // The subscribe is poisoned; keep sending the same error.
@@ -269,6 +423,7 @@ impl SubscribeProtocol {
             // to avoid sending `SubscribeResponse::DroppedAt`.
             self.subscribe_response_buffer = None;
         }
+        Some(sent_rows)
     }
 }
 
@@ -8,6 +8,9 @@
 // by the Apache License, Version 2.0.
 
 //! Convience typedefs for differential types.
+//!
+//! The row spines store their keys and values in columnar regions, whose large
+//! chunks can spill to disk. See [`crate::arrangement::spill`].
 
 #![allow(missing_docs)]
 
@@ -100,7 +100,7 @@ use mz_build_info::BuildInfo;
 use mz_compute_client::controller::{
     ActiveComputeController, ComputeController, ComputeControllerResponse, ReplicaId,
 };
-use mz_compute_client::protocol::response::{PeekResponse, SubscribeResponse};
+use mz_compute_client::protocol::response::{HydrationStatus, PeekResponse, SubscribeResponse};
 use mz_compute_client::service::{ComputeClient, ComputeGrpcClient};
 use mz_orchestrator::{NamespacedOrchestrator, Orchestrator, ServiceProcessMetrics};
 use mz_ore::metrics::MetricsRegistry;
@@ -108,6 +108,7 @@ use mz_ore::now::{EpochMillis, NowFn};
 use mz_ore::task::AbortOnDropHandle;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::compaction::CompactionQueueEntry;
 use mz_persist_client::PersistLocation;
 use mz_persist_types::Codec64;
 use mz_proto::RustType;
@@ -175,6 +176,11 @@ pub enum ControllerResponse<T = mz_repr::Timestamp> {
     ComputeReplicaHeartbeat(ReplicaId, DateTime<Utc>),
     /// Notification that new resource usage metrics are available for a given replica.
     ComputeReplicaMetrics(ReplicaId, Vec<ServiceProcessMetrics>),
+    /// Notification that the hydration status of a compute collection on a given replica
+    /// changed, or is no longer tracked if `None`.
+    ComputeHydrationStatus(GlobalId, ReplicaId, Option<HydrationStatus>),
+    /// Notification that the persist compaction queue of a given replica changed.
+    ComputeCompactionQueue(ReplicaId, Vec<CompactionQueueEntry>),
 }
 
 impl<T> From<ComputeControllerResponse<T>> for ControllerResponse<T> {
@@ -189,6 +195,12 @@ impl<T> From<ComputeControllerResponse<T>> for ControllerResponse<T> {
             ComputeControllerResponse::ReplicaHeartbeat(id, when) => {
                 ControllerResponse::ComputeReplicaHeartbeat(id, when)
             }
+            ComputeControllerResponse::HydrationStatus(id, replica_id, status) => {
+                ControllerResponse::ComputeHydrationStatus(id, replica_id, status)
+            }
+            ComputeControllerResponse::CompactionQueue(replica_id, queue) => {
+                ControllerResponse::ComputeCompactionQueue(replica_id, queue)
+            }
         }
     }
 }
@@ -228,7 +240,7 @@ pub struct Controller<T = mz_repr::Timestamp> {
     metrics_tx: UnboundedSender<(ReplicaId, Vec<ServiceProcessMetrics>)>,
     /// Receiver for the channel over which replica metrics are sent.
     metrics_rx: Peekable<UnboundedReceiverStream<(ReplicaId, Vec<ServiceProcessMetrics>)>>,
-    /// Periodic notification to record frontiers.
+    /// Periodic notification to record frontiers and quarantined errors.
     frontiers_ticker: Interval,
 
     /// The URL for Persist PubSub.
@@ -321,6 +333,11 @@ where
     async fn record_frontiers(&mut self) {
         let compute_frontiers = self.compute.replica_write_frontiers();
         self.storage.record_frontiers(compute_frontiers).await;
+
+        let quarantined_errors = self.compute.take_quarantined_errors();
+        self.storage
+            .record_quarantined_errors(quarantined_errors)
+            .await;
     }
 
     /// Produces a timestamp that reflects all data available in
@@ -476,6 +476,8 @@ impl Listeners {
                 .context("opening storage usage client")?,
         );
 
+        let persist_clients = Arc::clone(&config.controller.persist_clients);
+
         // Initialize controller.
         let controller = mz_controller::Controller::new(config.controller, envd_epoch).await;
 
@@ -512,6 +514,7 @@ impl Listeners {
             connection_context: config.connection_context,
             storage_usage_client,
             storage_usage_collection_interval: config.storage_usage_collection_interval,
+            persist_clients,
             storage_usage_retention_period: config.storage_usage_retention_period,
             segment_client: segment_client.clone(),
             egress_ips: config.egress_ips,
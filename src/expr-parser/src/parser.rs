@@ -70,7 +70,7 @@ pub fn try_parse_def(catalog: &TestCatalog, s: &str) -> Result<Def, String> {
 mod relation {
     use std::collections::BTreeMap;
 
-    use mz_expr::{Id, JoinImplementation, LocalId, MirRelationExpr};
+    use mz_expr::{Id, JoinImplementation, JoinKind, LocalId, MirRelationExpr};
     use mz_repr::{Diff, RelationType, Row, ScalarType};
 
     use super::*;
@@ -97,6 +97,8 @@ mod relation {
             parse_cross_join(ctx, input)
         } else if lookahead.peek(kw::Join) {
             parse_join(ctx, input)
+        } else if lookahead.peek(kw::LeftOuterJoin) {
+            parse_left_outer_join(ctx, input)
         } else if lookahead.peek(kw::Distinct) {
             parse_distinct(ctx, input)
         } else if lookahead.peek(kw::Reduce) {
@@ -358,6 +360,7 @@ mod relation {
             inputs,
             equivalences: vec![],
             implementation: JoinImplementation::Unimplemented,
+            kind: JoinKind::Inner,
         })
     }
 
@@ -377,6 +380,27 @@ mod relation {
             inputs,
             equivalences,
             implementation: JoinImplementation::Unimplemented,
+            kind: JoinKind::Inner,
+        })
+    }
+
+    fn parse_left_outer_join(ctx: CtxRef, input: ParseStream) -> Result {
+        let join = input.parse::<kw::LeftOuterJoin>()?;
+
+        input.parse::<kw::on>()?;
+        input.parse::<syn::Token![=]>()?;
+        let inner;
+        syn::parenthesized!(inner in input);
+        let equivalences = scalar::parse_join_equivalences(&inner)?;
+
+        let parse_inputs = ParseChildren::new(input, join.span().start());
+        let inputs = parse_inputs.parse_many(ctx, parse_expr)?;
+
+        Ok(MirRelationExpr::Join {
+            inputs,
+            equivalences,
+            implementation: JoinImplementation::Unimplemented,
+            kind: JoinKind::LeftOuter,
         })
     }
 
@@ -1457,6 +1481,7 @@ mod kw {
     syn::custom_keyword!(IS);
     syn::custom_keyword!(Join);
     syn::custom_keyword!(keys);
+    syn::custom_keyword!(LeftOuterJoin);
     syn::custom_keyword!(limit);
     syn::custom_keyword!(Map);
     syn::custom_keyword!(monotonic);
@@ -18,8 +18,8 @@ use mz_ore::str::{separated, Indent};
 use mz_repr::explain::text::DisplayText;
 use mz_repr::explain::ExplainError::LinearChainsPlusRecursive;
 use mz_repr::explain::{
-    AnnotatedPlan, Explain, ExplainConfig, ExplainError, ExprHumanizer, ScalarOps,
-    UnsupportedFormat, UsedIndexes,
+    AnnotatedPlan, Explain, ExplainConfig, ExplainError, ExprHumanizer, RecommendedIndexes,
+    ScalarOps, UnsupportedFormat, UsedIndexes,
 };
 
 use crate::interpret::{Interpreter, MfpEval, Trace};
@@ -36,6 +36,7 @@ pub struct ExplainContext<'a> {
     pub config: &'a ExplainConfig,
     pub humanizer: &'a dyn ExprHumanizer,
     pub used_indexes: UsedIndexes,
+    pub recommended_indexes: RecommendedIndexes,
     pub finishing: Option<RowSetFinishing>,
     pub duration: Duration,
 }
@@ -21,8 +21,8 @@ use mz_repr::{GlobalId, Row};
 
 use crate::explain::{ExplainMultiPlan, ExplainSinglePlan, ExplainSource};
 use crate::{
-    AggregateExpr, Id, JoinImplementation, JoinInputCharacteristics, LocalId, MapFilterProject,
-    MirRelationExpr, MirScalarExpr, RowSetFinishing,
+    AggregateExpr, Id, JoinImplementation, JoinInputCharacteristics, JoinKind, LocalId,
+    MapFilterProject, MirRelationExpr, MirScalarExpr, RowSetFinishing,
 };
 
 impl<'a, T: 'a> DisplayText for ExplainSinglePlan<'a, T>
@@ -49,6 +49,11 @@ where
             self.context.used_indexes.fmt_text(f, &mut ctx)?;
         }
 
+        if !self.context.recommended_indexes.is_empty() {
+            writeln!(f, "")?;
+            self.context.recommended_indexes.fmt_text(f, &mut ctx)?;
+        }
+
         if self.context.config.timing {
             writeln!(f, "")?;
             writeln!(f, "Optimization time: {:?}", self.context.duration)?;
@@ -125,6 +130,11 @@ where
             self.context.used_indexes.fmt_text(f, &mut ctx)?;
         }
 
+        if !self.context.recommended_indexes.is_empty() {
+            writeln!(f, "")?;
+            self.context.recommended_indexes.fmt_text(f, &mut ctx)?;
+        }
+
         if self.context.config.timing {
             writeln!(f, "")?;
             writeln!(f, "Optimization time: {:?}", self.context.duration)?;
@@ -414,6 +424,7 @@ impl MirRelationExpr {
                     implementation @ (JoinImplementation::Differential(..)
                     | JoinImplementation::DeltaQuery(..)
                     | JoinImplementation::Unimplemented),
+                kind,
             } => {
                 let has_equivalences = !equivalences.is_empty();
                 let equivalences = separated(
@@ -427,7 +438,9 @@ impl MirRelationExpr {
                     }),
                 );
 
-                if has_equivalences {
+                if let JoinKind::LeftOuter = kind {
+                    write!(f, "{}LeftOuterJoin on=({})", ctx.indent, equivalences)?;
+                } else if has_equivalences {
                     write!(f, "{}Join on=({})", ctx.indent, equivalences)?;
                 } else {
                     write!(f, "{}CrossJoin", ctx.indent)?;
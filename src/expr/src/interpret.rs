@@ -418,6 +418,33 @@ pub trait Interpreter {
             let result = mfp_eval.binary(&BinaryFunc::Gte, bound_range, mz_now.clone());
             results.push(result);
         }
+        for disjunction in &plan.disjunctions {
+            let terms = disjunction
+                .iter()
+                .map(|interval| {
+                    let mut conjuncts: Vec<_> =
+                        interval.guards.iter().map(|e| mfp_eval.expr(e)).collect();
+                    for bound in &interval.lower_bounds {
+                        let bound_range = mfp_eval.expr(bound);
+                        conjuncts.push(mfp_eval.binary(
+                            &BinaryFunc::Lte,
+                            bound_range,
+                            mz_now.clone(),
+                        ));
+                    }
+                    for bound in &interval.upper_bounds {
+                        let bound_range = mfp_eval.expr(bound);
+                        conjuncts.push(mfp_eval.binary(
+                            &BinaryFunc::Gte,
+                            bound_range,
+                            mz_now.clone(),
+                        ));
+                    }
+                    mfp_eval.variadic(&VariadicFunc::And, conjuncts)
+                })
+                .collect();
+            results.push(mfp_eval.variadic(&VariadicFunc::Or, terms));
+        }
         self.variadic(&VariadicFunc::And, results)
     }
 }
@@ -1425,15 +1425,19 @@ pub mod plan {
     use std::collections::BTreeMap;
     use std::iter;
 
+    use itertools::Either;
     use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
-    use mz_repr::{Datum, Diff, Row, RowArena};
+    use mz_repr::adt::datetime::DateTimeUnits;
+    use mz_repr::adt::interval::Interval;
+    use mz_repr::{Datum, Diff, Row, RowArena, ScalarType};
     use proptest::prelude::*;
     use proptest_derive::Arbitrary;
     use serde::{Deserialize, Serialize};
 
+    use crate::linear::{ProtoTemporalDisjunction, ProtoTemporalInterval};
     use crate::{
         func, BinaryFunc, EvalError, MapFilterProject, MirScalarExpr, ProtoMfpPlan,
-        ProtoSafeMfpPlan, UnaryFunc, UnmaterializableFunc,
+        ProtoSafeMfpPlan, UnaryFunc, UnmaterializableFunc, VariadicFunc,
     };
 
     /// A wrapper type which indicates it is safe to simply evaluate all expressions.
@@ -1537,6 +1541,44 @@ pub mod plan {
             Ok(true)
         }
 
+        /// Like [`Self::evaluate_inner`], but replaces the values of expressions that fail to
+        /// evaluate with `Datum::Null`, and pushes their errors to `errors`.
+        ///
+        /// Errors in predicates cannot be replaced and are returned as an `Err` variant, as is
+        /// the case for `evaluate_inner`. Note that a replaced value may cause later expressions
+        /// and predicates to evaluate differently than they would have if it had been `NULL`
+        /// to begin with.
+        pub fn evaluate_inner_nulling_errors<'b, 'a: 'b>(
+            &'a self,
+            datums: &'b mut Vec<Datum<'a>>,
+            arena: &'a RowArena,
+            errors: &mut Vec<EvalError>,
+        ) -> Result<bool, EvalError> {
+            let mut expression = 0;
+            for (support, predicate) in self.mfp.predicates.iter() {
+                while self.mfp.input_arity + expression < *support {
+                    let datum = self.mfp.expressions[expression].eval(&datums[..], arena);
+                    datums.push(datum.unwrap_or_else(|e| {
+                        errors.push(e);
+                        Datum::Null
+                    }));
+                    expression += 1;
+                }
+                if predicate.eval(&datums[..], arena)? != Datum::True {
+                    return Ok(false);
+                }
+            }
+            while expression < self.mfp.expressions.len() {
+                let datum = self.mfp.expressions[expression].eval(&datums[..], arena);
+                datums.push(datum.unwrap_or_else(|e| {
+                    errors.push(e);
+                    Datum::Null
+                }));
+                expression += 1;
+            }
+            Ok(true)
+        }
+
         /// Returns true if evaluation could introduce an error on non-error inputs.
         pub fn could_error(&self) -> bool {
             self.mfp.predicates.iter().any(|(_pos, e)| e.could_error())
@@ -1557,9 +1599,11 @@ pub mod plan {
     /// structure, and it is best to do that once and re-use the results.
     ///
     /// There are restrictions on the temporal predicates we currently support.
-    /// They must directly constrain `MzNow` from below or above,
-    /// by expressions that do not themselves contain `MzNow`.
-    /// Conjunctions of such constraints are also ok.
+    /// They must constrain `MzNow` from below or above, by expressions that do
+    /// not themselves contain `MzNow`. Conjunctions, disjunctions and `CASE`
+    /// expressions of such constraints are also ok, as are constraints that
+    /// equate a `date_trunc` or `date_bin` bucketing of `MzNow` with a
+    /// non-temporal expression.
     #[derive(Arbitrary, Clone, Debug, PartialEq)]
     pub struct MfpPlan {
         /// Normal predicates to evaluate on `&[Datum]` and expect `Ok(Datum::True)`.
@@ -1570,6 +1614,12 @@ pub mod plan {
         /// Expressions that when evaluated upper-bound `MzNow`.
         #[proptest(strategy = "prop::collection::vec(any::<MirScalarExpr>(), 0..2)")]
         pub(crate) upper_bounds: Vec<MirScalarExpr>,
+        /// Disjunctions of temporal intervals, each of which constrains `MzNow`
+        /// to the union of its intervals.
+        #[proptest(
+            strategy = "prop::collection::vec(prop::collection::vec(any::<TemporalInterval>(), 0..2), 0..2)"
+        )]
+        pub(crate) disjunctions: Vec<Vec<TemporalInterval>>,
     }
 
     impl RustType<ProtoMfpPlan> for MfpPlan {
@@ -1578,6 +1628,13 @@ pub mod plan {
                 mfp: Some(self.mfp.into_proto()),
                 lower_bounds: self.lower_bounds.into_proto(),
                 upper_bounds: self.upper_bounds.into_proto(),
+                disjunctions: self
+                    .disjunctions
+                    .iter()
+                    .map(|intervals| ProtoTemporalDisjunction {
+                        intervals: intervals.into_proto(),
+                    })
+                    .collect(),
             }
         }
 
@@ -1586,6 +1643,421 @@ pub mod plan {
                 mfp: proto.mfp.into_rust_if_some("ProtoMfpPlan::mfp")?,
                 lower_bounds: proto.lower_bounds.into_rust()?,
                 upper_bounds: proto.upper_bounds.into_rust()?,
+                disjunctions: proto
+                    .disjunctions
+                    .into_iter()
+                    .map(|disjunction| disjunction.intervals.into_rust())
+                    .collect::<Result<_, _>>()?,
+            })
+        }
+    }
+
+    /// A conjunction of temporal bounds that applies only to records for which
+    /// a conjunction of non-temporal predicates holds.
+    ///
+    /// The interval contains the times at least the greatest lower bound and
+    /// less than the least upper bound, if all of its guards evaluate to
+    /// `Datum::True`, and is empty otherwise.
+    #[derive(Arbitrary, Clone, Debug, Default, PartialEq)]
+    pub struct TemporalInterval {
+        /// Non-temporal predicates that must hold for the interval to be non-empty.
+        #[proptest(strategy = "prop::collection::vec(any::<MirScalarExpr>(), 0..2)")]
+        pub(crate) guards: Vec<MirScalarExpr>,
+        /// Expressions that when evaluated lower-bound `MzNow`.
+        #[proptest(strategy = "prop::collection::vec(any::<MirScalarExpr>(), 0..2)")]
+        pub(crate) lower_bounds: Vec<MirScalarExpr>,
+        /// Expressions that when evaluated upper-bound `MzNow`.
+        #[proptest(strategy = "prop::collection::vec(any::<MirScalarExpr>(), 0..2)")]
+        pub(crate) upper_bounds: Vec<MirScalarExpr>,
+    }
+
+    impl RustType<ProtoTemporalInterval> for TemporalInterval {
+        fn into_proto(&self) -> ProtoTemporalInterval {
+            ProtoTemporalInterval {
+                guards: self.guards.into_proto(),
+                lower_bounds: self.lower_bounds.into_proto(),
+                upper_bounds: self.upper_bounds.into_proto(),
+            }
+        }
+
+        fn from_proto(proto: ProtoTemporalInterval) -> Result<Self, TryFromProtoError> {
+            Ok(TemporalInterval {
+                guards: proto.guards.into_rust()?,
+                lower_bounds: proto.lower_bounds.into_rust()?,
+                upper_bounds: proto.upper_bounds.into_rust()?,
+            })
+        }
+    }
+
+    /// An interval of times, unbounded above if its upper bound is `None`.
+    type TimeInterval = (mz_repr::Timestamp, Option<mz_repr::Timestamp>);
+
+    impl TemporalInterval {
+        /// Returns the interval of both `self` and `other`.
+        fn intersect(&self, other: &TemporalInterval) -> TemporalInterval {
+            let mut result = self.clone();
+            result.guards.extend(other.guards.iter().cloned());
+            result
+                .lower_bounds
+                .extend(other.lower_bounds.iter().cloned());
+            result
+                .upper_bounds
+                .extend(other.upper_bounds.iter().cloned());
+            result
+        }
+
+        /// Evaluates the interval for `datums`, restricted to times at least `time`.
+        ///
+        /// Returns `None` if the interval is empty, including if any guard does
+        /// not hold or any bound evaluates to `NULL`.
+        fn evaluate<'a>(
+            &'a self,
+            datums: &[Datum<'a>],
+            arena: &'a RowArena,
+            time: mz_repr::Timestamp,
+        ) -> Result<Option<TimeInterval>, EvalError> {
+            for guard in self.guards.iter() {
+                if guard.eval(datums, arena)? != Datum::True {
+                    return Ok(None);
+                }
+            }
+            let mut lower_bound = time;
+            for l in self.lower_bounds.iter() {
+                match l.eval(datums, arena)? {
+                    Datum::MzTimestamp(d) => lower_bound = lower_bound.max(d),
+                    Datum::Null => return Ok(None),
+                    x => panic!("Non-mz_timestamp value in temporal predicate: {:?}", x),
+                }
+            }
+            let mut upper_bound: Option<mz_repr::Timestamp> = None;
+            for u in self.upper_bounds.iter() {
+                match u.eval(datums, arena)? {
+                    Datum::MzTimestamp(d) => {
+                        upper_bound = Some(upper_bound.map_or(d, |upper| upper.min(d)))
+                    }
+                    Datum::Null => return Ok(None),
+                    x => panic!("Non-mz_timestamp value in temporal predicate: {:?}", x),
+                }
+            }
+            match upper_bound {
+                Some(upper) if upper <= lower_bound => Ok(None),
+                _ => Ok(Some((lower_bound, upper_bound))),
+            }
+        }
+    }
+
+    /// Merges `intervals` into a sorted list of disjoint intervals that cover the same times.
+    fn union_intervals(mut intervals: Vec<TimeInterval>) -> Vec<TimeInterval> {
+        intervals.sort();
+        let mut result: Vec<TimeInterval> = Vec::with_capacity(intervals.len());
+        for (lower, upper) in intervals {
+            match result.last_mut() {
+                Some((_, last_upper)) if last_upper.map_or(true, |u| lower <= u) => {
+                    if last_upper.is_some() && upper.map_or(true, |u| Some(u) > *last_upper) {
+                        *last_upper = upper;
+                    }
+                }
+                _ => result.push((lower, upper)),
+            }
+        }
+        result
+    }
+
+    /// Intersects two sorted lists of disjoint intervals.
+    fn intersect_intervals(a: &[TimeInterval], b: &[TimeInterval]) -> Vec<TimeInterval> {
+        let mut result = Vec::new();
+        for (a_lower, a_upper) in a {
+            for (b_lower, b_upper) in b {
+                let lower = std::cmp::max(*a_lower, *b_lower);
+                let upper = match (a_upper, b_upper) {
+                    (Some(a), Some(b)) => Some(std::cmp::min(*a, *b)),
+                    (Some(u), None) | (None, Some(u)) => Some(*u),
+                    (None, None) => None,
+                };
+                if upper.map_or(true, |u| lower < u) {
+                    result.push((lower, upper));
+                }
+            }
+        }
+        result
+    }
+
+    /// The maximum number of intervals a single temporal predicate may expand into.
+    const MAX_TEMPORAL_INTERVALS: usize = 64;
+
+    /// Expands the temporal `predicate` into a disjunction of intervals.
+    fn temporal_intervals(predicate: MirScalarExpr) -> Result<Vec<TemporalInterval>, String> {
+        if !predicate.contains_temporal() {
+            return Ok(vec![TemporalInterval {
+                guards: vec![predicate],
+                ..Default::default()
+            }]);
+        }
+
+        let display = predicate.to_string();
+        let intervals = match predicate {
+            MirScalarExpr::CallVariadic {
+                func: VariadicFunc::Or,
+                exprs,
+            } => {
+                let mut intervals = Vec::new();
+                for expr in exprs {
+                    intervals.extend(temporal_intervals(expr)?);
+                }
+                intervals
+            }
+            MirScalarExpr::CallVariadic {
+                func: VariadicFunc::And,
+                exprs,
+            } => {
+                let mut intervals = vec![TemporalInterval::default()];
+                for expr in exprs {
+                    let conjunct = temporal_intervals(expr)?;
+                    intervals = intervals
+                        .iter()
+                        .flat_map(|i| conjunct.iter().map(move |c| i.intersect(c)))
+                        .collect();
+                    if intervals.len() > MAX_TEMPORAL_INTERVALS {
+                        break;
+                    }
+                }
+                intervals
+            }
+            MirScalarExpr::If { cond, then, els } if !cond.contains_temporal() => {
+                // `If` evaluates `els` if `cond` is false or null.
+                let not_cond = (*cond).clone().call_is_null().or((*cond).clone().not());
+                let mut intervals = temporal_intervals(*then)?;
+                for interval in intervals.iter_mut() {
+                    interval.guards.push((*cond).clone());
+                }
+                for mut interval in temporal_intervals(*els)? {
+                    interval.guards.push(not_cond.clone());
+                    intervals.push(interval);
+                }
+                intervals
+            }
+            MirScalarExpr::CallBinary { func, expr1, expr2 } => {
+                temporal_comparison(func, *expr1, *expr2)?
+            }
+            _ => {
+                return Err(format!(
+                    "Unsupported temporal predicate. Note: `mz_now()` must be directly compared to a non-temporal expression of mz_timestamp-castable type. Expression found: {}",
+                    display,
+                ));
+            }
+        };
+
+        if intervals.len() > MAX_TEMPORAL_INTERVALS {
+            return Err(format!(
+                "Unsupported temporal predicate. Note: temporal predicates may describe at most {} intervals. Expression found: {}",
+                MAX_TEMPORAL_INTERVALS, display,
+            ));
+        }
+        Ok(intervals)
+    }
+
+    /// True iff the temporal `predicate` only lower-bounds `MzNow`.
+    ///
+    /// Such predicates only delay the introduction of records, and never retract them.
+    pub(crate) fn is_temporal_lower_bound(predicate: &MirScalarExpr) -> bool {
+        temporal_intervals(predicate.clone())
+            .map(|intervals| intervals.iter().all(|i| i.upper_bounds.is_empty()))
+            .unwrap_or(false)
+    }
+
+    /// Expands the comparison `expr1 func expr2`, one of whose arguments is temporal, into a
+    /// disjunction of intervals.
+    fn temporal_comparison(
+        mut func: BinaryFunc,
+        mut expr1: MirScalarExpr,
+        mut expr2: MirScalarExpr,
+    ) -> Result<Vec<TemporalInterval>, String> {
+        let mz_now = MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzNow);
+
+        // Attempt to put the temporal expression in the first argument position.
+        if !expr1.contains_temporal() {
+            std::mem::swap(&mut expr1, &mut expr2);
+            func = match func {
+                BinaryFunc::Eq => BinaryFunc::Eq,
+                BinaryFunc::NotEq => BinaryFunc::NotEq,
+                BinaryFunc::Lt => BinaryFunc::Gt,
+                BinaryFunc::Lte => BinaryFunc::Gte,
+                BinaryFunc::Gt => BinaryFunc::Lt,
+                BinaryFunc::Gte => BinaryFunc::Lte,
+                x => {
+                    return Err(format!("Unsupported binary temporal operation: {:?}", x));
+                }
+            };
+        }
+
+        // Error if MLT is referenced in an unsupported position.
+        if expr2.contains_temporal() {
+            return Err(format!(
+                "Unsupported temporal predicate. Note: `mz_now()` must be directly compared to a mz_timestamp-castable expression. Expression found: {}",
+                MirScalarExpr::CallBinary { func, expr1: Box::new(expr1), expr2: Box::new(expr2) },
+            ));
+        }
+
+        if expr1 != mz_now {
+            return match temporal_bucket(&expr1, &mz_now) {
+                Some(bucket) if func == BinaryFunc::Eq => Ok(vec![bucket.interval(expr2)?]),
+                Some(_) => Err(format!(
+                    "Unsupported binary temporal operation on a bucketed `mz_now()`: {:?}",
+                    func
+                )),
+                None => Err(format!(
+                    "Unsupported temporal predicate. Note: `mz_now()` must be directly compared to a mz_timestamp-castable expression. Expression found: {}",
+                    MirScalarExpr::CallBinary { func, expr1: Box::new(expr1), expr2: Box::new(expr2) },
+                )),
+            };
+        }
+
+        let step = |expr: MirScalarExpr| {
+            expr.call_unary(UnaryFunc::StepMzTimestamp(func::StepMzTimestamp))
+        };
+        let lower = |bound| TemporalInterval {
+            lower_bounds: vec![bound],
+            ..Default::default()
+        };
+        let upper = |bound| TemporalInterval {
+            upper_bounds: vec![bound],
+            ..Default::default()
+        };
+
+        // LogicalTimestamp <OP> <EXPR2> for several supported operators.
+        let intervals = match func {
+            BinaryFunc::Eq => vec![TemporalInterval {
+                lower_bounds: vec![expr2.clone()],
+                upper_bounds: vec![step(expr2)],
+                ..Default::default()
+            }],
+            BinaryFunc::NotEq => vec![upper(expr2.clone()), lower(step(expr2))],
+            BinaryFunc::Lt => vec![upper(expr2)],
+            BinaryFunc::Lte => vec![upper(step(expr2))],
+            BinaryFunc::Gt => vec![lower(step(expr2))],
+            BinaryFunc::Gte => vec![lower(expr2)],
+            _ => {
+                return Err(format!("Unsupported binary temporal operation: {:?}", func));
+            }
+        };
+        Ok(intervals)
+    }
+
+    /// A bucketing of `MzNow` into consecutive buckets of timestamps.
+    enum TemporalBucket {
+        /// `date_trunc(units, mz_now()::timestamptz)`.
+        DateTrunc { units: DateTimeUnits },
+        /// `date_bin(stride, mz_now()::timestamptz)`.
+        DateBin { stride: MirScalarExpr },
+    }
+
+    /// Recognizes `expr` as a bucketing of `mz_now`.
+    fn temporal_bucket(expr: &MirScalarExpr, mz_now: &MirScalarExpr) -> Option<TemporalBucket> {
+        let is_mz_now_tz = |expr: &MirScalarExpr| {
+            matches!(
+                expr,
+                MirScalarExpr::CallUnary {
+                    func: UnaryFunc::CastMzTimestampToTimestampTz(_),
+                    expr,
+                } if &**expr == mz_now
+            )
+        };
+        match expr {
+            MirScalarExpr::CallUnary {
+                func: UnaryFunc::DateTruncTimestampTz(func::DateTruncTimestampTz(units)),
+                expr,
+            } if is_mz_now_tz(expr) => Some(TemporalBucket::DateTrunc { units: *units }),
+            MirScalarExpr::CallBinary {
+                func: BinaryFunc::DateTruncTimestampTz,
+                expr1,
+                expr2,
+            } if is_mz_now_tz(expr2) => Some(TemporalBucket::DateTrunc {
+                units: expr1.as_literal_str()?.parse().ok()?,
+            }),
+            MirScalarExpr::CallBinary {
+                func: BinaryFunc::DateBinTimestampTz,
+                expr1,
+                expr2,
+            } if is_mz_now_tz(expr2) && !expr1.contains_temporal() => {
+                Some(TemporalBucket::DateBin {
+                    stride: (**expr1).clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    impl TemporalBucket {
+        /// Returns the interval of times whose bucket is `start`.
+        ///
+        /// The interval is empty if `start` is not the start of a bucket.
+        ///
+        /// Times have millisecond precision, so buckets narrower than a millisecond, or whose
+        /// bounds do not fall on whole milliseconds, cannot be expressed and are rejected.
+        fn interval(self, start: MirScalarExpr) -> Result<TemporalInterval, String> {
+            let (bucket_of_start, end) = match self {
+                TemporalBucket::DateTrunc { units } => {
+                    let width = match units {
+                        DateTimeUnits::Milliseconds => Interval::new(0, 0, 1_000),
+                        DateTimeUnits::Second => Interval::new(0, 0, 1_000_000),
+                        DateTimeUnits::Minute => Interval::new(0, 0, 60 * 1_000_000),
+                        DateTimeUnits::Hour => Interval::new(0, 0, 60 * 60 * 1_000_000),
+                        DateTimeUnits::Day => Interval::new(0, 1, 0),
+                        DateTimeUnits::Week => Interval::new(0, 7, 0),
+                        DateTimeUnits::Month => Interval::new(1, 0, 0),
+                        DateTimeUnits::Quarter => Interval::new(3, 0, 0),
+                        DateTimeUnits::Year => Interval::new(12, 0, 0),
+                        DateTimeUnits::Decade => Interval::new(120, 0, 0),
+                        DateTimeUnits::Century => Interval::new(1_200, 0, 0),
+                        DateTimeUnits::Millennium => Interval::new(12_000, 0, 0),
+                        units => {
+                            return Err(format!(
+                                "Unsupported temporal predicate. Note: `date_trunc` of `mz_now()` does not support units {}",
+                                units
+                            ));
+                        }
+                    };
+                    (
+                        start.clone().call_unary(UnaryFunc::DateTruncTimestampTz(
+                            func::DateTruncTimestampTz(units),
+                        )),
+                        start.clone().call_binary(
+                            MirScalarExpr::literal_ok(Datum::Interval(width), ScalarType::Interval),
+                            BinaryFunc::AddTimestampTzInterval,
+                        ),
+                    )
+                }
+                TemporalBucket::DateBin { stride } => {
+                    if let Some(Ok(Datum::Interval(interval))) = stride.as_literal() {
+                        if interval.months != 0
+                            || interval.as_microseconds() < 1_000
+                            || interval.micros % 1_000 != 0
+                        {
+                            return Err(format!(
+                                "Unsupported temporal predicate. Note: `date_bin` of `mz_now()` requires a stride of whole milliseconds, found {}",
+                                interval
+                            ));
+                        }
+                    }
+                    (
+                        stride
+                            .clone()
+                            .call_binary(start.clone(), BinaryFunc::DateBinTimestampTz),
+                        start
+                            .clone()
+                            .call_binary(stride, BinaryFunc::AddTimestampTzInterval),
+                    )
+                }
+            };
+            let to_mz_timestamp = |expr: MirScalarExpr| {
+                expr.call_unary(UnaryFunc::CastTimestampTzToMzTimestamp(
+                    func::CastTimestampTzToMzTimestamp,
+                ))
+            };
+            Ok(TemporalInterval {
+                guards: vec![bucket_of_start.call_binary(start.clone(), BinaryFunc::Eq)],
+                lower_bounds: vec![to_mz_timestamp(start)],
+                upper_bounds: vec![to_mz_timestamp(end)],
             })
         }
     }
@@ -1601,6 +2073,8 @@ pub mod plan {
         /// mz_now cmp_op expr
         /// ```
         /// where `cmp_op` is a comparison operator and `expr` does not contain `mz_now`.
+        /// Temporal predicates that describe anything other than a single interval, for
+        /// example disjunctions, are expanded into disjunctions of intervals instead.
         ///
         /// If any unsupported expression is found, for example one that uses `mz_now`
         /// in an unsupported position, an error is returned.
@@ -1622,82 +2096,16 @@ pub mod plan {
                 }
             });
 
+            let mut disjunctions = Vec::new();
             for predicate in temporal.into_iter() {
-                // Supported temporal predicates are exclusively binary operators.
-                if let MirScalarExpr::CallBinary {
-                    mut func,
-                    mut expr1,
-                    mut expr2,
-                } = predicate
-                {
-                    // Attempt to put `LogicalTimestamp` in the first argument position.
-                    if !expr1.contains_temporal()
-                        && *expr2
-                            == MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzNow)
-                    {
-                        std::mem::swap(&mut expr1, &mut expr2);
-                        func = match func {
-                            BinaryFunc::Eq => BinaryFunc::Eq,
-                            BinaryFunc::Lt => BinaryFunc::Gt,
-                            BinaryFunc::Lte => BinaryFunc::Gte,
-                            BinaryFunc::Gt => BinaryFunc::Lt,
-                            BinaryFunc::Gte => BinaryFunc::Lte,
-                            x => {
-                                return Err(format!(
-                                    "Unsupported binary temporal operation: {:?}",
-                                    x
-                                ));
-                            }
-                        };
-                    }
-
-                    // Error if MLT is referenced in an unsupported position.
-                    if expr2.contains_temporal()
-                        || *expr1
-                            != MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzNow)
-                    {
-                        return Err(format!(
-                            "Unsupported temporal predicate. Note: `mz_now()` must be directly compared to a mz_timestamp-castable expression. Expression found: {}",
-                            MirScalarExpr::CallBinary { func, expr1, expr2 },
-                            ));
-                    }
-
-                    // LogicalTimestamp <OP> <EXPR2> for several supported operators.
-                    match func {
-                        BinaryFunc::Eq => {
-                            lower_bounds.push(*expr2.clone());
-                            upper_bounds.push(
-                                expr2.call_unary(UnaryFunc::StepMzTimestamp(func::StepMzTimestamp)),
-                            );
-                        }
-                        BinaryFunc::Lt => {
-                            upper_bounds.push(*expr2.clone());
-                        }
-                        BinaryFunc::Lte => {
-                            upper_bounds.push(
-                                expr2.call_unary(UnaryFunc::StepMzTimestamp(func::StepMzTimestamp)),
-                            );
-                        }
-                        BinaryFunc::Gt => {
-                            lower_bounds.push(
-                                expr2.call_unary(UnaryFunc::StepMzTimestamp(func::StepMzTimestamp)),
-                            );
-                        }
-                        BinaryFunc::Gte => {
-                            lower_bounds.push(*expr2.clone());
-                        }
-                        _ => {
-                            return Err(format!(
-                                "Unsupported binary temporal operation: {:?}",
-                                func
-                            ));
-                        }
-                    }
+                let mut intervals = temporal_intervals(predicate)?;
+                // A single unguarded interval constrains all records alike.
+                if intervals.len() == 1 && intervals[0].guards.is_empty() {
+                    let interval = intervals.pop().unwrap();
+                    lower_bounds.extend(interval.lower_bounds);
+                    upper_bounds.extend(interval.upper_bounds);
                 } else {
-                    return Err(format!(
-                        "Unsupported temporal predicate. Note: `mz_now()` must be directly compared to a non-temporal expression of mz_timestamp-castable type. Expression found: {}",
-                        predicate,
-                        ));
+                    disjunctions.push(intervals);
                 }
             }
 
@@ -1705,6 +2113,7 @@ pub mod plan {
                 mfp: SafeMfpPlan { mfp },
                 lower_bounds,
                 upper_bounds,
+                disjunctions,
             })
         }
 
@@ -1713,6 +2122,7 @@ pub mod plan {
             self.mfp.mfp.is_identity()
                 && self.lower_bounds.is_empty()
                 && self.upper_bounds.is_empty()
+                && self.disjunctions.is_empty()
         }
 
         /// Returns `self`, and leaves behind an identity operator that acts on its output.
@@ -1723,6 +2133,7 @@ pub mod plan {
                 },
                 lower_bounds: Default::default(),
                 upper_bounds: Default::default(),
+                disjunctions: Default::default(),
             };
             std::mem::swap(self, &mut identity);
             identity
@@ -1732,7 +2143,10 @@ pub mod plan {
         ///
         /// If that is not possible, the original instance is returned as an error.
         pub fn into_nontemporal(self) -> Result<SafeMfpPlan, Self> {
-            if self.lower_bounds.is_empty() && self.upper_bounds.is_empty() {
+            if self.lower_bounds.is_empty()
+                && self.upper_bounds.is_empty()
+                && self.disjunctions.is_empty()
+            {
                 Ok(self.mfp)
             } else {
                 Err(self)
@@ -1749,6 +2163,12 @@ pub mod plan {
                 .chain(&mut self.mfp.mfp.expressions)
                 .chain(&mut self.lower_bounds)
                 .chain(&mut self.upper_bounds)
+                .chain(self.disjunctions.iter_mut().flatten().flat_map(|interval| {
+                    iter::empty()
+                        .chain(&mut interval.guards)
+                        .chain(&mut interval.lower_bounds)
+                        .chain(&mut interval.upper_bounds)
+                }))
         }
 
         /// Evaluate the predicates, temporal and non-, and return times and differences for `data`.
@@ -1771,13 +2191,15 @@ pub mod plan {
         {
             match self.mfp.evaluate_inner(datums, arena) {
                 Err(e) => {
-                    return Some(Err((e.into(), time, diff)))
-                        .into_iter()
-                        .chain(None.into_iter());
+                    return Either::Left(
+                        Some(Err((e.into(), time, diff)))
+                            .into_iter()
+                            .chain(None.into_iter()),
+                    );
                 }
                 Ok(true) => {}
                 Ok(false) => {
-                    return None.into_iter().chain(None.into_iter());
+                    return Either::Left(None.into_iter().chain(None.into_iter()));
                 }
             }
 
@@ -1794,9 +2216,11 @@ pub mod plan {
             for l in self.lower_bounds.iter() {
                 match l.eval(datums, arena) {
                     Err(e) => {
-                        return Some(Err((e.into(), time, diff)))
-                            .into_iter()
-                            .chain(None.into_iter());
+                        return Either::Left(
+                            Some(Err((e.into(), time, diff)))
+                                .into_iter()
+                                .chain(None.into_iter()),
+                        );
                     }
                     Ok(Datum::MzTimestamp(d)) => {
                         lower_bound = lower_bound.max(d);
@@ -1812,7 +2236,7 @@ pub mod plan {
 
             // If the lower bound exceeds our `until` frontier, it should not appear in the output.
             if !valid_time(&lower_bound) {
-                return None.into_iter().chain(None.into_iter());
+                return Either::Left(None.into_iter().chain(None.into_iter()));
             }
 
             // If there are any upper bounds, determine the minimum upper bound.
@@ -1822,9 +2246,11 @@ pub mod plan {
                 if upper_bound != Some(lower_bound) {
                     match u.eval(datums, arena) {
                         Err(e) => {
-                            return Some(Err((e.into(), time, diff)))
-                                .into_iter()
-                                .chain(None.into_iter());
+                            return Either::Left(
+                                Some(Err((e.into(), time, diff)))
+                                    .into_iter()
+                                    .chain(None.into_iter()),
+                            );
                         }
                         Ok(Datum::MzTimestamp(d)) => {
                             if let Some(upper) = upper_bound {
@@ -1850,6 +2276,49 @@ pub mod plan {
                 }
             }
 
+            if !self.disjunctions.is_empty() {
+                if null_eval {
+                    return Either::Right(Vec::new().into_iter());
+                }
+                // Intersect the interval with the union of the intervals of each disjunction.
+                let mut intervals = match upper_bound {
+                    Some(upper) if upper <= lower_bound => Vec::new(),
+                    _ => vec![(lower_bound, upper_bound)],
+                };
+                for disjunction in self.disjunctions.iter() {
+                    if intervals.is_empty() {
+                        break;
+                    }
+                    let mut union = Vec::with_capacity(disjunction.len());
+                    for interval in disjunction {
+                        match interval.evaluate(datums, arena, lower_bound) {
+                            Err(e) => {
+                                return Either::Right(vec![Err((e.into(), time, diff))].into_iter())
+                            }
+                            Ok(Some(interval)) => union.push(interval),
+                            Ok(None) => {}
+                        }
+                    }
+                    intervals = intersect_intervals(&intervals, &union_intervals(union));
+                }
+
+                let mut updates = Vec::with_capacity(2 * intervals.len());
+                if !intervals.is_empty() {
+                    row_builder
+                        .packer()
+                        .extend(self.mfp.mfp.projection.iter().map(|c| datums[*c]));
+                }
+                for (lower, upper) in intervals {
+                    if valid_time(&lower) {
+                        updates.push(Ok((row_builder.clone(), lower, diff)));
+                    }
+                    if let Some(upper) = upper.filter(|upper| valid_time(upper)) {
+                        updates.push(Ok((row_builder.clone(), upper, -diff)));
+                    }
+                }
+                return Either::Right(updates.into_iter());
+            }
+
             // If the upper bound exceeds our `until` frontier, it should not appear in the output.
             if let Some(upper) = &mut upper_bound {
                 if !valid_time(upper) {
@@ -1866,9 +2335,9 @@ pub mod plan {
                 let upper_opt =
                     upper_bound.map(|upper_bound| Ok((row_builder.clone(), upper_bound, -diff)));
                 let lower = Some(Ok((row_builder.clone(), lower_bound, diff)));
-                lower.into_iter().chain(upper_opt.into_iter())
+                Either::Left(lower.into_iter().chain(upper_opt.into_iter()))
             } else {
-                None.into_iter().chain(None.into_iter())
+                Either::Left(None.into_iter().chain(None.into_iter()))
             }
         }
 
@@ -1877,18 +2346,240 @@ pub mod plan {
             self.mfp.could_error()
                 || self.lower_bounds.iter().any(|e| e.could_error())
                 || self.upper_bounds.iter().any(|e| e.could_error())
+                || self.disjunctions.iter().flatten().any(|interval| {
+                    interval
+                        .guards
+                        .iter()
+                        .chain(&interval.lower_bounds)
+                        .chain(&interval.upper_bounds)
+                        .any(|e| e.could_error())
+                })
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use chrono::{DateTime, NaiveDateTime, Utc};
     use mz_proto::protobuf_roundtrip;
+    use mz_repr::adt::timestamp::CheckedTimestamp;
+    use mz_repr::{Datum, Diff, Row, RowArena, ScalarType, Timestamp};
 
     use crate::linear::plan::*;
+    use crate::{func, BinaryFunc, EvalError, UnaryFunc, UnmaterializableFunc};
 
     use super::*;
 
+    fn mz_now() -> MirScalarExpr {
+        MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzNow)
+    }
+
+    fn mz_timestamp(t: u64) -> Datum<'static> {
+        Datum::MzTimestamp(Timestamp::from(t))
+    }
+
+    fn timestamptz(millis: i64) -> Datum<'static> {
+        let ts = NaiveDateTime::from_timestamp_millis(millis).unwrap();
+        Datum::TimestampTz(
+            CheckedTimestamp::from_timestamplike(DateTime::<Utc>::from_utc(ts, Utc)).unwrap(),
+        )
+    }
+
+    /// Plans `predicate` and returns the times and differences at which it admits `datums`
+    /// as of time `0`.
+    fn evaluate(predicate: MirScalarExpr, datums: &[Datum]) -> Vec<(Timestamp, Diff)> {
+        let mfp = MapFilterProject::new(datums.len()).filter(vec![predicate]);
+        let plan = MfpPlan::create_from(mfp).expect("supported temporal predicate");
+        let arena = RowArena::new();
+        let mut datums = datums.to_vec();
+        let mut row_builder = Row::default();
+        plan.evaluate::<EvalError, _>(
+            &mut datums,
+            &arena,
+            Timestamp::from(0u64),
+            1,
+            |_| true,
+            &mut row_builder,
+        )
+        .map(|result| {
+            let (_row, time, diff) = result.expect("evaluation succeeds");
+            (time, diff)
+        })
+        .collect()
+    }
+
+    fn updates(updates: &[(u64, Diff)]) -> Vec<(Timestamp, Diff)> {
+        updates
+            .iter()
+            .map(|(time, diff)| (Timestamp::from(*time), *diff))
+            .collect()
+    }
+
+    #[mz_ore::test]
+    fn test_temporal_disjunction() {
+        // mz_now() < #0 OR mz_now() >= #1
+        let predicate = mz_now()
+            .call_binary(MirScalarExpr::column(0), BinaryFunc::Lt)
+            .or(mz_now().call_binary(MirScalarExpr::column(1), BinaryFunc::Gte));
+        assert_eq!(
+            evaluate(predicate.clone(), &[mz_timestamp(10), mz_timestamp(20)]),
+            updates(&[(0, 1), (10, -1), (20, 1)]),
+        );
+        // Overlapping intervals are merged.
+        assert_eq!(
+            evaluate(predicate, &[mz_timestamp(20), mz_timestamp(10)]),
+            updates(&[(0, 1)]),
+        );
+
+        // mz_now() != #0
+        let predicate = mz_now().call_binary(MirScalarExpr::column(0), BinaryFunc::NotEq);
+        assert_eq!(
+            evaluate(predicate, &[mz_timestamp(10)]),
+            updates(&[(0, 1), (10, -1), (11, 1)]),
+        );
+
+        // Disjunctions are intersected with the other temporal predicates.
+        // mz_now() >= 5 AND (mz_now() < #0 OR mz_now() >= #1)
+        let predicate = mz_now()
+            .call_binary(
+                MirScalarExpr::literal_ok(mz_timestamp(5), ScalarType::MzTimestamp),
+                BinaryFunc::Gte,
+            )
+            .and(
+                mz_now()
+                    .call_binary(MirScalarExpr::column(0), BinaryFunc::Lt)
+                    .or(mz_now().call_binary(MirScalarExpr::column(1), BinaryFunc::Gte)),
+            );
+        assert_eq!(
+            evaluate(predicate, &[mz_timestamp(10), mz_timestamp(20)]),
+            updates(&[(5, 1), (10, -1), (20, 1)]),
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_temporal_case() {
+        // CASE WHEN #1 THEN mz_now() < #0 ELSE mz_now() >= #0 END
+        let predicate = MirScalarExpr::If {
+            cond: Box::new(MirScalarExpr::column(1)),
+            then: Box::new(mz_now().call_binary(MirScalarExpr::column(0), BinaryFunc::Lt)),
+            els: Box::new(mz_now().call_binary(MirScalarExpr::column(0), BinaryFunc::Gte)),
+        };
+        assert_eq!(
+            evaluate(predicate.clone(), &[mz_timestamp(10), Datum::True]),
+            updates(&[(0, 1), (10, -1)]),
+        );
+        assert_eq!(
+            evaluate(predicate.clone(), &[mz_timestamp(10), Datum::False]),
+            updates(&[(10, 1)]),
+        );
+        assert_eq!(
+            evaluate(predicate, &[mz_timestamp(10), Datum::Null]),
+            updates(&[(10, 1)]),
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_temporal_buckets() {
+        let now_tz = mz_now().call_unary(UnaryFunc::CastMzTimestampToTimestampTz(
+            func::CastMzTimestampToTimestampTz,
+        ));
+        // date_trunc('hour', mz_now()::timestamptz) = #0
+        let predicate = MirScalarExpr::literal_ok(Datum::String("hour"), ScalarType::String)
+            .call_binary(now_tz.clone(), BinaryFunc::DateTruncTimestampTz)
+            .call_binary(MirScalarExpr::column(0), BinaryFunc::Eq);
+        assert_eq!(
+            evaluate(predicate.clone(), &[timestamptz(3_600_000)]),
+            updates(&[(3_600_000, 1), (7_200_000, -1)]),
+        );
+        // Values that do not start a bucket are never admitted.
+        assert_eq!(evaluate(predicate, &[timestamptz(3_600_001)]), updates(&[]));
+
+        // Buckets may only be compared for equality.
+        let predicate = MirScalarExpr::literal_ok(Datum::String("hour"), ScalarType::String)
+            .call_binary(now_tz, BinaryFunc::DateTruncTimestampTz)
+            .call_binary(MirScalarExpr::column(0), BinaryFunc::Lt);
+        let mfp = MapFilterProject::new(1).filter(vec![predicate]);
+        assert!(MfpPlan::create_from(mfp).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_temporal_lower_bound() {
+        // mz_now() >= #0
+        let lower = mz_now().call_binary(MirScalarExpr::column(0), BinaryFunc::Gte);
+        assert!(lower.is_temporal_lower_bound());
+        // mz_now() > #0 OR (#2 AND mz_now() >= #1)
+        let predicate = mz_now()
+            .call_binary(MirScalarExpr::column(0), BinaryFunc::Gt)
+            .or(MirScalarExpr::column(2)
+                .and(mz_now().call_binary(MirScalarExpr::column(1), BinaryFunc::Gte)));
+        assert!(predicate.is_temporal_lower_bound());
+
+        // mz_now() < #1
+        let upper = mz_now().call_binary(MirScalarExpr::column(1), BinaryFunc::Lt);
+        assert!(!upper.is_temporal_lower_bound());
+        // mz_now() >= #0 AND mz_now() < #1
+        assert!(!lower.clone().and(upper.clone()).is_temporal_lower_bound());
+        // mz_now() >= #0 OR mz_now() < #1
+        assert!(!lower.or(upper).is_temporal_lower_bound());
+        // mz_now() = #0
+        let equal = mz_now().call_binary(MirScalarExpr::column(0), BinaryFunc::Eq);
+        assert!(!equal.is_temporal_lower_bound());
+        // Non-temporal predicates are not temporal lower bounds.
+        assert!(!MirScalarExpr::column(2).is_temporal_lower_bound());
+    }
+
+    #[mz_ore::test]
+    fn test_evaluate_nulling_errors() {
+        // Map (#0 / #1, #0 + 1), Filter (#3 > 1), Project (#2, #3)
+        let mfp = MapFilterProject::new(2)
+            .map(vec![
+                MirScalarExpr::column(0)
+                    .call_binary(MirScalarExpr::column(1), BinaryFunc::DivInt64),
+                MirScalarExpr::column(0).call_binary(
+                    MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
+                    BinaryFunc::AddInt64,
+                ),
+            ])
+            .filter(vec![MirScalarExpr::column(3).call_binary(
+                MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
+                BinaryFunc::Gt,
+            )])
+            .project(vec![2, 3]);
+        let plan = mfp
+            .into_plan()
+            .unwrap()
+            .into_nontemporal()
+            .expect("non-temporal");
+        let arena = RowArena::new();
+
+        let mut datums = vec![Datum::Int64(4), Datum::Int64(0)];
+        let mut errors = Vec::new();
+        let passed = plan.evaluate_inner_nulling_errors(&mut datums, &arena, &mut errors);
+        assert_eq!(passed, Ok(true));
+        assert_eq!(errors, vec![EvalError::DivisionByZero]);
+        let output: Vec<_> = plan.projection.iter().map(|c| datums[*c]).collect();
+        assert_eq!(output, vec![Datum::Null, Datum::Int64(5)]);
+
+        // Rows are still filtered after replacing errors.
+        let mut datums = vec![Datum::Int64(0), Datum::Int64(0)];
+        let mut errors = Vec::new();
+        let passed = plan.evaluate_inner_nulling_errors(&mut datums, &arena, &mut errors);
+        assert_eq!(passed, Ok(false));
+
+        // Errors in predicates cannot be replaced.
+        let mfp = MapFilterProject::new(2).filter(vec![MirScalarExpr::column(0)
+            .call_binary(MirScalarExpr::column(1), BinaryFunc::DivInt64)
+            .call_binary(
+                MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
+                BinaryFunc::Gt,
+            )]);
+        let plan = mfp.into_plan().unwrap().into_nontemporal().unwrap();
+        let mut datums = vec![Datum::Int64(4), Datum::Int64(0)];
+        let mut errors = Vec::new();
+        let passed = plan.evaluate_inner_nulling_errors(&mut datums, &arena, &mut errors);
+        assert_eq!(passed, Err(EvalError::DivisionByZero));
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(32))]
 
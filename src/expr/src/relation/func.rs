@@ -9,20 +9,22 @@
 
 #![allow(missing_docs)]
 
+use std::cmp::Ordering;
 use std::iter::Sum;
-use std::ops::Deref;
+use std::ops::{BitAnd, BitOr, BitXor, Deref};
 use std::{fmt, iter};
 
 use chrono::{DateTime, NaiveDateTime, Utc};
 use dec::OrderedDecimal;
 use itertools::Itertools;
 use mz_lowertest::MzReflect;
-use mz_ore::cast::CastFrom;
+use mz_ore::cast::{CastFrom, CastLossy};
 
 use mz_ore::str::separated;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::date::Date;
+use mz_repr::adt::hll::HyperLogLog;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::numeric::{self, NumericMaxScale};
 use mz_repr::adt::regex::Regex as ReprRegex;
@@ -157,6 +159,44 @@ where
     Datum::from(x)
 }
 
+fn approx_count_distinct<'a, I>(datums: I) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut sketch = HyperLogLog::new();
+    for datum in datums {
+        sketch.insert(datum);
+    }
+    Datum::Int64(i64::try_from(sketch.estimate()).unwrap_or(i64::MAX))
+}
+
+/// Collects the non-null `datums` into a histogram, i.e., their distinct values in ascending
+/// order with their multiplicities.
+fn histogram<'a, I>(datums: I) -> Vec<(Datum<'a>, Diff)>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut datums: Vec<_> = datums.into_iter().filter(|d| !d.is_null()).collect();
+    datums.sort();
+    datums
+        .into_iter()
+        .dedup_with_count()
+        .map(|(count, datum)| (datum, Diff::cast_from(count)))
+        .collect()
+}
+
+/// Returns the value at position `index` of the ascending order described by `histogram`.
+fn nth_value<'a>(histogram: &[(Datum<'a>, Diff)], mut index: u64) -> Datum<'a> {
+    for (value, count) in histogram {
+        let count = u64::try_from(*count).expect("histograms have positive multiplicities");
+        if index < count {
+            return *value;
+        }
+        index -= count;
+    }
+    panic!("position out of bounds of histogram");
+}
+
 fn any<'a, I>(datums: I) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -183,6 +223,36 @@ where
         })
 }
 
+fn xor<'a, I>(datums: I) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .fold(Datum::Null, |state, next| match state {
+            Datum::Null => next,
+            _ => Datum::from(state != next),
+        })
+}
+
+fn bitwise_datum<'a, I, DatumType, F>(datums: I, op: F) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+    DatumType: TryFrom<Datum<'a>>,
+    <DatumType as TryFrom<Datum<'a>>>::Error: std::fmt::Debug,
+    Datum<'a>: From<Option<DatumType>>,
+    F: Fn(DatumType, DatumType) -> DatumType,
+{
+    let x: Option<DatumType> = datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .map(|d| DatumType::try_from(d).expect("unexpected type"))
+        .reduce(op);
+
+    x.into()
+}
+
 fn string_agg<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -822,6 +892,42 @@ pub enum AggregateFunc {
     Count,
     Any,
     All,
+    /// The exclusive or of the non-null `Datum::True`s and `Datum::False`s, as `bool_xor`.
+    Xor,
+    BitAndInt16,
+    BitAndInt32,
+    BitAndInt64,
+    BitOrInt16,
+    BitOrInt32,
+    BitOrInt64,
+    BitXorInt16,
+    BitXorInt32,
+    BitXorInt64,
+    /// Estimates the number of distinct non-null `Datum`s with a HyperLogLog sketch.
+    ///
+    /// The sketches of subsets of the input can be merged, so this is rendered as a hierarchical
+    /// reduction over partial sketches. A `Datum` cannot be removed from a sketch, and so each
+    /// stage recomputes its sketches from its current inputs, unless the input is monotonic.
+    ApproxCountDistinct,
+    /// Interpolates the value at position `fraction` in the ascending order of the non-null
+    /// `Datum::Float64`s, as `percentile_cont(fraction) WITHIN GROUP (ORDER BY ..)`.
+    ///
+    /// The order statistics `PercentileCont`, `PercentileDisc`, and `Mode` only depend on the
+    /// histogram of their input, see [`AggregateFunc::eval_histogram`].
+    PercentileCont {
+        fraction: OrderedFloat<f64>,
+    },
+    /// Returns the first non-null `Datum` whose position in the order of the input is at
+    /// least `fraction`, as `percentile_disc(fraction) WITHIN GROUP (ORDER BY ..)`.
+    PercentileDisc {
+        fraction: OrderedFloat<f64>,
+        desc: bool,
+    },
+    /// Returns the most frequent non-null `Datum`, as `mode() WITHIN GROUP (ORDER BY ..)`.
+    /// Among equally frequent `Datum`s, returns the first in the order of the input.
+    Mode {
+        desc: bool,
+    },
     /// Accumulates `Datum::List`s whose first element is a JSON-typed `Datum`s
     /// into a JSON list. The other elements are columns used by `order_by`.
     ///
@@ -939,6 +1045,31 @@ impl Arbitrary for AggregateFunc {
             Just(AggregateFunc::Count).boxed(),
             Just(AggregateFunc::Any).boxed(),
             Just(AggregateFunc::All).boxed(),
+            Just(AggregateFunc::Xor).boxed(),
+            Just(AggregateFunc::BitAndInt16).boxed(),
+            Just(AggregateFunc::BitAndInt32).boxed(),
+            Just(AggregateFunc::BitAndInt64).boxed(),
+            Just(AggregateFunc::BitOrInt16).boxed(),
+            Just(AggregateFunc::BitOrInt32).boxed(),
+            Just(AggregateFunc::BitOrInt64).boxed(),
+            Just(AggregateFunc::BitXorInt16).boxed(),
+            Just(AggregateFunc::BitXorInt32).boxed(),
+            Just(AggregateFunc::BitXorInt64).boxed(),
+            Just(AggregateFunc::ApproxCountDistinct).boxed(),
+            (0.0..=1.0f64)
+                .prop_map(|fraction| AggregateFunc::PercentileCont {
+                    fraction: OrderedFloat(fraction),
+                })
+                .boxed(),
+            (0.0..=1.0f64, proptest_any::<bool>())
+                .prop_map(|(fraction, desc)| AggregateFunc::PercentileDisc {
+                    fraction: OrderedFloat(fraction),
+                    desc,
+                })
+                .boxed(),
+            proptest_any::<bool>()
+                .prop_map(|desc| AggregateFunc::Mode { desc })
+                .boxed(),
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::JsonbAgg { order_by })
                 .boxed(),
@@ -1055,6 +1186,31 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 AggregateFunc::Count => Kind::Count(()),
                 AggregateFunc::Any => Kind::Any(()),
                 AggregateFunc::All => Kind::All(()),
+                AggregateFunc::Xor => Kind::Xor(()),
+                AggregateFunc::BitAndInt16 => Kind::BitAndInt16(()),
+                AggregateFunc::BitAndInt32 => Kind::BitAndInt32(()),
+                AggregateFunc::BitAndInt64 => Kind::BitAndInt64(()),
+                AggregateFunc::BitOrInt16 => Kind::BitOrInt16(()),
+                AggregateFunc::BitOrInt32 => Kind::BitOrInt32(()),
+                AggregateFunc::BitOrInt64 => Kind::BitOrInt64(()),
+                AggregateFunc::BitXorInt16 => Kind::BitXorInt16(()),
+                AggregateFunc::BitXorInt32 => Kind::BitXorInt32(()),
+                AggregateFunc::BitXorInt64 => Kind::BitXorInt64(()),
+                AggregateFunc::ApproxCountDistinct => Kind::ApproxCountDistinct(()),
+                AggregateFunc::PercentileCont { fraction } => {
+                    Kind::PercentileCont(proto_aggregate_func::ProtoPercentileCont {
+                        fraction: fraction.into_inner(),
+                    })
+                }
+                AggregateFunc::PercentileDisc { fraction, desc } => {
+                    Kind::PercentileDisc(proto_aggregate_func::ProtoPercentileDisc {
+                        fraction: fraction.into_inner(),
+                        desc: *desc,
+                    })
+                }
+                AggregateFunc::Mode { desc } => {
+                    Kind::Mode(proto_aggregate_func::ProtoMode { desc: *desc })
+                }
                 AggregateFunc::JsonbAgg { order_by } => Kind::JsonbAgg(order_by.into_proto()),
                 AggregateFunc::JsonbObjectAgg { order_by } => {
                     Kind::JsonbObjectAgg(order_by.into_proto())
@@ -1146,6 +1302,25 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
             Kind::Count(()) => AggregateFunc::Count,
             Kind::Any(()) => AggregateFunc::Any,
             Kind::All(()) => AggregateFunc::All,
+            Kind::Xor(()) => AggregateFunc::Xor,
+            Kind::BitAndInt16(()) => AggregateFunc::BitAndInt16,
+            Kind::BitAndInt32(()) => AggregateFunc::BitAndInt32,
+            Kind::BitAndInt64(()) => AggregateFunc::BitAndInt64,
+            Kind::BitOrInt16(()) => AggregateFunc::BitOrInt16,
+            Kind::BitOrInt32(()) => AggregateFunc::BitOrInt32,
+            Kind::BitOrInt64(()) => AggregateFunc::BitOrInt64,
+            Kind::BitXorInt16(()) => AggregateFunc::BitXorInt16,
+            Kind::BitXorInt32(()) => AggregateFunc::BitXorInt32,
+            Kind::BitXorInt64(()) => AggregateFunc::BitXorInt64,
+            Kind::ApproxCountDistinct(()) => AggregateFunc::ApproxCountDistinct,
+            Kind::PercentileCont(ppc) => AggregateFunc::PercentileCont {
+                fraction: OrderedFloat(ppc.fraction),
+            },
+            Kind::PercentileDisc(ppd) => AggregateFunc::PercentileDisc {
+                fraction: OrderedFloat(ppd.fraction),
+                desc: ppd.desc,
+            },
+            Kind::Mode(pm) => AggregateFunc::Mode { desc: pm.desc },
             Kind::JsonbAgg(order_by) => AggregateFunc::JsonbAgg {
                 order_by: order_by.into_rust()?,
             },
@@ -1268,6 +1443,20 @@ impl AggregateFunc {
             AggregateFunc::Count => count(datums),
             AggregateFunc::Any => any(datums),
             AggregateFunc::All => all(datums),
+            AggregateFunc::Xor => xor(datums),
+            AggregateFunc::BitAndInt16 => bitwise_datum::<'a, I, i16, _>(datums, BitAnd::bitand),
+            AggregateFunc::BitAndInt32 => bitwise_datum::<'a, I, i32, _>(datums, BitAnd::bitand),
+            AggregateFunc::BitAndInt64 => bitwise_datum::<'a, I, i64, _>(datums, BitAnd::bitand),
+            AggregateFunc::BitOrInt16 => bitwise_datum::<'a, I, i16, _>(datums, BitOr::bitor),
+            AggregateFunc::BitOrInt32 => bitwise_datum::<'a, I, i32, _>(datums, BitOr::bitor),
+            AggregateFunc::BitOrInt64 => bitwise_datum::<'a, I, i64, _>(datums, BitOr::bitor),
+            AggregateFunc::BitXorInt16 => bitwise_datum::<'a, I, i16, _>(datums, BitXor::bitxor),
+            AggregateFunc::BitXorInt32 => bitwise_datum::<'a, I, i32, _>(datums, BitXor::bitxor),
+            AggregateFunc::BitXorInt64 => bitwise_datum::<'a, I, i64, _>(datums, BitXor::bitxor),
+            AggregateFunc::ApproxCountDistinct => approx_count_distinct(datums),
+            AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. } => self.eval_histogram(&histogram(datums)),
             AggregateFunc::JsonbAgg { order_by } => jsonb_agg(datums, temp_storage, order_by),
             AggregateFunc::JsonbObjectAgg { order_by } => {
                 jsonb_object_agg(datums, temp_storage, order_by)
@@ -1295,11 +1484,112 @@ impl AggregateFunc {
         }
     }
 
+    /// Evaluates an order statistic over the histogram of its input, i.e., the distinct non-null
+    /// values of its input in ascending order, with their positive multiplicities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not one of the order statistics [`AggregateFunc::PercentileCont`],
+    /// [`AggregateFunc::PercentileDisc`], or [`AggregateFunc::Mode`].
+    pub fn eval_histogram<'a>(&self, histogram: &[(Datum<'a>, Diff)]) -> Datum<'a> {
+        if let AggregateFunc::Mode { .. } = self {
+            return histogram
+                .iter()
+                .max_by(|lhs, rhs| self.mode_cmp(**lhs, **rhs))
+                .map_or(Datum::Null, |(value, _)| *value);
+        }
+        let total: u64 = histogram
+            .iter()
+            .map(|(_, count)| {
+                u64::try_from(*count).expect("histograms have positive multiplicities")
+            })
+            .sum();
+        if total == 0 {
+            return Datum::Null;
+        }
+        let (lower, upper, weight) = self.percentile_positions(total);
+        self.eval_percentile(
+            nth_value(histogram, lower),
+            nth_value(histogram, upper),
+            weight,
+        )
+    }
+
+    /// Returns the positions, counting from zero, in the ascending order of `total` non-null
+    /// input values of the values that determine a percentile, together with the weight of the
+    /// upper one, see [`AggregateFunc::eval_percentile`].
+    ///
+    /// Only [`AggregateFunc::PercentileCont`] interpolates between two positions, which are the
+    /// same for [`AggregateFunc::PercentileDisc`]. This lets renderings that count the values in
+    /// ranges of the order locate a percentile without visiting every value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `total` is zero or this is not a percentile.
+    pub fn percentile_positions(&self, total: u64) -> (u64, u64, f64) {
+        assert!(total > 0, "percentiles of empty inputs are null");
+        match self {
+            AggregateFunc::PercentileCont { fraction } => {
+                // Interpolate between the values around position `fraction * (total - 1)`,
+                // like PostgreSQL does.
+                let position = fraction.into_inner() * f64::cast_lossy(total - 1);
+                let lower = u64::cast_lossy(position.floor());
+                let upper = u64::cast_lossy(position.ceil());
+                (lower, upper, position - position.floor())
+            }
+            AggregateFunc::PercentileDisc { fraction, desc } => {
+                // The first value whose position, counting from one, is at least
+                // `fraction * total`.
+                let position =
+                    u64::cast_lossy((fraction.into_inner() * f64::cast_lossy(total)).ceil());
+                let index = position.clamp(1, total) - 1;
+                let index = if *desc { total - 1 - index } else { index };
+                (index, index, 0.0)
+            }
+            _ => panic!("{} is not a percentile", self),
+        }
+    }
+
+    /// Evaluates a percentile from the values at the positions returned by
+    /// [`AggregateFunc::percentile_positions`], interpolating between them by `weight`.
+    pub fn eval_percentile<'a>(
+        &self,
+        lower: Datum<'a>,
+        upper: Datum<'a>,
+        weight: f64,
+    ) -> Datum<'a> {
+        if lower == upper {
+            lower
+        } else {
+            let (lower, upper) = (lower.unwrap_float64(), upper.unwrap_float64());
+            Datum::from(lower + (upper - lower) * weight)
+        }
+    }
+
+    /// Compares two distinct non-null values of the input of [`AggregateFunc::Mode`] with their
+    /// multiplicities, such that the mode is the greatest.
+    ///
+    /// Among equally frequent values, the first in the order of the input is the greatest. The
+    /// mode of the union of disjoint sets of values is thus the greatest of their modes.
+    pub fn mode_cmp(
+        &self,
+        (lhs, lhs_count): (Datum, Diff),
+        (rhs, rhs_count): (Datum, Diff),
+    ) -> Ordering {
+        let desc = match self {
+            AggregateFunc::Mode { desc } => *desc,
+            _ => panic!("{} is not a mode", self),
+        };
+        lhs_count
+            .cmp(&rhs_count)
+            .then_with(|| if desc { lhs.cmp(&rhs) } else { rhs.cmp(&lhs) })
+    }
+
     /// Returns the output of the aggregation function when applied on an empty
     /// input relation.
     pub fn default(&self) -> Datum<'static> {
         match self {
-            AggregateFunc::Count => Datum::Int64(0),
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => Datum::Int64(0),
             AggregateFunc::Any => Datum::False,
             AggregateFunc::All => Datum::True,
             AggregateFunc::Dummy => Datum::Dummy,
@@ -1333,8 +1623,10 @@ impl AggregateFunc {
     pub fn output_type(&self, input_type: ColumnType) -> ColumnType {
         let scalar_type = match self {
             AggregateFunc::Count => ScalarType::Int64,
+            AggregateFunc::ApproxCountDistinct => ScalarType::Int64,
             AggregateFunc::Any => ScalarType::Bool,
             AggregateFunc::All => ScalarType::Bool,
+            AggregateFunc::Xor => ScalarType::Bool,
             AggregateFunc::JsonbAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::JsonbObjectAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::SumInt16 => ScalarType::Int64,
@@ -1440,7 +1732,7 @@ impl AggregateFunc {
         // Count never produces null, and other aggregations only produce
         // null in the presence of null inputs.
         let nullable = match self {
-            AggregateFunc::Count => false,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => false,
             // Use the nullability of the underlying column being aggregated, not the Records wrapping it
             AggregateFunc::StringAgg { .. } => match input_type.scalar_type {
                 // The outer Record wraps the input in the first position, and any ORDER BY expressions afterwards
@@ -1527,6 +1819,19 @@ impl AggregateFunc {
             | AggregateFunc::SumFloat32
             | AggregateFunc::SumFloat64
             | AggregateFunc::SumNumeric
+            | AggregateFunc::Xor
+            | AggregateFunc::BitAndInt16
+            | AggregateFunc::BitAndInt32
+            | AggregateFunc::BitAndInt64
+            | AggregateFunc::BitOrInt16
+            | AggregateFunc::BitOrInt32
+            | AggregateFunc::BitOrInt64
+            | AggregateFunc::BitXorInt16
+            | AggregateFunc::BitXorInt32
+            | AggregateFunc::BitXorInt64
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
             | AggregateFunc::StringAgg { .. } => true,
             // Count is never null
             AggregateFunc::Count => false,
@@ -1676,6 +1981,85 @@ fn generate_series_ts<T: TimestampLike>(
     Ok(trsi.map(move |i| (Row::pack_slice(&[conv(i)]), 1)))
 }
 
+/// The maximum number of hopping windows a single timestamp may be assigned to, which bounds the
+/// ratio between a window's size and its slide.
+const MAX_WINDOWS_PER_TIMESTAMP: i64 = 1024;
+
+/// Returns the length of a window `size` or `slide` in microseconds.
+fn window_micros(interval: Interval, name: &str) -> Result<i64, EvalError> {
+    if interval.months != 0 {
+        return Err(EvalError::InvalidParameterValue(format!(
+            "window {} cannot contain months or years",
+            name
+        )));
+    }
+    match i64::try_from(interval.as_microseconds()) {
+        Ok(micros) if micros > 0 => Ok(micros),
+        Ok(_) => Err(EvalError::InvalidParameterValue(format!(
+            "window {} must be greater than zero",
+            name
+        ))),
+        Err(_) => Err(EvalError::IntervalOutOfRange(interval.to_string())),
+    }
+}
+
+/// Returns the windows of length `size` that start every `slide` and contain `ts`, as rows of
+/// their start and end.
+///
+/// Windows are aligned to the Unix epoch. Tumbling windows are hopping windows whose `slide`
+/// equals their `size`. A timestamp is assigned to at most [`MAX_WINDOWS_PER_TIMESTAMP`] windows.
+fn hopping_windows<T: TimestampLike>(
+    ts: CheckedTimestamp<T>,
+    size: Interval,
+    slide: Interval,
+) -> Result<Vec<(Row, Diff)>, EvalError> {
+    let size = window_micros(size, "size")?;
+    let slide = window_micros(slide, "slide")?;
+    if size / slide > MAX_WINDOWS_PER_TIMESTAMP
+        || (size / slide == MAX_WINDOWS_PER_TIMESTAMP && size % slide != 0)
+    {
+        return Err(EvalError::InvalidParameterValue(format!(
+            "window size cannot exceed {} times the window slide",
+            MAX_WINDOWS_PER_TIMESTAMP
+        )));
+    }
+
+    let date_time = ts.date_time();
+    let micros = date_time
+        .timestamp()
+        .checked_mul(1_000_000)
+        .and_then(|micros| micros.checked_add(i64::from(date_time.timestamp_subsec_micros())))
+        .ok_or(EvalError::TimestampOutOfRange)?;
+    let to_datum = |micros: i64| -> Result<Datum<'static>, EvalError> {
+        let date_time = NaiveDateTime::from_timestamp_opt(0, 0)
+            .unwrap()
+            .checked_add_signed(chrono::Duration::microseconds(micros))
+            .ok_or(EvalError::TimestampOutOfRange)?;
+        T::from_date_time(date_time)
+            .try_into()
+            .map_err(|_| EvalError::TimestampOutOfRange)
+    };
+
+    let last_start = micros - micros.rem_euclid(slide);
+    let mut starts = Vec::new();
+    let mut start = last_start;
+    while start > micros.saturating_sub(size) {
+        starts.push(start);
+        start -= slide;
+    }
+    starts
+        .into_iter()
+        .rev()
+        .map(|start| {
+            let end = start
+                .checked_add(size)
+                .ok_or(EvalError::TimestampOutOfRange)?;
+            let row = Row::pack_slice(&[to_datum(start)?, to_datum(end)?]);
+            Ok((row, 1))
+        })
+        .collect()
+}
+
 fn generate_subscripts_array(
     a: Datum,
     dim: i32,
@@ -1762,6 +2146,24 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::Count => f.write_str("count"),
             AggregateFunc::Any => f.write_str("any"),
             AggregateFunc::All => f.write_str("all"),
+            AggregateFunc::Xor => f.write_str("xor"),
+            AggregateFunc::BitAndInt16 => f.write_str("bit_and"),
+            AggregateFunc::BitAndInt32 => f.write_str("bit_and"),
+            AggregateFunc::BitAndInt64 => f.write_str("bit_and"),
+            AggregateFunc::BitOrInt16 => f.write_str("bit_or"),
+            AggregateFunc::BitOrInt32 => f.write_str("bit_or"),
+            AggregateFunc::BitOrInt64 => f.write_str("bit_or"),
+            AggregateFunc::BitXorInt16 => f.write_str("bit_xor"),
+            AggregateFunc::BitXorInt32 => f.write_str("bit_xor"),
+            AggregateFunc::BitXorInt64 => f.write_str("bit_xor"),
+            AggregateFunc::ApproxCountDistinct => f.write_str("approx_count_distinct"),
+            AggregateFunc::PercentileCont { fraction } => {
+                write!(f, "percentile_cont[fraction={}]", fraction)
+            }
+            AggregateFunc::PercentileDisc { fraction, desc } => {
+                write!(f, "percentile_disc[fraction={}, desc={}]", fraction, desc)
+            }
+            AggregateFunc::Mode { desc } => write!(f, "mode[desc={}]", desc),
             AggregateFunc::JsonbAgg { order_by } => {
                 write!(f, "jsonb_agg[order_by=[{}]]", separated(", ", order_by))
             }
@@ -2031,6 +2433,13 @@ pub enum TableFunc {
     GenerateSeriesInt64,
     GenerateSeriesTimestamp,
     GenerateSeriesTimestampTz,
+    /// Assigns a timestamp to the tumbling window of a given size that contains it.
+    TumbleTimestamp,
+    TumbleTimestampTz,
+    /// Assigns a timestamp to all hopping windows of a given size and slide
+    /// that contain it.
+    HopTimestamp,
+    HopTimestampTz,
     Repeat,
     UnnestArray {
         el_typ: ScalarType,
@@ -2071,6 +2480,10 @@ impl RustType<ProtoTableFunc> for TableFunc {
                 TableFunc::GenerateSeriesInt64 => Kind::GenerateSeriesInt64(()),
                 TableFunc::GenerateSeriesTimestamp => Kind::GenerateSeriesTimestamp(()),
                 TableFunc::GenerateSeriesTimestampTz => Kind::GenerateSeriesTimestampTz(()),
+                TableFunc::TumbleTimestamp => Kind::TumbleTimestamp(()),
+                TableFunc::TumbleTimestampTz => Kind::TumbleTimestampTz(()),
+                TableFunc::HopTimestamp => Kind::HopTimestamp(()),
+                TableFunc::HopTimestampTz => Kind::HopTimestampTz(()),
                 TableFunc::Repeat => Kind::Repeat(()),
                 TableFunc::UnnestArray { el_typ } => Kind::UnnestArray(el_typ.into_proto()),
                 TableFunc::UnnestList { el_typ } => Kind::UnnestList(el_typ.into_proto()),
@@ -2108,6 +2521,10 @@ impl RustType<ProtoTableFunc> for TableFunc {
             Kind::GenerateSeriesInt64(()) => TableFunc::GenerateSeriesInt64,
             Kind::GenerateSeriesTimestamp(()) => TableFunc::GenerateSeriesTimestamp,
             Kind::GenerateSeriesTimestampTz(()) => TableFunc::GenerateSeriesTimestampTz,
+            Kind::TumbleTimestamp(()) => TableFunc::TumbleTimestamp,
+            Kind::TumbleTimestampTz(()) => TableFunc::TumbleTimestampTz,
+            Kind::HopTimestamp(()) => TableFunc::HopTimestamp,
+            Kind::HopTimestampTz(()) => TableFunc::HopTimestampTz,
             Kind::Repeat(()) => TableFunc::Repeat,
             Kind::UnnestArray(x) => TableFunc::UnnestArray {
                 el_typ: x.into_rust()?,
@@ -2193,6 +2610,32 @@ impl TableFunc {
                 )?;
                 Ok(Box::new(res))
             }
+            TableFunc::TumbleTimestamp => {
+                let size = datums[1].unwrap_interval();
+                let res = hopping_windows(datums[0].unwrap_timestamp(), size, size)?;
+                Ok(Box::new(res))
+            }
+            TableFunc::TumbleTimestampTz => {
+                let size = datums[1].unwrap_interval();
+                let res = hopping_windows(datums[0].unwrap_timestamptz(), size, size)?;
+                Ok(Box::new(res))
+            }
+            TableFunc::HopTimestamp => {
+                let res = hopping_windows(
+                    datums[0].unwrap_timestamp(),
+                    datums[1].unwrap_interval(),
+                    datums[2].unwrap_interval(),
+                )?;
+                Ok(Box::new(res))
+            }
+            TableFunc::HopTimestampTz => {
+                let res = hopping_windows(
+                    datums[0].unwrap_timestamptz(),
+                    datums[1].unwrap_interval(),
+                    datums[2].unwrap_interval(),
+                )?;
+                Ok(Box::new(res))
+            }
             TableFunc::GenerateSubscriptsArray => {
                 generate_subscripts_array(datums[0], datums[1].unwrap_int32())
             }
@@ -2295,6 +2738,22 @@ impl TableFunc {
                 let keys = vec![vec![0]];
                 (column_types, keys)
             }
+            TableFunc::TumbleTimestamp | TableFunc::HopTimestamp => {
+                let column_types = vec![
+                    ScalarType::Timestamp.nullable(false),
+                    ScalarType::Timestamp.nullable(false),
+                ];
+                let keys = vec![vec![0], vec![1]];
+                (column_types, keys)
+            }
+            TableFunc::TumbleTimestampTz | TableFunc::HopTimestampTz => {
+                let column_types = vec![
+                    ScalarType::TimestampTz.nullable(false),
+                    ScalarType::TimestampTz.nullable(false),
+                ];
+                let keys = vec![vec![0], vec![1]];
+                (column_types, keys)
+            }
             TableFunc::GenerateSubscriptsArray => {
                 let column_types = vec![ScalarType::Int32.nullable(false)];
                 let keys = vec![vec![0]];
@@ -2345,6 +2804,10 @@ impl TableFunc {
             TableFunc::GenerateSeriesInt64 => 1,
             TableFunc::GenerateSeriesTimestamp => 1,
             TableFunc::GenerateSeriesTimestampTz => 1,
+            TableFunc::TumbleTimestamp => 2,
+            TableFunc::TumbleTimestampTz => 2,
+            TableFunc::HopTimestamp => 2,
+            TableFunc::HopTimestampTz => 2,
             TableFunc::GenerateSubscriptsArray => 1,
             TableFunc::Repeat => 0,
             TableFunc::UnnestArray { .. } => 1,
@@ -2365,6 +2828,10 @@ impl TableFunc {
             | TableFunc::GenerateSeriesInt64
             | TableFunc::GenerateSeriesTimestamp
             | TableFunc::GenerateSeriesTimestampTz
+            | TableFunc::TumbleTimestamp
+            | TableFunc::TumbleTimestampTz
+            | TableFunc::HopTimestamp
+            | TableFunc::HopTimestampTz
             | TableFunc::GenerateSubscriptsArray
             | TableFunc::RegexpExtract(_)
             | TableFunc::CsvExtract(_)
@@ -2392,6 +2859,10 @@ impl TableFunc {
             TableFunc::GenerateSeriesInt64 => true,
             TableFunc::GenerateSeriesTimestamp => true,
             TableFunc::GenerateSeriesTimestampTz => true,
+            TableFunc::TumbleTimestamp => true,
+            TableFunc::TumbleTimestampTz => true,
+            TableFunc::HopTimestamp => true,
+            TableFunc::HopTimestampTz => true,
             TableFunc::GenerateSubscriptsArray => true,
             TableFunc::Repeat => false,
             TableFunc::UnnestArray { .. } => true,
@@ -2416,6 +2887,10 @@ impl fmt::Display for TableFunc {
             TableFunc::GenerateSeriesInt64 => f.write_str("generate_series"),
             TableFunc::GenerateSeriesTimestamp => f.write_str("generate_series"),
             TableFunc::GenerateSeriesTimestampTz => f.write_str("generate_series"),
+            TableFunc::TumbleTimestamp => f.write_str("tumble"),
+            TableFunc::TumbleTimestampTz => f.write_str("tumble"),
+            TableFunc::HopTimestamp => f.write_str("hop"),
+            TableFunc::HopTimestampTz => f.write_str("hop"),
             TableFunc::GenerateSubscriptsArray => f.write_str("generate_subscripts"),
             TableFunc::Repeat => f.write_str("repeat_row"),
             TableFunc::UnnestArray { .. } => f.write_str("unnest_array"),
@@ -443,6 +443,44 @@ impl JoinInputMapper {
             }
         }
     }
+
+    /// Returns the pairs of expressions on which input `index` of a left outer join is matched
+    /// against the first input (see [`crate::JoinKind::LeftOuter`]).
+    ///
+    /// The first expression of each pair is in the global context and references only the first
+    /// input (or no input at all), and the second expression is in the local context of input
+    /// `index`. A record of input `index` matches a record of the first input iff the expressions
+    /// of each pair evaluate to equal values.
+    pub fn outer_join_keys(
+        &self,
+        equivalences: &[Vec<MirScalarExpr>],
+        index: usize,
+    ) -> Vec<(MirScalarExpr, MirScalarExpr)> {
+        let mut keys = Vec::new();
+        for equivalence in equivalences {
+            let (local, preserved): (Vec<_>, Vec<_>) = equivalence
+                .iter()
+                .filter(|expr| {
+                    self.is_localized(expr, index) || self.lookup_inputs(expr).all(|i| i == 0)
+                })
+                .partition(|expr| self.is_localized(expr, index));
+            if let (Some(first_local), Some(first_preserved)) = (local.first(), preserved.first()) {
+                for expr in preserved.iter() {
+                    keys.push((
+                        (*expr).clone(),
+                        self.map_expr_to_local((*first_local).clone()),
+                    ));
+                }
+                for expr in local.iter().skip(1) {
+                    keys.push((
+                        (*first_preserved).clone(),
+                        self.map_expr_to_local((*expr).clone()),
+                    ));
+                }
+            }
+        }
+        keys
+    }
 }
 
 #[cfg(test)]
@@ -206,6 +206,11 @@ pub enum MirRelationExpr {
         /// Join implementation information.
         #[serde(default)]
         implementation: JoinImplementation,
+        /// Whether the join also produces records for which some inputs have no match.
+        ///
+        /// See [`JoinKind`] for the restrictions that outer joins place on `equivalences`.
+        #[serde(default, skip_serializing_if = "JoinKind::is_inner")]
+        kind: JoinKind,
     },
     /// Group a dataflow by some columns and aggregate over each group
     ///
@@ -433,6 +438,18 @@ impl MirRelationExpr {
                 }
                 result
             }
+            Join {
+                kind: JoinKind::LeftOuter,
+                ..
+            } => {
+                // Concatenate input column types, where the columns of all but the first
+                // input are null in records without a match.
+                let mut types = input_types.next().unwrap().clone();
+                for cols in input_types {
+                    types.extend(cols.iter().map(|typ| typ.clone().nullable(true)));
+                }
+                types
+            }
             Join { equivalences, .. } => {
                 // Concatenate input column types
                 let mut types = input_types.flat_map(|cols| cols.to_owned()).collect_vec();
@@ -736,6 +753,37 @@ impl MirRelationExpr {
                     input
                 }
             }
+            Join {
+                equivalences,
+                kind: JoinKind::LeftOuter,
+                ..
+            } => {
+                let input_mapper = crate::JoinInputMapper::new_from_input_arities(input_arities);
+                let preserved_keys = input_keys.next().unwrap().clone();
+
+                // Each record of the first input produces exactly one output record if every
+                // other input is looked up by one of its keys, and the keys of the first input
+                // are then keys of the output. Otherwise, we do not know of any keys.
+                let at_most_one_match = input_keys.enumerate().all(|(index, keys)| {
+                    let input = index + 1;
+                    keys.iter().any(|key| {
+                        key.iter().all(|column| {
+                            let column = input_mapper.map_column_to_global(*column, input);
+                            equivalences.iter().any(|class| {
+                                class.contains(&MirScalarExpr::Column(column))
+                                    && class
+                                        .iter()
+                                        .any(|expr| input_mapper.single_input(expr) == Some(0))
+                            })
+                        })
+                    })
+                });
+                if at_most_one_match {
+                    preserved_keys
+                } else {
+                    Vec::new()
+                }
+            }
             Join { equivalences, .. } => {
                 // It is important the `new_from_input_arities` constructor is
                 // used. Otherwise, Materialize may potentially end up in an
@@ -1129,7 +1177,12 @@ impl MirRelationExpr {
             self
         } else if self.is_constant_singleton() {
             right
-        } else if let MirRelationExpr::Join { inputs, .. } = &mut self {
+        } else if let MirRelationExpr::Join {
+            inputs,
+            kind: JoinKind::Inner,
+            ..
+        } = &mut self
+        {
             inputs.push(right);
             self
         } else {
@@ -1206,6 +1259,24 @@ impl MirRelationExpr {
             inputs,
             equivalences,
             implementation: JoinImplementation::Unimplemented,
+            kind: JoinKind::Inner,
+        }
+    }
+
+    /// Constructs a left outer join of `inputs[0]` with each of the other inputs.
+    ///
+    /// The first input is joined with each other input independently, and its records are
+    /// produced with nulls in place of the columns of inputs that have no matching records.
+    /// See [`JoinKind::LeftOuter`] for the restrictions on `equivalences`.
+    pub fn left_outer_join_scalars(
+        inputs: Vec<MirRelationExpr>,
+        equivalences: Vec<Vec<MirScalarExpr>>,
+    ) -> Self {
+        MirRelationExpr::Join {
+            inputs,
+            equivalences,
+            implementation: JoinImplementation::Unimplemented,
+            kind: JoinKind::LeftOuter,
         }
     }
 
@@ -2323,8 +2394,19 @@ impl AggregateExpr {
             | AggregateFunc::MinTimestampTz
             | AggregateFunc::Any
             | AggregateFunc::All
+            | AggregateFunc::BitAndInt16
+            | AggregateFunc::BitAndInt32
+            | AggregateFunc::BitAndInt64
+            | AggregateFunc::BitOrInt16
+            | AggregateFunc::BitOrInt32
+            | AggregateFunc::BitOrInt64
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
             | AggregateFunc::Dummy => self.expr.is_literal(),
-            AggregateFunc::Count => self.expr.is_literal_null(),
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => {
+                self.expr.is_literal_null()
+            }
             _ => self.expr.is_literal_err(),
         }
     }
@@ -2333,7 +2415,7 @@ impl AggregateExpr {
     pub fn on_unique(&self, input_type: &[ColumnType]) -> MirScalarExpr {
         match &self.func {
             // Count is one if non-null, and zero if null.
-            AggregateFunc::Count => self
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => self
                 .expr
                 .clone()
                 .call_unary(UnaryFunc::IsNull(crate::func::IsNull))
@@ -2619,6 +2701,19 @@ impl AggregateExpr {
             | AggregateFunc::SumNumeric
             | AggregateFunc::Any
             | AggregateFunc::All
+            | AggregateFunc::Xor
+            | AggregateFunc::BitAndInt16
+            | AggregateFunc::BitAndInt32
+            | AggregateFunc::BitAndInt64
+            | AggregateFunc::BitOrInt16
+            | AggregateFunc::BitOrInt32
+            | AggregateFunc::BitOrInt64
+            | AggregateFunc::BitXorInt16
+            | AggregateFunc::BitXorInt32
+            | AggregateFunc::BitXorInt64
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
             | AggregateFunc::Dummy => self.expr.clone(),
         }
     }
@@ -2688,6 +2783,44 @@ impl AggregateExpr {
     }
 }
 
+/// Describes which records a [`MirRelationExpr::Join`] produces.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    Hash,
+    MzReflect,
+)]
+pub enum JoinKind {
+    /// Produce the combinations of input records for which all equivalences hold.
+    #[default]
+    Inner,
+    /// Produce each record of the first input combined with the matching records of each other
+    /// input, where an input without matching records contributes a single record of nulls.
+    ///
+    /// The other inputs are matched independently of each other, and only against the first
+    /// input: a record of input `i > 0` matches a record of the first input if, in each
+    /// equivalence class that references input `i`, the expressions of both inputs evaluate to
+    /// equal values. Consequently, each expression of `equivalences` must reference a single
+    /// input, and each equivalence class must contain an expression of the first input and of
+    /// at least one other input.
+    LeftOuter,
+}
+
+impl JoinKind {
+    /// Returns `true` iff the value is [`JoinKind::Inner`].
+    pub fn is_inner(&self) -> bool {
+        matches!(self, JoinKind::Inner)
+    }
+}
+
 /// Describe a join implementation in dataflow.
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash, MzReflect)]
 pub enum JoinImplementation {
@@ -2778,6 +2911,10 @@ pub struct JoinInputCharacteristics {
     pub filters: FilterCharacteristics,
     /// We want to prefer input earlier in the input list, for stability of ordering.
     pub input: std::cmp::Reverse<usize>,
+    /// Estimated cost of the join order up to and including this input, if the order was
+    /// chosen by its cost. This is only for EXPLAIN, and comes after `input` so that it does not
+    /// affect the comparison of characteristics.
+    pub cost: Option<std::cmp::Reverse<usize>>,
 }
 
 impl JoinInputCharacteristics {
@@ -2797,6 +2934,7 @@ impl JoinInputCharacteristics {
             cardinality: cardinality.map(std::cmp::Reverse),
             filters,
             input: std::cmp::Reverse(input),
+            cost: None,
         }
     }
 
@@ -2816,6 +2954,9 @@ impl JoinInputCharacteristics {
             e.push_str(&format!("|{cardinality}|"));
         }
         e.push_str(&self.filters.explain());
+        if let Some(std::cmp::Reverse(cost)) = self.cost {
+            e.push_str(&format!("~{cost}"));
+        }
         e
     }
 }
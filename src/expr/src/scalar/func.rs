@@ -4410,6 +4410,7 @@ derive_unary!(
     CastNumericToUint64,
     CastNumericToString,
     CastMzTimestampToString,
+    CastMzTimestampToTimestampTz,
     CastStringToMzTimestamp,
     CastUint64ToMzTimestamp,
     CastUint32ToMzTimestamp,
@@ -5327,6 +5328,7 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::MzRowSize(_) => MzRowSize(()),
             UnaryFunc::MzTypeName(_) => MzTypeName(()),
             UnaryFunc::CastMzTimestampToString(_) => CastMzTimestampToString(()),
+            UnaryFunc::CastMzTimestampToTimestampTz(_) => CastMzTimestampToTimestampTz(()),
             UnaryFunc::CastStringToMzTimestamp(_) => CastStringToMzTimestamp(()),
             UnaryFunc::CastUint64ToMzTimestamp(_) => CastUint64ToMzTimestamp(()),
             UnaryFunc::CastUint32ToMzTimestamp(_) => CastUint32ToMzTimestamp(()),
@@ -5758,6 +5760,9 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                 MzTypeName(()) => Ok(impls::MzTypeName.into()),
 
                 CastMzTimestampToString(()) => Ok(impls::CastMzTimestampToString.into()),
+                CastMzTimestampToTimestampTz(()) => {
+                    Ok(impls::CastMzTimestampToTimestampTz.into())
+                }
                 CastStringToMzTimestamp(()) => Ok(impls::CastStringToMzTimestamp.into()),
                 CastUint64ToMzTimestamp(()) => Ok(impls::CastUint64ToMzTimestamp.into()),
                 CastUint32ToMzTimestamp(()) => Ok(impls::CastUint32ToMzTimestamp.into()),
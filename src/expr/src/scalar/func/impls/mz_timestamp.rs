@@ -15,9 +15,11 @@ use mz_repr::{strconv, Timestamp};
 
 use crate::EvalError;
 
-// Conversions to MzTimestamp, and a single conversion from MzTimestamp to
-// String. In general we want to make MzTimestamp a more opaque type, so we
-// easily support casting things to it but not from it.
+// Conversions to MzTimestamp, and conversions from MzTimestamp to String and
+// TimestampTz. In general we want to make MzTimestamp a more opaque type, so we
+// easily support casting things to it but not from it. The conversion to
+// TimestampTz exists so that temporal filters can bucket `mz_now()` with
+// `date_trunc` and `date_bin`.
 
 sqlfunc!(
     #[sqlname = "mz_timestamp_to_text"]
@@ -30,6 +32,20 @@ sqlfunc!(
     }
 );
 
+sqlfunc!(
+    #[sqlname = "mz_timestamp_to_timestamp_tz"]
+    #[is_monotone = true]
+    fn cast_mz_timestamp_to_timestamp_tz(
+        a: Timestamp,
+    ) -> Result<CheckedTimestamp<DateTime<Utc>>, EvalError> {
+        let millis = i64::try_from(u64::from(a)).map_err(|_| EvalError::TimestampOutOfRange)?;
+        let dt = NaiveDateTime::from_timestamp_millis(millis)
+            .ok_or(EvalError::TimestampOutOfRange)?;
+        CheckedTimestamp::from_timestamplike(DateTime::from_utc(dt, Utc))
+            .map_err(|_| EvalError::TimestampOutOfRange)
+    }
+);
+
 sqlfunc!(
     #[sqlname = "text_to_mz_timestamp"]
     #[preserves_uniqueness = false]
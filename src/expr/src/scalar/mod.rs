@@ -1808,6 +1808,13 @@ impl MirScalarExpr {
         contains
     }
 
+    /// True iff the expression is a temporal predicate that only lower-bounds `MzNow`.
+    ///
+    /// Records that pass such a predicate are never retracted by it, as time advances.
+    pub fn is_temporal_lower_bound(&self) -> bool {
+        self.contains_temporal() && crate::linear::plan::is_temporal_lower_bound(self)
+    }
+
     /// True iff the expression contains an `UnmaterializableFunc`.
     pub fn contains_unmaterializable(&self) -> bool {
         let mut contains = false;
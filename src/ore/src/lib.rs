@@ -128,6 +128,9 @@ pub mod panic;
 pub mod path;
 pub mod permutations;
 pub mod process;
+#[cfg_attr(nightly_doc_features, doc(cfg(feature = "region")))]
+#[cfg(feature = "region")]
+pub mod region;
 #[cfg_attr(nightly_doc_features, doc(cfg(feature = "process")))]
 pub mod result;
 #[cfg_attr(nightly_doc_features, doc(cfg(feature = "async")))]
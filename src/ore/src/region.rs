@@ -0,0 +1,347 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Region allocation that can spill to disk.
+//!
+//! [`SpillRegion`] mirrors the stable region from the `columnation` crate: it
+//! hands out slices whose addresses never change, backed by a list of
+//! fixed-capacity chunks. When spilling is enabled via [`set_spill_config`],
+//! large chunks are allocated in memory-mapped files in a scratch directory
+//! rather than on the heap. The operating system can then write cold pages of
+//! these chunks back to disk under memory pressure and fault them in again on
+//! access, which bounds the resident memory of large regions without any
+//! explicit eviction logic.
+//!
+//! Spilling is configured per thread. A process can host several components
+//! that use the same region types, like the compute and storage workers of a
+//! `clusterd`, and only the threads that opt in allocate file-backed chunks.
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use memmap2::MmapMut;
+
+use crate::cast::CastFrom;
+
+thread_local! {
+    /// The spill configuration of the current thread.
+    static SPILL_CONFIG: RefCell<Option<SpillConfig>> = RefCell::new(None);
+}
+
+/// The number of bytes in file-backed chunks across all threads of the process.
+static SPILLED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Configuration for spilling region allocations to disk.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpillConfig {
+    /// The directory in which to create the files backing spilled chunks.
+    pub path: PathBuf,
+    /// The minimum size in bytes of a chunk for it to be backed by a file.
+    ///
+    /// Smaller chunks are always allocated on the heap, as the cost of
+    /// creating and mapping a file would dominate.
+    pub min_chunk_bytes: usize,
+}
+
+/// Enables spilling region allocations to disk if `config` is `Some`, and
+/// disables it otherwise.
+///
+/// The configuration applies to all chunks allocated by the calling thread
+/// after the call returns. Other threads are not affected, and existing
+/// chunks retain their backing.
+pub fn set_spill_config(config: Option<SpillConfig>) {
+    SPILL_CONFIG.with(|c| *c.borrow_mut() = config);
+}
+
+/// Returns the spill configuration of the calling thread, or `None` if
+/// spilling is disabled.
+pub fn spill_config() -> Option<SpillConfig> {
+    SPILL_CONFIG.with(|c| c.borrow().clone())
+}
+
+/// Returns the number of bytes currently allocated in file-backed chunks by
+/// all threads of the process.
+pub fn spilled_bytes() -> usize {
+    SPILLED_BYTES.load(Ordering::Relaxed)
+}
+
+/// A stable region of `Copy` items that can spill large chunks to disk.
+///
+/// See the [module documentation](self) for details.
+pub struct SpillRegion<T> {
+    /// The chunk that is currently being filled.
+    local: Chunk<T>,
+    /// Filled chunks, kept around so that previously returned slices remain
+    /// valid.
+    stash: Vec<Chunk<T>>,
+    /// The maximum number of items in a chunk, unless a single copy requires
+    /// more.
+    limit: usize,
+}
+
+impl<T> Default for SpillRegion<T> {
+    fn default() -> Self {
+        Self {
+            local: Chunk::Heap(Vec::new()),
+            stash: Vec::new(),
+            limit: usize::MAX,
+        }
+    }
+}
+
+impl<T: Copy> SpillRegion<T> {
+    /// Constructs a region whose chunks hold at most `limit` items, unless a
+    /// single copy requires more.
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            limit,
+            ..Default::default()
+        }
+    }
+
+    /// Clears the contents of the region, without releasing the current
+    /// chunk.
+    ///
+    /// Slices previously returned by [`SpillRegion::copy_slice`] must no
+    /// longer be used.
+    pub fn clear(&mut self) {
+        self.local.clear();
+        self.stash.clear();
+    }
+
+    /// Copies `items` into the region and returns a mutable reference to the
+    /// copy.
+    ///
+    /// The returned slice remains at the same address until the region is
+    /// cleared or dropped.
+    pub fn copy_slice(&mut self, items: &[T]) -> &mut [T] {
+        self.reserve(items.len());
+        self.local.extend_from_slice(items)
+    }
+
+    /// Ensures that there is space in the current chunk for at least `count`
+    /// items, allocating a new chunk if necessary.
+    pub fn reserve(&mut self, count: usize) {
+        if count > self.local.capacity() - self.local.len() {
+            let mut next_len = (self.local.capacity() + 1).next_power_of_two();
+            next_len = std::cmp::min(next_len, self.limit);
+            next_len = std::cmp::max(count, next_len);
+            let new_local = Chunk::with_capacity(next_len);
+            if self.local.len() == 0 {
+                self.local = new_local;
+            } else {
+                self.stash
+                    .push(std::mem::replace(&mut self.local, new_local));
+            }
+        }
+    }
+
+    /// Returns the number of items in the region.
+    pub fn len(&self) -> usize {
+        self.local.len() + self.stash.iter().map(|c| c.len()).sum::<usize>()
+    }
+
+    /// Reports whether the region is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reports the size and capacity in bytes of the region's allocations,
+    /// including those backed by files.
+    pub fn heap_size(&self, mut callback: impl FnMut(usize, usize)) {
+        let size_of_t = size_of::<T>();
+        callback(
+            self.local.len() * size_of_t,
+            self.local.capacity() * size_of_t,
+        );
+        callback(
+            self.stash.len() * size_of::<Chunk<T>>(),
+            self.stash.capacity() * size_of::<Chunk<T>>(),
+        );
+        for chunk in &self.stash {
+            callback(chunk.len() * size_of_t, chunk.capacity() * size_of_t);
+        }
+    }
+
+    /// Returns the number of bytes allocated in file-backed chunks.
+    pub fn spilled_bytes(&self) -> usize {
+        std::iter::once(&self.local)
+            .chain(&self.stash)
+            .filter(|c| matches!(c, Chunk::File { .. }))
+            .map(|c| c.capacity() * size_of::<T>())
+            .sum()
+    }
+}
+
+/// A fixed-capacity allocation of items, either on the heap or in a
+/// memory-mapped file.
+enum Chunk<T> {
+    Heap(Vec<T>),
+    File {
+        /// The mapping of the backing file. The file itself is unlinked, so
+        /// that it is removed once the mapping is dropped.
+        map: MmapMut,
+        /// The number of initialized items.
+        len: usize,
+        /// The number of items the mapping can hold.
+        capacity: usize,
+        _marker: PhantomData<T>,
+    },
+}
+
+impl<T: Copy> Chunk<T> {
+    /// Allocates a chunk for `capacity` items, backed by a file if spilling is
+    /// enabled and the chunk is large enough.
+    ///
+    /// Falls back to the heap if the backing file cannot be created.
+    fn with_capacity(capacity: usize) -> Self {
+        let bytes = capacity.saturating_mul(size_of::<T>());
+        if let Some(config) = spill_config() {
+            if bytes > 0 && bytes >= config.min_chunk_bytes {
+                if let Ok(chunk) = Self::file_backed(&config.path, capacity, bytes) {
+                    return chunk;
+                }
+            }
+        }
+        Chunk::Heap(Vec::with_capacity(capacity))
+    }
+
+    fn file_backed(path: &Path, capacity: usize, bytes: usize) -> std::io::Result<Self> {
+        let file: File = tempfile::tempfile_in(path)?;
+        file.set_len(u64::cast_from(bytes))?;
+        // SAFETY: The file is unlinked and private to this process, so it
+        // cannot be modified by anyone else while it is mapped.
+        let map = unsafe { MmapMut::map_mut(&file)? };
+        SPILLED_BYTES.fetch_add(bytes, Ordering::Relaxed);
+        Ok(Chunk::File {
+            map,
+            len: 0,
+            capacity,
+            _marker: PhantomData,
+        })
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Chunk::Heap(vec) => vec.len(),
+            Chunk::File { len, .. } => *len,
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        match self {
+            Chunk::Heap(vec) => vec.capacity(),
+            Chunk::File { capacity, .. } => *capacity,
+        }
+    }
+
+    fn clear(&mut self) {
+        match self {
+            Chunk::Heap(vec) => vec.clear(),
+            Chunk::File { len, .. } => *len = 0,
+        }
+    }
+
+    /// Appends `items` to the chunk and returns the appended slice.
+    ///
+    /// Panics if the chunk lacks the capacity for `items`.
+    fn extend_from_slice(&mut self, items: &[T]) -> &mut [T] {
+        assert!(items.len() <= self.capacity() - self.len());
+        match self {
+            Chunk::Heap(vec) => {
+                let initial_len = vec.len();
+                vec.extend_from_slice(items);
+                &mut vec[initial_len..]
+            }
+            Chunk::File { map, len, .. } => {
+                // SAFETY: The mapping is page aligned and holds `capacity`
+                // items, of which the first `len` are initialized. We checked
+                // above that `items` fits into the remaining space.
+                let slice = unsafe {
+                    let start = map.as_mut_ptr().cast::<T>().add(*len);
+                    std::ptr::copy_nonoverlapping(items.as_ptr(), start, items.len());
+                    std::slice::from_raw_parts_mut(start, items.len())
+                };
+                *len += items.len();
+                slice
+            }
+        }
+    }
+}
+
+impl<T> Drop for Chunk<T> {
+    fn drop(&mut self) {
+        if let Chunk::File { capacity, .. } = self {
+            SPILLED_BYTES.fetch_sub(*capacity * size_of::<T>(), Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[crate::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `mmap`
+    fn test_spill_region() {
+        let mut region = SpillRegion::<u8>::with_limit(16);
+        let first = region.copy_slice(b"hello").as_ptr();
+        assert_eq!(region.spilled_bytes(), 0);
+
+        let dir = tempfile::tempdir().unwrap();
+        set_spill_config(Some(SpillConfig {
+            path: dir.path().to_owned(),
+            min_chunk_bytes: 8,
+        }));
+
+        // Exceeds the capacity of the first chunk, which moves to the stash.
+        let second = region.copy_slice(b"large enough to spill").to_vec();
+        assert_eq!(second, b"large enough to spill");
+        assert_eq!(region.len(), 26);
+        assert!(region.spilled_bytes() >= 21);
+
+        assert!(spilled_bytes() >= region.spilled_bytes());
+
+        // Previously returned slices remain valid.
+        let first = unsafe { std::slice::from_raw_parts(first, 5) };
+        assert_eq!(first, b"hello");
+
+        // Other threads do not observe the configuration.
+        std::thread::spawn(|| {
+            assert_eq!(spill_config(), None);
+            let mut region = SpillRegion::<u8>::with_limit(16);
+            region.copy_slice(b"large enough to spill");
+            assert_eq!(region.spilled_bytes(), 0);
+        })
+        .join()
+        .unwrap();
+
+        // Chunks smaller than the threshold stay on the heap.
+        let mut small = SpillRegion::<u8>::with_limit(4);
+        small.copy_slice(b"abc");
+        assert_eq!(small.spilled_bytes(), 0);
+
+        // Releasing file-backed chunks releases their accounting.
+        let spilled = region.spilled_bytes();
+        let before = spilled_bytes();
+        drop(region);
+        assert!(spilled_bytes() <= before - spilled);
+        set_spill_config(None);
+    }
+}
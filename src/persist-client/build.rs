@@ -108,6 +108,7 @@ fn main() {
             config,
             &[
                 "persist-client/src/cfg.proto",
+                "persist-client/src/cli/archive.proto",
                 "persist-client/src/internal/service.proto",
                 "persist-client/src/internal/state.proto",
                 "persist-client/src/internal/diff.proto",
@@ -112,6 +112,8 @@ enum Command {
     OpenLoop(crate::open_loop::Args),
     Inspect(mz_persist_client::cli::inspect::InspectArgs),
     Admin(mz_persist_client::cli::admin::AdminArgs),
+    Export(mz_persist_client::cli::archive::ExportArgs),
+    Import(mz_persist_client::cli::archive::ImportArgs),
     Service(crate::service::Args),
 }
 
@@ -166,6 +168,10 @@ fn main() {
         Command::Admin(command) => {
             runtime.block_on(mz_persist_client::cli::admin::run(command).instrument(root_span))
         }
+        Command::Export(args) => runtime
+            .block_on(mz_persist_client::cli::archive::run_export(args).instrument(root_span)),
+        Command::Import(args) => runtime
+            .block_on(mz_persist_client::cli::archive::run_import(args).instrument(root_span)),
         Command::Service(args) => runtime.block_on(crate::service::run(args).instrument(root_span)),
     };
 
@@ -10,17 +10,20 @@
 //! A handle to a batch of updates
 
 use std::collections::VecDeque;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 use std::marker::PhantomData;
 use std::ops::Range;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Instant;
 
+use anyhow::anyhow;
 use bytes::Bytes;
 use differential_dataflow::consolidation::consolidate_updates;
 use differential_dataflow::difference::Semigroup;
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::trace::Description;
+use mz_ore::bytes::SegmentedBytes;
 use mz_ore::cast::CastFrom;
 use mz_persist::indexed::columnar::{ColumnarRecords, ColumnarRecordsBuilder};
 use mz_persist::indexed::encoding::BlobTraceBatchPart;
@@ -28,7 +31,9 @@ use mz_persist::location::{Atomicity, Blob};
 use mz_persist_types::stats::trim_to_budget;
 use mz_persist_types::{Codec, Codec64};
 use mz_timely_util::order::Reverse;
+use proptest_derive::Arbitrary;
 use semver::Version;
+use serde::{Deserialize, Serialize};
 use timely::progress::{Antichain, Timestamp};
 use timely::PartialOrder;
 use tokio::task::JoinHandle;
@@ -193,6 +198,118 @@ pub enum Added {
     RecordAndParts,
 }
 
+/// The compression codec applied to the encoded bytes of a batch part before
+/// it is written to [Blob].
+///
+/// The codec used for each part is recorded in its metadata (see
+/// [HollowBatchPart]), so a shard may freely contain a mix of parts written
+/// with different codecs, including ones written before compression existed.
+#[derive(
+    Arbitrary,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub enum PartCompression {
+    /// The part is stored exactly as encoded.
+    #[default]
+    None,
+    /// The part is compressed as a single zstd frame.
+    Zstd,
+    /// The part is compressed as an lz4 block, prefixed with its uncompressed
+    /// length.
+    Lz4,
+}
+
+impl PartCompression {
+    /// The zstd compression level used for [PartCompression::Zstd].
+    ///
+    /// This is the zstd default, which is a reasonable tradeoff between ratio
+    /// and cpu for the text-heavy parts we expect to see.
+    const ZSTD_LEVEL: i32 = 3;
+
+    /// Returns true if this is [PartCompression::None].
+    pub fn is_none(&self) -> bool {
+        matches!(self, PartCompression::None)
+    }
+
+    /// Compresses the given encoded part with this codec.
+    ///
+    /// Returns the codec that was actually applied along with the resulting
+    /// bytes. If compression fails or doesn't make the part any smaller, the
+    /// part is returned unchanged and recorded as [PartCompression::None].
+    pub(crate) fn compress(self, buf: Vec<u8>) -> (PartCompression, Vec<u8>) {
+        let compressed = match self {
+            PartCompression::None => return (PartCompression::None, buf),
+            PartCompression::Zstd => match zstd::bulk::compress(&buf, Self::ZSTD_LEVEL) {
+                Ok(x) => x,
+                Err(err) => {
+                    warn!(
+                        "failed to zstd compress part, writing it uncompressed: {}",
+                        err
+                    );
+                    return (PartCompression::None, buf);
+                }
+            },
+            PartCompression::Lz4 => lz4_flex::block::compress_prepend_size(&buf),
+        };
+        if compressed.len() >= buf.len() {
+            (PartCompression::None, buf)
+        } else {
+            (self, compressed)
+        }
+    }
+
+    /// Decompresses a part that was written with this codec.
+    pub(crate) fn decompress(self, buf: SegmentedBytes) -> Result<SegmentedBytes, anyhow::Error> {
+        match self {
+            PartCompression::None => Ok(buf),
+            PartCompression::Zstd => {
+                let buf = zstd::stream::decode_all(buf.reader())
+                    .map_err(|err| anyhow!("invalid zstd part: {}", err))?;
+                Ok(SegmentedBytes::from(buf))
+            }
+            PartCompression::Lz4 => {
+                let buf = lz4_flex::block::decompress_size_prepended(&buf.into_contiguous())
+                    .map_err(|err| anyhow!("invalid lz4 part: {}", err))?;
+                Ok(SegmentedBytes::from(buf))
+            }
+        }
+    }
+}
+
+impl FromStr for PartCompression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(PartCompression::None),
+            "zstd" => Ok(PartCompression::Zstd),
+            "lz4" => Ok(PartCompression::Lz4),
+            _ => Err(format!("invalid part compression: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for PartCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            PartCompression::None => "none",
+            PartCompression::Zstd => "zstd",
+            PartCompression::Lz4 => "lz4",
+        };
+        f.write_str(s)
+    }
+}
+
 /// A snapshot of dynamic configs to make it easier to reason about an individual
 /// run of BatchBuilder.
 #[derive(Debug, Clone)]
@@ -202,6 +319,7 @@ pub struct BatchBuilderConfig {
     pub(crate) batch_builder_max_outstanding_parts: usize,
     pub(crate) stats_collection_enabled: bool,
     pub(crate) stats_budget: usize,
+    pub(crate) part_compression: PartCompression,
 }
 
 impl BatchBuilderConfig {
@@ -219,6 +337,7 @@ impl BatchBuilderConfig {
             // the rough upper bound on what we see for the total serialized
             // batch size in prod, so it will at worst double it.
             stats_budget: 1024,
+            part_compression: value.dynamic.part_compression(),
         }
     }
 }
@@ -678,7 +797,10 @@ pub(crate) struct BatchParts<T> {
     lower: Antichain<T>,
     blob: Arc<dyn Blob + Send + Sync>,
     isolated_runtime: Arc<IsolatedRuntime>,
-    writing_parts: VecDeque<(PartialBatchKey, JoinHandle<(usize, Option<LazyPartStats>)>)>,
+    writing_parts: VecDeque<(
+        PartialBatchKey,
+        JoinHandle<(usize, PartCompression, Option<usize>, Option<LazyPartStats>)>,
+    )>,
     finished_parts: Vec<HollowBatchPart>,
     batch_metrics: BatchWriteMetrics,
 }
@@ -735,6 +857,7 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
         let index = u64::cast_from(self.finished_parts.len() + self.writing_parts.len());
         let stats_collection_enabled = self.cfg.stats_collection_enabled;
         let stats_budget = self.cfg.stats_budget;
+        let part_compression = self.cfg.part_compression;
         let schemas = schemas.clone();
 
         let write_span = debug_span!("batch::write_part", shard = %self.shard_id).or_current();
@@ -748,7 +871,7 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
                     index,
                 };
 
-                let (stats, (buf, encode_time)) = isolated_runtime
+                let (stats, (buf, encode_time), compressed) = isolated_runtime
                     .spawn_named(|| "batch::encode_part", async move {
                         let stats = if stats_collection_enabled {
                             let stats_start = Instant::now();
@@ -774,11 +897,21 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
 
                         // Drop batch as soon as we can to reclaim its memory.
                         drop(batch);
-                        (stats, (Bytes::from(buf), encode_start.elapsed()))
+                        let encode_time = encode_start.elapsed();
+
+                        let compress_start = Instant::now();
+                        let uncompressed_len = buf.len();
+                        let (compression, buf) = part_compression.compress(buf);
+                        (
+                            stats,
+                            (Bytes::from(buf), encode_time),
+                            (compression, compress_start.elapsed(), uncompressed_len),
+                        )
                     })
                     .instrument(debug_span!("batch::encode_part"))
                     .await
                     .expect("part encode task failed");
+                let (compression, compress_time, uncompressed_len) = compressed;
                 // Can't use the `CodecMetrics::encode` helper because of async.
                 metrics.codecs.batch.encode_count.inc();
                 metrics
@@ -787,8 +920,17 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
                     .encode_seconds
                     .inc_by(encode_time.as_secs_f64());
 
-                let start = Instant::now();
                 let payload_len = buf.len();
+                if !part_compression.is_none() {
+                    metrics.compression.record_compress(
+                        compression,
+                        uncompressed_len,
+                        payload_len,
+                        compress_time,
+                    );
+                }
+
+                let start = Instant::now();
                 let () = retry_external(&metrics.retries.external.batch_set, || async {
                     shard_metrics.blob_sets.inc();
                     blob.set(&key, Bytes::clone(&buf), Atomicity::RequireAtomic)
@@ -805,7 +947,10 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
                         .inc_by(stats_step_timing.as_secs_f64());
                     stats
                 });
-                (payload_len, stats)
+                // Compaction and reads budget memory with the size of the
+                // decompressed part, so record both sizes.
+                let compressed_size_bytes = (!compression.is_none()).then_some(payload_len);
+                (uncompressed_len, compression, compressed_size_bytes, stats)
             }
             .instrument(write_span),
         );
@@ -817,17 +962,19 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
                 .writing_parts
                 .pop_front()
                 .expect("pop failed when len was just > some usize");
-            let (encoded_size_bytes, stats) = match handle
+            let (encoded_size_bytes, compression, compressed_size_bytes, stats) = match handle
                 .instrument(debug_span!("batch::max_outstanding"))
                 .await
             {
                 Ok(x) => x,
-                Err(err) if err.is_cancelled() => (0, None),
+                Err(err) if err.is_cancelled() => (0, PartCompression::None, None, None),
                 Err(err) => panic!("part upload task failed: {}", err),
             };
             self.finished_parts.push(HollowBatchPart {
                 key,
                 encoded_size_bytes,
+                compression,
+                compressed_size_bytes,
                 stats,
             });
         }
@@ -837,14 +984,17 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
     pub(crate) async fn finish(self) -> Vec<HollowBatchPart> {
         let mut parts = self.finished_parts;
         for (key, handle) in self.writing_parts {
-            let (encoded_size_bytes, stats) = match handle.await {
+            let (encoded_size_bytes, compression, compressed_size_bytes, stats) = match handle.await
+            {
                 Ok(x) => x,
-                Err(err) if err.is_cancelled() => (0, None),
+                Err(err) if err.is_cancelled() => (0, PartCompression::None, None, None),
                 Err(err) => panic!("part upload task failed: {}", err),
             };
             parts.push(HollowBatchPart {
                 key,
                 encoded_size_bytes,
+                compression,
+                compressed_size_bytes,
                 stats,
             });
         }
@@ -871,8 +1021,12 @@ pub(crate) fn validate_truncate_batch<T: Timestamp>(
 
 #[cfg(test)]
 mod tests {
+    use mz_proto::RustType;
+    use prost::Message;
+
     use crate::cache::PersistClientCache;
     use crate::internal::paths::{BlobKey, PartialBlobKey};
+    use crate::internal::state::ProtoHollowBatchPart;
     use crate::tests::{all_ok, CodecProduct};
     use crate::PersistLocation;
 
@@ -971,6 +1125,140 @@ mod tests {
         assert_eq!(read.expect_snapshot_and_fetch(3).await, all_ok(&data, 3));
     }
 
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn batch_builder_compression() {
+        let cache = PersistClientCache::new_no_metrics();
+        let client = cache
+            .open(PersistLocation {
+                blob_uri: "mem://".to_owned(),
+                consensus_uri: "mem://".to_owned(),
+            })
+            .await
+            .expect("client construction failed");
+        let (mut write, mut read) = client
+            .expect_open::<String, String, u64, i64>(ShardId::new())
+            .await;
+
+        // Write one batch with each codec so that readers have to deal with a
+        // shard containing a mix of them. The values are intentionally very
+        // compressible so that every codec is actually applied.
+        let codecs = [
+            PartCompression::None,
+            PartCompression::Zstd,
+            PartCompression::Lz4,
+        ];
+        let mut data = Vec::new();
+        for (idx, compression) in codecs.into_iter().enumerate() {
+            cache.cfg.dynamic.set_part_compression(compression);
+            let ts = u64::cast_from(idx);
+            let updates = (0..100)
+                .map(|x| ((format!("{}-{}", idx, x), "v".repeat(1024)), ts, 1))
+                .collect::<Vec<_>>();
+            let batch = write.expect_batch(&updates, ts, ts + 1).await;
+            assert_eq!(batch.batch.parts.len(), 1);
+            let part = &batch.batch.parts[0];
+            assert_eq!(part.compression, compression);
+            // The encoded size is that of the decompressed part, which is what
+            // compaction budgets memory with.
+            match part.compressed_size_bytes {
+                None => assert!(compression.is_none()),
+                Some(compressed_size_bytes) => {
+                    assert!(compressed_size_bytes < part.encoded_size_bytes)
+                }
+            }
+            write
+                .append_batch(
+                    batch,
+                    Antichain::from_elem(ts),
+                    Antichain::from_elem(ts + 1),
+                )
+                .await
+                .expect("invalid usage")
+                .expect("unexpected upper");
+            data.extend(updates);
+        }
+
+        let as_of = u64::cast_from(codecs.len() - 1);
+        assert_eq!(
+            read.expect_snapshot_and_fetch(as_of).await,
+            all_ok(&data, as_of)
+        );
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn batch_builder_compression_reads_old_parts() {
+        let cache = PersistClientCache::new_no_metrics();
+        let client = cache
+            .open(PersistLocation {
+                blob_uri: "mem://".to_owned(),
+                consensus_uri: "mem://".to_owned(),
+            })
+            .await
+            .expect("client construction failed");
+        let (mut write, mut read) = client
+            .expect_open::<String, String, u64, i64>(ShardId::new())
+            .await;
+
+        // Parts written before compression existed are stored as encoded, and
+        // their metadata has neither a codec nor a compressed size. Write an
+        // uncompressed part and replace its metadata with what an old version
+        // would have written: only the key and the encoded size.
+        let old_data = vec![(("1".to_owned(), "one".to_owned()), 0, 1)];
+        let mut batch = write.expect_batch(&old_data, 0, 1).await;
+        for part in batch.batch.parts.iter_mut() {
+            let mut buf = Vec::new();
+            prost::encoding::string::encode(1, &part.key.0, &mut buf);
+            prost::encoding::uint64::encode(2, &u64::cast_from(part.encoded_size_bytes), &mut buf);
+            let proto = ProtoHollowBatchPart::decode(buf.as_slice()).expect("valid proto");
+            *part = proto.into_rust().expect("valid part");
+            assert_eq!(part.compression, PartCompression::None);
+            assert_eq!(part.compressed_size_bytes, None);
+        }
+        write
+            .append_batch(batch, Antichain::from_elem(0), Antichain::from_elem(1))
+            .await
+            .expect("invalid usage")
+            .expect("unexpected upper");
+
+        // Parts written with compression enabled are readable alongside them.
+        cache
+            .cfg
+            .dynamic
+            .set_part_compression(PartCompression::Zstd);
+        let new_data = (0..100)
+            .map(|x| ((format!("{}", x), "v".repeat(1024)), 1, 1))
+            .collect::<Vec<_>>();
+        write.expect_compare_and_append(&new_data, 1, 2).await;
+
+        let data = old_data.into_iter().chain(new_data).collect::<Vec<_>>();
+        assert_eq!(read.expect_snapshot_and_fetch(1).await, all_ok(&data, 1));
+    }
+
+    #[mz_ore::test]
+    fn part_compression_roundtrip() {
+        let compressible = "abc".repeat(1024).into_bytes();
+        let incompressible = (0..=255u8).collect::<Vec<_>>();
+        for compression in [
+            PartCompression::None,
+            PartCompression::Zstd,
+            PartCompression::Lz4,
+        ] {
+            let (applied, buf) = compression.compress(compressible.clone());
+            assert_eq!(applied, compression);
+            let buf = applied
+                .decompress(SegmentedBytes::from(buf))
+                .expect("valid part");
+            assert_eq!(buf.into_contiguous(), compressible);
+
+            // Compression that doesn't help is skipped, and recorded as such.
+            let (applied, buf) = compression.compress(incompressible.clone());
+            assert_eq!(applied, PartCompression::None);
+            assert_eq!(buf, incompressible);
+        }
+    }
+
     #[mz_ore::test(tokio::test)]
     #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
     async fn batch_builder_keys() {
@@ -33,6 +33,7 @@ use tracing::{debug, instrument};
 
 use crate::async_runtime::IsolatedRuntime;
 use crate::error::{CodecConcreteType, CodecMismatch};
+use crate::internal::compact_scheduler::{CompactionQueueEntry, CompactionScheduler};
 use crate::internal::machine::retry_external;
 use crate::internal::metrics::{LockMetrics, Metrics, MetricsBlob, MetricsConsensus, ShardMetrics};
 use crate::internal::state::TypedState;
@@ -57,6 +58,7 @@ pub struct PersistClientCache {
     isolated_runtime: Arc<IsolatedRuntime>,
     pub(crate) state_cache: Arc<StateCache>,
     pubsub_sender: Arc<dyn PubSubSender>,
+    compaction_scheduler: CompactionScheduler,
     _pubsub_receiver_task: JoinHandle<()>,
 }
 
@@ -88,6 +90,8 @@ impl PersistClientCache {
             pubsub_client.receiver,
         );
 
+        let compaction_scheduler = CompactionScheduler::new(&cfg, Arc::clone(&metrics));
+
         PersistClientCache {
             cfg,
             metrics,
@@ -96,6 +100,7 @@ impl PersistClientCache {
             isolated_runtime: Arc::new(IsolatedRuntime::new()),
             state_cache,
             pubsub_sender: pubsub_client.sender,
+            compaction_scheduler,
             _pubsub_receiver_task,
         }
     }
@@ -124,7 +129,7 @@ impl PersistClientCache {
     pub async fn open(&self, location: PersistLocation) -> Result<PersistClient, ExternalError> {
         let blob = self.open_blob(location.blob_uri).await?;
         let consensus = self.open_consensus(location.consensus_uri).await?;
-        PersistClient::new(
+        let mut client = PersistClient::new(
             self.cfg.clone(),
             blob,
             consensus,
@@ -132,7 +137,15 @@ impl PersistClientCache {
             Arc::clone(&self.isolated_runtime),
             Arc::clone(&self.state_cache),
             Arc::clone(&self.pubsub_sender),
-        )
+        )?;
+        client.compaction_scheduler = self.compaction_scheduler.clone();
+        Ok(client)
+    }
+
+    /// Returns the compaction requests that are currently queued or running
+    /// in this process, in the order they will be granted.
+    pub fn compaction_queue(&self) -> Vec<CompactionQueueEntry> {
+        self.compaction_scheduler.queue()
     }
 
     // No sense in measuring rtt latencies more often than this.
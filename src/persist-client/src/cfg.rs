@@ -24,6 +24,8 @@ use proptest_derive::Arbitrary;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 
+use crate::batch::PartCompression;
+
 include!(concat!(env!("OUT_DIR"), "/mz_persist_client.cfg.rs"));
 
 /// The tunable knobs for persist.
@@ -92,6 +94,13 @@ pub struct PersistConfig {
     /// In Compactor::compact_and_apply_background, the maximum number of concurrent
     /// compaction requests that can execute for a given shard.
     pub compaction_concurrency_limit: usize,
+    /// The maximum number of concurrent compaction requests that can execute
+    /// in this process, across all shards.
+    pub compaction_process_concurrency_limit: usize,
+    /// The total memory that concurrently executing compaction requests in this
+    /// process may reserve. A request that needs more than this on its own is
+    /// still run, but only when no other compaction is executing.
+    pub compaction_memory_budget_bytes: usize,
     /// In Compactor::compact_and_apply_background, the maximum number of pending
     /// compaction requests to queue.
     pub compaction_queue_size: usize,
@@ -162,6 +171,7 @@ impl PersistConfig {
                     Self::DEFAULT_SINK_MINIMUM_BATCH_UPDATES,
                 ),
                 next_listen_batch_retryer: RwLock::new(Self::DEFAULT_NEXT_LISTEN_BATCH_RETRYER),
+                part_compression: RwLock::new(Self::DEFAULT_PART_COMPRESSION),
                 stats_audit_percent: AtomicUsize::new(Self::DEFAULT_STATS_AUDIT_PERCENT),
                 stats_collection_enabled: AtomicBool::new(Self::DEFAULT_STATS_COLLECTION_ENABLED),
                 stats_filter_enabled: AtomicBool::new(Self::DEFAULT_STATS_FILTER_ENABLED),
@@ -171,6 +181,8 @@ impl PersistConfig {
             }),
             compaction_enabled: !compaction_disabled,
             compaction_concurrency_limit: 5,
+            compaction_process_concurrency_limit: 10,
+            compaction_memory_budget_bytes: 4 * 1024 * MiB,
             compaction_queue_size: 20,
             compaction_yield_after_n_updates: 100_000,
             consensus_connection_pool_max_size: 50,
@@ -237,6 +249,8 @@ impl PersistConfig {
     pub const DEFAULT_CONSENSUS_CONNPOOL_TTL: Duration = Duration::from_secs(300);
     /// Default value for [`DynamicConfig::consensus_connection_pool_ttl_stagger`].
     pub const DEFAULT_CONSENSUS_CONNPOOL_TTL_STAGGER: Duration = Duration::from_secs(6);
+    /// Default value for [`DynamicConfig::part_compression`].
+    pub const DEFAULT_PART_COMPRESSION: PartCompression = PartCompression::None;
     /// Default value for [`DynamicConfig::stats_audit_percent`].
     pub const DEFAULT_STATS_AUDIT_PERCENT: usize = 0;
     /// Default value for [`DynamicConfig::stats_collection_enabled`].
@@ -354,6 +368,7 @@ pub struct DynamicConfig {
     consensus_connection_pool_ttl_stagger: RwLock<Duration>,
     sink_minimum_batch_updates: AtomicUsize,
     storage_sink_minimum_batch_updates: AtomicUsize,
+    part_compression: RwLock<PartCompression>,
     stats_audit_percent: AtomicUsize,
     stats_collection_enabled: AtomicBool,
     stats_filter_enabled: AtomicBool,
@@ -540,6 +555,17 @@ impl DynamicConfig {
             .load(Self::LOAD_ORDERING)
     }
 
+    /// The compression codec applied to newly written batch parts.
+    ///
+    /// Parts record the codec they were written with, so this may be changed
+    /// at any time without affecting the readability of existing data.
+    /// However, processes running a version of persist that predates part
+    /// compression cannot read compressed parts, so this must only be enabled
+    /// once every reader of a shard has been upgraded.
+    pub fn part_compression(&self) -> PartCompression {
+        *self.part_compression.read().expect("lock poisoned")
+    }
+
     /// Percent of filtered data to opt in to correctness auditing.
     pub fn stats_audit_percent(&self) -> usize {
         self.stats_audit_percent.load(Self::LOAD_ORDERING)
@@ -608,6 +634,10 @@ impl DynamicConfig {
         self.batch_builder_max_outstanding_parts
             .store(val, Self::LOAD_ORDERING);
     }
+    #[cfg(test)]
+    pub fn set_part_compression(&self, val: PartCompression) {
+        *self.part_compression.write().expect("lock poisoned") = val;
+    }
     pub fn set_compaction_memory_bound_bytes(&self, val: usize) {
         self.compaction_memory_bound_bytes
             .store(val, Self::LOAD_ORDERING);
@@ -667,6 +697,8 @@ pub struct PersistParameters {
     pub sink_minimum_batch_updates: Option<usize>,
     /// Configures [`PersistConfig::storage_sink_minimum_batch_updates`].
     pub storage_sink_minimum_batch_updates: Option<usize>,
+    /// Configures [`DynamicConfig::part_compression`].
+    pub part_compression: Option<PartCompression>,
     /// Configures [`DynamicConfig::stats_audit_percent`].
     pub stats_audit_percent: Option<usize>,
     /// Configures [`DynamicConfig::stats_collection_enabled`].
@@ -697,6 +729,7 @@ impl PersistParameters {
             sink_minimum_batch_updates: self_sink_minimum_batch_updates,
             storage_sink_minimum_batch_updates: self_storage_sink_minimum_batch_updates,
             next_listen_batch_retryer: self_next_listen_batch_retryer,
+            part_compression: self_part_compression,
             stats_audit_percent: self_stats_audit_percent,
             stats_collection_enabled: self_stats_collection_enabled,
             stats_filter_enabled: self_stats_filter_enabled,
@@ -715,6 +748,7 @@ impl PersistParameters {
             sink_minimum_batch_updates: other_sink_minimum_batch_updates,
             storage_sink_minimum_batch_updates: other_storage_sink_minimum_batch_updates,
             next_listen_batch_retryer: other_next_listen_batch_retryer,
+            part_compression: other_part_compression,
             stats_audit_percent: other_stats_audit_percent,
             stats_collection_enabled: other_stats_collection_enabled,
             stats_filter_enabled: other_stats_filter_enabled,
@@ -752,6 +786,9 @@ impl PersistParameters {
         if let Some(v) = other_next_listen_batch_retryer {
             *self_next_listen_batch_retryer = Some(v);
         }
+        if let Some(v) = other_part_compression {
+            *self_part_compression = Some(v)
+        }
         if let Some(v) = other_stats_audit_percent {
             *self_stats_audit_percent = Some(v)
         }
@@ -789,6 +826,7 @@ impl PersistParameters {
             sink_minimum_batch_updates,
             storage_sink_minimum_batch_updates,
             next_listen_batch_retryer,
+            part_compression,
             stats_audit_percent,
             stats_collection_enabled,
             stats_filter_enabled,
@@ -806,6 +844,7 @@ impl PersistParameters {
             && sink_minimum_batch_updates.is_none()
             && storage_sink_minimum_batch_updates.is_none()
             && next_listen_batch_retryer.is_none()
+            && part_compression.is_none()
             && stats_audit_percent.is_none()
             && stats_collection_enabled.is_none()
             && stats_filter_enabled.is_none()
@@ -832,6 +871,7 @@ impl PersistParameters {
             sink_minimum_batch_updates,
             storage_sink_minimum_batch_updates,
             next_listen_batch_retryer,
+            part_compression,
             stats_audit_percent,
             stats_collection_enabled,
             stats_filter_enabled,
@@ -908,6 +948,10 @@ impl PersistParameters {
                 .expect("lock poisoned");
             *retry = *retry_params;
         }
+        if let Some(part_compression) = part_compression {
+            let mut compression = cfg.dynamic.part_compression.write().expect("lock poisoned");
+            *compression = *part_compression;
+        }
         if let Some(stats_audit_percent) = stats_audit_percent {
             cfg.dynamic
                 .stats_audit_percent
@@ -958,6 +1002,7 @@ impl RustType<ProtoPersistParameters> for PersistParameters {
                 .storage_sink_minimum_batch_updates
                 .into_proto(),
             next_listen_batch_retryer: self.next_listen_batch_retryer.into_proto(),
+            part_compression: self.part_compression.map(|x| x.to_string()),
             stats_audit_percent: self.stats_audit_percent.into_proto(),
             stats_collection_enabled: self.stats_collection_enabled.into_proto(),
             stats_filter_enabled: self.stats_filter_enabled.into_proto(),
@@ -983,6 +1028,11 @@ impl RustType<ProtoPersistParameters> for PersistParameters {
                 .storage_sink_minimum_batch_updates
                 .into_rust()?,
             next_listen_batch_retryer: proto.next_listen_batch_retryer.into_rust()?,
+            part_compression: proto
+                .part_compression
+                .map(|x| x.parse())
+                .transpose()
+                .map_err(TryFromProtoError::UnknownEnumVariant)?,
             stats_audit_percent: proto.stats_audit_percent.into_rust()?,
             stats_collection_enabled: proto.stats_collection_enabled.into_rust()?,
             stats_filter_enabled: proto.stats_filter_enabled.into_rust()?,
@@ -26,6 +26,7 @@ use mz_persist::location::{
 };
 use mz_persist_types::codec_impls::TodoSchema;
 use prometheus::proto::{MetricFamily, MetricType};
+use timely::progress::Antichain;
 use tracing::{info, warn};
 
 use crate::async_runtime::IsolatedRuntime;
@@ -59,6 +60,10 @@ pub(crate) enum Command {
     ForceCompaction(ForceCompactionArgs),
     /// Manually kick off a GC run for a shard.
     ForceGc(ForceGcArgs),
+    /// Forks a shard into a new one that shares its blobs.
+    Fork(ForkArgs),
+    /// Manually releases the hold a shard has on its blobs for a fork.
+    ReleaseFork(ReleaseForkArgs),
 }
 
 /// Manually completes all fueled compactions in a shard.
@@ -79,6 +84,33 @@ pub(crate) struct ForceGcArgs {
     state: StateArgs,
 }
 
+/// Forks a shard into a new one that shares its blobs.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct ForkArgs {
+    #[clap(flatten)]
+    state: StateArgs,
+
+    /// The since of the fork. Defaults to the current since of the shard.
+    #[clap(long)]
+    since: Option<u64>,
+}
+
+/// Manually releases the hold a shard has on its blobs for a fork.
+///
+/// The fork releases the hold on its own once it no longer needs it, and the
+/// hold of a fork that was never created expires after the reader lease
+/// duration. This releases a hold without waiting for either, e.g. to abandon
+/// a fork.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct ReleaseForkArgs {
+    #[clap(flatten)]
+    state: StateArgs,
+
+    /// The fork whose hold to release.
+    #[clap(long)]
+    fork_id: String,
+}
+
 /// Runs the given read-write admin command.
 pub async fn run(command: AdminArgs) -> Result<(), anyhow::Error> {
     match command.command {
@@ -118,6 +150,39 @@ pub async fn run(command: AdminArgs) -> Result<(), anyhow::Error> {
             .await?;
             info_log_non_zero_metrics(&metrics_registry.gather());
         }
+        Command::Fork(args) => {
+            let shard_id = ShardId::from_str(&args.state.shard_id).expect("invalid shard id");
+            let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone());
+            let metrics_registry = MetricsRegistry::new();
+            let () = fork(
+                cfg,
+                &metrics_registry,
+                shard_id,
+                args.since,
+                &args.state.consensus_uri,
+                &args.state.blob_uri,
+                command.commit,
+            )
+            .await?;
+            info_log_non_zero_metrics(&metrics_registry.gather());
+        }
+        Command::ReleaseFork(args) => {
+            let shard_id = ShardId::from_str(&args.state.shard_id).expect("invalid shard id");
+            let fork_id = ShardId::from_str(&args.fork_id).expect("invalid fork id");
+            let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone());
+            let metrics_registry = MetricsRegistry::new();
+            let () = release_fork(
+                cfg,
+                &metrics_registry,
+                shard_id,
+                fork_id,
+                &args.state.consensus_uri,
+                &args.state.blob_uri,
+                command.commit,
+            )
+            .await?;
+            info_log_non_zero_metrics(&metrics_registry.gather());
+        }
     }
     Ok(())
 }
@@ -458,3 +523,72 @@ async fn force_gc(
 
     Ok(Box::new(machine))
 }
+
+/// Forks a shard into a new one that shares its blobs.
+///
+/// See [crate::PersistClient::fork_shard].
+pub async fn fork(
+    cfg: PersistConfig,
+    metrics_registry: &MetricsRegistry,
+    shard_id: ShardId,
+    since: Option<u64>,
+    consensus_uri: &str,
+    blob_uri: &str,
+    commit: bool,
+) -> Result<(), anyhow::Error> {
+    let metrics = Arc::new(Metrics::new(&cfg, metrics_registry));
+    let consensus = make_consensus(&cfg, consensus_uri, commit, Arc::clone(&metrics)).await?;
+    let blob = make_blob(&cfg, blob_uri, commit, Arc::clone(&metrics)).await?;
+    let mut machine = make_machine(&cfg, consensus, blob, metrics, shard_id, commit).await?;
+
+    let since = match since {
+        Some(since) => Antichain::from_elem(since),
+        None => machine.applier.since(),
+    };
+    let fork_id = ShardId::new();
+    info!(
+        "forking {} as of {:?} into {}",
+        shard_id,
+        since.elements(),
+        fork_id
+    );
+    if !commit {
+        info!("skipping fork because --commit is not set");
+        return Ok(());
+    }
+    let (res, maintenance) = machine
+        .fork(&fork_id, &since, &Diagnostics::from_purpose("admin fork"))
+        .await;
+    if !maintenance.is_empty() {
+        info!("ignoring non-empty requested maintenance: {maintenance:?}")
+    }
+    let () = res?;
+    info!("forked {} into {}", shard_id, fork_id);
+    Ok(())
+}
+
+/// Manually releases the hold a shard has on its blobs for a fork.
+pub async fn release_fork(
+    cfg: PersistConfig,
+    metrics_registry: &MetricsRegistry,
+    shard_id: ShardId,
+    fork_id: ShardId,
+    consensus_uri: &str,
+    blob_uri: &str,
+    commit: bool,
+) -> Result<(), anyhow::Error> {
+    let metrics = Arc::new(Metrics::new(&cfg, metrics_registry));
+    let consensus = make_consensus(&cfg, consensus_uri, commit, Arc::clone(&metrics)).await?;
+    let blob = make_blob(&cfg, blob_uri, commit, Arc::clone(&metrics)).await?;
+    let mut machine = make_machine(&cfg, consensus, blob, metrics, shard_id, commit).await?;
+    let (existed, maintenance) = machine.release_fork(&fork_id).await;
+    if !maintenance.is_empty() {
+        info!("ignoring non-empty requested maintenance: {maintenance:?}")
+    }
+    if existed {
+        info!("released hold on {} for fork {}", shard_id, fork_id);
+    } else {
+        info!("{} had no hold for fork {}", shard_id, fork_id);
+    }
+    Ok(())
+}
@@ -0,0 +1,717 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! CLI tools for moving the contents of a shard in and out of persist
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use differential_dataflow::consolidation::consolidate_updates;
+use differential_dataflow::trace::Description;
+use mz_ore::bytes::SegmentedBytes;
+use mz_ore::metrics::MetricsRegistry;
+use mz_ore::now::SYSTEM_TIME;
+use mz_persist::indexed::columnar::{ColumnarRecords, ColumnarRecordsBuilder};
+use mz_persist::indexed::encoding::BlobTraceBatchPart;
+use mz_persist_types::codec_impls::TodoSchema;
+use mz_persist_types::Codec64;
+use mz_proto::RustType;
+use prost::Message;
+use timely::progress::{Antichain, Timestamp};
+use timely::PartialOrder;
+use tracing::info;
+
+use crate::async_runtime::IsolatedRuntime;
+use crate::batch::{BatchBuilderConfig, BatchParts};
+use crate::cache::StateCache;
+use crate::cli::admin::{make_blob, make_consensus};
+use crate::cli::inspect::{StateArgs, K, KVTD_CODECS, V};
+use crate::fetch::{fetch_batch_part, Cursor};
+use crate::internal::encoding::Schemas;
+use crate::internal::machine::Machine;
+use crate::internal::state::{HandleDebugState, HollowBatch};
+use crate::read::LeasedReaderId;
+use crate::rpc::NoopPubSubSender;
+use crate::write::WriterId;
+use crate::{Diagnostics, Metrics, PersistConfig, ShardId, StateVersions, BUILD_INFO};
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_persist_client.cli.archive.rs"
+));
+
+/// The version of the archive format written by [export].
+///
+/// See [ProtoShardArchiveHeader::format_version].
+const ARCHIVE_FORMAT_VERSION: u64 = 1;
+
+/// Writes the contents of a shard to an archive file
+#[derive(Debug, clap::Parser)]
+pub struct ExportArgs {
+    #[clap(flatten)]
+    state: StateArgs,
+
+    /// File to write the archive to.
+    #[clap(long)]
+    output: PathBuf,
+
+    /// Time to consolidate the snapshot to. Defaults to the most recent
+    /// complete time in the shard.
+    #[clap(long, conflicts_with = "history_since")]
+    as_of: Option<u64>,
+
+    /// Consolidate the snapshot to this time and additionally include all
+    /// changes to the shard after it.
+    #[clap(long)]
+    history_since: Option<u64>,
+}
+
+/// Creates a new shard from an archive file written by `persistcli export`
+#[derive(Debug, clap::Parser)]
+pub struct ImportArgs {
+    /// Archive file to import.
+    #[clap(long)]
+    input: PathBuf,
+
+    /// Consensus to create the shard in.
+    #[clap(long, env = "CONSENSUS_URI")]
+    consensus_uri: String,
+
+    /// Blob to create the shard in.
+    #[clap(long, env = "BLOB_URI")]
+    blob_uri: String,
+
+    /// Whether to actually create the shard (defaults to dry run).
+    #[clap(long)]
+    commit: bool,
+}
+
+/// Runs `persistcli export`.
+pub async fn run_export(args: ExportArgs) -> Result<(), anyhow::Error> {
+    let shard_id = ShardId::from_str(&args.state.shard_id).expect("invalid shard id");
+    let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone());
+    let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
+    // Exporting is read-only.
+    let commit = false;
+    let consensus = make_consensus(
+        &cfg,
+        &args.state.consensus_uri,
+        commit,
+        Arc::clone(&metrics),
+    )
+    .await?;
+    let blob = make_blob(&cfg, &args.state.blob_uri, commit, Arc::clone(&metrics)).await?;
+    let state_versions = StateVersions::new(cfg, consensus, blob, Arc::clone(&metrics));
+
+    let mut out = BufWriter::new(File::create(&args.output)?);
+    let header = export(
+        &state_versions,
+        &metrics,
+        shard_id,
+        args.as_of,
+        args.history_since,
+        &mut out,
+    )
+    .await?;
+    out.flush()?;
+    info!(
+        "exported {} as of {} with upper {:?} to {}",
+        shard_id,
+        header.as_of,
+        header.upper,
+        args.output.display()
+    );
+    Ok(())
+}
+
+/// Runs `persistcli import`.
+pub async fn run_import(args: ImportArgs) -> Result<(), anyhow::Error> {
+    let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone());
+    let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
+    let consensus =
+        make_consensus(&cfg, &args.consensus_uri, args.commit, Arc::clone(&metrics)).await?;
+    let blob = make_blob(&cfg, &args.blob_uri, args.commit, Arc::clone(&metrics)).await?;
+    let state_versions = Arc::new(StateVersions::new(
+        cfg,
+        consensus,
+        blob,
+        Arc::clone(&metrics),
+    ));
+
+    let archive = std::fs::read(&args.input)?;
+    let shard_id = ShardId::new();
+    let header = import(state_versions, metrics, shard_id, &archive, args.commit).await?;
+    if args.commit {
+        info!(
+            "imported {} as of {} from {} into {}",
+            header.shard_id,
+            header.as_of,
+            args.input.display(),
+            shard_id
+        );
+    }
+    Ok(())
+}
+
+/// Writes an archive of the contents of `shard_id` to `out`.
+///
+/// The archive contains a snapshot consolidated to `as_of` (or
+/// `history_since`), followed by all changes after `history_since`, if set.
+/// The data is consolidated in memory, so this is only suitable for shards
+/// that comfortably fit.
+///
+/// This doesn't hold a lease on the shard, so concurrent compaction and gc may
+/// delete a blob out from under it, in which case it returns an error and is
+/// safe to retry.
+pub(crate) async fn export<W: Write>(
+    state_versions: &StateVersions,
+    metrics: &Metrics,
+    shard_id: ShardId,
+    as_of: Option<u64>,
+    history_since: Option<u64>,
+    out: &mut W,
+) -> Result<ProtoShardArchiveHeader, anyhow::Error> {
+    let versions = state_versions
+        .fetch_recent_live_diffs::<u64>(&shard_id)
+        .await;
+    if versions.0.is_empty() {
+        return Err(anyhow!("unknown shard {}", shard_id));
+    }
+    let state = state_versions
+        .fetch_current_state::<u64>(&shard_id, versions.0)
+        .await;
+    let mut header = ProtoShardArchiveHeader {
+        format_version: ARCHIVE_FORMAT_VERSION,
+        shard_id: shard_id.into_proto(),
+        build_version: state_versions.cfg.build_version.to_string(),
+        exported_at_ms: (state_versions.cfg.now)(),
+        key_codec: state.key_codec.clone(),
+        val_codec: state.val_codec.clone(),
+        ts_codec: state.ts_codec.clone(),
+        diff_codec: state.diff_codec.clone(),
+        as_of: 0,
+        upper: Vec::new(),
+    };
+    // Consolidation needs to understand diffs.
+    if header.diff_codec != <i64 as Codec64>::codec_name() {
+        return Err(anyhow!(
+            "unsupported diff codec {} for shard {}",
+            header.diff_codec,
+            shard_id
+        ));
+    }
+    let state = state.check_ts_codec(&shard_id)?;
+    let since = state.collections.trace.since();
+    let upper = state.collections.trace.upper();
+
+    let (as_of, history) = match (as_of, history_since) {
+        (_, Some(history_since)) => (history_since, true),
+        (Some(as_of), None) => (as_of, false),
+        (None, None) => match upper.as_option() {
+            Some(upper) => match upper.checked_sub(1) {
+                Some(as_of) => (as_of, false),
+                None => return Err(anyhow!("shard {} has no data to export", shard_id)),
+            },
+            None => {
+                return Err(anyhow!(
+                    "shard {} is closed, an explicit --as-of is required",
+                    shard_id
+                ))
+            }
+        },
+    };
+    if !since.less_equal(&as_of) || upper.less_equal(&as_of) {
+        return Err(anyhow!(
+            "{} is not in [since, upper) = [{:?}, {:?}) for shard {}",
+            as_of,
+            since.elements(),
+            upper.elements(),
+            shard_id
+        ));
+    }
+    let snapshot_upper = match as_of.checked_add(1) {
+        Some(x) => Antichain::from_elem(x),
+        None => Antichain::new(),
+    };
+
+    let shard_metrics = metrics.shards.shard(&shard_id, "export");
+    let mut snapshot = Vec::new();
+    let mut changes = Vec::new();
+    for batch in state.collections.trace.batches() {
+        if !history && !batch.desc.lower().less_equal(&as_of) {
+            continue;
+        }
+        for part in batch.parts.iter() {
+            let encoded_part = fetch_batch_part(
+                &shard_id,
+                state_versions.blob.as_ref(),
+                metrics,
+                &shard_metrics,
+                &metrics.read.snapshot,
+                &part.key,
+                part.compression,
+                &batch.desc,
+            )
+            .await?;
+            let mut cursor = Cursor::default();
+            while let Some((k, v, t, d)) = cursor.pop(&encoded_part) {
+                let kv = (k.to_vec(), v.to_vec());
+                let d = <i64 as Codec64>::decode(d);
+                if t <= as_of {
+                    snapshot.push((kv, as_of, d));
+                } else if history {
+                    changes.push((kv, t, d));
+                }
+            }
+        }
+    }
+    consolidate_updates(&mut snapshot);
+    consolidate_updates(&mut changes);
+
+    header.as_of = as_of;
+    header.upper = if history {
+        upper.elements().to_vec()
+    } else {
+        snapshot_upper.elements().to_vec()
+    };
+    out.write_all(&header.encode_length_delimited_to_vec())?;
+
+    let target_size = state_versions.cfg.dynamic.blob_target_size();
+    let batch_since = Antichain::from_elem(u64::minimum());
+    let snapshot_desc = Description::new(
+        Antichain::from_elem(u64::minimum()),
+        snapshot_upper.clone(),
+        batch_since.clone(),
+    );
+    write_parts(out, &snapshot_desc, &snapshot, target_size)?;
+    if history && &snapshot_upper != upper {
+        let changes_desc = Description::new(snapshot_upper, upper.clone(), batch_since);
+        write_parts(out, &changes_desc, &changes, target_size)?;
+    }
+    Ok(header)
+}
+
+/// Writes `updates` to `out` as a sequence of [ProtoShardArchivePart]s of
+/// roughly `target_size` bytes each.
+fn write_parts<W: Write>(
+    out: &mut W,
+    desc: &Description<u64>,
+    updates: &[((Vec<u8>, Vec<u8>), u64, i64)],
+    target_size: usize,
+) -> Result<(), anyhow::Error> {
+    let mut index = 0;
+    let mut builder = ColumnarRecordsBuilder::default();
+    let mut builder_size = 0;
+    for ((k, v), t, d) in updates {
+        let size = ColumnarRecordsBuilder::columnar_record_size(k.len(), v.len());
+        if builder.len() > 0 && builder_size + size > target_size {
+            let records = std::mem::take(&mut builder).finish();
+            write_part(out, desc, index, records)?;
+            index += 1;
+            builder_size = 0;
+        }
+        // If this fails, the individual record is too big to fit in a
+        // ColumnarRecords by itself, which persist never writes.
+        assert!(builder.push(((k, v), Codec64::encode(t), Codec64::encode(d))));
+        builder_size += size;
+    }
+    if builder.len() > 0 {
+        write_part(out, desc, index, builder.finish())?;
+    }
+    Ok(())
+}
+
+fn write_part<W: Write>(
+    out: &mut W,
+    desc: &Description<u64>,
+    index: u64,
+    records: ColumnarRecords,
+) -> Result<(), anyhow::Error> {
+    let part = BlobTraceBatchPart {
+        desc: desc.clone(),
+        index,
+        updates: vec![records],
+    };
+    let mut buf = Vec::new();
+    part.encode(&mut buf);
+    let part = ProtoShardArchivePart { part: buf };
+    out.write_all(&part.encode_length_delimited_to_vec())?;
+    Ok(())
+}
+
+/// Creates the shard `shard_id` from an archive written by [export].
+///
+/// Unless `commit` is set, this only validates the archive.
+pub(crate) async fn import(
+    state_versions: Arc<StateVersions>,
+    metrics: Arc<Metrics>,
+    shard_id: ShardId,
+    mut archive: &[u8],
+    commit: bool,
+) -> Result<ProtoShardArchiveHeader, anyhow::Error> {
+    let header = ProtoShardArchiveHeader::decode_length_delimited(&mut archive)?;
+    if header.format_version != ARCHIVE_FORMAT_VERSION {
+        return Err(anyhow!(
+            "unsupported archive format version {}",
+            header.format_version
+        ));
+    }
+    // The admin tooling only knows how to write shards with these.
+    if header.ts_codec != <u64 as Codec64>::codec_name()
+        || header.diff_codec != <i64 as Codec64>::codec_name()
+    {
+        return Err(anyhow!(
+            "unsupported ts codec {} or diff codec {}",
+            header.ts_codec,
+            header.diff_codec
+        ));
+    }
+    let upper = Antichain::from(header.upper.clone());
+
+    // Group the parts back up into the batches they were exported as.
+    let mut batches: Vec<(Description<u64>, Vec<ColumnarRecords>)> = Vec::new();
+    while !archive.is_empty() {
+        let part = ProtoShardArchivePart::decode_length_delimited(&mut archive)?;
+        let part = BlobTraceBatchPart::<u64>::decode(&SegmentedBytes::from(part.part))?;
+        match batches.last_mut() {
+            Some((desc, updates)) if *desc == part.desc => updates.extend(part.updates),
+            _ => batches.push((part.desc, part.updates)),
+        }
+    }
+    let mut expected_lower = Antichain::from_elem(u64::minimum());
+    for (desc, _) in batches.iter() {
+        if !PartialOrder::less_equal(&expected_lower, desc.lower())
+            || !PartialOrder::less_equal(desc.upper(), &upper)
+        {
+            return Err(anyhow!("malformed archive: unexpected part {:?}", desc));
+        }
+        expected_lower = desc.upper().clone();
+    }
+
+    let len = batches
+        .iter()
+        .flat_map(|(_, updates)| updates.iter().map(|x| x.len()))
+        .sum::<usize>();
+    info!(
+        "importing {} updates in {} batches from {} as of {} into {}",
+        len,
+        batches.len(),
+        header.shard_id,
+        header.as_of,
+        shard_id
+    );
+    if !commit {
+        info!("skipping import because --commit is not set");
+        return Ok(header);
+    }
+
+    // Convince the new shard to take on the codecs of the exported one. See
+    // the comment on [K] for why this is okay.
+    {
+        let mut kvtd = KVTD_CODECS.lock().expect("lockable");
+        *kvtd = (
+            header.key_codec.clone(),
+            header.val_codec.clone(),
+            header.ts_codec.clone(),
+            header.diff_codec.clone(),
+            None,
+        );
+    }
+    let cfg = state_versions.cfg.clone();
+    let mut machine = Machine::<K, V, u64, i64>::new(
+        cfg.clone(),
+        shard_id,
+        Arc::clone(&metrics),
+        Arc::clone(&state_versions),
+        Arc::new(StateCache::new(
+            &cfg,
+            Arc::clone(&metrics),
+            Arc::new(NoopPubSubSender),
+        )),
+        Arc::new(NoopPubSubSender),
+        Arc::new(IsolatedRuntime::new()),
+        Diagnostics::from_purpose("import"),
+    )
+    .await?;
+    let shard_upper = machine.applier.clone_upper();
+    if shard_upper != Antichain::from_elem(u64::minimum()) {
+        return Err(anyhow!(
+            "shard {} already has data up to {:?}",
+            shard_id,
+            shard_upper.elements()
+        ));
+    }
+
+    let writer_id = WriterId::new();
+    let mut batch_cfg = BatchBuilderConfig::new(&cfg, &writer_id);
+    // K and V can't decode anything, so there's nothing to compute stats with.
+    batch_cfg.stats_collection_enabled = false;
+    let schemas = Schemas::<K, V> {
+        key: Arc::new(TodoSchema::default()),
+        val: Arc::new(TodoSchema::default()),
+    };
+    let debug_info = HandleDebugState {
+        hostname: cfg.hostname.clone(),
+        purpose: "persistcli import".to_owned(),
+    };
+    let since = Antichain::from_elem(u64::minimum());
+    let mut lower = Antichain::from_elem(u64::minimum());
+    for (desc, updates) in batches {
+        // Any gaps between the batches in the archive are empty.
+        if &lower != desc.lower() {
+            let gap = Description::new(lower, desc.lower().clone(), since.clone());
+            append(&mut machine, &writer_id, &debug_info, empty_batch(gap)).await?;
+        }
+        let mut parts = BatchParts::new(
+            batch_cfg.clone(),
+            Arc::clone(&metrics),
+            Arc::clone(&machine.applier.shard_metrics),
+            shard_id,
+            desc.lower().clone(),
+            Arc::clone(&state_versions.blob),
+            Arc::clone(&machine.isolated_runtime),
+            &metrics.user,
+        );
+        let mut len = 0;
+        for updates in updates {
+            len += updates.len();
+            parts
+                .write(
+                    &schemas,
+                    updates,
+                    desc.upper().clone(),
+                    desc.since().clone(),
+                )
+                .await;
+        }
+        lower = desc.upper().clone();
+        let batch = HollowBatch {
+            desc,
+            parts: parts.finish().await,
+            len,
+            runs: Vec::new(),
+        };
+        append(&mut machine, &writer_id, &debug_info, batch).await?;
+    }
+    if lower != upper {
+        let gap = Description::new(lower, upper, since);
+        append(&mut machine, &writer_id, &debug_info, empty_batch(gap)).await?;
+    }
+    let _ = machine.expire_writer(&writer_id).await;
+
+    // Nothing can be read at times before the as_of, so mark them as such with
+    // a reader that holds the since there just long enough to downgrade it.
+    let as_of = Antichain::from_elem(header.as_of);
+    let reader_id = LeasedReaderId::new();
+    let _ = machine
+        .register_leased_reader(
+            &reader_id,
+            "persistcli import",
+            cfg.reader_lease_duration,
+            (cfg.now)(),
+        )
+        .await;
+    let _ = machine
+        .downgrade_since(&reader_id, None, &as_of, (cfg.now)())
+        .await;
+    let _ = machine.expire_leased_reader(&reader_id).await;
+
+    Ok(header)
+}
+
+fn empty_batch(desc: Description<u64>) -> HollowBatch<u64> {
+    HollowBatch {
+        desc,
+        parts: Vec::new(),
+        len: 0,
+        runs: Vec::new(),
+    }
+}
+
+async fn append(
+    machine: &mut Machine<K, V, u64, i64>,
+    writer_id: &WriterId,
+    debug_info: &HandleDebugState,
+    batch: HollowBatch<u64>,
+) -> Result<(), anyhow::Error> {
+    let heartbeat_timestamp_ms = (machine.applier.cfg.now)();
+    let res = machine
+        .compare_and_append(&batch, writer_id, debug_info, heartbeat_timestamp_ms)
+        .await;
+    match res {
+        // Nothing else is using the shard yet, so it can do without the
+        // compaction and such until something does.
+        Ok(Ok((_seqno, _maintenance))) => Ok(()),
+        Ok(Err(err)) => Err(anyhow!("invalid batch {:?}: {}", batch.desc, err)),
+        Err(upper) => Err(anyhow!(
+            "shard {} unexpectedly has upper {:?}",
+            machine.shard_id(),
+            upper.0.elements()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_persist::location::{Blob, Consensus};
+    use mz_persist::mem::{MemBlob, MemBlobConfig, MemConsensus};
+
+    use crate::tests::all_ok;
+    use crate::PersistClient;
+
+    use super::*;
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn export_import() {
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("1".to_owned(), "one".to_owned()), 3, -1),
+            (("3".to_owned(), "three".to_owned()), 4, 1),
+        ];
+
+        let cfg = PersistConfig::new_for_tests();
+        // Exercise archives with more than one part per batch.
+        cfg.dynamic.set_blob_target_size(10);
+        let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
+        let blob: Arc<dyn Blob + Send + Sync> = Arc::new(MemBlob::open(MemBlobConfig::default()));
+        let consensus: Arc<dyn Consensus + Send + Sync> = Arc::new(MemConsensus::default());
+        let client = PersistClient::new(
+            cfg.clone(),
+            Arc::clone(&blob),
+            Arc::clone(&consensus),
+            Arc::clone(&metrics),
+            Arc::new(IsolatedRuntime::new()),
+            Arc::new(StateCache::new(
+                &cfg,
+                Arc::clone(&metrics),
+                Arc::new(NoopPubSubSender),
+            )),
+            Arc::new(NoopPubSubSender),
+        )
+        .expect("client construction failed");
+        let state_versions = Arc::new(StateVersions::new(
+            cfg,
+            consensus,
+            blob,
+            Arc::clone(&metrics),
+        ));
+
+        let shard_id = ShardId::new();
+        let (mut write, _read) = client
+            .expect_open::<String, String, u64, i64>(shard_id)
+            .await;
+        write.expect_append(&data, vec![0], vec![5]).await;
+
+        // By default, export the most recent snapshot.
+        let mut archive = Vec::new();
+        let header = export(
+            &state_versions,
+            &metrics,
+            shard_id,
+            None,
+            None,
+            &mut archive,
+        )
+        .await
+        .expect("valid export");
+        assert_eq!((header.as_of, header.upper), (4, vec![5]));
+
+        // Export a snapshot as of 3, with and without the history after it.
+        let mut snapshot = Vec::new();
+        let _ = export(
+            &state_versions,
+            &metrics,
+            shard_id,
+            Some(3),
+            None,
+            &mut snapshot,
+        )
+        .await
+        .expect("valid export");
+        let mut history = Vec::new();
+        let _ = export(
+            &state_versions,
+            &metrics,
+            shard_id,
+            None,
+            Some(3),
+            &mut history,
+        )
+        .await
+        .expect("valid export");
+
+        // Can't export anything before the since or at or after the upper.
+        let mut out = Vec::new();
+        let res = export(&state_versions, &metrics, shard_id, Some(5), None, &mut out).await;
+        assert!(res.is_err());
+
+        // A dry run doesn't create anything.
+        let dry_run_id = ShardId::new();
+        let _ = import(
+            Arc::clone(&state_versions),
+            Arc::clone(&metrics),
+            dry_run_id,
+            &snapshot,
+            false,
+        )
+        .await
+        .expect("valid import");
+        assert!(state_versions
+            .fetch_recent_live_diffs::<u64>(&dry_run_id)
+            .await
+            .0
+            .is_empty());
+
+        // The snapshot imports as a shard with only the consolidated data at
+        // the as_of.
+        let snapshot_id = ShardId::new();
+        let _ = import(
+            Arc::clone(&state_versions),
+            Arc::clone(&metrics),
+            snapshot_id,
+            &snapshot,
+            true,
+        )
+        .await
+        .expect("valid import");
+        let (write, mut read) = client
+            .expect_open::<String, String, u64, i64>(snapshot_id)
+            .await;
+        assert_eq!(read.since(), &Antichain::from_elem(3));
+        assert_eq!(write.upper(), &Antichain::from_elem(4));
+        assert_eq!(
+            read.expect_snapshot_and_fetch(3).await,
+            all_ok(&data[..3], 3)
+        );
+
+        // With history, the changes after the as_of are all there too.
+        let history_id = ShardId::new();
+        let _ = import(
+            Arc::clone(&state_versions),
+            Arc::clone(&metrics),
+            history_id,
+            &history,
+            true,
+        )
+        .await
+        .expect("valid import");
+        let (write, mut read) = client
+            .expect_open::<String, String, u64, i64>(history_id)
+            .await;
+        assert_eq!(read.since(), &Antichain::from_elem(3));
+        assert_eq!(write.upper(), &Antichain::from_elem(5));
+        assert_eq!(read.expect_snapshot_and_fetch(4).await, all_ok(&data, 4));
+    }
+}
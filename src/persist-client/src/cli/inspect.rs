@@ -31,6 +31,7 @@ use prost::Message;
 use serde_json::json;
 
 use crate::async_runtime::IsolatedRuntime;
+use crate::batch::PartCompression;
 use crate::cache::StateCache;
 use crate::cli::admin::{make_blob, make_consensus};
 use crate::error::CodecConcreteType;
@@ -155,8 +156,7 @@ pub async fn run(command: InspectArgs) -> Result<(), anyhow::Error> {
             println!("{}", json!(blob_counts));
         }
         Command::BlobBatchPart(args) => {
-            let shard_id = ShardId::from_str(&args.shard_id).expect("invalid shard id");
-            let updates = blob_batch_part(&args.blob_uri, shard_id, args.key, args.limit).await?;
+            let updates = blob_batch_part(&args.state, args.key, args.limit).await?;
             println!("{}", json!(updates));
         }
         Command::UnreferencedBlobs(args) => {
@@ -317,22 +317,14 @@ pub async fn fetch_state_diffs(
 /// Arguments for viewing contents of a batch part
 #[derive(Debug, Clone, clap::Parser)]
 pub struct BlobBatchPartArgs {
-    /// Shard to view
-    #[clap(long)]
-    shard_id: String,
+    /// The shard of the part, and the consensus in which to look up its metadata.
+    #[clap(flatten)]
+    state: StateArgs,
 
     /// Blob key (without shard)
     #[clap(long)]
     key: String,
 
-    /// Blob to use
-    ///
-    /// When connecting to a deployed environment's blob, the necessary connection glue must be in
-    /// place. e.g. for S3, sign into SSO, set AWS_PROFILE and AWS_REGION appropriately, with a blob
-    /// URI scoped to the environment's bucket prefix.
-    #[clap(long)]
-    blob_uri: String,
-
     /// Number of updates to output. Default is unbounded.
     #[clap(long, default_value = "18446744073709551615")]
     limit: usize,
@@ -365,22 +357,36 @@ impl fmt::Debug for PrettyBytes<'_> {
 }
 
 /// Fetches the updates in a blob batch part
+///
+/// The compression codec of the part is read from its metadata in the live states of the shard.
+/// Parts that are no longer referenced by any live state are assumed to be uncompressed.
 pub async fn blob_batch_part(
-    blob_uri: &str,
-    shard_id: ShardId,
+    args: &StateArgs,
     partial_key: String,
     limit: usize,
 ) -> Result<impl serde::Serialize, anyhow::Error> {
-    let cfg = PersistConfig::new(&READ_ALL_BUILD_INFO, SYSTEM_TIME.clone());
-    let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
-    let blob = make_blob(&cfg, blob_uri, NO_COMMIT, metrics).await?;
+    let shard_id = args.shard_id();
+    let state_versions = args.open().await?;
 
-    let key = PartialBatchKey(partial_key).complete(&shard_id);
-    let part = blob
+    let partial_key = PartialBatchKey(partial_key);
+    let compression = part_compression(&state_versions, shard_id, &partial_key)
+        .await?
+        .unwrap_or_else(|| {
+            eprintln!(
+                "part {} is not referenced by any live state, assuming it is uncompressed",
+                partial_key
+            );
+            PartCompression::None
+        });
+
+    let key = partial_key.complete(&shard_id);
+    let part = state_versions
+        .blob
         .get(&*key)
         .await
         .expect("blob exists")
         .expect("part exists");
+    let part = compression.decompress(part)?;
     let part = BlobTraceBatchPart::<u64>::decode(&part).expect("decodable");
     let desc = part.desc.clone();
 
@@ -405,6 +411,30 @@ pub async fn blob_batch_part(
     Ok(out)
 }
 
+/// Returns the compression codec of the given part, as recorded in the live states of the shard.
+async fn part_compression(
+    state_versions: &StateVersions,
+    shard_id: ShardId,
+    partial_key: &PartialBatchKey,
+) -> Result<Option<PartCompression>, anyhow::Error> {
+    let mut state_iter = state_versions
+        .fetch_all_live_states::<u64>(shard_id)
+        .await
+        .expect("requested shard should exist")
+        .check_ts_codec()?;
+    let mut compression = None;
+    while let Some(v) = state_iter.next(|_| {}) {
+        for batch in v.collections.trace.batches() {
+            for part in &batch.parts {
+                if &part.key == partial_key {
+                    compression = Some(part.compression);
+                }
+            }
+        }
+    }
+    Ok(compression)
+}
+
 /// Arguments for commands that run only against the blob store.
 #[derive(Debug, Clone, clap::Parser)]
 pub struct BlobArgs {
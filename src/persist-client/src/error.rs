@@ -113,6 +113,32 @@ pub enum InvalidUsage<T> {
     CodecMismatch(Box<CodecMismatch>),
     /// An unregistered or expired [crate::write::WriterId] was used by [crate::write::WriteHandle]
     UnknownWriter(WriterId),
+    /// A shard was forked at a since for which a consistent snapshot is not
+    /// available.
+    InvalidForkSince {
+        /// The requested since of the fork
+        since: Antichain<T>,
+        /// The since of the shard being forked
+        shard_since: Antichain<T>,
+        /// The upper of the shard being forked
+        shard_upper: Antichain<T>,
+    },
+    /// A shard that still references parts owned by some other shard (i.e. a
+    /// fork that hasn't yet compacted away everything it was forked with) was
+    /// itself forked.
+    ForkOfFork {
+        /// The shard that was requested to be forked
+        shard_id: ShardId,
+        /// The shard that owns some of its parts
+        owner: ShardId,
+    },
+    /// The hold registered for a fork expired before the fork was created.
+    ForkLeaseExpired {
+        /// The shard that was requested to be forked
+        shard_id: ShardId,
+        /// The abandoned fork
+        fork_id: ShardId,
+    },
 }
 
 impl<T: Debug> std::fmt::Display for InvalidUsage<T> {
@@ -161,6 +187,25 @@ impl<T: Debug> std::fmt::Display for InvalidUsage<T> {
             InvalidUsage::UnknownWriter(writer_id) => {
                 write!(f, "writer id {} is not registered", writer_id)
             }
+            InvalidUsage::InvalidForkSince {
+                since,
+                shard_since,
+                shard_upper,
+            } => write!(
+                f,
+                "cannot fork at since {:?}: shard since is {:?} and upper is {:?}",
+                since, shard_since, shard_upper
+            ),
+            InvalidUsage::ForkOfFork { shard_id, owner } => write!(
+                f,
+                "cannot fork {} while it still references parts of the shard it was forked from {}",
+                shard_id, owner
+            ),
+            InvalidUsage::ForkLeaseExpired { shard_id, fork_id } => write!(
+                f,
+                "fork {} of {} took longer to create than the lease of its hold",
+                fork_id, shard_id
+            ),
         }
     }
 }
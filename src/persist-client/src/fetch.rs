@@ -27,6 +27,7 @@ use timely::progress::{Antichain, Timestamp};
 use timely::PartialOrder;
 use tracing::{debug_span, trace_span, Instrument};
 
+use crate::batch::PartCompression;
 use crate::error::InvalidUsage;
 use crate::internal::encoding::{LazyPartStats, Schemas};
 use crate::internal::machine::retry_external;
@@ -199,6 +200,7 @@ where
         shard_metrics,
         read_metrics,
         &part.key,
+        part.compression,
         &part.desc,
     )
     .await
@@ -243,6 +245,7 @@ pub(crate) async fn fetch_batch_part<T>(
     shard_metrics: &ShardMetrics,
     read_metrics: &ReadMetrics,
     key: &PartialBatchKey,
+    compression: PartCompression,
     registered_desc: &Description<T>,
 ) -> Result<EncodedPart<T>, anyhow::Error>
 where
@@ -272,6 +275,20 @@ where
     read_metrics.part_count.inc();
     read_metrics.part_bytes.inc_by(u64::cast_from(value.len()));
 
+    let value = if compression.is_none() {
+        value
+    } else {
+        let start = Instant::now();
+        let value = trace_span!("fetch_batch::decompress")
+            .in_scope(|| compression.decompress(value))
+            .map_err(|err| anyhow!("couldn't decompress batch at key {}: {}", key, err))?;
+        metrics
+            .compression
+            .decompress_seconds
+            .inc_by(start.elapsed().as_secs_f64());
+        value
+    };
+
     let part = trace_span!("fetch_batch::decode").in_scope(|| {
         let part = metrics
             .codecs
@@ -354,6 +371,7 @@ where
     pub(crate) desc: Description<T>,
     pub(crate) key: PartialBatchKey,
     pub(crate) encoded_size_bytes: usize,
+    pub(crate) compression: PartCompression,
     /// The `SeqNo` from which this part originated; we track this value as
     /// long as necessary to ensure the `SeqNo` isn't garbage collected while a
     /// read still depends on it.
@@ -384,6 +402,7 @@ where
             since: self.desc.since().iter().map(T::encode).collect(),
             key: self.key.clone(),
             encoded_size_bytes: self.encoded_size_bytes,
+            compression: self.compression,
             leased_seqno: self.leased_seqno,
             reader_id: self.reader_id.clone(),
             stats: self.stats.clone(),
@@ -682,6 +701,7 @@ pub struct SerdeLeasedBatchPart {
     since: Vec<[u8; 8]>,
     key: PartialBatchKey,
     encoded_size_bytes: usize,
+    compression: PartCompression,
     leased_seqno: Option<SeqNo>,
     reader_id: LeasedReaderId,
     stats: Option<LazyPartStats>,
@@ -717,6 +737,7 @@ impl<T: Timestamp + Codec64> LeasedBatchPart<T> {
             ),
             key: x.key,
             encoded_size_bytes: x.encoded_size_bytes,
+            compression: x.compression,
             leased_seqno: x.leased_seqno,
             reader_id: x.reader_id,
             stats: x.stats,
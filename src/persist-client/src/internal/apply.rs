@@ -24,6 +24,7 @@ use tracing::debug;
 
 use crate::cache::{LockingTypedState, StateCache};
 use crate::error::CodecMismatch;
+use crate::internal::compact_scheduler::CompactionDebt;
 use crate::internal::gc::GcReq;
 use crate::internal::maintenance::RoutineMaintenance;
 use crate::internal::metrics::{CmdMetrics, Metrics, ShardMetrics};
@@ -120,6 +121,25 @@ where
         Ok(ret)
     }
 
+    /// Returns an [Applier] for some other shard with the same codecs, sharing
+    /// this one's handles to the outside world.
+    pub async fn for_shard(
+        &self,
+        shard_id: ShardId,
+        diagnostics: Diagnostics,
+    ) -> Result<Self, Box<CodecMismatch>> {
+        Self::new(
+            self.cfg.clone(),
+            shard_id,
+            Arc::clone(&self.metrics),
+            Arc::clone(&self.state_versions),
+            Arc::clone(&self.shared_states),
+            Arc::clone(&self.pubsub_sender),
+            diagnostics,
+        )
+        .await
+    }
+
     /// Returns a new [StateWatch] for changes to this Applier's State.
     pub fn watch(&self) -> StateWatch<K, V, T, D> {
         StateWatch::new(Arc::clone(&self.state), Arc::clone(&self.metrics))
@@ -236,6 +256,13 @@ where
             })
     }
 
+    pub fn compaction_debt(&self) -> CompactionDebt {
+        self.state
+            .read_lock(&self.metrics.locks.applier_read_noncacheable, |state| {
+                state.compaction_debt()
+            })
+    }
+
     pub fn snapshot(&self, as_of: &Antichain<T>) -> Result<Vec<HollowBatch<T>>, SnapshotErr<T>> {
         self.state
             .read_lock(&self.metrics.locks.applier_read_noncacheable, |state| {
@@ -382,6 +409,11 @@ where
                     .timeout_read
                     .inc_by(u64::cast_from(expiry_metrics.readers_expired));
 
+                metrics
+                    .lease
+                    .timeout_fork
+                    .inc_by(u64::cast_from(expiry_metrics.forks_expired));
+
                 metrics
                     .state
                     .writer_removed
@@ -18,7 +18,7 @@ use anyhow::anyhow;
 use differential_dataflow::difference::Semigroup;
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::trace::Description;
-use futures_util::TryFutureExt;
+use futures_util::{FutureExt, TryFutureExt};
 use mz_ore::cast::CastFrom;
 use mz_ore::error::ErrorExt;
 use mz_ore::task::spawn;
@@ -28,14 +28,15 @@ use mz_persist_types::{Codec, Codec64};
 use timely::progress::{Antichain, Timestamp};
 use timely::PartialOrder;
 use tokio::sync::mpsc::Sender;
-use tokio::sync::{mpsc, oneshot, TryAcquireError};
+use tokio::sync::{mpsc, oneshot};
 use tokio::task::JoinHandle;
-use tracing::{debug, debug_span, trace, warn, Instrument, Span};
+use tracing::{debug, debug_span, trace, Instrument, Span};
 
 use crate::async_runtime::IsolatedRuntime;
 use crate::batch::{BatchBuilderConfig, BatchBuilderInternal};
 use crate::cfg::MiB;
 use crate::fetch::{fetch_batch_part, Cursor, EncodedPart};
+use crate::internal::compact_scheduler::CompactionScheduler;
 use crate::internal::encoding::Schemas;
 use crate::internal::gc::GarbageCollector;
 use crate::internal::machine::{retry_external, Machine};
@@ -132,6 +133,7 @@ where
         writer_id: WriterId,
         schemas: Schemas<K, V>,
         gc: GarbageCollector<K, V, T, D>,
+        scheduler: CompactionScheduler,
     ) -> Self {
         let (compact_req_sender, mut compact_req_receiver) = mpsc::channel::<(
             Instant,
@@ -139,9 +141,6 @@ where
             Machine<K, V, T, D>,
             oneshot::Sender<Result<ApplyMergeResult, anyhow::Error>>,
         )>(cfg.compaction_queue_size);
-        let concurrency_limit = Arc::new(tokio::sync::Semaphore::new(
-            cfg.compaction_concurrency_limit,
-        ));
 
         // spin off a single task responsible for executing compaction requests.
         // work is enqueued into the task through a channel
@@ -152,24 +151,22 @@ where
                 assert_eq!(req.shard_id, machine.shard_id());
                 let metrics = Arc::clone(&machine.applier.metrics);
 
+                // Compaction never needs more than its memory bound, but small
+                // requests need much less than that.
+                let memory_bytes = std::cmp::min(
+                    machine.applier.cfg.dynamic.compaction_memory_bound_bytes(),
+                    Self::input_bytes(&req),
+                );
+                let debt = machine.applier.compaction_debt();
                 let permit = {
-                    let inner = Arc::clone(&concurrency_limit);
+                    let mut acquire = Box::pin(scheduler.acquire(req.shard_id, debt, memory_bytes));
                     // perform a non-blocking attempt to acquire a permit so we can
-                    // record how often we're ever blocked on the concurrency limit
-                    match inner.try_acquire_owned() {
-                        Ok(permit) => permit,
-                        Err(TryAcquireError::NoPermits) => {
+                    // record how often we're ever blocked on the scheduler
+                    match (&mut acquire).now_or_never() {
+                        Some(permit) => permit,
+                        None => {
                             metrics.compaction.concurrency_waits.inc();
-                            Arc::clone(&concurrency_limit)
-                                .acquire_owned()
-                                .await
-                                .expect("semaphore is never closed")
-                        }
-                        Err(TryAcquireError::Closed) => {
-                            // should never happen in practice. the semaphore is
-                            // never explicitly closed, nor will it close on Drop
-                            warn!("semaphore for shard {} is closed", machine.shard_id());
-                            continue;
+                            acquire.await
                         }
                     }
                 };
@@ -268,6 +265,14 @@ where
         Some(compaction_completed_receiver)
     }
 
+    fn input_bytes(req: &CompactReq<T>) -> usize {
+        req.inputs
+            .iter()
+            .flat_map(|batch| batch.parts.iter())
+            .map(|parts| parts.encoded_size_bytes)
+            .sum::<usize>()
+    }
+
     async fn compact_and_apply(
         cfg: PersistConfig,
         blob: Arc<dyn Blob + Send + Sync>,
@@ -284,12 +289,7 @@ where
 
         // pick a timeout for our compaction request proportional to the amount
         // of data that must be read (with a minimum set by PersistConfig)
-        let total_input_bytes = req
-            .inputs
-            .iter()
-            .flat_map(|batch| batch.parts.iter())
-            .map(|parts| parts.encoded_size_bytes)
-            .sum::<usize>();
+        let total_input_bytes = Self::input_bytes(&req);
         let timeout = Duration::max(
             // either our minimum timeout
             cfg.dynamic.compaction_minimum_timeout(),
@@ -920,6 +920,7 @@ impl<'a, T: Timestamp + Lattice + Codec64> CompactionPart<'a, T> {
                     shard_metrics,
                     &metrics.read.compaction,
                     &part.key,
+                    part.compression,
                     part_desc,
                 )
                 .await
@@ -978,6 +979,7 @@ fn start_prefetches<T: Timestamp + Lattice + Codec64>(
             let metrics = Arc::clone(metrics);
             let shard_metrics = Arc::clone(shard_metrics);
             let part_key = part.key.clone();
+            let part_compression = part.compression;
             let part_desc = part_desc.clone();
             let handle = spawn(
                 || "persist::compaction::prefetch",
@@ -989,6 +991,7 @@ fn start_prefetches<T: Timestamp + Lattice + Codec64>(
                         &shard_metrics,
                         &metrics.read.compaction,
                         &part_key,
+                        part_compression,
                         &part_desc,
                     )
                     .await
@@ -1005,6 +1008,7 @@ mod tests {
     use mz_persist_types::codec_impls::{StringSchema, UnitSchema};
     use timely::progress::Antichain;
 
+    use crate::batch::PartCompression;
     use crate::internal::paths::PartialBatchKey;
     use crate::tests::{
         all_ok, expect_fetch_part, new_test_client, new_test_client_cache, CodecProduct,
@@ -1180,6 +1184,8 @@ mod tests {
             .map(|encoded_size_bytes| HollowBatchPart {
                 key: PartialBatchKey("".into()),
                 encoded_size_bytes,
+                compression: PartCompression::None,
+                compressed_size_bytes: None,
                 stats: None,
             })
             .collect::<Vec<_>>();
@@ -0,0 +1,408 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Process-wide scheduling of compaction work.
+//!
+//! Each [crate::internal::compact::Compactor] used to have its own concurrency
+//! limit, which meant that a handful of shards with lots of small writes could
+//! keep compaction busy while shards with a large merge backlog waited behind
+//! them. Instead, every compaction in the process now asks a shared
+//! [CompactionScheduler] for a [CompactionPermit] before it starts. Waiting
+//! requests are granted in order of their shard's [CompactionDebt], so the
+//! shards with the worst read amplification are merged first, subject to a
+//! global concurrency limit, a per-shard concurrency limit, and a budget on the
+//! total memory reserved by running compactions.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use mz_ore::cast::CastFrom;
+use mz_ore::now::{EpochMillis, NowFn};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+
+use crate::internal::metrics::Metrics;
+use crate::{PersistConfig, ShardId};
+
+/// The outstanding compaction work for a shard.
+///
+/// Debt is ordered first by the number of runs (a reader must merge every run
+/// in the trace, so this is the read amplification of the shard) and then by
+/// the number of bytes sitting in batches that are waiting to be merged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CompactionDebt {
+    /// The number of sorted runs across all batches in the trace.
+    pub runs: usize,
+    /// The encoded size of the batches that have an outstanding merge.
+    pub bytes_awaiting_merge: usize,
+}
+
+/// Whether a compaction request is waiting for or holding a permit.
+#[derive(Arbitrary, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompactionTaskState {
+    /// The request is waiting to be scheduled.
+    Queued,
+    /// The request has been granted a permit and is running.
+    Running,
+}
+
+impl CompactionTaskState {
+    /// A human-readable name for the state.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompactionTaskState::Queued => "queued",
+            CompactionTaskState::Running => "running",
+        }
+    }
+}
+
+/// A point-in-time description of one compaction request known to a
+/// [CompactionScheduler].
+#[derive(Arbitrary, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactionQueueEntry {
+    /// The shard being compacted.
+    pub shard_id: ShardId,
+    /// Whether the request is queued or running.
+    pub state: CompactionTaskState,
+    /// The number of runs in the shard when the request was made.
+    pub runs: usize,
+    /// The bytes awaiting merge in the shard when the request was made.
+    pub bytes_awaiting_merge: usize,
+    /// The memory reserved (or to be reserved) by the request.
+    pub memory_bytes: usize,
+    /// The time at which the request was made, in milliseconds since the Unix
+    /// epoch.
+    pub enqueued_at: EpochMillis,
+}
+
+/// Limits enforced by a [CompactionScheduler].
+#[derive(Debug, Clone)]
+struct SchedulerLimits {
+    concurrency: usize,
+    shard_concurrency: usize,
+    memory_budget_bytes: usize,
+}
+
+#[derive(Debug)]
+struct Task {
+    shard_id: ShardId,
+    debt: CompactionDebt,
+    memory_bytes: usize,
+    enqueued_at: EpochMillis,
+    // Present while the task is queued, taken when it is granted a permit.
+    waker: Option<oneshot::Sender<()>>,
+}
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    next_id: u64,
+    queued: BTreeMap<u64, Task>,
+    running: BTreeMap<u64, Task>,
+    memory_in_use_bytes: usize,
+}
+
+#[derive(Debug)]
+struct SchedulerInner {
+    limits: SchedulerLimits,
+    metrics: Arc<Metrics>,
+    now: NowFn,
+    state: Mutex<SchedulerState>,
+}
+
+/// A process-wide scheduler for compaction requests.
+///
+/// Cloning returns a handle to the same scheduler.
+#[derive(Debug, Clone)]
+pub struct CompactionScheduler {
+    inner: Arc<SchedulerInner>,
+}
+
+impl CompactionScheduler {
+    /// Returns a new [CompactionScheduler] using the limits in `cfg`.
+    pub fn new(cfg: &PersistConfig, metrics: Arc<Metrics>) -> Self {
+        let limits = SchedulerLimits {
+            concurrency: cfg.compaction_process_concurrency_limit,
+            shard_concurrency: cfg.compaction_concurrency_limit,
+            memory_budget_bytes: cfg.compaction_memory_budget_bytes,
+        };
+        CompactionScheduler {
+            inner: Arc::new(SchedulerInner {
+                limits,
+                metrics,
+                now: cfg.now.clone(),
+                state: Mutex::new(SchedulerState::default()),
+            }),
+        }
+    }
+
+    /// Waits until a compaction of `shard_id` with the given debt and memory
+    /// requirement may run.
+    ///
+    /// The returned permit must be held for the duration of the compaction.
+    /// This is cancel-safe: dropping the future before it resolves removes the
+    /// request from the queue.
+    pub async fn acquire(
+        &self,
+        shard_id: ShardId,
+        debt: CompactionDebt,
+        memory_bytes: usize,
+    ) -> CompactionPermit {
+        let (tx, rx) = oneshot::channel();
+        let enqueued_at = (self.inner.now)();
+        let permit = {
+            let mut state = self.inner.state.lock().expect("lock poisoned");
+            let id = state.next_id;
+            state.next_id += 1;
+            state.queued.insert(
+                id,
+                Task {
+                    shard_id,
+                    debt,
+                    memory_bytes,
+                    enqueued_at,
+                    waker: Some(tx),
+                },
+            );
+            self.inner.grant(&mut state);
+            CompactionPermit {
+                id,
+                inner: Arc::clone(&self.inner),
+            }
+        };
+        if rx.await.is_err() {
+            // The sender is only ever dropped without sending if the task is
+            // removed from the queue, which only happens when the permit is
+            // dropped. We're holding the permit, so this is unreachable.
+            unreachable!("compaction task removed from queue while waiting");
+        }
+        permit
+    }
+
+    /// Returns a snapshot of all queued and running compaction requests, in
+    /// the order they would be granted.
+    pub fn queue(&self) -> Vec<CompactionQueueEntry> {
+        let state = self.inner.state.lock().expect("lock poisoned");
+        let entry = |task: &Task, task_state| CompactionQueueEntry {
+            shard_id: task.shard_id,
+            state: task_state,
+            runs: task.debt.runs,
+            bytes_awaiting_merge: task.debt.bytes_awaiting_merge,
+            memory_bytes: task.memory_bytes,
+            enqueued_at: task.enqueued_at,
+        };
+        let mut queued = state.queued.iter().collect::<Vec<_>>();
+        queued.sort_by_key(|(id, task)| (Reverse(task.debt), **id));
+        state
+            .running
+            .values()
+            .map(|task| entry(task, CompactionTaskState::Running))
+            .chain(
+                queued
+                    .into_iter()
+                    .map(|(_, task)| entry(task, CompactionTaskState::Queued)),
+            )
+            .collect()
+    }
+}
+
+impl SchedulerInner {
+    /// Grants permits to as many queued tasks as the limits allow.
+    ///
+    /// Tasks are considered in priority order. If the highest priority task
+    /// that isn't blocked by its shard's concurrency limit doesn't fit in the
+    /// remaining memory budget, nothing else is granted until it does, so that
+    /// a steady stream of small compactions can't starve a large one.
+    fn grant(&self, state: &mut SchedulerState) {
+        loop {
+            if state.running.len() >= self.limits.concurrency {
+                break;
+            }
+            let next = state
+                .queued
+                .iter()
+                .filter(|(_, task)| {
+                    let shard_running = state
+                        .running
+                        .values()
+                        .filter(|x| x.shard_id == task.shard_id)
+                        .count();
+                    shard_running < self.limits.shard_concurrency
+                })
+                .max_by_key(|(id, task)| (task.debt, Reverse(**id)))
+                .map(|(id, task)| (*id, task.memory_bytes));
+            let Some((id, memory_bytes)) = next else {
+                break;
+            };
+            // Always admit something if nothing is running, even if it's
+            // larger than the whole budget, so we make progress.
+            let fits = state.memory_in_use_bytes.saturating_add(memory_bytes)
+                <= self.limits.memory_budget_bytes;
+            if !fits && !state.running.is_empty() {
+                self.metrics.compaction.scheduler.memory_waits.inc();
+                break;
+            }
+            let mut task = state.queued.remove(&id).expect("task is queued");
+            state.memory_in_use_bytes += task.memory_bytes;
+            if let Some(waker) = task.waker.take() {
+                // If the receiver is gone, the permit is about to be dropped
+                // and will remove the task again.
+                let _ = waker.send(());
+            }
+            state.running.insert(id, task);
+        }
+        self.update_metrics(state);
+    }
+
+    fn release(&self, id: u64) {
+        let mut state = self.state.lock().expect("lock poisoned");
+        if state.queued.remove(&id).is_none() {
+            if let Some(task) = state.running.remove(&id) {
+                state.memory_in_use_bytes -= task.memory_bytes;
+            }
+        }
+        self.grant(&mut state);
+    }
+
+    fn update_metrics(&self, state: &SchedulerState) {
+        let metrics = &self.metrics.compaction.scheduler;
+        metrics.queued.set(u64::cast_from(state.queued.len()));
+        metrics.running.set(u64::cast_from(state.running.len()));
+        metrics
+            .memory_in_use_bytes
+            .set(u64::cast_from(state.memory_in_use_bytes));
+        let max_runs = state
+            .queued
+            .values()
+            .map(|x| x.debt.runs)
+            .max()
+            .unwrap_or_default();
+        metrics.max_queued_runs.set(u64::cast_from(max_runs));
+    }
+}
+
+/// Permission from a [CompactionScheduler] to run a compaction.
+///
+/// Dropping the permit releases its concurrency and memory reservations.
+#[derive(Debug)]
+pub struct CompactionPermit {
+    id: u64,
+    inner: Arc<SchedulerInner>,
+}
+
+impl Drop for CompactionPermit {
+    fn drop(&mut self) {
+        self.inner.release(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+    use mz_ore::metrics::MetricsRegistry;
+
+    use super::*;
+
+    fn scheduler(
+        concurrency: usize,
+        shard_concurrency: usize,
+        memory: usize,
+    ) -> CompactionScheduler {
+        let mut cfg = PersistConfig::new_for_tests();
+        cfg.compaction_process_concurrency_limit = concurrency;
+        cfg.compaction_concurrency_limit = shard_concurrency;
+        cfg.compaction_memory_budget_bytes = memory;
+        let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
+        CompactionScheduler::new(&cfg, metrics)
+    }
+
+    fn debt(runs: usize) -> CompactionDebt {
+        CompactionDebt {
+            runs,
+            bytes_awaiting_merge: 0,
+        }
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn priority() {
+        let scheduler = scheduler(1, 1, usize::MAX);
+        let (s0, s1, s2) = (ShardId::new(), ShardId::new(), ShardId::new());
+
+        let running = scheduler.acquire(s0, debt(1), 0).await;
+        let mut cold = Box::pin(scheduler.acquire(s1, debt(2), 0));
+        let mut hot = Box::pin(scheduler.acquire(s2, debt(10), 0));
+        assert!((&mut cold).now_or_never().is_none());
+        assert!((&mut hot).now_or_never().is_none());
+
+        let queue = scheduler.queue();
+        let shards = queue
+            .iter()
+            .map(|x| (x.shard_id, x.state))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            shards,
+            vec![
+                (s0, CompactionTaskState::Running),
+                (s2, CompactionTaskState::Queued),
+                (s1, CompactionTaskState::Queued),
+            ]
+        );
+
+        // The request with the most debt goes next, even though it was queued
+        // later.
+        drop(running);
+        let hot = (&mut hot).now_or_never().expect("granted");
+        assert!((&mut cold).now_or_never().is_none());
+        drop(hot);
+        let _cold = cold.now_or_never().expect("granted");
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn limits() {
+        let scheduler = scheduler(3, 1, 10);
+        let (s0, s1, s2) = (ShardId::new(), ShardId::new(), ShardId::new());
+
+        // A second compaction of the same shard waits for the first.
+        let a = scheduler.acquire(s0, debt(5), 4).await;
+        let mut b = Box::pin(scheduler.acquire(s0, debt(5), 4));
+        assert!((&mut b).now_or_never().is_none());
+
+        // Other shards are unaffected, up to the memory budget.
+        let c = scheduler.acquire(s1, debt(1), 4).await;
+        let mut d = Box::pin(scheduler.acquire(s2, debt(1), 4));
+        assert!((&mut d).now_or_never().is_none());
+
+        drop(c);
+        let d = (&mut d).now_or_never().expect("granted");
+        drop(a);
+        let b = (&mut b).now_or_never().expect("granted");
+        drop((b, d));
+
+        // Something larger than the whole budget still runs once nothing else
+        // is running.
+        let big = scheduler.acquire(s0, debt(1), 100).await;
+        let mut small = Box::pin(scheduler.acquire(s1, debt(1), 1));
+        assert!((&mut small).now_or_never().is_none());
+        drop(big);
+        drop(small.now_or_never().expect("granted"));
+
+        // Dropping a waiting request removes it from the queue.
+        let a = scheduler.acquire(s0, debt(1), 0).await;
+        let mut b = Box::pin(scheduler.acquire(s0, debt(1), 0));
+        assert!((&mut b).now_or_never().is_none());
+        assert_eq!(scheduler.queue().len(), 2);
+        drop(b);
+        assert_eq!(scheduler.queue().len(), 1);
+        drop(a);
+        assert_eq!(scheduler.queue().len(), 0);
+    }
+}
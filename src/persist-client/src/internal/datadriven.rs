@@ -16,6 +16,7 @@ use differential_dataflow::trace::Description;
 use timely::progress::Antichain;
 use tokio::sync::Mutex;
 
+use crate::batch::PartCompression;
 use crate::internal::paths::PartialBatchKey;
 use crate::internal::state::{HollowBatch, HollowBatchPart};
 
@@ -106,6 +107,8 @@ impl<'a> DirectiveArgs<'a> {
                 .map(|x| HollowBatchPart {
                     key: PartialBatchKey((*x).to_owned()),
                     encoded_size_bytes: 0,
+                    compression: PartCompression::None,
+                    compressed_size_bytes: None,
                     stats: None,
                 })
                 .collect(),
@@ -31,17 +31,18 @@ use timely::PartialOrder;
 use tracing::debug;
 use uuid::Uuid;
 
+use crate::batch::PartCompression;
 use crate::critical::CriticalReaderId;
 use crate::error::{CodecMismatch, CodecMismatchT};
 use crate::internal::metrics::Metrics;
 use crate::internal::paths::{PartialBatchKey, PartialRollupKey};
 use crate::internal::state::{
-    CriticalReaderState, HandleDebugState, HollowBatch, HollowBatchPart, HollowRollup,
-    IdempotencyToken, LeasedReaderState, OpaqueState, ProtoCriticalReaderState,
+    CriticalReaderState, ForkState, HandleDebugState, HollowBatch, HollowBatchPart, HollowRollup,
+    IdempotencyToken, LeasedReaderState, OpaqueState, ProtoCriticalReaderState, ProtoForkState,
     ProtoHandleDebugState, ProtoHollowBatch, ProtoHollowBatchPart, ProtoHollowRollup,
-    ProtoLeasedReaderState, ProtoStateDiff, ProtoStateField, ProtoStateFieldDiffType,
-    ProtoStateFieldDiffs, ProtoStateRollup, ProtoTrace, ProtoU64Antichain, ProtoU64Description,
-    ProtoWriterState, State, StateCollections, TypedState, WriterState,
+    ProtoLeasedReaderState, ProtoPartCompression, ProtoStateDiff, ProtoStateField,
+    ProtoStateFieldDiffType, ProtoStateFieldDiffs, ProtoStateRollup, ProtoTrace, ProtoU64Antichain,
+    ProtoU64Description, ProtoWriterState, State, StateCollections, TypedState, WriterState,
 };
 use crate::internal::state_diff::{
     ProtoStateFieldDiff, ProtoStateFieldDiffsWriter, StateDiff, StateFieldDiff, StateFieldValDiff,
@@ -342,6 +343,7 @@ impl<T: Timestamp + Codec64> RustType<ProtoStateDiff> for StateDiff<T> {
             leased_readers,
             critical_readers,
             writers,
+            forks,
             since,
             spine,
         } = self;
@@ -361,6 +363,7 @@ impl<T: Timestamp + Codec64> RustType<ProtoStateDiff> for StateDiff<T> {
             &mut writer,
         );
         field_diffs_into_proto(ProtoStateField::Writers, writers, &mut writer);
+        field_diffs_into_proto(ProtoStateField::Forks, forks, &mut writer);
         field_diffs_into_proto(ProtoStateField::Since, since, &mut writer);
         field_diffs_into_proto(ProtoStateField::Spine, spine, &mut writer);
 
@@ -464,6 +467,14 @@ impl<T: Timestamp + Codec64> RustType<ProtoStateDiff> for StateDiff<T> {
                             |v| v.into_rust(),
                         )?
                     }
+                    ProtoStateField::Forks => {
+                        field_diff_into_rust::<String, ProtoForkState, _, _, _, _>(
+                            diff,
+                            &mut state_diff.forks,
+                            |k| k.into_rust(),
+                            |v| v.into_rust(),
+                        )?
+                    }
                     ProtoStateField::Since => {
                         field_diff_into_rust::<(), ProtoU64Antichain, _, _, _, _>(
                             diff,
@@ -640,6 +651,12 @@ where
                 .iter()
                 .map(|(id, state)| (id.into_proto(), state.into_proto()))
                 .collect(),
+            forks: self
+                .collections
+                .forks
+                .iter()
+                .map(|(id, state)| (id.into_proto(), state.into_proto()))
+                .collect(),
             trace: Some(self.collections.trace.into_proto()),
         }
     }
@@ -795,12 +812,17 @@ impl<T: Timestamp + Lattice + Codec64> RustType<ProtoStateRollup> for UntypedSta
         for (id, state) in x.writers {
             writers.insert(id.into_rust()?, state.into_rust()?);
         }
+        let mut forks = BTreeMap::new();
+        for (id, state) in x.forks {
+            forks.insert(id.into_rust()?, state.into_rust()?);
+        }
         let collections = StateCollections {
             rollups,
             last_gc_req: x.last_gc_req.into_rust()?,
             leased_readers,
             critical_readers,
             writers,
+            forks,
             trace: x.trace.into_rust_if_some("trace")?,
         };
         let state = State {
@@ -970,6 +992,24 @@ impl<T: Timestamp + Codec64> RustType<ProtoWriterState> for WriterState<T> {
     }
 }
 
+impl RustType<ProtoForkState> for ForkState {
+    fn into_proto(&self) -> ProtoForkState {
+        ProtoForkState {
+            seqno: self.seqno.into_proto(),
+            created_timestamp_ms: self.created_timestamp_ms.into_proto(),
+            lease_expiry_timestamp_ms: self.lease_expiry_timestamp_ms.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoForkState) -> Result<Self, TryFromProtoError> {
+        Ok(ForkState {
+            seqno: proto.seqno.into_rust()?,
+            created_timestamp_ms: proto.created_timestamp_ms.into_rust()?,
+            lease_expiry_timestamp_ms: proto.lease_expiry_timestamp_ms.into_rust()?,
+        })
+    }
+}
+
 impl RustType<ProtoHandleDebugState> for HandleDebugState {
     fn into_proto(&self) -> ProtoHandleDebugState {
         ProtoHandleDebugState {
@@ -1008,6 +1048,8 @@ impl<T: Timestamp + Codec64> RustType<ProtoHollowBatch> for HollowBatch<T> {
                 .map(|key| HollowBatchPart {
                     key: PartialBatchKey(key),
                     encoded_size_bytes: 0,
+                    compression: PartCompression::None,
+                    compressed_size_bytes: None,
                     stats: None,
                 }),
         );
@@ -1025,19 +1067,50 @@ impl RustType<ProtoHollowBatchPart> for HollowBatchPart {
         ProtoHollowBatchPart {
             key: self.key.into_proto(),
             encoded_size_bytes: self.encoded_size_bytes.into_proto(),
+            compression: self.compression.into_proto().into(),
+            compressed_size_bytes: self.compressed_size_bytes.into_proto(),
             key_stats: self.stats.into_proto(),
         }
     }
 
     fn from_proto(proto: ProtoHollowBatchPart) -> Result<Self, TryFromProtoError> {
+        // MIGRATION: Parts written before we supported compression don't have
+        // this field set, which decodes as the (correct) default of no
+        // compression.
+        let compression = ProtoPartCompression::from_i32(proto.compression).ok_or_else(|| {
+            TryFromProtoError::UnknownEnumVariant(format!(
+                "ProtoPartCompression::{}",
+                proto.compression
+            ))
+        })?;
         Ok(HollowBatchPart {
             key: proto.key.into_rust()?,
             encoded_size_bytes: proto.encoded_size_bytes.into_rust()?,
+            compression: compression.into_rust()?,
+            compressed_size_bytes: proto.compressed_size_bytes.into_rust()?,
             stats: proto.key_stats.into_rust()?,
         })
     }
 }
 
+impl RustType<ProtoPartCompression> for PartCompression {
+    fn into_proto(&self) -> ProtoPartCompression {
+        match self {
+            PartCompression::None => ProtoPartCompression::None,
+            PartCompression::Zstd => ProtoPartCompression::Zstd,
+            PartCompression::Lz4 => ProtoPartCompression::Lz4,
+        }
+    }
+
+    fn from_proto(proto: ProtoPartCompression) -> Result<Self, TryFromProtoError> {
+        Ok(match proto {
+            ProtoPartCompression::None => PartCompression::None,
+            ProtoPartCompression::Zstd => PartCompression::Zstd,
+            ProtoPartCompression::Lz4 => PartCompression::Lz4,
+        })
+    }
+}
+
 /// Aggregate statistics about data contained in a part.
 ///
 /// These are "lazy" in the sense that we don't decode them (or even validate
@@ -1282,6 +1355,8 @@ mod tests {
             parts: vec![HollowBatchPart {
                 key: PartialBatchKey("a".into()),
                 encoded_size_bytes: 5,
+                compression: PartCompression::None,
+                compressed_size_bytes: None,
                 stats: None,
             }],
             runs: vec![],
@@ -1300,6 +1375,8 @@ mod tests {
         expected.parts.push(HollowBatchPart {
             key: PartialBatchKey("b".into()),
             encoded_size_bytes: 0,
+            compression: PartCompression::None,
+            compressed_size_bytes: None,
             stats: None,
         });
         assert_eq!(<HollowBatch<u64>>::from_proto(old).unwrap(), expected);
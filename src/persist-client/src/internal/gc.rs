@@ -35,9 +35,9 @@ use crate::internal::machine::{retry_external, Machine};
 use crate::internal::maintenance::RoutineMaintenance;
 use crate::internal::metrics::{GcStepTimings, RetryMetrics};
 use crate::internal::paths::{BlobKey, PartialBatchKey, PartialBlobKey, PartialRollupKey};
-use crate::internal::state::HollowBlobRef;
+use crate::internal::state::{HollowBlobRef, State};
 use crate::internal::state_versions::{InspectDiff, StateVersionsIter};
-use crate::ShardId;
+use crate::{Diagnostics, ShardId};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct GcReq {
@@ -484,6 +484,11 @@ where
                 }
             });
 
+            // Parts owned by a shard we were forked from are that shard's to
+            // delete, but once we're done with them, it needs to hear about it.
+            let forked_from = Self::remove_forked_parts(&mut batch_parts_to_delete);
+            Self::release_fork_holds(states.state(), forked_from, machine, gc_results).await;
+
             gc_results.truncated_consensus_to.push(truncate_lt);
             gc_results.batch_parts_deleted_from_blob += batch_parts_to_delete.len();
             gc_results.rollups_deleted_from_blob += rollups_to_delete.len();
@@ -540,6 +545,64 @@ where
         timer(&metrics.find_deletable_blobs_seconds);
     }
 
+    /// Removes any parts owned by some other shard (see
+    /// [PartialBatchKey::owning_shard]) from `batch_parts`, returning the set
+    /// of owners.
+    fn remove_forked_parts(batch_parts: &mut BTreeSet<PartialBatchKey>) -> BTreeSet<ShardId> {
+        let mut owners = BTreeSet::new();
+        batch_parts.retain(|key| match key.owning_shard() {
+            Some(owner) => {
+                owners.insert(owner);
+                false
+            }
+            None => true,
+        });
+        owners
+    }
+
+    /// Releases the hold that each shard in `forked_from` has on its blobs on
+    /// behalf of this shard, if `state` no longer references any of them.
+    ///
+    /// A fork never gains a reference to a part owned by another shard after
+    /// it's created, so if `state` doesn't reference any, neither does any
+    /// later version. This runs before Consensus is truncated to `state`, so
+    /// if we crash in between, the next GC will find the same parts and
+    /// release the hold then.
+    async fn release_fork_holds(
+        state: &State<T>,
+        forked_from: BTreeSet<ShardId>,
+        machine: &Machine<K, V, T, D>,
+        gc_results: &mut GcResults,
+    ) {
+        for owner in forked_from {
+            let mut still_referenced = false;
+            state.map_blobs(|blob| {
+                if let HollowBlobRef::Batch(batch) = blob {
+                    still_referenced |= batch
+                        .parts
+                        .iter()
+                        .any(|x| x.key.owning_shard() == Some(owner));
+                }
+            });
+            if still_referenced {
+                continue;
+            }
+
+            let mut owner_machine = machine
+                .for_shard(owner, Diagnostics::from_purpose("gc release fork"))
+                .await
+                .expect("a fork has the same codecs as the shard it was forked from");
+            // Any gc this unblocks in the owner is picked up by its next state
+            // transition, so the maintenance can be dropped.
+            let (released, _maintenance) = owner_machine.release_fork(&machine.shard_id()).await;
+            if released {
+                debug!("released hold on {} for fork {}", owner, machine.shard_id());
+                machine.applier.metrics.gc.forks_released.inc();
+                gc_results.forks_released.push(owner);
+            }
+        }
+    }
+
     /// Deletes `batch_parts` and `rollups` from Blob.
     /// Truncates Consensus to `truncate_lt`.
     async fn delete_and_truncate<F>(
@@ -628,6 +691,7 @@ pub(crate) struct GcResults {
     pub(crate) rollups_deleted_from_blob: usize,
     pub(crate) truncated_consensus_to: Vec<SeqNo>,
     pub(crate) rollups_removed_from_state: Vec<SeqNo>,
+    pub(crate) forks_released: Vec<ShardId>,
 }
 
 #[derive(Debug)]
@@ -102,6 +102,20 @@ where
         })
     }
 
+    /// Returns a [Machine] for some other shard with the same codecs, sharing
+    /// this one's handles to the outside world.
+    pub async fn for_shard(
+        &self,
+        shard_id: ShardId,
+        diagnostics: Diagnostics,
+    ) -> Result<Self, Box<CodecMismatch>> {
+        let applier = self.applier.for_shard(shard_id, diagnostics).await?;
+        Ok(Machine {
+            applier,
+            isolated_runtime: Arc::clone(&self.isolated_runtime),
+        })
+    }
+
     pub fn shard_id(&self) -> ShardId {
         self.applier.shard_id
     }
@@ -208,6 +222,80 @@ where
         (state, maintenance)
     }
 
+    /// Forks this shard as of `since` into the new shard `fork_id`.
+    ///
+    /// See [crate::PersistClient::fork_shard] for details.
+    pub async fn fork(
+        &mut self,
+        fork_id: &ShardId,
+        since: &Antichain<T>,
+        diagnostics: &Diagnostics,
+    ) -> (Result<(), InvalidUsage<T>>, RoutineMaintenance) {
+        // First register a hold on everything the fork is about to reference,
+        // so that none of it can be deleted out from under it. The hold is
+        // leased, so that it expires if we crash before creating the fork.
+        let metrics = Arc::clone(&self.applier.metrics);
+        let shard_id = self.shard_id();
+        let lease_duration_ms = u64::try_from(self.applier.cfg.reader_lease_duration.as_millis())
+            .expect("lease duration as millis should fit within u64");
+        let (_seqno, batches, mut maintenance) = self
+            .apply_unbatched_idempotent_cmd(&metrics.cmds.register_fork, |seqno, cfg, state| {
+                state.register_fork(
+                    &shard_id,
+                    fork_id,
+                    seqno,
+                    since,
+                    (cfg.now)(),
+                    lease_duration_ms,
+                )
+            })
+            .await;
+        let batches = match batches {
+            Ok(x) => x,
+            Err(err) => return (Err(err), maintenance),
+        };
+
+        // Then create the fork, seeded with them.
+        let fork_metrics = metrics.shards.shard(fork_id, &diagnostics.shard_name);
+        let _fork_state = self
+            .applier
+            .state_versions
+            .init_forked_shard::<K, V, T, D>(&fork_metrics, &shard_id, since, batches)
+            .await;
+
+        // Finally, make the hold permanent. From here on, only the fork
+        // releases it. If the lease ran out in the meantime, the parts the fork
+        // was seeded with may already be gone, and the fork must not be used.
+        let (_seqno, confirmed, confirm_maintenance) = self
+            .apply_unbatched_idempotent_cmd(&metrics.cmds.confirm_fork, |_, _, state| {
+                state.confirm_fork(fork_id)
+            })
+            .await;
+        maintenance.merge(confirm_maintenance);
+        if !confirmed {
+            return (
+                Err(InvalidUsage::ForkLeaseExpired {
+                    shard_id,
+                    fork_id: *fork_id,
+                }),
+                maintenance,
+            );
+        }
+        (Ok(()), maintenance)
+    }
+
+    /// Releases the hold on this shard's blobs on behalf of the fork
+    /// `fork_id`, returning whether it existed.
+    pub async fn release_fork(&mut self, fork_id: &ShardId) -> (bool, RoutineMaintenance) {
+        let metrics = Arc::clone(&self.applier.metrics);
+        let (_seqno, existed, maintenance) = self
+            .apply_unbatched_idempotent_cmd(&metrics.cmds.release_fork, |_, _, state| {
+                state.release_fork(fork_id)
+            })
+            .await;
+        (existed, maintenance)
+    }
+
     pub async fn compare_and_append(
         &mut self,
         batch: &HollowBatch<T>,
@@ -1386,6 +1474,7 @@ pub mod datadriven {
                 datadriven.machine.applier.shard_metrics.as_ref(),
                 &datadriven.client.metrics.read.batch_fetcher,
                 &part.key,
+                part.compression,
                 &batch.desc,
             )
             .await
@@ -1564,6 +1653,7 @@ pub mod datadriven {
                     datadriven.machine.applier.shard_metrics.as_ref(),
                     &datadriven.client.metrics.read.batch_fetcher,
                     &part.key,
+                    part.compression,
                     &batch.desc,
                 )
                 .await
@@ -34,6 +34,7 @@ use prometheus::{CounterVec, Gauge, GaugeVec, Histogram, HistogramVec, IntCounte
 use timely::progress::Antichain;
 use tracing::instrument;
 
+use crate::batch::PartCompression;
 use crate::internal::paths::BlobKey;
 use crate::{PersistConfig, ShardId};
 
@@ -67,6 +68,8 @@ pub struct Metrics {
     pub lease: LeaseMetrics,
     /// Metrics for various encodings and decodings.
     pub codecs: CodecsMetrics,
+    /// Metrics for batch part compression.
+    pub compression: PartCompressionMetrics,
     /// Metrics for (incremental) state updates and fetches.
     pub state: StateMetrics,
     /// Metrics for various per-shard measurements.
@@ -115,6 +118,7 @@ impl Metrics {
             cmds: vecs.cmds_metrics(registry),
             retries: vecs.retries_metrics(),
             codecs: vecs.codecs_metrics(),
+            compression: PartCompressionMetrics::new(registry),
             user: BatchWriteMetrics::new(registry, "user"),
             read: vecs.batch_part_read_metrics(),
             compaction: CompactionMetrics::new(registry),
@@ -150,6 +154,19 @@ impl Metrics {
             total_written as f64 / user_written as f64
         }
     }
+
+    /// Returns the lifetime ratio of compressed to uncompressed bytes for
+    /// batch parts written with compression enabled.
+    ///
+    /// Only exposed for tests, persistcli, and benchmarks.
+    pub fn compression_ratio(&self) -> f64 {
+        let compressed = self.compression.compressed_bytes.get();
+        let uncompressed = self.compression.uncompressed_bytes.get();
+        #[allow(clippy::as_conversions)]
+        {
+            compressed as f64 / uncompressed as f64
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -376,6 +393,9 @@ impl MetricsVecs {
             expire_writer: self.cmd_metrics("expire_writer"),
             merge_res: self.cmd_metrics("merge_res"),
             become_tombstone: self.cmd_metrics("become_tombstone"),
+            register_fork: self.cmd_metrics("register_fork"),
+            confirm_fork: self.cmd_metrics("confirm_fork"),
+            release_fork: self.cmd_metrics("release_fork"),
         }
     }
 
@@ -576,6 +596,9 @@ pub struct CmdsMetrics {
     pub(crate) expire_writer: CmdMetrics,
     pub(crate) merge_res: CmdMetrics,
     pub(crate) become_tombstone: CmdMetrics,
+    pub(crate) register_fork: CmdMetrics,
+    pub(crate) confirm_fork: CmdMetrics,
+    pub(crate) release_fork: CmdMetrics,
 }
 
 #[derive(Debug)]
@@ -701,6 +724,70 @@ impl BatchWriteMetrics {
     }
 }
 
+#[derive(Debug)]
+pub struct PartCompressionMetrics {
+    pub(crate) compressed_bytes: IntCounter,
+    pub(crate) uncompressed_bytes: IntCounter,
+    pub(crate) compress_seconds: Counter,
+    pub(crate) decompress_seconds: Counter,
+    pub(crate) incompressible_parts: IntCounter,
+    pub(crate) ratio: Histogram,
+}
+
+impl PartCompressionMetrics {
+    fn new(registry: &MetricsRegistry) -> Self {
+        PartCompressionMetrics {
+            compressed_bytes: registry.register(metric!(
+                name: "mz_persist_compression_compressed_bytes",
+                help: "total size of batch parts after compression",
+            )),
+            uncompressed_bytes: registry.register(metric!(
+                name: "mz_persist_compression_uncompressed_bytes",
+                help: "total size of batch parts before compression",
+            )),
+            compress_seconds: registry.register(metric!(
+                name: "mz_persist_compression_compress_seconds",
+                help: "time spent compressing batch parts",
+            )),
+            decompress_seconds: registry.register(metric!(
+                name: "mz_persist_compression_decompress_seconds",
+                help: "time spent decompressing batch parts",
+            )),
+            incompressible_parts: registry.register(metric!(
+                name: "mz_persist_compression_incompressible_parts",
+                help: "count of batch parts written uncompressed because compression didn't shrink them",
+            )),
+            ratio: registry.register(metric!(
+                name: "mz_persist_compression_ratio",
+                help: "histogram of the ratio of compressed to uncompressed size of batch parts",
+                buckets: prometheus::linear_buckets(0.1, 0.1, 10).expect("buckets"),
+            )),
+        }
+    }
+
+    /// Records the result of compressing a single batch part with the
+    /// configured codec, `applied` being the codec that was actually used.
+    pub(crate) fn record_compress(
+        &self,
+        applied: PartCompression,
+        uncompressed_len: usize,
+        compressed_len: usize,
+        elapsed: Duration,
+    ) {
+        self.compress_seconds.inc_by(elapsed.as_secs_f64());
+        self.uncompressed_bytes
+            .inc_by(u64::cast_from(uncompressed_len));
+        self.compressed_bytes.inc_by(u64::cast_from(compressed_len));
+        if applied.is_none() {
+            self.incompressible_parts.inc();
+        }
+        if uncompressed_len > 0 {
+            self.ratio
+                .observe(f64::cast_lossy(compressed_len) / f64::cast_lossy(uncompressed_len));
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct CompactionMetrics {
     pub(crate) requested: IntCounter,
@@ -728,6 +815,7 @@ pub struct CompactionMetrics {
 
     pub(crate) batch: BatchWriteMetrics,
     pub(crate) steps: CompactionStepTimings,
+    pub(crate) scheduler: CompactionSchedulerMetrics,
 
     pub(crate) _steps_vec: CounterVec,
 }
@@ -779,7 +867,7 @@ impl CompactionMetrics {
             )),
             concurrency_waits: registry.register(metric!(
                 name: "mz_persist_compaction_concurrency_waits",
-                help: "count of compaction requests that ever blocked due to concurrency limit",
+                help: "count of compaction requests that ever blocked waiting for the compaction scheduler",
             )),
             queued_seconds: registry.register(metric!(
                 name: "mz_persist_compaction_queued_seconds",
@@ -827,11 +915,48 @@ impl CompactionMetrics {
             )),
             batch: BatchWriteMetrics::new(registry, "compaction"),
             steps: CompactionStepTimings::new(step_timings.clone()),
+            scheduler: CompactionSchedulerMetrics::new(registry),
             _steps_vec: step_timings,
         }
     }
 }
 
+#[derive(Debug)]
+pub struct CompactionSchedulerMetrics {
+    pub(crate) queued: UIntGauge,
+    pub(crate) running: UIntGauge,
+    pub(crate) memory_in_use_bytes: UIntGauge,
+    pub(crate) max_queued_runs: UIntGauge,
+    pub(crate) memory_waits: IntCounter,
+}
+
+impl CompactionSchedulerMetrics {
+    fn new(registry: &MetricsRegistry) -> Self {
+        CompactionSchedulerMetrics {
+            queued: registry.register(metric!(
+                name: "mz_persist_compaction_scheduler_queued",
+                help: "count of compaction requests waiting for a permit",
+            )),
+            running: registry.register(metric!(
+                name: "mz_persist_compaction_scheduler_running",
+                help: "count of compaction requests holding a permit",
+            )),
+            memory_in_use_bytes: registry.register(metric!(
+                name: "mz_persist_compaction_scheduler_memory_in_use_bytes",
+                help: "memory reserved by compaction requests holding a permit",
+            )),
+            max_queued_runs: registry.register(metric!(
+                name: "mz_persist_compaction_scheduler_max_queued_runs",
+                help: "largest run count of any shard with a compaction request waiting for a permit",
+            )),
+            memory_waits: registry.register(metric!(
+                name: "mz_persist_compaction_scheduler_memory_waits",
+                help: "count of times the next compaction request was held back by the memory budget",
+            )),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct CompactionStepTimings {
     pub(crate) part_fetch_seconds: Counter,
@@ -854,6 +979,7 @@ pub struct GcMetrics {
     pub(crate) finished: IntCounter,
     pub(crate) merged: IntCounter,
     pub(crate) seconds: Counter,
+    pub(crate) forks_released: IntCounter,
     pub(crate) steps: GcStepTimings,
 }
 
@@ -913,6 +1039,10 @@ impl GcMetrics {
                 name: "mz_persist_gc_seconds",
                 help: "time spent in garbage collections",
             )),
+            forks_released: registry.register(metric!(
+                name: "mz_persist_gc_forks_released",
+                help: "count of holds on the shard a fork was forked from released by gc",
+            )),
             steps: GcStepTimings::new(step_timings),
         }
     }
@@ -921,6 +1051,7 @@ impl GcMetrics {
 #[derive(Debug)]
 pub struct LeaseMetrics {
     pub(crate) timeout_read: IntCounter,
+    pub(crate) timeout_fork: IntCounter,
     pub(crate) dropped_part: IntCounter,
 }
 
@@ -931,6 +1062,10 @@ impl LeaseMetrics {
                 name: "mz_persist_lease_timeout_read",
                 help: "count of readers whose lease timed out",
             )),
+            timeout_fork: registry.register(metric!(
+                name: "mz_persist_lease_timeout_fork",
+                help: "count of holds of unfinished forks whose lease timed out",
+            )),
             dropped_part: registry.register(metric!(
                 name: "mz_persist_lease_dropped_part",
                 help: "count of LeasedBatchParts that were dropped without being politely returned",
@@ -1145,6 +1280,8 @@ pub struct ShardsMetrics {
     blob_gets: mz_ore::metrics::IntCounterVec,
     blob_sets: mz_ore::metrics::IntCounterVec,
     live_writers: mz_ore::metrics::UIntGaugeVec,
+    compaction_debt_runs: mz_ore::metrics::UIntGaugeVec,
+    compaction_debt_bytes: mz_ore::metrics::UIntGaugeVec,
     // We hand out `Arc<ShardMetrics>` to read and write handles, but store it
     // here as `Weak`. This allows us to discover if it's no longer in use and
     // so we can remove it from the map.
@@ -1307,6 +1444,16 @@ impl ShardsMetrics {
                 help: "number of writers that have recently appended updates to this shard",
                 var_labels: ["shard", "name"],
             )),
+            compaction_debt_runs: registry.register(metric!(
+                name: "mz_persist_shard_compaction_debt_runs",
+                help: "count of sorted runs across all batches by shard",
+                var_labels: ["shard", "name"],
+            )),
+            compaction_debt_bytes: registry.register(metric!(
+                name: "mz_persist_shard_compaction_debt_bytes",
+                help: "encoded size of batches awaiting a merge by shard",
+                var_labels: ["shard", "name"],
+            )),
             shards,
         }
     }
@@ -1379,6 +1526,8 @@ pub struct ShardMetrics {
     pub blob_gets: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
     pub blob_sets: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
     pub live_writers: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
+    pub compaction_debt_runs: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
+    pub compaction_debt_bytes: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
 }
 
 impl ShardMetrics {
@@ -1469,6 +1618,12 @@ impl ShardMetrics {
                 .get_delete_on_drop_counter(vec![shard.clone(), name.to_string()]),
             live_writers: shards_metrics
                 .live_writers
+                .get_delete_on_drop_gauge(vec![shard.clone(), name.to_string()]),
+            compaction_debt_runs: shards_metrics
+                .compaction_debt_runs
+                .get_delete_on_drop_gauge(vec![shard.clone(), name.to_string()]),
+            compaction_debt_bytes: shards_metrics
+                .compaction_debt_bytes
                 .get_delete_on_drop_gauge(vec![shard, name.to_string()]),
         }
     }
@@ -116,6 +116,11 @@ impl Display for WriterKey {
 /// Used to reduce the bytes needed to refer to a blob key in memory and in
 /// persistent state, all access to blobs are always within the context of an
 /// individual shard.
+///
+/// The one exception is a shard created by forking another one: the parts it
+/// shares with the shard it was forked from are owned by that shard, and so
+/// are referenced by a key that is already qualified with the owning
+/// [ShardId]. See [Self::owned_by].
 #[derive(Arbitrary, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct PartialBatchKey(pub(crate) String);
 
@@ -124,8 +129,37 @@ impl PartialBatchKey {
         PartialBatchKey(format!("{}/{}", version, part_id))
     }
 
+    /// Returns a key for this part that refers to it as owned by the given
+    /// shard, regardless of which shard's state it ends up referenced from.
+    ///
+    /// This is a no-op if the key already names an owning shard.
+    pub fn owned_by(&self, shard_id: &ShardId) -> Self {
+        if self.owning_shard().is_some() {
+            self.clone()
+        } else {
+            PartialBatchKey(format!("{}/{}", shard_id, self))
+        }
+    }
+
+    /// Returns the shard that owns this part, if it's not the shard whose
+    /// state references it.
+    ///
+    /// Shard ids and writer keys have distinct prefixes, so a key naming an
+    /// owning shard is unambiguous.
+    pub fn owning_shard(&self) -> Option<ShardId> {
+        if !self.0.starts_with('s') {
+            return None;
+        }
+        let (shard, _) = self.0.split_once('/')?;
+        ShardId::from_str(shard).ok()
+    }
+
     pub fn complete(&self, shard_id: &ShardId) -> BlobKey {
-        BlobKey(format!("{}/{}", shard_id, self))
+        if self.owning_shard().is_some() {
+            BlobKey(self.0.clone())
+        } else {
+            BlobKey(format!("{}/{}", shard_id, self))
+        }
     }
 }
 
@@ -323,6 +357,36 @@ mod tests {
         );
     }
 
+    #[mz_ore::test]
+    fn partial_blob_key_owned_by() {
+        let (shard_id, owner_id, writer_id, part_id) = (
+            ShardId::new(),
+            ShardId::new(),
+            WriterId::new(),
+            PartId::new(),
+        );
+        let partial_key = PartialBatchKey::new(&WriterKey::Id(writer_id.clone()), &part_id);
+        assert_eq!(partial_key.owning_shard(), None);
+
+        let owned_key = partial_key.owned_by(&owner_id);
+        assert_eq!(owned_key.owning_shard(), Some(owner_id));
+        // Re-qualifying a key keeps the original owner.
+        assert_eq!(owned_key.owned_by(&shard_id), owned_key);
+        // An owned key completes to the owner's blob, no matter which shard
+        // references it.
+        assert_eq!(
+            owned_key.complete(&shard_id),
+            partial_key.complete(&owner_id)
+        );
+        assert_eq!(
+            BlobKey::parse_ids(&owned_key.complete(&shard_id)),
+            Ok((
+                owner_id,
+                PartialBlobKey::Batch(WriterKey::Id(writer_id), part_id)
+            ))
+        );
+    }
+
     #[mz_ore::test]
     fn blob_key_parse() -> Result<(), String> {
         let (shard_id, writer_id, part_id) = (ShardId::new(), WriterId::new(), PartId::new());
@@ -32,8 +32,10 @@ use timely::PartialOrder;
 use tracing::info;
 use uuid::Uuid;
 
+use crate::batch::PartCompression;
 use crate::critical::CriticalReaderId;
 use crate::error::{Determinacy, InvalidUsage};
+use crate::internal::compact_scheduler::CompactionDebt;
 use crate::internal::encoding::{parse_id, LazyPartStats};
 use crate::internal::gc::GcReq;
 use crate::internal::paths::{PartialBatchKey, PartialRollupKey};
@@ -145,6 +147,30 @@ pub struct WriterState<T> {
     pub debug: HandleDebugState,
 }
 
+/// A hold on the blobs of a shard on behalf of a shard that was forked from
+/// it.
+///
+/// A fork shares the parts of the shard it was forked from instead of copying
+/// them. Like a leased reader, the hold is a seqno capability: no blob that is
+/// referenced by a version of state at or after [Self::seqno] is deleted until
+/// the fork releases the hold, which its GC does once none of its live
+/// versions of state reference any of these parts anymore.
+///
+/// The hold is registered before the fork is created. Until the fork confirms
+/// that it exists, the hold is leased, so that it expires if the process
+/// crashes in between.
+#[derive(Arbitrary, Clone, Debug, PartialEq, Serialize)]
+pub struct ForkState {
+    /// The seqno capability of this fork.
+    pub seqno: SeqNo,
+    /// UNIX_EPOCH timestamp (in millis) of when this fork was created. For
+    /// debugging.
+    pub created_timestamp_ms: u64,
+    /// UNIX_EPOCH timestamp (in millis) after which this hold expires, or
+    /// `None` once the fork has been created.
+    pub lease_expiry_timestamp_ms: Option<u64>,
+}
+
 /// Debugging info for a reader or writer.
 #[derive(Arbitrary, Clone, Debug, Default, PartialEq, Serialize)]
 pub struct HandleDebugState {
@@ -160,14 +186,42 @@ pub struct HandleDebugState {
 pub struct HollowBatchPart {
     /// Pointer usable to retrieve the updates.
     pub key: PartialBatchKey,
-    /// The encoded size of this part.
+    /// The encoded size of this part, before any compression.
+    ///
+    /// This is the size of the part once fetched and decompressed, and so what
+    /// compaction and reads budget memory with.
     pub encoded_size_bytes: usize,
+    /// The compression codec applied to the encoded part before it was written
+    /// to blob.
+    ///
+    /// NB: The `proptest` and `serde` attributes keep the `state_serde.json`
+    /// golden (and the inspect output of uncompressed parts) stable.
+    #[serde(skip_serializing_if = "PartCompression::is_none")]
+    #[proptest(value = "PartCompression::None")]
+    pub compression: PartCompression,
+    /// The size of this part in blob, if it was compressed.
+    ///
+    /// Uncompressed parts are stored exactly as encoded, see
+    /// [Self::stored_size_bytes].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[proptest(value = "None")]
+    pub compressed_size_bytes: Option<usize>,
     /// Aggregate statistics about data contained in this part.
     #[serde(serialize_with = "serialize_part_stats")]
     #[proptest(strategy = "super::encoding::any_some_lazy_part_stats()")]
     pub stats: Option<LazyPartStats>,
 }
 
+impl HollowBatchPart {
+    /// The size of this part in blob.
+    pub fn stored_size_bytes(&self) -> usize {
+        match self.compressed_size_bytes {
+            Some(compressed_size_bytes) => compressed_size_bytes,
+            None => self.encoded_size_bytes,
+        }
+    }
+}
+
 /// A [Batch] but with the updates themselves stored externally.
 ///
 /// [Batch]: differential_dataflow::trace::BatchReader
@@ -355,6 +409,10 @@ pub struct StateCollections<T> {
     pub(crate) critical_readers: BTreeMap<CriticalReaderId, CriticalReaderState<T>>,
     pub(crate) writers: BTreeMap<WriterId, WriterState<T>>,
 
+    // - Invariant: Every part with a key owned by another shard is covered by
+    //   a fork hold in that shard's state.
+    pub(crate) forks: BTreeMap<ShardId, ForkState>,
+
     // - Invariant: `trace.since == meet(all reader.since)`
     // - Invariant: `trace.since` doesn't regress across state versions.
     // - Invariant: `trace.upper` doesn't regress across state versions.
@@ -504,6 +562,126 @@ where
         Continue(state)
     }
 
+    /// Registers a hold on the blobs of this shard on behalf of `fork_id`,
+    /// returning the batches the fork is to be seeded with.
+    ///
+    /// The fork will be readable as of `since`, so this requires that a
+    /// consistent snapshot is available at it.
+    pub fn register_fork(
+        &mut self,
+        shard_id: &ShardId,
+        fork_id: &ShardId,
+        seqno: SeqNo,
+        since: &Antichain<T>,
+        created_timestamp_ms: u64,
+        lease_duration_ms: u64,
+    ) -> ControlFlow<
+        NoOpStateTransition<Result<Vec<HollowBatch<T>>, InvalidUsage<T>>>,
+        Result<Vec<HollowBatch<T>>, InvalidUsage<T>>,
+    > {
+        // NB: This also catches tombstones, which have an empty since and
+        // upper.
+        if !PartialOrder::less_equal(self.trace.since(), since)
+            || PartialOrder::less_equal(self.trace.upper(), since)
+        {
+            return Break(NoOpStateTransition(Err(InvalidUsage::InvalidForkSince {
+                since: since.clone(),
+                shard_since: self.trace.since().clone(),
+                shard_upper: self.trace.upper().clone(),
+            })));
+        }
+
+        let batches = self
+            .trace
+            .batches()
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        // The hold registered here only covers the parts owned by this shard,
+        // so refuse to hand out parts that are only kept alive by a hold in
+        // some other shard. The fork can be forked once it has compacted them
+        // away.
+        let owner = batches
+            .iter()
+            .flat_map(|b| b.parts.iter())
+            .find_map(|part| part.key.owning_shard());
+        if let Some(owner) = owner {
+            return Break(NoOpStateTransition(Err(InvalidUsage::ForkOfFork {
+                shard_id: *shard_id,
+                owner,
+            })));
+        }
+
+        // If this fork already has a hold, it's at an earlier seqno, which
+        // covers everything referenced by this one too.
+        self.forks.entry(*fork_id).or_insert_with(|| ForkState {
+            seqno,
+            created_timestamp_ms,
+            lease_expiry_timestamp_ms: Some(created_timestamp_ms.saturating_add(lease_duration_ms)),
+        });
+        Continue(Ok(batches))
+    }
+
+    /// Turns the leased hold registered by [Self::register_fork] into one that
+    /// only the fork releases, now that the fork has been created.
+    ///
+    /// Returns whether the hold still existed. If it didn't, it expired before
+    /// the fork was created, and the fork must not be used.
+    pub fn confirm_fork(
+        &mut self,
+        fork_id: &ShardId,
+    ) -> ControlFlow<NoOpStateTransition<bool>, bool> {
+        match self.forks.get_mut(fork_id) {
+            None => Break(NoOpStateTransition(false)),
+            Some(ForkState {
+                lease_expiry_timestamp_ms: None,
+                ..
+            }) => Break(NoOpStateTransition(true)),
+            Some(fork) => {
+                fork.lease_expiry_timestamp_ms = None;
+                Continue(true)
+            }
+        }
+    }
+
+    /// Releases the hold registered by [Self::register_fork], if any.
+    ///
+    /// Returns whether there was a hold to release.
+    pub fn release_fork(
+        &mut self,
+        fork_id: &ShardId,
+    ) -> ControlFlow<NoOpStateTransition<bool>, bool> {
+        // NB: Unlike most commands, this is allowed on a tombstone, so that a
+        // fork of a since-finalized shard can still let go of its blobs.
+        if self.forks.remove(fork_id).is_none() {
+            return Break(NoOpStateTransition(false));
+        }
+        Continue(true)
+    }
+
+    /// Seeds the (empty) trace of a newly created fork with the batches of
+    /// the shard `owner` it was forked from, as returned by
+    /// [Self::register_fork].
+    pub fn ingest_fork(
+        &mut self,
+        owner: &ShardId,
+        since: &Antichain<T>,
+        batches: Vec<HollowBatch<T>>,
+    ) {
+        assert_eq!(self.trace.upper(), &Antichain::from_elem(T::minimum()));
+        let mut trace = Trace::default();
+        for mut batch in batches {
+            for part in batch.parts.iter_mut() {
+                part.key = part.key.owned_by(owner);
+            }
+            // The fork is free to compact these whenever it gets around to
+            // it, so there's no need to hold on to the merge reqs.
+            let _merge_reqs = trace.push_batch(batch);
+        }
+        trace.downgrade_since(since);
+        self.trace = trace;
+    }
+
     pub fn compare_and_append(
         &mut self,
         batch: &HollowBatch<T>,
@@ -889,6 +1067,9 @@ where
         for cap in self.leased_readers.values() {
             seqno_since = std::cmp::min(seqno_since, cap.seqno);
         }
+        for fork in self.forks.values() {
+            seqno_since = std::cmp::min(seqno_since, fork.seqno);
+        }
         // critical_readers don't hold a seqno capability.
         seqno_since
     }
@@ -1060,6 +1241,7 @@ where
                 leased_readers: BTreeMap::new(),
                 critical_readers: BTreeMap::new(),
                 writers: BTreeMap::new(),
+                forks: BTreeMap::new(),
                 trace: Trace::default(),
             },
         };
@@ -1124,6 +1306,10 @@ where
         self.collections.trace.num_spine_batches()
     }
 
+    pub fn compaction_debt(&self) -> CompactionDebt {
+        self.collections.trace.compaction_debt()
+    }
+
     pub fn size_metrics(&self) -> StateSizeMetrics {
         let mut ret = StateSizeMetrics::default();
         self.map_blobs(|x| match x {
@@ -1232,6 +1418,16 @@ where
             }
             retain
         });
+        self.collections.forks.retain(|k, v| {
+            let retain = v
+                .lease_expiry_timestamp_ms
+                .map_or(true, |expiry| expiry >= walltime_ms);
+            if !retain {
+                info!("Force expiring hold of unfinished fork ({k}) of shard ({shard_id})");
+                metrics.forks_expired += 1;
+            }
+            retain
+        });
         metrics
     }
 
@@ -1363,10 +1559,11 @@ impl<T: Serialize> Serialize for State<T> {
                     leased_readers,
                     critical_readers,
                     writers,
+                    forks,
                     trace,
                 },
         } = self;
-        let mut s = s.serialize_struct("State", 13)?;
+        let mut s = s.serialize_struct("State", 14)?;
         let () = s.serialize_field("applier_version", &applier_version.to_string())?;
         let () = s.serialize_field("shard_id", shard_id)?;
         let () = s.serialize_field("seqno", seqno)?;
@@ -1377,6 +1574,9 @@ impl<T: Serialize> Serialize for State<T> {
         let () = s.serialize_field("leased_readers", leased_readers)?;
         let () = s.serialize_field("critical_readers", critical_readers)?;
         let () = s.serialize_field("writers", writers)?;
+        if !forks.is_empty() {
+            let () = s.serialize_field("forks", forks)?;
+        }
         let () = s.serialize_field("since", &trace.since().elements())?;
         let () = s.serialize_field("upper", &trace.upper().elements())?;
         let () = s.serialize_field("batches", &trace.batches().into_iter().collect::<Vec<_>>())?;
@@ -1399,6 +1599,7 @@ pub struct StateSizeMetrics {
 pub struct ExpiryMetrics {
     pub(crate) readers_expired: usize,
     pub(crate) writers_expired: usize,
+    pub(crate) forks_expired: usize,
 }
 
 /// Wrapper for Antichain that represents a Since
@@ -1588,6 +1789,7 @@ pub(crate) mod tests {
                     leased_readers,
                     critical_readers,
                     writers,
+                    forks: BTreeMap::new(),
                     trace,
                 },
             },
@@ -1606,6 +1808,8 @@ pub(crate) mod tests {
                 .map(|x| HollowBatchPart {
                     key: PartialBatchKey((*x).to_owned()),
                     encoded_size_bytes: 0,
+                    compression: PartCompression::None,
+                    compressed_size_bytes: None,
                     stats: None,
                 })
                 .collect(),
@@ -2143,6 +2347,150 @@ pub(crate) mod tests {
             .is_continue());
     }
 
+    #[mz_ore::test]
+    fn fork() {
+        let shard_id = ShardId::new();
+        let mut state = TypedState::<String, String, u64, i64>::new(
+            DUMMY_BUILD_INFO.semver_version(),
+            shard_id,
+            "".to_owned(),
+            0,
+        );
+        let now = SYSTEM_TIME.clone();
+        let writer_id = WriterId::new();
+        assert!(state
+            .collections
+            .compare_and_append(
+                &hollow(0, 5, &["key1"], 1),
+                &writer_id,
+                now(),
+                LEASE_DURATION_MS,
+                &IdempotencyToken::new(),
+                &debug_state(),
+            )
+            .is_continue());
+
+        // Can't fork at a since that's not yet readable.
+        let fork_id = ShardId::new();
+        assert!(matches!(
+            state.collections.register_fork(
+                &shard_id,
+                &fork_id,
+                SeqNo(3),
+                &Antichain::from_elem(5),
+                now(),
+                LEASE_DURATION_MS
+            ),
+            Break(NoOpStateTransition(Err(
+                InvalidUsage::InvalidForkSince { .. }
+            )))
+        ));
+        assert_eq!(state.collections.forks.len(), 0);
+
+        // A successful fork hands out the trace and holds back seqno_since.
+        let batches = match state.collections.register_fork(
+            &shard_id,
+            &fork_id,
+            SeqNo(3),
+            &Antichain::from_elem(2),
+            now(),
+            LEASE_DURATION_MS,
+        ) {
+            Continue(Ok(batches)) => batches,
+            x => panic!("unexpected result: {:?}", x),
+        };
+        assert_eq!(batches, vec![hollow(0, 5, &["key1"], 1)]);
+        assert_eq!(state.collections.seqno_since(SeqNo(10)), SeqNo(3));
+
+        // Registering the same fork again keeps the original hold.
+        assert!(state
+            .collections
+            .register_fork(
+                &shard_id,
+                &fork_id,
+                SeqNo(7),
+                &Antichain::from_elem(2),
+                now(),
+                LEASE_DURATION_MS
+            )
+            .is_continue());
+        assert_eq!(state.collections.seqno_since(SeqNo(10)), SeqNo(3));
+
+        // The fork's parts are all owned by the source shard and so it can't
+        // itself be forked.
+        let mut fork = TypedState::<String, String, u64, i64>::new(
+            DUMMY_BUILD_INFO.semver_version(),
+            fork_id,
+            "".to_owned(),
+            0,
+        );
+        fork.collections
+            .ingest_fork(&shard_id, &Antichain::from_elem(2), batches);
+        assert_eq!(fork.collections.trace.since(), &Antichain::from_elem(2));
+        assert_eq!(fork.collections.trace.upper(), &Antichain::from_elem(5));
+        fork.collections.trace.map_batches(|b| {
+            for part in b.parts.iter() {
+                assert_eq!(part.key.owning_shard(), Some(shard_id));
+            }
+        });
+        assert!(matches!(
+            fork.collections.register_fork(
+                &fork_id,
+                &ShardId::new(),
+                SeqNo(0),
+                &Antichain::from_elem(2),
+                now(),
+                LEASE_DURATION_MS
+            ),
+            Break(NoOpStateTransition(Err(InvalidUsage::ForkOfFork { .. })))
+        ));
+
+        // Confirming the fork makes the hold permanent, idempotently.
+        assert_eq!(state.collections.confirm_fork(&fork_id), Continue(true));
+        assert_eq!(
+            state.collections.confirm_fork(&fork_id),
+            Break(NoOpStateTransition(true))
+        );
+        assert_eq!(
+            state.collections.forks[&fork_id].lease_expiry_timestamp_ms,
+            None
+        );
+        state.expire_at(u64::MAX);
+        assert_eq!(state.collections.seqno_since(SeqNo(10)), SeqNo(3));
+
+        // Releasing the hold is idempotent.
+        assert_eq!(state.collections.release_fork(&fork_id), Continue(true));
+        assert_eq!(state.collections.seqno_since(SeqNo(10)), SeqNo(10));
+        assert_eq!(
+            state.collections.release_fork(&fork_id),
+            Break(NoOpStateTransition(false))
+        );
+
+        // The hold of a fork that is never created expires with its lease, and
+        // can then no longer be confirmed.
+        let fork_id = ShardId::new();
+        let created = now();
+        assert!(state
+            .collections
+            .register_fork(
+                &shard_id,
+                &fork_id,
+                SeqNo(8),
+                &Antichain::from_elem(2),
+                created,
+                LEASE_DURATION_MS
+            )
+            .is_continue());
+        state.expire_at(created + LEASE_DURATION_MS);
+        assert_eq!(state.collections.seqno_since(SeqNo(10)), SeqNo(8));
+        state.expire_at(created + LEASE_DURATION_MS + 1);
+        assert_eq!(state.collections.seqno_since(SeqNo(10)), SeqNo(10));
+        assert_eq!(
+            state.collections.confirm_fork(&fork_id),
+            Break(NoOpStateTransition(false))
+        );
+    }
+
     #[mz_ore::test]
     fn maybe_gc() {
         let mut state = TypedState::<String, String, u64, i64>::new(
@@ -25,7 +25,7 @@ use tracing::{debug, error};
 use crate::critical::CriticalReaderId;
 use crate::internal::paths::PartialRollupKey;
 use crate::internal::state::{
-    CriticalReaderState, HollowBatch, HollowBlobRef, HollowRollup, LeasedReaderState,
+    CriticalReaderState, ForkState, HollowBatch, HollowBlobRef, HollowRollup, LeasedReaderState,
     ProtoStateField, ProtoStateFieldDiffType, ProtoStateFieldDiffs, State, StateCollections,
     WriterState,
 };
@@ -76,6 +76,7 @@ pub struct StateDiff<T> {
     pub(crate) leased_readers: Vec<StateFieldDiff<LeasedReaderId, LeasedReaderState<T>>>,
     pub(crate) critical_readers: Vec<StateFieldDiff<CriticalReaderId, CriticalReaderState<T>>>,
     pub(crate) writers: Vec<StateFieldDiff<WriterId, WriterState<T>>>,
+    pub(crate) forks: Vec<StateFieldDiff<ShardId, ForkState>>,
     pub(crate) since: Vec<StateFieldDiff<(), Antichain<T>>>,
     pub(crate) spine: Vec<StateFieldDiff<HollowBatch<T>, ()>>,
 }
@@ -100,6 +101,7 @@ impl<T: Timestamp + Codec64> StateDiff<T> {
             leased_readers: Vec::default(),
             critical_readers: Vec::default(),
             writers: Vec::default(),
+            forks: Vec::default(),
             since: Vec::default(),
             spine: Vec::default(),
         }
@@ -123,6 +125,7 @@ impl<T: Timestamp + Lattice + Codec64> StateDiff<T> {
                     leased_readers: from_leased_readers,
                     critical_readers: from_critical_readers,
                     writers: from_writers,
+                    forks: from_forks,
                     trace: from_trace,
                 },
         } = from;
@@ -139,6 +142,7 @@ impl<T: Timestamp + Lattice + Codec64> StateDiff<T> {
                     leased_readers: to_leased_readers,
                     critical_readers: to_critical_readers,
                     writers: to_writers,
+                    forks: to_forks,
                     trace: to_trace,
                 },
         } = to;
@@ -166,6 +170,7 @@ impl<T: Timestamp + Lattice + Codec64> StateDiff<T> {
             &mut diffs.critical_readers,
         );
         diff_field_sorted_iter(from_writers.iter(), to_writers, &mut diffs.writers);
+        diff_field_sorted_iter(from_forks.iter(), to_forks, &mut diffs.forks);
         diff_field_single(from_trace.since(), to_trace.since(), &mut diffs.since);
         diff_field_spine(from_trace, to_trace, &mut diffs.spine);
         diffs
@@ -339,6 +344,7 @@ impl<T: Timestamp + Lattice + Codec64> State<T> {
             leased_readers: diff_leased_readers,
             critical_readers: diff_critical_readers,
             writers: diff_writers,
+            forks: diff_forks,
             since: diff_since,
             spine: diff_spine,
         } = diff;
@@ -370,6 +376,7 @@ impl<T: Timestamp + Lattice + Codec64> State<T> {
             leased_readers,
             critical_readers,
             writers,
+            forks,
             trace,
         } = &mut self.collections;
 
@@ -378,6 +385,7 @@ impl<T: Timestamp + Lattice + Codec64> State<T> {
         apply_diffs_map("leased_readers", diff_leased_readers, leased_readers)?;
         apply_diffs_map("critical_readers", diff_critical_readers, critical_readers)?;
         apply_diffs_map("writers", diff_writers, writers)?;
+        apply_diffs_map("forks", diff_forks, forks)?;
 
         for x in diff_since {
             match x.val {
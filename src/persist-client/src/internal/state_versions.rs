@@ -25,7 +25,7 @@ use mz_persist::location::{
 };
 use mz_persist::retry::Retry;
 use mz_persist_types::{Codec, Codec64};
-use timely::progress::Timestamp;
+use timely::progress::{Antichain, Timestamp};
 use tracing::{debug, debug_span, trace, warn, Instrument};
 
 use crate::error::{CodecMismatch, CodecMismatchT};
@@ -33,9 +33,10 @@ use crate::internal::encoding::UntypedState;
 use crate::internal::machine::{retry_determinate, retry_external};
 use crate::internal::metrics::ShardMetrics;
 use crate::internal::paths::{BlobKey, PartialBlobKey, PartialRollupKey, RollupId};
-#[cfg(debug_assertions)]
-use crate::internal::state::HollowBatch;
-use crate::internal::state::{HollowBlobRef, HollowRollup, NoOpStateTransition, State, TypedState};
+use crate::internal::state::{
+    HollowBatch, HollowBlobRef, HollowRollup, NoOpStateTransition, State, StateCollections,
+    TypedState,
+};
 use crate::internal::state_diff::{StateDiff, StateFieldValDiff};
 use crate::{Metrics, PersistConfig, ShardId};
 
@@ -159,7 +160,7 @@ impl StateVersions {
         }
 
         // Shard is not initialized, try initializing it.
-        let (initial_state, initial_diff) = self.write_initial_rollup(shard_metrics).await;
+        let (initial_state, initial_diff) = self.write_initial_rollup(shard_metrics, |_| {}).await;
         let (cas_res, _diff) =
             retry_external(&self.metrics.retries.external.maybe_init_cas, || async {
                 self.try_compare_and_set_current(
@@ -209,6 +210,70 @@ impl StateVersions {
         }
     }
 
+    /// Creates the state of a shard forked from `owner`, seeded with the given
+    /// batches of `owner` and readable as of `since`.
+    ///
+    /// The shard must not have been initialized yet, which is trivially true
+    /// for a freshly generated [ShardId].
+    pub async fn init_forked_shard<K, V, T, D>(
+        &self,
+        shard_metrics: &ShardMetrics,
+        owner: &ShardId,
+        since: &Antichain<T>,
+        batches: Vec<HollowBatch<T>>,
+    ) -> TypedState<K, V, T, D>
+    where
+        K: Debug + Codec,
+        V: Debug + Codec,
+        T: Timestamp + Lattice + Codec64,
+        D: Semigroup + Codec64,
+    {
+        let shard_id = shard_metrics.shard_id;
+        let (initial_state, initial_diff) = self
+            .write_initial_rollup(shard_metrics, |state| {
+                state.ingest_fork(owner, since, batches.clone())
+            })
+            .await;
+        let (cas_res, _diff) =
+            retry_external(&self.metrics.retries.external.maybe_init_cas, || async {
+                self.try_compare_and_set_current(
+                    "init_forked_shard",
+                    shard_metrics,
+                    None,
+                    &initial_state,
+                    &initial_diff,
+                )
+                .await
+                .map_err(|err| err.into())
+            })
+            .await;
+        match cas_res {
+            CaSResult::Committed => initial_state,
+            CaSResult::ExpectationMismatch => {
+                // The only way for this to happen to a fresh shard is that a
+                // previous attempt at our CaS got an Indeterminate error, but
+                // actually went through.
+                let recent_live_diffs = self.fetch_recent_live_diffs::<T>(&shard_id).await;
+                let state = self
+                    .fetch_current_state(&shard_id, recent_live_diffs.0)
+                    .await
+                    .check_codecs::<K, V, D>(&shard_id)
+                    .expect("forked shard should have the codecs it was created with");
+                let (_, rollup) = initial_state.latest_rollup();
+                assert!(
+                    state
+                        .collections
+                        .rollups
+                        .values()
+                        .any(|x| x.key == rollup.key),
+                    "fork {} was unexpectedly already initialized",
+                    shard_id
+                );
+                state
+            }
+        }
+    }
+
     /// Updates the state of a shard to a new `current` iff `expected` matches
     /// `current`.
     ///
@@ -313,6 +378,13 @@ impl StateVersions {
                 shard_metrics
                     .live_writers
                     .set(u64::cast_from(new_state.collections.writers.len()));
+                let debt = new_state.compaction_debt();
+                shard_metrics
+                    .compaction_debt_runs
+                    .set(u64::cast_from(debt.runs));
+                shard_metrics
+                    .compaction_debt_bytes
+                    .set(u64::cast_from(debt.bytes_awaiting_merge));
                 Ok((CaSResult::Committed, new))
             }
             CaSResult::ExpectationMismatch => {
@@ -585,6 +657,7 @@ impl StateVersions {
     async fn write_initial_rollup<K, V, T, D>(
         &self,
         shard_metrics: &ShardMetrics,
+        mut seed: impl FnMut(&mut StateCollections<T>),
     ) -> (TypedState<K, V, T, D>, StateDiff<T>)
     where
         K: Debug + Codec,
@@ -608,6 +681,7 @@ impl StateVersions {
         };
         let (applied, initial_state) = match empty_state
             .clone_apply(&self.cfg, &mut |_, _, state| {
+                seed(state);
                 state.add_rollup((rollup_seqno, &rollup))
             }) {
             Continue(x) => x,
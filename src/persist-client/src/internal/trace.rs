@@ -59,6 +59,7 @@ use timely::progress::frontier::AntichainRef;
 use timely::progress::{Antichain, Timestamp};
 use timely::PartialOrder;
 
+use crate::internal::compact_scheduler::CompactionDebt;
 use crate::internal::state::HollowBatch;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -224,6 +225,37 @@ impl<T: Timestamp + Lattice> Trace<T> {
         ApplyMergeResult::NotAppliedNoMatch
     }
 
+    /// Returns the outstanding compaction work in this trace.
+    ///
+    /// Every run of every batch must be merged together by a reader, so the
+    /// total number of runs is a measure of read amplification. Batches that
+    /// belong to a fueled (but not yet applied) merge are awaiting compaction.
+    pub(crate) fn compaction_debt(&self) -> CompactionDebt {
+        fn runs<T>(b: &HollowBatch<T>) -> usize {
+            if b.parts.is_empty() {
+                0
+            } else {
+                b.runs.len() + 1
+            }
+        }
+        let mut debt = CompactionDebt::default();
+        self.spine.map_batches(|b| match b {
+            SpineBatch::Merged(b) => debt.runs += runs(&b.batch),
+            SpineBatch::Fueled { parts, .. } => {
+                for b in parts.iter() {
+                    debt.runs += runs(&b.batch);
+                    debt.bytes_awaiting_merge += b
+                        .batch
+                        .parts
+                        .iter()
+                        .map(|x| x.encoded_size_bytes)
+                        .sum::<usize>();
+                }
+            }
+        });
+        debt
+    }
+
     pub(crate) fn all_fueled_merge_reqs(&self) -> Vec<FueledMergeReq<T>> {
         let mut reqs = Vec::new();
         self.spine.map_batches(|b| match b {
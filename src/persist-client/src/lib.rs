@@ -93,7 +93,7 @@ use mz_persist::location::{Blob, Consensus, ExternalError};
 use mz_persist_types::{Codec, Codec64, Opaque};
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
-use timely::progress::Timestamp;
+use timely::progress::{Antichain, Timestamp};
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -104,6 +104,7 @@ use crate::critical::{CriticalReaderId, SinceHandle};
 use crate::error::InvalidUsage;
 use crate::fetch::BatchFetcher;
 use crate::internal::compact::Compactor;
+use crate::internal::compact_scheduler::{CompactionQueueEntry, CompactionScheduler};
 use crate::internal::encoding::{parse_id, Schemas};
 use crate::internal::gc::GarbageCollector;
 use crate::internal::machine::{retry_external, Machine};
@@ -120,8 +121,13 @@ pub mod cfg;
 pub mod cli {
     //! Persist command-line utilities
     pub mod admin;
+    pub mod archive;
     pub mod inspect;
 }
+pub mod compaction {
+    //! Introspection into compaction scheduling.
+    pub use crate::internal::compact_scheduler::{CompactionQueueEntry, CompactionTaskState};
+}
 pub mod critical;
 pub mod error;
 pub mod fetch;
@@ -144,6 +150,7 @@ pub mod write;
 mod internal {
     pub mod apply;
     pub mod compact;
+    pub mod compact_scheduler;
     pub mod encoding;
     pub mod gc;
     pub mod machine;
@@ -285,6 +292,7 @@ pub struct PersistClient {
     isolated_runtime: Arc<IsolatedRuntime>,
     shared_states: Arc<StateCache>,
     pubsub_sender: Arc<dyn PubSubSender>,
+    pub(crate) compaction_scheduler: CompactionScheduler,
 }
 
 impl PersistClient {
@@ -304,6 +312,10 @@ impl PersistClient {
     ) -> Result<Self, ExternalError> {
         // TODO: Verify somehow that blob matches consensus to prevent
         // accidental misuse.
+        //
+        // Clients opened through [crate::cache::PersistClientCache] replace
+        // this with the scheduler shared by the whole process.
+        let compaction_scheduler = CompactionScheduler::new(&cfg, Arc::clone(&metrics));
         Ok(PersistClient {
             cfg,
             blob,
@@ -312,9 +324,16 @@ impl PersistClient {
             isolated_runtime,
             shared_states,
             pubsub_sender,
+            compaction_scheduler,
         })
     }
 
+    /// Returns the compaction requests that are currently queued or running
+    /// in this process, in the order they will be granted.
+    pub fn compaction_queue(&self) -> Vec<CompactionQueueEntry> {
+        self.compaction_scheduler.queue()
+    }
+
     /// Provides capabilities for the durable TVC identified by `shard_id` at
     /// its current since and upper frontiers.
     ///
@@ -629,6 +648,7 @@ impl PersistClient {
                 writer_id.clone(),
                 schemas.clone(),
                 gc.clone(),
+                self.compaction_scheduler.clone(),
             )
         });
         let upper = machine.applier.clone_upper();
@@ -649,6 +669,66 @@ impl PersistClient {
         Ok(writer)
     }
 
+    /// Forks the shard `shard_id` as of `since` into a new shard, returning
+    /// the id of the fork.
+    ///
+    /// The fork starts out with the contents, upper, and codecs of `shard_id`
+    /// and a since of `since`, so it can be read at any time in `[since,
+    /// upper)` exactly as `shard_id` could be at the time of the fork. After
+    /// that, the two shards are independent: writes, compaction, and
+    /// finalization of either are never visible in the other. This makes it
+    /// possible to, e.g., try out new transformations against a consistent
+    /// snapshot of production data without any risk to it.
+    ///
+    /// Forking is cheap: the fork shares the blobs of `shard_id` instead of
+    /// copying them, and `shard_id` holds on to the shared blobs until the fork
+    /// has compacted them all away (or been finalized). Until then, this hold
+    /// also keeps `shard_id` from truncating its state history, much like a
+    /// reader that isn't making progress, so forks are intended to be
+    /// short-lived. If the process crashes in the middle of forking, the hold
+    /// expires after the reader lease duration, like the lease of a reader.
+    ///
+    /// A fork can't itself be forked until it has compacted away the blobs it
+    /// shares with `shard_id`.
+    #[instrument(level = "debug", skip_all, fields(shard = %shard_id))]
+    pub async fn fork_shard<K, V, T, D>(
+        &self,
+        shard_id: ShardId,
+        since: Antichain<T>,
+        diagnostics: Diagnostics,
+    ) -> Result<ShardId, InvalidUsage<T>>
+    where
+        K: Debug + Codec,
+        V: Debug + Codec,
+        T: Timestamp + Lattice + Codec64,
+        D: Semigroup + Codec64 + Send + Sync,
+    {
+        let state_versions = StateVersions::new(
+            self.cfg.clone(),
+            Arc::clone(&self.consensus),
+            Arc::clone(&self.blob),
+            Arc::clone(&self.metrics),
+        );
+        let mut machine = Machine::<K, V, T, D>::new(
+            self.cfg.clone(),
+            shard_id,
+            Arc::clone(&self.metrics),
+            Arc::new(state_versions),
+            Arc::clone(&self.shared_states),
+            Arc::clone(&self.pubsub_sender),
+            Arc::clone(&self.isolated_runtime),
+            diagnostics.clone(),
+        )
+        .await?;
+        let gc = GarbageCollector::new(machine.clone(), Arc::clone(&self.isolated_runtime));
+
+        let fork_id = ShardId::new();
+        let (res, maintenance) = machine.fork(&fork_id, &since, &diagnostics).await;
+        maintenance.start_performing(&machine, &gc);
+        let () = res?;
+        Ok(fork_id)
+    }
+
     /// Returns the internal state of the shard for debugging and QA.
     ///
     /// We'll be thoughtful about making unnecessary changes, but the **output
@@ -926,6 +1006,90 @@ mod tests {
         assert_eq!(read.since(), &Antichain::from_elem(2));
     }
 
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn fork_shard() {
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("3".to_owned(), "three".to_owned()), 3, 1),
+        ];
+
+        let client = new_test_client().await;
+        let shard_id = ShardId::new();
+        let (mut write, mut read) = client
+            .expect_open::<String, String, u64, i64>(shard_id)
+            .await;
+        write
+            .expect_append(&data[..2], write.upper().clone(), vec![3])
+            .await;
+
+        // A fork's since must be in [since, upper) of the source shard.
+        let res = client
+            .fork_shard::<String, String, u64, i64>(
+                shard_id,
+                Antichain::from_elem(3),
+                Diagnostics::for_tests(),
+            )
+            .await;
+        assert!(matches!(res, Err(InvalidUsage::InvalidForkSince { .. })));
+
+        let fork_id = client
+            .fork_shard::<String, String, u64, i64>(
+                shard_id,
+                Antichain::from_elem(1),
+                Diagnostics::for_tests(),
+            )
+            .await
+            .expect("valid fork");
+        assert_ne!(fork_id, shard_id);
+
+        // The fork starts out with the source's contents, since, and upper.
+        let (mut fork_write, mut fork_read) = client
+            .expect_open::<String, String, u64, i64>(fork_id)
+            .await;
+        assert_eq!(fork_read.since(), &Antichain::from_elem(1));
+        assert_eq!(fork_write.upper(), &Antichain::from_elem(3));
+        assert_eq!(
+            fork_read.expect_snapshot_and_fetch(2).await,
+            all_ok(&data[..2], 2)
+        );
+
+        // No data was copied: the fork's parts all point at the source's blobs.
+        let fork_parts = fork_read
+            .machine
+            .applier
+            .snapshot(&Antichain::from_elem(2))
+            .expect("valid as_of");
+        let fork_parts = fork_parts.iter().flat_map(|b| b.parts.iter());
+        for part in fork_parts {
+            assert_eq!(part.key.owning_shard(), Some(shard_id));
+        }
+
+        // Writes after the fork are independent.
+        let fork_data = vec![(("4".to_owned(), "four".to_owned()), 3, 1)];
+        write.expect_append(&data[2..], vec![3], vec![4]).await;
+        fork_write.expect_append(&fork_data, vec![3], vec![4]).await;
+        assert_eq!(read.expect_snapshot_and_fetch(3).await, all_ok(&data, 3));
+        let mut expected = data[..2].to_vec();
+        expected.extend(fork_data);
+        assert_eq!(
+            fork_read.expect_snapshot_and_fetch(3).await,
+            all_ok(&expected, 3)
+        );
+
+        // A shard that still shares blobs with its source can't itself be
+        // forked.
+        let res = client
+            .fork_shard::<String, String, u64, i64>(
+                fork_id,
+                Antichain::from_elem(1),
+                Diagnostics::for_tests(),
+            )
+            .await;
+        assert!(matches!(res, Err(InvalidUsage::ForkOfFork { .. })));
+    }
+
     // Sanity check that the open_reader and open_writer calls work.
     #[mz_ore::test(tokio::test)]
     #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
@@ -43,6 +43,7 @@ use crate::internal::machine::Machine;
 use crate::internal::metrics::{Metrics, MetricsRetryStream};
 use crate::internal::state::{HollowBatch, Since};
 use crate::internal::watch::StateWatch;
+use crate::stats::SnapshotStats;
 use crate::{parse_id, GarbageCollector, PersistConfig};
 
 /// An opaque identifier for a reader of a persist durable TVC (aka shard).
@@ -710,6 +711,29 @@ where
         Ok(leased_parts)
     }
 
+    /// Returns aggregate statistics about the contents of the shard TVC at the
+    /// given frontier.
+    ///
+    /// This command returns statistics about the contents of this shard as of
+    /// `as_of` once they are known, without fetching the contents themselves.
+    /// This may "block" (in an async-friendly way) if `as_of` is greater or
+    /// equal to the current `upper` of the shard.
+    ///
+    /// The `Since` error indicates that the requested `as_of` cannot be served
+    /// (the caller has out of date information) and includes the smallest
+    /// `as_of` that would have been accepted.
+    #[instrument(level = "debug", skip_all, fields(shard = %self.machine.shard_id()))]
+    pub async fn snapshot_stats(&self, as_of: Antichain<T>) -> Result<SnapshotStats<T>, Since<T>> {
+        let mut machine = self.machine.clone();
+        let batches = machine.snapshot(&as_of).await?;
+        let num_updates = batches.iter().map(|b| b.len).sum();
+        Ok(SnapshotStats {
+            shard_id: machine.shard_id(),
+            as_of,
+            num_updates,
+        })
+    }
+
     /// Returns a snapshot of all of a shard's data using `as_of`, followed by
     /// listening to any future updates.
     ///
@@ -739,6 +763,7 @@ where
             key: part.key,
             stats: part.stats,
             encoded_size_bytes: part.encoded_size_bytes,
+            compression: part.compression,
             leased_seqno: Some(self.lease_seqno()),
             filter_pushdown_audit: false,
         })
@@ -1067,6 +1092,38 @@ where
         }
         Ok(contents)
     }
+
+    /// Generates a [Self::snapshot], and streams out all of the updates
+    /// in the batches it contains, one part at a time.
+    ///
+    /// Unlike [Self::snapshot_and_fetch], this never holds more than a single
+    /// part in memory, and so the output is only consolidated within each part.
+    pub async fn snapshot_and_stream(
+        &mut self,
+        as_of: Antichain<T>,
+    ) -> Result<impl Stream<Item = ((Result<K, String>, Result<V, String>), T, D)> + '_, Since<T>>
+    {
+        let snap = self.snapshot(as_of).await?;
+
+        Ok(async_stream::stream! {
+            for part in snap {
+                let (part, fetched_part) = fetch_leased_part(
+                    part,
+                    self.blob.as_ref(),
+                    Arc::clone(&self.metrics),
+                    &self.metrics.read.snapshot,
+                    &self.machine.applier.shard_metrics,
+                    Some(&self.reader_id),
+                    self.schemas.clone(),
+                )
+                .await;
+                self.process_returned_leased_part(part);
+                for update in fetched_part {
+                    yield update;
+                }
+            }
+        })
+    }
 }
 
 impl<K, V, T, D> ReadHandle<K, V, T, D>
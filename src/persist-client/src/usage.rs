@@ -13,7 +13,7 @@ use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Instant;
 
-use futures::stream::{FuturesUnordered, StreamExt};
+use futures::stream::{FuturesUnordered, Stream, StreamExt};
 use mz_ore::cast::CastFrom;
 use mz_persist::location::Blob;
 use tokio::sync::Semaphore;
@@ -215,7 +215,7 @@ impl StorageUsageClient {
             diff.referenced_blob_fn(|blob| match blob {
                 HollowBlobRef::Batch(batch) => {
                     for part in &batch.parts {
-                        batches_bytes += part.encoded_size_bytes;
+                        batches_bytes += part.stored_size_bytes();
                     }
                 }
                 HollowBlobRef::Rollup(rollup) => {
@@ -296,6 +296,49 @@ impl StorageUsageClient {
     /// See [Self::shard_usage_audit] for more details on when to use a full audit.
     pub async fn shards_usage_audit(&self) -> ShardsUsageAudit {
         let blob_usage = self.blob_raw_usage(BlobKeyPrefix::All).await;
+        self.record_blob_usage_metrics(&blob_usage);
+
+        let by_shard = self
+            .shards_usage_given_blob_usage(&blob_usage.by_shard)
+            .await;
+        ShardsUsageAudit {
+            by_shard,
+            unattributable_bytes: blob_usage.unattributable_bytes,
+        }
+    }
+
+    /// Computes [ShardUsageAudit] for every shard in an env, yielding the
+    /// result in batches of at most `batch_size` shards.
+    ///
+    /// [Blob] is still listed in full up front, but the (per-shard) state of
+    /// [mz_persist::location::Consensus] is only fetched one batch at a time,
+    /// so callers can process and drop each batch before the next is audited.
+    /// Bytes not attributable to any shard are reported in the first batch
+    /// only, so that the batches sum to the result of
+    /// [Self::shards_usage_audit].
+    pub fn shards_usage_audit_batched(
+        &self,
+        batch_size: usize,
+    ) -> impl Stream<Item = ShardsUsageAudit> + '_ {
+        assert!(batch_size > 0, "batch_size must be positive");
+        async_stream::stream! {
+            let blob_usage = self.blob_raw_usage(BlobKeyPrefix::All).await;
+            self.record_blob_usage_metrics(&blob_usage);
+
+            let mut unattributable_bytes = blob_usage.unattributable_bytes;
+            let mut shards = blob_usage.by_shard.into_iter().peekable();
+            while shards.peek().is_some() {
+                let batch: BTreeMap<_, _> = shards.by_ref().take(batch_size).collect();
+                let by_shard = self.shards_usage_given_blob_usage(&batch).await;
+                yield ShardsUsageAudit {
+                    by_shard,
+                    unattributable_bytes: std::mem::take(&mut unattributable_bytes),
+                };
+            }
+        }
+    }
+
+    fn record_blob_usage_metrics(&self, blob_usage: &BlobUsage) {
         self.metrics
             .audit
             .blob_batch_part_bytes
@@ -314,10 +357,15 @@ impl StorageUsageClient {
             .set(blob_usage.rollup_count);
         self.metrics.audit.blob_bytes.set(blob_usage.total_size);
         self.metrics.audit.blob_count.set(blob_usage.total_count);
+    }
 
+    async fn shards_usage_given_blob_usage(
+        &self,
+        by_shard: &BTreeMap<ShardId, ShardBlobUsage>,
+    ) -> BTreeMap<ShardId, ShardUsageAudit> {
         let semaphore = Semaphore::new(self.cfg.dynamic.usage_state_fetch_concurrency_limit());
         let by_shard_futures = FuturesUnordered::new();
-        for (shard_id, total_bytes) in blob_usage.by_shard.iter() {
+        for (shard_id, total_bytes) in by_shard.iter() {
             let shard_usage_fut = async {
                 let _permit = semaphore
                     .acquire()
@@ -330,12 +378,7 @@ impl StorageUsageClient {
             };
             by_shard_futures.push(shard_usage_fut);
         }
-
-        let by_shard = by_shard_futures.collect().await;
-        ShardsUsageAudit {
-            by_shard,
-            unattributable_bytes: blob_usage.unattributable_bytes,
-        }
+        by_shard_futures.collect().await
     }
 
     async fn blob_raw_usage(&self, prefix: BlobKeyPrefix<'_>) -> BlobUsage {
@@ -457,10 +500,10 @@ impl StorageUsageClient {
                             let writer_referenced_batches_bytes =
                                 referenced_batches_bytes.entry(writer_id).or_default();
                             *writer_referenced_batches_bytes +=
-                                u64::cast_from(part.encoded_size_bytes);
+                                u64::cast_from(part.stored_size_bytes());
                         } else {
                             // Unexpected, but don't need to panic here.
-                            referenced_other_bytes += u64::cast_from(part.encoded_size_bytes);
+                            referenced_other_bytes += u64::cast_from(part.stored_size_bytes());
                         }
                     }
                 }
@@ -476,7 +519,7 @@ impl StorageUsageClient {
         states_iter.state().map_blobs(|x| match x {
             HollowBlobRef::Batch(x) => {
                 for part in x.parts.iter() {
-                    current_state_batches_bytes += u64::cast_from(part.encoded_size_bytes);
+                    current_state_batches_bytes += u64::cast_from(part.stored_size_bytes());
                 }
             }
             HollowBlobRef::Rollup(x) => {
@@ -944,12 +987,12 @@ mod tests {
             .batch
             .parts
             .iter()
-            .map(|x| u64::cast_from(x.encoded_size_bytes))
+            .map(|x| u64::cast_from(x.stored_size_bytes()))
             .sum::<u64>()
             + b2.batch
                 .parts
                 .iter()
-                .map(|x| u64::cast_from(x.encoded_size_bytes))
+                .map(|x| u64::cast_from(x.stored_size_bytes()))
                 .sum::<u64>();
 
         write
@@ -966,6 +1009,50 @@ mod tests {
         assert_eq!(shard_usage_referenced.batches_bytes, batches_size);
     }
 
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn usage_audit_batched() {
+        mz_ore::test::init_logging();
+
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+        ];
+
+        let client = new_test_client().await;
+        for _ in 0..5 {
+            let (mut write, _read) = client
+                .expect_open::<String, String, u64, i64>(ShardId::new())
+                .await;
+            write.expect_compare_and_append(&data, 0, 3).await;
+        }
+
+        let usage = StorageUsageClient::open(client);
+        let audit = usage.shards_usage_audit().await;
+        assert_eq!(audit.by_shard.len(), 5);
+
+        let batches: Vec<_> = usage.shards_usage_audit_batched(2).collect().await;
+        assert_eq!(
+            batches.iter().map(|b| b.by_shard.len()).collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        let by_shard: BTreeMap<_, _> = batches
+            .iter()
+            .flat_map(|b| b.by_shard.iter())
+            .map(|(shard_id, usage)| (*shard_id, usage.total_bytes()))
+            .collect();
+        let expected: BTreeMap<_, _> = audit
+            .by_shard
+            .iter()
+            .map(|(shard_id, usage)| (*shard_id, usage.total_bytes()))
+            .collect();
+        assert_eq!(by_shard, expected);
+        assert_eq!(
+            batches.iter().map(|b| b.unattributable_bytes).sum::<u64>(),
+            audit.unattributable_bytes
+        );
+    }
+
     fn writer_id(x: char) -> WriterId {
         let x = vec![x, x, x, x].iter().collect::<String>();
         let s = format!("w{x}{x}-{x}-{x}-{x}-{x}{x}{x}");
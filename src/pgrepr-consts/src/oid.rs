@@ -341,3 +341,10 @@ pub const FUNC_HAS_ROLE_OID_TEXT_TEXT_OID: u32 = 16_622;
 pub const FUNC_HAS_ROLE_OID_OID_TEXT_OID: u32 = 16_623;
 pub const FUNC_HAS_ROLE_TEXT_TEXT_OID: u32 = 16_624;
 pub const FUNC_HAS_ROLE_OID_TEXT_OID: u32 = 16_625;
+pub const FUNC_APPROX_COUNT_DISTINCT_OID: u32 = 16_626;
+pub const FUNC_MEDIAN_OID: u32 = 16_627;
+pub const FUNC_BOOL_XOR_OID: u32 = 16_628;
+pub const FUNC_TUMBLE_TS_OID: u32 = 16_629;
+pub const FUNC_TUMBLE_TSTZ_OID: u32 = 16_630;
+pub const FUNC_HOP_TS_OID: u32 = 16_631;
+pub const FUNC_HOP_TSTZ_OID: u32 = 16_632;
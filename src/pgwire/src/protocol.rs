@@ -39,9 +39,9 @@ use mz_sql::session::vars::{ConnectionCounter, DropConnection, VarInput};
 use postgres::error::SqlState;
 use tokio::io::{self, AsyncRead, AsyncWrite};
 use tokio::select;
-use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc;
 use tokio::time::{self};
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, warn, Instrument};
 
 use crate::codec::FramedConn;
@@ -1257,7 +1257,7 @@ where
         &'s mut self,
         parent: &'p tracing::Span,
         mut rows: RowsFuture,
-    ) -> Result<UnboundedReceiver<PeekResponseUnary>, io::Error>
+    ) -> Result<mpsc::Receiver<PeekResponseUnary>, io::Error>
     where
         'p: 's,
     {
@@ -1270,8 +1270,8 @@ where
                 tokio::select! {
                     err = self.conn.wait_closed() => return Err(err),
                     rows = &mut rows => {
-                        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-                        tx.send(rows).expect("send must succeed");
+                        let (tx, rx) = mpsc::channel(1);
+                        tx.try_send(rows).expect("send must succeed");
                         return Ok(rx);
                     }
                     notice = self.adapter_client.session().recv_notice() => {
@@ -1358,7 +1358,7 @@ where
                     row_desc,
                     portal_name,
                     InProgressRows::new(RecordFirstRowStream::new(
-                        Box::new(UnboundedReceiverStream::new(rows)),
+                        Box::new(ReceiverStream::new(rows)),
                         execute_started,
                         &self.adapter_client,
                     )),
@@ -1390,7 +1390,7 @@ where
                 }
                 command_complete!()
             }
-            ExecuteResponse::Subscribing { rx } => {
+            ExecuteResponse::Subscribing { rx, acknowledger } => {
                 if fetch_portal_name.is_none() {
                     let mut msg = ErrorResponse::notice(
                         SqlState::WARNING,
@@ -1410,11 +1410,14 @@ where
                 self.send_rows(
                     row_desc,
                     portal_name,
-                    InProgressRows::new(RecordFirstRowStream::new(
-                        Box::new(UnboundedReceiverStream::new(rx)),
-                        execute_started,
-                        &self.adapter_client,
-                    )),
+                    InProgressRows::subscribe(
+                        RecordFirstRowStream::new(
+                            Box::new(ReceiverStream::new(rx)),
+                            execute_started,
+                            &self.adapter_client,
+                        ),
+                        acknowledger,
+                    ),
                     max_rows,
                     get_response,
                     fetch_portal_name,
@@ -1426,7 +1429,7 @@ where
                 let row_desc =
                     row_desc.expect("missing row description for ExecuteResponse::CopyTo");
                 let rows = match *resp {
-                    ExecuteResponse::Subscribing { rx } => rx,
+                    ExecuteResponse::Subscribing { rx, .. } => rx,
                     ExecuteResponse::SendingRows {
                         future: rows_rx,
                         span,
@@ -1444,7 +1447,7 @@ where
                     format,
                     row_desc,
                     RecordFirstRowStream::new(
-                        Box::new(UnboundedReceiverStream::new(rows)),
+                        Box::new(ReceiverStream::new(rows)),
                         execute_started,
                         &self.adapter_client,
                     ),
@@ -1520,7 +1523,8 @@ where
             | ExecuteResponse::RevokedRole
             | ExecuteResponse::StartedTransaction { .. }
             | ExecuteResponse::Updated(..)
-            | ExecuteResponse::ValidatedConnection => {
+            | ExecuteResponse::ValidatedConnection
+            | ExecuteResponse::Analyzed => {
                 command_complete!()
             }
         };
@@ -1557,6 +1561,12 @@ where
             .result_formats
             .clone();
 
+        // A client that fetches more rows from a cursor has consumed the rows
+        // it fetched before.
+        if fetch_portal_name.is_some() {
+            rows.acknowledge();
+        }
+
         let (mut wait_once, mut deadline) = match timeout {
             ExecuteTimeout::None => (false, None),
             ExecuteTimeout::Seconds(t) => (
@@ -1602,7 +1612,7 @@ where
                         FetchResult::Notice(notice)
                     }
                     _ = cancel_fut => FetchResult::Canceled,
-                    batch = rows.remaining.recv() => match batch {
+                    batch = rows.recv() => match batch {
                         None => FetchResult::Rows(None),
                         Some(PeekResponseUnary::Rows(rows)) => FetchResult::Rows(Some(rows)),
                         Some(PeekResponseUnary::Error(err)) => FetchResult::Error(err),
@@ -0,0 +1,328 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! HyperLogLog sketches for approximate distinct counting.
+//!
+//! A sketch summarizes a set of datums in a fixed number of registers, and can estimate the
+//! number of distinct datums in the set with a standard error of about 1.6%. Sketches of two
+//! sets can be merged into a sketch of their union, but not the other way around: there is no
+//! way to remove a datum from a sketch.
+//!
+//! Sketches are encoded as byte strings, so that partially aggregated sketches can be carried
+//! between the stages of a reduction as [`Datum::Bytes`]. Sketches of few datums use a sparse
+//! encoding that only records their non-zero registers.
+
+use std::hash::{Hash, Hasher};
+
+use mz_ore::cast::CastLossy;
+use thiserror::Error;
+
+use crate::Datum;
+
+/// The number of bits of a datum's hash that select one of the sketch's registers.
+pub const HLL_PRECISION: u32 = 12;
+
+/// The number of registers in a sketch.
+const REGISTERS: usize = 1 << HLL_PRECISION;
+
+/// The tag of a sparse encoding, which is followed by `(index, value)` entries.
+const SPARSE_TAG: u8 = 0;
+/// The tag of a dense encoding, which is followed by the value of each register.
+const DENSE_TAG: u8 = 1;
+/// The encoded length of an entry of a sparse encoding.
+const SPARSE_ENTRY_LEN: usize = 3;
+
+/// An error that occurs when decoding a byte string that is not a sketch.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid HyperLogLog sketch")]
+pub struct InvalidHyperLogLog;
+
+/// A HyperLogLog sketch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperLogLog {
+    registers: Registers,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Registers {
+    /// The non-zero registers, as `(index, value)` pairs sorted by index.
+    Sparse(Vec<(u16, u8)>),
+    /// The value of every register.
+    Dense(Vec<u8>),
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        HyperLogLog::new()
+    }
+}
+
+impl HyperLogLog {
+    /// Creates a sketch of the empty set.
+    pub fn new() -> Self {
+        HyperLogLog {
+            registers: Registers::Sparse(Vec::new()),
+        }
+    }
+
+    /// Creates a sketch of the set containing only `datum`, or of the empty set if `datum` is
+    /// null.
+    pub fn from_datum(datum: Datum) -> Self {
+        let mut sketch = HyperLogLog::new();
+        sketch.insert(datum);
+        sketch
+    }
+
+    /// Adds `datum` to the sketched set. Null datums are ignored.
+    pub fn insert(&mut self, datum: Datum) {
+        if datum.is_null() {
+            return;
+        }
+        let mut hasher = SketchHasher::default();
+        datum.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        // The leading bits select the register, and the register records the position of the
+        // leftmost one bit in the remaining bits.
+        let index = u16::try_from(hash >> (64 - HLL_PRECISION)).expect("must fit");
+        let remaining = hash << HLL_PRECISION;
+        let value = remaining.leading_zeros().min(64 - HLL_PRECISION) + 1;
+        self.raise(index, u8::try_from(value).expect("must fit"));
+    }
+
+    /// Merges `other` into this sketch, which then describes the union of both sets.
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        match &other.registers {
+            Registers::Sparse(entries) => {
+                for (index, value) in entries {
+                    self.raise(*index, *value);
+                }
+            }
+            Registers::Dense(values) => {
+                self.densify();
+                let Registers::Dense(registers) = &mut self.registers else {
+                    unreachable!("sketch was densified");
+                };
+                for (register, value) in registers.iter_mut().zip(values) {
+                    *register = std::cmp::max(*register, *value);
+                }
+            }
+        }
+    }
+
+    /// Estimates the number of distinct datums in the sketched set.
+    pub fn estimate(&self) -> u64 {
+        let (zeros, sum) = match &self.registers {
+            Registers::Sparse(entries) => {
+                let zeros = REGISTERS - entries.len();
+                let sum = entries
+                    .iter()
+                    .map(|(_, value)| register_weight(*value))
+                    .sum::<f64>();
+                (zeros, sum + f64::cast_lossy(zeros))
+            }
+            Registers::Dense(registers) => {
+                let zeros = registers.iter().filter(|value| **value == 0).count();
+                let sum = registers
+                    .iter()
+                    .map(|value| register_weight(*value))
+                    .sum::<f64>();
+                (zeros, sum)
+            }
+        };
+
+        let m = f64::cast_lossy(REGISTERS);
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let raw = alpha * m * m / sum;
+        // Small cardinalities are estimated more accurately by the fraction of empty registers.
+        let estimate = if raw <= 2.5 * m && zeros > 0 {
+            m * (m / f64::cast_lossy(zeros)).ln()
+        } else {
+            raw
+        };
+        u64::cast_lossy(estimate.round())
+    }
+
+    /// Encodes the sketch as a byte string.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match &self.registers {
+            Registers::Sparse(entries) => {
+                let mut bytes = Vec::with_capacity(1 + entries.len() * SPARSE_ENTRY_LEN);
+                bytes.push(SPARSE_TAG);
+                for (index, value) in entries {
+                    bytes.extend(index.to_le_bytes());
+                    bytes.push(*value);
+                }
+                bytes
+            }
+            Registers::Dense(registers) => {
+                let mut bytes = Vec::with_capacity(1 + REGISTERS);
+                bytes.push(DENSE_TAG);
+                bytes.extend(registers);
+                bytes
+            }
+        }
+    }
+
+    /// Decodes a sketch encoded by [`HyperLogLog::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InvalidHyperLogLog> {
+        let registers = match bytes.split_first() {
+            Some((&SPARSE_TAG, entries)) => {
+                if entries.len() % SPARSE_ENTRY_LEN != 0 {
+                    return Err(InvalidHyperLogLog);
+                }
+                let entries: Vec<_> = entries
+                    .chunks_exact(SPARSE_ENTRY_LEN)
+                    .map(|entry| (u16::from_le_bytes([entry[0], entry[1]]), entry[2]))
+                    .collect();
+                let sorted = entries.windows(2).all(|pair| pair[0].0 < pair[1].0);
+                let in_range = entries
+                    .last()
+                    .map_or(true, |(index, _)| usize::from(*index) < REGISTERS);
+                if !sorted || !in_range {
+                    return Err(InvalidHyperLogLog);
+                }
+                Registers::Sparse(entries)
+            }
+            Some((&DENSE_TAG, registers)) if registers.len() == REGISTERS => {
+                Registers::Dense(registers.to_vec())
+            }
+            _ => return Err(InvalidHyperLogLog),
+        };
+        Ok(HyperLogLog { registers })
+    }
+
+    /// Raises the register at `index` to at least `value`.
+    fn raise(&mut self, index: u16, value: u8) {
+        match &mut self.registers {
+            Registers::Sparse(entries) => {
+                match entries.binary_search_by_key(&index, |(index, _)| *index) {
+                    Ok(position) => {
+                        let register = &mut entries[position].1;
+                        *register = std::cmp::max(*register, value);
+                    }
+                    Err(position) => {
+                        entries.insert(position, (index, value));
+                        // Switch to the dense encoding once it is no larger.
+                        if entries.len() * SPARSE_ENTRY_LEN >= REGISTERS {
+                            self.densify();
+                        }
+                    }
+                }
+            }
+            Registers::Dense(registers) => {
+                let register = &mut registers[usize::from(index)];
+                *register = std::cmp::max(*register, value);
+            }
+        }
+    }
+
+    /// Switches the sketch to the dense representation.
+    fn densify(&mut self) {
+        if let Registers::Sparse(entries) = &self.registers {
+            let mut registers = vec![0; REGISTERS];
+            for (index, value) in entries {
+                registers[usize::from(*index)] = *value;
+            }
+            self.registers = Registers::Dense(registers);
+        }
+    }
+}
+
+/// The contribution `2^-value` of a register to the harmonic mean of all registers.
+fn register_weight(value: u8) -> f64 {
+    2f64.powi(-i32::from(value))
+}
+
+/// A hasher for the datums inserted into sketches.
+///
+/// Sketches built on different processes are merged with one another, and so their hashes must
+/// not depend on per-process state, as those of [`std::collections::hash_map::RandomState`] do.
+/// This hashes with 64-bit FNV-1a, followed by the finalizer of MurmurHash3 to spread the
+/// hash's entropy over all of its bits.
+struct SketchHasher(u64);
+
+impl Default for SketchHasher {
+    fn default() -> Self {
+        SketchHasher(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for SketchHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        let mut hash = self.0;
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+        hash ^= hash >> 33;
+        hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sketch_of(values: impl IntoIterator<Item = i64>) -> HyperLogLog {
+        let mut sketch = HyperLogLog::new();
+        for value in values {
+            sketch.insert(Datum::Int64(value));
+        }
+        sketch
+    }
+
+    #[mz_ore::test]
+    fn test_estimate() {
+        assert_eq!(HyperLogLog::new().estimate(), 0);
+        assert_eq!(HyperLogLog::from_datum(Datum::Null).estimate(), 0);
+        assert_eq!(sketch_of([1, 1, 1]).estimate(), 1);
+
+        for count in [10, 1_000, 100_000] {
+            let estimate = f64::cast_lossy(sketch_of(0..count).estimate());
+            let expected = f64::cast_lossy(u64::try_from(count).unwrap());
+            let error = (estimate - expected).abs() / expected;
+            assert!(error < 0.05, "estimate {estimate} for {count} values");
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_merge() {
+        for (left, right) in [(0..10, 5..20), (0..5_000, 2_500..10_000), (0..10, 0..5_000)] {
+            let mut merged = sketch_of(left.clone());
+            merged.merge(&sketch_of(right.clone()));
+            assert_eq!(merged.estimate(), sketch_of(left.chain(right)).estimate());
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_roundtrip() {
+        for count in [0, 10, 5_000] {
+            let sketch = sketch_of(0..count);
+            let bytes = sketch.to_bytes();
+            assert_eq!(HyperLogLog::from_bytes(&bytes), Ok(sketch));
+        }
+        assert_eq!(HyperLogLog::from_bytes(&[]), Err(InvalidHyperLogLog));
+        assert_eq!(
+            HyperLogLog::from_bytes(&[DENSE_TAG, 0]),
+            Err(InvalidHyperLogLog)
+        );
+        assert_eq!(
+            HyperLogLog::from_bytes(&[SPARSE_TAG, 0, 0]),
+            Err(InvalidHyperLogLog)
+        );
+    }
+}
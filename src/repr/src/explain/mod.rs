@@ -175,6 +175,8 @@ pub struct ExplainConfig {
     pub filter_pushdown: bool,
     /// Show cardinality information.
     pub cardinality: bool,
+    /// Show the indexes that the plan would use if they existed.
+    pub recommended_indexes: bool,
 }
 
 impl Default for ExplainConfig {
@@ -193,6 +195,7 @@ impl Default for ExplainConfig {
             types: false,
             filter_pushdown: false,
             cardinality: false,
+            recommended_indexes: false,
         }
     }
 }
@@ -231,6 +234,7 @@ impl TryFrom<BTreeSet<String>> for ExplainConfig {
             types: flags.remove("types"),
             filter_pushdown: flags.remove("filter_pushdown") || flags.remove("mfp_pushdown"),
             cardinality: flags.remove("cardinality"),
+            recommended_indexes: flags.remove("recommended_indexes"),
         };
         if flags.is_empty() {
             Ok(result)
@@ -534,6 +538,31 @@ impl UsedIndexes {
     }
 }
 
+/// A set of indexes that the explained plan would use if they existed.
+#[derive(Debug, Default)]
+pub struct RecommendedIndexes(Vec<RecommendedIndex>);
+
+/// An index that the explained plan would use if it existed.
+#[derive(Debug)]
+pub struct RecommendedIndex {
+    /// The object to index.
+    pub on_id: GlobalId,
+    /// The key of the index, rendered in terms of the columns of the object.
+    pub key: String,
+    /// Why the index is recommended.
+    pub reason: String,
+}
+
+impl RecommendedIndexes {
+    pub fn new(values: Vec<RecommendedIndex>) -> RecommendedIndexes {
+        RecommendedIndexes(values)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -635,6 +664,7 @@ mod tests {
             types: false,
             filter_pushdown: false,
             cardinality: false,
+            recommended_indexes: false,
         };
         let context = ExplainContext {
             env,
@@ -14,7 +14,8 @@ use std::fmt;
 use mz_ore::str::{Indent, IndentLike};
 
 use crate::explain::{
-    CompactScalarSeq, ExprHumanizer, Indices, ScalarOps, UnsupportedFormat, UsedIndexes,
+    CompactScalarSeq, ExprHumanizer, Indices, RecommendedIndexes, ScalarOps, UnsupportedFormat,
+    UsedIndexes,
 };
 use crate::Row;
 
@@ -83,6 +84,32 @@ where
     }
 }
 
+impl<'a, C> DisplayText<C> for RecommendedIndexes
+where
+    C: AsMut<Indent> + AsRef<&'a dyn ExprHumanizer>,
+{
+    fn fmt_text(&self, f: &mut fmt::Formatter<'_>, ctx: &mut C) -> fmt::Result {
+        writeln!(f, "{}Recommended Indexes:", ctx.as_mut())?;
+        *ctx.as_mut() += 1;
+        for index in &self.0 {
+            let on_name = ctx
+                .as_ref()
+                .humanize_id(index.on_id)
+                .unwrap_or_else(|| index.on_id.to_string());
+            writeln!(
+                f,
+                "{}- {} ({}) [{}]",
+                ctx.as_mut(),
+                on_name,
+                index.key,
+                index.reason
+            )?;
+        }
+        *ctx.as_mut() -= 1;
+        Ok(())
+    }
+}
+
 impl<'a> fmt::Display for Indices<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut is_first = true;
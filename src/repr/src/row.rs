@@ -167,17 +167,20 @@ impl Ord for Row {
 #[allow(missing_debug_implementations)]
 mod columnation {
 
-    use columnation::{Columnation, Region, StableRegion};
+    use columnation::{Columnation, Region};
+    use mz_ore::region::SpillRegion;
 
     use crate::Row;
 
     /// Region allocation for `Row` data.
     ///
     /// Content bytes are stored in stable contiguous memory locations,
-    /// and then a `Row` referencing them is falsified.
+    /// and then a `Row` referencing them is falsified. Large allocations
+    /// are backed by files when spilling is enabled, see
+    /// [`mz_ore::region::set_spill_config`].
     #[derive(Default)]
     pub struct RowStack {
-        region: StableRegion<u8>,
+        region: SpillRegion<u8>,
     }
 
     impl Columnation for Row {
@@ -101,6 +101,7 @@ pub enum Statement<T: AstInfo> {
     AlterDefaultPrivileges(AlterDefaultPrivilegesStatement<T>),
     ReassignOwned(ReassignOwnedStatement<T>),
     ValidateConnection(ValidateConnectionStatement<T>),
+    Analyze(AnalyzeStatement<T>),
 }
 
 impl<T: AstInfo> AstDisplay for Statement<T> {
@@ -166,6 +167,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::AlterDefaultPrivileges(stmt) => f.write_node(stmt),
             Statement::ReassignOwned(stmt) => f.write_node(stmt),
             Statement::ValidateConnection(stmt) => f.write_node(stmt),
+            Statement::Analyze(stmt) => f.write_node(stmt),
         }
     }
 }
@@ -234,6 +236,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::AlterDefaultPrivileges => "alter_default_privileges",
         StatementKind::ReassignOwned => "reassign_owned",
         StatementKind::ValidateConnection => "validate_connection",
+        StatementKind::Analyze => "analyze",
     }
 }
 
@@ -655,6 +658,21 @@ impl<T: AstInfo> AstDisplay for ValidateConnectionStatement<T> {
 }
 impl_display_t!(ValidateConnectionStatement);
 
+/// `ANALYZE`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnalyzeStatement<T: AstInfo> {
+    /// The relation to analyze
+    pub name: T::ItemName,
+}
+
+impl<T: AstInfo> AstDisplay for AnalyzeStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ANALYZE ");
+        f.write_node(&self.name);
+    }
+}
+impl_display_t!(AnalyzeStatement);
+
 /// `CREATE SOURCE <name> FROM WEBHOOK`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateWebhookSourceStatement<T: AstInfo> {
@@ -1158,6 +1176,7 @@ pub struct CreateMaterializedViewStatement<T: AstInfo> {
     pub name: UnresolvedItemName,
     pub columns: Vec<Ident>,
     pub in_cluster: Option<T::ClusterName>,
+    pub on_error: Option<OnErrorBehavior>,
     pub query: Query<T>,
 }
 
@@ -1188,12 +1207,36 @@ impl<T: AstInfo> AstDisplay for CreateMaterializedViewStatement<T> {
             f.write_node(cluster);
         }
 
+        if let Some(on_error) = &self.on_error {
+            f.write_str(" ON ERROR ");
+            f.write_node(on_error);
+        }
+
         f.write_str(" AS ");
         f.write_node(&self.query);
     }
 }
 impl_display_t!(CreateMaterializedViewStatement);
 
+/// How a materialized view handles rows whose evaluation errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OnErrorBehavior {
+    /// Drop the row from the view.
+    SkipRow,
+    /// Replace the erroring values with `NULL`.
+    Null,
+}
+
+impl AstDisplay for OnErrorBehavior {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            Self::SkipRow => f.write_str("SKIP ROW"),
+            Self::Null => f.write_str("NULL"),
+        }
+    }
+}
+impl_display!(OnErrorBehavior);
+
 /// `ALTER SET CLUSTER`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AlterSetClusterStatement<T: AstInfo> {
@@ -1231,6 +1274,7 @@ pub struct CreateTableStatement<T: AstInfo> {
     pub constraints: Vec<TableConstraint<T>>,
     pub if_not_exists: bool,
     pub temporary: bool,
+    pub with_options: Vec<TableOption<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for CreateTableStatement<T> {
@@ -1251,10 +1295,48 @@ impl<T: AstInfo> AstDisplay for CreateTableStatement<T> {
             f.write_node(&display::comma_separated(&self.constraints));
         }
         f.write_str(")");
+        if !self.with_options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.with_options));
+            f.write_str(")");
+        }
     }
 }
 impl_display_t!(CreateTableStatement);
 
+/// An option in a `CREATE TABLE` statement.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TableOptionName {
+    // The `APPEND ONLY` option
+    AppendOnly,
+}
+
+impl AstDisplay for TableOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            TableOptionName::AppendOnly => {
+                f.write_str("APPEND ONLY");
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableOption<T: AstInfo> {
+    pub name: TableOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for TableOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+
 /// `CREATE INDEX`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateIndexStatement<T: AstInfo> {
@@ -2549,6 +2631,11 @@ impl_display!(RollbackStatement);
 pub enum SubscribeOptionName {
     Snapshot,
     Progress,
+    Name,
+    Retention,
+    BatchSize,
+    EmitEvery,
+    MaxPendingRows,
 }
 
 impl AstDisplay for SubscribeOptionName {
@@ -2556,6 +2643,11 @@ impl AstDisplay for SubscribeOptionName {
         match self {
             SubscribeOptionName::Snapshot => f.write_str("SNAPSHOT"),
             SubscribeOptionName::Progress => f.write_str("PROGRESS"),
+            SubscribeOptionName::Name => f.write_str("NAME"),
+            SubscribeOptionName::Retention => f.write_str("RETENTION"),
+            SubscribeOptionName::BatchSize => f.write_str("BATCH SIZE"),
+            SubscribeOptionName::EmitEvery => f.write_str("EMIT EVERY"),
+            SubscribeOptionName::MaxPendingRows => f.write_str("MAX PENDING ROWS"),
         }
     }
 }
@@ -2583,6 +2675,7 @@ impl_display_t!(SubscribeOption);
 pub struct SubscribeStatement<T: AstInfo> {
     pub relation: SubscribeRelation<T>,
     pub options: Vec<SubscribeOption<T>>,
+    pub resume_from: Option<SubscribeResume<T>>,
     pub as_of: Option<AsOf<T>>,
     pub up_to: Option<Expr<T>>,
     pub output: SubscribeOutput<T>,
@@ -2597,6 +2690,10 @@ impl<T: AstInfo> AstDisplay for SubscribeStatement<T> {
             f.write_node(&display::comma_separated(&self.options));
             f.write_str(")");
         }
+        if let Some(resume_from) = &self.resume_from {
+            f.write_str(" ");
+            f.write_node(resume_from);
+        }
         if let Some(as_of) = &self.as_of {
             f.write_str(" ");
             f.write_node(as_of);
@@ -2610,6 +2707,29 @@ impl<T: AstInfo> AstDisplay for SubscribeStatement<T> {
 }
 impl_display_t!(SubscribeStatement);
 
+/// The `RESUME FROM` clause of a `SUBSCRIBE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SubscribeResume<T: AstInfo> {
+    /// The name of the subscribe to resume.
+    pub name: String,
+    /// The timestamp up to which the client has received the subscribe's
+    /// updates, if the client chooses where to resume.
+    pub at: Option<Expr<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for SubscribeResume<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("RESUME FROM '");
+        f.write_node(&display::escape_single_quote_string(&self.name));
+        f.write_str("'");
+        if let Some(at) = &self.at {
+            f.write_str(" AT ");
+            f.write_node(at);
+        }
+    }
+}
+impl_display_t!(SubscribeResume);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SubscribeRelation<T: AstInfo> {
     Name(T::ItemName),
@@ -444,6 +444,9 @@ impl<'a> Parser<'a> {
                 Token::Keyword(VALIDATE) => Ok(self
                     .parse_validate()
                     .map_parser_err(StatementKind::ValidateConnection)?),
+                Token::Keyword(ANALYZE) => Ok(self
+                    .parse_analyze()
+                    .map_parser_err(StatementKind::Analyze)?),
                 Token::Keyword(kw) => parser_err!(
                     self,
                     self.peek_prev_pos(),
@@ -761,6 +764,28 @@ impl<'a> Parser<'a> {
             ));
         }
 
+        let within_group = if self.parse_keywords(&[WITHIN, GROUP]) {
+            if distinct {
+                return Err(self.error(
+                    self.peek_prev_pos(),
+                    "cannot use DISTINCT with WITHIN GROUP".to_string(),
+                ));
+            }
+            if matches!(&args, FunctionArgs::Args { order_by, .. } if !order_by.is_empty()) {
+                return Err(self.error(
+                    self.peek_prev_pos(),
+                    "cannot use multiple ORDER BY clauses with WITHIN GROUP".to_string(),
+                ));
+            }
+            self.expect_token(&Token::LParen)?;
+            self.expect_keywords(&[ORDER, BY])?;
+            let order_by = self.parse_comma_separated(Parser::parse_order_by_expr)?;
+            self.expect_token(&Token::RParen)?;
+            order_by
+        } else {
+            vec![]
+        };
+
         let filter = if self.parse_keyword(FILTER) {
             self.expect_token(&Token::LParen)?;
             self.expect_keyword(WHERE)?;
@@ -816,6 +841,7 @@ impl<'a> Parser<'a> {
             filter,
             over,
             distinct,
+            within_group,
         })
     }
 
@@ -948,6 +974,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -1011,6 +1038,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -1029,6 +1057,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -1260,6 +1289,7 @@ impl<'a> Parser<'a> {
                         filter: None,
                         over: None,
                         distinct: false,
+                        within_group: vec![],
                     }))
                 }
                 COLLATE => Ok(Expr::Collate {
@@ -1384,6 +1414,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -2157,6 +2188,13 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    /// Parse an `ANALYZE` statement, assuming that the `ANALYZE` token
+    /// has already been consumed.
+    fn parse_analyze(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let name = self.parse_raw_name()?;
+        Ok(Statement::Analyze(AnalyzeStatement { name }))
+    }
+
     fn parse_create_connection(&mut self) -> Result<Statement<Raw>, ParserError> {
         self.expect_keyword(CONNECTION)?;
         let if_not_exists = self.parse_if_not_exists()?;
@@ -3201,6 +3239,18 @@ impl<'a> Parser<'a> {
         let name = self.parse_item_name()?;
         let columns = self.parse_parenthesized_column_list(Optional)?;
         let in_cluster = self.parse_optional_in_cluster()?;
+        let on_error = if self.parse_keywords(&[ON, ERROR]) {
+            match self.expect_one_of_keywords(&[SKIP, NULL])? {
+                SKIP => {
+                    self.expect_keyword(ROW)?;
+                    Some(OnErrorBehavior::SkipRow)
+                }
+                NULL => Some(OnErrorBehavior::Null),
+                _ => unreachable!(),
+            }
+        } else {
+            None
+        };
 
         self.expect_keyword(AS)?;
         let query = self.parse_query()?;
@@ -3211,6 +3261,7 @@ impl<'a> Parser<'a> {
                 name,
                 columns,
                 in_cluster,
+                on_error,
                 query,
             },
         ))
@@ -3779,15 +3830,40 @@ impl<'a> Parser<'a> {
         // parse optional column list (schema)
         let (columns, constraints) = self.parse_columns(Mandatory)?;
 
+        let with_options = if self.parse_keyword(WITH) {
+            self.expect_token(&Token::LParen)?;
+            let o = if matches!(self.peek_token(), Some(Token::RParen)) {
+                vec![]
+            } else {
+                self.parse_comma_separated(Parser::parse_table_option)?
+            };
+            self.expect_token(&Token::RParen)?;
+            o
+        } else {
+            vec![]
+        };
+
         Ok(Statement::CreateTable(CreateTableStatement {
             name: table_name,
             columns,
             constraints,
             if_not_exists,
             temporary,
+            with_options,
         }))
     }
 
+    fn parse_table_option_name(&mut self) -> Result<TableOptionName, ParserError> {
+        self.expect_keywords(&[APPEND, ONLY])?;
+        Ok(TableOptionName::AppendOnly)
+    }
+
+    fn parse_table_option(&mut self) -> Result<TableOption<Raw>, ParserError> {
+        let name = self.parse_table_option_name()?;
+        let value = self.parse_optional_option_value()?;
+        Ok(TableOption { name, value })
+    }
+
     fn parse_columns(
         &mut self,
         optional: IsOptional,
@@ -6224,6 +6300,7 @@ impl<'a> Parser<'a> {
                         filter: None,
                         over: None,
                         distinct: false,
+                        within_group: vec![],
                     },
                     alias,
                     with_ordinality,
@@ -6296,6 +6373,7 @@ impl<'a> Parser<'a> {
                         filter: None,
                         over: None,
                         distinct: false,
+                        within_group: vec![],
                     },
                     alias,
                     with_ordinality,
@@ -6638,6 +6716,17 @@ impl<'a> Parser<'a> {
         } else {
             vec![]
         };
+        let resume_from = if self.parse_keywords(&[RESUME, FROM]) {
+            let name = self.parse_literal_string()?;
+            let at = if self.parse_keyword(AT) {
+                Some(self.parse_expr()?)
+            } else {
+                None
+            };
+            Some(SubscribeResume { name, at })
+        } else {
+            None
+        };
         let as_of = self.parse_optional_as_of()?;
         let up_to = self.parse_optional_up_to()?;
         let output = if self.parse_keywords(&[ENVELOPE]) {
@@ -6662,6 +6751,7 @@ impl<'a> Parser<'a> {
         Ok(Statement::Subscribe(SubscribeStatement {
             relation,
             options,
+            resume_from,
             as_of,
             up_to,
             output,
@@ -6669,9 +6759,25 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_subscribe_option(&mut self) -> Result<SubscribeOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[PROGRESS, SNAPSHOT])? {
+        let name = match self
+            .expect_one_of_keywords(&[PROGRESS, SNAPSHOT, NAME, RETENTION, BATCH, EMIT, MAX])?
+        {
             PROGRESS => SubscribeOptionName::Progress,
             SNAPSHOT => SubscribeOptionName::Snapshot,
+            NAME => SubscribeOptionName::Name,
+            RETENTION => SubscribeOptionName::Retention,
+            BATCH => {
+                self.expect_keyword(SIZE)?;
+                SubscribeOptionName::BatchSize
+            }
+            EMIT => {
+                self.expect_keyword(EVERY)?;
+                SubscribeOptionName::EmitEvery
+            }
+            MAX => {
+                self.expect_keywords(&[PENDING, ROWS])?;
+                SubscribeOptionName::MaxPendingRows
+            }
             _ => unreachable!(),
         };
         Ok(SubscribeOption {
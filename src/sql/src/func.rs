@@ -22,6 +22,7 @@ use mz_pgrepr::oid;
 use mz_repr::role_id::RoleId;
 use mz_repr::{ColumnName, ColumnType, Datum, RelationType, Row, ScalarBaseType, ScalarType};
 use once_cell::sync::Lazy;
+use ordered_float::OrderedFloat;
 
 use crate::ast::{SelectStatement, Statement};
 use crate::catalog::{CatalogType, TypeCategory, TypeReference};
@@ -471,6 +472,39 @@ fn experimental_sql_impl_table_func(
     sql_impl_table_func_inner(sql, Some(feature))
 }
 
+/// Plans a call to the windowing table function `func`, which assigns the timestamp in the first
+/// of `exprs` to windows.
+///
+/// Each window is retracted once `mz_now()` reaches its end, so that windowed computations over
+/// event time do not retain closed windows indefinitely.
+///
+/// TODO: Aggregations grouped by window are monotonic until their window closes, but are not
+/// yet planned as monotonic reductions. See `MonotonicFlag`.
+fn plan_window_table_func(
+    ecx: &ExprContext,
+    func: TableFunc,
+    exprs: Vec<HirScalarExpr>,
+) -> Result<TableFuncPlan, PlanError> {
+    ecx.require_feature_flag(&vars::ENABLE_WINDOW_TABLE_FUNCTIONS)?;
+    let cast_to_mz_timestamp = match func {
+        TableFunc::TumbleTimestamp | TableFunc::HopTimestamp => {
+            UnaryFunc::CastTimestampToMzTimestamp(func::CastTimestampToMzTimestamp)
+        }
+        TableFunc::TumbleTimestampTz | TableFunc::HopTimestampTz => {
+            UnaryFunc::CastTimestampTzToMzTimestamp(func::CastTimestampTzToMzTimestamp)
+        }
+        _ => unreachable!("{} is not a windowing table function", func),
+    };
+    let expiry = HirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzNow).call_binary(
+        HirScalarExpr::column(1).call_unary(cast_to_mz_timestamp),
+        BinaryFunc::Lt,
+    );
+    Ok(TableFuncPlan {
+        expr: HirRelationExpr::CallTable { func, exprs }.filter(vec![expiry]),
+        column_names: vec!["window_start".into(), "window_end".into()],
+    })
+}
+
 /// Describes a single function's implementation.
 pub struct FuncImpl<R> {
     pub oid: u32,
@@ -2737,6 +2771,21 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             }) => ArrayAny, 2335;
             params!(ArrayAny) => Operation::unary(|_ecx, _e| bail_unsupported!("array_agg on arrays")) => ArrayAny, 4053;
         },
+        "bit_and" => Aggregate {
+            params!(Int16) => AggregateFunc::BitAndInt16 => Int16, 2236;
+            params!(Int32) => AggregateFunc::BitAndInt32 => Int32, 2238;
+            params!(Int64) => AggregateFunc::BitAndInt64 => Int64, 2240;
+        },
+        "bit_or" => Aggregate {
+            params!(Int16) => AggregateFunc::BitOrInt16 => Int16, 2237;
+            params!(Int32) => AggregateFunc::BitOrInt32 => Int32, 2239;
+            params!(Int64) => AggregateFunc::BitOrInt64 => Int64, 2241;
+        },
+        "bit_xor" => Aggregate {
+            params!(Int16) => AggregateFunc::BitXorInt16 => Int16, 6164;
+            params!(Int32) => AggregateFunc::BitXorInt32 => Int32, 6165;
+            params!(Int64) => AggregateFunc::BitXorInt64 => Int64, 6166;
+        },
         "bool_and" => Aggregate {
             params!(Bool) => Operation::nullary(|_ecx| catalog_name_only!("bool_and")) => Bool, 2517;
         },
@@ -2750,6 +2799,15 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             }) => Int64, 2803;
             params!(Any) => AggregateFunc::Count => Int64, 2147;
         },
+        "corr" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("corr")) => Float64, 2829;
+        },
+        "covar_pop" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("covar_pop")) => Float64, 2827;
+        },
+        "covar_samp" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("covar_samp")) => Float64, 2828;
+        },
         "max" => Aggregate {
             params!(Bool) => AggregateFunc::MaxBool => Bool, oid::FUNC_MAX_BOOL_OID;
             params!(Int16) => AggregateFunc::MaxInt16 => Int16, 2117;
@@ -2831,6 +2889,57 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 Ok((e, AggregateFunc::JsonbObjectAgg { order_by }))
             }) => Jsonb, 3270;
         },
+        "mode" => Aggregate {
+            params!(AnyElement) => Operation::unary_ordered(|_ecx, e, order_by| {
+                let desc = order_by.first().map_or(false, |o| o.desc);
+                Ok((e, AggregateFunc::Mode { desc }))
+            }) => AnyElement, 3994;
+        },
+        "percentile_cont" => Aggregate {
+            params!(Float64, Float64) => Operation::binary_ordered(|_ecx, fraction, e, order_by| {
+                let mut fraction = percentile_fraction(fraction)?;
+                // Interpolating in descending order is interpolating at the
+                // opposite fraction in ascending order.
+                if order_by.first().map_or(false, |o| o.desc) {
+                    fraction = 1.0 - fraction;
+                }
+                Ok((e, AggregateFunc::PercentileCont { fraction: OrderedFloat(fraction) }))
+            }) => Float64, 3974;
+        },
+        "percentile_disc" => Aggregate {
+            params!(Float64, AnyElement) => Operation::binary_ordered(|_ecx, fraction, e, order_by| {
+                let fraction = OrderedFloat(percentile_fraction(fraction)?);
+                let desc = order_by.first().map_or(false, |o| o.desc);
+                Ok((e, AggregateFunc::PercentileDisc { fraction, desc }))
+            }) => AnyElement, 3972;
+        },
+        "regr_avgx" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_avgx")) => Float64, 2822;
+        },
+        "regr_avgy" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_avgy")) => Float64, 2823;
+        },
+        "regr_count" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_count")) => Int64, 2818;
+        },
+        "regr_intercept" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_intercept")) => Float64, 2826;
+        },
+        "regr_r2" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_r2")) => Float64, 2824;
+        },
+        "regr_slope" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_slope")) => Float64, 2825;
+        },
+        "regr_sxx" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_sxx")) => Float64, 2819;
+        },
+        "regr_sxy" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_sxy")) => Float64, 2821;
+        },
+        "regr_syy" => Aggregate {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_syy")) => Float64, 2820;
+        },
         "string_agg" => Aggregate {
             params!(String, String) => Operation::binary_ordered(|_ecx, value, sep, order_by| {
                 let e = HirScalarExpr::CallVariadic {
@@ -3189,6 +3298,12 @@ pub static MZ_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 })
             }) => ReturnType::set_of(RecordAny), oid::FUNC_CSV_EXTRACT_OID;
         },
+        "approx_count_distinct" => Aggregate {
+            params!(Any) => AggregateFunc::ApproxCountDistinct => Int64, oid::FUNC_APPROX_COUNT_DISTINCT_OID;
+        },
+        "bool_xor" => Aggregate {
+            params!(Bool) => AggregateFunc::Xor => Bool, oid::FUNC_BOOL_XOR_OID;
+        },
         "concat_agg" => Aggregate {
             params!(Any) => Operation::unary(|_ecx, _e| bail_unsupported!("concat_agg")) => String, oid::FUNC_CONCAT_AGG_OID;
         },
@@ -3359,6 +3474,9 @@ pub static MZ_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "map_length" => Scalar {
             params![MapAny] => UnaryFunc::MapLength(func::MapLength) => Int32, oid::FUNC_MAP_LENGTH_OID;
         },
+        "median" => Aggregate {
+            params!(Float64) => AggregateFunc::Median => Float64, oid::FUNC_MEDIAN_OID;
+        },
         "mz_environment_id" => Scalar {
             params!() => UnmaterializableFunc::MzEnvironmentId => String, oid::FUNC_MZ_ENVIRONMENT_ID_OID;
         },
@@ -3398,6 +3516,24 @@ pub static MZ_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 })
             }) => ReturnType::set_of(RecordAny), oid::FUNC_REGEXP_EXTRACT_OID;
         },
+        "tumble" => Table {
+            // (timestamp, size)
+            params!(Timestamp, Interval) => Operation::variadic(move |ecx, exprs| {
+                plan_window_table_func(ecx, TableFunc::TumbleTimestamp, exprs)
+            }) => ReturnType::set_of(RecordAny), oid::FUNC_TUMBLE_TS_OID;
+            params!(TimestampTz, Interval) => Operation::variadic(move |ecx, exprs| {
+                plan_window_table_func(ecx, TableFunc::TumbleTimestampTz, exprs)
+            }) => ReturnType::set_of(RecordAny), oid::FUNC_TUMBLE_TSTZ_OID;
+        },
+        "hop" => Table {
+            // (timestamp, size, slide)
+            params!(Timestamp, Interval, Interval) => Operation::variadic(move |ecx, exprs| {
+                plan_window_table_func(ecx, TableFunc::HopTimestamp, exprs)
+            }) => ReturnType::set_of(RecordAny), oid::FUNC_HOP_TS_OID;
+            params!(TimestampTz, Interval, Interval) => Operation::variadic(move |ecx, exprs| {
+                plan_window_table_func(ecx, TableFunc::HopTimestampTz, exprs)
+            }) => ReturnType::set_of(RecordAny), oid::FUNC_HOP_TSTZ_OID;
+        },
         "repeat_row" => Table {
             params!(Int64) => Operation::unary(move |ecx, n| {
                 ecx.require_feature_flag(&crate::session::vars::ENABLE_REPEAT_ROW)?;
@@ -3729,6 +3865,18 @@ fn digest(algorithm: &'static str) -> Operation<HirScalarExpr> {
     })
 }
 
+/// Extracts the fraction of a `percentile_cont` or `percentile_disc` call,
+/// which must be a constant between 0 and 1.
+fn percentile_fraction(fraction: HirScalarExpr) -> Result<f64, PlanError> {
+    match fraction.into_literal_float64() {
+        None => sql_bail!("percentile fraction must be a constant"),
+        Some(f) if !(0.0..=1.0).contains(&f) => {
+            sql_bail!("percentile value {} is not between 0 and 1", f)
+        }
+        Some(f) => Ok(f),
+    }
+}
+
 fn array_to_string(
     ecx: &ExprContext,
     exprs: Vec<HirScalarExpr>,
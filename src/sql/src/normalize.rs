@@ -300,6 +300,7 @@ pub fn create_statement(
             constraints: _,
             if_not_exists,
             temporary,
+            with_options: _,
         }) => {
             *name = if *temporary {
                 allocate_temporary_name(name)?
@@ -371,6 +372,7 @@ pub fn create_statement(
             name,
             columns: _,
             in_cluster: _,
+            on_error: _,
             query,
         }) => {
             *name = allocate_name(name)?;
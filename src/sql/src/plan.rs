@@ -36,6 +36,7 @@ use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use enum_kinds::EnumKind;
+use mz_compute_client::types::sinks::QuarantineMode;
 use mz_controller::clusters::{ClusterId, ReplicaId};
 use mz_expr::{CollectionPlan, ColumnOrder, MirRelationExpr, MirScalarExpr, RowSetFinishing};
 use mz_ore::now::{self, NOW_ZERO};
@@ -175,6 +176,7 @@ pub enum Plan {
     ReassignOwned(ReassignOwnedPlan),
     SideEffectingFunc(SideEffectingFunc),
     ValidateConnection(ValidateConnectionPlan),
+    Analyze(AnalyzePlan),
 }
 
 impl Plan {
@@ -269,6 +271,7 @@ impl Plan {
             StatementKind::Subscribe => vec![PlanKind::Subscribe],
             StatementKind::Update => vec![PlanKind::ReadThenWrite],
             StatementKind::ValidateConnection => vec![PlanKind::ValidateConnection],
+            StatementKind::Analyze => vec![PlanKind::Analyze],
         }
     }
 
@@ -396,6 +399,7 @@ impl Plan {
             Plan::ReassignOwned(_) => "reassign owned",
             Plan::SideEffectingFunc(_) => "side effecting func",
             Plan::ValidateConnection(_) => "validate connection",
+            Plan::Analyze(_) => "analyze",
         }
     }
 }
@@ -585,6 +589,12 @@ pub struct ValidateConnectionPlan {
     pub connection: mz_storage_client::types::connections::Connection,
 }
 
+#[derive(Debug)]
+pub struct AnalyzePlan {
+    /// The relation to analyze.
+    pub id: GlobalId,
+}
+
 #[derive(Debug)]
 pub struct CreateSecretPlan {
     pub name: QualifiedItemName,
@@ -741,9 +751,35 @@ pub struct SubscribePlan {
     pub copy_to: Option<CopyFormat>,
     pub emit_progress: bool,
     pub output: SubscribeOutput,
-}
-
-#[derive(Debug)]
+    /// Present if the subscribe can be resumed after its connection goes away.
+    pub resumable: Option<ResumableSubscribe>,
+    /// The maximum number of rows sent to the client in a single batch.
+    pub batch_size: Option<u64>,
+    /// If present, updates are coalesced into intervals of this length, and
+    /// only the net change of each interval is emitted.
+    pub emit_interval: Option<Duration>,
+    /// If present, the maximum number of rows each dataflow worker sends
+    /// before waiting for the client to receive them.
+    pub max_pending_rows: Option<u64>,
+}
+
+/// Describes how a named subscribe outlives the connection that runs it.
+#[derive(Debug, Clone)]
+pub struct ResumableSubscribe {
+    /// The name of the subscribe, which is also the token it is resumed from.
+    pub name: String,
+    /// Whether this continues a previously started subscribe of the same name.
+    pub resume: bool,
+    /// The timestamp up to which the client has received the updates of the
+    /// resumed subscribe. If not specified, the subscribe resumes where the
+    /// coordinator last saw the client receive its updates.
+    pub resume_at: Option<MirScalarExpr>,
+    /// How long to retain the subscribe after its connection goes away. If
+    /// not specified, resuming keeps the previous retention.
+    pub retention: Option<Duration>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum SubscribeFrom {
     Id(GlobalId),
     Query {
@@ -1058,6 +1094,8 @@ pub struct Table {
     pub desc: RelationDesc,
     pub defaults: Vec<Expr<Aug>>,
     pub temporary: bool,
+    /// Whether the table only admits insertions.
+    pub append_only: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -1145,6 +1183,7 @@ pub struct MaterializedView {
     pub expr: mz_expr::MirRelationExpr,
     pub column_names: Vec<ColumnName>,
     pub cluster_id: ClusterId,
+    pub on_error: Option<QuarantineMode>,
 }
 
 #[derive(Clone, Debug)]
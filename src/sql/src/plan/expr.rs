@@ -31,6 +31,7 @@ use mz_ore::stack::RecursionLimitError;
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::numeric::NumericMaxScale;
 use mz_repr::*;
+use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
 
 use crate::plan::error::PlanError;
@@ -897,6 +898,37 @@ pub enum AggregateFunc {
     Count,
     Any,
     All,
+    /// The exclusive or of the non-null `Datum`s, i.e. `bool_xor`.
+    Xor,
+    BitAndInt16,
+    BitAndInt32,
+    BitAndInt64,
+    BitOrInt16,
+    BitOrInt32,
+    BitOrInt64,
+    BitXorInt16,
+    BitXorInt32,
+    BitXorInt64,
+    /// Estimates the number of distinct non-null `Datum`s.
+    ApproxCountDistinct,
+    /// The continuous percentile at `fraction` of the non-null `Datum`s,
+    /// i.e. `percentile_cont(fraction) WITHIN GROUP (ORDER BY ..)`.
+    PercentileCont {
+        fraction: OrderedFloat<f64>,
+    },
+    /// The discrete percentile at `fraction` of the non-null `Datum`s,
+    /// i.e. `percentile_disc(fraction) WITHIN GROUP (ORDER BY ..)`.
+    PercentileDisc {
+        fraction: OrderedFloat<f64>,
+        desc: bool,
+    },
+    /// The most frequent non-null `Datum`, i.e. `mode() WITHIN GROUP (ORDER BY ..)`.
+    Mode {
+        desc: bool,
+    },
+    /// The median of the non-null `Datum`s, which is the continuous percentile
+    /// at one half.
+    Median,
     /// Accumulates `Datum::List`s whose first element is a JSON-typed `Datum`s
     /// into a JSON list. The other elements are columns used by `order_by`.
     ///
@@ -977,6 +1009,27 @@ impl AggregateFunc {
             AggregateFunc::Count => mz_expr::AggregateFunc::Count,
             AggregateFunc::Any => mz_expr::AggregateFunc::Any,
             AggregateFunc::All => mz_expr::AggregateFunc::All,
+            AggregateFunc::Xor => mz_expr::AggregateFunc::Xor,
+            AggregateFunc::BitAndInt16 => mz_expr::AggregateFunc::BitAndInt16,
+            AggregateFunc::BitAndInt32 => mz_expr::AggregateFunc::BitAndInt32,
+            AggregateFunc::BitAndInt64 => mz_expr::AggregateFunc::BitAndInt64,
+            AggregateFunc::BitOrInt16 => mz_expr::AggregateFunc::BitOrInt16,
+            AggregateFunc::BitOrInt32 => mz_expr::AggregateFunc::BitOrInt32,
+            AggregateFunc::BitOrInt64 => mz_expr::AggregateFunc::BitOrInt64,
+            AggregateFunc::BitXorInt16 => mz_expr::AggregateFunc::BitXorInt16,
+            AggregateFunc::BitXorInt32 => mz_expr::AggregateFunc::BitXorInt32,
+            AggregateFunc::BitXorInt64 => mz_expr::AggregateFunc::BitXorInt64,
+            AggregateFunc::ApproxCountDistinct => mz_expr::AggregateFunc::ApproxCountDistinct,
+            AggregateFunc::PercentileCont { fraction } => {
+                mz_expr::AggregateFunc::PercentileCont { fraction }
+            }
+            AggregateFunc::PercentileDisc { fraction, desc } => {
+                mz_expr::AggregateFunc::PercentileDisc { fraction, desc }
+            }
+            AggregateFunc::Mode { desc } => mz_expr::AggregateFunc::Mode { desc },
+            AggregateFunc::Median => mz_expr::AggregateFunc::PercentileCont {
+                fraction: OrderedFloat(0.5),
+            },
             AggregateFunc::JsonbAgg { order_by } => mz_expr::AggregateFunc::JsonbAgg { order_by },
             AggregateFunc::JsonbObjectAgg { order_by } => {
                 mz_expr::AggregateFunc::JsonbObjectAgg { order_by }
@@ -1012,9 +1065,10 @@ impl AggregateFunc {
     /// counts.
     pub fn output_type(&self, input_type: ColumnType) -> ColumnType {
         let scalar_type = match self {
-            AggregateFunc::Count => ScalarType::Int64,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => ScalarType::Int64,
             AggregateFunc::Any => ScalarType::Bool,
             AggregateFunc::All => ScalarType::Bool,
+            AggregateFunc::Xor => ScalarType::Bool,
             AggregateFunc::JsonbAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::JsonbObjectAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::StringAgg { .. } => ScalarType::String,
@@ -1036,7 +1090,10 @@ impl AggregateFunc {
             _ => input_type.scalar_type,
         };
         // max/min/sum return null on empty sets
-        let nullable = !matches!(self, AggregateFunc::Count);
+        let nullable = !matches!(
+            self,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct
+        );
         scalar_type.nullable(nullable)
     }
 
@@ -1051,6 +1108,16 @@ impl AggregateFunc {
                 | StringAgg { .. }
         )
     }
+
+    /// Reports whether this is an ordered-set aggregate, which must be called
+    /// with a `WITHIN GROUP (ORDER BY ..)` clause.
+    pub fn is_ordered_set(&self) -> bool {
+        use AggregateFunc::*;
+        matches!(
+            self,
+            PercentileCont { .. } | PercentileDisc { .. } | Mode { .. }
+        )
+    }
 }
 
 impl HirRelationExpr {
@@ -2845,6 +2912,25 @@ impl HirScalarExpr {
         })
     }
 
+    /// Attempts to simplify this expression to a literal 64-bit float.
+    ///
+    /// Returns `None` if this expression cannot be simplified, e.g. because it
+    /// contains non-literal values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this expression does not have type [`ScalarType::Float64`].
+    pub fn into_literal_float64(self) -> Option<f64> {
+        self.simplify_to_literal().and_then(|row| {
+            let datum = row.unpack_first();
+            if datum.is_null() {
+                None
+            } else {
+                Some(datum.unwrap_float64())
+            }
+        })
+    }
+
     /// Attempts to simplify this expression to a literal string.
     ///
     /// Returns `None` if this expression cannot be simplified, e.g. because it
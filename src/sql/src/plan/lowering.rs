@@ -47,6 +47,7 @@ use mz_repr::*;
 use crate::plan::expr::{
     AggregateExpr, ColumnOrder, ColumnRef, HirRelationExpr, HirScalarExpr, JoinKind, WindowExprType,
 };
+use crate::plan::optimize::OptimizerConfig;
 use crate::plan::{transform_expr, PlanError};
 
 /// Maps a leveled column reference to a specific column.
@@ -129,7 +130,7 @@ impl HirRelationExpr {
     /// Rewrite `self` into a `mz_expr::MirRelationExpr`.
     /// This requires rewriting all correlated subqueries (nested `HirRelationExpr`s) into flat queries
     #[tracing::instrument(target = "optimizer", level = "debug", name = "hir_to_mir", skip_all)]
-    pub fn lower(self) -> Result<mz_expr::MirRelationExpr, PlanError> {
+    pub fn lower(self, config: &OptimizerConfig) -> Result<mz_expr::MirRelationExpr, PlanError> {
         let result = match self {
             // We directly rewrite a Constant into the corresponding `MirRelationExpr::Constant`
             // to ensure that the downstream optimizer can easily bypass most
@@ -149,7 +150,13 @@ impl HirRelationExpr {
                 transform_expr::try_simplify_quantified_comparisons(&mut other);
                 mz_expr::MirRelationExpr::constant(vec![vec![]], RelationType::new(vec![]))
                     .let_in_fallible(&mut id_gen, |id_gen, get_outer| {
-                        other.applied_to(id_gen, get_outer, &ColumnMap::empty(), &mut CteMap::new())
+                        other.applied_to(
+                            id_gen,
+                            get_outer,
+                            &ColumnMap::empty(),
+                            &mut CteMap::new(),
+                            config,
+                        )
                     })?
             }
         };
@@ -177,6 +184,7 @@ impl HirRelationExpr {
         get_outer: mz_expr::MirRelationExpr,
         col_map: &ColumnMap,
         cte_map: &mut CteMap,
+        config: &OptimizerConfig,
     ) -> Result<mz_expr::MirRelationExpr, PlanError> {
         maybe_grow(|| {
             use mz_expr::MirRelationExpr as SR;
@@ -263,7 +271,8 @@ impl HirRelationExpr {
                     value,
                     body,
                 } => {
-                    let value = value.applied_to(id_gen, get_outer.clone(), col_map, cte_map)?;
+                    let value =
+                        value.applied_to(id_gen, get_outer.clone(), col_map, cte_map, config)?;
                     value.let_in_fallible(id_gen, |id_gen, get_value| {
                         let (new_id, typ) = if let mz_expr::MirRelationExpr::Get {
                             id: mz_expr::Id::Local(id),
@@ -288,7 +297,7 @@ impl HirRelationExpr {
                                 outer_relation: get_outer.clone(),
                             },
                         );
-                        let body = body.applied_to(id_gen, get_outer, col_map, cte_map);
+                        let body = body.applied_to(id_gen, get_outer, col_map, cte_map, config);
                         if let Some(old_value) = old_value {
                             cte_map.insert(id, old_value);
                         } else {
@@ -337,10 +346,11 @@ impl HirRelationExpr {
                             get_outer.clone(),
                             col_map,
                             cte_map,
+                            config,
                         )?);
                     }
 
-                    let mir_body = body.applied_to(id_gen, get_outer, col_map, cte_map)?;
+                    let mir_body = body.applied_to(id_gen, get_outer, col_map, cte_map, config)?;
 
                     // Remove our bindings and reinstate any shadowed bindings.
                     for (id, shadowed) in shadowed_bindings {
@@ -362,7 +372,8 @@ impl HirRelationExpr {
                 Project { input, outputs } => {
                     // Projections should be applied to the decorrelated `inner`, and to its columns,
                     // which means rebasing `outputs` to start `get_outer.arity()` columns later.
-                    let input = input.applied_to(id_gen, get_outer.clone(), col_map, cte_map)?;
+                    let input =
+                        input.applied_to(id_gen, get_outer.clone(), col_map, cte_map, config)?;
                     let outputs = (0..get_outer.arity())
                         .chain(outputs.into_iter().map(|i| get_outer.arity() + i))
                         .collect::<Vec<_>>();
@@ -375,7 +386,8 @@ impl HirRelationExpr {
                     // arity of the HIR fragments lowered so far.
                     let mut lowered_arity = input.arity();
 
-                    let mut input = input.applied_to(id_gen, get_outer, col_map, cte_map)?;
+                    let mut input =
+                        input.applied_to(id_gen, get_outer, col_map, cte_map, config)?;
 
                     // Lower subqueries in maximally sized batches, such as no subquery in the current
                     // batch depends on columns from the same batch.
@@ -402,7 +414,7 @@ impl HirRelationExpr {
 
                         let old_arity = input.arity();
                         let (with_subqueries, subquery_map) = HirScalarExpr::lower_subqueries(
-                            &scalars, id_gen, col_map, cte_map, input,
+                            &scalars, id_gen, col_map, cte_map, input, config,
                         )?;
                         input = with_subqueries;
 
@@ -422,6 +434,7 @@ impl HirRelationExpr {
                                 cte_map,
                                 &mut input,
                                 &Some(&subquery_map),
+                                config,
                             )?;
                             input = input.map_one(scalar);
                             scalar_columns.push(input.arity() - 1);
@@ -444,7 +457,7 @@ impl HirRelationExpr {
 
                     let exprs = exprs
                         .into_iter()
-                        .map(|e| e.applied_to(id_gen, col_map, cte_map, &mut input, &None))
+                        .map(|e| e.applied_to(id_gen, col_map, cte_map, &mut input, &None, config))
                         .collect::<Result<Vec<_>, _>>()?;
 
                     let new_arity = input.arity();
@@ -464,11 +477,12 @@ impl HirRelationExpr {
                     // Filter expressions may contain correlated subqueries.
                     // We extend `get_outer` with sufficient values to determine the value of the predicate,
                     // then filter the results, then strip off any columns that were added for this purpose.
-                    let mut input = input.applied_to(id_gen, get_outer, col_map, cte_map)?;
+                    let mut input =
+                        input.applied_to(id_gen, get_outer, col_map, cte_map, config)?;
                     for predicate in predicates {
                         let old_arity = input.arity();
-                        let predicate =
-                            predicate.applied_to(id_gen, col_map, cte_map, &mut input, &None)?;
+                        let predicate = predicate
+                            .applied_to(id_gen, col_map, cte_map, &mut input, &None, config)?;
                         let new_arity = input.arity();
                         input = input.filter(vec![predicate]);
                         if old_arity != new_arity {
@@ -495,7 +509,7 @@ impl HirRelationExpr {
 
                     assert!(kind.can_be_correlated());
 
-                    let left = left.applied_to(id_gen, get_outer, col_map, cte_map)?;
+                    let left = left.applied_to(id_gen, get_outer, col_map, cte_map, config)?;
                     left.let_in_fallible(id_gen, |id_gen, get_left| {
                         let apply_requires_distinct_outer = false;
                         let mut join = branch(
@@ -506,13 +520,15 @@ impl HirRelationExpr {
                             *right,
                             apply_requires_distinct_outer,
                             |id_gen, right, get_left, col_map, cte_map| {
-                                right.applied_to(id_gen, get_left, col_map, cte_map)
+                                right.applied_to(id_gen, get_left, col_map, cte_map, config)
                             },
+                            config,
                         )?;
 
                         // Plan the `on` predicate.
                         let old_arity = join.arity();
-                        let on = on.applied_to(id_gen, col_map, cte_map, &mut join, &None)?;
+                        let on =
+                            on.applied_to(id_gen, col_map, cte_map, &mut join, &None, config)?;
                         join = join.filter(vec![on]);
                         let new_arity = join.arity();
                         if old_arity != new_arity {
@@ -553,13 +569,19 @@ impl HirRelationExpr {
                     // against the records present in the left and right (decorrelated) inputs,
                     // depending on the type of join.
                     let oa = get_outer.arity();
-                    let left = left.applied_to(id_gen, get_outer.clone(), col_map, cte_map)?;
+                    let left =
+                        left.applied_to(id_gen, get_outer.clone(), col_map, cte_map, config)?;
                     let lt = left.typ().column_types.into_iter().skip(oa).collect_vec();
                     let la = lt.len();
                     left.let_in_fallible(id_gen, |id_gen, get_left| {
                         let right_col_map = col_map.enter_scope(0);
-                        let right =
-                            right.applied_to(id_gen, get_outer.clone(), &right_col_map, cte_map)?;
+                        let right = right.applied_to(
+                            id_gen,
+                            get_outer.clone(),
+                            &right_col_map,
+                            cte_map,
+                            config,
+                        )?;
                         let rt = right.typ().column_types.into_iter().skip(oa).collect_vec();
                         let ra = rt.len();
                         right.let_in_fallible(id_gen, |id_gen, get_right| {
@@ -574,14 +596,31 @@ impl HirRelationExpr {
                                     .collect(),
                             );
                             let old_arity = product.arity();
-                            let on =
-                                on.applied_to(id_gen, col_map, cte_map, &mut product, &None)?;
+                            let on = on.applied_to(
+                                id_gen,
+                                col_map,
+                                cte_map,
+                                &mut product,
+                                &None,
+                                config,
+                            )?;
 
                             // Attempt an efficient equijoin implementation, in which outer joins are
                             // more efficiently rendered than in general. This can return `None` if
                             // such a plan is not possible, for example if `on` does not describe an
                             // equijoin between columns of `left` and `right`.
                             if kind != JoinKind::Inner {
+                                if config.enable_native_outer_joins {
+                                    if let Some(joined) = attempt_native_outer_join(
+                                        get_left.clone(),
+                                        get_right.clone(),
+                                        on.clone(),
+                                        kind.clone(),
+                                        oa,
+                                    ) {
+                                        return Ok(joined);
+                                    }
+                                }
                                 if let Some(joined) = attempt_outer_join(
                                     get_left.clone(),
                                     get_right.clone(),
@@ -654,11 +693,18 @@ impl HirRelationExpr {
                             get_outer.clone(),
                             col_map,
                             cte_map,
+                            config,
                         )?),
                         inputs: inputs
                             .into_iter()
                             .map(|input| {
-                                input.applied_to(id_gen, get_outer.clone(), col_map, cte_map)
+                                input.applied_to(
+                                    id_gen,
+                                    get_outer.clone(),
+                                    col_map,
+                                    cte_map,
+                                    config,
+                                )
                             })
                             .collect::<Result<Vec<_>, _>>()?,
                     }
@@ -673,13 +719,15 @@ impl HirRelationExpr {
                     // In addition, here an empty reduction key signifies that we need to supply default values
                     // in the case that there are no results (as in a SQL aggregation without an explicit GROUP BY).
                     let mut input =
-                        input.applied_to(id_gen, get_outer.clone(), col_map, cte_map)?;
+                        input.applied_to(id_gen, get_outer.clone(), col_map, cte_map, config)?;
                     let applied_group_key = (0..get_outer.arity())
                         .chain(group_key.iter().map(|i| get_outer.arity() + i))
                         .collect();
                     let applied_aggregates = aggregates
                         .into_iter()
-                        .map(|aggregate| aggregate.applied_to(id_gen, col_map, cte_map, &mut input))
+                        .map(|aggregate| {
+                            aggregate.applied_to(id_gen, col_map, cte_map, &mut input, config)
+                        })
                         .collect::<Result<Vec<_>, _>>()?;
                     let input_type = input.typ();
                     let default = applied_aggregates
@@ -704,7 +752,7 @@ impl HirRelationExpr {
                 Distinct { input } => {
                     // Distinct is uncomplicated.
                     input
-                        .applied_to(id_gen, get_outer, col_map, cte_map)?
+                        .applied_to(id_gen, get_outer, col_map, cte_map, config)?
                         .distinct()
                 }
                 TopK {
@@ -716,7 +764,8 @@ impl HirRelationExpr {
                     expected_group_size,
                 } => {
                     // TopK is uncomplicated, except that we must group by the columns of `get_outer` as well.
-                    let input = input.applied_to(id_gen, get_outer.clone(), col_map, cte_map)?;
+                    let input =
+                        input.applied_to(id_gen, get_outer.clone(), col_map, cte_map, config)?;
                     let applied_group_key = (0..get_outer.arity())
                         .chain(group_key.iter().map(|i| get_outer.arity() + i))
                         .collect();
@@ -739,13 +788,13 @@ impl HirRelationExpr {
                 Negate { input } => {
                     // Negate is uncomplicated.
                     input
-                        .applied_to(id_gen, get_outer, col_map, cte_map)?
+                        .applied_to(id_gen, get_outer, col_map, cte_map, config)?
                         .negate()
                 }
                 Threshold { input } => {
                     // Threshold is uncomplicated.
                     input
-                        .applied_to(id_gen, get_outer, col_map, cte_map)?
+                        .applied_to(id_gen, get_outer, col_map, cte_map, config)?
                         .threshold()
                 }
             })
@@ -771,6 +820,7 @@ impl HirScalarExpr {
         cte_map: &mut CteMap,
         inner: &mut mz_expr::MirRelationExpr,
         subquery_map: &Option<&BTreeMap<HirScalarExpr, usize>>,
+        config: &OptimizerConfig,
     ) -> Result<mz_expr::MirScalarExpr, PlanError> {
         maybe_grow(|| {
             use mz_expr::MirScalarExpr as SS;
@@ -796,6 +846,7 @@ impl HirScalarExpr {
                         cte_map,
                         inner,
                         subquery_map,
+                        config,
                     )?),
                 },
                 CallBinary { func, expr1, expr2 } => SS::CallBinary {
@@ -806,6 +857,7 @@ impl HirScalarExpr {
                         cte_map,
                         inner,
                         subquery_map,
+                        config,
                     )?),
                     expr2: Box::new(expr2.applied_to(
                         id_gen,
@@ -813,13 +865,16 @@ impl HirScalarExpr {
                         cte_map,
                         inner,
                         subquery_map,
+                        config,
                     )?),
                 },
                 CallVariadic { func, exprs } => SS::CallVariadic {
                     func,
                     exprs: exprs
                         .into_iter()
-                        .map(|expr| expr.applied_to(id_gen, col_map, cte_map, inner, subquery_map))
+                        .map(|expr| {
+                            expr.applied_to(id_gen, col_map, cte_map, inner, subquery_map, config)
+                        })
                         .collect::<Result<Vec<_>, _>>()?,
                 },
                 If { cond, then, els } => {
@@ -845,7 +900,7 @@ impl HirScalarExpr {
 
                     let inner_arity = inner.arity();
                     let cond_expr =
-                        cond.applied_to(id_gen, col_map, cte_map, inner, subquery_map)?;
+                        cond.applied_to(id_gen, col_map, cte_map, inner, subquery_map, config)?;
 
                     // Defensive copies, in case we mangle these in decorrelation.
                     let inner_clone = inner.clone();
@@ -854,9 +909,9 @@ impl HirScalarExpr {
 
                     let cond_arity = inner.arity();
                     let then_expr =
-                        then.applied_to(id_gen, col_map, cte_map, inner, subquery_map)?;
+                        then.applied_to(id_gen, col_map, cte_map, inner, subquery_map, config)?;
                     let else_expr =
-                        els.applied_to(id_gen, col_map, cte_map, inner, subquery_map)?;
+                        els.applied_to(id_gen, col_map, cte_map, inner, subquery_map, config)?;
 
                     if cond_arity == inner.arity() {
                         // If no additional columns were added, we simply return the
@@ -881,6 +936,7 @@ impl HirScalarExpr {
                                 cte_map,
                                 &mut then_inner,
                                 subquery_map,
+                                config,
                             )?;
                             let then_arity = then_inner.arity();
                             then_inner = then_inner
@@ -903,6 +959,7 @@ impl HirScalarExpr {
                                 cte_map,
                                 &mut else_inner,
                                 subquery_map,
+                                config,
                             )?;
                             let else_arity = else_inner.arity();
                             else_inner = else_inner
@@ -937,6 +994,7 @@ impl HirScalarExpr {
                         cte_map,
                         *expr,
                         apply_requires_distinct_outer,
+                        config,
                     )?;
                     SS::Column(inner.arity() - 1)
                 }
@@ -950,6 +1008,7 @@ impl HirScalarExpr {
                         cte_map,
                         *expr,
                         apply_requires_distinct_outer,
+                        config,
                     )?;
                     SS::Column(inner.arity() - 1)
                 }
@@ -973,6 +1032,7 @@ impl HirScalarExpr {
                                                 cte_map,
                                                 &mut get_inner,
                                                 subquery_map,
+                                                config,
                                             )
                                         })
                                         .collect::<Result<Vec<_>, _>>()?;
@@ -997,6 +1057,7 @@ impl HirScalarExpr {
                                             cte_map,
                                             &mut get_inner,
                                             subquery_map,
+                                            config,
                                         )?;
                                         if let mz_expr::MirScalarExpr::Column(c) = key {
                                             group_key.push(c);
@@ -1151,6 +1212,7 @@ impl HirScalarExpr {
                                                 cte_map,
                                                 &mut get_inner,
                                                 subquery_map,
+                                                config,
                                             )
                                         })
                                         .collect::<Result<Vec<_>, _>>()?;
@@ -1162,6 +1224,7 @@ impl HirScalarExpr {
                                         cte_map,
                                         &mut get_inner,
                                         subquery_map,
+                                        config,
                                     )?;
                                     let mir_encoded_args_type = mir_encoded_args
                                         .typ(&get_inner.typ().column_types)
@@ -1187,6 +1250,7 @@ impl HirScalarExpr {
                                             cte_map,
                                             &mut get_inner,
                                             subquery_map,
+                                            config,
                                         )?;
                                         if let mz_expr::MirScalarExpr::Column(c) = key {
                                             group_key.push(c);
@@ -1371,6 +1435,7 @@ impl HirScalarExpr {
         col_map: &ColumnMap,
         cte_map: &mut CteMap,
         inner: mz_expr::MirRelationExpr,
+        config: &OptimizerConfig,
     ) -> Result<(mz_expr::MirRelationExpr, BTreeMap<HirScalarExpr, usize>), PlanError> {
         let mut subquery_map = BTreeMap::new();
         let output = inner.let_in_fallible(id_gen, |id_gen, get_inner| {
@@ -1395,6 +1460,7 @@ impl HirScalarExpr {
                                 cte_map,
                                 (**expr).clone(),
                                 apply_requires_distinct_outer,
+                                config,
                             )
                             .unwrap();
 
@@ -1409,6 +1475,7 @@ impl HirScalarExpr {
                                 cte_map,
                                 (**expr).clone(),
                                 apply_requires_distinct_outer,
+                                config,
                             )
                             .unwrap();
                             subqueries.push((e.clone(), subquery));
@@ -1526,6 +1593,7 @@ fn branch<F>(
     inner: HirRelationExpr,
     apply_requires_distinct_outer: bool,
     apply: F,
+    config: &OptimizerConfig,
 ) -> Result<mz_expr::MirRelationExpr, PlanError>
 where
     F: FnOnce(
@@ -1709,6 +1777,7 @@ fn apply_scalar_subquery(
     cte_map: &mut CteMap,
     scalar_subquery: HirRelationExpr,
     apply_requires_distinct_outer: bool,
+    config: &OptimizerConfig,
 ) -> Result<mz_expr::MirRelationExpr, PlanError> {
     branch(
         id_gen,
@@ -1719,7 +1788,7 @@ fn apply_scalar_subquery(
         apply_requires_distinct_outer,
         |id_gen, expr, get_inner, col_map, cte_map| {
             // compute for every row in get_inner
-            let select = expr.applied_to(id_gen, get_inner.clone(), col_map, cte_map)?;
+            let select = expr.applied_to(id_gen, get_inner.clone(), col_map, cte_map, config)?;
             let col_type = select.typ().column_types.into_last();
 
             let inner_arity = get_inner.arity();
@@ -1755,6 +1824,7 @@ fn apply_scalar_subquery(
             let default = vec![(Datum::Null, col_type.scalar_type)];
             Ok(get_inner.lookup(id_gen, guarded, default))
         },
+        config,
     )
 }
 
@@ -1765,6 +1835,7 @@ fn apply_existential_subquery(
     cte_map: &mut CteMap,
     subquery_expr: HirRelationExpr,
     apply_requires_distinct_outer: bool,
+    config: &OptimizerConfig,
 ) -> Result<mz_expr::MirRelationExpr, PlanError> {
     branch(
         id_gen,
@@ -1776,7 +1847,7 @@ fn apply_existential_subquery(
         |id_gen, expr, get_inner, col_map, cte_map| {
             let exists = expr
                 // compute for every row in get_inner
-                .applied_to(id_gen, get_inner.clone(), col_map, cte_map)?
+                .applied_to(id_gen, get_inner.clone(), col_map, cte_map, config)?
                 // throw away actual values and just remember whether or not there were __any__ rows
                 .distinct_by((0..get_inner.arity()).collect())
                 // Append true to anything that returned any rows.
@@ -1785,6 +1856,7 @@ fn apply_existential_subquery(
             // append False to anything that didn't return any rows
             Ok(get_inner.lookup(id_gen, exists, vec![(Datum::False, ScalarType::Bool)]))
         },
+        config,
     )
 }
 
@@ -1795,6 +1867,7 @@ impl AggregateExpr {
         col_map: &ColumnMap,
         cte_map: &mut CteMap,
         inner: &mut mz_expr::MirRelationExpr,
+        config: &OptimizerConfig,
     ) -> Result<mz_expr::AggregateExpr, PlanError> {
         let AggregateExpr {
             func,
@@ -1804,7 +1877,7 @@ impl AggregateExpr {
 
         Ok(mz_expr::AggregateExpr {
             func: func.into_expr(),
-            expr: expr.applied_to(id_gen, col_map, cte_map, inner, &None)?,
+            expr: expr.applied_to(id_gen, col_map, cte_map, inner, &None, config)?,
             distinct,
         })
     }
@@ -1872,6 +1945,116 @@ pub(crate) fn derive_equijoin_cols(
     }
 }
 
+/// Attempts to plan an outer join as a native outer join, if `on` has equijoin structure and the
+/// inputs are not correlated with an outer context.
+///
+/// Unlike [`attempt_outer_join`], the result does not union the inner join with the unmatched
+/// records, which allows the join to be rendered by reusing the arrangements of its inputs.
+/// A native outer join preserves only one of its inputs, and so a full outer join is planned as
+/// a native left outer join, unioned with the records of `right` that have no match in `left`.
+fn attempt_native_outer_join(
+    left: mz_expr::MirRelationExpr,
+    right: mz_expr::MirRelationExpr,
+    mut on: mz_expr::MirScalarExpr,
+    kind: JoinKind,
+    oa: usize,
+) -> Option<mz_expr::MirRelationExpr> {
+    // The optional inputs of a native outer join cannot be matched on the columns of an outer
+    // context, which would need to be matched between all inputs.
+    if oa != 0 {
+        return None;
+    }
+
+    let la = left.arity();
+    let ra = right.arity();
+
+    let mut output_type = left.typ().column_types;
+    output_type.extend(right.typ().column_types);
+    on.reduce(&output_type);
+
+    let (l_keys, r_keys) = derive_equijoin_cols(oa, la, ra, vec![on])?;
+
+    // For full outer joins, the records of `right` without a match, padded with nulls.
+    let unmatched_right = if kind == JoinKind::FullOuter {
+        // The keys of `left` that can be matched by `right`.
+        let left_keys = left
+            .clone()
+            .filter(
+                l_keys
+                    .iter()
+                    .map(|c| mz_expr::MirScalarExpr::Column(*c).call_is_null().not())
+                    .collect::<Vec<_>>(),
+            )
+            .project(l_keys.clone())
+            .distinct();
+        // Rows in `right` that are matched by `left`.
+        let right_present = mz_expr::MirRelationExpr::join(
+            vec![right.clone(), left_keys],
+            r_keys
+                .iter()
+                .enumerate()
+                .map(|(i, c)| vec![(0, *c), (1, i)])
+                .collect(),
+        )
+        .project((0..ra).collect());
+        // Determine the types of nulls to use as filler.
+        let left_fill = output_type[..la]
+            .iter()
+            .map(|typ| mz_expr::MirScalarExpr::literal_null(typ.scalar_type.clone()))
+            .collect();
+        Some(
+            right_present
+                .negate()
+                .union(right.clone())
+                .map(left_fill)
+                // Permute left fill before right values.
+                .project((ra..ra + la).chain(0..ra).collect()),
+        )
+    } else {
+        None
+    };
+
+    let (preserved, preserved_keys, optional, optional_keys) = match kind {
+        JoinKind::LeftOuter | JoinKind::FullOuter => (left, l_keys, right, r_keys),
+        JoinKind::RightOuter => (right, r_keys, left, l_keys),
+        JoinKind::Inner => return None,
+    };
+    let preserved_arity = preserved.arity();
+    let optional_arity = optional.arity();
+
+    // Nulls never satisfy the equality predicates, but would be matched by the equivalences.
+    let optional = optional.filter(
+        optional_keys
+            .iter()
+            .map(|c| mz_expr::MirScalarExpr::Column(*c).call_is_null().not())
+            .collect::<Vec<_>>(),
+    );
+    let equivalences = preserved_keys
+        .into_iter()
+        .zip(optional_keys)
+        .map(|(p, o)| {
+            vec![
+                mz_expr::MirScalarExpr::Column(p),
+                mz_expr::MirScalarExpr::Column(preserved_arity + o),
+            ]
+        })
+        .collect();
+    let join =
+        mz_expr::MirRelationExpr::left_outer_join_scalars(vec![preserved, optional], equivalences);
+
+    Some(match kind {
+        JoinKind::RightOuter => join.project(
+            (preserved_arity..preserved_arity + optional_arity)
+                .chain(0..preserved_arity)
+                .collect(),
+        ),
+        _ => match unmatched_right {
+            Some(unmatched_right) => join.union(unmatched_right),
+            None => join,
+        },
+    })
+}
+
 /// Attempts an efficient outer join, if `on` has equijoin structure.
 fn attempt_outer_join(
     left: mz_expr::MirRelationExpr,
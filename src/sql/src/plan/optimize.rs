@@ -11,17 +11,28 @@
 
 use crate::plan::expr::HirRelationExpr;
 use crate::plan::{PlanError, StatementContext};
+use crate::session::vars::SystemVars;
 
 /// Feature flags for the [`HirRelationExpr::optimize_and_lower()`] logic.
 #[derive(Debug)]
 pub struct OptimizerConfig {
-    // TODO: add parameters driving the optimization pass here
+    /// Plan left and right outer equijoins as native outer joins.
+    pub enable_native_outer_joins: bool,
 }
 
 /// Convert a reference to a [`StatementContext`] to an [`OptimizerConfig`].
 impl<'a> From<&StatementContext<'a>> for OptimizerConfig {
-    fn from(_scx: &StatementContext) -> Self {
-        OptimizerConfig {}
+    fn from(scx: &StatementContext) -> Self {
+        OptimizerConfig::from(scx.catalog.system_vars())
+    }
+}
+
+/// Convert a reference to [`SystemVars`] to an [`OptimizerConfig`].
+impl From<&SystemVars> for OptimizerConfig {
+    fn from(vars: &SystemVars) -> Self {
+        OptimizerConfig {
+            enable_native_outer_joins: vars.enable_native_outer_joins(),
+        }
     }
 }
 
@@ -31,8 +42,8 @@ impl HirRelationExpr {
     /// The optimization path is fully-determined by the values of the feature flag defined in the [`OptimizerConfig`].
     pub fn optimize_and_lower(
         self,
-        _config: &OptimizerConfig,
+        config: &OptimizerConfig,
     ) -> Result<mz_expr::MirRelationExpr, PlanError> {
-        self.lower()
+        self.lower(config)
     }
 }
@@ -1189,6 +1189,51 @@ fn check_col_index(name: &str, e: &Expr<Aug>, max: usize) -> Result<Option<usize
     }
 }
 
+/// Plans the quantity of a `LIMIT` or `OFFSET` clause, as indicated by `name`.
+///
+/// The quantity can be any expression that simplifies to a constant, which is cast to an
+/// integer. Returns `None` if the quantity is `NULL`, which means no limit or no offset.
+fn plan_limit_quantity(
+    qcx: &QueryContext,
+    name: &str,
+    quantity: &Expr<Aug>,
+) -> Result<Option<usize>, PlanError> {
+    if let Expr::Value(Value::Number(x)) = quantity {
+        if let Ok(quantity) = x.parse() {
+            return Ok(Some(quantity));
+        }
+    }
+
+    let scope = Scope::empty();
+    let desc = RelationDesc::empty();
+    let qcx = QueryContext::root(qcx.scx, qcx.lifetime);
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name,
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    let mut expr = plan_expr(ecx, quantity)
+        .and_then(|expr| expr.cast_to(ecx, CastContext::Assignment, &ScalarType::Int64))
+        .and_then(|expr| expr.lower_uncorrelated())
+        .map_err(|_| sql_err!("{} must be an integer constant", name))?;
+    expr.reduce(&[]);
+    match expr {
+        MirScalarExpr::Literal(Ok(row), _) => match row.unpack_first() {
+            Datum::Null => Ok(None),
+            datum => usize::try_from(datum.unwrap_int64())
+                .map(Some)
+                .map_err(|_| sql_err!("{} must not be negative", name)),
+        },
+        MirScalarExpr::Literal(Err(e), _) => Err(e.into()),
+        _ => sql_bail!("{} must be an integer constant", name),
+    }
+}
+
 fn plan_query(
     qcx: &mut QueryContext,
     q: &Query<Aug>,
@@ -1207,22 +1252,17 @@ fn plan_query_inner(
     let limit = match &q.limit {
         None => None,
         Some(Limit {
-            quantity: Expr::Value(Value::Number(x)),
+            quantity,
             with_ties: false,
-        }) => Some(x.parse()?),
+        }) => plan_limit_quantity(qcx, "LIMIT", quantity)?,
         Some(Limit {
             quantity: _,
             with_ties: true,
         }) => bail_unsupported!("FETCH ... WITH TIES"),
-        Some(Limit {
-            quantity: _,
-            with_ties: _,
-        }) => sql_bail!("LIMIT must be an integer constant"),
     };
     let offset = match &q.offset {
         None => 0,
-        Some(Expr::Value(Value::Number(x))) => x.parse()?,
-        _ => sql_bail!("OFFSET must be an integer constant"),
+        Some(offset) => plan_limit_quantity(qcx, "OFFSET", offset)?.unwrap_or(0),
     };
 
     let (mut result, scope, finishing, expected_group_size) = match &q.body {
@@ -2838,6 +2878,7 @@ fn plan_table_function_internal(
         filter,
         over,
         distinct,
+        within_group,
     }: &Function<Aug>,
     with_ordinality: bool,
     table_name: Option<FullItemName>,
@@ -2845,6 +2886,10 @@ fn plan_table_function_internal(
     assert!(filter.is_none(), "cannot parse table function with FILTER");
     assert!(over.is_none(), "cannot parse table function with OVER");
     assert!(!*distinct, "cannot parse table function with DISTINCT");
+    assert!(
+        within_group.is_empty(),
+        "cannot parse table function with WITHIN GROUP"
+    );
 
     let ecx = &ExprContext {
         qcx,
@@ -4485,6 +4530,7 @@ fn plan_aggregate(
         filter,
         over,
         distinct,
+        within_group,
     }: &Function<Aug>,
 ) -> Result<AggregateExpr, PlanError> {
     // Normal aggregate functions, like `sum`, expect as input a single expression
@@ -4517,10 +4563,12 @@ fn plan_aggregate(
     // rules to all aggregates, not just `count`, since we may one day support
     // user-defined aggregates, including user-defined aggregates that take no
     // parameters.
-    let (args, order_by) = match &args {
+    let (mut args, order_by) = match &args {
         FunctionArgs::Star => (vec![], vec![]),
         FunctionArgs::Args { args, order_by } => {
-            if args.is_empty() {
+            // Ordered-set aggregates, like `mode`, may have no direct arguments,
+            // as their aggregated argument is in the WITHIN GROUP clause.
+            if args.is_empty() && within_group.is_empty() {
                 sql_bail!(
                     "{}(*) must be used to call a parameterless aggregate function",
                     ecx.qcx
@@ -4534,9 +4582,45 @@ fn plan_aggregate(
         }
     };
 
-    let (order_by_exprs, col_orders) = plan_function_order_by(ecx, &order_by)?;
+    let (order_by_exprs, col_orders) = if within_group.is_empty() {
+        plan_function_order_by(ecx, &order_by)?
+    } else {
+        // The aggregated argument of an ordered-set aggregate, like
+        // `percentile_cont(0.5) WITHIN GROUP (ORDER BY x)`, is given by the
+        // WITHIN GROUP clause, and follows the direct arguments. The
+        // implementation only learns of the requested ordering.
+        let obe = match &within_group[..] {
+            [obe] => obe,
+            _ => sql_bail!(
+                "{} expects exactly one expression in WITHIN GROUP",
+                ecx.qcx
+                    .scx
+                    .humanize_resolved_name(name)
+                    .expect("name actually resolved")
+            ),
+        };
+        args.push(plan_expr(ecx, &obe.expr)?);
+        (vec![], vec![resolve_desc_and_nulls_last(obe, 0)])
+    };
 
     let (mut expr, func) = func::select_impl(ecx, FuncSpec::Func(name), impls, args, col_orders)?;
+    if !within_group.is_empty() && !func.is_ordered_set() {
+        sql_bail!(
+            "{} is not an ordered-set aggregate, so it cannot have WITHIN GROUP",
+            ecx.qcx
+                .scx
+                .humanize_resolved_name(name)
+                .expect("name actually resolved")
+        );
+    } else if within_group.is_empty() && func.is_ordered_set() {
+        sql_bail!(
+            "WITHIN GROUP is required for ordered-set aggregate {}",
+            ecx.qcx
+                .scx
+                .humanize_resolved_name(name)
+                .expect("name actually resolved")
+        );
+    }
     if let Some(filter) = &filter {
         // If a filter is present, as in
         //
@@ -4699,6 +4783,7 @@ fn plan_function<'a>(
         filter,
         over,
         distinct,
+        within_group,
     }: &'a Function<Aug>,
 ) -> Result<HirScalarExpr, PlanError> {
     let impls = match resolve_func(ecx, name, args)? {
@@ -4804,6 +4889,15 @@ fn plan_function<'a>(
                 .expect("already resolved")
         );
     }
+    if !within_group.is_empty() {
+        sql_bail!(
+            "WITHIN GROUP specified, but {} is not an aggregate function",
+            ecx.qcx
+                .scx
+                .humanize_resolved_name(name)
+                .expect("already resolved")
+        );
+    }
 
     let scalar_args = match &args {
         FunctionArgs::Star => {
@@ -5022,6 +5116,7 @@ fn validate_window_function_plan<'a>(
         filter,
         over,
         distinct,
+        within_group,
     }: &'a Function<Aug>,
 ) -> Result<
     (
@@ -5053,6 +5148,13 @@ fn validate_window_function_plan<'a>(
         bail_unsupported!("FILTER in non-aggregate window functions");
     }
 
+    if !within_group.is_empty() {
+        sql_bail!(
+            "WITHIN GROUP specified, but {} is not an aggregate function",
+            name
+        );
+    }
+
     let window_spec = match over.as_ref() {
         Some(over) => over,
         None => sql_bail!("window function {} requires an OVER clause", name),
@@ -5449,6 +5551,7 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
                     filter,
                     over: _,
                     distinct: _,
+                    within_group,
                 } = func;
                 if let Some(filter) = filter {
                     self.visit_expr_mut(filter);
@@ -5459,6 +5562,9 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
                     .push("aggregate function calls");
 
                 self.visit_function_args_mut(args);
+                for order_by in within_group {
+                    self.visit_order_by_expr_mut(order_by);
+                }
 
                 self.within_aggregate = old_within_aggregate;
                 self.table_disallowed_context.pop();
@@ -5516,14 +5622,17 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
                 filter: None,
                 over: None,
                 distinct: false,
+                within_group,
             } = &func
             {
-                // Identical table functions can be de-duplicated.
-                let id = self
-                    .tables
-                    .entry(func)
-                    .or_insert_with(|| format!("table_func_{}", Uuid::new_v4()));
-                *expr = Expr::Identifier(vec![Ident::from(id.clone())]);
+                if within_group.is_empty() {
+                    // Identical table functions can be de-duplicated.
+                    let id = self
+                        .tables
+                        .entry(func)
+                        .or_insert_with(|| format!("table_func_{}", Uuid::new_v4()));
+                    *expr = Expr::Identifier(vec![Ident::from(id.clone())]);
+                }
             }
         }
         if let Some(context) = disallowed_context {
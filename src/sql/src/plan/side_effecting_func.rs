@@ -161,12 +161,13 @@ fn extract_sef_call(
                 filter: None,
                 over: None,
                 distinct: false,
+                within_group,
             }),
         alias: None,
     }] = &projection[..] else {
         return Ok(None);
     };
-    if !order_by.is_empty() {
+    if !order_by.is_empty() || !within_group.is_empty() {
         return Ok(None);
     }
 
@@ -37,6 +37,7 @@ use crate::plan::{query, with_options, Params, Plan, PlanContext, PlanKind};
 use crate::session::vars::FeatureFlag;
 
 mod acl;
+mod analyze;
 pub(crate) mod ddl;
 mod dml;
 mod raise;
@@ -223,6 +224,7 @@ pub fn describe(
             scl::describe_inspect_shard(&scx, stmt)?
         }
         Statement::ValidateConnection(stmt) => validate::describe_validate_connection(&scx, stmt)?,
+        Statement::Analyze(stmt) => analyze::describe_analyze(&scx, stmt)?,
     };
 
     let desc = desc.with_params(scx.finalize_param_types()?);
@@ -385,6 +387,7 @@ pub fn plan(
         Statement::Raise(stmt) => raise::plan_raise(scx, stmt),
         Statement::Show(ShowStatement::InspectShard(stmt)) => scl::plan_inspect_shard(scx, stmt),
         Statement::ValidateConnection(stmt) => validate::plan_validate_connection(scx, stmt),
+        Statement::Analyze(stmt) => analyze::plan_analyze(scx, stmt),
     };
 
     if let Ok(plan) = &plan {
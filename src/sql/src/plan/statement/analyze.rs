@@ -0,0 +1,44 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Queries that collect statistics about the contents of relations.
+
+use crate::ast::AnalyzeStatement;
+use crate::catalog::CatalogItemType;
+use crate::names::Aug;
+use crate::plan::statement::{StatementContext, StatementDesc};
+use crate::plan::{AnalyzePlan, Plan, PlanError};
+use crate::session::vars;
+
+pub fn describe_analyze(
+    _: &StatementContext,
+    _: AnalyzeStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_analyze(
+    scx: &StatementContext,
+    stmt: AnalyzeStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_ANALYZE)?;
+    let item = scx.get_item_by_resolved_name(&stmt.name)?;
+
+    // Only relations that are stored in persist can be analyzed.
+    match item.item_type() {
+        CatalogItemType::Table | CatalogItemType::Source | CatalogItemType::MaterializedView => {
+            Ok(Plan::Analyze(AnalyzePlan { id: item.id() }))
+        }
+        _ => sql_bail!(
+            "cannot analyze {} '{}'",
+            item.item_type(),
+            stmt.name.full_name_str()
+        ),
+    }
+}
@@ -17,6 +17,7 @@ use std::fmt::Write;
 use std::iter;
 
 use itertools::Itertools;
+use mz_compute_client::types::sinks::QuarantineMode;
 use mz_controller::clusters::{ClusterId, ReplicaId, DEFAULT_REPLICA_LOGGING_INTERVAL_MICROS};
 use mz_expr::CollectionPlan;
 use mz_interchange::avro::AvroSchemaGenerator;
@@ -78,11 +79,11 @@ use crate::ast::{
     IndexOptionName, KafkaBroker, KafkaBrokerAwsPrivatelinkOption,
     KafkaBrokerAwsPrivatelinkOptionName, KafkaBrokerTunnel, KafkaConfigOptionName,
     KafkaConnectionOption, KafkaConnectionOptionName, KeyConstraint, LoadGeneratorOption,
-    LoadGeneratorOptionName, PgConfigOption, PgConfigOptionName, PostgresConnectionOption,
-    PostgresConnectionOptionName, ProtobufSchema, QualifiedReplica, ReferencedSubsources,
-    ReplicaDefinition, ReplicaOption, ReplicaOptionName, RoleAttribute, SourceIncludeMetadata,
-    SourceIncludeMetadataType, SshConnectionOptionName, Statement, TableConstraint,
-    UnresolvedDatabaseName, ViewDefinition,
+    LoadGeneratorOptionName, OnErrorBehavior, PgConfigOption, PgConfigOptionName,
+    PostgresConnectionOption, PostgresConnectionOptionName, ProtobufSchema, QualifiedReplica,
+    ReferencedSubsources, ReplicaDefinition, ReplicaOption, ReplicaOptionName, RoleAttribute,
+    SourceIncludeMetadata, SourceIncludeMetadataType, SshConnectionOptionName, Statement,
+    TableConstraint, TableOption, TableOptionName, UnresolvedDatabaseName, ViewDefinition,
 };
 use crate::catalog::{
     CatalogCluster, CatalogDatabase, CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails,
@@ -178,6 +179,8 @@ pub fn plan_create_schema(
     }))
 }
 
+generate_extracted_config!(TableOption, (AppendOnly, bool, Default(false)));
+
 pub fn describe_create_table(
     _: &StatementContext,
     _: CreateTableStatement<Aug>,
@@ -195,6 +198,7 @@ pub fn plan_create_table(
         constraints,
         if_not_exists,
         temporary,
+        with_options,
     } = &stmt;
 
     let names: Vec<_> = columns
@@ -311,6 +315,11 @@ pub fn plan_create_table(
         scx.require_feature_flag(&vars::ENABLE_TABLE_KEYS)?
     }
 
+    let TableOptionExtracted { append_only, .. } = with_options.clone().try_into()?;
+    if append_only {
+        scx.require_feature_flag(&vars::ENABLE_APPEND_ONLY_TABLES)?;
+    }
+
     let typ = RelationType::new(column_types).with_keys(keys);
 
     let temporary = *temporary;
@@ -338,6 +347,7 @@ pub fn plan_create_table(
         desc,
         defaults,
         temporary,
+        append_only,
     };
     Ok(Plan::CreateTable(CreateTablePlan {
         name,
@@ -1958,6 +1968,17 @@ pub fn plan_create_materialized_view(
         print_name: None,
     });
 
+    let on_error = match stmt.on_error {
+        None => None,
+        Some(on_error) => {
+            scx.require_feature_flag(&vars::ENABLE_MATERIALIZED_VIEW_ON_ERROR)?;
+            Some(match on_error {
+                OnErrorBehavior::SkipRow => QuarantineMode::SkipRow,
+                OnErrorBehavior::Null => QuarantineMode::Null,
+            })
+        }
+    };
+
     let create_sql =
         normalize::create_statement(scx, Statement::CreateMaterializedView(stmt.clone()))?;
 
@@ -2049,6 +2070,7 @@ pub fn plan_create_materialized_view(
             expr,
             column_names,
             cluster_id,
+            on_error,
         },
         replace,
         drop_ids,
@@ -19,6 +19,7 @@ use itertools::Itertools;
 use mz_expr::MirRelationExpr;
 use mz_ore::collections::CollectionExt;
 use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams};
+use mz_repr::adt::interval::Interval;
 use mz_repr::adt::numeric::NumericMaxScale;
 use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::{RelationDesc, ScalarType};
@@ -29,8 +30,8 @@ use crate::ast::{
     AstInfo, CopyDirection, CopyOption, CopyOptionName, CopyRelation, CopyStatement, CopyTarget,
     CreateMaterializedViewStatement, CreateViewStatement, DeleteStatement, ExplainStage,
     ExplainStatement, Explainee, Ident, InsertStatement, Query, SelectStatement, Statement,
-    SubscribeOption, SubscribeOptionName, SubscribeRelation, SubscribeStatement, UpdateStatement,
-    ViewDefinition,
+    SubscribeOption, SubscribeOptionName, SubscribeRelation, SubscribeResume, SubscribeStatement,
+    UpdateStatement, ViewDefinition,
 };
 use crate::catalog::CatalogItemType;
 use crate::names::{self, Aug, ResolvedItemName};
@@ -42,7 +43,8 @@ use crate::plan::with_options::TryFromValue;
 use crate::plan::{self, side_effecting_func};
 use crate::plan::{
     query, CopyFormat, CopyFromPlan, ExplainPlan, InsertPlan, MutationKind, Params, Plan,
-    PlanError, QueryContext, ReadThenWritePlan, SelectPlan, SubscribeFrom, SubscribePlan,
+    PlanError, QueryContext, ReadThenWritePlan, ResumableSubscribe, SelectPlan, SubscribeFrom,
+    SubscribePlan,
 };
 use crate::session::vars;
 
@@ -393,7 +395,16 @@ pub fn plan_query(
     })
 }
 
-generate_extracted_config!(SubscribeOption, (Snapshot, bool), (Progress, bool));
+generate_extracted_config!(
+    SubscribeOption,
+    (Snapshot, bool),
+    (Progress, bool),
+    (Name, String),
+    (Retention, Interval),
+    (BatchSize, u64),
+    (EmitEvery, Interval),
+    (MaxPendingRows, u64)
+);
 
 pub fn describe_subscribe(
     scx: &StatementContext,
@@ -474,6 +485,7 @@ pub fn plan_subscribe(
     SubscribeStatement {
         relation,
         options,
+        resume_from,
         as_of,
         up_to,
         output,
@@ -518,6 +530,7 @@ pub fn plan_subscribe(
         }
     };
 
+    let as_of_specified = as_of.is_some();
     let when = query::plan_as_of(scx, as_of)?;
     let up_to = up_to.map(|up_to| plan_up_to(scx, up_to)).transpose()?;
 
@@ -621,8 +634,69 @@ pub fn plan_subscribe(
     };
 
     let SubscribeOptionExtracted {
-        progress, snapshot, ..
+        progress,
+        snapshot,
+        name,
+        retention,
+        batch_size,
+        emit_every,
+        max_pending_rows,
+        ..
     } = options.try_into()?;
+
+    if batch_size.is_some() || emit_every.is_some() || max_pending_rows.is_some() {
+        scx.require_feature_flag(&vars::ENABLE_SUBSCRIBE_FLOW_CONTROL)?;
+    }
+    if batch_size == Some(0) {
+        sql_bail!("BATCH SIZE must be greater than zero");
+    }
+    if max_pending_rows == Some(0) {
+        sql_bail!("MAX PENDING ROWS must be greater than zero");
+    }
+    let emit_interval = emit_every
+        .map(|emit_every| emit_every.duration())
+        .transpose()?;
+    if emit_interval.map_or(false, |interval| interval.as_millis() == 0) {
+        sql_bail!("EMIT EVERY must be at least 1 millisecond");
+    }
+
+    if name.is_some() || retention.is_some() || resume_from.is_some() {
+        scx.require_feature_flag(&vars::ENABLE_RESUMABLE_SUBSCRIBE)?;
+    }
+    let retention = retention
+        .map(|retention| retention.duration())
+        .transpose()?;
+    let resumable = match (name, resume_from) {
+        (None, None) => {
+            if retention.is_some() {
+                sql_bail!("RETENTION can only be specified for named subscribes");
+            }
+            None
+        }
+        (Some(_), Some(_)) => sql_bail!("cannot specify both NAME and RESUME FROM"),
+        (Some(name), None) => Some(ResumableSubscribe {
+            name,
+            resume: false,
+            resume_at: None,
+            retention,
+        }),
+        (None, Some(SubscribeResume { name, at })) => {
+            if as_of_specified {
+                sql_bail!("cannot specify both AS OF and RESUME FROM");
+            }
+            if snapshot == Some(true) {
+                sql_bail!("resumed subscribes do not emit a snapshot");
+            }
+            let resume_at = at.map(|at| plan_up_to(scx, at)).transpose()?;
+            Some(ResumableSubscribe {
+                name,
+                resume: true,
+                resume_at,
+                retention,
+            })
+        }
+    };
+
     Ok(Plan::Subscribe(SubscribePlan {
         from,
         when,
@@ -631,6 +705,10 @@ pub fn plan_subscribe(
         copy_to,
         emit_progress: progress.unwrap_or(false),
         output,
+        resumable,
+        batch_size,
+        emit_interval,
+        max_pending_rows,
     }))
 }
 
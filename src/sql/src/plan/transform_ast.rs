@@ -17,8 +17,8 @@ use mz_ore::stack::{CheckedRecursion, RecursionGuard};
 use mz_repr::namespaces::{MZ_CATALOG_SCHEMA, MZ_INTERNAL_SCHEMA, PG_CATALOG_SCHEMA};
 use mz_sql_parser::ast::visit_mut::{self, VisitMut, VisitMutNode};
 use mz_sql_parser::ast::{
-    Expr, Function, FunctionArgs, Ident, Op, OrderByExpr, Query, Select, SelectItem, TableAlias,
-    TableFactor, TableWithJoins, Value,
+    Expr, Function, FunctionArgs, Ident, IsExprConstruct, Op, OrderByExpr, Query, Select,
+    SelectItem, TableAlias, TableFactor, TableWithJoins, Value,
 };
 use uuid::Uuid;
 
@@ -58,6 +58,9 @@ where
 //   * Rewrites the suite of standard deviation and variance functions in a
 //     manner similar to `avg`.
 //
+//   * Rewrites the covariance and linear regression functions, like `corr`
+//     and `regr_slope`, in terms of sums of their arguments and products.
+//
 // TODO(sploiselle): rewrite these in terms of func::sql_op!
 struct FuncRewriter<'a> {
     scx: &'a StatementContext<'a>,
@@ -98,6 +101,14 @@ impl<'a> FuncRewriter<'a> {
         })
     }
 
+    fn float64_data_type(&self) -> ResolvedDataType {
+        self.resolve_known_valid_data_type(&PartialItemName {
+            database: None,
+            schema: Some(PG_CATALOG_SCHEMA.into()),
+            item: "float8".into(),
+        })
+    }
+
     // Divides `lhs` by `rhs` but replaces division-by-zero errors with NULL;
     // note that this is semantically equivalent to `NULLIF(rhs, 0)`.
     fn plan_divide(lhs: Expr<Aug>, rhs: Expr<Aug>) -> Expr<Aug> {
@@ -131,6 +142,7 @@ impl<'a> FuncRewriter<'a> {
             filter,
             over: None,
             distinct,
+            within_group: vec![],
         })
     }
 
@@ -296,6 +308,125 @@ impl<'a> FuncRewriter<'a> {
         sum.gt(Expr::Value(Value::Number(0.to_string())))
     }
 
+    fn plan_regression(
+        &mut self,
+        name: &str,
+        y: Expr<Aug>,
+        x: Expr<Aug>,
+        filter: Option<Box<Expr<Aug>>>,
+        distinct: bool,
+    ) -> Expr<Aug> {
+        // Like the variance calculation above, this uses the "textbook"
+        // algorithm, which only needs sums of the inputs and their products,
+        // and so can be maintained with accumulable reductions.
+        //
+        // Only the rows where both `y` and `x` are non-null take part. Writing
+        // N, Sx, Sy, Sxx, Syy, and Sxy for the count and sums over these rows,
+        // the code below converts the function into an expression in
+        //
+        //     regr_sxx(y, x) = Sxx - Sx² / N
+        //     regr_syy(y, x) = Syy - Sy² / N
+        //     regr_sxy(y, x) = Sxy - Sx * Sy / N
+        //
+        // e.g., covar_pop(y, x) is regr_sxy(y, x) / N, and regr_slope(y, x) is
+        // regr_sxy(y, x) / regr_sxx(y, x).
+        if distinct && self.status.is_ok() {
+            self.status = Err(PlanError::Unstructured(format!(
+                "DISTINCT is not supported for {}",
+                name
+            )));
+        }
+
+        // The `CASE` expressions implicitly cast their results to `float8`, as
+        // PostgreSQL's functions only take `float8` arguments.
+        let float64 = self.float64_data_type();
+        let paired = |value: Expr<Aug>, other: Expr<Aug>| Expr::Case {
+            operand: None,
+            conditions: vec![Expr::IsExpr {
+                expr: Box::new(other),
+                construct: IsExprConstruct::Null,
+                negated: false,
+            }],
+            results: vec![Expr::null().cast(float64.clone())],
+            else_result: Some(Box::new(value)),
+        };
+        let (y, x) = (paired(y.clone(), x.clone()), paired(x, y));
+
+        let mut sum = |expr: Expr<Aug>| {
+            self.plan_agg(
+                self.scx
+                    .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, "sum"]),
+                expr,
+                vec![],
+                filter.clone(),
+                false,
+            )
+        };
+        let sum_x = sum(x.clone());
+        let sum_y = sum(y.clone());
+        let sum_xx = sum(x.clone().multiply(x.clone()));
+        let sum_yy = sum(y.clone().multiply(y.clone()));
+        let sum_xy = sum(x.clone().multiply(y));
+        let count = self.plan_agg(
+            self.scx
+                .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, "count"]),
+            x,
+            vec![],
+            filter,
+            false,
+        );
+
+        let sxx = sum_xx.minus(Self::plan_divide(
+            sum_x.clone().multiply(sum_x.clone()),
+            count.clone(),
+        ));
+        let syy = sum_yy.minus(Self::plan_divide(
+            sum_y.clone().multiply(sum_y.clone()),
+            count.clone(),
+        ));
+        let sxy = sum_xy.minus(Self::plan_divide(
+            sum_x.clone().multiply(sum_y.clone()),
+            count.clone(),
+        ));
+        let slope = Self::plan_divide(sxy.clone(), sxx.clone());
+        match name {
+            "regr_count" => count,
+            "regr_avgx" => Self::plan_divide(sum_x, count),
+            "regr_avgy" => Self::plan_divide(sum_y, count),
+            "regr_sxx" => sxx,
+            "regr_syy" => syy,
+            "regr_sxy" => sxy,
+            "covar_pop" => Self::plan_divide(sxy, count),
+            "covar_samp" => Self::plan_divide(sxy, count.minus(Expr::number("1"))),
+            "corr" => Self::plan_divide(
+                sxy,
+                sxx.multiply(syy).call_unary(
+                    self.scx
+                        .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, "sqrt"]),
+                ),
+            ),
+            "regr_slope" => slope,
+            "regr_intercept" => Self::plan_divide(sum_y.minus(sum_x.multiply(slope)), count),
+            "regr_r2" => {
+                // Like PostgreSQL, a vertical line has no fit, and a
+                // horizontal line is a perfect one.
+                Expr::Case {
+                    operand: None,
+                    conditions: vec![syy
+                        .clone()
+                        .equals(Expr::number("0"))
+                        .and(sxx.clone().equals(Expr::number("0")).negate())],
+                    results: vec![Expr::number("1").cast(float64)],
+                    else_result: Some(Box::new(Self::plan_divide(
+                        sxy.clone().multiply(sxy),
+                        sxx.multiply(syy),
+                    ))),
+                }
+            }
+            _ => unreachable!("{} is not a regression function", name),
+        }
+    }
+
     fn rewrite_function(&mut self, func: &Function<Aug>) -> Option<(Ident, Expr<Aug>)> {
         if let Function {
             name,
@@ -303,6 +434,7 @@ impl<'a> FuncRewriter<'a> {
             filter,
             distinct,
             over: None,
+            within_group,
         } = func
         {
             let name = match name {
@@ -343,17 +475,34 @@ impl<'a> FuncRewriter<'a> {
             } else if args.len() == 2 {
                 let (lhs, rhs) = (args[0].clone(), args[1].clone());
                 match name.as_str() {
-                    "mod" => lhs.modulo(rhs),
-                    "pow" => Expr::call(
+                    "mod" if within_group.is_empty() => lhs.modulo(rhs),
+                    "pow" if within_group.is_empty() => Expr::call(
                         self.scx
                             .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, "power"]),
                         vec![lhs, rhs],
                     ),
+                    "corr" | "covar_pop" | "covar_samp" | "regr_avgx" | "regr_avgy"
+                    | "regr_count" | "regr_intercept" | "regr_r2" | "regr_slope" | "regr_sxx"
+                    | "regr_sxy" | "regr_syy" => {
+                        self.plan_regression(&name, lhs, rhs, filter, distinct)
+                    }
                     _ => return None,
                 }
             } else {
                 return None;
             };
+            if !within_group.is_empty() {
+                // None of the aggregates rewritten here are ordered-set
+                // aggregates, and they cannot be planned without being
+                // rewritten, so reject the call here.
+                if self.status.is_ok() {
+                    self.status = Err(PlanError::Unstructured(format!(
+                        "{} is not an ordered-set aggregate, so it cannot have WITHIN GROUP",
+                        name
+                    )));
+                }
+                return None;
+            }
             Some((Ident::new(name), expr))
         } else {
             None
@@ -571,6 +720,7 @@ impl<'a> Desugarer<'a> {
                                 filter: None,
                                 over: None,
                                 distinct: false,
+                                within_group: vec![],
                             },
                             alias: Some(TableAlias {
                                 name: Ident::new("_"),
@@ -274,6 +274,7 @@ static VALID_CASTS: Lazy<BTreeMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
 
         // MZ_TIMESTAMP
         (MzTimestamp, String) => Assignment: CastMzTimestampToString(func::CastMzTimestampToString),
+        (MzTimestamp, TimestampTz) => Explicit: CastMzTimestampToTimestampTz(func::CastMzTimestampToTimestampTz),
         (String, MzTimestamp) => Assignment: CastStringToMzTimestamp(func::CastStringToMzTimestamp),
         (UInt64, MzTimestamp) => Implicit: CastUint64ToMzTimestamp(func::CastUint64ToMzTimestamp),
         (UInt32, MzTimestamp) => Implicit: CastUint32ToMzTimestamp(func::CastUint32ToMzTimestamp),
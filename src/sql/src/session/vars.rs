@@ -77,6 +77,7 @@ use mz_build_info::BuildInfo;
 use mz_ore::cast;
 use mz_ore::cast::CastFrom;
 use mz_ore::str::StrExt;
+use mz_persist_client::batch::PartCompression;
 use mz_persist_client::cfg::PersistConfig;
 use mz_repr::adt::numeric::Numeric;
 use mz_sql_parser::ast::TransactionIsolationLevel;
@@ -981,6 +982,15 @@ const PERSIST_ROLLUP_THRESHOLD: ServerVar<usize> = ServerVar {
     internal: true,
 };
 
+/// Controls [`mz_persist_client::cfg::DynamicConfig::part_compression`].
+const PERSIST_PART_COMPRESSION: ServerVar<PartCompression> = ServerVar {
+    name: UncasedStr::new("persist_part_compression"),
+    value: &PersistConfig::DEFAULT_PART_COMPRESSION,
+    description: "The compression codec (none, zstd, or lz4) applied to newly written \
+                  persist batch parts (Materialize).",
+    internal: true,
+};
+
 /// Boolean flag indicating that the remote configuration was synchronized at
 /// least once with the persistent [SessionVars].
 pub static CONFIG_HAS_SYNCED_ONCE: ServerVar<bool> = ServerVar {
@@ -1081,6 +1091,15 @@ const ENABLE_MZ_JOIN_CORE: ServerVar<bool> = ServerVar {
     internal: true,
 };
 
+const ENABLE_COMPUTE_ARRANGEMENT_SPILLING: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("enable_compute_arrangement_spilling"),
+    value: &false,
+    description:
+        "Feature flag indicating whether compute replicas with disk may spill large arrangements \
+         to their scratch directory (Materialize).",
+    internal: true,
+};
+
 pub const ENABLE_DEFAULT_CONNECTION_VALIDATION: ServerVar<bool> = ServerVar {
     name: UncasedStr::new("enable_default_connection_validation"),
     value: &true,
@@ -1134,6 +1153,25 @@ pub const ENABLE_CONSOLIDATE_AFTER_UNION_NEGATE: ServerVar<bool> = ServerVar {
     internal: false,
 };
 
+/// The number of rows per group, counting those skipped by the offset, beyond which TopK
+/// operators are maintained in order, if `enable_ordered_top_k` is set.
+const ORDERED_TOP_K_THRESHOLD: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("ordered_top_k_threshold"),
+    value: &16,
+    description: "The number of rows per group, counting those skipped by the offset, beyond which TopK operators are maintained in order (Materialize).",
+    internal: true,
+};
+
+/// How long `ALTER CLUSTER` waits for new or remaining replicas to hydrate before dropping
+/// the replicas they replace, if `enable_hydration_aware_cluster_alter` is set.
+const CLUSTER_ALTER_HYDRATION_TIMEOUT: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("cluster_alter_hydration_timeout"),
+    value: &Duration::from_secs(300),
+    description:
+        "How long ALTER CLUSTER waits for replicas to hydrate before giving up (Materialize).",
+    internal: true,
+};
+
 /// Configuration for gRPC client connections.
 mod grpc_client {
     use super::*;
@@ -1221,6 +1259,8 @@ feature_flags!(
     // Gates for other feature flags
     (allow_real_time_recency, "real time recency"),
     // Actual feature flags
+    (enable_analyze, "ANALYZE statement"),
+    (enable_append_only_tables, "APPEND ONLY tables"),
     (
         enable_binary_date_bin,
         "the binary version of date_bin function"
@@ -1247,7 +1287,16 @@ feature_flags!(
         enable_envelope_upsert_in_subscribe,
         "`ENVELOPE UPSERT` can be used in `SUBSCRIBE`"
     ),
+    (
+        enable_hydration_aware_cluster_alter,
+        "blocking ALTER CLUSTER on replica hydration"
+    ),
     (enable_index_options, "INDEX OPTIONS"),
+    (
+        enable_incremental_window_functions,
+        "incremental maintenance of window functions"
+    ),
+    (enable_ordered_top_k, "ordered TopK maintenance"),
     (
         enable_kafka_config_denylist_options,
         "Kafka sources with non-allowlisted options"
@@ -1259,6 +1308,15 @@ feature_flags!(
         enable_logical_compaction_window,
         "LOGICAL COMPACTION WINDOW"
     ),
+    (
+        enable_materialized_view_on_error,
+        "ON ERROR for materialized views"
+    ),
+    (enable_resumable_subscribe, "named, resumable SUBSCRIBE"),
+    (
+        enable_subscribe_flow_control,
+        "SUBSCRIBE batching and flow control options"
+    ),
     (
         enable_monotonic_oneshot_selects,
         "monotonic evaluation of one-shot SELECT queries"
@@ -1292,6 +1350,14 @@ feature_flags!(
         enable_disk_cluster_replicas,
         "`WITH (DISK)` for cluster replicas"
     ),
+    (
+        enable_window_table_functions,
+        "the tumble and hop functions"
+    ),
+    (
+        enable_subplan_sharing,
+        "sharing of subplans across dataflows"
+    ),
     (enable_with_mutually_recursive, "WITH MUTUALLY RECURSIVE"),
     (
         enable_within_timestamp_order_by_in_subscribe,
@@ -1301,6 +1367,10 @@ feature_flags!(
         enable_cardinality_estimates,
         "join planning with cardinality estimates"
     ),
+    (
+        enable_native_outer_joins,
+        "native rendering of left and right outer joins"
+    ),
     (
         enable_connection_validation_syntax,
         "CREATE CONNECTION .. WITH (VALIDATE) and VALIDATE CONNECTION syntax"
@@ -1954,6 +2024,7 @@ impl SystemVars {
             .with_var(&PERSIST_PUBSUB_CLIENT_ENABLED)
             .with_var(&PERSIST_PUBSUB_PUSH_DIFF_ENABLED)
             .with_var(&PERSIST_ROLLUP_THRESHOLD)
+            .with_var(&PERSIST_PART_COMPRESSION)
             .with_var(&METRICS_RETENTION)
             .with_var(&UNSAFE_MOCK_AUDIT_EVENT_TIMESTAMP)
             .with_var(&ENABLE_LD_RBAC_CHECKS)
@@ -1968,8 +2039,11 @@ impl SystemVars {
             .with_var(&KEEP_N_SOURCE_STATUS_HISTORY_ENTRIES)
             .with_var(&KEEP_N_SINK_STATUS_HISTORY_ENTRIES)
             .with_var(&ENABLE_MZ_JOIN_CORE)
+            .with_var(&ENABLE_COMPUTE_ARRANGEMENT_SPILLING)
             .with_var(&ENABLE_STORAGE_SHARD_FINALIZATION)
             .with_var(&ENABLE_CONSOLIDATE_AFTER_UNION_NEGATE)
+            .with_var(&ORDERED_TOP_K_THRESHOLD)
+            .with_var(&CLUSTER_ALTER_HYDRATION_TIMEOUT)
             .with_var(&ENABLE_DEFAULT_CONNECTION_VALIDATION)
             .with_var(&LOGGING_FILTER)
             .with_var(&OPENTELEMETRY_FILTER)
@@ -2471,6 +2545,11 @@ impl SystemVars {
         *self.expect_value(&PERSIST_ROLLUP_THRESHOLD)
     }
 
+    /// Returns the `persist_part_compression` configuration parameter.
+    pub fn persist_part_compression(&self) -> PartCompression {
+        *self.expect_value(&PERSIST_PART_COMPRESSION)
+    }
+
     /// Returns the `metrics_retention` configuration parameter.
     pub fn metrics_retention(&self) -> Duration {
         *self.expect_value(&METRICS_RETENTION)
@@ -2509,6 +2588,11 @@ impl SystemVars {
         *self.expect_value(&ENABLE_MZ_JOIN_CORE)
     }
 
+    /// Returns the `enable_compute_arrangement_spilling` configuration parameter.
+    pub fn enable_compute_arrangement_spilling(&self) -> bool {
+        *self.expect_value(&ENABLE_COMPUTE_ARRANGEMENT_SPILLING)
+    }
+
     /// Returns the `enable_storage_shard_finalization` configuration parameter.
     pub fn enable_storage_shard_finalization(&self) -> bool {
         *self.expect_value(&ENABLE_STORAGE_SHARD_FINALIZATION)
@@ -2518,6 +2602,16 @@ impl SystemVars {
         *self.expect_value(&ENABLE_CONSOLIDATE_AFTER_UNION_NEGATE)
     }
 
+    /// Returns the `ordered_top_k_threshold` configuration parameter.
+    pub fn ordered_top_k_threshold(&self) -> usize {
+        *self.expect_value(&ORDERED_TOP_K_THRESHOLD)
+    }
+
+    /// Returns the `cluster_alter_hydration_timeout` configuration parameter.
+    pub fn cluster_alter_hydration_timeout(&self) -> Duration {
+        *self.expect_value(&CLUSTER_ALTER_HYDRATION_TIMEOUT)
+    }
+
     /// Returns the `enable_default_connection_validation` configuration parameter.
     pub fn enable_default_connection_validation(&self) -> bool {
         *self.expect_value(&ENABLE_DEFAULT_CONNECTION_VALIDATION)
@@ -3888,6 +3982,28 @@ impl Value for CloneableEnvFilter {
     }
 }
 
+impl Value for PartCompression {
+    fn type_name() -> String {
+        "persist_part_compression".to_string()
+    }
+
+    fn parse<'a>(
+        param: &'a (dyn Var + Send + Sync),
+        input: VarInput,
+    ) -> Result<Self::Owned, VarError> {
+        let s = extract_single_value(param, input)?;
+        PartCompression::from_str(s).map_err(|_| VarError::ConstrainedParameter {
+            parameter: param.into(),
+            values: vec![s.to_string()],
+            valid_values: Some(vec!["none", "zstd", "lz4"]),
+        })
+    }
+
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
 pub fn is_tracing_var(name: &str) -> bool {
     name == LOGGING_FILTER.name() || name == OPENTELEMETRY_FILTER.name()
 }
@@ -3897,6 +4013,7 @@ pub fn is_compute_config_var(name: &str) -> bool {
     name == MAX_RESULT_SIZE.name()
         || name == DATAFLOW_MAX_INFLIGHT_BYTES.name()
         || name == ENABLE_MZ_JOIN_CORE.name()
+        || name == ENABLE_COMPUTE_ARRANGEMENT_SPILLING.name()
         || is_persist_config_var(name)
         || is_tracing_var(name)
 }
@@ -3953,6 +4070,7 @@ fn is_persist_config_var(name: &str) -> bool {
         || name == PERSIST_STATS_FILTER_ENABLED.name()
         || name == PERSIST_PUBSUB_CLIENT_ENABLED.name()
         || name == PERSIST_PUBSUB_PUSH_DIFF_ENABLED.name()
+        || name == PERSIST_PART_COMPRESSION.name()
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
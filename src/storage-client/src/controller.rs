@@ -30,6 +30,7 @@ use async_trait::async_trait;
 use bytes::BufMut;
 use derivative::Derivative;
 use differential_dataflow::lattice::Lattice;
+use futures::stream::BoxStream;
 use itertools::Itertools;
 use mz_build_info::BuildInfo;
 use mz_cluster_client::client::ClusterReplicaLocation;
@@ -121,6 +122,9 @@ pub enum IntrospectionType {
     /// automatically bump the write frontier from time to time.
     SinkStatusHistory,
     SourceStatusHistory,
+    /// We're not responsible for appending to this collection automatically, but we compact it
+    /// when it is registered.
+    MaterializedViewQuarantinedErrorHistory,
     ShardMapping,
     Frontiers,
 
@@ -428,6 +432,20 @@ pub trait StorageController: Debug + Send {
         as_of: Self::Timestamp,
     ) -> Result<Vec<(Row, Diff)>, StorageError>;
 
+    /// Returns a stream of the updates in the snapshot of the contents of the local input named
+    /// `id` at `as_of`.
+    ///
+    /// Unlike [`StorageController::snapshot`], the stream does not borrow the controller and
+    /// reads the snapshot one part at a time, so that large snapshots can be processed without
+    /// holding them in memory or blocking the controller's owner. The updates are not
+    /// consolidated. The caller must hold back the collection's since to `as_of` until the
+    /// stream is exhausted.
+    fn snapshot_stream(
+        &self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+    ) -> Result<BoxStream<'static, Result<(Row, Diff), StorageError>>, StorageError>;
+
     /// Returns aggregate statistics about the contents of the local input named
     /// `id` at `as_of`.
     async fn snapshot_stats(
@@ -526,6 +544,20 @@ pub trait StorageController: Debug + Send {
         &mut self,
         external_frontiers: BTreeMap<(GlobalId, ReplicaId), Antichain<Self::Timestamp>>,
     );
+
+    /// Records the evaluation errors that materialized views quarantined instead of writing them
+    /// to their collections.
+    ///
+    /// `errors` contains tuples of materialized view ID, time, error, input key, and the change
+    /// in multiplicity of the error at that time.
+    async fn record_quarantined_errors(
+        &mut self,
+        errors: Vec<(GlobalId, Self::Timestamp, String, Row, Diff)>,
+    );
+
+    /// Returns the upper of the collection that records the errors quarantined by materialized
+    /// views.
+    fn quarantined_error_history_upper(&self) -> &Antichain<Self::Timestamp>;
 }
 
 /// Compaction policies for collections maintained by `Controller`.
@@ -1581,6 +1613,9 @@ where
                             )
                             .await;
                         }
+                        IntrospectionType::MaterializedViewQuarantinedErrorHistory => {
+                            self.consolidate_quarantined_error_history().await;
+                        }
                     }
                 }
                 DataSource::Webhook => {
@@ -1942,29 +1977,7 @@ where
     ) -> Result<Vec<(Row, Diff)>, StorageError> {
         let as_of = Antichain::from_elem(as_of);
         let metadata = &self.collection(id)?.collection_metadata;
-
-        let persist_client = self
-            .persist
-            .open(metadata.persist_location.clone())
-            .await
-            .unwrap();
-
-        // We create a new read handle every time someone requests a snapshot and then immediately
-        // expire it instead of keeping a read handle permanently in our state to avoid having it
-        // heartbeat continously. The assumption is that calls to snapshot are rare and therefore
-        // worth it to always create a new handle.
-        let mut read_handle = persist_client
-            .open_leased_reader::<SourceData, (), _, _>(
-                metadata.data_shard,
-                Arc::new(metadata.relation_desc.clone()),
-                Arc::new(UnitSchema),
-                Diagnostics {
-                    shard_name: id.to_string(),
-                    handle_purpose: format!("snapshot {}", id),
-                },
-            )
-            .await
-            .expect("invalid persist usage");
+        let mut read_handle = open_snapshot_handle(&self.persist, id, metadata).await;
 
         match read_handle.snapshot_and_fetch(as_of).await {
             Ok(contents) => {
@@ -1981,6 +1994,30 @@ where
         }
     }
 
+    fn snapshot_stream(
+        &self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+    ) -> Result<BoxStream<'static, Result<(Row, Diff), StorageError>>, StorageError> {
+        use futures::stream::StreamExt;
+
+        let metadata = self.collection(id)?.collection_metadata.clone();
+        let persist = Arc::clone(&self.persist);
+        Ok(Box::pin(async_stream::try_stream! {
+            let as_of = Antichain::from_elem(as_of);
+            let mut read_handle = open_snapshot_handle(&persist, id, &metadata).await;
+            let contents = read_handle
+                .snapshot_and_stream(as_of)
+                .await
+                .map_err(|_| StorageError::ReadBeforeSince(id))?;
+            let mut contents = std::pin::pin!(contents);
+            while let Some(((data, _), _, diff)) = contents.next().await {
+                let row = data.expect("invalid protobuf data").0?;
+                yield (row, diff);
+            }
+        }))
+    }
+
     async fn snapshot_stats(
         &self,
         id: GlobalId,
@@ -2513,6 +2550,38 @@ where
         )
         .await;
     }
+
+    async fn record_quarantined_errors(
+        &mut self,
+        errors: Vec<(GlobalId, Self::Timestamp, String, Row, Diff)>,
+    ) {
+        if errors.is_empty() {
+            return;
+        }
+
+        let updates = errors
+            .into_iter()
+            .map(|(id, time, error, input_key, diff)| {
+                let row = healthcheck::pack_quarantined_error_row(
+                    id,
+                    time.into(),
+                    &error,
+                    &input_key,
+                    diff,
+                );
+                (row, 1)
+            })
+            .collect();
+        let id = self.state.introspection_ids
+            [&IntrospectionType::MaterializedViewQuarantinedErrorHistory];
+        self.append_to_managed_collection(id, updates).await;
+    }
+
+    fn quarantined_error_history_upper(&self) -> &Antichain<Self::Timestamp> {
+        let id = self.state.introspection_ids
+            [&IntrospectionType::MaterializedViewQuarantinedErrorHistory];
+        &self.state.collections[&id].write_frontier
+    }
 }
 
 /// A wrapper struct that presents the adapter token to a format that is understandable by persist
@@ -2939,6 +3008,63 @@ where
         self.append_to_managed_collection(id, updates).await;
     }
 
+    /// Compacts the quarantined error history, so that it contains at most one entry for each
+    /// quarantined error that has not been retracted.
+    ///
+    /// The entries of an error are replaced by a single entry at the time of its latest change
+    /// that carries the error's total count, and are removed altogether if the count is zero.
+    async fn consolidate_quarantined_error_history(&mut self) {
+        let id = self.state.introspection_ids
+            [&IntrospectionType::MaterializedViewQuarantinedErrorHistory];
+
+        let rows = match self.state.collections[&id]
+            .write_frontier
+            .elements()
+            .iter()
+            .min()
+        {
+            Some(f) if f > &T::minimum() => {
+                let as_of = f.step_back().unwrap();
+
+                self.snapshot(id, as_of).await.expect("snapshot succeeds")
+            }
+            // If collection is closed or the frontier is the minimum, we cannot
+            // or don't need to consolidate (respectively).
+            _ => return,
+        };
+
+        // BTreeMap<(MaterializedViewId, Error, InputKey), (LatestOccurredAt, Count, Rows)>
+        let mut entries: BTreeMap<Vec<Datum>, (Datum, i64, Vec<(Row, Diff)>)> = BTreeMap::new();
+        for (row, diff) in rows.iter() {
+            let datums = row.unpack();
+            let (occurred_at, key, count) = (datums[0], datums[1..4].to_vec(), datums[4]);
+            let entry = entries
+                .entry(key)
+                .or_insert_with(|| (occurred_at, 0, Vec::new()));
+            entry.0 = std::cmp::max(entry.0, occurred_at);
+            entry.1 += count.unwrap_int64() * diff;
+            entry.2.push((row.clone(), *diff));
+        }
+
+        let mut updates = vec![];
+        for (key, (occurred_at, count, rows)) in entries {
+            if count != 0 && rows.len() == 1 {
+                continue;
+            }
+            updates.extend(rows.into_iter().map(|(row, diff)| (row, -diff)));
+            if count != 0 {
+                let mut row = Row::default();
+                let mut packer = row.packer();
+                packer.push(occurred_at);
+                packer.extend(key);
+                packer.push(Datum::Int64(count));
+                updates.push((row, 1));
+            }
+        }
+
+        self.append_to_managed_collection(id, updates).await;
+    }
+
     /// Appends a new global ID, shard ID pair to the appropriate collection.
     /// Use a `diff` of 1 to append a new entry; -1 to retract an existing
     /// entry.
@@ -3421,6 +3547,38 @@ where
     }
 }
 
+/// Opens a read handle on the collection `id`, described by `metadata`, to read a snapshot.
+async fn open_snapshot_handle<T>(
+    persist: &PersistClientCache,
+    id: GlobalId,
+    metadata: &CollectionMetadata,
+) -> ReadHandle<SourceData, (), T, Diff>
+where
+    T: Timestamp + Lattice + Codec64,
+{
+    let persist_client = persist
+        .open(metadata.persist_location.clone())
+        .await
+        .unwrap();
+
+    // We create a new read handle every time someone requests a snapshot and then immediately
+    // expire it instead of keeping a read handle permanently in our state to avoid having it
+    // heartbeat continously. The assumption is that calls to snapshot are rare and therefore
+    // worth it to always create a new handle.
+    persist_client
+        .open_leased_reader::<SourceData, (), _, _>(
+            metadata.data_shard,
+            Arc::new(metadata.relation_desc.clone()),
+            Arc::new(UnitSchema),
+            Diagnostics {
+                shard_name: id.to_string(),
+                handle_purpose: format!("snapshot {}", id),
+            },
+        )
+        .await
+        .expect("invalid persist usage")
+}
+
 /// State maintained about individual collections.
 #[derive(Debug)]
 pub struct CollectionState<T> {
@@ -318,6 +318,14 @@ pub struct MonotonicAppender {
 
 impl MonotonicAppender {
     pub async fn append(self, updates: Vec<(Row, Diff)>) -> Result<(), StorageError> {
+        // Retractions would violate the monotonicity that readers of the collection rely on.
+        if updates.iter().any(|(_, diff)| *diff < 0) {
+            return Err(StorageError::InvalidUsage(format!(
+                "cannot retract updates from monotonic collection {}",
+                self.id
+            )));
+        }
+
         let (tx, rx) = oneshot::channel();
 
         // Make sure there is space available on the channel.
@@ -69,6 +69,36 @@ pub static MZ_SOURCE_STATUS_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
         .with_column("details", ScalarType::Jsonb.nullable(true))
 });
 
+pub static MZ_MATERIALIZED_VIEW_QUARANTINED_ERROR_HISTORY_DESC: Lazy<RelationDesc> =
+    Lazy::new(|| {
+        RelationDesc::empty()
+            .with_column("occurred_at", ScalarType::MzTimestamp.nullable(false))
+            .with_column("materialized_view_id", ScalarType::String.nullable(false))
+            .with_column("error", ScalarType::String.nullable(false))
+            .with_column("input_key", ScalarType::String.nullable(false))
+            .with_column("count", ScalarType::Int64.nullable(false))
+    });
+
+/// Packs a row of the quarantined error history, recording that the multiplicity of `error`,
+/// caused by the input row with key `input_key`, changed by `count` at `ts`.
+pub fn pack_quarantined_error_row(
+    materialized_view_id: GlobalId,
+    ts: mz_repr::Timestamp,
+    error: &str,
+    input_key: &Row,
+    count: i64,
+) -> Row {
+    let materialized_view_id = materialized_view_id.to_string();
+    let input_key = input_key.to_string();
+    Row::pack_slice(&[
+        Datum::MzTimestamp(ts),
+        Datum::String(&materialized_view_id),
+        Datum::String(error),
+        Datum::String(&input_key),
+        Datum::Int64(count),
+    ])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -10,6 +10,7 @@
 //! Definition and helper structs for the [`Cardinality`] attribute.
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
 
 use mz_expr::{
     BinaryFunc, Id, JoinImplementation, MirRelationExpr, MirScalarExpr, TableFunc, UnaryFunc,
@@ -24,6 +25,7 @@ use ordered_float::OrderedFloat;
 use crate::attribute::subtree_size::SubtreeSize;
 use crate::attribute::unique_keys::UniqueKeys;
 use crate::attribute::{Attribute, DerivedAttributes, Env, RequiredAttributes};
+use crate::statistics::CollectionStatistics;
 use crate::symbolic::SymbolicExpression;
 
 use super::Arity;
@@ -38,6 +40,8 @@ pub struct Cardinality {
     pub results: Vec<SymExp>,
     /// A factorizer for generating appropriating scaling factors
     pub factorize: Box<dyn Factorizer + Send + Sync>,
+    /// Statistics about the contents of global collections, as collected by `ANALYZE`
+    pub statistics: BTreeMap<GlobalId, Arc<CollectionStatistics>>,
 }
 
 impl Default for Cardinality {
@@ -48,6 +52,31 @@ impl Default for Cardinality {
             factorize: Box::new(WorstCaseFactorizer {
                 cardinalities: BTreeMap::new(),
             }),
+            statistics: BTreeMap::new(),
+        }
+    }
+}
+
+impl Cardinality {
+    /// Creates a [`Cardinality`] attribute that uses `statistics` to estimate the selectivity of
+    /// predicates on and the number of groups in the collections they describe
+    pub fn with_statistics(statistics: BTreeMap<GlobalId, Arc<CollectionStatistics>>) -> Self {
+        Cardinality {
+            statistics,
+            ..Cardinality::default()
+        }
+    }
+
+    /// The statistics of `expr`, if it reads a global collection that has statistics
+    fn statistics_of<'a>(
+        statistics: &'a BTreeMap<GlobalId, Arc<CollectionStatistics>>,
+        expr: &MirRelationExpr,
+    ) -> Option<&'a CollectionStatistics> {
+        match expr {
+            MirRelationExpr::Get {
+                id: Id::Global(id), ..
+            } => statistics.get(id).map(|statistics| &**statistics),
+            _ => None,
         }
     }
 }
@@ -77,10 +106,13 @@ pub trait Factorizer {
     /// The result should be in the range (0, 1.0]
     fn predicate(&self, expr: &MirScalarExpr, unique_columns: &BTreeSet<usize>) -> SymExp;
     /// Computes selectivity for a filter
+    ///
+    /// `statistics` describe the contents of the input, if known
     fn filter(
         &self,
         predicates: &Vec<MirScalarExpr>,
         keys: &Vec<Vec<usize>>,
+        statistics: Option<&CollectionStatistics>,
         input: &SymExp,
     ) -> SymExp;
     /// Computes selectivity for a join; the cardinality estimate for each input is paired with the keys on that input
@@ -94,10 +126,13 @@ pub trait Factorizer {
         inputs: Vec<&SymExp>,
     ) -> SymExp;
     /// Computes selectivity for a reduce
+    ///
+    /// `statistics` describe the contents of the input, if known
     fn reduce(
         &self,
         group_key: &Vec<MirScalarExpr>,
         expected_group_size: &Option<u64>,
+        statistics: Option<&CollectionStatistics>,
         input: &SymExp,
     ) -> SymExp;
     /// Computes selectivity for a topk
@@ -238,6 +273,7 @@ impl Factorizer for WorstCaseFactorizer {
         &self,
         predicates: &Vec<MirScalarExpr>,
         keys: &Vec<Vec<usize>>,
+        statistics: Option<&CollectionStatistics>,
         input: &SymExp,
     ) -> SymExp {
         // TODO(mgree): should we try to do something for indices built on multiple columns?
@@ -252,7 +288,11 @@ impl Factorizer for WorstCaseFactorizer {
         let mut factor = SymExp::from(1.0);
 
         for expr in predicates {
-            let predicate_scaling_factor = self.predicate(expr, &unique_columns);
+            // prefer estimates from statistics, when they inform the predicate
+            let predicate_scaling_factor = match statistics.and_then(|s| s.selectivity(expr)) {
+                Some(selectivity) => SymExp::from(selectivity),
+                None => self.predicate(expr, &unique_columns),
+            };
 
             // constant scaling factors should be in (0,1]
             debug_assert!(match predicate_scaling_factor {
@@ -330,11 +370,27 @@ impl Factorizer for WorstCaseFactorizer {
         &self,
         group_key: &Vec<MirScalarExpr>,
         expected_group_size: &Option<u64>,
+        statistics: Option<&CollectionStatistics>,
         input: &SymExp,
     ) -> SymExp {
         // TODO(mgree): if no `group_key` is present, we can do way better
 
-        if let Some(group_size) = expected_group_size {
+        // with statistics, we can bound the number of groups by the number of distinct keys
+        let key_columns = group_key
+            .iter()
+            .map(|expr| match expr {
+                MirScalarExpr::Column(col) => Some(*col),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        let distinct_keys = statistics
+            .zip(key_columns)
+            .filter(|(_, key_columns)| !key_columns.is_empty())
+            .and_then(|(statistics, key_columns)| statistics.distinct_count(&key_columns));
+
+        if let Some(distinct_keys) = distinct_keys {
+            SymExp::min(input.clone(), SymExp::from(distinct_keys.max(1.0)))
+        } else if let Some(group_size) = expected_group_size {
             input / f64::cast_lossy(*group_size)
         } else if group_key.is_empty() {
             SymExp::from(1)
@@ -418,11 +474,12 @@ impl Attribute for Cardinality {
                 let input = &self.results[n - 1];
                 self.results.push(self.factorize.flat_map(func, input));
             }
-            Filter { predicates, .. } => {
+            Filter { input, predicates } => {
+                let statistics = Self::statistics_of(&self.statistics, input);
                 let input = &self.results[n - 1];
                 let keys = &deps.get_results::<UniqueKeys>()[n - 1];
                 self.results
-                    .push(self.factorize.filter(predicates, keys, input));
+                    .push(self.factorize.filter(predicates, keys, statistics, input));
             }
             Join {
                 equivalences,
@@ -461,13 +518,19 @@ impl Attribute for Cardinality {
                 ));
             }
             Reduce {
+                input,
                 group_key,
                 expected_group_size,
                 ..
             } => {
+                let statistics = Self::statistics_of(&self.statistics, input);
                 let input = &self.results[n - 1];
-                self.results
-                    .push(self.factorize.reduce(group_key, expected_group_size, input));
+                self.results.push(self.factorize.reduce(
+                    group_key,
+                    expected_group_size,
+                    statistics,
+                    input,
+                ));
             }
             TopK {
                 group_key,
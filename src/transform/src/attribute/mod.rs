@@ -241,6 +241,15 @@ impl RequiredAttributes {
         }
     }
 
+    /// Add an attribute that [DerivedAttributes] should derive, starting from the
+    /// given (e.g., preconfigured) instance instead of the default one
+    pub fn require_with<A: Attribute>(&mut self, attribute: A) {
+        if !self.attributes.contains_key::<AsKey<A>>() {
+            A::add_dependencies(self);
+        }
+        self.attributes.insert::<AsKey<A>>(attribute);
+    }
+
     /// Consume `self`, producing an [DerivedAttributes]
     pub fn finish(self) -> DerivedAttributes {
         DerivedAttributes {
@@ -15,7 +15,8 @@ use itertools::{zip_eq, Itertools};
 use mz_expr::visit::Visit;
 use mz_expr::JoinImplementation::IndexedFilter;
 use mz_expr::{
-    func, EvalError, LetRecLimit, MirRelationExpr, MirScalarExpr, UnaryFunc, RECURSION_LIMIT,
+    func, EvalError, JoinKind, LetRecLimit, MirRelationExpr, MirScalarExpr, UnaryFunc,
+    RECURSION_LIMIT,
 };
 use mz_ore::cast::CastFrom;
 use mz_ore::soft_panic_or_log;
@@ -312,11 +313,20 @@ impl ColumnKnowledge {
                     inputs,
                     equivalences,
                     implementation,
-                    ..
+                    kind,
                 } => {
                     // Aggregate column knowledge from each input into one `Vec`.
                     let mut knowledges = Vec::new();
-                    for input in inputs.iter_mut() {
+                    for (index, input) in inputs.iter_mut().enumerate() {
+                        if index > 0 && *kind == JoinKind::LeftOuter {
+                            // The columns of all but the first input of an outer join may be
+                            // null, and are not constrained by the equivalences.
+                            self.harvest(input, knowledge, knowledge_stack)?;
+                            knowledges.extend(
+                                std::iter::repeat(DatumKnowledge::any(true)).take(input.arity()),
+                            );
+                            continue;
+                        }
                         for mut knowledge in self.harvest(input, knowledge, knowledge_stack)? {
                             // Do not propagate error literals beyond join inputs, since that may result
                             // in them being propagated to other inputs of the join and evaluated when
@@ -338,6 +348,10 @@ impl ColumnKnowledge {
                             typ
                         });
 
+                    if *kind == JoinKind::LeftOuter {
+                        return Ok(knowledges);
+                    }
+
                     for equivalence in equivalences.iter_mut() {
                         let mut knowledge = DatumKnowledge::top();
 
@@ -430,7 +444,9 @@ impl ColumnKnowledge {
                                 // These methods propagate constant values exactly.
                                 knowledge
                             }
-                            AggregateFunc::Count => DatumKnowledge::any(false),
+                            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct => {
+                                DatumKnowledge::any(false)
+                            }
                             _ => {
                                 // The remaining aggregates are non-null if
                                 // their inputs are non-null. This is correct
@@ -16,9 +16,10 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 
-use mz_compute_client::types::dataflows::DataflowDesc;
+use mz_compute_client::types::dataflows::{DataflowDesc, IndexDesc};
 use mz_expr::visit::Visit;
-use mz_expr::{CollectionPlan, Id, LocalId, MapFilterProject, MirRelationExpr};
+use mz_expr::{CollectionPlan, Id, LocalId, MapFilterProject, MirRelationExpr, MirScalarExpr};
+use mz_repr::GlobalId;
 use tracing::warn;
 
 use crate::monotonic::MonotonicFlag;
@@ -433,6 +434,159 @@ pub fn optimize_dataflow_monotonic(dataflow: &mut DataflowDesc) -> Result<(), Tr
     Ok(())
 }
 
+/// A view or materialized view whose contents can be read by dataflows instead of recomputing a
+/// structurally identical subplan.
+#[derive(Clone, Debug)]
+pub struct SharedSubplan {
+    /// The ID of the view or materialized view.
+    pub id: GlobalId,
+    /// The optimized expression that defines the view.
+    pub plan: MirRelationExpr,
+    /// How the contents of the view are read.
+    pub import: SharedSubplanImport,
+    /// Whether the view is monotonic.
+    pub monotonic: bool,
+}
+
+/// How dataflows read the contents of a [`SharedSubplan`].
+#[derive(Clone, Debug)]
+pub enum SharedSubplanImport {
+    /// The contents are read from the index with the given ID and key.
+    Index {
+        /// The ID of the index.
+        id: GlobalId,
+        /// The key of the index.
+        key: Vec<MirScalarExpr>,
+    },
+    /// The contents are read from the storage collection of a materialized view.
+    Persist,
+}
+
+impl SharedSubplan {
+    /// Returns the ID of the collection that dataflows sharing this subplan read.
+    pub fn import_id(&self) -> GlobalId {
+        match &self.import {
+            SharedSubplanImport::Index { id, .. } => *id,
+            SharedSubplanImport::Persist => self.id,
+        }
+    }
+}
+
+/// Returns whether `expr` is worth sharing across dataflows.
+///
+/// Only joins and reductions are considered, as other operators are cheap to recompute. The
+/// expression must not reference local bindings, so that it is meaningful outside of its context.
+pub fn is_shareable_subplan(expr: &MirRelationExpr) -> bool {
+    if !matches!(
+        expr,
+        MirRelationExpr::Join { .. }
+            | MirRelationExpr::Reduce { .. }
+            | MirRelationExpr::TopK { .. }
+    ) {
+        return false;
+    }
+    let mut closed = true;
+    expr.visit_pre(|e| {
+        if let MirRelationExpr::Get {
+            id: Id::Local(_), ..
+        } = e
+        {
+            closed = false;
+        }
+    });
+    closed
+}
+
+/// Returns the subplans of the objects built by `dataflow` that are worth sharing with other
+/// dataflows.
+pub fn shareable_subplans(dataflow: &DataflowDesc) -> BTreeSet<MirRelationExpr> {
+    let mut subplans = BTreeSet::new();
+    for build_desc in &dataflow.objects_to_build {
+        build_desc.plan.as_inner().visit_pre(|e| {
+            if is_shareable_subplan(e) {
+                subplans.insert(e.clone());
+            }
+        });
+    }
+    subplans
+}
+
+/// Replaces subplans of the objects built by `dataflow` that are structurally identical to the
+/// plan of one of the views in `subplans` by reads of that view, and imports the collection that
+/// holds the contents of the view.
+///
+/// If the same plan is shared by more than one view, an index is preferred over the storage
+/// collection of a materialized view, as the latter needs to be arranged again. Otherwise, the
+/// first view in `subplans` is read. Returns the IDs of
+/// the imported collections, see [`SharedSubplan::import_id`].
+#[tracing::instrument(
+    target = "optimizer",
+    level = "debug",
+    skip_all,
+    fields(path.segment ="share_subplans")
+)]
+pub fn share_subplans(
+    dataflow: &mut DataflowDesc,
+    subplans: &[SharedSubplan],
+) -> Result<BTreeSet<GlobalId>, TransformError> {
+    // Views that the dataflow builds or exports itself cannot be read in its place.
+    let built: BTreeSet<_> = dataflow
+        .objects_to_build
+        .iter()
+        .map(|bd| bd.id)
+        .chain(dataflow.export_ids())
+        .collect();
+    let mut by_plan: BTreeMap<_, &SharedSubplan> = BTreeMap::new();
+    for subplan in subplans {
+        if built.contains(&subplan.id) || !is_shareable_subplan(&subplan.plan) {
+            continue;
+        }
+        let current = by_plan.entry(&subplan.plan).or_insert(subplan);
+        if matches!(current.import, SharedSubplanImport::Persist)
+            && matches!(subplan.import, SharedSubplanImport::Index { .. })
+        {
+            *current = subplan;
+        }
+    }
+    if by_plan.is_empty() {
+        return Ok(BTreeSet::new());
+    }
+
+    let mut shared = BTreeSet::new();
+    for build_desc in dataflow.objects_to_build.iter_mut() {
+        build_desc.plan.as_inner_mut().visit_mut_pre(&mut |e| {
+            if let Some(subplan) = by_plan.get(&*e) {
+                *e = MirRelationExpr::global_get(subplan.id, e.typ());
+                shared.insert(subplan.id);
+            }
+        })?;
+    }
+
+    let mut imported = BTreeSet::new();
+    for subplan in by_plan.values() {
+        if !shared.contains(&subplan.id) {
+            continue;
+        }
+        match &subplan.import {
+            SharedSubplanImport::Index { id, key } => {
+                let index_desc = IndexDesc {
+                    on_id: subplan.id,
+                    key: key.clone(),
+                };
+                dataflow.import_index(*id, index_desc, subplan.plan.typ(), subplan.monotonic);
+            }
+            SharedSubplanImport::Persist => {
+                dataflow.import_source(subplan.id, subplan.plan.typ(), subplan.monotonic);
+            }
+        }
+        imported.insert(subplan.import_id());
+    }
+
+    mz_repr::explain::trace_plan(dataflow);
+
+    Ok(imported)
+}
+
 /// Restricts the indexes imported by `dataflow` to only the ones it needs.
 ///
 /// The input `dataflow` should import all indexes belonging to all views it
@@ -507,3 +661,84 @@ fn optimize_dataflow_index_imports(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_expr::OptimizedMirRelationExpr;
+    use mz_repr::{ColumnType, RelationType, ScalarType};
+
+    use super::*;
+
+    fn get(id: u64) -> MirRelationExpr {
+        let typ = RelationType::new(vec![
+            ColumnType {
+                scalar_type: ScalarType::Int64,
+                nullable: false,
+            };
+            2
+        ]);
+        MirRelationExpr::global_get(GlobalId::User(id), typ)
+    }
+
+    fn join() -> MirRelationExpr {
+        MirRelationExpr::join(vec![get(1), get(2)], vec![vec![(0, 0), (1, 0)]])
+    }
+
+    #[mz_ore::test]
+    fn test_share_subplans() {
+        let mut dataflow = DataflowDesc::new("test".into());
+        dataflow.insert_plan(
+            GlobalId::User(10),
+            OptimizedMirRelationExpr::declare_optimized(join().project(vec![0, 3])),
+        );
+        assert_eq!(shareable_subplans(&dataflow), BTreeSet::from([join()]));
+
+        let materialized = SharedSubplan {
+            id: GlobalId::User(5),
+            plan: join(),
+            import: SharedSubplanImport::Persist,
+            monotonic: false,
+        };
+        let indexed = SharedSubplan {
+            id: GlobalId::User(3),
+            plan: join(),
+            import: SharedSubplanImport::Index {
+                id: GlobalId::User(4),
+                key: vec![MirScalarExpr::column(0)],
+            },
+            monotonic: false,
+        };
+
+        // An index is preferred over the storage collection of a materialized view.
+        let mut indexed_dataflow = dataflow.clone();
+        let subplans = [materialized.clone(), indexed.clone()];
+        let imported = share_subplans(&mut indexed_dataflow, &subplans).unwrap();
+        assert_eq!(imported, BTreeSet::from([GlobalId::User(4)]));
+        assert_eq!(
+            indexed_dataflow.objects_to_build[0].plan.as_inner(),
+            &MirRelationExpr::global_get(GlobalId::User(3), join().typ()).project(vec![0, 3]),
+        );
+        assert!(indexed_dataflow
+            .index_imports
+            .contains_key(&GlobalId::User(4)));
+        assert!(indexed_dataflow.source_imports.is_empty());
+
+        let imported = share_subplans(&mut dataflow, &[materialized]).unwrap();
+        assert_eq!(imported, BTreeSet::from([GlobalId::User(5)]));
+        assert_eq!(
+            dataflow.objects_to_build[0].plan.as_inner(),
+            &MirRelationExpr::global_get(GlobalId::User(5), join().typ()).project(vec![0, 3]),
+        );
+        assert!(dataflow.source_imports.contains_key(&GlobalId::User(5)));
+
+        // A view is not read by a dataflow that builds it.
+        let mut dataflow = DataflowDesc::new("test".into());
+        dataflow.insert_plan(
+            GlobalId::User(3),
+            OptimizedMirRelationExpr::declare_optimized(join()),
+        );
+        let imported = share_subplans(&mut dataflow, &[indexed]).unwrap();
+        assert!(imported.is_empty());
+        assert!(dataflow.index_imports.is_empty());
+    }
+}
@@ -227,6 +227,7 @@ impl Demand {
                     inputs,
                     equivalences,
                     implementation: _,
+                    kind,
                 } => {
                     let input_mapper = JoinInputMapper::new(inputs);
 
@@ -236,7 +237,10 @@ impl Demand {
                     // that some other column would have been more helpful, but we don't have a great
                     // reason to do that at the moment.
                     let mut permutation: Vec<usize> = (0..input_mapper.total_columns()).collect();
-                    for equivalence in equivalences.iter() {
+                    // The columns of an outer join are only equal when the optional inputs match,
+                    // so they cannot be remapped for an outer join.
+                    let remappable = kind.is_inner();
+                    for equivalence in equivalences.iter().filter(|_| remappable) {
                         let mut first_column = None;
                         for expr in equivalence.iter() {
                             if let MirScalarExpr::Column(c) = expr {
@@ -15,7 +15,9 @@ use std::convert::TryInto;
 use std::iter;
 
 use mz_expr::visit::Visit;
-use mz_expr::{AggregateExpr, ColumnOrder, EvalError, MirRelationExpr, MirScalarExpr, TableFunc};
+use mz_expr::{
+    AggregateExpr, ColumnOrder, EvalError, JoinKind, MirRelationExpr, MirScalarExpr, TableFunc,
+};
 use mz_repr::{ColumnType, Datum, Diff, RelationType, Row, RowArena};
 
 use crate::{TransformArgs, TransformError};
@@ -288,6 +290,16 @@ impl FoldConstants {
                     };
                 }
             }
+            MirRelationExpr::Join {
+                inputs,
+                kind: JoinKind::LeftOuter,
+                ..
+            } => {
+                // Only an empty preserved input empties an outer join.
+                if inputs[0].is_empty() {
+                    relation.take_safely();
+                }
+            }
             MirRelationExpr::Join {
                 inputs,
                 equivalences,
@@ -20,9 +20,14 @@
 //! Unit collections have no columns and a count of one, and a join with such
 //! a collection act as the identity operator on collections. Once removed,
 //! we may find joins with zero or one input, which can be further simplified.
+//!
+//! Left outer joins are not fused with inner joins. A left outer join whose first
+//! input is itself a left outer join is fused with it if its equivalences only
+//! reference the first input of the nested join, as is the case for chains of
+//! `LEFT JOIN`s from a fact table to dimension tables.
 
 use mz_expr::visit::Visit;
-use mz_expr::{MirRelationExpr, MirScalarExpr};
+use mz_expr::{JoinKind, MirRelationExpr, MirScalarExpr};
 use mz_repr::RelationType;
 
 use crate::{TransformArgs, TransformError};
@@ -56,6 +61,12 @@ impl Join {
     /// Fuses multiple `Join` operators into one `Join` operator.
     pub fn action(relation: &mut MirRelationExpr) -> Result<(), TransformError> {
         if let MirRelationExpr::Join {
+            kind: JoinKind::LeftOuter,
+            ..
+        } = relation
+        {
+            Self::fuse_left_outer(relation);
+        } else if let MirRelationExpr::Join {
             inputs,
             equivalences,
             ..
@@ -70,6 +81,7 @@ impl Join {
                     MirRelationExpr::Join {
                         inputs,
                         equivalences,
+                        kind: JoinKind::Inner,
                         ..
                     } => {
                         // Merge the inputs into the new join being built.
@@ -79,6 +91,7 @@ impl Join {
                         if let MirRelationExpr::Join {
                             inputs,
                             equivalences,
+                            kind: JoinKind::Inner,
                             ..
                         } = *input
                         {
@@ -101,6 +114,54 @@ impl Join {
         }
         Ok(())
     }
+
+    /// Fuses a left outer join with a left outer join that is its first input.
+    ///
+    /// The columns of the fused join are those of the nested join followed by those of the
+    /// other inputs of the outer one, so column references remain unchanged. Fusion is only
+    /// correct if the equivalences of the outer join do not reference columns of the inputs
+    /// that the nested join may pad with nulls.
+    fn fuse_left_outer(relation: &mut MirRelationExpr) {
+        if let MirRelationExpr::Join {
+            inputs,
+            equivalences,
+            implementation,
+            kind: JoinKind::LeftOuter,
+        } = relation
+        {
+            let fusable = match &inputs[0] {
+                MirRelationExpr::Join {
+                    inputs: nested_inputs,
+                    kind: JoinKind::LeftOuter,
+                    ..
+                } => {
+                    let preserved_arity = nested_inputs[0].arity();
+                    let nested_arity = inputs[0].arity();
+                    equivalences.iter().flatten().all(|expr| {
+                        expr.support()
+                            .into_iter()
+                            .all(|c| c < preserved_arity || c >= nested_arity)
+                    })
+                }
+                _ => false,
+            };
+            if fusable {
+                let optional_inputs = inputs.drain(1..).collect::<Vec<_>>();
+                if let MirRelationExpr::Join {
+                    inputs: nested_inputs,
+                    equivalences: mut nested_equivalences,
+                    ..
+                } = inputs.pop().unwrap()
+                {
+                    nested_equivalences.append(equivalences);
+                    *inputs = nested_inputs;
+                    inputs.extend(optional_inputs);
+                    *equivalences = nested_equivalences;
+                    *implementation = mz_expr::JoinImplementation::Unimplemented;
+                }
+            }
+        }
+    }
 }
 
 /// Helper builder for fusing the inputs of nested joins into a single Join expression.
@@ -186,11 +247,7 @@ impl JoinBuilder {
                 // not re-write that code here.
                 self.inputs.pop().unwrap()
             }
-            _ => MirRelationExpr::Join {
-                inputs: self.inputs,
-                equivalences: self.equivalences,
-                implementation: mz_expr::JoinImplementation::Unimplemented,
-            },
+            _ => MirRelationExpr::join_scalars(self.inputs, self.equivalences),
         };
 
         if !self.predicates.is_empty() {
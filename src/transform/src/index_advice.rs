@@ -0,0 +1,291 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Recommendation of indexes from optimized plans.
+//!
+//! An optimized plan reveals where the absence of an index costs work:
+//!
+//!  * A `Filter` that equates expressions over a collection with literals, and that
+//!    `LiteralConstraints` could not turn into an index lookup, has to scan the whole collection.
+//!    An index keyed by the constrained expressions would turn the scan into a lookup.
+//!  * An `ArrangeBy` directly on a collection means that a join has to build an arrangement of
+//!    the collection that no existing index provides. An index with the same key would be reused
+//!    instead.
+//!
+//! The recommendations are hypothetical: they describe indexes the plan would use if they
+//! existed, without considering their maintenance cost.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use mz_compute_client::types::dataflows::DataflowDesc;
+use mz_expr::{Id, MirRelationExpr, MirScalarExpr, UnaryFunc};
+use mz_repr::GlobalId;
+
+use crate::IndexOracle;
+
+/// The reason an index is recommended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IndexAdviceReason {
+    /// The plan filters the collection by equating the key with literals.
+    Filter,
+    /// The plan arranges the collection by the key for a join.
+    Join,
+}
+
+impl IndexAdviceReason {
+    /// Returns the name of the reason, as used in system relations.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IndexAdviceReason::Filter => "filter",
+            IndexAdviceReason::Join => "join",
+        }
+    }
+}
+
+impl fmt::Display for IndexAdviceReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An index that does not exist, but that a plan would use if it did.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct IndexAdvice {
+    /// The collection to index.
+    pub on_id: GlobalId,
+    /// The key of the index, in terms of the columns of the collection.
+    pub key: Vec<MirScalarExpr>,
+    /// Why the index is recommended.
+    pub reason: IndexAdviceReason,
+}
+
+impl IndexAdvice {
+    /// Returns whether one of the existing `indexes` already serves the purpose of the
+    /// recommended index.
+    pub fn is_served_by(&self, indexes: &dyn IndexOracle) -> bool {
+        let mut index_keys = indexes.indexes_on(self.on_id);
+        match self.reason {
+            // An existing index is usable for the lookup if the filter constrains all of its
+            // key fields.
+            IndexAdviceReason::Filter => {
+                index_keys.any(|index_key| index_key.iter().all(|field| self.key.contains(field)))
+            }
+            IndexAdviceReason::Join => index_keys.any(|index_key| index_key == &self.key[..]),
+        }
+    }
+}
+
+/// Returns the indexes that the optimized `dataflow` would use if they existed.
+///
+/// Collections built by the dataflow itself are only recommended for indexing if they are
+/// not transient.
+pub fn dataflow_index_advice(
+    dataflow: &DataflowDesc,
+    indexes: &dyn IndexOracle,
+) -> BTreeSet<IndexAdvice> {
+    let mut advice = BTreeSet::new();
+    for build in &dataflow.objects_to_build {
+        advice.extend(index_advice(build.plan.as_inner(), indexes));
+    }
+    advice
+}
+
+/// Returns the indexes that the optimized `expr` would use if they existed.
+pub fn index_advice(expr: &MirRelationExpr, indexes: &dyn IndexOracle) -> BTreeSet<IndexAdvice> {
+    let mut advice = BTreeSet::new();
+    expr.visit_pre(|expr| match expr {
+        MirRelationExpr::Filter { input, predicates } => {
+            let Some(on_id) = indexable_get(input) else {
+                return;
+            };
+            let mut key: Vec<_> = predicates
+                .iter()
+                .filter_map(|p| p.any_expr_eq_literal())
+                .filter(|e| !e.is_literal())
+                .collect();
+            key.sort();
+            key.dedup();
+            if key.is_empty() {
+                return;
+            }
+            let filter_advice = IndexAdvice {
+                on_id,
+                key,
+                reason: IndexAdviceReason::Filter,
+            };
+            if !filter_advice.is_served_by(indexes) {
+                advice.insert(filter_advice);
+            }
+        }
+        MirRelationExpr::ArrangeBy { input, keys } => {
+            // Joins filter out `NULL` keys before arranging their inputs. An index would be used
+            // regardless, so look through such filters.
+            let input = match &**input {
+                MirRelationExpr::Filter { input, predicates }
+                    if predicates.iter().all(is_not_null_test) =>
+                {
+                    input
+                }
+                input => input,
+            };
+            let Some(on_id) = indexable_get(input) else {
+                return;
+            };
+            for key in keys {
+                let join_advice = IndexAdvice {
+                    on_id,
+                    key: key.clone(),
+                    reason: IndexAdviceReason::Join,
+                };
+                if !join_advice.is_served_by(indexes) {
+                    advice.insert(join_advice);
+                }
+            }
+        }
+        _ => {}
+    });
+    advice
+}
+
+/// Returns the ID of the collection read by `expr`, if `expr` reads a collection that can be
+/// indexed.
+fn indexable_get(expr: &MirRelationExpr) -> Option<GlobalId> {
+    match expr {
+        MirRelationExpr::Get {
+            id: Id::Global(id), ..
+        } if !id.is_transient() => Some(*id),
+        _ => None,
+    }
+}
+
+/// Returns whether `predicate` is of the form `expr IS NOT NULL`.
+fn is_not_null_test(predicate: &MirScalarExpr) -> bool {
+    match predicate {
+        MirScalarExpr::CallUnary {
+            func: UnaryFunc::Not(_),
+            expr,
+        } => matches!(
+            &**expr,
+            MirScalarExpr::CallUnary {
+                func: UnaryFunc::IsNull(_),
+                ..
+            }
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use mz_expr::BinaryFunc;
+    use mz_repr::{ColumnType, Datum, RelationType, ScalarType};
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct TestIndexOracle(BTreeMap<GlobalId, Vec<Vec<MirScalarExpr>>>);
+
+    impl IndexOracle for TestIndexOracle {
+        fn indexes_on(&self, id: GlobalId) -> Box<dyn Iterator<Item = &[MirScalarExpr]> + '_> {
+            Box::new(self.0.get(&id).into_iter().flatten().map(|k| &k[..]))
+        }
+    }
+
+    fn get(id: u64) -> MirRelationExpr {
+        let typ = RelationType::new(vec![
+            ColumnType {
+                scalar_type: ScalarType::Int64,
+                nullable: true,
+            };
+            2
+        ]);
+        MirRelationExpr::global_get(GlobalId::User(id), typ)
+    }
+
+    fn column_eq(column: usize, value: i64) -> MirScalarExpr {
+        MirScalarExpr::column(column).call_binary(
+            MirScalarExpr::literal_ok(Datum::Int64(value), ScalarType::Int64),
+            BinaryFunc::Eq,
+        )
+    }
+
+    #[mz_ore::test]
+    fn test_filter_advice() {
+        let expr = get(1).filter(vec![column_eq(1, 5), column_eq(0, 3)]);
+
+        let advice = index_advice(&expr, &TestIndexOracle::default());
+        let expected = IndexAdvice {
+            on_id: GlobalId::User(1),
+            key: vec![MirScalarExpr::column(0), MirScalarExpr::column(1)],
+            reason: IndexAdviceReason::Filter,
+        };
+        assert_eq!(advice.into_iter().collect::<Vec<_>>(), vec![expected]);
+
+        // An index on a subset of the constrained columns serves the filter.
+        let mut oracle = TestIndexOracle::default();
+        oracle
+            .0
+            .insert(GlobalId::User(1), vec![vec![MirScalarExpr::column(1)]]);
+        assert!(index_advice(&expr, &oracle).is_empty());
+
+        // An index on a column that is not constrained does not.
+        let mut oracle = TestIndexOracle::default();
+        oracle.0.insert(
+            GlobalId::User(1),
+            vec![vec![MirScalarExpr::column(0), MirScalarExpr::column(2)]],
+        );
+        assert_eq!(index_advice(&expr, &oracle).len(), 1);
+    }
+
+    #[mz_ore::test]
+    fn test_join_advice() {
+        let key = vec![MirScalarExpr::column(0)];
+        let expr = MirRelationExpr::join(
+            vec![
+                get(1).arrange_by(&[key.clone()]),
+                get(2).arrange_by(&[key.clone()]),
+            ],
+            vec![vec![(0, 0), (1, 0)]],
+        );
+
+        let mut oracle = TestIndexOracle::default();
+        oracle.0.insert(GlobalId::User(2), vec![key.clone()]);
+        let advice = index_advice(&expr, &oracle);
+        let expected = IndexAdvice {
+            on_id: GlobalId::User(1),
+            key: key.clone(),
+            reason: IndexAdviceReason::Join,
+        };
+        assert_eq!(
+            advice.into_iter().collect::<Vec<_>>(),
+            vec![expected.clone()]
+        );
+
+        // Filters that remove `NULL` keys do not prevent the recommendation.
+        let expr = MirRelationExpr::join(
+            vec![
+                get(1)
+                    .filter(vec![MirScalarExpr::column(0).call_is_null().not()])
+                    .arrange_by(&[key.clone()]),
+                get(2).arrange_by(&[key.clone()]),
+            ],
+            vec![vec![(0, 0), (1, 0)]],
+        );
+        let advice = index_advice(&expr, &oracle);
+        assert_eq!(advice.into_iter().collect::<Vec<_>>(), vec![expected]);
+
+        // Transient collections are not recommended for indexing.
+        let expr = MirRelationExpr::global_get(GlobalId::Transient(1), RelationType::empty())
+            .arrange_by(&[vec![]]);
+        assert!(index_advice(&expr, &oracle).is_empty());
+    }
+}
@@ -15,14 +15,19 @@
 //! This includes determining the type of join (e.g. differential linear, or delta queries),
 //! determining the orders of collections, lifting predicates if useful arrangements exist,
 //! and identifying opportunities to use indexes to replace filters.
+//!
+//! Orders are chosen by heuristics on the available arrangements and unique keys, unless we
+//! have cardinality estimates for all inputs of a join. In that case, we choose the orders with
+//! the lowest estimated cost, considering all orders of small joins and building orders of
+//! larger joins greedily.
 
 use std::collections::BTreeMap;
 
 use mz_expr::visit::{Visit, VisitChildren};
 use mz_expr::JoinImplementation::{Differential, IndexedFilter, Unimplemented};
 use mz_expr::{
-    FilterCharacteristics, Id, JoinInputCharacteristics, JoinInputMapper, MapFilterProject,
-    MirRelationExpr, MirScalarExpr, RECURSION_LIMIT,
+    CollectionPlan, FilterCharacteristics, Id, JoinInputCharacteristics, JoinInputMapper, JoinKind,
+    MapFilterProject, MirRelationExpr, MirScalarExpr, RECURSION_LIMIT,
 };
 use mz_ore::cast::{CastFrom, CastLossy, TryCastFrom};
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
@@ -141,12 +146,15 @@ impl JoinImplementation {
             // refactor this to make ArrangeBy unambiguous somehow. Maybe move JoinImplementation
             // to the lowering.)
             implementation: implementation @ (Unimplemented | Differential(..)),
+            kind,
         } = relation
         {
+            let kind = *kind;
             let input_types = inputs.iter().map(|i| i.typ()).collect::<Vec<_>>();
 
             // Canonicalize the equivalence classes
-            if matches!(implementation, Unimplemented) {
+            // (The equivalences of outer joins must keep referencing single inputs.)
+            if matches!(implementation, Unimplemented) && kind.is_inner() {
                 // Let's do this only if it's the first run of JoinImplementation, in which case we
                 // are guaranteed to produce a new plan, which will be compatible with the modified
                 // equivalences from the below call. Otherwise, if we already have a Differential or
@@ -169,8 +177,15 @@ impl JoinImplementation {
             // Symbolic terms in the cardinality estimate
             let mut symbolics = std::collections::BTreeSet::new();
             for input in inputs.iter() {
+                // Statistics about the collections read by the input let us estimate the
+                // selectivity of its predicates.
+                let statistics = input
+                    .depends_on()
+                    .into_iter()
+                    .filter_map(|id| Some((id, stats.collection_statistics(id)?)))
+                    .collect();
                 let mut builder = RequiredAttributes::default();
-                builder.require::<Cardinality>();
+                builder.require_with(Cardinality::with_statistics(statistics));
                 let mut attributes = builder.finish();
 
                 input.visit(&mut attributes)?;
@@ -403,6 +418,31 @@ impl JoinImplementation {
 
             let old_implementation = implementation.clone();
 
+            if kind == JoinKind::LeftOuter {
+                match old_implementation {
+                    Unimplemented => {
+                        *relation = outer::plan_delta_query(
+                            relation,
+                            &input_mapper,
+                            &available_arrangements,
+                        )
+                        .or_else(|_| outer::plan_differential(relation, &input_mapper))
+                        .expect("Failed to produce an outer join plan")
+                    }
+                    Differential(..) => {
+                        if let Ok(delta_query_plan) = outer::plan_delta_query(
+                            relation,
+                            &input_mapper,
+                            &available_arrangements,
+                        ) {
+                            *relation = delta_query_plan;
+                        }
+                    }
+                    _ => unreachable!(), // because of the match statement that is one level up
+                }
+                return Ok(());
+            }
+
             let delta_query_plan = || {
                 delta_queries::plan(
                     relation,
@@ -519,6 +559,7 @@ mod delta_queries {
             inputs,
             equivalences,
             implementation,
+            ..
         } = &mut new_join
         {
             if inputs.len() <= 2 {
@@ -545,7 +586,7 @@ mod delta_queries {
             }
 
             // Determine a viable order for each relation, or return `Err` if none found.
-            let orders = super::optimize_orders(
+            let mut orders = super::optimize_orders(
                 equivalences,
                 available,
                 unique_keys,
@@ -554,6 +595,25 @@ mod delta_queries {
                 input_mapper,
             );
 
+            // If we have cardinality estimates for all inputs, we prefer the orders with the
+            // lowest estimated cost, unless they need arrangements that don't exist yet.
+            if let Some(cost_based_orders) = super::optimize_orders_by_cost(
+                equivalences,
+                available,
+                unique_keys,
+                cardinalities,
+                filters,
+                input_mapper,
+                true,
+            ) {
+                if cost_based_orders
+                    .iter()
+                    .all(|o| o.iter().skip(1).all(|(c, _, _)| c.arranged))
+                {
+                    orders = cost_based_orders;
+                }
+            }
+
             // A viable delta query requires that, for every order,
             // there is an arrangement for every input except for
             // the starting one.
@@ -623,6 +683,7 @@ mod differential {
             inputs,
             equivalences,
             implementation,
+            ..
         } = &mut new_join
         {
             // We compute one order for each possible starting point, and we will choose one from
@@ -640,6 +701,20 @@ mod differential {
                 input_mapper,
             );
 
+            // If we have cardinality estimates for all inputs, we instead choose the order with
+            // the lowest estimated cost.
+            if let Some(cost_based_orders) = super::optimize_orders_by_cost(
+                equivalences,
+                available,
+                unique_keys,
+                cardinalities,
+                filters,
+                input_mapper,
+                false,
+            ) {
+                orders = cost_based_orders;
+            }
+
             // Inside each order, we take the `FilterCharacteristics` from each element, and OR it
             // to every other element to the right. This is because we are gonna be looking for the
             // worst `Characteristic` in every order, and for this it makes sense to include a
@@ -730,6 +805,181 @@ mod differential {
     }
 }
 
+mod outer {
+    use mz_expr::{
+        JoinImplementation, JoinInputCharacteristics, JoinInputMapper, MapFilterProject,
+        MirRelationExpr, MirScalarExpr,
+    };
+
+    use crate::TransformError;
+
+    /// The arrangement keys that a left outer join needs: the key of each other input, and the
+    /// key of the first input that matches it.
+    fn keys(
+        equivalences: &[Vec<MirScalarExpr>],
+        input_mapper: &JoinInputMapper,
+    ) -> Vec<(Vec<MirScalarExpr>, Vec<MirScalarExpr>)> {
+        (1..input_mapper.total_inputs())
+            .map(|index| {
+                input_mapper
+                    .outer_join_keys(equivalences, index)
+                    .into_iter()
+                    .unzip()
+            })
+            .collect()
+    }
+
+    /// The arrangements available for each input of an outer join.
+    ///
+    /// Only projections can be lifted from the other inputs, as maps and filters would be
+    /// applied to their padded records too. Arrangements of inputs that have other operators on
+    /// top are thus not available.
+    fn available_arrangements(
+        inputs: &[MirRelationExpr],
+        available: &[Vec<Vec<MirScalarExpr>>],
+    ) -> Vec<Vec<Vec<MirScalarExpr>>> {
+        inputs
+            .iter()
+            .zip(available)
+            .enumerate()
+            .map(|(index, (input, available))| {
+                let (mfp, _) = MapFilterProject::extract_non_errors_from_expr(input);
+                if index == 0 || (mfp.expressions.is_empty() && mfp.predicates.is_empty()) {
+                    available.clone()
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect()
+    }
+
+    /// Creates a delta query plan for a left outer join.
+    ///
+    /// The first input is looked up in each other input, and each other input is looked up in
+    /// the first input and then in the remaining inputs. The plan is only viable if all of these
+    /// arrangements are available.
+    pub fn plan_delta_query(
+        join: &MirRelationExpr,
+        input_mapper: &JoinInputMapper,
+        available: &[Vec<Vec<MirScalarExpr>>],
+    ) -> Result<MirRelationExpr, TransformError> {
+        let mut new_join = join.clone();
+
+        if let MirRelationExpr::Join {
+            inputs,
+            equivalences,
+            implementation,
+            ..
+        } = &mut new_join
+        {
+            // As with inner joins, we prefer a differential plan for two inputs.
+            if inputs.len() <= 2 {
+                return Err(TransformError::Internal(String::from(
+                    "should be planned as differential plan",
+                )));
+            }
+
+            let keys = keys(equivalences, input_mapper);
+            let available = available_arrangements(inputs, available);
+            let viable = keys.iter().enumerate().all(|(i, (preserved_key, key))| {
+                available[0].contains(preserved_key) && available[i + 1].contains(key)
+            });
+            if !viable {
+                return Err(TransformError::Internal(String::from(
+                    "delta plan not viable",
+                )));
+            }
+
+            let mut orders = vec![keys
+                .iter()
+                .enumerate()
+                .map(|(i, (_, key))| (i + 1, key.clone(), None))
+                .collect::<Vec<_>>()];
+            for (i, (preserved_key, _)) in keys.iter().enumerate() {
+                let mut order = vec![(0, preserved_key.clone(), None)];
+                order.extend(
+                    keys.iter()
+                        .enumerate()
+                        .filter(|(j, _)| *j != i)
+                        .map(|(j, (_, key))| (j + 1, key.clone(), None)),
+                );
+                orders.push(order);
+            }
+
+            let (lifted_mfp, lifted_projections) =
+                super::implement_arrangements(inputs, &available, orders.iter().flatten());
+            orders
+                .iter_mut()
+                .for_each(|order| super::permute_order(order, &lifted_projections));
+
+            *implementation = JoinImplementation::DeltaQuery(orders);
+
+            super::install_lifted_mfp(&mut new_join, lifted_mfp)?;
+
+            Ok(new_join)
+        } else {
+            Err(TransformError::Internal(String::from(
+                "outer::plan_delta_query call on non-join expression",
+            )))
+        }
+    }
+
+    /// Creates a linear differential plan for a left outer join.
+    ///
+    /// The plan starts from the first input, and looks up the other inputs in order.
+    pub fn plan_differential(
+        join: &MirRelationExpr,
+        input_mapper: &JoinInputMapper,
+    ) -> Result<MirRelationExpr, TransformError> {
+        let mut new_join = join.clone();
+
+        if let MirRelationExpr::Join {
+            inputs,
+            equivalences,
+            implementation,
+            ..
+        } = &mut new_join
+        {
+            let keys = keys(equivalences, input_mapper);
+            let Some((start_key, _)) = keys.first() else {
+                return Err(TransformError::Internal(String::from(
+                    "outer join without optional inputs",
+                )));
+            };
+            let mut start_key = start_key.clone();
+            let mut order: Vec<(usize, Vec<MirScalarExpr>, Option<JoinInputCharacteristics>)> =
+                keys.iter()
+                    .enumerate()
+                    .map(|(i, (_, key))| (i + 1, key.clone(), None))
+                    .collect();
+
+            // No arrangements are available for the purpose of lifting mfps, as the first input
+            // is only arranged by a single key, and the other inputs may only lift projections.
+            let available = vec![Vec::new(); inputs.len()];
+            let start = (0, start_key.clone(), None);
+            let (lifted_mfp, lifted_projections) = super::implement_arrangements(
+                inputs,
+                &available,
+                std::iter::once(&start).chain(order.iter()),
+            );
+            if let Some(proj) = &lifted_projections[0] {
+                start_key.iter_mut().for_each(|k| k.permute(proj));
+            }
+            super::permute_order(&mut order, &lifted_projections);
+
+            *implementation = JoinImplementation::Differential((0, Some(start_key), None), order);
+
+            super::install_lifted_mfp(&mut new_join, lifted_mfp)?;
+
+            Ok(new_join)
+        } else {
+            Err(TransformError::Internal(String::from(
+                "outer::plan_differential call on non-join expression",
+            )))
+        }
+    }
+}
+
 /// Modify `inputs` to ensure specified arrangements are available.
 ///
 /// Lift filter predicates when all needed arrangements are otherwise available.
@@ -833,7 +1083,10 @@ fn install_lifted_mfp(
 ) -> Result<(), TransformError> {
     if !mfp.is_identity() {
         let (mut map, mut filter, project) = mfp.as_map_filter_project();
-        if let MirRelationExpr::Join { equivalences, .. } = new_join {
+        if let MirRelationExpr::Join {
+            equivalences, kind, ..
+        } = new_join
+        {
             for equivalence in equivalences.iter_mut() {
                 for expr in equivalence.iter_mut() {
                     // permute `equivalences` in light of the project being lifted
@@ -864,13 +1117,16 @@ fn install_lifted_mfp(
             //  (This allows for almost the same optimizations as when `Demand`
             //  used to insert Projections that were marking some columns to be
             //  identical, when Demand used to run after `JoinImplementation`.)
-            let canonicalizer_map = mz_expr::canonicalize::get_canonicalizer_map(equivalences);
-            for expr in map.iter_mut().chain(filter.iter_mut()) {
-                expr.visit_mut_post(&mut |e| {
-                    if let Some(canonical_expr) = canonicalizer_map.get(e) {
-                        *e = canonical_expr.clone();
-                    }
-                })?
+            // (The expressions of an outer join equivalence are not equal for padded records.)
+            if kind.is_inner() {
+                let canonicalizer_map = mz_expr::canonicalize::get_canonicalizer_map(equivalences);
+                for expr in map.iter_mut().chain(filter.iter_mut()) {
+                    expr.visit_mut_post(&mut |e| {
+                        if let Some(canonical_expr) = canonicalizer_map.get(e) {
+                            *e = canonical_expr.clone();
+                        }
+                    })?
+                }
             }
         }
         *new_join = new_join.clone().map(map).filter(filter).project(project);
@@ -914,6 +1170,98 @@ fn optimize_orders(
         .collect::<Vec<_>>()
 }
 
+/// The largest number of inputs for which we find the cheapest join order by considering all
+/// sets of inputs that can be joined first. Larger joins are ordered greedily.
+const MAX_EXHAUSTIVE_INPUTS: usize = 8;
+
+/// Orders the inputs by their estimated cost, if we have cardinality estimates for all inputs.
+///
+/// For a differential join (`delta` is false), this returns the single cheapest order. For a
+/// delta join, this returns the cheapest order for each starting input.
+fn optimize_orders_by_cost(
+    equivalences: &[Vec<MirScalarExpr>], // join equivalences: inside a Vec, the exprs are equivalent
+    available: &[Vec<Vec<MirScalarExpr>>], // available arrangements per input
+    unique_keys: &[Vec<Vec<usize>>],     // unique keys per input
+    cardinalities: &[Option<usize>],     // cardinalities of input relations
+    filters: &[FilterCharacteristics],   // filter characteristics per input
+    input_mapper: &JoinInputMapper,      // join helper
+    delta: bool,                         // whether to plan the paths of a delta join
+) -> Option<Vec<Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)>>> {
+    let cost_cardinalities = cardinalities
+        .iter()
+        .map(|c| c.map(f64::cast_lossy))
+        .collect::<Option<Vec<_>>>()?;
+    if cost_cardinalities.len() < 2 {
+        return None;
+    }
+    let mut orderer = Orderer::new(
+        equivalences,
+        available,
+        unique_keys,
+        cardinalities,
+        filters,
+        input_mapper,
+    );
+    orderer.cost_cardinalities = cost_cardinalities;
+    let starts = if delta {
+        (0..available.len()).map(|i| vec![i]).collect::<Vec<_>>()
+    } else {
+        vec![(0..available.len()).collect()]
+    };
+    Some(
+        starts
+            .iter()
+            .map(|starts| orderer.optimize_order_by_cost(starts, !delta))
+            .collect(),
+    )
+}
+
+/// Estimates the cost of a join order, returning the total cost after each of its inputs.
+///
+/// The cost counts the records of each intermediate result, which the join has to produce, and
+/// the records of each arrangement that the join has to build rather than reuse. A linear join
+/// arranges its first input and each intermediate result but the last one, whereas the path of
+/// a delta join only arranges the inputs after its first.
+///
+/// We estimate the size of joining an input by assuming that each of its keys matches
+/// [`WORST_CASE_SELECTIVITY`] of its records, unless the key is unique.
+///
+/// [`WORST_CASE_SELECTIVITY`]: crate::attribute::cardinality::WORST_CASE_SELECTIVITY
+fn order_costs(
+    order: &[(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)],
+    cardinalities: &[f64],
+    linear: bool,
+) -> Vec<f64> {
+    let (start_characteristics, _, start) = &order[0];
+    let mut size = cardinalities[*start];
+    let mut cost = if linear && !start_characteristics.arranged {
+        size
+    } else {
+        0.0
+    };
+    let mut costs = Vec::with_capacity(order.len());
+    costs.push(cost);
+    for (position, (characteristics, _, input)) in order.iter().enumerate().skip(1) {
+        if linear && position > 1 {
+            // The previous intermediate result is arranged to join it with this input.
+            cost += size;
+        }
+        if !characteristics.arranged {
+            cost += cardinalities[*input];
+        }
+        size = if characteristics.key_length == 0 {
+            size * cardinalities[*input]
+        } else if characteristics.unique_key {
+            size
+        } else {
+            size * cardinalities[*input] * crate::attribute::cardinality::WORST_CASE_SELECTIVITY
+        };
+        cost += size;
+        costs.push(cost);
+    }
+    costs
+}
+
 struct Orderer<'a> {
     inputs: usize,
     equivalences: &'a [Vec<MirScalarExpr>],
@@ -925,6 +1273,9 @@ struct Orderer<'a> {
     reverse_equivalences: Vec<Vec<(usize, usize)>>,
     unique_arrangement: Vec<Vec<bool>>,
 
+    /// Cardinality estimates for all inputs, when ordering inputs by cost.
+    cost_cardinalities: Vec<f64>,
+
     order: Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)>,
     placed: Vec<bool>,
     bound: Vec<Vec<MirScalarExpr>>,
@@ -980,6 +1331,7 @@ impl<'a> Orderer<'a> {
             input_mapper,
             reverse_equivalences,
             unique_arrangement,
+            cost_cardinalities: Vec::new(),
             order,
             placed,
             bound,
@@ -993,6 +1345,157 @@ impl<'a> Orderer<'a> {
         &mut self,
         start: usize,
     ) -> Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)> {
+        self.reset();
+
+        // Main loop, ordering all the inputs.
+        if self.inputs > 1 {
+            self.order_input(start);
+            while self.order.len() < self.inputs - 1 {
+                let (characteristics, key, input) = self.priority_queue.pop().unwrap();
+                // put the tuple into `self.order` unless the tuple with the same
+                // input is already in `self.order`. For all inputs other than
+                // start, `self.placed[input]` is an indication of whether a
+                // corresponding tuple is already in `self.order`.
+                if !self.placed[input] {
+                    // non-starting inputs are ordered in decreasing priority
+                    self.order.push((characteristics, key, input));
+                    self.order_input(input);
+                }
+            }
+        }
+
+        self.finish_order(start)
+    }
+
+    /// Orders the inputs in the given `sequence`, which need not contain all inputs.
+    ///
+    /// Each input is joined using its best key among the ones that are available once the inputs
+    /// before it have been placed, where the keys are ranked as in `optimize_order_for`.
+    fn order_sequence(
+        &mut self,
+        sequence: &[usize],
+    ) -> Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)> {
+        self.reset();
+
+        let start = sequence[0];
+        self.order_input(start);
+        for input in sequence[1..].iter() {
+            // There is always at least the cross join candidate for each input.
+            let candidate = self
+                .priority_queue
+                .iter()
+                .filter(|(_, _, candidate)| candidate == input)
+                .max()
+                .cloned()
+                .expect("cross join candidate");
+            self.order.push(candidate);
+            self.order_input(*input);
+        }
+
+        self.finish_order(start)
+    }
+
+    /// Finds the order with the lowest estimated cost among those that start with one of `starts`.
+    ///
+    /// Each input of the returned order is annotated with the total cost of the order up to that
+    /// input. See [`order_costs`] for how we estimate costs.
+    fn optimize_order_by_cost(
+        &mut self,
+        starts: &[usize],
+        linear: bool,
+    ) -> Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)> {
+        let sequence = if self.inputs <= MAX_EXHAUSTIVE_INPUTS {
+            self.cheapest_sequence(starts, linear)
+        } else {
+            self.greedy_sequence(starts, linear)
+        };
+        let mut order = self.order_sequence(&sequence);
+        let costs = order_costs(&order, &self.cost_cardinalities, linear);
+        for ((characteristics, _, _), cost) in order.iter_mut().zip(costs) {
+            characteristics.cost = Some(std::cmp::Reverse(usize::cast_lossy(cost.ceil())));
+        }
+        order
+    }
+
+    /// The estimated cost of joining the inputs in the given `sequence`.
+    fn sequence_cost(&mut self, sequence: &[usize], linear: bool) -> f64 {
+        let order = self.order_sequence(sequence);
+        let costs = order_costs(&order, &self.cost_cardinalities, linear);
+        *costs.last().expect("non-empty order")
+    }
+
+    /// Finds the cheapest sequence of all inputs by dynamic programming.
+    ///
+    /// For each set of inputs, we remember the cheapest sequence of these inputs that we found,
+    /// and we extend it by each of the remaining inputs. This is exact if the cost of joining an
+    /// input only depends on the inputs before it, but not on their order.
+    fn cheapest_sequence(&mut self, starts: &[usize], linear: bool) -> Vec<usize> {
+        let mut cheapest: Vec<Option<(f64, Vec<usize>)>> = vec![None; 1 << self.inputs];
+        for start in starts {
+            cheapest[1 << start] = Some((0.0, vec![*start]));
+        }
+        // Supersets of a set of inputs come after it in this iteration order.
+        for set in 1..cheapest.len() {
+            let Some((_, sequence)) = cheapest[set].clone() else {
+                continue;
+            };
+            for input in 0..self.inputs {
+                if set & (1 << input) != 0 {
+                    continue;
+                }
+                let mut extended = sequence.clone();
+                extended.push(input);
+                let cost = self.sequence_cost(&extended, linear);
+                let entry = &mut cheapest[set | (1 << input)];
+                if entry
+                    .as_ref()
+                    .map_or(true, |(cheapest, _)| cost < *cheapest)
+                {
+                    *entry = Some((cost, extended));
+                }
+            }
+        }
+        let (_, sequence) = cheapest.pop().flatten().expect("sequence of all inputs");
+        sequence
+    }
+
+    /// Finds a cheap sequence of all inputs by repeatedly appending the input that is cheapest
+    /// to join next.
+    fn greedy_sequence(&mut self, starts: &[usize], linear: bool) -> Vec<usize> {
+        let mut cheapest: Option<(f64, Vec<usize>)> = None;
+        for start in starts {
+            let mut sequence = vec![*start];
+            let mut cost = 0.0;
+            while sequence.len() < self.inputs {
+                let mut next: Option<(f64, usize)> = None;
+                for input in 0..self.inputs {
+                    if sequence.contains(&input) {
+                        continue;
+                    }
+                    sequence.push(input);
+                    let input_cost = self.sequence_cost(&sequence, linear);
+                    sequence.pop();
+                    if next.map_or(true, |(next_cost, _)| input_cost < next_cost) {
+                        next = Some((input_cost, input));
+                    }
+                }
+                let (next_cost, input) = next.expect("remaining input");
+                sequence.push(input);
+                cost = next_cost;
+            }
+            if cheapest
+                .as_ref()
+                .map_or(true, |(cheapest, _)| cost < *cheapest)
+            {
+                cheapest = Some((cost, sequence));
+            }
+        }
+        let (_, sequence) = cheapest.expect("at least one start");
+        sequence
+    }
+
+    /// Resets the state of the orderer, and introduces cross joins as candidates.
+    fn reset(&mut self) {
         self.order.clear();
         self.priority_queue.clear();
         for input in 0..self.inputs {
@@ -1041,24 +1544,13 @@ impl<'a> Orderer<'a> {
                 ));
             }
         }
+    }
 
-        // Main loop, ordering all the inputs.
-        if self.inputs > 1 {
-            self.order_input(start);
-            while self.order.len() < self.inputs - 1 {
-                let (characteristics, key, input) = self.priority_queue.pop().unwrap();
-                // put the tuple into `self.order` unless the tuple with the same
-                // input is already in `self.order`. For all inputs other than
-                // start, `self.placed[input]` is an indication of whether a
-                // corresponding tuple is already in `self.order`.
-                if !self.placed[input] {
-                    // non-starting inputs are ordered in decreasing priority
-                    self.order.push((characteristics, key, input));
-                    self.order_input(input);
-                }
-            }
-        }
-
+    /// Completes `self.order`, which contains the inputs after `start`, with an item for `start`.
+    fn finish_order(
+        &mut self,
+        start: usize,
+    ) -> Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)> {
         // `order` now contains all the inputs except the first. Let's create an item for the first
         // input. We know which input that is, but we need to compute a key and characteristics.
         // We start with some default values:
@@ -1229,3 +1721,82 @@ impl<'a> Orderer<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the inputs of `order` along with their reported costs.
+    fn inputs_and_costs(
+        order: &[(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)],
+    ) -> Vec<(usize, usize)> {
+        order
+            .iter()
+            .map(|(c, _, input)| (*input, c.cost.expect("cost").0))
+            .collect()
+    }
+
+    #[mz_ore::test]
+    fn test_optimize_orders_by_cost() {
+        // A chain `%0.#1 = %1.#0` and `%1.#1 = %2.#0` of inputs with 1000, 10, and 100 records,
+        // all of which are arranged by their join keys.
+        let input_mapper = JoinInputMapper::new_from_input_arities([2, 2, 2].into_iter());
+        let equivalences = vec![
+            vec![MirScalarExpr::Column(1), MirScalarExpr::Column(2)],
+            vec![MirScalarExpr::Column(3), MirScalarExpr::Column(4)],
+        ];
+        let available = vec![
+            vec![vec![MirScalarExpr::Column(1)]],
+            vec![
+                vec![MirScalarExpr::Column(0)],
+                vec![MirScalarExpr::Column(1)],
+            ],
+            vec![vec![MirScalarExpr::Column(0)]],
+        ];
+        let unique_keys = vec![vec![]; 3];
+        let filters = vec![FilterCharacteristics::none(); 3];
+        let orders_by_cost = |cardinalities: &[Option<usize>], delta| {
+            optimize_orders_by_cost(
+                &equivalences,
+                &available,
+                &unique_keys,
+                cardinalities,
+                &filters,
+                &input_mapper,
+                delta,
+            )
+        };
+
+        // Without estimates for all inputs, we don't order by cost.
+        assert!(orders_by_cost(&[Some(1000), None, Some(100)], false).is_none());
+
+        let cardinalities = [Some(1000), Some(10), Some(100)];
+
+        // A linear join should start with the smaller inputs, to keep its intermediate
+        // arrangement small.
+        let orders = orders_by_cost(&cardinalities, false).unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(
+            inputs_and_costs(&orders[0]),
+            vec![(1, 0), (2, 100), (0, 10200)]
+        );
+
+        // The paths of a delta join must start with their own input, but should avoid cross
+        // joins and join the smaller inputs first.
+        let orders = orders_by_cost(&cardinalities, true).unwrap();
+        assert_eq!(
+            orders
+                .iter()
+                .map(|o| inputs_and_costs(o.as_slice()))
+                .collect::<Vec<_>>(),
+            vec![
+                vec![(0, 0), (1, 1000), (2, 11000)],
+                vec![(1, 0), (2, 100), (0, 10100)],
+                vec![(2, 0), (1, 100), (0, 10100)],
+            ]
+        );
+        assert!(orders
+            .iter()
+            .all(|o| o.iter().skip(1).all(|(c, _, _)| c.arranged)));
+    }
+}
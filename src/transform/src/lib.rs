@@ -89,6 +89,7 @@
 
 use std::error::Error;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::{fmt, iter};
 
 use mz_expr::visit::Visit;
@@ -98,6 +99,8 @@ use mz_ore::stack::RecursionLimitError;
 use mz_repr::GlobalId;
 use tracing::error;
 
+use crate::statistics::CollectionStatistics;
+
 pub mod attribute;
 pub mod canonicalization;
 pub mod canonicalize_mfp;
@@ -108,6 +111,7 @@ pub mod dataflow;
 pub mod demand;
 pub mod fold_constants;
 pub mod fusion;
+pub mod index_advice;
 pub mod join_implementation;
 pub mod literal_constraints;
 pub mod literal_lifting;
@@ -123,6 +127,7 @@ pub mod reduce_elision;
 pub mod reduction_pushdown;
 pub mod redundant_join;
 pub mod semijoin_idempotence;
+pub mod statistics;
 pub mod symbolic;
 pub mod threshold_elision;
 pub mod typecheck;
@@ -266,6 +271,12 @@ pub trait StatisticsOracle: fmt::Debug + Send {
     ///
     /// Returning `None` means "no estimate"; returning `Some(0)` means estimating that the shard backing `id` is empty
     fn cardinality_estimate(&self, id: GlobalId) -> Option<usize>;
+
+    /// Returns the statistics about the contents of the given identifier, as last collected by
+    /// `ANALYZE`.
+    ///
+    /// Returning `None` means the identifier has not been analyzed.
+    fn collection_statistics(&self, id: GlobalId) -> Option<Arc<CollectionStatistics>>;
 }
 
 /// A [`StatisticsOracle`] that knows nothing and can give no estimates.
@@ -276,6 +287,10 @@ impl StatisticsOracle for EmptyStatisticsOracle {
     fn cardinality_estimate(&self, _: GlobalId) -> Option<usize> {
         None
     }
+
+    fn collection_statistics(&self, _: GlobalId) -> Option<Arc<CollectionStatistics>> {
+        None
+    }
 }
 
 /// A sequence of transformations iterated some number of times.
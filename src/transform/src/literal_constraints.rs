@@ -24,7 +24,9 @@ use itertools::Itertools;
 use mz_expr::canonicalize::canonicalize_predicates;
 use mz_expr::visit::{Visit, VisitChildren};
 use mz_expr::JoinImplementation::IndexedFilter;
-use mz_expr::{BinaryFunc, Id, MapFilterProject, MirRelationExpr, MirScalarExpr, VariadicFunc};
+use mz_expr::{
+    BinaryFunc, Id, JoinKind, MapFilterProject, MirRelationExpr, MirScalarExpr, VariadicFunc,
+};
 use mz_ore::collections::CollectionExt;
 use mz_ore::iter::IteratorExt;
 use mz_ore::stack::RecursionLimitError;
@@ -170,6 +172,7 @@ impl LiteralConstraints {
                             })
                             .collect(),
                         implementation: IndexedFilter(inp_id, key.clone(), possible_vals),
+                        kind: JoinKind::Inner,
                     };
 
                     // Rebuild the MFP to add the projection that removes the columns coming from
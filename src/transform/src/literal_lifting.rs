@@ -459,8 +459,22 @@ impl LiteralLifting {
                     inputs,
                     equivalences,
                     implementation,
+                    kind,
                 } => {
-                    if !matches!(implementation, IndexedFilter(..)) {
+                    if !kind.is_inner() {
+                        // Literals of the optional inputs of an outer join are null for unmatched
+                        // records, so literals are not lifted around outer joins.
+                        for mut input in inputs.iter_mut() {
+                            let literals = self.action(input, gets)?;
+                            if !literals.is_empty() {
+                                if let MirRelationExpr::ArrangeBy { input, .. } = &mut input {
+                                    **input = input.take_dangerous().map(literals);
+                                } else {
+                                    *input = input.take_dangerous().map(literals);
+                                }
+                            }
+                        }
+                    } else if !matches!(implementation, IndexedFilter(..)) {
                         // before lifting, save the original shape of the inputs
                         let old_input_mapper = JoinInputMapper::new(inputs);
 
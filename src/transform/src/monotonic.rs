@@ -57,11 +57,20 @@ impl MonotonicFlag {
                 MirRelationExpr::Project { input, .. } => self.apply(input, mon_ids, locals)?,
                 MirRelationExpr::Filter { input, predicates } => {
                     let is_monotonic = self.apply(input, mon_ids, locals)?;
-                    // Non-temporal predicates can introduce non-monotonicity, as they
-                    // can result in the future removal of records.
-                    // TODO: this could be improved to only restrict if upper bounds
-                    // are present, as temporal lower bounds only delay introduction.
-                    is_monotonic && !predicates.iter().any(|p| p.contains_temporal())
+                    // Temporal predicates with upper bounds can introduce non-monotonicity,
+                    // as they can result in the future removal of records. Temporal lower
+                    // bounds only delay the introduction of records.
+                    //
+                    // TODO: An upper bound that only depends on columns a `Reduce` groups by,
+                    // such as the `window_end` of `tumble` and `hop`, removes all records of a
+                    // group at once, so the `Reduce` is monotonic per group until the group
+                    // expires. Using monotonic reductions for such inputs needs a way for them
+                    // to drop the state of expired groups, which monotonic reductions cannot
+                    // retract today.
+                    is_monotonic
+                        && predicates
+                            .iter()
+                            .all(|p| !p.contains_temporal() || p.is_temporal_lower_bound())
                 }
                 MirRelationExpr::Map { input, .. } => self.apply(input, mon_ids, locals)?,
                 MirRelationExpr::TopK {
@@ -94,9 +103,10 @@ impl MonotonicFlag {
                     let is_monotonic = self.apply(input, mon_ids, locals)?;
                     is_monotonic && func.preserves_monotonicity()
                 }
-                MirRelationExpr::Join { inputs, .. } => {
+                MirRelationExpr::Join { inputs, kind, .. } => {
                     // If all inputs to the join are monotonic then so is the join.
-                    let mut monotonic = true;
+                    // Outer joins retract their padded records when a match appears.
+                    let mut monotonic = kind.is_inner();
                     for input in inputs.iter_mut() {
                         let monotonic_i = self.apply(input, mon_ids, locals)?;
                         monotonic = monotonic && monotonic_i;
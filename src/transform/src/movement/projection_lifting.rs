@@ -206,6 +206,7 @@ impl ProjectionLifting {
                     inputs,
                     equivalences,
                     implementation,
+                    kind: _,
                 } => {
                     for input in inputs.iter_mut() {
                         self.action(input, gets)?;
@@ -217,6 +217,7 @@ impl NonNullRequirements {
                 MirRelationExpr::Join {
                     inputs,
                     equivalences,
+                    kind,
                     ..
                 } => {
                     let input_types = inputs.iter().map(|i| i.typ()).collect::<Vec<_>>();
@@ -225,6 +226,18 @@ impl NonNullRequirements {
 
                     let mut new_columns = input_mapper.split_column_set_by_input(columns.iter());
 
+                    if !kind.is_inner() {
+                        // Only the requirements on the preserved input of an outer join can be
+                        // pushed down, as the columns of the other inputs are padded with nulls.
+                        for (index, (input, columns)) in
+                            inputs.iter_mut().zip(new_columns).enumerate()
+                        {
+                            let columns = if index == 0 { columns } else { BTreeSet::new() };
+                            self.action(input, columns, gets)?;
+                        }
+                        return Ok(());
+                    }
+
                     // `variable` smears constraints around.
                     // Also, any non-nullable columns impose constraints on their equivalence class.
                     for equivalence in equivalences {
@@ -84,7 +84,7 @@ use std::collections::{BTreeMap, BTreeSet};
 use itertools::Itertools;
 use mz_expr::visit::{Visit, VisitChildren};
 use mz_expr::{
-    func, AggregateFunc, Id, JoinInputMapper, LocalId, MirRelationExpr, MirScalarExpr,
+    func, AggregateFunc, Id, JoinInputMapper, JoinKind, LocalId, MirRelationExpr, MirScalarExpr,
     VariadicFunc, RECURSION_LIMIT,
 };
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
@@ -183,6 +183,32 @@ impl PredicatePushdown {
                                 .or_insert_with(|| predicates.iter().cloned().collect())
                                 .retain(|p| predicates.contains(p));
                         }
+                        MirRelationExpr::Join {
+                            inputs,
+                            kind: JoinKind::LeftOuter,
+                            ..
+                        } => {
+                            // Only predicates on the columns of the first input can be pushed
+                            // down, as the other inputs determine which of its records are
+                            // padded with nulls. Equalities must not become join constraints,
+                            // for the same reason.
+                            let preserved_arity = inputs[0].arity();
+                            let mut push_down = Vec::new();
+                            predicates.retain(|predicate| {
+                                let pushable = (!predicate.is_literal_err() || all_errors)
+                                    && predicate.support().into_iter().all(|c| c < preserved_arity);
+                                if pushable {
+                                    push_down.push(predicate.clone());
+                                }
+                                !pushable
+                            });
+                            if !push_down.is_empty() {
+                                inputs[0] = inputs[0].take_dangerous().filter(push_down);
+                            }
+
+                            // Recursively descend on the join
+                            self.action(input, get_predicates)?;
+                        }
                         MirRelationExpr::Join {
                             inputs,
                             equivalences,
@@ -551,6 +577,18 @@ impl PredicatePushdown {
 
                     Ok(())
                 }
+                MirRelationExpr::Join {
+                    inputs,
+                    kind: JoinKind::LeftOuter,
+                    ..
+                } => {
+                    // The equivalences of outer joins only determine which records match,
+                    // and cannot be pushed down as predicates.
+                    for input in inputs.iter_mut() {
+                        self.action(input, get_predicates)?;
+                    }
+                    Ok(())
+                }
                 MirRelationExpr::Join {
                     inputs,
                     equivalences,
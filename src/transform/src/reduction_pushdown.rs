@@ -51,7 +51,7 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::iter::FromIterator;
 
 use mz_expr::visit::Visit;
-use mz_expr::{AggregateExpr, JoinInputMapper, MirRelationExpr, MirScalarExpr};
+use mz_expr::{AggregateExpr, JoinInputMapper, JoinKind, MirRelationExpr, MirScalarExpr};
 
 use crate::TransformArgs;
 
@@ -140,6 +140,7 @@ impl ReductionPushdown {
                 inputs,
                 equivalences,
                 implementation: _,
+                kind: JoinKind::Inner,
             } = &mut **input
             {
                 if let Some(new_relation_expr) = try_push_reduce_through_join(
@@ -170,7 +170,17 @@ impl RedundantJoin {
                     inputs,
                     equivalences,
                     implementation,
+                    kind,
                 } => {
+                    if !kind.is_inner() {
+                        // The optional inputs of an outer join cannot be removed, and the
+                        // provenance of its padded columns is not tracked.
+                        for i in inputs.iter_mut() {
+                            self.action(i, ctx)?;
+                        }
+                        return Ok(Vec::new());
+                    }
+
                     // This logic first applies what it has learned about its input provenance,
                     // and if it finds a redundant join input it removes it. In that case, it
                     // also fails to produce exciting provenance information, partly out of
@@ -28,7 +28,9 @@
 use itertools::Itertools;
 use std::collections::BTreeMap;
 
-use mz_expr::{Id, JoinInputMapper, LocalId, MirRelationExpr, MirScalarExpr, RECURSION_LIMIT};
+use mz_expr::{
+    Id, JoinInputMapper, JoinKind, LocalId, MirRelationExpr, MirScalarExpr, RECURSION_LIMIT,
+};
 use mz_ore::id_gen::IdGen;
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
 
@@ -159,7 +161,7 @@ impl SemijoinIdempotence {
                     inputs,
                     equivalences,
                     implementation,
-                    ..
+                    kind: JoinKind::Inner,
                 } => {
                     attempt_join_simplification(
                         inputs,
@@ -340,6 +342,7 @@ fn list_replacements(
         MirRelationExpr::Join {
             inputs,
             equivalences,
+            kind: JoinKind::Inner,
             ..
         } => {
             results.extend(list_replacements_join(
@@ -0,0 +1,557 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Statistics about the contents of collections.
+//!
+//! [`CollectionStatistics`] are computed by `ANALYZE` from a snapshot of a collection, which a
+//! [`StatisticsBuilder`] consumes one update at a time, and describe the distribution of values
+//! in each of its columns. The [`Cardinality`] attribute
+//! uses them to estimate the selectivity of predicates on the collection and the number of
+//! groups of reductions over it, instead of falling back to worst-case guesses.
+//!
+//! Null fractions and distinct counts are computed over all rows of the snapshot, the latter
+//! with a [`HyperLogLog`] sketch. The most common values and the histograms are computed from
+//! a systematic sample of at most [`SAMPLE_SIZE`] rows.
+//!
+//! [`Cardinality`]: crate::attribute::Cardinality
+
+use std::collections::BTreeMap;
+
+use mz_expr::{BinaryFunc, MirScalarExpr, UnaryFunc, VariadicFunc};
+use mz_ore::cast::{CastFrom, CastLossy};
+use mz_repr::adt::hll::HyperLogLog;
+use mz_repr::{Datum, Diff, Row};
+
+/// The maximum number of rows sampled to compute most common values and histograms.
+pub const SAMPLE_SIZE: u64 = 30_000;
+
+/// The maximum number of most common values recorded for each column.
+pub const MAX_MOST_COMMON_VALUES: usize = 10;
+
+/// The number of buckets of the histogram recorded for each column.
+pub const HISTOGRAM_BUCKETS: usize = 10;
+
+/// Statistics about the contents of a collection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollectionStatistics {
+    /// The number of rows in the collection.
+    pub row_count: u64,
+    /// Statistics about each column of the collection.
+    pub columns: Vec<ColumnStatistics>,
+}
+
+/// Statistics about the values in a column of a collection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnStatistics {
+    /// The fraction of rows in which the column is null.
+    pub null_fraction: f64,
+    /// The estimated number of distinct non-null values in the column.
+    pub distinct_count: u64,
+    /// The most common non-null values in the column, as single-datum rows, paired with the
+    /// fraction of rows that contain them, most common first.
+    pub most_common_values: Vec<(Row, f64)>,
+    /// The bounds of buckets that hold roughly equal numbers of the non-null values that are not
+    /// among `most_common_values`, as single-datum rows in ascending order.
+    ///
+    /// Empty if there are too few such values to build a histogram.
+    pub histogram_bounds: Vec<Row>,
+}
+
+impl CollectionStatistics {
+    /// Computes statistics about a collection of `arity` columns from a snapshot of its
+    /// contents.
+    pub fn from_snapshot(arity: usize, mut snapshot: Vec<(Row, Diff)>) -> Self {
+        differential_dataflow::consolidation::consolidate(&mut snapshot);
+
+        let row_count = snapshot
+            .iter()
+            .map(|(_, diff)| u64::try_from(*diff).unwrap_or(0))
+            .sum::<u64>();
+        let mut builder = StatisticsBuilder::new(arity, row_count);
+        for (row, diff) in &snapshot {
+            builder.push(row, *diff);
+        }
+        builder.finish()
+    }
+
+    /// Estimates the fraction of rows that satisfy `predicate`.
+    ///
+    /// Returns `None` if the statistics don't inform the selectivity of the predicate, and
+    /// otherwise estimates that at least one row satisfies it.
+    pub fn selectivity(&self, predicate: &MirScalarExpr) -> Option<f64> {
+        let selectivity = self.estimate(predicate)?;
+        let min_selectivity = 1.0 / f64::cast_lossy(std::cmp::max(1, self.row_count));
+        Some(selectivity.clamp(min_selectivity, 1.0))
+    }
+
+    /// Estimates the number of distinct values of the given columns, counting null as a value.
+    ///
+    /// Assumes that the values of different columns are independent.
+    pub fn distinct_count(&self, columns: &[usize]) -> Option<f64> {
+        let mut count = 1.0;
+        for column in columns {
+            let stats = self.columns.get(*column)?;
+            let nulls = if stats.null_fraction > 0.0 { 1.0 } else { 0.0 };
+            count *= f64::cast_lossy(stats.distinct_count) + nulls;
+        }
+        Some(count)
+    }
+
+    fn estimate(&self, predicate: &MirScalarExpr) -> Option<f64> {
+        match predicate {
+            MirScalarExpr::CallUnary { func, expr } => match (func, &**expr) {
+                (UnaryFunc::Not(_), expr) => Some(1.0 - self.estimate(expr)?),
+                (UnaryFunc::IsNull(_), MirScalarExpr::Column(column)) => {
+                    Some(self.columns.get(*column)?.null_fraction)
+                }
+                _ => None,
+            },
+            MirScalarExpr::CallBinary { func, expr1, expr2 } => {
+                let (column, datum, func) = match (&**expr1, &**expr2) {
+                    (MirScalarExpr::Column(column), literal) => {
+                        (*column, literal.as_literal()?.ok()?, func.clone())
+                    }
+                    (literal, MirScalarExpr::Column(column)) => {
+                        // Flip the comparison so that the column is on the left.
+                        let func = match func {
+                            BinaryFunc::Lt => BinaryFunc::Gt,
+                            BinaryFunc::Lte => BinaryFunc::Gte,
+                            BinaryFunc::Gt => BinaryFunc::Lt,
+                            BinaryFunc::Gte => BinaryFunc::Lte,
+                            func => func.clone(),
+                        };
+                        (*column, literal.as_literal()?.ok()?, func)
+                    }
+                    _ => return None,
+                };
+                let stats = self.columns.get(column)?;
+                if datum.is_null() {
+                    // Comparisons with null are never true.
+                    return Some(0.0);
+                }
+                match func {
+                    BinaryFunc::Eq => Some(stats.eq_selectivity(datum)),
+                    BinaryFunc::NotEq => {
+                        Some(1.0 - stats.null_fraction - stats.eq_selectivity(datum))
+                    }
+                    BinaryFunc::Lt => Some(stats.lt_selectivity(datum, false)),
+                    BinaryFunc::Lte => Some(stats.lt_selectivity(datum, true)),
+                    BinaryFunc::Gt => {
+                        Some(1.0 - stats.null_fraction - stats.lt_selectivity(datum, true))
+                    }
+                    BinaryFunc::Gte => {
+                        Some(1.0 - stats.null_fraction - stats.lt_selectivity(datum, false))
+                    }
+                    _ => None,
+                }
+            }
+            MirScalarExpr::CallVariadic { func, exprs } => match func {
+                VariadicFunc::And => exprs
+                    .iter()
+                    .map(|expr| self.estimate(expr))
+                    .product::<Option<f64>>(),
+                VariadicFunc::Or => exprs.iter().try_fold(0.0, |selectivity, expr| {
+                    let other = self.estimate(expr)?;
+                    Some(selectivity + other - selectivity * other)
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+        .map(|selectivity| selectivity.clamp(0.0, 1.0))
+    }
+}
+
+/// Computes [`CollectionStatistics`] from the updates of a snapshot of a collection, without
+/// holding the snapshot in memory.
+///
+/// The updates need not be consolidated. Retractions are subtracted from the row and null counts,
+/// but not from the distinct count sketches and the sample, so that retracted values that were
+/// not consolidated away are slightly overrepresented.
+#[derive(Debug)]
+pub struct StatisticsBuilder {
+    /// Every `interval`-th row is sampled, counting each row as often as its multiplicity.
+    interval: u64,
+    /// The number of rows inserted so far, which determines the rows to sample.
+    seen: u64,
+    /// The number of rows sampled so far.
+    sampled: u64,
+    row_count: i64,
+    null_counts: Vec<i64>,
+    sketches: Vec<HyperLogLog>,
+    /// The number of times each non-null value of each column was sampled, as single-datum rows.
+    samples: Vec<BTreeMap<Row, u64>>,
+}
+
+impl StatisticsBuilder {
+    /// Returns a builder for statistics about a collection of `arity` columns, which is expected
+    /// to hold about `expected_rows` rows.
+    ///
+    /// The expected number of rows determines the sampling interval. If the collection turns out
+    /// to be larger, the interval is doubled as necessary to keep the sample bounded.
+    pub fn new(arity: usize, expected_rows: u64) -> Self {
+        StatisticsBuilder {
+            interval: std::cmp::max(1, (expected_rows + SAMPLE_SIZE - 1) / SAMPLE_SIZE),
+            seen: 0,
+            sampled: 0,
+            row_count: 0,
+            null_counts: vec![0; arity],
+            sketches: vec![HyperLogLog::new(); arity],
+            samples: vec![BTreeMap::new(); arity],
+        }
+    }
+
+    /// Adds an update of the snapshot to the statistics.
+    pub fn push(&mut self, row: &Row, diff: Diff) {
+        let arity = self.null_counts.len();
+        self.row_count += diff;
+        let Ok(copies) = u64::try_from(diff) else {
+            for (column, datum) in row.iter().enumerate().take(arity) {
+                if datum.is_null() {
+                    self.null_counts[column] += diff;
+                }
+            }
+            return;
+        };
+
+        let sampled = (self.seen + copies) / self.interval - self.seen / self.interval;
+        self.seen += copies;
+        self.sampled += sampled;
+        for (column, datum) in row.iter().enumerate().take(arity) {
+            if datum.is_null() {
+                self.null_counts[column] += diff;
+            } else {
+                self.sketches[column].insert(datum);
+                if sampled > 0 {
+                    *self.samples[column]
+                        .entry(Row::pack_slice(&[datum]))
+                        .or_default() += sampled;
+                }
+            }
+        }
+
+        // Thin out the sample if the collection is larger than expected.
+        if self.sampled > 2 * SAMPLE_SIZE {
+            self.interval *= 2;
+            self.sampled /= 2;
+            for sample in &mut self.samples {
+                sample.retain(|_, count| {
+                    *count /= 2;
+                    *count > 0
+                });
+            }
+        }
+    }
+
+    /// Returns the statistics about the updates added so far.
+    pub fn finish(self) -> CollectionStatistics {
+        let row_count = u64::try_from(self.row_count).unwrap_or(0);
+        let sample_size = self.sampled;
+        let columns = self
+            .null_counts
+            .into_iter()
+            .zip(self.sketches)
+            .zip(self.samples)
+            .map(|((null_count, sketch), sample)| {
+                let null_count = std::cmp::min(u64::try_from(null_count).unwrap_or(0), row_count);
+                ColumnStatistics::from_sample(row_count, null_count, &sketch, &sample, sample_size)
+            })
+            .collect();
+
+        CollectionStatistics { row_count, columns }
+    }
+}
+
+impl ColumnStatistics {
+    /// Computes statistics about a column from the number of rows and nulls in the collection,
+    /// a sketch of its non-null values, and the counts of the non-null values in a sample of
+    /// `sample_size` rows.
+    fn from_sample(
+        row_count: u64,
+        null_count: u64,
+        sketch: &HyperLogLog,
+        sample: &BTreeMap<Row, u64>,
+        sample_size: u64,
+    ) -> Self {
+        // Order the sampled values by datum, rather than by their encoding.
+        let sample: BTreeMap<Datum, u64> = sample
+            .iter()
+            .map(|(value, count)| (value.unpack_first(), *count))
+            .collect();
+        let null_fraction = if row_count == 0 {
+            0.0
+        } else {
+            f64::cast_lossy(null_count) / f64::cast_lossy(row_count)
+        };
+        // The sketch may underestimate small counts, but there are at least as many distinct
+        // values as there are in the sample.
+        let distinct_count = std::cmp::max(sketch.estimate(), u64::cast_from(sample.len()));
+
+        // Values are common if they occur more than once in the sample and more often than the
+        // average value, or if the sample contains few enough values to record them all.
+        let sampled_values = sample.values().sum::<u64>();
+        let average = f64::cast_lossy(sampled_values) / f64::cast_lossy(sample.len().max(1));
+        let complete = sample.len() <= MAX_MOST_COMMON_VALUES;
+        let mut candidates: Vec<_> = sample
+            .iter()
+            .filter(|(_, count)| complete || (**count > 1 && f64::cast_lossy(**count) > average))
+            .collect();
+        candidates.sort_by(|(d1, c1), (d2, c2)| c2.cmp(c1).then_with(|| d1.cmp(d2)));
+        candidates.truncate(MAX_MOST_COMMON_VALUES);
+        let most_common_values: Vec<_> = candidates
+            .iter()
+            .map(|(datum, count)| {
+                let frequency = f64::cast_lossy(**count) / f64::cast_lossy(sample_size.max(1));
+                (Row::pack_slice(&[**datum]), frequency)
+            })
+            .collect();
+
+        // Place the bounds of the histogram at equally spaced positions among the remaining
+        // values, in order.
+        let remaining: Vec<_> = sample
+            .iter()
+            .filter(|(datum, _)| !candidates.iter().any(|(common, _)| common == datum))
+            .collect();
+        let remaining_values = remaining.iter().map(|(_, count)| **count).sum::<u64>();
+        let mut histogram_bounds = Vec::new();
+        if remaining.len() >= 2 {
+            let buckets = u64::cast_from(HISTOGRAM_BUCKETS);
+            let mut values = remaining.iter();
+            let mut current = values.next().expect("at least two values");
+            let mut position = *current.1;
+            for bucket in 0..=buckets {
+                let target = bucket * (remaining_values - 1) / buckets;
+                while position <= target {
+                    current = values.next().expect("target is in range");
+                    position += *current.1;
+                }
+                let bound = Row::pack_slice(&[*current.0]);
+                if histogram_bounds.last() != Some(&bound) {
+                    histogram_bounds.push(bound);
+                }
+            }
+        }
+
+        ColumnStatistics {
+            null_fraction,
+            distinct_count,
+            most_common_values,
+            histogram_bounds,
+        }
+    }
+
+    /// The fraction of rows whose value is neither null nor among the most common values.
+    fn histogram_fraction(&self) -> f64 {
+        let common = self
+            .most_common_values
+            .iter()
+            .map(|(_, frequency)| frequency)
+            .sum::<f64>();
+        (1.0 - self.null_fraction - common).max(0.0)
+    }
+
+    /// Estimates the fraction of rows in which the column equals `datum`.
+    pub fn eq_selectivity(&self, datum: Datum) -> f64 {
+        if let Some((_, frequency)) = self
+            .most_common_values
+            .iter()
+            .find(|(value, _)| value.unpack_first() == datum)
+        {
+            return *frequency;
+        }
+        // Assume that the remaining values are equally common.
+        let common = u64::cast_from(self.most_common_values.len());
+        let others = std::cmp::max(1, self.distinct_count.saturating_sub(common));
+        self.histogram_fraction() / f64::cast_lossy(others)
+    }
+
+    /// Estimates the fraction of rows in which the column is less than `datum`, or less than or
+    /// equal to it if `inclusive` is set.
+    pub fn lt_selectivity(&self, datum: Datum, inclusive: bool) -> f64 {
+        let common = self
+            .most_common_values
+            .iter()
+            .filter(|(value, _)| {
+                let value = value.unpack_first();
+                value < datum || (inclusive && value == datum)
+            })
+            .map(|(_, frequency)| frequency)
+            .sum::<f64>();
+        common + self.histogram_fraction() * self.histogram_position(datum)
+    }
+
+    /// Estimates the fraction of the values in the histogram that are less than `datum`.
+    ///
+    /// Assumes that `datum` lies in the middle of the bucket it falls into.
+    fn histogram_position(&self, datum: Datum) -> f64 {
+        let Some((first, last)) = self
+            .histogram_bounds
+            .first()
+            .zip(self.histogram_bounds.last())
+        else {
+            return 0.5;
+        };
+        if datum <= first.unpack_first() {
+            0.0
+        } else if datum > last.unpack_first() {
+            1.0
+        } else {
+            let buckets = self.histogram_bounds.len() - 1;
+            let below = self
+                .histogram_bounds
+                .partition_point(|bound| bound.unpack_first() < datum);
+            (f64::cast_lossy(below) - 0.5) / f64::cast_lossy(buckets)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    fn column_eq(column: usize, value: i64) -> MirScalarExpr {
+        MirScalarExpr::column(column).call_binary(
+            MirScalarExpr::literal_ok(Datum::Int64(value), ScalarType::Int64),
+            BinaryFunc::Eq,
+        )
+    }
+
+    #[mz_ore::test]
+    fn test_from_snapshot() {
+        // Column 0 is unique, column 1 holds `i % 4`, and column 2 is null in every other row.
+        let snapshot = (0..1000)
+            .map(|i| {
+                let nullable = if i % 2 == 0 {
+                    Datum::Null
+                } else {
+                    Datum::Int64(i)
+                };
+                (
+                    Row::pack_slice(&[Datum::Int64(i), Datum::Int64(i % 4), nullable]),
+                    1,
+                )
+            })
+            .collect();
+        let stats = CollectionStatistics::from_snapshot(3, snapshot);
+
+        assert_eq!(stats.row_count, 1000);
+        let [unique, small, nullable] = &stats.columns[..] else {
+            panic!("expected three columns");
+        };
+
+        assert_eq!(unique.null_fraction, 0.0);
+        assert!((950..=1050).contains(&unique.distinct_count));
+        assert!(unique.most_common_values.is_empty());
+        assert_eq!(unique.histogram_bounds.len(), HISTOGRAM_BUCKETS + 1);
+        assert_eq!(
+            unique.histogram_bounds[0],
+            Row::pack_slice(&[Datum::Int64(0)])
+        );
+        assert_eq!(
+            unique.histogram_bounds[HISTOGRAM_BUCKETS],
+            Row::pack_slice(&[Datum::Int64(999)])
+        );
+
+        assert_eq!(small.distinct_count, 4);
+        assert_eq!(small.most_common_values.len(), 4);
+        assert!(small.histogram_bounds.is_empty());
+        assert_eq!(small.eq_selectivity(Datum::Int64(2)), 0.25);
+
+        assert_eq!(nullable.null_fraction, 0.5);
+        assert_eq!(
+            stats.selectivity(&MirScalarExpr::column(2).call_is_null()),
+            Some(0.5)
+        );
+
+        // Selectivities of predicates.
+        let eq = stats.selectivity(&column_eq(0, 500)).unwrap();
+        assert!((0.0005..0.002).contains(&eq), "{eq}");
+        assert_eq!(stats.selectivity(&column_eq(1, 3)), Some(0.25));
+        // Missing values are estimated to match a single row.
+        assert_eq!(stats.selectivity(&column_eq(1, 7)), Some(0.001));
+        let lt = MirScalarExpr::column(0).call_binary(
+            MirScalarExpr::literal_ok(Datum::Int64(250), ScalarType::Int64),
+            BinaryFunc::Lt,
+        );
+        let lt = stats.selectivity(&lt).unwrap();
+        assert!((0.2..0.3).contains(&lt), "{lt}");
+        let or = MirScalarExpr::CallVariadic {
+            func: VariadicFunc::Or,
+            exprs: vec![column_eq(1, 0), column_eq(1, 1)],
+        };
+        assert_eq!(stats.selectivity(&or), Some(0.4375));
+        let unknown =
+            MirScalarExpr::column(0).call_binary(MirScalarExpr::column(1), BinaryFunc::Eq);
+        assert_eq!(stats.selectivity(&unknown), None);
+
+        let nullable_groups = f64::cast_lossy(nullable.distinct_count) + 1.0;
+        assert_eq!(stats.distinct_count(&[1, 2]), Some(4.0 * nullable_groups));
+        assert_eq!(stats.distinct_count(&[3]), None);
+    }
+
+    #[mz_ore::test]
+    fn test_from_snapshot_multiplicities() {
+        // Retractions are consolidated away, and multiplicities count towards frequencies.
+        let snapshot = vec![
+            (Row::pack_slice(&[Datum::Int64(1)]), 3),
+            (Row::pack_slice(&[Datum::Int64(2)]), 2),
+            (Row::pack_slice(&[Datum::Int64(2)]), -1),
+            (Row::pack_slice(&[Datum::Int64(3)]), 0),
+        ];
+        let stats = CollectionStatistics::from_snapshot(1, snapshot);
+        assert_eq!(stats.row_count, 4);
+        assert_eq!(stats.columns[0].distinct_count, 2);
+        assert_eq!(
+            stats.columns[0].most_common_values,
+            vec![
+                (Row::pack_slice(&[Datum::Int64(1)]), 0.75),
+                (Row::pack_slice(&[Datum::Int64(2)]), 0.25)
+            ]
+        );
+
+        let empty = CollectionStatistics::from_snapshot(1, vec![]);
+        assert_eq!(empty.row_count, 0);
+        assert_eq!(empty.columns[0].distinct_count, 0);
+        assert_eq!(empty.selectivity(&column_eq(0, 1)), Some(1.0));
+    }
+
+    #[mz_ore::test]
+    fn test_builder() {
+        // A collection much larger than expected keeps a bounded sample.
+        let mut builder = StatisticsBuilder::new(1, 0);
+        for i in 0..200_000 {
+            builder.push(&Row::pack_slice(&[Datum::Int64(i)]), 1);
+            assert!(builder.sampled <= 2 * SAMPLE_SIZE);
+        }
+        assert!(builder.samples[0].len() <= usize::cast_from(2 * SAMPLE_SIZE));
+        // Unconsolidated retractions are subtracted from the counts.
+        builder.push(&Row::pack_slice(&[Datum::Null]), 2);
+        builder.push(&Row::pack_slice(&[Datum::Null]), -1);
+        builder.push(&Row::pack_slice(&[Datum::Int64(0)]), -1);
+        let stats = builder.finish();
+
+        assert_eq!(stats.row_count, 200_000);
+        let column = &stats.columns[0];
+        assert_eq!(column.null_fraction, 1.0 / 200_000.0);
+        assert!(
+            (190_000..=210_000).contains(&column.distinct_count),
+            "{}",
+            column.distinct_count
+        );
+        assert_eq!(column.histogram_bounds.len(), HISTOGRAM_BUCKETS + 1);
+        let lt = MirScalarExpr::column(0).call_binary(
+            MirScalarExpr::literal_ok(Datum::Int64(50_000), ScalarType::Int64),
+            BinaryFunc::Lt,
+        );
+        let lt = stats.selectivity(&lt).unwrap();
+        assert!((0.2..0.3).contains(&lt), "{lt}");
+    }
+}
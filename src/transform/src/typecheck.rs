@@ -16,7 +16,7 @@ use std::rc::Rc;
 
 use itertools::Itertools;
 use mz_expr::{
-    non_nullable_columns, AggregateExpr, ColumnOrder, Id, JoinImplementation, LocalId,
+    non_nullable_columns, AggregateExpr, ColumnOrder, Id, JoinImplementation, JoinKind, LocalId,
     MirRelationExpr, MirScalarExpr, RECURSION_LIMIT,
 };
 use mz_ore::stack::{CheckedRecursion, RecursionGuard, RecursionLimitError};
@@ -620,6 +620,7 @@ impl Typecheck {
                 inputs,
                 equivalences,
                 implementation,
+                kind,
             } => {
                 let mut t_in_global = Vec::new();
                 let mut t_in_local = vec![Vec::new(); inputs.len()];
@@ -751,6 +752,14 @@ impl Typecheck {
                     JoinImplementation::Unimplemented => (),
                 }
 
+                // the columns of the optional inputs of an outer join are padded with nulls
+                if *kind == JoinKind::LeftOuter {
+                    let preserved_arity = t_in_local[0].len();
+                    for t in t_in_global.iter_mut().skip(preserved_arity) {
+                        t.nullable = true;
+                    }
+                }
+
                 Ok(t_in_global)
             }
             Reduce {
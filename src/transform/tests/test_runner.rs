@@ -95,7 +95,9 @@ mod tests {
     use mz_lowertest::{deserialize, tokenize};
     use mz_ore::collections::HashMap;
     use mz_ore::str::separated;
-    use mz_repr::explain::{Explain, ExplainConfig, ExplainFormat, UsedIndexes};
+    use mz_repr::explain::{
+        Explain, ExplainConfig, ExplainFormat, RecommendedIndexes, UsedIndexes,
+    };
     use mz_repr::GlobalId;
     use mz_transform::dataflow::{optimize_dataflow_demand_inner, optimize_dataflow_filters_inner};
     use mz_transform::{Optimizer, Transform, TransformArgs};
@@ -210,6 +212,7 @@ mod tests {
                     config: &config,
                     humanizer: cat,
                     used_indexes: UsedIndexes::new(vec![]),
+                    recommended_indexes: RecommendedIndexes::default(),
                     finishing: None,
                     duration: Duration::default(),
                 };
@@ -68,6 +68,19 @@ With
         - ("a", 1, 2)
         - ("b", 3, 4)
 
+# Only predicates on the preserved input are pushed through a left outer join
+apply pipeline=predicate_pushdown
+Filter (#1 > 0) AND (#3 = 5)
+  LeftOuterJoin on=(#0 = #2)
+    Get t0
+    Get t0
+----
+Filter (#3 = 5)
+  LeftOuterJoin on=(#0 = #2)
+    Filter (#1 > 0)
+      Get t0
+    Get t0
+
 
 ## LetRec cases
 ## ------------
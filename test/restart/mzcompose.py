@@ -6,7 +6,9 @@
 # As of the Change Date specified in that file, in accordance with
 # the Business Source License, use of this software will be governed
 # by the Apache License, Version 2.0.
+import time
 from textwrap import dedent
+from threading import Thread
 
 import pg8000.exceptions
 
@@ -397,6 +399,161 @@ def workflow_bound_size_mz_status_history(c: Composition) -> None:
     )
 
 
+def workflow_column_statistics(c: Composition) -> None:
+    c.up("materialized")
+
+    c.sql(
+        "ALTER SYSTEM SET enable_analyze = true",
+        port=6877,
+        user="mz_system",
+    )
+    c.sql("CREATE TABLE analyzed (a int, b text)")
+    c.sql("INSERT INTO analyzed SELECT x, NULL FROM generate_series(1, 100) x")
+    c.sql("CREATE TABLE dropped (a int)")
+    c.sql("ANALYZE analyzed")
+    c.sql("ANALYZE dropped")
+    c.sql("DROP TABLE dropped")
+
+    query = """
+        SELECT s.position, s.row_count, s.null_fraction, s.distinct_count,
+            s.histogram_bounds, s.analyzed_at
+        FROM mz_internal.mz_column_statistics s
+        ORDER BY s.object_id, s.position
+    """
+    statistics = c.sql_query(query)
+
+    # Restart mz.
+    c.kill("materialized")
+    c.up("materialized")
+
+    # Verify that the statistics are restored unchanged.
+    restart_statistics = c.sql_query(query)
+    if statistics != restart_statistics or len(statistics) != 2:
+        print("initial statistics:", statistics)
+        print("statistics after restart:", restart_statistics)
+        raise Exception("column statistics not retained across restart")
+
+
+def workflow_retained_subscribes(c: Composition) -> None:
+    c.up("testdrive_no_reset", persistent=True)
+    c.up("materialized")
+
+    c.sql(
+        "ALTER SYSTEM SET enable_resumable_subscribe = true",
+        port=6877,
+        user="mz_system",
+    )
+    c.testdrive(
+        service="testdrive_no_reset",
+        input=dedent(
+            """
+            $ set-regex match=\\d{13} replacement=<TIMESTAMP>
+
+            > CREATE TABLE retained (a int)
+            > CREATE DEFAULT INDEX ON retained
+            > INSERT INTO retained VALUES (1)
+
+            > BEGIN
+            > DECLARE c CURSOR FOR SUBSCRIBE retained WITH (NAME = 'retained', RETENTION = '1h')
+            > FETCH 1 c WITH (timeout = '60s')
+            <TIMESTAMP> 1 1
+            > FETCH 0 c
+            > COMMIT
+
+            > INSERT INTO retained VALUES (2)
+            """
+        ),
+    )
+
+    # Restart mz.
+    c.kill("materialized")
+    c.up("materialized")
+
+    # Verify that the subscribe can be resumed, and emits only the updates that
+    # were not acknowledged before the restart.
+    c.testdrive(
+        service="testdrive_no_reset",
+        input=dedent(
+            """
+            $ set-regex match=\\d{13} replacement=<TIMESTAMP>
+
+            > INSERT INTO retained VALUES (3)
+
+            > BEGIN
+            > DECLARE c CURSOR FOR SUBSCRIBE retained RESUME FROM 'retained'
+            > FETCH 2 c WITH (timeout = '60s')
+            <TIMESTAMP> 1 2
+            <TIMESTAMP> 1 3
+            > COMMIT
+            """
+        ),
+    )
+
+
+def workflow_pending_cluster_reconfiguration(c: Composition) -> None:
+    c.up("materialized")
+
+    c.sql(
+        "ALTER SYSTEM SET enable_hydration_aware_cluster_alter = true",
+        port=6877,
+        user="mz_system",
+    )
+    # A materialized view that takes long to hydrate, so that altering its
+    # cluster waits for the new replica to hydrate.
+    c.sql(
+        """
+        CREATE CLUSTER reconfigured SIZE '1', REPLICATION FACTOR 1;
+        CREATE MATERIALIZED VIEW slow IN CLUSTER reconfigured AS
+            SELECT count(*) FROM generate_series(1, 100000) a, generate_series(1, 100000) b;
+        """
+    )
+
+    def kill_when_pending() -> None:
+        while not c.sql_query(
+            """
+            SELECT r.name
+            FROM mz_cluster_replicas r
+            JOIN mz_clusters c ON c.id = r.cluster_id
+            WHERE c.name = 'reconfigured' AND r.name = 'r1-pending'
+            """
+        ):
+            time.sleep(0.1)
+        c.kill("materialized")
+
+    killer = Thread(target=kill_when_pending)
+    killer.start()
+    try:
+        c.sql("ALTER CLUSTER reconfigured SET (SIZE '2')")
+    except pg8000.exceptions.InterfaceError:
+        pass
+    else:
+        raise Exception("ALTER CLUSTER completed before materialized was killed")
+    killer.join()
+
+    c.up("materialized")
+
+    # The interrupted reconfiguration is abandoned: the pending replica is
+    # dropped and the cluster keeps its old configuration.
+    replicas = c.sql_query(
+        """
+        SELECT r.name, r.size
+        FROM mz_cluster_replicas r
+        JOIN mz_clusters c ON c.id = r.cluster_id
+        WHERE c.name = 'reconfigured'
+        """
+    )
+    assert replicas == [["r1", "1"]], replicas
+    size = c.sql_query("SELECT size FROM mz_clusters WHERE name = 'reconfigured'")
+    assert size == [["1"]], size
+
+    c.sql("DROP CLUSTER reconfigured CASCADE")
+    c.sql(
+        "ALTER SYSTEM RESET enable_hydration_aware_cluster_alter",
+        port=6877,
+        user="mz_system",
+    )
+
+
 def workflow_default(c: Composition) -> None:
     c.workflow("github-17578")
     c.workflow("github-8021")
@@ -406,3 +563,6 @@ def workflow_default(c: Composition) -> None:
     c.workflow("allowed-cluster-replica-sizes")
     c.workflow("drop-materialize-database")
     c.workflow("bound-size-mz-status-history")
+    c.workflow("column-statistics")
+    c.workflow("retained-subscribes")
+    c.workflow("pending-cluster-reconfiguration")
@@ -258,3 +258,57 @@ def workflow_default(c: Composition, parser: WorkflowArgumentParser) -> None:
                 """
             )
         )
+
+    c.workflow("history")
+
+
+def workflow_history(c: Composition) -> None:
+    """Confirm that mz_storage_usage_history is retained across restarts, up to
+    the retention period.
+    """
+    retention_period_secs = 60
+
+    with c.override(
+        Materialized(
+            environment_extra=[
+                f"MZ_STORAGE_USAGE_COLLECTION_INTERVAL={COLLECTION_INTERVAL_SECS}s",
+                f"MZ_STORAGE_USAGE_RETENTION_PERIOD={retention_period_secs}s",
+            ],
+        )
+    ):
+        c.up("materialized")
+
+        c.sql("CREATE TABLE history_obj (f1 TEXT)")
+        c.sql(
+            "INSERT INTO history_obj SELECT generate_series::text || REPEAT('x', 1024) FROM generate_series(1, 1024)"
+        )
+        time.sleep(COLLECTION_INTERVAL_SECS + 1)
+
+        query = """
+            SELECT min(h.collection_timestamp)::text, bool_and(h.live_bytes > 0)
+            FROM mz_internal.mz_storage_usage_history h
+            JOIN mz_tables t ON h.object_id = t.id
+            WHERE t.name = 'history_obj'
+        """
+        ((first_collection, has_bytes),) = c.sql_query(query)
+        assert first_collection is not None, "storage usage history not collected"
+        assert has_bytes, "storage usage history reports no live bytes"
+
+        # Verify that the history survives a restart.
+        c.kill("materialized")
+        c.up("materialized")
+        ((restart_first_collection, _),) = c.sql_query(query)
+        assert (
+            restart_first_collection == first_collection
+        ), f"storage usage history not retained across restart: {first_collection} != {restart_first_collection}"
+
+        # Verify that collections older than the retention period are pruned
+        # on restart.
+        time.sleep(retention_period_secs + 1)
+        c.kill("materialized")
+        c.up("materialized")
+        ((pruned_first_collection, _),) = c.sql_query(query)
+        assert (
+            pruned_first_collection is None
+            or pruned_first_collection > first_collection
+        ), f"storage usage history not pruned: {pruned_first_collection}"